@@ -183,6 +183,7 @@ class Aps {
   final Signal<List<String>> logs = signal([]);
   // 开机自启动
   final Signal<KVNetworkStatus?> netStatus = signal(null); // 网络状态
+  final Signal<String> instanceId = signal(''); // 当前网络实例ID
   /// 更新玩家名称
   Future<void> updatePlayerName(String name) async {
     PlayerName.value = name;
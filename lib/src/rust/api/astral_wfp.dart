@@ -7,8 +7,9 @@ import '../frb_generated.dart';
 import '../lib.dart';
 import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
 
-// These functions are ignored because they are not marked as `pub`: `add_filters_windows`, `add_ip_condition`, `add_network_filter`, `cleanup_windows`, `delete_filters_windows`, `get_layers_for_rule`, `initialize_windows`, `validate_rule_layer_compatibility`
-// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `eq`, `eq`, `eq`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `from_str`
+// These functions are ignored because they are not marked as `pub`: `add_ip_condition`, `add_network_filter`, `default_backend`, `get_layer_name`, `get_layers_for_rule`, `layer_name`, `new`, `validate_rule_layer_compatibility`, `with_backend`
+// These types are ignored because they are neither used by any `pub` functions nor (for structs and enums) marked `#[frb(unignore)]`: `WfpBackend`
+// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `clone`, `clone`, `clone`, `clone`, `clone`, `eq`, `eq`, `eq`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `from_str`
 
 Future<Uint16List> toWideString({required String s}) =>
     RustLib.instance.api.crateApiAstralWfpToWideString(s: s);
@@ -126,17 +127,27 @@ abstract class WfpController implements RustOpaqueInterface {
 
   set filterIds(Uint64List filterIds);
 
+  Future<String> backendName();
+
   Future<void> cleanup();
 
   Future<int> deleteFilters({required Uint64List filterIds});
 
+  Future<int> deleteRule({required String name});
+
   Future<void> getLayerName({required Guid layerKey});
 
   Future<void> initialize();
 
+  Future<List<FilterRule>> listRules();
+
+  Future<Uint64List> loadRules({required String path});
+
   // HINT: Make it `#[frb(sync)]` to let it become the default constructor of Dart class.
   static Future<WfpController> newInstance() =>
       RustLib.instance.api.crateApiAstralWfpWfpControllerNew();
+
+  Future<void> saveRules({required String path});
 }
 
 /// 网络流量方向
@@ -0,0 +1,215 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.11.1.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import '../frb_generated.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+import 'package:freezed_annotation/freezed_annotation.dart' hide protected;
+part 'event.freezed.dart';
+
+// These functions are ignored because they are not marked as `pub`: `describe`, `forward_instance_events`, `kind`, `new`, `publish_event`, `should_deliver`
+// These types are ignored because they are neither used by any `pub` functions nor (for structs and enums) marked `#[frb(unignore)]`: `EVENT_HUB`
+// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `clone`, `clone`, `clone`, `clone`, `deref`, `eq`, `fmt`, `fmt`, `fmt`, `fmt`, `from`, `from`, `initialize`
+
+/// 订阅结构化事件流
+///
+/// kinds 为空时接收全部事件，否则只接收指定类型的事件；
+/// instance_id 不为空时只接收该实例的事件。
+/// Lagged 和 Closed 事件总是会被推送，以便调用方感知丢失的事件。
+Stream<KVEventRecord> subscribeEvents({
+  required List<KVEventKind> kinds,
+  String? instanceId,
+}) => RustLib.instance.api.crateApiEventSubscribeEvents(
+  kinds: kinds,
+  instanceId: instanceId,
+);
+
+/// 与 GlobalCtxEvent 一一对应的结构化事件
+@freezed
+sealed class KVEvent with _$KVEvent {
+  const KVEvent._();
+
+  const factory KVEvent.tunDeviceReady({required String devName}) =
+      KVEvent_TunDeviceReady;
+
+  const factory KVEvent.tunDeviceError({required String error}) =
+      KVEvent_TunDeviceError;
+
+  const factory KVEvent.peerAdded({required int peerId}) = KVEvent_PeerAdded;
+
+  const factory KVEvent.peerRemoved({required int peerId}) =
+      KVEvent_PeerRemoved;
+
+  const factory KVEvent.peerConnAdded({required KVPeerConn conn}) =
+      KVEvent_PeerConnAdded;
+
+  const factory KVEvent.peerConnRemoved({required KVPeerConn conn}) =
+      KVEvent_PeerConnRemoved;
+
+  const factory KVEvent.listenerAdded({required String url}) =
+      KVEvent_ListenerAdded;
+
+  const factory KVEvent.listenerAddFailed({
+    required String url,
+    required String error,
+  }) = KVEvent_ListenerAddFailed;
+
+  const factory KVEvent.listenerAcceptFailed({
+    required String url,
+    required String error,
+  }) = KVEvent_ListenerAcceptFailed;
+
+  const factory KVEvent.connectionAccepted({
+    required String localUrl,
+    required String remoteUrl,
+  }) = KVEvent_ConnectionAccepted;
+
+  const factory KVEvent.connectionError({
+    required String localUrl,
+    required String remoteUrl,
+    required String error,
+  }) = KVEvent_ConnectionError;
+
+  const factory KVEvent.connecting({required String url}) = KVEvent_Connecting;
+
+  const factory KVEvent.connectError({
+    required String dst,
+    required String ipVersion,
+    required String error,
+  }) = KVEvent_ConnectError;
+
+  const factory KVEvent.vpnPortalStarted({required String portal}) =
+      KVEvent_VpnPortalStarted;
+
+  const factory KVEvent.vpnPortalClientConnected({
+    required String portal,
+    required String clientAddr,
+  }) = KVEvent_VpnPortalClientConnected;
+
+  const factory KVEvent.vpnPortalClientDisconnected({
+    required String portal,
+    required String clientAddr,
+  }) = KVEvent_VpnPortalClientDisconnected;
+
+  const factory KVEvent.dhcpIpv4Changed({
+    required String? oldCidr,
+    required String? newCidr,
+  }) = KVEvent_DhcpIpv4Changed;
+
+  const factory KVEvent.dhcpIpv4Conflicted({required String? cidr}) =
+      KVEvent_DhcpIpv4Conflicted;
+
+  const factory KVEvent.portForwardAdded({
+    required String bindAddr,
+    required String dstAddr,
+    required String proto,
+  }) = KVEvent_PortForwardAdded;
+
+  /// 事件处理落后，期间丢失了 missed 个事件
+  const factory KVEvent.lagged({required BigInt missed}) = KVEvent_Lagged;
+
+  /// 事件源已关闭，不会再有新的事件
+  const factory KVEvent.closed() = KVEvent_Closed;
+}
+
+/// 事件类型，用于订阅时按类型过滤
+enum KVEventKind {
+  tunDeviceReady,
+  tunDeviceError,
+  peerAdded,
+  peerRemoved,
+  peerConnAdded,
+  peerConnRemoved,
+  listenerAdded,
+  listenerAddFailed,
+  listenerAcceptFailed,
+  connectionAccepted,
+  connectionError,
+  connecting,
+  connectError,
+  vpnPortalStarted,
+  vpnPortalClientConnected,
+  vpnPortalClientDisconnected,
+  dhcpIpv4Changed,
+  dhcpIpv4Conflicted,
+  portForwardAdded,
+  lagged,
+  closed,
+}
+
+/// 带时间戳的事件记录
+class KVEventRecord {
+  final String instanceId;
+  final PlatformInt64 timestampMs;
+  final KVEvent event;
+
+  const KVEventRecord({
+    required this.instanceId,
+    required this.timestampMs,
+    required this.event,
+  });
+
+  @override
+  int get hashCode =>
+      instanceId.hashCode ^
+      timestampMs.hashCode ^
+      event.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is KVEventRecord &&
+          runtimeType == other.runtimeType &&
+          instanceId == other.instanceId &&
+          timestampMs == other.timestampMs &&
+          event == other.event;
+}
+
+/// 节点连接信息
+class KVPeerConn {
+  final String connId;
+  final int myPeerId;
+  final int peerId;
+  final String tunnelType;
+  final String localAddr;
+  final String remoteAddr;
+  final bool isClient;
+  final String networkName;
+
+  const KVPeerConn({
+    required this.connId,
+    required this.myPeerId,
+    required this.peerId,
+    required this.tunnelType,
+    required this.localAddr,
+    required this.remoteAddr,
+    required this.isClient,
+    required this.networkName,
+  });
+
+  @override
+  int get hashCode =>
+      connId.hashCode ^
+      myPeerId.hashCode ^
+      peerId.hashCode ^
+      tunnelType.hashCode ^
+      localAddr.hashCode ^
+      remoteAddr.hashCode ^
+      isClient.hashCode ^
+      networkName.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is KVPeerConn &&
+          runtimeType == other.runtimeType &&
+          connId == other.connId &&
+          myPeerId == other.myPeerId &&
+          peerId == other.peerId &&
+          tunnelType == other.tunnelType &&
+          localAddr == other.localAddr &&
+          remoteAddr == other.remoteAddr &&
+          isClient == other.isClient &&
+          networkName == other.networkName;
+}
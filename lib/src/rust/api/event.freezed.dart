@@ -0,0 +1,1413 @@
+// dart format width=80
+// coverage:ignore-file
+// GENERATED CODE - DO NOT MODIFY BY HAND
+// ignore_for_file: type=lint
+// ignore_for_file: unused_element, deprecated_member_use, deprecated_member_use_from_same_package, use_function_type_syntax_for_parameters, unnecessary_const, avoid_init_to_null, invalid_override_different_default_values_named, prefer_expression_function_bodies, annotate_overrides, invalid_annotation_target, unnecessary_question_mark
+
+part of 'event.dart';
+
+// **************************************************************************
+// FreezedGenerator
+// **************************************************************************
+
+// dart format off
+T _$identity<T>(T value) => value;
+/// @nodoc
+mixin _$KVEvent {
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent);
+}
+
+
+@override
+int get hashCode => runtimeType.hashCode;
+
+@override
+String toString() {
+  return 'KVEvent()';
+}
+
+
+}
+
+/// @nodoc
+class $KVEventCopyWith<$Res>  {
+$KVEventCopyWith(KVEvent _, $Res Function(KVEvent) __);
+}
+
+
+/// @nodoc
+
+
+class KVEvent_TunDeviceReady extends KVEvent {
+  const KVEvent_TunDeviceReady({required this.devName}): super._();
+  
+
+ final  String devName;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_TunDeviceReadyCopyWith<KVEvent_TunDeviceReady> get copyWith => _$KVEvent_TunDeviceReadyCopyWithImpl<KVEvent_TunDeviceReady>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_TunDeviceReady&&(identical(other.devName, devName) || other.devName == devName));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,devName);
+
+@override
+String toString() {
+  return 'KVEvent.tunDeviceReady(devName: $devName)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_TunDeviceReadyCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_TunDeviceReadyCopyWith(KVEvent_TunDeviceReady value, $Res Function(KVEvent_TunDeviceReady) _then) = _$KVEvent_TunDeviceReadyCopyWithImpl;
+@useResult
+$Res call({
+ String devName
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_TunDeviceReadyCopyWithImpl<$Res>
+    implements $KVEvent_TunDeviceReadyCopyWith<$Res> {
+  _$KVEvent_TunDeviceReadyCopyWithImpl(this._self, this._then);
+
+  final KVEvent_TunDeviceReady _self;
+  final $Res Function(KVEvent_TunDeviceReady) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? devName = null,}) {
+  return _then(KVEvent_TunDeviceReady(
+devName: null == devName ? _self.devName : devName // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_TunDeviceError extends KVEvent {
+  const KVEvent_TunDeviceError({required this.error}): super._();
+  
+
+ final  String error;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_TunDeviceErrorCopyWith<KVEvent_TunDeviceError> get copyWith => _$KVEvent_TunDeviceErrorCopyWithImpl<KVEvent_TunDeviceError>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_TunDeviceError&&(identical(other.error, error) || other.error == error));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,error);
+
+@override
+String toString() {
+  return 'KVEvent.tunDeviceError(error: $error)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_TunDeviceErrorCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_TunDeviceErrorCopyWith(KVEvent_TunDeviceError value, $Res Function(KVEvent_TunDeviceError) _then) = _$KVEvent_TunDeviceErrorCopyWithImpl;
+@useResult
+$Res call({
+ String error
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_TunDeviceErrorCopyWithImpl<$Res>
+    implements $KVEvent_TunDeviceErrorCopyWith<$Res> {
+  _$KVEvent_TunDeviceErrorCopyWithImpl(this._self, this._then);
+
+  final KVEvent_TunDeviceError _self;
+  final $Res Function(KVEvent_TunDeviceError) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? error = null,}) {
+  return _then(KVEvent_TunDeviceError(
+error: null == error ? _self.error : error // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_PeerAdded extends KVEvent {
+  const KVEvent_PeerAdded({required this.peerId}): super._();
+  
+
+ final  int peerId;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_PeerAddedCopyWith<KVEvent_PeerAdded> get copyWith => _$KVEvent_PeerAddedCopyWithImpl<KVEvent_PeerAdded>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_PeerAdded&&(identical(other.peerId, peerId) || other.peerId == peerId));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,peerId);
+
+@override
+String toString() {
+  return 'KVEvent.peerAdded(peerId: $peerId)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_PeerAddedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_PeerAddedCopyWith(KVEvent_PeerAdded value, $Res Function(KVEvent_PeerAdded) _then) = _$KVEvent_PeerAddedCopyWithImpl;
+@useResult
+$Res call({
+ int peerId
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_PeerAddedCopyWithImpl<$Res>
+    implements $KVEvent_PeerAddedCopyWith<$Res> {
+  _$KVEvent_PeerAddedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_PeerAdded _self;
+  final $Res Function(KVEvent_PeerAdded) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? peerId = null,}) {
+  return _then(KVEvent_PeerAdded(
+peerId: null == peerId ? _self.peerId : peerId // ignore: cast_nullable_to_non_nullable
+as int,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_PeerRemoved extends KVEvent {
+  const KVEvent_PeerRemoved({required this.peerId}): super._();
+  
+
+ final  int peerId;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_PeerRemovedCopyWith<KVEvent_PeerRemoved> get copyWith => _$KVEvent_PeerRemovedCopyWithImpl<KVEvent_PeerRemoved>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_PeerRemoved&&(identical(other.peerId, peerId) || other.peerId == peerId));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,peerId);
+
+@override
+String toString() {
+  return 'KVEvent.peerRemoved(peerId: $peerId)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_PeerRemovedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_PeerRemovedCopyWith(KVEvent_PeerRemoved value, $Res Function(KVEvent_PeerRemoved) _then) = _$KVEvent_PeerRemovedCopyWithImpl;
+@useResult
+$Res call({
+ int peerId
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_PeerRemovedCopyWithImpl<$Res>
+    implements $KVEvent_PeerRemovedCopyWith<$Res> {
+  _$KVEvent_PeerRemovedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_PeerRemoved _self;
+  final $Res Function(KVEvent_PeerRemoved) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? peerId = null,}) {
+  return _then(KVEvent_PeerRemoved(
+peerId: null == peerId ? _self.peerId : peerId // ignore: cast_nullable_to_non_nullable
+as int,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_PeerConnAdded extends KVEvent {
+  const KVEvent_PeerConnAdded({required this.conn}): super._();
+  
+
+ final  KVPeerConn conn;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_PeerConnAddedCopyWith<KVEvent_PeerConnAdded> get copyWith => _$KVEvent_PeerConnAddedCopyWithImpl<KVEvent_PeerConnAdded>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_PeerConnAdded&&(identical(other.conn, conn) || other.conn == conn));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,conn);
+
+@override
+String toString() {
+  return 'KVEvent.peerConnAdded(conn: $conn)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_PeerConnAddedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_PeerConnAddedCopyWith(KVEvent_PeerConnAdded value, $Res Function(KVEvent_PeerConnAdded) _then) = _$KVEvent_PeerConnAddedCopyWithImpl;
+@useResult
+$Res call({
+ KVPeerConn conn
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_PeerConnAddedCopyWithImpl<$Res>
+    implements $KVEvent_PeerConnAddedCopyWith<$Res> {
+  _$KVEvent_PeerConnAddedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_PeerConnAdded _self;
+  final $Res Function(KVEvent_PeerConnAdded) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? conn = null,}) {
+  return _then(KVEvent_PeerConnAdded(
+conn: null == conn ? _self.conn : conn // ignore: cast_nullable_to_non_nullable
+as KVPeerConn,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_PeerConnRemoved extends KVEvent {
+  const KVEvent_PeerConnRemoved({required this.conn}): super._();
+  
+
+ final  KVPeerConn conn;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_PeerConnRemovedCopyWith<KVEvent_PeerConnRemoved> get copyWith => _$KVEvent_PeerConnRemovedCopyWithImpl<KVEvent_PeerConnRemoved>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_PeerConnRemoved&&(identical(other.conn, conn) || other.conn == conn));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,conn);
+
+@override
+String toString() {
+  return 'KVEvent.peerConnRemoved(conn: $conn)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_PeerConnRemovedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_PeerConnRemovedCopyWith(KVEvent_PeerConnRemoved value, $Res Function(KVEvent_PeerConnRemoved) _then) = _$KVEvent_PeerConnRemovedCopyWithImpl;
+@useResult
+$Res call({
+ KVPeerConn conn
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_PeerConnRemovedCopyWithImpl<$Res>
+    implements $KVEvent_PeerConnRemovedCopyWith<$Res> {
+  _$KVEvent_PeerConnRemovedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_PeerConnRemoved _self;
+  final $Res Function(KVEvent_PeerConnRemoved) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? conn = null,}) {
+  return _then(KVEvent_PeerConnRemoved(
+conn: null == conn ? _self.conn : conn // ignore: cast_nullable_to_non_nullable
+as KVPeerConn,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_ListenerAdded extends KVEvent {
+  const KVEvent_ListenerAdded({required this.url}): super._();
+  
+
+ final  String url;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_ListenerAddedCopyWith<KVEvent_ListenerAdded> get copyWith => _$KVEvent_ListenerAddedCopyWithImpl<KVEvent_ListenerAdded>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_ListenerAdded&&(identical(other.url, url) || other.url == url));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,url);
+
+@override
+String toString() {
+  return 'KVEvent.listenerAdded(url: $url)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_ListenerAddedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_ListenerAddedCopyWith(KVEvent_ListenerAdded value, $Res Function(KVEvent_ListenerAdded) _then) = _$KVEvent_ListenerAddedCopyWithImpl;
+@useResult
+$Res call({
+ String url
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_ListenerAddedCopyWithImpl<$Res>
+    implements $KVEvent_ListenerAddedCopyWith<$Res> {
+  _$KVEvent_ListenerAddedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_ListenerAdded _self;
+  final $Res Function(KVEvent_ListenerAdded) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? url = null,}) {
+  return _then(KVEvent_ListenerAdded(
+url: null == url ? _self.url : url // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_ListenerAddFailed extends KVEvent {
+  const KVEvent_ListenerAddFailed({required this.url, required this.error}): super._();
+  
+
+ final  String url;
+ final  String error;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_ListenerAddFailedCopyWith<KVEvent_ListenerAddFailed> get copyWith => _$KVEvent_ListenerAddFailedCopyWithImpl<KVEvent_ListenerAddFailed>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_ListenerAddFailed&&(identical(other.url, url) || other.url == url)&&(identical(other.error, error) || other.error == error));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,url,error);
+
+@override
+String toString() {
+  return 'KVEvent.listenerAddFailed(url: $url, error: $error)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_ListenerAddFailedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_ListenerAddFailedCopyWith(KVEvent_ListenerAddFailed value, $Res Function(KVEvent_ListenerAddFailed) _then) = _$KVEvent_ListenerAddFailedCopyWithImpl;
+@useResult
+$Res call({
+ String url, String error
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_ListenerAddFailedCopyWithImpl<$Res>
+    implements $KVEvent_ListenerAddFailedCopyWith<$Res> {
+  _$KVEvent_ListenerAddFailedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_ListenerAddFailed _self;
+  final $Res Function(KVEvent_ListenerAddFailed) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? url = null,Object? error = null,}) {
+  return _then(KVEvent_ListenerAddFailed(
+url: null == url ? _self.url : url // ignore: cast_nullable_to_non_nullable
+as String,error: null == error ? _self.error : error // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_ListenerAcceptFailed extends KVEvent {
+  const KVEvent_ListenerAcceptFailed({required this.url, required this.error}): super._();
+  
+
+ final  String url;
+ final  String error;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_ListenerAcceptFailedCopyWith<KVEvent_ListenerAcceptFailed> get copyWith => _$KVEvent_ListenerAcceptFailedCopyWithImpl<KVEvent_ListenerAcceptFailed>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_ListenerAcceptFailed&&(identical(other.url, url) || other.url == url)&&(identical(other.error, error) || other.error == error));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,url,error);
+
+@override
+String toString() {
+  return 'KVEvent.listenerAcceptFailed(url: $url, error: $error)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_ListenerAcceptFailedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_ListenerAcceptFailedCopyWith(KVEvent_ListenerAcceptFailed value, $Res Function(KVEvent_ListenerAcceptFailed) _then) = _$KVEvent_ListenerAcceptFailedCopyWithImpl;
+@useResult
+$Res call({
+ String url, String error
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_ListenerAcceptFailedCopyWithImpl<$Res>
+    implements $KVEvent_ListenerAcceptFailedCopyWith<$Res> {
+  _$KVEvent_ListenerAcceptFailedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_ListenerAcceptFailed _self;
+  final $Res Function(KVEvent_ListenerAcceptFailed) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? url = null,Object? error = null,}) {
+  return _then(KVEvent_ListenerAcceptFailed(
+url: null == url ? _self.url : url // ignore: cast_nullable_to_non_nullable
+as String,error: null == error ? _self.error : error // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_ConnectionAccepted extends KVEvent {
+  const KVEvent_ConnectionAccepted({required this.localUrl, required this.remoteUrl}): super._();
+  
+
+ final  String localUrl;
+ final  String remoteUrl;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_ConnectionAcceptedCopyWith<KVEvent_ConnectionAccepted> get copyWith => _$KVEvent_ConnectionAcceptedCopyWithImpl<KVEvent_ConnectionAccepted>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_ConnectionAccepted&&(identical(other.localUrl, localUrl) || other.localUrl == localUrl)&&(identical(other.remoteUrl, remoteUrl) || other.remoteUrl == remoteUrl));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,localUrl,remoteUrl);
+
+@override
+String toString() {
+  return 'KVEvent.connectionAccepted(localUrl: $localUrl, remoteUrl: $remoteUrl)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_ConnectionAcceptedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_ConnectionAcceptedCopyWith(KVEvent_ConnectionAccepted value, $Res Function(KVEvent_ConnectionAccepted) _then) = _$KVEvent_ConnectionAcceptedCopyWithImpl;
+@useResult
+$Res call({
+ String localUrl, String remoteUrl
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_ConnectionAcceptedCopyWithImpl<$Res>
+    implements $KVEvent_ConnectionAcceptedCopyWith<$Res> {
+  _$KVEvent_ConnectionAcceptedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_ConnectionAccepted _self;
+  final $Res Function(KVEvent_ConnectionAccepted) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? localUrl = null,Object? remoteUrl = null,}) {
+  return _then(KVEvent_ConnectionAccepted(
+localUrl: null == localUrl ? _self.localUrl : localUrl // ignore: cast_nullable_to_non_nullable
+as String,remoteUrl: null == remoteUrl ? _self.remoteUrl : remoteUrl // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_ConnectionError extends KVEvent {
+  const KVEvent_ConnectionError({required this.localUrl, required this.remoteUrl, required this.error}): super._();
+  
+
+ final  String localUrl;
+ final  String remoteUrl;
+ final  String error;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_ConnectionErrorCopyWith<KVEvent_ConnectionError> get copyWith => _$KVEvent_ConnectionErrorCopyWithImpl<KVEvent_ConnectionError>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_ConnectionError&&(identical(other.localUrl, localUrl) || other.localUrl == localUrl)&&(identical(other.remoteUrl, remoteUrl) || other.remoteUrl == remoteUrl)&&(identical(other.error, error) || other.error == error));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,localUrl,remoteUrl,error);
+
+@override
+String toString() {
+  return 'KVEvent.connectionError(localUrl: $localUrl, remoteUrl: $remoteUrl, error: $error)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_ConnectionErrorCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_ConnectionErrorCopyWith(KVEvent_ConnectionError value, $Res Function(KVEvent_ConnectionError) _then) = _$KVEvent_ConnectionErrorCopyWithImpl;
+@useResult
+$Res call({
+ String localUrl, String remoteUrl, String error
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_ConnectionErrorCopyWithImpl<$Res>
+    implements $KVEvent_ConnectionErrorCopyWith<$Res> {
+  _$KVEvent_ConnectionErrorCopyWithImpl(this._self, this._then);
+
+  final KVEvent_ConnectionError _self;
+  final $Res Function(KVEvent_ConnectionError) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? localUrl = null,Object? remoteUrl = null,Object? error = null,}) {
+  return _then(KVEvent_ConnectionError(
+localUrl: null == localUrl ? _self.localUrl : localUrl // ignore: cast_nullable_to_non_nullable
+as String,remoteUrl: null == remoteUrl ? _self.remoteUrl : remoteUrl // ignore: cast_nullable_to_non_nullable
+as String,error: null == error ? _self.error : error // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_Connecting extends KVEvent {
+  const KVEvent_Connecting({required this.url}): super._();
+  
+
+ final  String url;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_ConnectingCopyWith<KVEvent_Connecting> get copyWith => _$KVEvent_ConnectingCopyWithImpl<KVEvent_Connecting>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_Connecting&&(identical(other.url, url) || other.url == url));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,url);
+
+@override
+String toString() {
+  return 'KVEvent.connecting(url: $url)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_ConnectingCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_ConnectingCopyWith(KVEvent_Connecting value, $Res Function(KVEvent_Connecting) _then) = _$KVEvent_ConnectingCopyWithImpl;
+@useResult
+$Res call({
+ String url
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_ConnectingCopyWithImpl<$Res>
+    implements $KVEvent_ConnectingCopyWith<$Res> {
+  _$KVEvent_ConnectingCopyWithImpl(this._self, this._then);
+
+  final KVEvent_Connecting _self;
+  final $Res Function(KVEvent_Connecting) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? url = null,}) {
+  return _then(KVEvent_Connecting(
+url: null == url ? _self.url : url // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_ConnectError extends KVEvent {
+  const KVEvent_ConnectError({required this.dst, required this.ipVersion, required this.error}): super._();
+  
+
+ final  String dst;
+ final  String ipVersion;
+ final  String error;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_ConnectErrorCopyWith<KVEvent_ConnectError> get copyWith => _$KVEvent_ConnectErrorCopyWithImpl<KVEvent_ConnectError>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_ConnectError&&(identical(other.dst, dst) || other.dst == dst)&&(identical(other.ipVersion, ipVersion) || other.ipVersion == ipVersion)&&(identical(other.error, error) || other.error == error));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,dst,ipVersion,error);
+
+@override
+String toString() {
+  return 'KVEvent.connectError(dst: $dst, ipVersion: $ipVersion, error: $error)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_ConnectErrorCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_ConnectErrorCopyWith(KVEvent_ConnectError value, $Res Function(KVEvent_ConnectError) _then) = _$KVEvent_ConnectErrorCopyWithImpl;
+@useResult
+$Res call({
+ String dst, String ipVersion, String error
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_ConnectErrorCopyWithImpl<$Res>
+    implements $KVEvent_ConnectErrorCopyWith<$Res> {
+  _$KVEvent_ConnectErrorCopyWithImpl(this._self, this._then);
+
+  final KVEvent_ConnectError _self;
+  final $Res Function(KVEvent_ConnectError) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? dst = null,Object? ipVersion = null,Object? error = null,}) {
+  return _then(KVEvent_ConnectError(
+dst: null == dst ? _self.dst : dst // ignore: cast_nullable_to_non_nullable
+as String,ipVersion: null == ipVersion ? _self.ipVersion : ipVersion // ignore: cast_nullable_to_non_nullable
+as String,error: null == error ? _self.error : error // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_VpnPortalStarted extends KVEvent {
+  const KVEvent_VpnPortalStarted({required this.portal}): super._();
+  
+
+ final  String portal;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_VpnPortalStartedCopyWith<KVEvent_VpnPortalStarted> get copyWith => _$KVEvent_VpnPortalStartedCopyWithImpl<KVEvent_VpnPortalStarted>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_VpnPortalStarted&&(identical(other.portal, portal) || other.portal == portal));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,portal);
+
+@override
+String toString() {
+  return 'KVEvent.vpnPortalStarted(portal: $portal)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_VpnPortalStartedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_VpnPortalStartedCopyWith(KVEvent_VpnPortalStarted value, $Res Function(KVEvent_VpnPortalStarted) _then) = _$KVEvent_VpnPortalStartedCopyWithImpl;
+@useResult
+$Res call({
+ String portal
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_VpnPortalStartedCopyWithImpl<$Res>
+    implements $KVEvent_VpnPortalStartedCopyWith<$Res> {
+  _$KVEvent_VpnPortalStartedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_VpnPortalStarted _self;
+  final $Res Function(KVEvent_VpnPortalStarted) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? portal = null,}) {
+  return _then(KVEvent_VpnPortalStarted(
+portal: null == portal ? _self.portal : portal // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_VpnPortalClientConnected extends KVEvent {
+  const KVEvent_VpnPortalClientConnected({required this.portal, required this.clientAddr}): super._();
+  
+
+ final  String portal;
+ final  String clientAddr;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_VpnPortalClientConnectedCopyWith<KVEvent_VpnPortalClientConnected> get copyWith => _$KVEvent_VpnPortalClientConnectedCopyWithImpl<KVEvent_VpnPortalClientConnected>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_VpnPortalClientConnected&&(identical(other.portal, portal) || other.portal == portal)&&(identical(other.clientAddr, clientAddr) || other.clientAddr == clientAddr));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,portal,clientAddr);
+
+@override
+String toString() {
+  return 'KVEvent.vpnPortalClientConnected(portal: $portal, clientAddr: $clientAddr)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_VpnPortalClientConnectedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_VpnPortalClientConnectedCopyWith(KVEvent_VpnPortalClientConnected value, $Res Function(KVEvent_VpnPortalClientConnected) _then) = _$KVEvent_VpnPortalClientConnectedCopyWithImpl;
+@useResult
+$Res call({
+ String portal, String clientAddr
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_VpnPortalClientConnectedCopyWithImpl<$Res>
+    implements $KVEvent_VpnPortalClientConnectedCopyWith<$Res> {
+  _$KVEvent_VpnPortalClientConnectedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_VpnPortalClientConnected _self;
+  final $Res Function(KVEvent_VpnPortalClientConnected) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? portal = null,Object? clientAddr = null,}) {
+  return _then(KVEvent_VpnPortalClientConnected(
+portal: null == portal ? _self.portal : portal // ignore: cast_nullable_to_non_nullable
+as String,clientAddr: null == clientAddr ? _self.clientAddr : clientAddr // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_VpnPortalClientDisconnected extends KVEvent {
+  const KVEvent_VpnPortalClientDisconnected({required this.portal, required this.clientAddr}): super._();
+  
+
+ final  String portal;
+ final  String clientAddr;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_VpnPortalClientDisconnectedCopyWith<KVEvent_VpnPortalClientDisconnected> get copyWith => _$KVEvent_VpnPortalClientDisconnectedCopyWithImpl<KVEvent_VpnPortalClientDisconnected>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_VpnPortalClientDisconnected&&(identical(other.portal, portal) || other.portal == portal)&&(identical(other.clientAddr, clientAddr) || other.clientAddr == clientAddr));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,portal,clientAddr);
+
+@override
+String toString() {
+  return 'KVEvent.vpnPortalClientDisconnected(portal: $portal, clientAddr: $clientAddr)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_VpnPortalClientDisconnectedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_VpnPortalClientDisconnectedCopyWith(KVEvent_VpnPortalClientDisconnected value, $Res Function(KVEvent_VpnPortalClientDisconnected) _then) = _$KVEvent_VpnPortalClientDisconnectedCopyWithImpl;
+@useResult
+$Res call({
+ String portal, String clientAddr
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_VpnPortalClientDisconnectedCopyWithImpl<$Res>
+    implements $KVEvent_VpnPortalClientDisconnectedCopyWith<$Res> {
+  _$KVEvent_VpnPortalClientDisconnectedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_VpnPortalClientDisconnected _self;
+  final $Res Function(KVEvent_VpnPortalClientDisconnected) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? portal = null,Object? clientAddr = null,}) {
+  return _then(KVEvent_VpnPortalClientDisconnected(
+portal: null == portal ? _self.portal : portal // ignore: cast_nullable_to_non_nullable
+as String,clientAddr: null == clientAddr ? _self.clientAddr : clientAddr // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_DhcpIpv4Changed extends KVEvent {
+  const KVEvent_DhcpIpv4Changed({required this.oldCidr, required this.newCidr}): super._();
+  
+
+ final  String? oldCidr;
+ final  String? newCidr;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_DhcpIpv4ChangedCopyWith<KVEvent_DhcpIpv4Changed> get copyWith => _$KVEvent_DhcpIpv4ChangedCopyWithImpl<KVEvent_DhcpIpv4Changed>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_DhcpIpv4Changed&&(identical(other.oldCidr, oldCidr) || other.oldCidr == oldCidr)&&(identical(other.newCidr, newCidr) || other.newCidr == newCidr));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,oldCidr,newCidr);
+
+@override
+String toString() {
+  return 'KVEvent.dhcpIpv4Changed(oldCidr: $oldCidr, newCidr: $newCidr)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_DhcpIpv4ChangedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_DhcpIpv4ChangedCopyWith(KVEvent_DhcpIpv4Changed value, $Res Function(KVEvent_DhcpIpv4Changed) _then) = _$KVEvent_DhcpIpv4ChangedCopyWithImpl;
+@useResult
+$Res call({
+ String? oldCidr, String? newCidr
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_DhcpIpv4ChangedCopyWithImpl<$Res>
+    implements $KVEvent_DhcpIpv4ChangedCopyWith<$Res> {
+  _$KVEvent_DhcpIpv4ChangedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_DhcpIpv4Changed _self;
+  final $Res Function(KVEvent_DhcpIpv4Changed) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? oldCidr = freezed,Object? newCidr = freezed,}) {
+  return _then(KVEvent_DhcpIpv4Changed(
+oldCidr: freezed == oldCidr ? _self.oldCidr : oldCidr // ignore: cast_nullable_to_non_nullable
+as String?,newCidr: freezed == newCidr ? _self.newCidr : newCidr // ignore: cast_nullable_to_non_nullable
+as String?,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_DhcpIpv4Conflicted extends KVEvent {
+  const KVEvent_DhcpIpv4Conflicted({required this.cidr}): super._();
+  
+
+ final  String? cidr;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_DhcpIpv4ConflictedCopyWith<KVEvent_DhcpIpv4Conflicted> get copyWith => _$KVEvent_DhcpIpv4ConflictedCopyWithImpl<KVEvent_DhcpIpv4Conflicted>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_DhcpIpv4Conflicted&&(identical(other.cidr, cidr) || other.cidr == cidr));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,cidr);
+
+@override
+String toString() {
+  return 'KVEvent.dhcpIpv4Conflicted(cidr: $cidr)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_DhcpIpv4ConflictedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_DhcpIpv4ConflictedCopyWith(KVEvent_DhcpIpv4Conflicted value, $Res Function(KVEvent_DhcpIpv4Conflicted) _then) = _$KVEvent_DhcpIpv4ConflictedCopyWithImpl;
+@useResult
+$Res call({
+ String? cidr
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_DhcpIpv4ConflictedCopyWithImpl<$Res>
+    implements $KVEvent_DhcpIpv4ConflictedCopyWith<$Res> {
+  _$KVEvent_DhcpIpv4ConflictedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_DhcpIpv4Conflicted _self;
+  final $Res Function(KVEvent_DhcpIpv4Conflicted) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? cidr = freezed,}) {
+  return _then(KVEvent_DhcpIpv4Conflicted(
+cidr: freezed == cidr ? _self.cidr : cidr // ignore: cast_nullable_to_non_nullable
+as String?,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_PortForwardAdded extends KVEvent {
+  const KVEvent_PortForwardAdded({required this.bindAddr, required this.dstAddr, required this.proto}): super._();
+  
+
+ final  String bindAddr;
+ final  String dstAddr;
+ final  String proto;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_PortForwardAddedCopyWith<KVEvent_PortForwardAdded> get copyWith => _$KVEvent_PortForwardAddedCopyWithImpl<KVEvent_PortForwardAdded>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_PortForwardAdded&&(identical(other.bindAddr, bindAddr) || other.bindAddr == bindAddr)&&(identical(other.dstAddr, dstAddr) || other.dstAddr == dstAddr)&&(identical(other.proto, proto) || other.proto == proto));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,bindAddr,dstAddr,proto);
+
+@override
+String toString() {
+  return 'KVEvent.portForwardAdded(bindAddr: $bindAddr, dstAddr: $dstAddr, proto: $proto)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_PortForwardAddedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_PortForwardAddedCopyWith(KVEvent_PortForwardAdded value, $Res Function(KVEvent_PortForwardAdded) _then) = _$KVEvent_PortForwardAddedCopyWithImpl;
+@useResult
+$Res call({
+ String bindAddr, String dstAddr, String proto
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_PortForwardAddedCopyWithImpl<$Res>
+    implements $KVEvent_PortForwardAddedCopyWith<$Res> {
+  _$KVEvent_PortForwardAddedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_PortForwardAdded _self;
+  final $Res Function(KVEvent_PortForwardAdded) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? bindAddr = null,Object? dstAddr = null,Object? proto = null,}) {
+  return _then(KVEvent_PortForwardAdded(
+bindAddr: null == bindAddr ? _self.bindAddr : bindAddr // ignore: cast_nullable_to_non_nullable
+as String,dstAddr: null == dstAddr ? _self.dstAddr : dstAddr // ignore: cast_nullable_to_non_nullable
+as String,proto: null == proto ? _self.proto : proto // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_Lagged extends KVEvent {
+  const KVEvent_Lagged({required this.missed}): super._();
+  
+
+ final  BigInt missed;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$KVEvent_LaggedCopyWith<KVEvent_Lagged> get copyWith => _$KVEvent_LaggedCopyWithImpl<KVEvent_Lagged>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_Lagged&&(identical(other.missed, missed) || other.missed == missed));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,missed);
+
+@override
+String toString() {
+  return 'KVEvent.lagged(missed: $missed)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $KVEvent_LaggedCopyWith<$Res> implements $KVEventCopyWith<$Res> {
+  factory $KVEvent_LaggedCopyWith(KVEvent_Lagged value, $Res Function(KVEvent_Lagged) _then) = _$KVEvent_LaggedCopyWithImpl;
+@useResult
+$Res call({
+ BigInt missed
+});
+
+
+
+
+}
+/// @nodoc
+class _$KVEvent_LaggedCopyWithImpl<$Res>
+    implements $KVEvent_LaggedCopyWith<$Res> {
+  _$KVEvent_LaggedCopyWithImpl(this._self, this._then);
+
+  final KVEvent_Lagged _self;
+  final $Res Function(KVEvent_Lagged) _then;
+
+/// Create a copy of KVEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? missed = null,}) {
+  return _then(KVEvent_Lagged(
+missed: null == missed ? _self.missed : missed // ignore: cast_nullable_to_non_nullable
+as BigInt,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class KVEvent_Closed extends KVEvent {
+  const KVEvent_Closed(): super._();
+  
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is KVEvent_Closed);
+}
+
+
+@override
+int get hashCode => runtimeType.hashCode;
+
+@override
+String toString() {
+  return 'KVEvent.closed()';
+}
+
+
+}
+
+// dart format on
@@ -0,0 +1,239 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.11.1.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import '../frb_generated.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+import 'simple.dart';
+
+// These functions are ignored because they are not marked as `pub`: `flatten_profile`, `from_config`, `parse`, `parse_list`, `profile_to_config`, `push`, `to_config`, `validate_flags`
+// These types are ignored because they are neither used by any `pub` functions nor (for structs and enums) marked `#[frb(unignore)]`: `FieldErrors`
+// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `clone`, `clone`, `clone`, `clone`, `clone`, `default`, `default`, `eq`, `eq`, `eq`, `eq`, `eq`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `from`, `from`
+
+/// 返回一个默认的配置档案
+Future<NetworkProfile> defaultProfile() =>
+    RustLib.instance.api.crateApiProfileDefaultProfile();
+
+/// 校验配置档案，返回所有字段错误，为空表示校验通过
+Future<List<ProfileFieldError>> validateProfile({
+  required NetworkProfile profile,
+}) => RustLib.instance.api.crateApiProfileValidateProfile(profile: profile);
+
+/// 从 TOML 文本导入配置档案
+Future<NetworkProfile> importProfileToml({required String toml}) =>
+    RustLib.instance.api.crateApiProfileImportProfileToml(toml: toml);
+
+/// 把配置档案导出为 TOML 文本
+Future<String> exportProfileToml({required NetworkProfile profile}) =>
+    RustLib.instance.api.crateApiProfileExportProfileToml(profile: profile);
+
+/// 比较两个配置档案，返回发生变化的字段
+Future<List<ProfileChange>> diffProfiles({
+  required NetworkProfile oldProfile,
+  required NetworkProfile newProfile,
+}) => RustLib.instance.api.crateApiProfileDiffProfiles(
+  oldProfile: oldProfile,
+  newProfile: newProfile,
+);
+
+/// 网络配置档案，与 TomlConfigLoader 的字段一一对应
+class NetworkProfile {
+  final String? instanceId;
+  final String instanceName;
+  final String? hostname;
+  final String networkName;
+  final String networkSecret;
+  final bool dhcp;
+  final String? ipv4;
+  final String? ipv6;
+  final List<String> peers;
+  final List<String> listeners;
+  final List<String> mappedListeners;
+  final List<ProfileProxyNetwork> proxyNetworks;
+  final List<String> exitNodes;
+  final List<String>? routes;
+  final String? socks5Portal;
+  final List<Forward> portForwards;
+  final ProfileVpnPortal? vpnPortal;
+  final String? rpcPortal;
+  final List<String>? rpcPortalWhitelist;
+  final List<String>? stunServers;
+  final List<String>? stunServersV6;
+  final List<String> tcpWhitelist;
+  final List<String> udpWhitelist;
+  final String? aclJson;
+  final FlagsC flags;
+
+  const NetworkProfile({
+    required this.instanceId,
+    required this.instanceName,
+    required this.hostname,
+    required this.networkName,
+    required this.networkSecret,
+    required this.dhcp,
+    required this.ipv4,
+    required this.ipv6,
+    required this.peers,
+    required this.listeners,
+    required this.mappedListeners,
+    required this.proxyNetworks,
+    required this.exitNodes,
+    required this.routes,
+    required this.socks5Portal,
+    required this.portForwards,
+    required this.vpnPortal,
+    required this.rpcPortal,
+    required this.rpcPortalWhitelist,
+    required this.stunServers,
+    required this.stunServersV6,
+    required this.tcpWhitelist,
+    required this.udpWhitelist,
+    required this.aclJson,
+    required this.flags,
+  });
+
+  @override
+  int get hashCode =>
+      instanceId.hashCode ^
+      instanceName.hashCode ^
+      hostname.hashCode ^
+      networkName.hashCode ^
+      networkSecret.hashCode ^
+      dhcp.hashCode ^
+      ipv4.hashCode ^
+      ipv6.hashCode ^
+      peers.hashCode ^
+      listeners.hashCode ^
+      mappedListeners.hashCode ^
+      proxyNetworks.hashCode ^
+      exitNodes.hashCode ^
+      routes.hashCode ^
+      socks5Portal.hashCode ^
+      portForwards.hashCode ^
+      vpnPortal.hashCode ^
+      rpcPortal.hashCode ^
+      rpcPortalWhitelist.hashCode ^
+      stunServers.hashCode ^
+      stunServersV6.hashCode ^
+      tcpWhitelist.hashCode ^
+      udpWhitelist.hashCode ^
+      aclJson.hashCode ^
+      flags.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is NetworkProfile &&
+          runtimeType == other.runtimeType &&
+          instanceId == other.instanceId &&
+          instanceName == other.instanceName &&
+          hostname == other.hostname &&
+          networkName == other.networkName &&
+          networkSecret == other.networkSecret &&
+          dhcp == other.dhcp &&
+          ipv4 == other.ipv4 &&
+          ipv6 == other.ipv6 &&
+          peers == other.peers &&
+          listeners == other.listeners &&
+          mappedListeners == other.mappedListeners &&
+          proxyNetworks == other.proxyNetworks &&
+          exitNodes == other.exitNodes &&
+          routes == other.routes &&
+          socks5Portal == other.socks5Portal &&
+          portForwards == other.portForwards &&
+          vpnPortal == other.vpnPortal &&
+          rpcPortal == other.rpcPortal &&
+          rpcPortalWhitelist == other.rpcPortalWhitelist &&
+          stunServers == other.stunServers &&
+          stunServersV6 == other.stunServersV6 &&
+          tcpWhitelist == other.tcpWhitelist &&
+          udpWhitelist == other.udpWhitelist &&
+          aclJson == other.aclJson &&
+          flags == other.flags;
+}
+
+/// 两个配置档案之间的单个字段差异
+class ProfileChange {
+  final String field;
+  final String? oldValue;
+  final String? newValue;
+
+  const ProfileChange({
+    required this.field,
+    required this.oldValue,
+    required this.newValue,
+  });
+
+  @override
+  int get hashCode => field.hashCode ^ oldValue.hashCode ^ newValue.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ProfileChange &&
+          runtimeType == other.runtimeType &&
+          field == other.field &&
+          oldValue == other.oldValue &&
+          newValue == other.newValue;
+}
+
+/// 单个字段的校验错误
+class ProfileFieldError {
+  final String field;
+  final String message;
+
+  const ProfileFieldError({required this.field, required this.message});
+
+  @override
+  int get hashCode => field.hashCode ^ message.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ProfileFieldError &&
+          runtimeType == other.runtimeType &&
+          field == other.field &&
+          message == other.message;
+}
+
+/// 子网代理配置
+class ProfileProxyNetwork {
+  final String cidr;
+  final String? mappedCidr;
+
+  const ProfileProxyNetwork({required this.cidr, required this.mappedCidr});
+
+  @override
+  int get hashCode => cidr.hashCode ^ mappedCidr.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ProfileProxyNetwork &&
+          runtimeType == other.runtimeType &&
+          cidr == other.cidr &&
+          mappedCidr == other.mappedCidr;
+}
+
+/// WireGuard VPN 门户配置
+class ProfileVpnPortal {
+  final String clientCidr;
+  final String wireguardListen;
+
+  const ProfileVpnPortal({
+    required this.clientCidr,
+    required this.wireguardListen,
+  });
+
+  @override
+  int get hashCode => clientCidr.hashCode ^ wireguardListen.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is ProfileVpnPortal &&
+          runtimeType == other.runtimeType &&
+          clientCidr == other.clientCidr &&
+          wireguardListen == other.wireguardListen;
+}
@@ -6,31 +6,37 @@
 import '../frb_generated.dart';
 import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
 
-// These functions are ignored because they are not marked as `pub`: `create_and_store_network_instance`, `peer_conn_info_to_string`
-// These types are ignored because they are neither used by any `pub` functions nor (for structs and enums) marked `#[frb(unignore)]`: `RT`
-// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `deref`, `initialize`
+// These functions are ignored because they are not marked as `pub`: `create_and_store_network_instance`, `get_instance_running_info`, `handle_event`, `parse_instance_id`, `pick_dev_name`, `stop_instance`
+// These types are ignored because they are neither used by any `pub` functions nor (for structs and enums) marked `#[frb(unignore)]`: `INSTANCE_MANAGER`, `KVConfigApplyReport`, `KVShutdownReport`, `KVShutdownStep`, `RT`
+// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `clone`, `clone`, `clone`, `clone`, `clone`, `deref`, `deref`, `eq`, `eq`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `initialize`, `initialize`
 
 Future<void> sendUdpToLocalhost({required String message}) =>
     RustLib.instance.api.crateApiSimpleSendUdpToLocalhost(message: message);
 
-Future<JoinHandle> handleEvent({required EventBusSubscriber events}) =>
-    RustLib.instance.api.crateApiSimpleHandleEvent(events: events);
-
 Future<String> easytierVersion() =>
     RustLib.instance.api.crateApiSimpleEasytierVersion();
 
 Future<bool> isEasytierRunning() =>
     RustLib.instance.api.crateApiSimpleIsEasytierRunning();
 
-Future<List<String>> getIps() => RustLib.instance.api.crateApiSimpleGetIps();
+Future<bool> isInstanceRunning({required String instanceId}) => RustLib
+    .instance
+    .api
+    .crateApiSimpleIsInstanceRunning(instanceId: instanceId);
+
+Future<List<KVInstanceInfo>> listInstances() =>
+    RustLib.instance.api.crateApiSimpleListInstances();
 
-Future<void> setTunFd({required int fd}) =>
-    RustLib.instance.api.crateApiSimpleSetTunFd(fd: fd);
+Future<List<String>> getIps({required String instanceId}) =>
+    RustLib.instance.api.crateApiSimpleGetIps(instanceId: instanceId);
 
-Future<String> getRunningInfo() =>
-    RustLib.instance.api.crateApiSimpleGetRunningInfo();
+Future<void> setTunFd({required String instanceId, required int fd}) =>
+    RustLib.instance.api.crateApiSimpleSetTunFd(instanceId: instanceId, fd: fd);
 
-Future<JoinHandleResultString> createServer({
+Future<String> getRunningInfo({required String instanceId}) =>
+    RustLib.instance.api.crateApiSimpleGetRunningInfo(instanceId: instanceId);
+
+Future<JoinHandleResultStringString> createServer({
   required String username,
   required bool enableDhcp,
   required String specifiedIp,
@@ -54,24 +60,53 @@ Future<JoinHandleResultString> createServer({
   flag: flag,
 );
 
-Future<void> closeServer() => RustLib.instance.api.crateApiSimpleCloseServer();
+Future<JoinHandleResultStringString> createServerWithProfile({
+  required NetworkProfile profile,
+}) => RustLib.instance.api.crateApiSimpleCreateServerWithProfile(
+  profile: profile,
+);
+
+Future<JoinHandleResultKvConfigApplyReportString> applyProfile({
+  required String instanceId,
+  required NetworkProfile profile,
+}) => RustLib.instance.api.crateApiSimpleApplyProfile(
+  instanceId: instanceId,
+  profile: profile,
+);
+
+Future<JoinHandleResultKvShutdownReportString> closeServer({
+  required String instanceId,
+}) => RustLib.instance.api.crateApiSimpleCloseServer(instanceId: instanceId);
 
-Future<List<PeerRoutePair>> getPeerRoutePairs() =>
-    RustLib.instance.api.crateApiSimpleGetPeerRoutePairs();
+Future<JoinHandleVecKvShutdownReport> closeAllServers() =>
+    RustLib.instance.api.crateApiSimpleCloseAllServers();
 
-Future<KVNetworkStatus> getNetworkStatus() =>
-    RustLib.instance.api.crateApiSimpleGetNetworkStatus();
+Future<KVExitFlowInfo> getExitFlows({required String instanceId}) =>
+    RustLib.instance.api.crateApiSimpleGetExitFlows(instanceId: instanceId);
+
+Future<List<PeerRoutePair>> getPeerRoutePairs({required String instanceId}) =>
+    RustLib.instance.api.crateApiSimpleGetPeerRoutePairs(
+      instanceId: instanceId,
+    );
+
+Future<KVNetworkStatus> getNetworkStatus({required String instanceId}) =>
+    RustLib.instance.api.crateApiSimpleGetNetworkStatus(instanceId: instanceId);
 
 Future<void> initApp() => RustLib.instance.api.crateApiSimpleInitApp();
 
-// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<EventBusSubscriber>>
-abstract class EventBusSubscriber implements RustOpaqueInterface {}
+// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle < Result < KVConfigApplyReport , String > >>>
+abstract class JoinHandleResultKvConfigApplyReportString
+    implements RustOpaqueInterface {}
 
-// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner< JoinHandle < () >>>
-abstract class JoinHandle implements RustOpaqueInterface {}
+// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle < Result < KVShutdownReport , String > >>>
+abstract class JoinHandleResultKvShutdownReportString
+    implements RustOpaqueInterface {}
 
-// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle < Result < () , String > >>>
-abstract class JoinHandleResultString implements RustOpaqueInterface {}
+// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle < Result < String , String > >>>
+abstract class JoinHandleResultStringString implements RustOpaqueInterface {}
+
+// Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle < Vec < KVShutdownReport > >>>
+abstract class JoinHandleVecKvShutdownReport implements RustOpaqueInterface {}
 
 // Rust type: RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<PeerRoutePair>>
 abstract class PeerRoutePair implements RustOpaqueInterface {}
@@ -102,6 +137,11 @@ class FlagsC {
   final bool enableQuicProxy;
   final bool disableQuicInput;
   final bool disableSymHolePunching;
+  final bool enableRelayForeignNetworkKcp;
+  final BigInt foreignRelayBpsLimit;
+  final int multiThreadCount;
+  final String encryptionAlgorithm;
+  final bool enableMultipathBonding;
 
   const FlagsC({
     required this.defaultProtocol,
@@ -129,6 +169,11 @@ class FlagsC {
     required this.enableQuicProxy,
     required this.disableQuicInput,
     required this.disableSymHolePunching,
+    required this.enableRelayForeignNetworkKcp,
+    required this.foreignRelayBpsLimit,
+    required this.multiThreadCount,
+    required this.encryptionAlgorithm,
+    required this.enableMultipathBonding,
   });
 
   @override
@@ -157,7 +202,12 @@ class FlagsC {
       privateMode.hashCode ^
       enableQuicProxy.hashCode ^
       disableQuicInput.hashCode ^
-      disableSymHolePunching.hashCode;
+      disableSymHolePunching.hashCode ^
+      enableRelayForeignNetworkKcp.hashCode ^
+      foreignRelayBpsLimit.hashCode ^
+      multiThreadCount.hashCode ^
+      encryptionAlgorithm.hashCode ^
+      enableMultipathBonding.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -188,7 +238,12 @@ class FlagsC {
           privateMode == other.privateMode &&
           enableQuicProxy == other.enableQuicProxy &&
           disableQuicInput == other.disableQuicInput &&
-          disableSymHolePunching == other.disableSymHolePunching;
+          disableSymHolePunching == other.disableSymHolePunching &&
+          enableRelayForeignNetworkKcp == other.enableRelayForeignNetworkKcp &&
+          foreignRelayBpsLimit == other.foreignRelayBpsLimit &&
+          multiThreadCount == other.multiThreadCount &&
+          encryptionAlgorithm == other.encryptionAlgorithm &&
+          enableMultipathBonding == other.enableMultipathBonding;
 }
 
 class Forward {
@@ -215,6 +270,131 @@ class Forward {
           proto == other.proto;
 }
 
+class KVExitFlow {
+  final String protocol;
+  final String src;
+  final String dst;
+  final String exitNode;
+  final BigInt idleMs;
+
+  const KVExitFlow({
+    required this.protocol,
+    required this.src,
+    required this.dst,
+    required this.exitNode,
+    required this.idleMs,
+  });
+
+  @override
+  int get hashCode =>
+      protocol.hashCode ^
+      src.hashCode ^
+      dst.hashCode ^
+      exitNode.hashCode ^
+      idleMs.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is KVExitFlow &&
+          runtimeType == other.runtimeType &&
+          protocol == other.protocol &&
+          src == other.src &&
+          dst == other.dst &&
+          exitNode == other.exitNode &&
+          idleMs == other.idleMs;
+}
+
+class KVExitFlowInfo {
+  final List<KVExitNodeStatus> exitNodes;
+  final List<KVExitFlow> flows;
+  final BigInt totalFlows;
+
+  const KVExitFlowInfo({
+    required this.exitNodes,
+    required this.flows,
+    required this.totalFlows,
+  });
+
+  @override
+  int get hashCode => exitNodes.hashCode ^ flows.hashCode ^ totalFlows.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is KVExitFlowInfo &&
+          runtimeType == other.runtimeType &&
+          exitNodes == other.exitNodes &&
+          flows == other.flows &&
+          totalFlows == other.totalFlows;
+}
+
+class KVExitNodeStatus {
+  final String exitNode;
+  final bool reachable;
+  final bool healthy;
+  final double? rttMs;
+  final int consecutiveFailures;
+
+  const KVExitNodeStatus({
+    required this.exitNode,
+    required this.reachable,
+    required this.healthy,
+    required this.rttMs,
+    required this.consecutiveFailures,
+  });
+
+  @override
+  int get hashCode =>
+      exitNode.hashCode ^
+      reachable.hashCode ^
+      healthy.hashCode ^
+      rttMs.hashCode ^
+      consecutiveFailures.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is KVExitNodeStatus &&
+          runtimeType == other.runtimeType &&
+          exitNode == other.exitNode &&
+          reachable == other.reachable &&
+          healthy == other.healthy &&
+          rttMs == other.rttMs &&
+          consecutiveFailures == other.consecutiveFailures;
+}
+
+class KVInstanceInfo {
+  final String instanceId;
+  final String networkName;
+  final bool running;
+  final String? errorMsg;
+
+  const KVInstanceInfo({
+    required this.instanceId,
+    required this.networkName,
+    required this.running,
+    required this.errorMsg,
+  });
+
+  @override
+  int get hashCode =>
+      instanceId.hashCode ^
+      networkName.hashCode ^
+      running.hashCode ^
+      errorMsg.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is KVInstanceInfo &&
+          runtimeType == other.runtimeType &&
+          instanceId == other.instanceId &&
+          networkName == other.networkName &&
+          running == other.running &&
+          errorMsg == other.errorMsg;
+}
+
 class KVNetworkStatus {
   final BigInt totalNodes;
   final List<KVNodeInfo> nodes;
@@ -0,0 +1,107 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.11.1.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import '../frb_generated.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+
+// These functions are ignored because they are not marked as `pub`: `disable`, `enable`, `reapply`, `release_for_instance`
+// These types are ignored because they are neither used by any `pub` functions nor (for structs and enums) marked `#[frb(unignore)]`: `ActiveSplitTunnel`, `SPLIT_TUNNEL`, `SplitTunnelState`
+// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `clone`, `clone`, `clone`, `deref`, `eq`, `eq`, `fmt`, `fmt`, `fmt`, `initialize`
+
+Future<List<SplitTunnelRule>> getSplitTunnelRules() =>
+    RustLib.instance.api.crateApiSplitTunnelGetSplitTunnelRules();
+
+Future<void> setSplitTunnelRules({required List<SplitTunnelRule> rules}) =>
+    RustLib.instance.api.crateApiSplitTunnelSetSplitTunnelRules(rules: rules);
+
+Future<void> addSplitTunnelRule({required String appPath}) => RustLib
+    .instance
+    .api
+    .crateApiSplitTunnelAddSplitTunnelRule(appPath: appPath);
+
+Future<bool> removeSplitTunnelRule({required String appPath}) => RustLib
+    .instance
+    .api
+    .crateApiSplitTunnelRemoveSplitTunnelRule(appPath: appPath);
+
+Future<SplitTunnelMode> getSplitTunnelMode() =>
+    RustLib.instance.api.crateApiSplitTunnelGetSplitTunnelMode();
+
+Future<void> setSplitTunnelMode({required SplitTunnelMode mode}) =>
+    RustLib.instance.api.crateApiSplitTunnelSetSplitTunnelMode(mode: mode);
+
+Future<void> enableSplitTunnel({required String instanceId}) => RustLib
+    .instance
+    .api
+    .crateApiSplitTunnelEnableSplitTunnel(instanceId: instanceId);
+
+Future<void> disableSplitTunnel() =>
+    RustLib.instance.api.crateApiSplitTunnelDisableSplitTunnel();
+
+Future<KVSplitTunnelStatus> getSplitTunnelStatus() =>
+    RustLib.instance.api.crateApiSplitTunnelGetSplitTunnelStatus();
+
+/// 分应用路由当前的生效状态
+class KVSplitTunnelStatus {
+  final bool active;
+  final String instanceId;
+  final String devName;
+  final SplitTunnelMode mode;
+  final int appCount;
+
+  const KVSplitTunnelStatus({
+    required this.active,
+    required this.instanceId,
+    required this.devName,
+    required this.mode,
+    required this.appCount,
+  });
+
+  @override
+  int get hashCode =>
+      active.hashCode ^
+      instanceId.hashCode ^
+      devName.hashCode ^
+      mode.hashCode ^
+      appCount.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is KVSplitTunnelStatus &&
+          runtimeType == other.runtimeType &&
+          active == other.active &&
+          instanceId == other.instanceId &&
+          devName == other.devName &&
+          mode == other.mode &&
+          appCount == other.appCount;
+}
+
+/// 分应用路由模式
+enum SplitTunnelMode {
+  /// 只有列表中的程序走虚拟网络
+  include,
+  /// 列表中的程序不走虚拟网络
+  exclude,
+}
+
+/// 单条分应用路由规则
+class SplitTunnelRule {
+  final String appPath;
+  final bool enabled;
+
+  const SplitTunnelRule({required this.appPath, required this.enabled});
+
+  @override
+  int get hashCode => appPath.hashCode ^ enabled.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is SplitTunnelRule &&
+          runtimeType == other.runtimeType &&
+          appPath == other.appPath &&
+          enabled == other.enabled;
+}
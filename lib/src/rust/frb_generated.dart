@@ -4,10 +4,13 @@
 // ignore_for_file: unused_import, unused_element, unnecessary_import, duplicate_ignore, invalid_use_of_internal_member, annotate_overrides, non_constant_identifier_names, curly_braces_in_flow_control_structures, prefer_const_literals_to_create_immutables, unused_field
 
 import 'api/astral_wfp.dart';
+import 'api/event.dart';
 import 'api/firewall.dart';
 import 'api/hops.dart';
 import 'api/nt.dart';
+import 'api/profile.dart';
 import 'api/simple.dart';
+import 'api/split_tunnel.dart';
 import 'api/utils.dart';
 import 'dart:async';
 import 'dart:convert';
@@ -70,7 +73,7 @@ class RustLib extends BaseEntrypoint<RustLibApi, RustLibApiImpl, RustLibWire> {
   String get codegenVersion => '2.11.1';
 
   @override
-  int get rustContentHash => 294099415;
+  int get rustContentHash => 896517624;
 
   static const kDefaultExternalLibraryLoaderConfig =
       ExternalLibraryLoaderConfig(
@@ -248,6 +251,10 @@ abstract class RustLibApi extends BaseApi {
     required Uint64List filterIds,
   });
 
+  Future<String> crateApiAstralWfpWfpControllerBackendName({
+    required WfpController that,
+  });
+
   Future<void> crateApiAstralWfpWfpControllerCleanup({
     required WfpController that,
   });
@@ -257,6 +264,11 @@ abstract class RustLibApi extends BaseApi {
     required Uint64List filterIds,
   });
 
+  Future<int> crateApiAstralWfpWfpControllerDeleteRule({
+    required WfpController that,
+    required String name,
+  });
+
   Future<void> crateApiAstralWfpWfpControllerGetLayerName({
     required WfpController that,
     required Guid layerKey,
@@ -266,11 +278,36 @@ abstract class RustLibApi extends BaseApi {
     required WfpController that,
   });
 
+  Future<List<FilterRule>> crateApiAstralWfpWfpControllerListRules({
+    required WfpController that,
+  });
+
+  Future<Uint64List> crateApiAstralWfpWfpControllerLoadRules({
+    required WfpController that,
+    required String path,
+  });
+
   Future<WfpController> crateApiAstralWfpWfpControllerNew();
 
+  Future<void> crateApiAstralWfpWfpControllerSaveRules({
+    required WfpController that,
+    required String path,
+  });
+
+  Future<void> crateApiSplitTunnelAddSplitTunnelRule({required String appPath});
+
+  Future<JoinHandleResultKvConfigApplyReportString> crateApiSimpleApplyProfile({
+    required String instanceId,
+    required NetworkProfile profile,
+  });
+
   Future<bool> crateApiUtilsCheckSudo();
 
-  Future<void> crateApiSimpleCloseServer();
+  Future<JoinHandleVecKvShutdownReport> crateApiSimpleCloseAllServers();
+
+  Future<JoinHandleResultKvShutdownReportString> crateApiSimpleCloseServer({
+    required String instanceId,
+  });
 
   Future<JoinHandleResultString> crateApiSimpleCreateServer({
     required String username,
@@ -285,30 +322,73 @@ abstract class RustLibApi extends BaseApi {
     required FlagsC flag,
   });
 
+  Future<JoinHandleResultStringString> crateApiSimpleCreateServerWithProfile({
+    required NetworkProfile profile,
+  });
+
+  Future<NetworkProfile> crateApiProfileDefaultProfile();
+
+  Future<List<ProfileChange>> crateApiProfileDiffProfiles({
+    required NetworkProfile oldProfile,
+    required NetworkProfile newProfile,
+  });
+
+  Future<void> crateApiSplitTunnelDisableSplitTunnel();
+
   Future<String> crateApiSimpleEasytierVersion();
 
+  Future<void> crateApiSplitTunnelEnableSplitTunnel({
+    required String instanceId,
+  });
+
+  Future<String> crateApiProfileExportProfileToml({
+    required NetworkProfile profile,
+  });
+
   Future<List<(String, int)>> crateApiHopsGetAllInterfacesMetrics();
 
+  Future<KVExitFlowInfo> crateApiSimpleGetExitFlows({
+    required String instanceId,
+  });
+
   Future<bool> crateApiFirewallGetFirewallStatus({required int profileIndex});
 
-  Future<List<String>> crateApiSimpleGetIps();
+  Future<List<String>> crateApiSimpleGetIps({required String instanceId});
 
-  Future<KVNetworkStatus> crateApiSimpleGetNetworkStatus();
+  Future<KVNetworkStatus> crateApiSimpleGetNetworkStatus({
+    required String instanceId,
+  });
 
   Future<String?> crateApiNtGetNtPath({required String dosPath});
 
-  Future<List<PeerRoutePair>> crateApiSimpleGetPeerRoutePairs();
+  Future<List<PeerRoutePair>> crateApiSimpleGetPeerRoutePairs({
+    required String instanceId,
+  });
+
+  Future<String> crateApiSimpleGetRunningInfo({required String instanceId});
 
-  Future<String> crateApiSimpleGetRunningInfo();
+  Future<SplitTunnelMode> crateApiSplitTunnelGetSplitTunnelMode();
 
-  Future<JoinHandle> crateApiSimpleHandleEvent({
-    required EventBusSubscriber events,
+  Future<List<SplitTunnelRule>> crateApiSplitTunnelGetSplitTunnelRules();
+
+  Future<KVSplitTunnelStatus> crateApiSplitTunnelGetSplitTunnelStatus();
+
+  Future<NetworkProfile> crateApiProfileImportProfileToml({
+    required String toml,
   });
 
   Future<void> crateApiSimpleInitApp();
 
   Future<bool> crateApiSimpleIsEasytierRunning();
 
+  Future<bool> crateApiSimpleIsInstanceRunning({required String instanceId});
+
+  Future<List<KVInstanceInfo>> crateApiSimpleListInstances();
+
+  Future<bool> crateApiSplitTunnelRemoveSplitTunnelRule({
+    required String appPath,
+  });
+
   Future<void> crateApiSimpleSendUdpToLocalhost({required String message});
 
   Future<void> crateApiFirewallSetFirewallStatus({
@@ -321,18 +401,29 @@ abstract class RustLibApi extends BaseApi {
     required int metric,
   });
 
-  Future<void> crateApiSimpleSetTunFd({required int fd});
+  Future<void> crateApiSplitTunnelSetSplitTunnelMode({
+    required SplitTunnelMode mode,
+  });
 
-  Future<Uint16List> crateApiAstralWfpToWideString({required String s});
+  Future<void> crateApiSplitTunnelSetSplitTunnelRules({
+    required List<SplitTunnelRule> rules,
+  });
 
-  RustArcIncrementStrongCountFnType
-  get rust_arc_increment_strong_count_EventBusSubscriber;
+  Future<void> crateApiSimpleSetTunFd({
+    required String instanceId,
+    required int fd,
+  });
 
-  RustArcDecrementStrongCountFnType
-  get rust_arc_decrement_strong_count_EventBusSubscriber;
+  Stream<KVEventRecord> crateApiEventSubscribeEvents({
+    required List<KVEventKind> kinds,
+    String? instanceId,
+  });
 
-  CrossPlatformFinalizerArg
-  get rust_arc_decrement_strong_count_EventBusSubscriberPtr;
+  Future<Uint16List> crateApiAstralWfpToWideString({required String s});
+
+  Future<List<ProfileFieldError>> crateApiProfileValidateProfile({
+    required NetworkProfile profile,
+  });
 
   RustArcIncrementStrongCountFnType
   get rust_arc_increment_strong_count_FilterRule;
@@ -363,21 +454,40 @@ abstract class RustLibApi extends BaseApi {
   CrossPlatformFinalizerArg get rust_arc_decrement_strong_count_IpNetworkPtr;
 
   RustArcIncrementStrongCountFnType
-  get rust_arc_increment_strong_count_JoinHandle;
+  get rust_arc_increment_strong_count_JoinHandleResultKvConfigApplyReportString;
+
+  RustArcDecrementStrongCountFnType
+  get rust_arc_decrement_strong_count_JoinHandleResultKvConfigApplyReportString;
+
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_JoinHandleResultKvConfigApplyReportStringPtr;
+
+  RustArcIncrementStrongCountFnType
+  get rust_arc_increment_strong_count_JoinHandleResultKvShutdownReportString;
+
+  RustArcDecrementStrongCountFnType
+  get rust_arc_decrement_strong_count_JoinHandleResultKvShutdownReportString;
+
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_JoinHandleResultKvShutdownReportStringPtr;
+
+  RustArcIncrementStrongCountFnType
+  get rust_arc_increment_strong_count_JoinHandleResultStringString;
 
   RustArcDecrementStrongCountFnType
-  get rust_arc_decrement_strong_count_JoinHandle;
+  get rust_arc_decrement_strong_count_JoinHandleResultStringString;
 
-  CrossPlatformFinalizerArg get rust_arc_decrement_strong_count_JoinHandlePtr;
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_JoinHandleResultStringStringPtr;
 
   RustArcIncrementStrongCountFnType
-  get rust_arc_increment_strong_count_JoinHandleResultString;
+  get rust_arc_increment_strong_count_JoinHandleVecKvShutdownReport;
 
   RustArcDecrementStrongCountFnType
-  get rust_arc_decrement_strong_count_JoinHandleResultString;
+  get rust_arc_decrement_strong_count_JoinHandleVecKvShutdownReport;
 
   CrossPlatformFinalizerArg
-  get rust_arc_decrement_strong_count_JoinHandleResultStringPtr;
+  get rust_arc_decrement_strong_count_JoinHandleVecKvShutdownReportPtr;
 
   RustArcIncrementStrongCountFnType
   get rust_arc_increment_strong_count_PeerRoutePair;
@@ -1673,6 +1783,42 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
         argNames: ["that", "filterIds"],
       );
 
+  @override
+  Future<String> crateApiAstralWfpWfpControllerBackendName({
+    required WfpController that,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerWfpController(
+            that,
+            serializer,
+          );
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 37,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_String,
+          decodeErrorData: null,
+        ),
+        constMeta: kCrateApiAstralWfpWfpControllerBackendNameConstMeta,
+        argValues: [that],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiAstralWfpWfpControllerBackendNameConstMeta =>
+      const TaskConstMeta(
+        debugName: "WfpController_backend_name",
+        argNames: ["that"],
+      );
+
   @override
   Future<void> crateApiAstralWfpWfpControllerCleanup({
     required WfpController that,
@@ -1688,7 +1834,7 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 37,
+            funcId: 38,
             port: port_,
           );
         },
@@ -1726,7 +1872,7 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 38,
+            funcId: 39,
             port: port_,
           );
         },
@@ -1747,6 +1893,44 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
         argNames: ["that", "filterIds"],
       );
 
+  @override
+  Future<int> crateApiAstralWfpWfpControllerDeleteRule({
+    required WfpController that,
+    required String name,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerWfpController(
+            that,
+            serializer,
+          );
+          sse_encode_String(name, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 40,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_u_32,
+          decodeErrorData: sse_decode_AnyhowException,
+        ),
+        constMeta: kCrateApiAstralWfpWfpControllerDeleteRuleConstMeta,
+        argValues: [that, name],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiAstralWfpWfpControllerDeleteRuleConstMeta =>
+      const TaskConstMeta(
+        debugName: "WfpController_delete_rule",
+        argNames: ["that", "name"],
+      );
+
   @override
   Future<void> crateApiAstralWfpWfpControllerGetLayerName({
     required WfpController that,
@@ -1767,7 +1951,7 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 39,
+            funcId: 41,
             port: port_,
           );
         },
@@ -1803,7 +1987,7 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 40,
+            funcId: 42,
             port: port_,
           );
         },
@@ -1825,62 +2009,82 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
       );
 
   @override
-  Future<WfpController> crateApiAstralWfpWfpControllerNew() {
+  Future<List<FilterRule>> crateApiAstralWfpWfpControllerListRules({
+    required WfpController that,
+  }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerWfpController(
+            that,
+            serializer,
+          );
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 41,
+            funcId: 43,
             port: port_,
           );
         },
         codec: SseCodec(
           decodeSuccessData:
-              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerWfpController,
-          decodeErrorData: sse_decode_AnyhowException,
+              sse_decode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule,
+          decodeErrorData: null,
         ),
-        constMeta: kCrateApiAstralWfpWfpControllerNewConstMeta,
-        argValues: [],
+        constMeta: kCrateApiAstralWfpWfpControllerListRulesConstMeta,
+        argValues: [that],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiAstralWfpWfpControllerNewConstMeta =>
-      const TaskConstMeta(debugName: "WfpController_new", argNames: []);
+  TaskConstMeta get kCrateApiAstralWfpWfpControllerListRulesConstMeta =>
+      const TaskConstMeta(
+        debugName: "WfpController_list_rules",
+        argNames: ["that"],
+      );
 
   @override
-  Future<bool> crateApiUtilsCheckSudo() {
+  Future<Uint64List> crateApiAstralWfpWfpControllerLoadRules({
+    required WfpController that,
+    required String path,
+  }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_Auto_RefMut_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerWfpController(
+            that,
+            serializer,
+          );
+          sse_encode_String(path, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 42,
+            funcId: 44,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_bool,
-          decodeErrorData: null,
+          decodeSuccessData: sse_decode_list_prim_u_64_strict,
+          decodeErrorData: sse_decode_AnyhowException,
         ),
-        constMeta: kCrateApiUtilsCheckSudoConstMeta,
-        argValues: [],
+        constMeta: kCrateApiAstralWfpWfpControllerLoadRulesConstMeta,
+        argValues: [that, path],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiUtilsCheckSudoConstMeta =>
-      const TaskConstMeta(debugName: "check_sudo", argNames: []);
+  TaskConstMeta get kCrateApiAstralWfpWfpControllerLoadRulesConstMeta =>
+      const TaskConstMeta(
+        debugName: "WfpController_load_rules",
+        argNames: ["that", "path"],
+      );
 
   @override
-  Future<void> crateApiSimpleCloseServer() {
+  Future<WfpController> crateApiAstralWfpWfpControllerNew() {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
@@ -1888,187 +2092,755 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 43,
+            funcId: 45,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_unit,
-          decodeErrorData: null,
+          decodeSuccessData:
+              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerWfpController,
+          decodeErrorData: sse_decode_AnyhowException,
         ),
-        constMeta: kCrateApiSimpleCloseServerConstMeta,
+        constMeta: kCrateApiAstralWfpWfpControllerNewConstMeta,
         argValues: [],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiSimpleCloseServerConstMeta =>
-      const TaskConstMeta(debugName: "close_server", argNames: []);
+  TaskConstMeta get kCrateApiAstralWfpWfpControllerNewConstMeta =>
+      const TaskConstMeta(debugName: "WfpController_new", argNames: []);
 
   @override
-  Future<JoinHandleResultString> crateApiSimpleCreateServer({
-    required String username,
-    required bool enableDhcp,
-    required String specifiedIp,
-    required String roomName,
-    required String roomPassword,
-    required List<String> severurl,
-    required List<String> onurl,
-    required List<String> cidrs,
-    required List<Forward> forwards,
-    required FlagsC flag,
+  Future<void> crateApiAstralWfpWfpControllerSaveRules({
+    required WfpController that,
+    required String path,
   }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
-          sse_encode_String(username, serializer);
-          sse_encode_bool(enableDhcp, serializer);
-          sse_encode_String(specifiedIp, serializer);
-          sse_encode_String(roomName, serializer);
-          sse_encode_String(roomPassword, serializer);
-          sse_encode_list_String(severurl, serializer);
-          sse_encode_list_String(onurl, serializer);
-          sse_encode_list_String(cidrs, serializer);
-          sse_encode_list_forward(forwards, serializer);
-          sse_encode_box_autoadd_flags_c(flag, serializer);
+          sse_encode_Auto_Ref_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerWfpController(
+            that,
+            serializer,
+          );
+          sse_encode_String(path, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 44,
+            funcId: 46,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData:
-              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString,
-          decodeErrorData: null,
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_AnyhowException,
         ),
-        constMeta: kCrateApiSimpleCreateServerConstMeta,
-        argValues: [
-          username,
-          enableDhcp,
-          specifiedIp,
-          roomName,
-          roomPassword,
-          severurl,
-          onurl,
-          cidrs,
-          forwards,
-          flag,
-        ],
+        constMeta: kCrateApiAstralWfpWfpControllerSaveRulesConstMeta,
+        argValues: [that, path],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiSimpleCreateServerConstMeta => const TaskConstMeta(
-    debugName: "create_server",
-    argNames: [
-      "username",
-      "enableDhcp",
-      "specifiedIp",
-      "roomName",
-      "roomPassword",
-      "severurl",
-      "onurl",
-      "cidrs",
-      "forwards",
-      "flag",
-    ],
-  );
+  TaskConstMeta get kCrateApiAstralWfpWfpControllerSaveRulesConstMeta =>
+      const TaskConstMeta(
+        debugName: "WfpController_save_rules",
+        argNames: ["that", "path"],
+      );
 
   @override
-  Future<String> crateApiSimpleEasytierVersion() {
+  Future<void> crateApiSplitTunnelAddSplitTunnelRule({
+    required String appPath,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(appPath, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 47,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_String,
+        ),
+        constMeta: kCrateApiSplitTunnelAddSplitTunnelRuleConstMeta,
+        argValues: [appPath],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSplitTunnelAddSplitTunnelRuleConstMeta =>
+      const TaskConstMeta(
+        debugName: "add_split_tunnel_rule",
+        argNames: ["appPath"],
+      );
+
+  @override
+  Future<JoinHandleResultKvConfigApplyReportString> crateApiSimpleApplyProfile({
+    required String instanceId,
+    required NetworkProfile profile,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(instanceId, serializer);
+          sse_encode_box_autoadd_network_profile(profile, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 48,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData:
+              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString,
+          decodeErrorData: null,
+        ),
+        constMeta: kCrateApiSimpleApplyProfileConstMeta,
+        argValues: [instanceId, profile],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSimpleApplyProfileConstMeta => const TaskConstMeta(
+    debugName: "apply_profile",
+    argNames: ["instanceId", "profile"],
+  );
+
+  @override
+  Future<bool> crateApiUtilsCheckSudo() {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 49,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_bool,
+          decodeErrorData: null,
+        ),
+        constMeta: kCrateApiUtilsCheckSudoConstMeta,
+        argValues: [],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiUtilsCheckSudoConstMeta =>
+      const TaskConstMeta(debugName: "check_sudo", argNames: []);
+
+  @override
+  Future<JoinHandleVecKvShutdownReport> crateApiSimpleCloseAllServers() {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 50,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData:
+              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport,
+          decodeErrorData: null,
+        ),
+        constMeta: kCrateApiSimpleCloseAllServersConstMeta,
+        argValues: [],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSimpleCloseAllServersConstMeta =>
+      const TaskConstMeta(debugName: "close_all_servers", argNames: []);
+
+  @override
+  Future<JoinHandleResultKvShutdownReportString> crateApiSimpleCloseServer({
+    required String instanceId,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(instanceId, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 51,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData:
+              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString,
+          decodeErrorData: null,
+        ),
+        constMeta: kCrateApiSimpleCloseServerConstMeta,
+        argValues: [instanceId],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSimpleCloseServerConstMeta =>
+      const TaskConstMeta(debugName: "close_server", argNames: ["instanceId"]);
+
+  @override
+  Future<JoinHandleResultString> crateApiSimpleCreateServer({
+    required String username,
+    required bool enableDhcp,
+    required String specifiedIp,
+    required String roomName,
+    required String roomPassword,
+    required List<String> severurl,
+    required List<String> onurl,
+    required List<String> cidrs,
+    required List<Forward> forwards,
+    required FlagsC flag,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(username, serializer);
+          sse_encode_bool(enableDhcp, serializer);
+          sse_encode_String(specifiedIp, serializer);
+          sse_encode_String(roomName, serializer);
+          sse_encode_String(roomPassword, serializer);
+          sse_encode_list_String(severurl, serializer);
+          sse_encode_list_String(onurl, serializer);
+          sse_encode_list_String(cidrs, serializer);
+          sse_encode_list_forward(forwards, serializer);
+          sse_encode_box_autoadd_flags_c(flag, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 52,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData:
+              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString,
+          decodeErrorData: null,
+        ),
+        constMeta: kCrateApiSimpleCreateServerConstMeta,
+        argValues: [
+          username,
+          enableDhcp,
+          specifiedIp,
+          roomName,
+          roomPassword,
+          severurl,
+          onurl,
+          cidrs,
+          forwards,
+          flag,
+        ],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSimpleCreateServerConstMeta => const TaskConstMeta(
+    debugName: "create_server",
+    argNames: [
+      "username",
+      "enableDhcp",
+      "specifiedIp",
+      "roomName",
+      "roomPassword",
+      "severurl",
+      "onurl",
+      "cidrs",
+      "forwards",
+      "flag",
+    ],
+  );
+
+  @override
+  Future<JoinHandleResultStringString> crateApiSimpleCreateServerWithProfile({
+    required NetworkProfile profile,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_box_autoadd_network_profile(profile, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 53,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData:
+              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString,
+          decodeErrorData: null,
+        ),
+        constMeta: kCrateApiSimpleCreateServerWithProfileConstMeta,
+        argValues: [profile],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSimpleCreateServerWithProfileConstMeta =>
+      const TaskConstMeta(
+        debugName: "create_server_with_profile",
+        argNames: ["profile"],
+      );
+
+  @override
+  Future<NetworkProfile> crateApiProfileDefaultProfile() {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 54,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_network_profile,
+          decodeErrorData: null,
+        ),
+        constMeta: kCrateApiProfileDefaultProfileConstMeta,
+        argValues: [],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiProfileDefaultProfileConstMeta =>
+      const TaskConstMeta(debugName: "default_profile", argNames: []);
+
+  @override
+  Future<List<ProfileChange>> crateApiProfileDiffProfiles({
+    required NetworkProfile oldProfile,
+    required NetworkProfile newProfile,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_box_autoadd_network_profile(oldProfile, serializer);
+          sse_encode_box_autoadd_network_profile(newProfile, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 55,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_list_profile_change,
+          decodeErrorData: null,
+        ),
+        constMeta: kCrateApiProfileDiffProfilesConstMeta,
+        argValues: [oldProfile, newProfile],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiProfileDiffProfilesConstMeta =>
+      const TaskConstMeta(
+        debugName: "diff_profiles",
+        argNames: ["oldProfile", "newProfile"],
+      );
+
+  @override
+  Future<void> crateApiSplitTunnelDisableSplitTunnel() {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 56,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_String,
+        ),
+        constMeta: kCrateApiSplitTunnelDisableSplitTunnelConstMeta,
+        argValues: [],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSplitTunnelDisableSplitTunnelConstMeta =>
+      const TaskConstMeta(debugName: "disable_split_tunnel", argNames: []);
+
+  @override
+  Future<String> crateApiSimpleEasytierVersion() {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 57,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_String,
+          decodeErrorData: sse_decode_String,
+        ),
+        constMeta: kCrateApiSimpleEasytierVersionConstMeta,
+        argValues: [],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSimpleEasytierVersionConstMeta =>
+      const TaskConstMeta(debugName: "easytier_version", argNames: []);
+
+  @override
+  Future<void> crateApiSplitTunnelEnableSplitTunnel({
+    required String instanceId,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(instanceId, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 58,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_String,
+        ),
+        constMeta: kCrateApiSplitTunnelEnableSplitTunnelConstMeta,
+        argValues: [instanceId],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSplitTunnelEnableSplitTunnelConstMeta =>
+      const TaskConstMeta(
+        debugName: "enable_split_tunnel",
+        argNames: ["instanceId"],
+      );
+
+  @override
+  Future<String> crateApiProfileExportProfileToml({
+    required NetworkProfile profile,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_box_autoadd_network_profile(profile, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 59,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_String,
+          decodeErrorData: sse_decode_String,
+        ),
+        constMeta: kCrateApiProfileExportProfileTomlConstMeta,
+        argValues: [profile],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiProfileExportProfileTomlConstMeta =>
+      const TaskConstMeta(
+        debugName: "export_profile_toml",
+        argNames: ["profile"],
+      );
+
+  @override
+  Future<List<(String, int)>> crateApiHopsGetAllInterfacesMetrics() {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 60,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_list_record_string_u_32,
+          decodeErrorData: sse_decode_AnyhowException,
+        ),
+        constMeta: kCrateApiHopsGetAllInterfacesMetricsConstMeta,
+        argValues: [],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiHopsGetAllInterfacesMetricsConstMeta =>
+      const TaskConstMeta(
+        debugName: "get_all_interfaces_metrics",
+        argNames: [],
+      );
+
+  @override
+  Future<KVExitFlowInfo> crateApiSimpleGetExitFlows({
+    required String instanceId,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(instanceId, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 61,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_kv_exit_flow_info,
+          decodeErrorData: sse_decode_String,
+        ),
+        constMeta: kCrateApiSimpleGetExitFlowsConstMeta,
+        argValues: [instanceId],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSimpleGetExitFlowsConstMeta => const TaskConstMeta(
+    debugName: "get_exit_flows",
+    argNames: ["instanceId"],
+  );
+
+  @override
+  Future<bool> crateApiFirewallGetFirewallStatus({required int profileIndex}) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_u_32(profileIndex, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 62,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_bool,
+          decodeErrorData: sse_decode_AnyhowException,
+        ),
+        constMeta: kCrateApiFirewallGetFirewallStatusConstMeta,
+        argValues: [profileIndex],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiFirewallGetFirewallStatusConstMeta =>
+      const TaskConstMeta(
+        debugName: "get_firewall_status",
+        argNames: ["profileIndex"],
+      );
+
+  @override
+  Future<List<String>> crateApiSimpleGetIps({required String instanceId}) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(instanceId, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 63,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_list_String,
+          decodeErrorData: null,
+        ),
+        constMeta: kCrateApiSimpleGetIpsConstMeta,
+        argValues: [instanceId],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSimpleGetIpsConstMeta =>
+      const TaskConstMeta(debugName: "get_ips", argNames: ["instanceId"]);
+
+  @override
+  Future<KVNetworkStatus> crateApiSimpleGetNetworkStatus({
+    required String instanceId,
+  }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(instanceId, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 45,
+            funcId: 64,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_String,
-          decodeErrorData: sse_decode_String,
+          decodeSuccessData: sse_decode_kv_network_status,
+          decodeErrorData: null,
         ),
-        constMeta: kCrateApiSimpleEasytierVersionConstMeta,
-        argValues: [],
+        constMeta: kCrateApiSimpleGetNetworkStatusConstMeta,
+        argValues: [instanceId],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiSimpleEasytierVersionConstMeta =>
-      const TaskConstMeta(debugName: "easytier_version", argNames: []);
+  TaskConstMeta get kCrateApiSimpleGetNetworkStatusConstMeta =>
+      const TaskConstMeta(
+        debugName: "get_network_status",
+        argNames: ["instanceId"],
+      );
 
   @override
-  Future<List<(String, int)>> crateApiHopsGetAllInterfacesMetrics() {
+  Future<String?> crateApiNtGetNtPath({required String dosPath}) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(dosPath, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 46,
+            funcId: 65,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_list_record_string_u_32,
-          decodeErrorData: sse_decode_AnyhowException,
+          decodeSuccessData: sse_decode_opt_String,
+          decodeErrorData: null,
         ),
-        constMeta: kCrateApiHopsGetAllInterfacesMetricsConstMeta,
-        argValues: [],
+        constMeta: kCrateApiNtGetNtPathConstMeta,
+        argValues: [dosPath],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiHopsGetAllInterfacesMetricsConstMeta =>
+  TaskConstMeta get kCrateApiNtGetNtPathConstMeta =>
+      const TaskConstMeta(debugName: "get_nt_path", argNames: ["dosPath"]);
+
+  @override
+  Future<List<PeerRoutePair>> crateApiSimpleGetPeerRoutePairs({
+    required String instanceId,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(instanceId, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 66,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData:
+              sse_decode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPeerRoutePair,
+          decodeErrorData: sse_decode_String,
+        ),
+        constMeta: kCrateApiSimpleGetPeerRoutePairsConstMeta,
+        argValues: [instanceId],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSimpleGetPeerRoutePairsConstMeta =>
       const TaskConstMeta(
-        debugName: "get_all_interfaces_metrics",
-        argNames: [],
+        debugName: "get_peer_route_pairs",
+        argNames: ["instanceId"],
       );
 
   @override
-  Future<bool> crateApiFirewallGetFirewallStatus({required int profileIndex}) {
+  Future<String> crateApiSimpleGetRunningInfo({required String instanceId}) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
-          sse_encode_u_32(profileIndex, serializer);
+          sse_encode_String(instanceId, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 47,
+            funcId: 67,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_bool,
-          decodeErrorData: sse_decode_AnyhowException,
+          decodeSuccessData: sse_decode_String,
+          decodeErrorData: null,
         ),
-        constMeta: kCrateApiFirewallGetFirewallStatusConstMeta,
-        argValues: [profileIndex],
+        constMeta: kCrateApiSimpleGetRunningInfoConstMeta,
+        argValues: [instanceId],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiFirewallGetFirewallStatusConstMeta =>
+  TaskConstMeta get kCrateApiSimpleGetRunningInfoConstMeta =>
       const TaskConstMeta(
-        debugName: "get_firewall_status",
-        argNames: ["profileIndex"],
+        debugName: "get_running_info",
+        argNames: ["instanceId"],
       );
 
   @override
-  Future<List<String>> crateApiSimpleGetIps() {
+  Future<SplitTunnelMode> crateApiSplitTunnelGetSplitTunnelMode() {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
@@ -2076,26 +2848,26 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 48,
+            funcId: 68,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_list_String,
+          decodeSuccessData: sse_decode_split_tunnel_mode,
           decodeErrorData: null,
         ),
-        constMeta: kCrateApiSimpleGetIpsConstMeta,
+        constMeta: kCrateApiSplitTunnelGetSplitTunnelModeConstMeta,
         argValues: [],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiSimpleGetIpsConstMeta =>
-      const TaskConstMeta(debugName: "get_ips", argNames: []);
+  TaskConstMeta get kCrateApiSplitTunnelGetSplitTunnelModeConstMeta =>
+      const TaskConstMeta(debugName: "get_split_tunnel_mode", argNames: []);
 
   @override
-  Future<KVNetworkStatus> crateApiSimpleGetNetworkStatus() {
+  Future<List<SplitTunnelRule>> crateApiSplitTunnelGetSplitTunnelRules() {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
@@ -2103,82 +2875,83 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 49,
+            funcId: 69,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_kv_network_status,
+          decodeSuccessData: sse_decode_list_split_tunnel_rule,
           decodeErrorData: null,
         ),
-        constMeta: kCrateApiSimpleGetNetworkStatusConstMeta,
+        constMeta: kCrateApiSplitTunnelGetSplitTunnelRulesConstMeta,
         argValues: [],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiSimpleGetNetworkStatusConstMeta =>
-      const TaskConstMeta(debugName: "get_network_status", argNames: []);
+  TaskConstMeta get kCrateApiSplitTunnelGetSplitTunnelRulesConstMeta =>
+      const TaskConstMeta(debugName: "get_split_tunnel_rules", argNames: []);
 
   @override
-  Future<String?> crateApiNtGetNtPath({required String dosPath}) {
+  Future<KVSplitTunnelStatus> crateApiSplitTunnelGetSplitTunnelStatus() {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
-          sse_encode_String(dosPath, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 50,
+            funcId: 70,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_opt_String,
+          decodeSuccessData: sse_decode_kv_split_tunnel_status,
           decodeErrorData: null,
         ),
-        constMeta: kCrateApiNtGetNtPathConstMeta,
-        argValues: [dosPath],
+        constMeta: kCrateApiSplitTunnelGetSplitTunnelStatusConstMeta,
+        argValues: [],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiNtGetNtPathConstMeta =>
-      const TaskConstMeta(debugName: "get_nt_path", argNames: ["dosPath"]);
+  TaskConstMeta get kCrateApiSplitTunnelGetSplitTunnelStatusConstMeta =>
+      const TaskConstMeta(debugName: "get_split_tunnel_status", argNames: []);
 
   @override
-  Future<List<PeerRoutePair>> crateApiSimpleGetPeerRoutePairs() {
+  Future<NetworkProfile> crateApiProfileImportProfileToml({
+    required String toml,
+  }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(toml, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 51,
+            funcId: 71,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData:
-              sse_decode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPeerRoutePair,
+          decodeSuccessData: sse_decode_network_profile,
           decodeErrorData: sse_decode_String,
         ),
-        constMeta: kCrateApiSimpleGetPeerRoutePairsConstMeta,
-        argValues: [],
+        constMeta: kCrateApiProfileImportProfileTomlConstMeta,
+        argValues: [toml],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiSimpleGetPeerRoutePairsConstMeta =>
-      const TaskConstMeta(debugName: "get_peer_route_pairs", argNames: []);
+  TaskConstMeta get kCrateApiProfileImportProfileTomlConstMeta =>
+      const TaskConstMeta(debugName: "import_profile_toml", argNames: ["toml"]);
 
   @override
-  Future<String> crateApiSimpleGetRunningInfo() {
+  Future<void> crateApiSimpleInitApp() {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
@@ -2186,60 +2959,84 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 52,
+            funcId: 72,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_String,
+          decodeSuccessData: sse_decode_unit,
           decodeErrorData: null,
         ),
-        constMeta: kCrateApiSimpleGetRunningInfoConstMeta,
+        constMeta: kCrateApiSimpleInitAppConstMeta,
         argValues: [],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiSimpleGetRunningInfoConstMeta =>
-      const TaskConstMeta(debugName: "get_running_info", argNames: []);
+  TaskConstMeta get kCrateApiSimpleInitAppConstMeta =>
+      const TaskConstMeta(debugName: "init_app", argNames: []);
 
   @override
-  Future<JoinHandle> crateApiSimpleHandleEvent({
-    required EventBusSubscriber events,
-  }) {
+  Future<bool> crateApiSimpleIsEasytierRunning() {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
-          sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-            events,
+          pdeCallFfi(
+            generalizedFrbRustBinding,
             serializer,
+            funcId: 73,
+            port: port_,
           );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_bool,
+          decodeErrorData: null,
+        ),
+        constMeta: kCrateApiSimpleIsEasytierRunningConstMeta,
+        argValues: [],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSimpleIsEasytierRunningConstMeta =>
+      const TaskConstMeta(debugName: "is_easytier_running", argNames: []);
+
+  @override
+  Future<bool> crateApiSimpleIsInstanceRunning({required String instanceId}) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(instanceId, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 53,
+            funcId: 74,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData:
-              sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle,
+          decodeSuccessData: sse_decode_bool,
           decodeErrorData: null,
         ),
-        constMeta: kCrateApiSimpleHandleEventConstMeta,
-        argValues: [events],
+        constMeta: kCrateApiSimpleIsInstanceRunningConstMeta,
+        argValues: [instanceId],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiSimpleHandleEventConstMeta =>
-      const TaskConstMeta(debugName: "handle_event", argNames: ["events"]);
+  TaskConstMeta get kCrateApiSimpleIsInstanceRunningConstMeta =>
+      const TaskConstMeta(
+        debugName: "is_instance_running",
+        argNames: ["instanceId"],
+      );
 
   @override
-  Future<void> crateApiSimpleInitApp() {
+  Future<List<KVInstanceInfo>> crateApiSimpleListInstances() {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
@@ -2247,50 +3044,56 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 54,
+            funcId: 75,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_unit,
+          decodeSuccessData: sse_decode_list_kv_instance_info,
           decodeErrorData: null,
         ),
-        constMeta: kCrateApiSimpleInitAppConstMeta,
+        constMeta: kCrateApiSimpleListInstancesConstMeta,
         argValues: [],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiSimpleInitAppConstMeta =>
-      const TaskConstMeta(debugName: "init_app", argNames: []);
+  TaskConstMeta get kCrateApiSimpleListInstancesConstMeta =>
+      const TaskConstMeta(debugName: "list_instances", argNames: []);
 
   @override
-  Future<bool> crateApiSimpleIsEasytierRunning() {
+  Future<bool> crateApiSplitTunnelRemoveSplitTunnelRule({
+    required String appPath,
+  }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(appPath, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 55,
+            funcId: 76,
             port: port_,
           );
         },
         codec: SseCodec(
           decodeSuccessData: sse_decode_bool,
-          decodeErrorData: null,
+          decodeErrorData: sse_decode_String,
         ),
-        constMeta: kCrateApiSimpleIsEasytierRunningConstMeta,
-        argValues: [],
+        constMeta: kCrateApiSplitTunnelRemoveSplitTunnelRuleConstMeta,
+        argValues: [appPath],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiSimpleIsEasytierRunningConstMeta =>
-      const TaskConstMeta(debugName: "is_easytier_running", argNames: []);
+  TaskConstMeta get kCrateApiSplitTunnelRemoveSplitTunnelRuleConstMeta =>
+      const TaskConstMeta(
+        debugName: "remove_split_tunnel_rule",
+        argNames: ["appPath"],
+      );
 
   @override
   Future<void> crateApiSimpleSendUdpToLocalhost({required String message}) {
@@ -2298,11 +3101,114 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
-          sse_encode_String(message, serializer);
+          sse_encode_String(message, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 77,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_String,
+        ),
+        constMeta: kCrateApiSimpleSendUdpToLocalhostConstMeta,
+        argValues: [message],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiSimpleSendUdpToLocalhostConstMeta =>
+      const TaskConstMeta(
+        debugName: "send_udp_to_localhost",
+        argNames: ["message"],
+      );
+
+  @override
+  Future<void> crateApiFirewallSetFirewallStatus({
+    required int profileIndex,
+    required bool enable,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_u_32(profileIndex, serializer);
+          sse_encode_bool(enable, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 78,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_AnyhowException,
+        ),
+        constMeta: kCrateApiFirewallSetFirewallStatusConstMeta,
+        argValues: [profileIndex, enable],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiFirewallSetFirewallStatusConstMeta =>
+      const TaskConstMeta(
+        debugName: "set_firewall_status",
+        argNames: ["profileIndex", "enable"],
+      );
+
+  @override
+  Future<void> crateApiHopsSetInterfaceMetric({
+    required String interfaceName,
+    required int metric,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_String(interfaceName, serializer);
+          sse_encode_u_32(metric, serializer);
+          pdeCallFfi(
+            generalizedFrbRustBinding,
+            serializer,
+            funcId: 79,
+            port: port_,
+          );
+        },
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_AnyhowException,
+        ),
+        constMeta: kCrateApiHopsSetInterfaceMetricConstMeta,
+        argValues: [interfaceName, metric],
+        apiImpl: this,
+      ),
+    );
+  }
+
+  TaskConstMeta get kCrateApiHopsSetInterfaceMetricConstMeta =>
+      const TaskConstMeta(
+        debugName: "set_interface_metric",
+        argNames: ["interfaceName", "metric"],
+      );
+
+  @override
+  Future<void> crateApiSplitTunnelSetSplitTunnelMode({
+    required SplitTunnelMode mode,
+  }) {
+    return handler.executeNormal(
+      NormalTask(
+        callFfi: (port_) {
+          final serializer = SseSerializer(generalizedFrbRustBinding);
+          sse_encode_split_tunnel_mode(mode, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 56,
+            funcId: 80,
             port: port_,
           );
         },
@@ -2310,152 +3216,183 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
           decodeSuccessData: sse_decode_unit,
           decodeErrorData: sse_decode_String,
         ),
-        constMeta: kCrateApiSimpleSendUdpToLocalhostConstMeta,
-        argValues: [message],
+        constMeta: kCrateApiSplitTunnelSetSplitTunnelModeConstMeta,
+        argValues: [mode],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiSimpleSendUdpToLocalhostConstMeta =>
+  TaskConstMeta get kCrateApiSplitTunnelSetSplitTunnelModeConstMeta =>
       const TaskConstMeta(
-        debugName: "send_udp_to_localhost",
-        argNames: ["message"],
+        debugName: "set_split_tunnel_mode",
+        argNames: ["mode"],
       );
 
   @override
-  Future<void> crateApiFirewallSetFirewallStatus({
-    required int profileIndex,
-    required bool enable,
+  Future<void> crateApiSplitTunnelSetSplitTunnelRules({
+    required List<SplitTunnelRule> rules,
   }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
-          sse_encode_u_32(profileIndex, serializer);
-          sse_encode_bool(enable, serializer);
+          sse_encode_list_split_tunnel_rule(rules, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 57,
+            funcId: 81,
             port: port_,
           );
         },
         codec: SseCodec(
           decodeSuccessData: sse_decode_unit,
-          decodeErrorData: sse_decode_AnyhowException,
+          decodeErrorData: sse_decode_String,
         ),
-        constMeta: kCrateApiFirewallSetFirewallStatusConstMeta,
-        argValues: [profileIndex, enable],
+        constMeta: kCrateApiSplitTunnelSetSplitTunnelRulesConstMeta,
+        argValues: [rules],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiFirewallSetFirewallStatusConstMeta =>
+  TaskConstMeta get kCrateApiSplitTunnelSetSplitTunnelRulesConstMeta =>
       const TaskConstMeta(
-        debugName: "set_firewall_status",
-        argNames: ["profileIndex", "enable"],
+        debugName: "set_split_tunnel_rules",
+        argNames: ["rules"],
       );
 
   @override
-  Future<void> crateApiHopsSetInterfaceMetric({
-    required String interfaceName,
-    required int metric,
+  Future<void> crateApiSimpleSetTunFd({
+    required String instanceId,
+    required int fd,
   }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
-          sse_encode_String(interfaceName, serializer);
-          sse_encode_u_32(metric, serializer);
+          sse_encode_String(instanceId, serializer);
+          sse_encode_i_32(fd, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 58,
+            funcId: 82,
             port: port_,
           );
         },
         codec: SseCodec(
           decodeSuccessData: sse_decode_unit,
-          decodeErrorData: sse_decode_AnyhowException,
+          decodeErrorData: sse_decode_String,
         ),
-        constMeta: kCrateApiHopsSetInterfaceMetricConstMeta,
-        argValues: [interfaceName, metric],
+        constMeta: kCrateApiSimpleSetTunFdConstMeta,
+        argValues: [instanceId, fd],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiHopsSetInterfaceMetricConstMeta =>
+  TaskConstMeta get kCrateApiSimpleSetTunFdConstMeta => const TaskConstMeta(
+    debugName: "set_tun_fd",
+    argNames: ["instanceId", "fd"],
+  );
+
+  @override
+  Stream<KVEventRecord> crateApiEventSubscribeEvents({
+    required List<KVEventKind> kinds,
+    String? instanceId,
+  }) {
+    final sink = RustStreamSink<KVEventRecord>();
+    unawaited(
+      handler.executeNormal(
+        NormalTask(
+          callFfi: (port_) {
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            sse_encode_StreamSink_kv_event_record_Sse(sink, serializer);
+            sse_encode_list_kv_event_kind(kinds, serializer);
+            sse_encode_opt_String(instanceId, serializer);
+            pdeCallFfi(
+              generalizedFrbRustBinding,
+              serializer,
+              funcId: 83,
+              port: port_,
+            );
+          },
+          codec: SseCodec(
+            decodeSuccessData: sse_decode_unit,
+            decodeErrorData: sse_decode_String,
+          ),
+          constMeta: kCrateApiEventSubscribeEventsConstMeta,
+          argValues: [sink, kinds, instanceId],
+          apiImpl: this,
+        ),
+      ),
+    );
+    return sink.stream;
+  }
+
+  TaskConstMeta get kCrateApiEventSubscribeEventsConstMeta =>
       const TaskConstMeta(
-        debugName: "set_interface_metric",
-        argNames: ["interfaceName", "metric"],
+        debugName: "subscribe_events",
+        argNames: ["sink", "kinds", "instanceId"],
       );
 
   @override
-  Future<void> crateApiSimpleSetTunFd({required int fd}) {
+  Future<Uint16List> crateApiAstralWfpToWideString({required String s}) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
-          sse_encode_i_32(fd, serializer);
+          sse_encode_String(s, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 59,
+            funcId: 84,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_unit,
-          decodeErrorData: sse_decode_String,
+          decodeSuccessData: sse_decode_list_prim_u_16_strict,
+          decodeErrorData: null,
         ),
-        constMeta: kCrateApiSimpleSetTunFdConstMeta,
-        argValues: [fd],
+        constMeta: kCrateApiAstralWfpToWideStringConstMeta,
+        argValues: [s],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiSimpleSetTunFdConstMeta =>
-      const TaskConstMeta(debugName: "set_tun_fd", argNames: ["fd"]);
+  TaskConstMeta get kCrateApiAstralWfpToWideStringConstMeta =>
+      const TaskConstMeta(debugName: "to_wide_string", argNames: ["s"]);
 
   @override
-  Future<Uint16List> crateApiAstralWfpToWideString({required String s}) {
+  Future<List<ProfileFieldError>> crateApiProfileValidateProfile({
+    required NetworkProfile profile,
+  }) {
     return handler.executeNormal(
       NormalTask(
         callFfi: (port_) {
           final serializer = SseSerializer(generalizedFrbRustBinding);
-          sse_encode_String(s, serializer);
+          sse_encode_box_autoadd_network_profile(profile, serializer);
           pdeCallFfi(
             generalizedFrbRustBinding,
             serializer,
-            funcId: 60,
+            funcId: 85,
             port: port_,
           );
         },
         codec: SseCodec(
-          decodeSuccessData: sse_decode_list_prim_u_16_strict,
+          decodeSuccessData: sse_decode_list_profile_field_error,
           decodeErrorData: null,
         ),
-        constMeta: kCrateApiAstralWfpToWideStringConstMeta,
-        argValues: [s],
+        constMeta: kCrateApiProfileValidateProfileConstMeta,
+        argValues: [profile],
         apiImpl: this,
       ),
     );
   }
 
-  TaskConstMeta get kCrateApiAstralWfpToWideStringConstMeta =>
-      const TaskConstMeta(debugName: "to_wide_string", argNames: ["s"]);
-
-  RustArcIncrementStrongCountFnType
-  get rust_arc_increment_strong_count_EventBusSubscriber =>
-      wire.rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber;
-
-  RustArcDecrementStrongCountFnType
-  get rust_arc_decrement_strong_count_EventBusSubscriber =>
-      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber;
+  TaskConstMeta get kCrateApiProfileValidateProfileConstMeta =>
+      const TaskConstMeta(debugName: "validate_profile", argNames: ["profile"]);
 
   RustArcIncrementStrongCountFnType
   get rust_arc_increment_strong_count_FilterRule =>
@@ -2488,20 +3425,36 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
       wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerIpNetwork;
 
   RustArcIncrementStrongCountFnType
-  get rust_arc_increment_strong_count_JoinHandle =>
-      wire.rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle;
+  get rust_arc_increment_strong_count_JoinHandleResultKvConfigApplyReportString =>
+      wire.rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString;
+
+  RustArcDecrementStrongCountFnType
+  get rust_arc_decrement_strong_count_JoinHandleResultKvConfigApplyReportString =>
+      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString;
+
+  RustArcIncrementStrongCountFnType
+  get rust_arc_increment_strong_count_JoinHandleResultKvShutdownReportString =>
+      wire.rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString;
+
+  RustArcDecrementStrongCountFnType
+  get rust_arc_decrement_strong_count_JoinHandleResultKvShutdownReportString =>
+      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString;
+
+  RustArcIncrementStrongCountFnType
+  get rust_arc_increment_strong_count_JoinHandleResultStringString =>
+      wire.rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString;
 
   RustArcDecrementStrongCountFnType
-  get rust_arc_decrement_strong_count_JoinHandle =>
-      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle;
+  get rust_arc_decrement_strong_count_JoinHandleResultStringString =>
+      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString;
 
   RustArcIncrementStrongCountFnType
-  get rust_arc_increment_strong_count_JoinHandleResultString =>
-      wire.rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString;
+  get rust_arc_increment_strong_count_JoinHandleVecKvShutdownReport =>
+      wire.rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport;
 
   RustArcDecrementStrongCountFnType
-  get rust_arc_decrement_strong_count_JoinHandleResultString =>
-      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString;
+  get rust_arc_decrement_strong_count_JoinHandleVecKvShutdownReport =>
+      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport;
 
   RustArcIncrementStrongCountFnType
   get rust_arc_increment_strong_count_PeerRoutePair =>
@@ -2525,15 +3478,6 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return AnyhowException(raw as String);
   }
 
-  @protected
-  EventBusSubscriber
-  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    dynamic raw,
-  ) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return EventBusSubscriberImpl.frbInternalDcoDecode(raw as List<dynamic>);
-  }
-
   @protected
   FilterRule
   dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -2562,21 +3506,45 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
   }
 
   @protected
-  JoinHandle
-  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  JoinHandleResultKvConfigApplyReportString
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return JoinHandleResultKvConfigApplyReportStringImpl.frbInternalDcoDecode(
+      raw as List<dynamic>,
+    );
+  }
+
+  @protected
+  JoinHandleResultKvShutdownReportString
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return JoinHandleResultKvShutdownReportStringImpl.frbInternalDcoDecode(
+      raw as List<dynamic>,
+    );
+  }
+
+  @protected
+  JoinHandleResultStringString
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
     dynamic raw,
   ) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return JoinHandleImpl.frbInternalDcoDecode(raw as List<dynamic>);
+    return JoinHandleResultStringStringImpl.frbInternalDcoDecode(
+      raw as List<dynamic>,
+    );
   }
 
   @protected
-  JoinHandleResultString
-  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  JoinHandleVecKvShutdownReport
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     dynamic raw,
   ) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return JoinHandleResultStringImpl.frbInternalDcoDecode(
+    return JoinHandleVecKvShutdownReportImpl.frbInternalDcoDecode(
       raw as List<dynamic>,
     );
   }
@@ -2662,15 +3630,6 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return WfpControllerImpl.frbInternalDcoDecode(raw as List<dynamic>);
   }
 
-  @protected
-  EventBusSubscriber
-  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    dynamic raw,
-  ) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return EventBusSubscriberImpl.frbInternalDcoDecode(raw as List<dynamic>);
-  }
-
   @protected
   FilterRule
   dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -2708,21 +3667,45 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
   }
 
   @protected
-  JoinHandle
-  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  JoinHandleResultKvConfigApplyReportString
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return JoinHandleResultKvConfigApplyReportStringImpl.frbInternalDcoDecode(
+      raw as List<dynamic>,
+    );
+  }
+
+  @protected
+  JoinHandleResultKvShutdownReportString
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return JoinHandleResultKvShutdownReportStringImpl.frbInternalDcoDecode(
+      raw as List<dynamic>,
+    );
+  }
+
+  @protected
+  JoinHandleResultStringString
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
     dynamic raw,
   ) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return JoinHandleImpl.frbInternalDcoDecode(raw as List<dynamic>);
+    return JoinHandleResultStringStringImpl.frbInternalDcoDecode(
+      raw as List<dynamic>,
+    );
   }
 
   @protected
-  JoinHandleResultString
-  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  JoinHandleVecKvShutdownReport
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     dynamic raw,
   ) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return JoinHandleResultStringImpl.frbInternalDcoDecode(
+    return JoinHandleVecKvShutdownReportImpl.frbInternalDcoDecode(
       raw as List<dynamic>,
     );
   }
@@ -2745,6 +3728,14 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return WfpControllerImpl.frbInternalDcoDecode(raw as List<dynamic>);
   }
 
+  @protected
+  RustStreamSink<KVEventRecord> dco_decode_StreamSink_kv_event_record_Sse(
+    dynamic raw,
+  ) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    throw UnimplementedError();
+  }
+
   @protected
   String dco_decode_String(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
@@ -2757,12 +3748,30 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return raw as bool;
   }
 
+  @protected
+  double dco_decode_box_autoadd_f_64(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as double;
+  }
+
   @protected
   FlagsC dco_decode_box_autoadd_flags_c(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
     return dco_decode_flags_c(raw);
   }
 
+  @protected
+  NetworkProfile dco_decode_box_autoadd_network_profile(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dco_decode_network_profile(raw);
+  }
+
+  @protected
+  ProfileVpnPortal dco_decode_box_autoadd_profile_vpn_portal(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dco_decode_profile_vpn_portal(raw);
+  }
+
   @protected
   Protocol dco_decode_box_autoadd_protocol(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
@@ -2815,8 +3824,8 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
   FlagsC dco_decode_flags_c(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
     final arr = raw as List<dynamic>;
-    if (arr.length != 25)
-      throw Exception('unexpected arr length: expect 25 but see ${arr.length}');
+    if (arr.length != 30)
+      throw Exception('unexpected arr length: expect 30 but see ${arr.length}');
     return FlagsC(
       defaultProtocol: dco_decode_String(arr[0]),
       devName: dco_decode_String(arr[1]),
@@ -2843,6 +3852,11 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
       enableQuicProxy: dco_decode_bool(arr[22]),
       disableQuicInput: dco_decode_bool(arr[23]),
       disableSymHolePunching: dco_decode_bool(arr[24]),
+      enableRelayForeignNetworkKcp: dco_decode_bool(arr[25]),
+      foreignRelayBpsLimit: dco_decode_u_64(arr[26]),
+      multiThreadCount: dco_decode_u_32(arr[27]),
+      encryptionAlgorithm: dco_decode_String(arr[28]),
+      enableMultipathBonding: dco_decode_bool(arr[29]),
     );
   }
 
@@ -2865,6 +3879,169 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return raw as int;
   }
 
+  @protected
+  PlatformInt64 dco_decode_i_64(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dcoDecodeI64(raw);
+  }
+
+  @protected
+  KVEvent dco_decode_kv_event(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    switch (raw[0]) {
+      case 0:
+        return KVEvent_TunDeviceReady(devName: dco_decode_String(raw[1]));
+      case 1:
+        return KVEvent_TunDeviceError(error: dco_decode_String(raw[1]));
+      case 2:
+        return KVEvent_PeerAdded(peerId: dco_decode_u_32(raw[1]));
+      case 3:
+        return KVEvent_PeerRemoved(peerId: dco_decode_u_32(raw[1]));
+      case 4:
+        return KVEvent_PeerConnAdded(conn: dco_decode_kv_peer_conn(raw[1]));
+      case 5:
+        return KVEvent_PeerConnRemoved(conn: dco_decode_kv_peer_conn(raw[1]));
+      case 6:
+        return KVEvent_ListenerAdded(url: dco_decode_String(raw[1]));
+      case 7:
+        return KVEvent_ListenerAddFailed(
+          url: dco_decode_String(raw[1]),
+          error: dco_decode_String(raw[2]),
+        );
+      case 8:
+        return KVEvent_ListenerAcceptFailed(
+          url: dco_decode_String(raw[1]),
+          error: dco_decode_String(raw[2]),
+        );
+      case 9:
+        return KVEvent_ConnectionAccepted(
+          localUrl: dco_decode_String(raw[1]),
+          remoteUrl: dco_decode_String(raw[2]),
+        );
+      case 10:
+        return KVEvent_ConnectionError(
+          localUrl: dco_decode_String(raw[1]),
+          remoteUrl: dco_decode_String(raw[2]),
+          error: dco_decode_String(raw[3]),
+        );
+      case 11:
+        return KVEvent_Connecting(url: dco_decode_String(raw[1]));
+      case 12:
+        return KVEvent_ConnectError(
+          dst: dco_decode_String(raw[1]),
+          ipVersion: dco_decode_String(raw[2]),
+          error: dco_decode_String(raw[3]),
+        );
+      case 13:
+        return KVEvent_VpnPortalStarted(portal: dco_decode_String(raw[1]));
+      case 14:
+        return KVEvent_VpnPortalClientConnected(
+          portal: dco_decode_String(raw[1]),
+          clientAddr: dco_decode_String(raw[2]),
+        );
+      case 15:
+        return KVEvent_VpnPortalClientDisconnected(
+          portal: dco_decode_String(raw[1]),
+          clientAddr: dco_decode_String(raw[2]),
+        );
+      case 16:
+        return KVEvent_DhcpIpv4Changed(
+          oldCidr: dco_decode_opt_String(raw[1]),
+          newCidr: dco_decode_opt_String(raw[2]),
+        );
+      case 17:
+        return KVEvent_DhcpIpv4Conflicted(cidr: dco_decode_opt_String(raw[1]));
+      case 18:
+        return KVEvent_PortForwardAdded(
+          bindAddr: dco_decode_String(raw[1]),
+          dstAddr: dco_decode_String(raw[2]),
+          proto: dco_decode_String(raw[3]),
+        );
+      case 19:
+        return KVEvent_Lagged(missed: dco_decode_u_64(raw[1]));
+      case 20:
+        return KVEvent_Closed();
+      default:
+        throw Exception("unreachable");
+    }
+  }
+
+  @protected
+  KVEventKind dco_decode_kv_event_kind(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return KVEventKind.values[raw as int];
+  }
+
+  @protected
+  KVEventRecord dco_decode_kv_event_record(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 3)
+      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+    return KVEventRecord(
+      instanceId: dco_decode_String(arr[0]),
+      timestampMs: dco_decode_i_64(arr[1]),
+      event: dco_decode_kv_event(arr[2]),
+    );
+  }
+
+  @protected
+  KVExitFlow dco_decode_kv_exit_flow(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 5)
+      throw Exception('unexpected arr length: expect 5 but see ${arr.length}');
+    return KVExitFlow(
+      protocol: dco_decode_String(arr[0]),
+      src: dco_decode_String(arr[1]),
+      dst: dco_decode_String(arr[2]),
+      exitNode: dco_decode_String(arr[3]),
+      idleMs: dco_decode_u_64(arr[4]),
+    );
+  }
+
+  @protected
+  KVExitFlowInfo dco_decode_kv_exit_flow_info(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 3)
+      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+    return KVExitFlowInfo(
+      exitNodes: dco_decode_list_kv_exit_node_status(arr[0]),
+      flows: dco_decode_list_kv_exit_flow(arr[1]),
+      totalFlows: dco_decode_u_64(arr[2]),
+    );
+  }
+
+  @protected
+  KVExitNodeStatus dco_decode_kv_exit_node_status(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 5)
+      throw Exception('unexpected arr length: expect 5 but see ${arr.length}');
+    return KVExitNodeStatus(
+      exitNode: dco_decode_String(arr[0]),
+      reachable: dco_decode_bool(arr[1]),
+      healthy: dco_decode_bool(arr[2]),
+      rttMs: dco_decode_opt_box_autoadd_f_64(arr[3]),
+      consecutiveFailures: dco_decode_u_32(arr[4]),
+    );
+  }
+
+  @protected
+  KVInstanceInfo dco_decode_kv_instance_info(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 4)
+      throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
+    return KVInstanceInfo(
+      instanceId: dco_decode_String(arr[0]),
+      networkName: dco_decode_String(arr[1]),
+      running: dco_decode_bool(arr[2]),
+      errorMsg: dco_decode_opt_String(arr[3]),
+    );
+  }
+
   @protected
   KVNetworkStatus dco_decode_kv_network_status(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
@@ -2915,6 +4092,39 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     );
   }
 
+  @protected
+  KVPeerConn dco_decode_kv_peer_conn(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 8)
+      throw Exception('unexpected arr length: expect 8 but see ${arr.length}');
+    return KVPeerConn(
+      connId: dco_decode_String(arr[0]),
+      myPeerId: dco_decode_u_32(arr[1]),
+      peerId: dco_decode_u_32(arr[2]),
+      tunnelType: dco_decode_String(arr[3]),
+      localAddr: dco_decode_String(arr[4]),
+      remoteAddr: dco_decode_String(arr[5]),
+      isClient: dco_decode_bool(arr[6]),
+      networkName: dco_decode_String(arr[7]),
+    );
+  }
+
+  @protected
+  KVSplitTunnelStatus dco_decode_kv_split_tunnel_status(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 5)
+      throw Exception('unexpected arr length: expect 5 but see ${arr.length}');
+    return KVSplitTunnelStatus(
+      active: dco_decode_bool(arr[0]),
+      instanceId: dco_decode_String(arr[1]),
+      devName: dco_decode_String(arr[2]),
+      mode: dco_decode_split_tunnel_mode(arr[3]),
+      appCount: dco_decode_u_32(arr[4]),
+    );
+  }
+
   @protected
   List<FilterRule>
   dco_decode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -2953,6 +4163,30 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return (raw as List<dynamic>).map(dco_decode_forward).toList();
   }
 
+  @protected
+  List<KVEventKind> dco_decode_list_kv_event_kind(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_kv_event_kind).toList();
+  }
+
+  @protected
+  List<KVExitFlow> dco_decode_list_kv_exit_flow(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_kv_exit_flow).toList();
+  }
+
+  @protected
+  List<KVExitNodeStatus> dco_decode_list_kv_exit_node_status(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_kv_exit_node_status).toList();
+  }
+
+  @protected
+  List<KVInstanceInfo> dco_decode_list_kv_instance_info(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_kv_instance_info).toList();
+  }
+
   @protected
   List<KVNodeConnectionStats> dco_decode_list_kv_node_connection_stats(
     dynamic raw,
@@ -2976,27 +4210,88 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
   }
 
   @protected
-  Uint16List dco_decode_list_prim_u_16_strict(dynamic raw) {
+  Uint16List dco_decode_list_prim_u_16_strict(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as Uint16List;
+  }
+
+  @protected
+  Uint64List dco_decode_list_prim_u_64_strict(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dcoDecodeUint64List(raw);
+  }
+
+  @protected
+  Uint8List dco_decode_list_prim_u_8_strict(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as Uint8List;
+  }
+
+  @protected
+  List<ProfileChange> dco_decode_list_profile_change(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_profile_change).toList();
+  }
+
+  @protected
+  List<ProfileFieldError> dco_decode_list_profile_field_error(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_profile_field_error).toList();
+  }
+
+  @protected
+  List<ProfileProxyNetwork> dco_decode_list_profile_proxy_network(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as Uint16List;
+    return (raw as List<dynamic>)
+        .map(dco_decode_profile_proxy_network)
+        .toList();
   }
 
   @protected
-  Uint64List dco_decode_list_prim_u_64_strict(dynamic raw) {
+  List<(String, int)> dco_decode_list_record_string_u_32(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return dcoDecodeUint64List(raw);
+    return (raw as List<dynamic>).map(dco_decode_record_string_u_32).toList();
   }
 
   @protected
-  Uint8List dco_decode_list_prim_u_8_strict(dynamic raw) {
+  List<SplitTunnelRule> dco_decode_list_split_tunnel_rule(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as Uint8List;
+    return (raw as List<dynamic>).map(dco_decode_split_tunnel_rule).toList();
   }
 
   @protected
-  List<(String, int)> dco_decode_list_record_string_u_32(dynamic raw) {
+  NetworkProfile dco_decode_network_profile(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return (raw as List<dynamic>).map(dco_decode_record_string_u_32).toList();
+    final arr = raw as List<dynamic>;
+    if (arr.length != 25)
+      throw Exception('unexpected arr length: expect 25 but see ${arr.length}');
+    return NetworkProfile(
+      instanceId: dco_decode_opt_String(arr[0]),
+      instanceName: dco_decode_String(arr[1]),
+      hostname: dco_decode_opt_String(arr[2]),
+      networkName: dco_decode_String(arr[3]),
+      networkSecret: dco_decode_String(arr[4]),
+      dhcp: dco_decode_bool(arr[5]),
+      ipv4: dco_decode_opt_String(arr[6]),
+      ipv6: dco_decode_opt_String(arr[7]),
+      peers: dco_decode_list_String(arr[8]),
+      listeners: dco_decode_list_String(arr[9]),
+      mappedListeners: dco_decode_list_String(arr[10]),
+      proxyNetworks: dco_decode_list_profile_proxy_network(arr[11]),
+      exitNodes: dco_decode_list_String(arr[12]),
+      routes: dco_decode_opt_list_String(arr[13]),
+      socks5Portal: dco_decode_opt_String(arr[14]),
+      portForwards: dco_decode_list_forward(arr[15]),
+      vpnPortal: dco_decode_opt_box_autoadd_profile_vpn_portal(arr[16]),
+      rpcPortal: dco_decode_opt_String(arr[17]),
+      rpcPortalWhitelist: dco_decode_opt_list_String(arr[18]),
+      stunServers: dco_decode_opt_list_String(arr[19]),
+      stunServersV6: dco_decode_opt_list_String(arr[20]),
+      tcpWhitelist: dco_decode_list_String(arr[21]),
+      udpWhitelist: dco_decode_list_String(arr[22]),
+      aclJson: dco_decode_opt_String(arr[23]),
+      flags: dco_decode_flags_c(arr[24]),
+    );
   }
 
   @protected
@@ -3019,6 +4314,18 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return raw == null ? null : dco_decode_String(raw);
   }
 
+  @protected
+  double? dco_decode_opt_box_autoadd_f_64(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw == null ? null : dco_decode_box_autoadd_f_64(raw);
+  }
+
+  @protected
+  ProfileVpnPortal? dco_decode_opt_box_autoadd_profile_vpn_portal(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw == null ? null : dco_decode_box_autoadd_profile_vpn_portal(raw);
+  }
+
   @protected
   Protocol? dco_decode_opt_box_autoadd_protocol(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
@@ -3043,6 +4350,61 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return raw == null ? null : dco_decode_box_autoadd_u_32(raw);
   }
 
+  @protected
+  List<String>? dco_decode_opt_list_String(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw == null ? null : dco_decode_list_String(raw);
+  }
+
+  @protected
+  ProfileChange dco_decode_profile_change(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 3)
+      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+    return ProfileChange(
+      field: dco_decode_String(arr[0]),
+      oldValue: dco_decode_opt_String(arr[1]),
+      newValue: dco_decode_opt_String(arr[2]),
+    );
+  }
+
+  @protected
+  ProfileFieldError dco_decode_profile_field_error(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 2)
+      throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+    return ProfileFieldError(
+      field: dco_decode_String(arr[0]),
+      message: dco_decode_String(arr[1]),
+    );
+  }
+
+  @protected
+  ProfileProxyNetwork dco_decode_profile_proxy_network(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 2)
+      throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+    return ProfileProxyNetwork(
+      cidr: dco_decode_String(arr[0]),
+      mappedCidr: dco_decode_opt_String(arr[1]),
+    );
+  }
+
+  @protected
+  ProfileVpnPortal dco_decode_profile_vpn_portal(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 2)
+      throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+    return ProfileVpnPortal(
+      clientCidr: dco_decode_String(arr[0]),
+      wireguardListen: dco_decode_String(arr[1]),
+    );
+  }
+
   @protected
   Protocol dco_decode_protocol(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
@@ -3069,6 +4431,24 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return (dco_decode_u_16(arr[0]), dco_decode_u_16(arr[1]));
   }
 
+  @protected
+  SplitTunnelMode dco_decode_split_tunnel_mode(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return SplitTunnelMode.values[raw as int];
+  }
+
+  @protected
+  SplitTunnelRule dco_decode_split_tunnel_rule(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 2)
+      throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+    return SplitTunnelRule(
+      appPath: dco_decode_String(arr[0]),
+      enabled: dco_decode_bool(arr[1]),
+    );
+  }
+
   @protected
   int dco_decode_u_16(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
@@ -3112,18 +4492,6 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return AnyhowException(inner);
   }
 
-  @protected
-  EventBusSubscriber
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    SseDeserializer deserializer,
-  ) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    return EventBusSubscriberImpl.frbInternalSseDecode(
-      sse_decode_usize(deserializer),
-      sse_decode_i_32(deserializer),
-    );
-  }
-
   @protected
   FilterRule
   sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -3161,24 +4529,48 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
   }
 
   @protected
-  JoinHandle
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  JoinHandleResultKvConfigApplyReportString
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return JoinHandleResultKvConfigApplyReportStringImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  JoinHandleResultKvShutdownReportString
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return JoinHandleResultKvShutdownReportStringImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  JoinHandleResultStringString
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
     SseDeserializer deserializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return JoinHandleImpl.frbInternalSseDecode(
+    return JoinHandleResultStringStringImpl.frbInternalSseDecode(
       sse_decode_usize(deserializer),
       sse_decode_i_32(deserializer),
     );
   }
 
   @protected
-  JoinHandleResultString
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  JoinHandleVecKvShutdownReport
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     SseDeserializer deserializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return JoinHandleResultStringImpl.frbInternalSseDecode(
+    return JoinHandleVecKvShutdownReportImpl.frbInternalSseDecode(
       sse_decode_usize(deserializer),
       sse_decode_i_32(deserializer),
     );
@@ -3292,18 +4684,6 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     );
   }
 
-  @protected
-  EventBusSubscriber
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    SseDeserializer deserializer,
-  ) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    return EventBusSubscriberImpl.frbInternalSseDecode(
-      sse_decode_usize(deserializer),
-      sse_decode_i_32(deserializer),
-    );
-  }
-
   @protected
   FilterRule
   sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -3353,24 +4733,48 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
   }
 
   @protected
-  JoinHandle
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  JoinHandleResultKvConfigApplyReportString
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return JoinHandleResultKvConfigApplyReportStringImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  JoinHandleResultKvShutdownReportString
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return JoinHandleResultKvShutdownReportStringImpl.frbInternalSseDecode(
+      sse_decode_usize(deserializer),
+      sse_decode_i_32(deserializer),
+    );
+  }
+
+  @protected
+  JoinHandleResultStringString
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
     SseDeserializer deserializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return JoinHandleImpl.frbInternalSseDecode(
+    return JoinHandleResultStringStringImpl.frbInternalSseDecode(
       sse_decode_usize(deserializer),
       sse_decode_i_32(deserializer),
     );
   }
 
   @protected
-  JoinHandleResultString
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  JoinHandleVecKvShutdownReport
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     SseDeserializer deserializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    return JoinHandleResultStringImpl.frbInternalSseDecode(
+    return JoinHandleVecKvShutdownReportImpl.frbInternalSseDecode(
       sse_decode_usize(deserializer),
       sse_decode_i_32(deserializer),
     );
@@ -3400,6 +4804,14 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     );
   }
 
+  @protected
+  RustStreamSink<KVEventRecord> sse_decode_StreamSink_kv_event_record_Sse(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    throw UnimplementedError('Unreachable ()');
+  }
+
   @protected
   String sse_decode_String(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -3413,12 +4825,34 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return deserializer.buffer.getUint8() != 0;
   }
 
+  @protected
+  double sse_decode_box_autoadd_f_64(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_f_64(deserializer));
+  }
+
   @protected
   FlagsC sse_decode_box_autoadd_flags_c(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
     return (sse_decode_flags_c(deserializer));
   }
 
+  @protected
+  NetworkProfile sse_decode_box_autoadd_network_profile(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_network_profile(deserializer));
+  }
+
+  @protected
+  ProfileVpnPortal sse_decode_box_autoadd_profile_vpn_portal(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_profile_vpn_portal(deserializer));
+  }
+
   @protected
   Protocol sse_decode_box_autoadd_protocol(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -3499,6 +4933,11 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     var var_enableQuicProxy = sse_decode_bool(deserializer);
     var var_disableQuicInput = sse_decode_bool(deserializer);
     var var_disableSymHolePunching = sse_decode_bool(deserializer);
+    var var_enableRelayForeignNetworkKcp = sse_decode_bool(deserializer);
+    var var_foreignRelayBpsLimit = sse_decode_u_64(deserializer);
+    var var_multiThreadCount = sse_decode_u_32(deserializer);
+    var var_encryptionAlgorithm = sse_decode_String(deserializer);
+    var var_enableMultipathBonding = sse_decode_bool(deserializer);
     return FlagsC(
       defaultProtocol: var_defaultProtocol,
       devName: var_devName,
@@ -3525,6 +4964,11 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
       enableQuicProxy: var_enableQuicProxy,
       disableQuicInput: var_disableQuicInput,
       disableSymHolePunching: var_disableSymHolePunching,
+      enableRelayForeignNetworkKcp: var_enableRelayForeignNetworkKcp,
+      foreignRelayBpsLimit: var_foreignRelayBpsLimit,
+      multiThreadCount: var_multiThreadCount,
+      encryptionAlgorithm: var_encryptionAlgorithm,
+      enableMultipathBonding: var_enableMultipathBonding,
     );
   }
 
@@ -3547,6 +4991,205 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return deserializer.buffer.getInt32();
   }
 
+  @protected
+  PlatformInt64 sse_decode_i_64(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return deserializer.buffer.getPlatformInt64();
+  }
+
+  @protected
+  KVEvent sse_decode_kv_event(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var tag_ = sse_decode_i_32(deserializer);
+    switch (tag_) {
+      case 0:
+        var var_devName = sse_decode_String(deserializer);
+        return KVEvent_TunDeviceReady(devName: var_devName);
+      case 1:
+        var var_error = sse_decode_String(deserializer);
+        return KVEvent_TunDeviceError(error: var_error);
+      case 2:
+        var var_peerId = sse_decode_u_32(deserializer);
+        return KVEvent_PeerAdded(peerId: var_peerId);
+      case 3:
+        var var_peerId = sse_decode_u_32(deserializer);
+        return KVEvent_PeerRemoved(peerId: var_peerId);
+      case 4:
+        var var_conn = sse_decode_kv_peer_conn(deserializer);
+        return KVEvent_PeerConnAdded(conn: var_conn);
+      case 5:
+        var var_conn = sse_decode_kv_peer_conn(deserializer);
+        return KVEvent_PeerConnRemoved(conn: var_conn);
+      case 6:
+        var var_url = sse_decode_String(deserializer);
+        return KVEvent_ListenerAdded(url: var_url);
+      case 7:
+        var var_url = sse_decode_String(deserializer);
+        var var_error = sse_decode_String(deserializer);
+        return KVEvent_ListenerAddFailed(url: var_url, error: var_error);
+      case 8:
+        var var_url = sse_decode_String(deserializer);
+        var var_error = sse_decode_String(deserializer);
+        return KVEvent_ListenerAcceptFailed(url: var_url, error: var_error);
+      case 9:
+        var var_localUrl = sse_decode_String(deserializer);
+        var var_remoteUrl = sse_decode_String(deserializer);
+        return KVEvent_ConnectionAccepted(
+          localUrl: var_localUrl,
+          remoteUrl: var_remoteUrl,
+        );
+      case 10:
+        var var_localUrl = sse_decode_String(deserializer);
+        var var_remoteUrl = sse_decode_String(deserializer);
+        var var_error = sse_decode_String(deserializer);
+        return KVEvent_ConnectionError(
+          localUrl: var_localUrl,
+          remoteUrl: var_remoteUrl,
+          error: var_error,
+        );
+      case 11:
+        var var_url = sse_decode_String(deserializer);
+        return KVEvent_Connecting(url: var_url);
+      case 12:
+        var var_dst = sse_decode_String(deserializer);
+        var var_ipVersion = sse_decode_String(deserializer);
+        var var_error = sse_decode_String(deserializer);
+        return KVEvent_ConnectError(
+          dst: var_dst,
+          ipVersion: var_ipVersion,
+          error: var_error,
+        );
+      case 13:
+        var var_portal = sse_decode_String(deserializer);
+        return KVEvent_VpnPortalStarted(portal: var_portal);
+      case 14:
+        var var_portal = sse_decode_String(deserializer);
+        var var_clientAddr = sse_decode_String(deserializer);
+        return KVEvent_VpnPortalClientConnected(
+          portal: var_portal,
+          clientAddr: var_clientAddr,
+        );
+      case 15:
+        var var_portal = sse_decode_String(deserializer);
+        var var_clientAddr = sse_decode_String(deserializer);
+        return KVEvent_VpnPortalClientDisconnected(
+          portal: var_portal,
+          clientAddr: var_clientAddr,
+        );
+      case 16:
+        var var_oldCidr = sse_decode_opt_String(deserializer);
+        var var_newCidr = sse_decode_opt_String(deserializer);
+        return KVEvent_DhcpIpv4Changed(
+          oldCidr: var_oldCidr,
+          newCidr: var_newCidr,
+        );
+      case 17:
+        var var_cidr = sse_decode_opt_String(deserializer);
+        return KVEvent_DhcpIpv4Conflicted(cidr: var_cidr);
+      case 18:
+        var var_bindAddr = sse_decode_String(deserializer);
+        var var_dstAddr = sse_decode_String(deserializer);
+        var var_proto = sse_decode_String(deserializer);
+        return KVEvent_PortForwardAdded(
+          bindAddr: var_bindAddr,
+          dstAddr: var_dstAddr,
+          proto: var_proto,
+        );
+      case 19:
+        var var_missed = sse_decode_u_64(deserializer);
+        return KVEvent_Lagged(missed: var_missed);
+      case 20:
+        return KVEvent_Closed();
+      default:
+        throw UnimplementedError('');
+    }
+  }
+
+  @protected
+  KVEventKind sse_decode_kv_event_kind(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var inner = sse_decode_i_32(deserializer);
+    return KVEventKind.values[inner];
+  }
+
+  @protected
+  KVEventRecord sse_decode_kv_event_record(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_instanceId = sse_decode_String(deserializer);
+    var var_timestampMs = sse_decode_i_64(deserializer);
+    var var_event = sse_decode_kv_event(deserializer);
+    return KVEventRecord(
+      instanceId: var_instanceId,
+      timestampMs: var_timestampMs,
+      event: var_event,
+    );
+  }
+
+  @protected
+  KVExitFlow sse_decode_kv_exit_flow(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_protocol = sse_decode_String(deserializer);
+    var var_src = sse_decode_String(deserializer);
+    var var_dst = sse_decode_String(deserializer);
+    var var_exitNode = sse_decode_String(deserializer);
+    var var_idleMs = sse_decode_u_64(deserializer);
+    return KVExitFlow(
+      protocol: var_protocol,
+      src: var_src,
+      dst: var_dst,
+      exitNode: var_exitNode,
+      idleMs: var_idleMs,
+    );
+  }
+
+  @protected
+  KVExitFlowInfo sse_decode_kv_exit_flow_info(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_exitNodes = sse_decode_list_kv_exit_node_status(deserializer);
+    var var_flows = sse_decode_list_kv_exit_flow(deserializer);
+    var var_totalFlows = sse_decode_u_64(deserializer);
+    return KVExitFlowInfo(
+      exitNodes: var_exitNodes,
+      flows: var_flows,
+      totalFlows: var_totalFlows,
+    );
+  }
+
+  @protected
+  KVExitNodeStatus sse_decode_kv_exit_node_status(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_exitNode = sse_decode_String(deserializer);
+    var var_reachable = sse_decode_bool(deserializer);
+    var var_healthy = sse_decode_bool(deserializer);
+    var var_rttMs = sse_decode_opt_box_autoadd_f_64(deserializer);
+    var var_consecutiveFailures = sse_decode_u_32(deserializer);
+    return KVExitNodeStatus(
+      exitNode: var_exitNode,
+      reachable: var_reachable,
+      healthy: var_healthy,
+      rttMs: var_rttMs,
+      consecutiveFailures: var_consecutiveFailures,
+    );
+  }
+
+  @protected
+  KVInstanceInfo sse_decode_kv_instance_info(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_instanceId = sse_decode_String(deserializer);
+    var var_networkName = sse_decode_String(deserializer);
+    var var_running = sse_decode_bool(deserializer);
+    var var_errorMsg = sse_decode_opt_String(deserializer);
+    return KVInstanceInfo(
+      instanceId: var_instanceId,
+      networkName: var_networkName,
+      running: var_running,
+      errorMsg: var_errorMsg,
+    );
+  }
+
   @protected
   KVNetworkStatus sse_decode_kv_network_status(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -3609,6 +5252,48 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     );
   }
 
+  @protected
+  KVPeerConn sse_decode_kv_peer_conn(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_connId = sse_decode_String(deserializer);
+    var var_myPeerId = sse_decode_u_32(deserializer);
+    var var_peerId = sse_decode_u_32(deserializer);
+    var var_tunnelType = sse_decode_String(deserializer);
+    var var_localAddr = sse_decode_String(deserializer);
+    var var_remoteAddr = sse_decode_String(deserializer);
+    var var_isClient = sse_decode_bool(deserializer);
+    var var_networkName = sse_decode_String(deserializer);
+    return KVPeerConn(
+      connId: var_connId,
+      myPeerId: var_myPeerId,
+      peerId: var_peerId,
+      tunnelType: var_tunnelType,
+      localAddr: var_localAddr,
+      remoteAddr: var_remoteAddr,
+      isClient: var_isClient,
+      networkName: var_networkName,
+    );
+  }
+
+  @protected
+  KVSplitTunnelStatus sse_decode_kv_split_tunnel_status(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_active = sse_decode_bool(deserializer);
+    var var_instanceId = sse_decode_String(deserializer);
+    var var_devName = sse_decode_String(deserializer);
+    var var_mode = sse_decode_split_tunnel_mode(deserializer);
+    var var_appCount = sse_decode_u_32(deserializer);
+    return KVSplitTunnelStatus(
+      active: var_active,
+      instanceId: var_instanceId,
+      devName: var_devName,
+      mode: var_mode,
+      appCount: var_appCount,
+    );
+  }
+
   @protected
   List<FilterRule>
   sse_decode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -3617,56 +5302,110 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     // Codec=Sse (Serialization based), see doc to use other codecs
 
     var len_ = sse_decode_i_32(deserializer);
-    var ans_ = <FilterRule>[];
+    var ans_ = <FilterRule>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(
+        sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
+          deserializer,
+        ),
+      );
+    }
+    return ans_;
+  }
+
+  @protected
+  List<PeerRoutePair>
+  sse_decode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPeerRoutePair(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <PeerRoutePair>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(
+        sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPeerRoutePair(
+          deserializer,
+        ),
+      );
+    }
+    return ans_;
+  }
+
+  @protected
+  List<String> sse_decode_list_String(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <String>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_String(deserializer));
+    }
+    return ans_;
+  }
+
+  @protected
+  List<Forward> sse_decode_list_forward(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <Forward>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_forward(deserializer));
+    }
+    return ans_;
+  }
+
+  @protected
+  List<KVEventKind> sse_decode_list_kv_event_kind(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <KVEventKind>[];
     for (var idx_ = 0; idx_ < len_; ++idx_) {
-      ans_.add(
-        sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
-          deserializer,
-        ),
-      );
+      ans_.add(sse_decode_kv_event_kind(deserializer));
     }
     return ans_;
   }
 
   @protected
-  List<PeerRoutePair>
-  sse_decode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPeerRoutePair(
-    SseDeserializer deserializer,
-  ) {
+  List<KVExitFlow> sse_decode_list_kv_exit_flow(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
 
     var len_ = sse_decode_i_32(deserializer);
-    var ans_ = <PeerRoutePair>[];
+    var ans_ = <KVExitFlow>[];
     for (var idx_ = 0; idx_ < len_; ++idx_) {
-      ans_.add(
-        sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerPeerRoutePair(
-          deserializer,
-        ),
-      );
+      ans_.add(sse_decode_kv_exit_flow(deserializer));
     }
     return ans_;
   }
 
   @protected
-  List<String> sse_decode_list_String(SseDeserializer deserializer) {
+  List<KVExitNodeStatus> sse_decode_list_kv_exit_node_status(
+    SseDeserializer deserializer,
+  ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
 
     var len_ = sse_decode_i_32(deserializer);
-    var ans_ = <String>[];
+    var ans_ = <KVExitNodeStatus>[];
     for (var idx_ = 0; idx_ < len_; ++idx_) {
-      ans_.add(sse_decode_String(deserializer));
+      ans_.add(sse_decode_kv_exit_node_status(deserializer));
     }
     return ans_;
   }
 
   @protected
-  List<Forward> sse_decode_list_forward(SseDeserializer deserializer) {
+  List<KVInstanceInfo> sse_decode_list_kv_instance_info(
+    SseDeserializer deserializer,
+  ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
 
     var len_ = sse_decode_i_32(deserializer);
-    var ans_ = <Forward>[];
+    var ans_ = <KVInstanceInfo>[];
     for (var idx_ = 0; idx_ < len_; ++idx_) {
-      ans_.add(sse_decode_forward(deserializer));
+      ans_.add(sse_decode_kv_instance_info(deserializer));
     }
     return ans_;
   }
@@ -3732,6 +5471,48 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return deserializer.buffer.getUint8List(len_);
   }
 
+  @protected
+  List<ProfileChange> sse_decode_list_profile_change(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <ProfileChange>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_profile_change(deserializer));
+    }
+    return ans_;
+  }
+
+  @protected
+  List<ProfileFieldError> sse_decode_list_profile_field_error(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <ProfileFieldError>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_profile_field_error(deserializer));
+    }
+    return ans_;
+  }
+
+  @protected
+  List<ProfileProxyNetwork> sse_decode_list_profile_proxy_network(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <ProfileProxyNetwork>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_profile_proxy_network(deserializer));
+    }
+    return ans_;
+  }
+
   @protected
   List<(String, int)> sse_decode_list_record_string_u_32(
     SseDeserializer deserializer,
@@ -3746,6 +5527,79 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return ans_;
   }
 
+  @protected
+  List<SplitTunnelRule> sse_decode_list_split_tunnel_rule(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <SplitTunnelRule>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_split_tunnel_rule(deserializer));
+    }
+    return ans_;
+  }
+
+  @protected
+  NetworkProfile sse_decode_network_profile(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_instanceId = sse_decode_opt_String(deserializer);
+    var var_instanceName = sse_decode_String(deserializer);
+    var var_hostname = sse_decode_opt_String(deserializer);
+    var var_networkName = sse_decode_String(deserializer);
+    var var_networkSecret = sse_decode_String(deserializer);
+    var var_dhcp = sse_decode_bool(deserializer);
+    var var_ipv4 = sse_decode_opt_String(deserializer);
+    var var_ipv6 = sse_decode_opt_String(deserializer);
+    var var_peers = sse_decode_list_String(deserializer);
+    var var_listeners = sse_decode_list_String(deserializer);
+    var var_mappedListeners = sse_decode_list_String(deserializer);
+    var var_proxyNetworks = sse_decode_list_profile_proxy_network(deserializer);
+    var var_exitNodes = sse_decode_list_String(deserializer);
+    var var_routes = sse_decode_opt_list_String(deserializer);
+    var var_socks5Portal = sse_decode_opt_String(deserializer);
+    var var_portForwards = sse_decode_list_forward(deserializer);
+    var var_vpnPortal = sse_decode_opt_box_autoadd_profile_vpn_portal(
+      deserializer,
+    );
+    var var_rpcPortal = sse_decode_opt_String(deserializer);
+    var var_rpcPortalWhitelist = sse_decode_opt_list_String(deserializer);
+    var var_stunServers = sse_decode_opt_list_String(deserializer);
+    var var_stunServersV6 = sse_decode_opt_list_String(deserializer);
+    var var_tcpWhitelist = sse_decode_list_String(deserializer);
+    var var_udpWhitelist = sse_decode_list_String(deserializer);
+    var var_aclJson = sse_decode_opt_String(deserializer);
+    var var_flags = sse_decode_flags_c(deserializer);
+    return NetworkProfile(
+      instanceId: var_instanceId,
+      instanceName: var_instanceName,
+      hostname: var_hostname,
+      networkName: var_networkName,
+      networkSecret: var_networkSecret,
+      dhcp: var_dhcp,
+      ipv4: var_ipv4,
+      ipv6: var_ipv6,
+      peers: var_peers,
+      listeners: var_listeners,
+      mappedListeners: var_mappedListeners,
+      proxyNetworks: var_proxyNetworks,
+      exitNodes: var_exitNodes,
+      routes: var_routes,
+      socks5Portal: var_socks5Portal,
+      portForwards: var_portForwards,
+      vpnPortal: var_vpnPortal,
+      rpcPortal: var_rpcPortal,
+      rpcPortalWhitelist: var_rpcPortalWhitelist,
+      stunServers: var_stunServers,
+      stunServersV6: var_stunServersV6,
+      tcpWhitelist: var_tcpWhitelist,
+      udpWhitelist: var_udpWhitelist,
+      aclJson: var_aclJson,
+      flags: var_flags,
+    );
+  }
+
   @protected
   NodeHopStats sse_decode_node_hop_stats(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -3772,6 +5626,30 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     }
   }
 
+  @protected
+  double? sse_decode_opt_box_autoadd_f_64(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    if (sse_decode_bool(deserializer)) {
+      return (sse_decode_box_autoadd_f_64(deserializer));
+    } else {
+      return null;
+    }
+  }
+
+  @protected
+  ProfileVpnPortal? sse_decode_opt_box_autoadd_profile_vpn_portal(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    if (sse_decode_bool(deserializer)) {
+      return (sse_decode_box_autoadd_profile_vpn_portal(deserializer));
+    } else {
+      return null;
+    }
+  }
+
   @protected
   Protocol? sse_decode_opt_box_autoadd_protocol(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -3818,6 +5696,61 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     }
   }
 
+  @protected
+  List<String>? sse_decode_opt_list_String(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    if (sse_decode_bool(deserializer)) {
+      return (sse_decode_list_String(deserializer));
+    } else {
+      return null;
+    }
+  }
+
+  @protected
+  ProfileChange sse_decode_profile_change(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_field = sse_decode_String(deserializer);
+    var var_oldValue = sse_decode_opt_String(deserializer);
+    var var_newValue = sse_decode_opt_String(deserializer);
+    return ProfileChange(
+      field: var_field,
+      oldValue: var_oldValue,
+      newValue: var_newValue,
+    );
+  }
+
+  @protected
+  ProfileFieldError sse_decode_profile_field_error(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_field = sse_decode_String(deserializer);
+    var var_message = sse_decode_String(deserializer);
+    return ProfileFieldError(field: var_field, message: var_message);
+  }
+
+  @protected
+  ProfileProxyNetwork sse_decode_profile_proxy_network(
+    SseDeserializer deserializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_cidr = sse_decode_String(deserializer);
+    var var_mappedCidr = sse_decode_opt_String(deserializer);
+    return ProfileProxyNetwork(cidr: var_cidr, mappedCidr: var_mappedCidr);
+  }
+
+  @protected
+  ProfileVpnPortal sse_decode_profile_vpn_portal(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_clientCidr = sse_decode_String(deserializer);
+    var var_wireguardListen = sse_decode_String(deserializer);
+    return ProfileVpnPortal(
+      clientCidr: var_clientCidr,
+      wireguardListen: var_wireguardListen,
+    );
+  }
+
   @protected
   Protocol sse_decode_protocol(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -3841,6 +5774,21 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return (var_field0, var_field1);
   }
 
+  @protected
+  SplitTunnelMode sse_decode_split_tunnel_mode(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var inner = sse_decode_i_32(deserializer);
+    return SplitTunnelMode.values[inner];
+  }
+
+  @protected
+  SplitTunnelRule sse_decode_split_tunnel_rule(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_appPath = sse_decode_String(deserializer);
+    var var_enabled = sse_decode_bool(deserializer);
+    return SplitTunnelRule(appPath: var_appPath, enabled: var_enabled);
+  }
+
   @protected
   int sse_decode_u_16(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -3887,78 +5835,95 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
 
   @protected
   void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    EventBusSubscriber self,
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
+    FilterRule self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
     sse_encode_usize(
-      (self as EventBusSubscriberImpl).frbInternalSseEncode(move: true),
+      (self as FilterRuleImpl).frbInternalSseEncode(move: true),
       serializer,
     );
   }
 
   @protected
   void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
-    FilterRule self,
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerIpAddr(
+    IpAddr self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
     sse_encode_usize(
-      (self as FilterRuleImpl).frbInternalSseEncode(move: true),
+      (self as IpAddrImpl).frbInternalSseEncode(move: true),
       serializer,
     );
   }
 
   @protected
   void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerIpAddr(
-    IpAddr self,
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerIpNetwork(
+    IpNetwork self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
     sse_encode_usize(
-      (self as IpAddrImpl).frbInternalSseEncode(move: true),
+      (self as IpNetworkImpl).frbInternalSseEncode(move: true),
       serializer,
     );
   }
 
   @protected
   void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerIpNetwork(
-    IpNetwork self,
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    JoinHandleResultKvConfigApplyReportString self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
     sse_encode_usize(
-      (self as IpNetworkImpl).frbInternalSseEncode(move: true),
+      (self as JoinHandleResultKvConfigApplyReportStringImpl)
+          .frbInternalSseEncode(move: true),
+      serializer,
+    );
+  }
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    JoinHandleResultKvShutdownReportString self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as JoinHandleResultKvShutdownReportStringImpl)
+          .frbInternalSseEncode(move: true),
       serializer,
     );
   }
 
   @protected
   void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
-    JoinHandle self,
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    JoinHandleResultStringString self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
     sse_encode_usize(
-      (self as JoinHandleImpl).frbInternalSseEncode(move: true),
+      (self as JoinHandleResultStringStringImpl)
+          .frbInternalSseEncode(move: true),
       serializer,
     );
   }
 
   @protected
   void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
-    JoinHandleResultString self,
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
+    JoinHandleVecKvShutdownReport self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
     sse_encode_usize(
-      (self as JoinHandleResultStringImpl).frbInternalSseEncode(move: true),
+      (self as JoinHandleVecKvShutdownReportImpl)
+          .frbInternalSseEncode(move: true),
       serializer,
     );
   }
@@ -4080,19 +6045,6 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     );
   }
 
-  @protected
-  void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    EventBusSubscriber self,
-    SseSerializer serializer,
-  ) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_usize(
-      (self as EventBusSubscriberImpl).frbInternalSseEncode(move: null),
-      serializer,
-    );
-  }
-
   @protected
   void
   sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -4147,26 +6099,56 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
 
   @protected
   void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
-    JoinHandle self,
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    JoinHandleResultKvConfigApplyReportString self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as JoinHandleResultKvConfigApplyReportStringImpl)
+          .frbInternalSseEncode(move: null),
+      serializer,
+    );
+  }
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    JoinHandleResultKvShutdownReportString self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_usize(
+      (self as JoinHandleResultKvShutdownReportStringImpl)
+          .frbInternalSseEncode(move: null),
+      serializer,
+    );
+  }
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    JoinHandleResultStringString self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
     sse_encode_usize(
-      (self as JoinHandleImpl).frbInternalSseEncode(move: null),
+      (self as JoinHandleResultStringStringImpl)
+          .frbInternalSseEncode(move: null),
       serializer,
     );
   }
 
   @protected
   void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
-    JoinHandleResultString self,
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
+    JoinHandleVecKvShutdownReport self,
     SseSerializer serializer,
   ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
     sse_encode_usize(
-      (self as JoinHandleResultStringImpl).frbInternalSseEncode(move: null),
+      (self as JoinHandleVecKvShutdownReportImpl)
+          .frbInternalSseEncode(move: null),
       serializer,
     );
   }
@@ -4197,6 +6179,23 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     );
   }
 
+  @protected
+  void sse_encode_StreamSink_kv_event_record_Sse(
+    RustStreamSink<KVEventRecord> self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(
+      self.setupAndSerialize(
+        codec: SseCodec(
+          decodeSuccessData: sse_decode_kv_event_record,
+          decodeErrorData: sse_decode_AnyhowException,
+        ),
+      ),
+      serializer,
+    );
+  }
+
   @protected
   void sse_encode_String(String self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -4209,12 +6208,36 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     serializer.buffer.putUint8(self ? 1 : 0);
   }
 
+  @protected
+  void sse_encode_box_autoadd_f_64(double self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_f_64(self, serializer);
+  }
+
   @protected
   void sse_encode_box_autoadd_flags_c(FlagsC self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
     sse_encode_flags_c(self, serializer);
   }
 
+  @protected
+  void sse_encode_box_autoadd_network_profile(
+    NetworkProfile self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_network_profile(self, serializer);
+  }
+
+  @protected
+  void sse_encode_box_autoadd_profile_vpn_portal(
+    ProfileVpnPortal self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_profile_vpn_portal(self, serializer);
+  }
+
   @protected
   void sse_encode_box_autoadd_protocol(
     Protocol self,
@@ -4297,20 +6320,199 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     sse_encode_bool(self.enableQuicProxy, serializer);
     sse_encode_bool(self.disableQuicInput, serializer);
     sse_encode_bool(self.disableSymHolePunching, serializer);
+    sse_encode_bool(self.enableRelayForeignNetworkKcp, serializer);
+    sse_encode_u_64(self.foreignRelayBpsLimit, serializer);
+    sse_encode_u_32(self.multiThreadCount, serializer);
+    sse_encode_String(self.encryptionAlgorithm, serializer);
+    sse_encode_bool(self.enableMultipathBonding, serializer);
+  }
+
+  @protected
+  void sse_encode_forward(Forward self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.bindAddr, serializer);
+    sse_encode_String(self.dstAddr, serializer);
+    sse_encode_String(self.proto, serializer);
+  }
+
+  @protected
+  void sse_encode_i_32(int self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    serializer.buffer.putInt32(self);
+  }
+
+  @protected
+  void sse_encode_i_64(PlatformInt64 self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    serializer.buffer.putPlatformInt64(self);
+  }
+
+  @protected
+  void sse_encode_kv_event(KVEvent self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    switch (self) {
+      case KVEvent_TunDeviceReady(devName: final devName):
+        sse_encode_i_32(0, serializer);
+        sse_encode_String(devName, serializer);
+      case KVEvent_TunDeviceError(error: final error):
+        sse_encode_i_32(1, serializer);
+        sse_encode_String(error, serializer);
+      case KVEvent_PeerAdded(peerId: final peerId):
+        sse_encode_i_32(2, serializer);
+        sse_encode_u_32(peerId, serializer);
+      case KVEvent_PeerRemoved(peerId: final peerId):
+        sse_encode_i_32(3, serializer);
+        sse_encode_u_32(peerId, serializer);
+      case KVEvent_PeerConnAdded(conn: final conn):
+        sse_encode_i_32(4, serializer);
+        sse_encode_kv_peer_conn(conn, serializer);
+      case KVEvent_PeerConnRemoved(conn: final conn):
+        sse_encode_i_32(5, serializer);
+        sse_encode_kv_peer_conn(conn, serializer);
+      case KVEvent_ListenerAdded(url: final url):
+        sse_encode_i_32(6, serializer);
+        sse_encode_String(url, serializer);
+      case KVEvent_ListenerAddFailed(url: final url, error: final error):
+        sse_encode_i_32(7, serializer);
+        sse_encode_String(url, serializer);
+        sse_encode_String(error, serializer);
+      case KVEvent_ListenerAcceptFailed(url: final url, error: final error):
+        sse_encode_i_32(8, serializer);
+        sse_encode_String(url, serializer);
+        sse_encode_String(error, serializer);
+      case KVEvent_ConnectionAccepted(
+        localUrl: final localUrl,
+        remoteUrl: final remoteUrl,
+      ):
+        sse_encode_i_32(9, serializer);
+        sse_encode_String(localUrl, serializer);
+        sse_encode_String(remoteUrl, serializer);
+      case KVEvent_ConnectionError(
+        localUrl: final localUrl,
+        remoteUrl: final remoteUrl,
+        error: final error,
+      ):
+        sse_encode_i_32(10, serializer);
+        sse_encode_String(localUrl, serializer);
+        sse_encode_String(remoteUrl, serializer);
+        sse_encode_String(error, serializer);
+      case KVEvent_Connecting(url: final url):
+        sse_encode_i_32(11, serializer);
+        sse_encode_String(url, serializer);
+      case KVEvent_ConnectError(
+        dst: final dst,
+        ipVersion: final ipVersion,
+        error: final error,
+      ):
+        sse_encode_i_32(12, serializer);
+        sse_encode_String(dst, serializer);
+        sse_encode_String(ipVersion, serializer);
+        sse_encode_String(error, serializer);
+      case KVEvent_VpnPortalStarted(portal: final portal):
+        sse_encode_i_32(13, serializer);
+        sse_encode_String(portal, serializer);
+      case KVEvent_VpnPortalClientConnected(
+        portal: final portal,
+        clientAddr: final clientAddr,
+      ):
+        sse_encode_i_32(14, serializer);
+        sse_encode_String(portal, serializer);
+        sse_encode_String(clientAddr, serializer);
+      case KVEvent_VpnPortalClientDisconnected(
+        portal: final portal,
+        clientAddr: final clientAddr,
+      ):
+        sse_encode_i_32(15, serializer);
+        sse_encode_String(portal, serializer);
+        sse_encode_String(clientAddr, serializer);
+      case KVEvent_DhcpIpv4Changed(
+        oldCidr: final oldCidr,
+        newCidr: final newCidr,
+      ):
+        sse_encode_i_32(16, serializer);
+        sse_encode_opt_String(oldCidr, serializer);
+        sse_encode_opt_String(newCidr, serializer);
+      case KVEvent_DhcpIpv4Conflicted(cidr: final cidr):
+        sse_encode_i_32(17, serializer);
+        sse_encode_opt_String(cidr, serializer);
+      case KVEvent_PortForwardAdded(
+        bindAddr: final bindAddr,
+        dstAddr: final dstAddr,
+        proto: final proto,
+      ):
+        sse_encode_i_32(18, serializer);
+        sse_encode_String(bindAddr, serializer);
+        sse_encode_String(dstAddr, serializer);
+        sse_encode_String(proto, serializer);
+      case KVEvent_Lagged(missed: final missed):
+        sse_encode_i_32(19, serializer);
+        sse_encode_u_64(missed, serializer);
+      case KVEvent_Closed():
+        sse_encode_i_32(20, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_kv_event_kind(KVEventKind self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.index, serializer);
+  }
+
+  @protected
+  void sse_encode_kv_event_record(
+    KVEventRecord self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.instanceId, serializer);
+    sse_encode_i_64(self.timestampMs, serializer);
+    sse_encode_kv_event(self.event, serializer);
+  }
+
+  @protected
+  void sse_encode_kv_exit_flow(KVExitFlow self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.protocol, serializer);
+    sse_encode_String(self.src, serializer);
+    sse_encode_String(self.dst, serializer);
+    sse_encode_String(self.exitNode, serializer);
+    sse_encode_u_64(self.idleMs, serializer);
   }
 
   @protected
-  void sse_encode_forward(Forward self, SseSerializer serializer) {
+  void sse_encode_kv_exit_flow_info(
+    KVExitFlowInfo self,
+    SseSerializer serializer,
+  ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_String(self.bindAddr, serializer);
-    sse_encode_String(self.dstAddr, serializer);
-    sse_encode_String(self.proto, serializer);
+    sse_encode_list_kv_exit_node_status(self.exitNodes, serializer);
+    sse_encode_list_kv_exit_flow(self.flows, serializer);
+    sse_encode_u_64(self.totalFlows, serializer);
   }
 
   @protected
-  void sse_encode_i_32(int self, SseSerializer serializer) {
+  void sse_encode_kv_exit_node_status(
+    KVExitNodeStatus self,
+    SseSerializer serializer,
+  ) {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    serializer.buffer.putInt32(self);
+    sse_encode_String(self.exitNode, serializer);
+    sse_encode_bool(self.reachable, serializer);
+    sse_encode_bool(self.healthy, serializer);
+    sse_encode_opt_box_autoadd_f_64(self.rttMs, serializer);
+    sse_encode_u_32(self.consecutiveFailures, serializer);
+  }
+
+  @protected
+  void sse_encode_kv_instance_info(
+    KVInstanceInfo self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.instanceId, serializer);
+    sse_encode_String(self.networkName, serializer);
+    sse_encode_bool(self.running, serializer);
+    sse_encode_opt_String(self.errorMsg, serializer);
   }
 
   @protected
@@ -4354,6 +6556,32 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     sse_encode_i_32(self.cost, serializer);
   }
 
+  @protected
+  void sse_encode_kv_peer_conn(KVPeerConn self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.connId, serializer);
+    sse_encode_u_32(self.myPeerId, serializer);
+    sse_encode_u_32(self.peerId, serializer);
+    sse_encode_String(self.tunnelType, serializer);
+    sse_encode_String(self.localAddr, serializer);
+    sse_encode_String(self.remoteAddr, serializer);
+    sse_encode_bool(self.isClient, serializer);
+    sse_encode_String(self.networkName, serializer);
+  }
+
+  @protected
+  void sse_encode_kv_split_tunnel_status(
+    KVSplitTunnelStatus self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_bool(self.active, serializer);
+    sse_encode_String(self.instanceId, serializer);
+    sse_encode_String(self.devName, serializer);
+    sse_encode_split_tunnel_mode(self.mode, serializer);
+    sse_encode_u_32(self.appCount, serializer);
+  }
+
   @protected
   void
   sse_encode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -4404,6 +6632,54 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     }
   }
 
+  @protected
+  void sse_encode_list_kv_event_kind(
+    List<KVEventKind> self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_kv_event_kind(item, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_list_kv_exit_flow(
+    List<KVExitFlow> self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_kv_exit_flow(item, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_list_kv_exit_node_status(
+    List<KVExitNodeStatus> self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_kv_exit_node_status(item, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_list_kv_instance_info(
+    List<KVInstanceInfo> self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_kv_instance_info(item, serializer);
+    }
+  }
+
   @protected
   void sse_encode_list_kv_node_connection_stats(
     List<KVNodeConnectionStats> self,
@@ -4470,6 +6746,42 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     serializer.buffer.putUint8List(self);
   }
 
+  @protected
+  void sse_encode_list_profile_change(
+    List<ProfileChange> self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_profile_change(item, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_list_profile_field_error(
+    List<ProfileFieldError> self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_profile_field_error(item, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_list_profile_proxy_network(
+    List<ProfileProxyNetwork> self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_profile_proxy_network(item, serializer);
+    }
+  }
+
   @protected
   void sse_encode_list_record_string_u_32(
     List<(String, int)> self,
@@ -4482,6 +6794,51 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     }
   }
 
+  @protected
+  void sse_encode_list_split_tunnel_rule(
+    List<SplitTunnelRule> self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_split_tunnel_rule(item, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_network_profile(
+    NetworkProfile self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_opt_String(self.instanceId, serializer);
+    sse_encode_String(self.instanceName, serializer);
+    sse_encode_opt_String(self.hostname, serializer);
+    sse_encode_String(self.networkName, serializer);
+    sse_encode_String(self.networkSecret, serializer);
+    sse_encode_bool(self.dhcp, serializer);
+    sse_encode_opt_String(self.ipv4, serializer);
+    sse_encode_opt_String(self.ipv6, serializer);
+    sse_encode_list_String(self.peers, serializer);
+    sse_encode_list_String(self.listeners, serializer);
+    sse_encode_list_String(self.mappedListeners, serializer);
+    sse_encode_list_profile_proxy_network(self.proxyNetworks, serializer);
+    sse_encode_list_String(self.exitNodes, serializer);
+    sse_encode_opt_list_String(self.routes, serializer);
+    sse_encode_opt_String(self.socks5Portal, serializer);
+    sse_encode_list_forward(self.portForwards, serializer);
+    sse_encode_opt_box_autoadd_profile_vpn_portal(self.vpnPortal, serializer);
+    sse_encode_opt_String(self.rpcPortal, serializer);
+    sse_encode_opt_list_String(self.rpcPortalWhitelist, serializer);
+    sse_encode_opt_list_String(self.stunServers, serializer);
+    sse_encode_opt_list_String(self.stunServersV6, serializer);
+    sse_encode_list_String(self.tcpWhitelist, serializer);
+    sse_encode_list_String(self.udpWhitelist, serializer);
+    sse_encode_opt_String(self.aclJson, serializer);
+    sse_encode_flags_c(self.flags, serializer);
+  }
+
   @protected
   void sse_encode_node_hop_stats(NodeHopStats self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -4501,6 +6858,29 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     }
   }
 
+  @protected
+  void sse_encode_opt_box_autoadd_f_64(double? self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    sse_encode_bool(self != null, serializer);
+    if (self != null) {
+      sse_encode_box_autoadd_f_64(self, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_opt_box_autoadd_profile_vpn_portal(
+    ProfileVpnPortal? self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    sse_encode_bool(self != null, serializer);
+    if (self != null) {
+      sse_encode_box_autoadd_profile_vpn_portal(self, serializer);
+    }
+  }
+
   @protected
   void sse_encode_opt_box_autoadd_protocol(
     Protocol? self,
@@ -4547,6 +6927,57 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     }
   }
 
+  @protected
+  void sse_encode_opt_list_String(
+    List<String>? self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    sse_encode_bool(self != null, serializer);
+    if (self != null) {
+      sse_encode_list_String(self, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_profile_change(ProfileChange self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.field, serializer);
+    sse_encode_opt_String(self.oldValue, serializer);
+    sse_encode_opt_String(self.newValue, serializer);
+  }
+
+  @protected
+  void sse_encode_profile_field_error(
+    ProfileFieldError self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.field, serializer);
+    sse_encode_String(self.message, serializer);
+  }
+
+  @protected
+  void sse_encode_profile_proxy_network(
+    ProfileProxyNetwork self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.cidr, serializer);
+    sse_encode_opt_String(self.mappedCidr, serializer);
+  }
+
+  @protected
+  void sse_encode_profile_vpn_portal(
+    ProfileVpnPortal self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.clientCidr, serializer);
+    sse_encode_String(self.wireguardListen, serializer);
+  }
+
   @protected
   void sse_encode_protocol(Protocol self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -4570,6 +7001,25 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     sse_encode_u_16(self.$2, serializer);
   }
 
+  @protected
+  void sse_encode_split_tunnel_mode(
+    SplitTunnelMode self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.index, serializer);
+  }
+
+  @protected
+  void sse_encode_split_tunnel_rule(
+    SplitTunnelRule self,
+    SseSerializer serializer,
+  ) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.appPath, serializer);
+    sse_encode_bool(self.enabled, serializer);
+  }
+
   @protected
   void sse_encode_u_16(int self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -4606,31 +7056,6 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
   }
 }
 
-@sealed
-class EventBusSubscriberImpl extends RustOpaque implements EventBusSubscriber {
-  // Not to be used by end users
-  EventBusSubscriberImpl.frbInternalDcoDecode(List<dynamic> wire)
-    : super.frbInternalDcoDecode(wire, _kStaticData);
-
-  // Not to be used by end users
-  EventBusSubscriberImpl.frbInternalSseDecode(
-    BigInt ptr,
-    int externalSizeOnNative,
-  ) : super.frbInternalSseDecode(ptr, externalSizeOnNative, _kStaticData);
-
-  static final _kStaticData = RustArcStaticData(
-    rustArcIncrementStrongCount:
-        RustLib.instance.api.rust_arc_increment_strong_count_EventBusSubscriber,
-    rustArcDecrementStrongCount:
-        RustLib.instance.api.rust_arc_decrement_strong_count_EventBusSubscriber,
-    rustArcDecrementStrongCountPtr:
-        RustLib
-            .instance
-            .api
-            .rust_arc_decrement_strong_count_EventBusSubscriberPtr,
-  );
-}
-
 @sealed
 class FilterRuleImpl extends RustOpaque implements FilterRule {
   // Not to be used by end users
@@ -4844,34 +7269,112 @@ class IpNetworkImpl extends RustOpaque implements IpNetwork {
 }
 
 @sealed
-class JoinHandleImpl extends RustOpaque implements JoinHandle {
+class JoinHandleResultKvConfigApplyReportStringImpl extends RustOpaque
+    implements JoinHandleResultKvConfigApplyReportString {
+  // Not to be used by end users
+  JoinHandleResultKvConfigApplyReportStringImpl.frbInternalDcoDecode(
+    List<dynamic> wire,
+  ) : super.frbInternalDcoDecode(wire, _kStaticData);
+
+  // Not to be used by end users
+  JoinHandleResultKvConfigApplyReportStringImpl.frbInternalSseDecode(
+    BigInt ptr,
+    int externalSizeOnNative,
+  ) : super.frbInternalSseDecode(ptr, externalSizeOnNative, _kStaticData);
+
+  static final _kStaticData = RustArcStaticData(
+    rustArcIncrementStrongCount:
+        RustLib
+            .instance
+            .api
+            .rust_arc_increment_strong_count_JoinHandleResultKvConfigApplyReportString,
+    rustArcDecrementStrongCount:
+        RustLib
+            .instance
+            .api
+            .rust_arc_decrement_strong_count_JoinHandleResultKvConfigApplyReportString,
+    rustArcDecrementStrongCountPtr:
+        RustLib
+            .instance
+            .api
+            .rust_arc_decrement_strong_count_JoinHandleResultKvConfigApplyReportStringPtr,
+  );
+}
+
+@sealed
+class JoinHandleResultKvShutdownReportStringImpl extends RustOpaque
+    implements JoinHandleResultKvShutdownReportString {
+  // Not to be used by end users
+  JoinHandleResultKvShutdownReportStringImpl.frbInternalDcoDecode(
+    List<dynamic> wire,
+  ) : super.frbInternalDcoDecode(wire, _kStaticData);
+
+  // Not to be used by end users
+  JoinHandleResultKvShutdownReportStringImpl.frbInternalSseDecode(
+    BigInt ptr,
+    int externalSizeOnNative,
+  ) : super.frbInternalSseDecode(ptr, externalSizeOnNative, _kStaticData);
+
+  static final _kStaticData = RustArcStaticData(
+    rustArcIncrementStrongCount:
+        RustLib
+            .instance
+            .api
+            .rust_arc_increment_strong_count_JoinHandleResultKvShutdownReportString,
+    rustArcDecrementStrongCount:
+        RustLib
+            .instance
+            .api
+            .rust_arc_decrement_strong_count_JoinHandleResultKvShutdownReportString,
+    rustArcDecrementStrongCountPtr:
+        RustLib
+            .instance
+            .api
+            .rust_arc_decrement_strong_count_JoinHandleResultKvShutdownReportStringPtr,
+  );
+}
+
+@sealed
+class JoinHandleResultStringStringImpl extends RustOpaque
+    implements JoinHandleResultStringString {
   // Not to be used by end users
-  JoinHandleImpl.frbInternalDcoDecode(List<dynamic> wire)
+  JoinHandleResultStringStringImpl.frbInternalDcoDecode(List<dynamic> wire)
     : super.frbInternalDcoDecode(wire, _kStaticData);
 
   // Not to be used by end users
-  JoinHandleImpl.frbInternalSseDecode(BigInt ptr, int externalSizeOnNative)
-    : super.frbInternalSseDecode(ptr, externalSizeOnNative, _kStaticData);
+  JoinHandleResultStringStringImpl.frbInternalSseDecode(
+    BigInt ptr,
+    int externalSizeOnNative,
+  ) : super.frbInternalSseDecode(ptr, externalSizeOnNative, _kStaticData);
 
   static final _kStaticData = RustArcStaticData(
     rustArcIncrementStrongCount:
-        RustLib.instance.api.rust_arc_increment_strong_count_JoinHandle,
+        RustLib
+            .instance
+            .api
+            .rust_arc_increment_strong_count_JoinHandleResultStringString,
     rustArcDecrementStrongCount:
-        RustLib.instance.api.rust_arc_decrement_strong_count_JoinHandle,
+        RustLib
+            .instance
+            .api
+            .rust_arc_decrement_strong_count_JoinHandleResultStringString,
     rustArcDecrementStrongCountPtr:
-        RustLib.instance.api.rust_arc_decrement_strong_count_JoinHandlePtr,
+        RustLib
+            .instance
+            .api
+            .rust_arc_decrement_strong_count_JoinHandleResultStringStringPtr,
   );
 }
 
 @sealed
-class JoinHandleResultStringImpl extends RustOpaque
-    implements JoinHandleResultString {
+class JoinHandleVecKvShutdownReportImpl extends RustOpaque
+    implements JoinHandleVecKvShutdownReport {
   // Not to be used by end users
-  JoinHandleResultStringImpl.frbInternalDcoDecode(List<dynamic> wire)
+  JoinHandleVecKvShutdownReportImpl.frbInternalDcoDecode(List<dynamic> wire)
     : super.frbInternalDcoDecode(wire, _kStaticData);
 
   // Not to be used by end users
-  JoinHandleResultStringImpl.frbInternalSseDecode(
+  JoinHandleVecKvShutdownReportImpl.frbInternalSseDecode(
     BigInt ptr,
     int externalSizeOnNative,
   ) : super.frbInternalSseDecode(ptr, externalSizeOnNative, _kStaticData);
@@ -4881,17 +7384,17 @@ class JoinHandleResultStringImpl extends RustOpaque
         RustLib
             .instance
             .api
-            .rust_arc_increment_strong_count_JoinHandleResultString,
+            .rust_arc_increment_strong_count_JoinHandleVecKvShutdownReport,
     rustArcDecrementStrongCount:
         RustLib
             .instance
             .api
-            .rust_arc_decrement_strong_count_JoinHandleResultString,
+            .rust_arc_decrement_strong_count_JoinHandleVecKvShutdownReport,
     rustArcDecrementStrongCountPtr:
         RustLib
             .instance
             .api
-            .rust_arc_decrement_strong_count_JoinHandleResultStringPtr,
+            .rust_arc_decrement_strong_count_JoinHandleVecKvShutdownReportPtr,
   );
 }
 
@@ -4948,6 +7451,9 @@ class WfpControllerImpl extends RustOpaque implements WfpController {
         filterIds: filterIds,
       );
 
+  Future<String> backendName() => RustLib.instance.api
+      .crateApiAstralWfpWfpControllerBackendName(that: this);
+
   Future<void> cleanup() =>
       RustLib.instance.api.crateApiAstralWfpWfpControllerCleanup(that: this);
 
@@ -4957,6 +7463,9 @@ class WfpControllerImpl extends RustOpaque implements WfpController {
         filterIds: filterIds,
       );
 
+  Future<int> deleteRule({required String name}) => RustLib.instance.api
+      .crateApiAstralWfpWfpControllerDeleteRule(that: this, name: name);
+
   Future<void> getLayerName({required Guid layerKey}) =>
       RustLib.instance.api.crateApiAstralWfpWfpControllerGetLayerName(
         that: this,
@@ -4965,4 +7474,13 @@ class WfpControllerImpl extends RustOpaque implements WfpController {
 
   Future<void> initialize() =>
       RustLib.instance.api.crateApiAstralWfpWfpControllerInitialize(that: this);
+
+  Future<List<FilterRule>> listRules() =>
+      RustLib.instance.api.crateApiAstralWfpWfpControllerListRules(that: this);
+
+  Future<Uint64List> loadRules({required String path}) => RustLib.instance.api
+      .crateApiAstralWfpWfpControllerLoadRules(that: this, path: path);
+
+  Future<void> saveRules({required String path}) => RustLib.instance.api
+      .crateApiAstralWfpWfpControllerSaveRules(that: this, path: path);
 }
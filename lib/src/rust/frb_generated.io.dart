@@ -4,10 +4,13 @@
 // ignore_for_file: unused_import, unused_element, unnecessary_import, duplicate_ignore, invalid_use_of_internal_member, annotate_overrides, non_constant_identifier_names, curly_braces_in_flow_control_structures, prefer_const_literals_to_create_immutables, unused_field
 
 import 'api/astral_wfp.dart';
+import 'api/event.dart';
 import 'api/firewall.dart';
 import 'api/hops.dart';
 import 'api/nt.dart';
+import 'api/profile.dart';
 import 'api/simple.dart';
+import 'api/split_tunnel.dart';
 import 'api/utils.dart';
 import 'dart:async';
 import 'dart:convert';
@@ -24,10 +27,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     required super.portManager,
   });
 
-  CrossPlatformFinalizerArg
-  get rust_arc_decrement_strong_count_EventBusSubscriberPtr =>
-      wire._rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriberPtr;
-
   CrossPlatformFinalizerArg get rust_arc_decrement_strong_count_FilterRulePtr =>
       wire._rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRulePtr;
 
@@ -40,12 +39,21 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   CrossPlatformFinalizerArg get rust_arc_decrement_strong_count_IpNetworkPtr =>
       wire._rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerIpNetworkPtr;
 
-  CrossPlatformFinalizerArg get rust_arc_decrement_strong_count_JoinHandlePtr =>
-      wire._rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandlePtr;
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_JoinHandleResultKvConfigApplyReportStringPtr =>
+      wire._rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportStringPtr;
+
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_JoinHandleResultKvShutdownReportStringPtr =>
+      wire._rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportStringPtr;
+
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_JoinHandleResultStringStringPtr =>
+      wire._rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringStringPtr;
 
   CrossPlatformFinalizerArg
-  get rust_arc_decrement_strong_count_JoinHandleResultStringPtr =>
-      wire._rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringPtr;
+  get rust_arc_decrement_strong_count_JoinHandleVecKvShutdownReportPtr =>
+      wire._rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReportPtr;
 
   CrossPlatformFinalizerArg
   get rust_arc_decrement_strong_count_PeerRoutePairPtr =>
@@ -58,12 +66,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   AnyhowException dco_decode_AnyhowException(dynamic raw);
 
-  @protected
-  EventBusSubscriber
-  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    dynamic raw,
-  );
-
   @protected
   FilterRule
   dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -83,14 +85,26 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   );
 
   @protected
-  JoinHandle
-  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  JoinHandleResultKvConfigApplyReportString
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    dynamic raw,
+  );
+
+  @protected
+  JoinHandleResultKvShutdownReportString
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
     dynamic raw,
   );
 
   @protected
-  JoinHandleResultString
-  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  JoinHandleResultStringString
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    dynamic raw,
+  );
+
+  @protected
+  JoinHandleVecKvShutdownReport
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     dynamic raw,
   );
 
@@ -148,12 +162,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     dynamic raw,
   );
 
-  @protected
-  EventBusSubscriber
-  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    dynamic raw,
-  );
-
   @protected
   FilterRule
   dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -179,14 +187,26 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   );
 
   @protected
-  JoinHandle
-  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  JoinHandleResultKvConfigApplyReportString
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    dynamic raw,
+  );
+
+  @protected
+  JoinHandleResultKvShutdownReportString
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
     dynamic raw,
   );
 
   @protected
-  JoinHandleResultString
-  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  JoinHandleResultStringString
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    dynamic raw,
+  );
+
+  @protected
+  JoinHandleVecKvShutdownReport
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     dynamic raw,
   );
 
@@ -202,15 +222,29 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     dynamic raw,
   );
 
+  @protected
+  RustStreamSink<KVEventRecord> dco_decode_StreamSink_kv_event_record_Sse(
+    dynamic raw,
+  );
+
   @protected
   String dco_decode_String(dynamic raw);
 
   @protected
   bool dco_decode_bool(dynamic raw);
 
+  @protected
+  double dco_decode_box_autoadd_f_64(dynamic raw);
+
   @protected
   FlagsC dco_decode_box_autoadd_flags_c(dynamic raw);
 
+  @protected
+  NetworkProfile dco_decode_box_autoadd_network_profile(dynamic raw);
+
+  @protected
+  ProfileVpnPortal dco_decode_box_autoadd_profile_vpn_portal(dynamic raw);
+
   @protected
   Protocol dco_decode_box_autoadd_protocol(dynamic raw);
 
@@ -244,6 +278,30 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   int dco_decode_i_32(dynamic raw);
 
+  @protected
+  PlatformInt64 dco_decode_i_64(dynamic raw);
+
+  @protected
+  KVEvent dco_decode_kv_event(dynamic raw);
+
+  @protected
+  KVEventKind dco_decode_kv_event_kind(dynamic raw);
+
+  @protected
+  KVEventRecord dco_decode_kv_event_record(dynamic raw);
+
+  @protected
+  KVExitFlow dco_decode_kv_exit_flow(dynamic raw);
+
+  @protected
+  KVExitFlowInfo dco_decode_kv_exit_flow_info(dynamic raw);
+
+  @protected
+  KVExitNodeStatus dco_decode_kv_exit_node_status(dynamic raw);
+
+  @protected
+  KVInstanceInfo dco_decode_kv_instance_info(dynamic raw);
+
   @protected
   KVNetworkStatus dco_decode_kv_network_status(dynamic raw);
 
@@ -253,6 +311,12 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   KVNodeInfo dco_decode_kv_node_info(dynamic raw);
 
+  @protected
+  KVPeerConn dco_decode_kv_peer_conn(dynamic raw);
+
+  @protected
+  KVSplitTunnelStatus dco_decode_kv_split_tunnel_status(dynamic raw);
+
   @protected
   List<FilterRule>
   dco_decode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -271,6 +335,18 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   List<Forward> dco_decode_list_forward(dynamic raw);
 
+  @protected
+  List<KVEventKind> dco_decode_list_kv_event_kind(dynamic raw);
+
+  @protected
+  List<KVExitFlow> dco_decode_list_kv_exit_flow(dynamic raw);
+
+  @protected
+  List<KVExitNodeStatus> dco_decode_list_kv_exit_node_status(dynamic raw);
+
+  @protected
+  List<KVInstanceInfo> dco_decode_list_kv_instance_info(dynamic raw);
+
   @protected
   List<KVNodeConnectionStats> dco_decode_list_kv_node_connection_stats(
     dynamic raw,
@@ -291,15 +367,36 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   Uint8List dco_decode_list_prim_u_8_strict(dynamic raw);
 
+  @protected
+  List<ProfileChange> dco_decode_list_profile_change(dynamic raw);
+
+  @protected
+  List<ProfileFieldError> dco_decode_list_profile_field_error(dynamic raw);
+
+  @protected
+  List<ProfileProxyNetwork> dco_decode_list_profile_proxy_network(dynamic raw);
+
   @protected
   List<(String, int)> dco_decode_list_record_string_u_32(dynamic raw);
 
+  @protected
+  List<SplitTunnelRule> dco_decode_list_split_tunnel_rule(dynamic raw);
+
+  @protected
+  NetworkProfile dco_decode_network_profile(dynamic raw);
+
   @protected
   NodeHopStats dco_decode_node_hop_stats(dynamic raw);
 
   @protected
   String? dco_decode_opt_String(dynamic raw);
 
+  @protected
+  double? dco_decode_opt_box_autoadd_f_64(dynamic raw);
+
+  @protected
+  ProfileVpnPortal? dco_decode_opt_box_autoadd_profile_vpn_portal(dynamic raw);
+
   @protected
   Protocol? dco_decode_opt_box_autoadd_protocol(dynamic raw);
 
@@ -312,6 +409,21 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   int? dco_decode_opt_box_autoadd_u_32(dynamic raw);
 
+  @protected
+  List<String>? dco_decode_opt_list_String(dynamic raw);
+
+  @protected
+  ProfileChange dco_decode_profile_change(dynamic raw);
+
+  @protected
+  ProfileFieldError dco_decode_profile_field_error(dynamic raw);
+
+  @protected
+  ProfileProxyNetwork dco_decode_profile_proxy_network(dynamic raw);
+
+  @protected
+  ProfileVpnPortal dco_decode_profile_vpn_portal(dynamic raw);
+
   @protected
   Protocol dco_decode_protocol(dynamic raw);
 
@@ -321,6 +433,12 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   (int, int) dco_decode_record_u_16_u_16(dynamic raw);
 
+  @protected
+  SplitTunnelMode dco_decode_split_tunnel_mode(dynamic raw);
+
+  @protected
+  SplitTunnelRule dco_decode_split_tunnel_rule(dynamic raw);
+
   @protected
   int dco_decode_u_16(dynamic raw);
 
@@ -342,12 +460,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   AnyhowException sse_decode_AnyhowException(SseDeserializer deserializer);
 
-  @protected
-  EventBusSubscriber
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    SseDeserializer deserializer,
-  );
-
   @protected
   FilterRule
   sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -367,14 +479,26 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   );
 
   @protected
-  JoinHandle
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  JoinHandleResultKvConfigApplyReportString
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  JoinHandleResultKvShutdownReportString
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
     SseDeserializer deserializer,
   );
 
   @protected
-  JoinHandleResultString
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  JoinHandleResultStringString
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  JoinHandleVecKvShutdownReport
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     SseDeserializer deserializer,
   );
 
@@ -432,12 +556,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseDeserializer deserializer,
   );
 
-  @protected
-  EventBusSubscriber
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    SseDeserializer deserializer,
-  );
-
   @protected
   FilterRule
   sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -463,14 +581,26 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   );
 
   @protected
-  JoinHandle
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  JoinHandleResultKvConfigApplyReportString
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  JoinHandleResultKvShutdownReportString
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
     SseDeserializer deserializer,
   );
 
   @protected
-  JoinHandleResultString
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  JoinHandleResultStringString
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  JoinHandleVecKvShutdownReport
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     SseDeserializer deserializer,
   );
 
@@ -486,15 +616,33 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseDeserializer deserializer,
   );
 
+  @protected
+  RustStreamSink<KVEventRecord> sse_decode_StreamSink_kv_event_record_Sse(
+    SseDeserializer deserializer,
+  );
+
   @protected
   String sse_decode_String(SseDeserializer deserializer);
 
   @protected
   bool sse_decode_bool(SseDeserializer deserializer);
 
+  @protected
+  double sse_decode_box_autoadd_f_64(SseDeserializer deserializer);
+
   @protected
   FlagsC sse_decode_box_autoadd_flags_c(SseDeserializer deserializer);
 
+  @protected
+  NetworkProfile sse_decode_box_autoadd_network_profile(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProfileVpnPortal sse_decode_box_autoadd_profile_vpn_portal(
+    SseDeserializer deserializer,
+  );
+
   @protected
   Protocol sse_decode_box_autoadd_protocol(SseDeserializer deserializer);
 
@@ -530,6 +678,30 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   int sse_decode_i_32(SseDeserializer deserializer);
 
+  @protected
+  PlatformInt64 sse_decode_i_64(SseDeserializer deserializer);
+
+  @protected
+  KVEvent sse_decode_kv_event(SseDeserializer deserializer);
+
+  @protected
+  KVEventKind sse_decode_kv_event_kind(SseDeserializer deserializer);
+
+  @protected
+  KVEventRecord sse_decode_kv_event_record(SseDeserializer deserializer);
+
+  @protected
+  KVExitFlow sse_decode_kv_exit_flow(SseDeserializer deserializer);
+
+  @protected
+  KVExitFlowInfo sse_decode_kv_exit_flow_info(SseDeserializer deserializer);
+
+  @protected
+  KVExitNodeStatus sse_decode_kv_exit_node_status(SseDeserializer deserializer);
+
+  @protected
+  KVInstanceInfo sse_decode_kv_instance_info(SseDeserializer deserializer);
+
   @protected
   KVNetworkStatus sse_decode_kv_network_status(SseDeserializer deserializer);
 
@@ -541,6 +713,14 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   KVNodeInfo sse_decode_kv_node_info(SseDeserializer deserializer);
 
+  @protected
+  KVPeerConn sse_decode_kv_peer_conn(SseDeserializer deserializer);
+
+  @protected
+  KVSplitTunnelStatus sse_decode_kv_split_tunnel_status(
+    SseDeserializer deserializer,
+  );
+
   @protected
   List<FilterRule>
   sse_decode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -559,6 +739,22 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   List<Forward> sse_decode_list_forward(SseDeserializer deserializer);
 
+  @protected
+  List<KVEventKind> sse_decode_list_kv_event_kind(SseDeserializer deserializer);
+
+  @protected
+  List<KVExitFlow> sse_decode_list_kv_exit_flow(SseDeserializer deserializer);
+
+  @protected
+  List<KVExitNodeStatus> sse_decode_list_kv_exit_node_status(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<KVInstanceInfo> sse_decode_list_kv_instance_info(
+    SseDeserializer deserializer,
+  );
+
   @protected
   List<KVNodeConnectionStats> sse_decode_list_kv_node_connection_stats(
     SseDeserializer deserializer,
@@ -581,17 +777,48 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   Uint8List sse_decode_list_prim_u_8_strict(SseDeserializer deserializer);
 
+  @protected
+  List<ProfileChange> sse_decode_list_profile_change(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<ProfileFieldError> sse_decode_list_profile_field_error(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<ProfileProxyNetwork> sse_decode_list_profile_proxy_network(
+    SseDeserializer deserializer,
+  );
+
   @protected
   List<(String, int)> sse_decode_list_record_string_u_32(
     SseDeserializer deserializer,
   );
 
+  @protected
+  List<SplitTunnelRule> sse_decode_list_split_tunnel_rule(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  NetworkProfile sse_decode_network_profile(SseDeserializer deserializer);
+
   @protected
   NodeHopStats sse_decode_node_hop_stats(SseDeserializer deserializer);
 
   @protected
   String? sse_decode_opt_String(SseDeserializer deserializer);
 
+  @protected
+  double? sse_decode_opt_box_autoadd_f_64(SseDeserializer deserializer);
+
+  @protected
+  ProfileVpnPortal? sse_decode_opt_box_autoadd_profile_vpn_portal(
+    SseDeserializer deserializer,
+  );
+
   @protected
   Protocol? sse_decode_opt_box_autoadd_protocol(SseDeserializer deserializer);
 
@@ -606,6 +833,25 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   int? sse_decode_opt_box_autoadd_u_32(SseDeserializer deserializer);
 
+  @protected
+  List<String>? sse_decode_opt_list_String(SseDeserializer deserializer);
+
+  @protected
+  ProfileChange sse_decode_profile_change(SseDeserializer deserializer);
+
+  @protected
+  ProfileFieldError sse_decode_profile_field_error(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProfileProxyNetwork sse_decode_profile_proxy_network(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProfileVpnPortal sse_decode_profile_vpn_portal(SseDeserializer deserializer);
+
   @protected
   Protocol sse_decode_protocol(SseDeserializer deserializer);
 
@@ -615,6 +861,12 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   (int, int) sse_decode_record_u_16_u_16(SseDeserializer deserializer);
 
+  @protected
+  SplitTunnelMode sse_decode_split_tunnel_mode(SseDeserializer deserializer);
+
+  @protected
+  SplitTunnelRule sse_decode_split_tunnel_rule(SseDeserializer deserializer);
+
   @protected
   int sse_decode_u_16(SseDeserializer deserializer);
 
@@ -639,13 +891,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
-  @protected
-  void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    EventBusSubscriber self,
-    SseSerializer serializer,
-  );
-
   @protected
   void
   sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -669,15 +914,29 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
 
   @protected
   void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
-    JoinHandle self,
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    JoinHandleResultKvConfigApplyReportString self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    JoinHandleResultKvShutdownReportString self,
     SseSerializer serializer,
   );
 
   @protected
   void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
-    JoinHandleResultString self,
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    JoinHandleResultStringString self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
+    JoinHandleVecKvShutdownReport self,
     SseSerializer serializer,
   );
 
@@ -744,13 +1003,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
-  @protected
-  void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    EventBusSubscriber self,
-    SseSerializer serializer,
-  );
-
   @protected
   void
   sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -781,15 +1033,29 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
 
   @protected
   void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
-    JoinHandle self,
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    JoinHandleResultKvConfigApplyReportString self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    JoinHandleResultKvShutdownReportString self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    JoinHandleResultStringString self,
     SseSerializer serializer,
   );
 
   @protected
   void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
-    JoinHandleResultString self,
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
+    JoinHandleVecKvShutdownReport self,
     SseSerializer serializer,
   );
 
@@ -807,15 +1073,36 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_StreamSink_kv_event_record_Sse(
+    RustStreamSink<KVEventRecord> self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_String(String self, SseSerializer serializer);
 
   @protected
   void sse_encode_bool(bool self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_box_autoadd_f_64(double self, SseSerializer serializer);
+
   @protected
   void sse_encode_box_autoadd_flags_c(FlagsC self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_box_autoadd_network_profile(
+    NetworkProfile self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_profile_vpn_portal(
+    ProfileVpnPortal self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_box_autoadd_protocol(Protocol self, SseSerializer serializer);
 
@@ -852,6 +1139,39 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   void sse_encode_i_32(int self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_i_64(PlatformInt64 self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_kv_event(KVEvent self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_kv_event_kind(KVEventKind self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_kv_event_record(KVEventRecord self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_kv_exit_flow(KVExitFlow self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_kv_exit_flow_info(
+    KVExitFlowInfo self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_kv_exit_node_status(
+    KVExitNodeStatus self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_kv_instance_info(
+    KVInstanceInfo self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_kv_network_status(
     KVNetworkStatus self,
@@ -867,6 +1187,15 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   void sse_encode_kv_node_info(KVNodeInfo self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_kv_peer_conn(KVPeerConn self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_kv_split_tunnel_status(
+    KVSplitTunnelStatus self,
+    SseSerializer serializer,
+  );
+
   @protected
   void
   sse_encode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -887,6 +1216,30 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   void sse_encode_list_forward(List<Forward> self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_list_kv_event_kind(
+    List<KVEventKind> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_kv_exit_flow(
+    List<KVExitFlow> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_kv_exit_node_status(
+    List<KVExitNodeStatus> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_kv_instance_info(
+    List<KVInstanceInfo> self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_list_kv_node_connection_stats(
     List<KVNodeConnectionStats> self,
@@ -923,18 +1276,57 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_list_profile_change(
+    List<ProfileChange> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_profile_field_error(
+    List<ProfileFieldError> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_profile_proxy_network(
+    List<ProfileProxyNetwork> self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_list_record_string_u_32(
     List<(String, int)> self,
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_list_split_tunnel_rule(
+    List<SplitTunnelRule> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_network_profile(
+    NetworkProfile self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_node_hop_stats(NodeHopStats self, SseSerializer serializer);
 
   @protected
   void sse_encode_opt_String(String? self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_opt_box_autoadd_f_64(double? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_box_autoadd_profile_vpn_portal(
+    ProfileVpnPortal? self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_opt_box_autoadd_protocol(
     Protocol? self,
@@ -953,6 +1345,30 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   void sse_encode_opt_box_autoadd_u_32(int? self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_opt_list_String(List<String>? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_profile_change(ProfileChange self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_profile_field_error(
+    ProfileFieldError self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_profile_proxy_network(
+    ProfileProxyNetwork self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_profile_vpn_portal(
+    ProfileVpnPortal self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_protocol(Protocol self, SseSerializer serializer);
 
@@ -965,6 +1381,18 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   void sse_encode_record_u_16_u_16((int, int) self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_split_tunnel_mode(
+    SplitTunnelMode self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_split_tunnel_rule(
+    SplitTunnelRule self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_u_16(int self, SseSerializer serializer);
 
@@ -998,40 +1426,6 @@ class RustLibWire implements BaseWire {
   RustLibWire(ffi.DynamicLibrary dynamicLibrary)
     : _lookup = dynamicLibrary.lookup;
 
-  void
-  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    ffi.Pointer<ffi.Void> ptr,
-  ) {
-    return _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-      ptr,
-    );
-  }
-
-  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriberPtr =
-      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<ffi.Void>)>>(
-        'frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber',
-      );
-  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber =
-      _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriberPtr
-          .asFunction<void Function(ffi.Pointer<ffi.Void>)>();
-
-  void
-  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    ffi.Pointer<ffi.Void> ptr,
-  ) {
-    return _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-      ptr,
-    );
-  }
-
-  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriberPtr =
-      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<ffi.Void>)>>(
-        'frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber',
-      );
-  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber =
-      _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriberPtr
-          .asFunction<void Function(ffi.Pointer<ffi.Void>)>();
-
   void
   rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
     ffi.Pointer<ffi.Void> ptr,
@@ -1169,71 +1563,139 @@ class RustLibWire implements BaseWire {
           .asFunction<void Function(ffi.Pointer<ffi.Void>)>();
 
   void
-  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    ffi.Pointer<ffi.Void> ptr,
+  ) {
+    return _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+      ptr,
+    );
+  }
+
+  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportStringPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<ffi.Void>)>>(
+        'frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString',
+      );
+  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString =
+      _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportStringPtr
+          .asFunction<void Function(ffi.Pointer<ffi.Void>)>();
+
+  void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    ffi.Pointer<ffi.Void> ptr,
+  ) {
+    return _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+      ptr,
+    );
+  }
+
+  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportStringPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<ffi.Void>)>>(
+        'frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString',
+      );
+  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString =
+      _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportStringPtr
+          .asFunction<void Function(ffi.Pointer<ffi.Void>)>();
+
+  void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    ffi.Pointer<ffi.Void> ptr,
+  ) {
+    return _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+      ptr,
+    );
+  }
+
+  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportStringPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<ffi.Void>)>>(
+        'frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString',
+      );
+  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString =
+      _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportStringPtr
+          .asFunction<void Function(ffi.Pointer<ffi.Void>)>();
+
+  void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    ffi.Pointer<ffi.Void> ptr,
+  ) {
+    return _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+      ptr,
+    );
+  }
+
+  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportStringPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<ffi.Void>)>>(
+        'frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString',
+      );
+  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString =
+      _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportStringPtr
+          .asFunction<void Function(ffi.Pointer<ffi.Void>)>();
+
+  void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
     ffi.Pointer<ffi.Void> ptr,
   ) {
-    return _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+    return _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
       ptr,
     );
   }
 
-  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandlePtr =
+  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringStringPtr =
       _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<ffi.Void>)>>(
-        'frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle',
+        'frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString',
       );
-  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle =
-      _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandlePtr
+  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString =
+      _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringStringPtr
           .asFunction<void Function(ffi.Pointer<ffi.Void>)>();
 
   void
-  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
     ffi.Pointer<ffi.Void> ptr,
   ) {
-    return _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+    return _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
       ptr,
     );
   }
 
-  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandlePtr =
+  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringStringPtr =
       _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<ffi.Void>)>>(
-        'frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle',
+        'frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString',
       );
-  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle =
-      _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandlePtr
+  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString =
+      _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringStringPtr
           .asFunction<void Function(ffi.Pointer<ffi.Void>)>();
 
   void
-  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     ffi.Pointer<ffi.Void> ptr,
   ) {
-    return _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+    return _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
       ptr,
     );
   }
 
-  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringPtr =
+  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReportPtr =
       _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<ffi.Void>)>>(
-        'frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString',
+        'frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport',
       );
-  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString =
-      _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringPtr
+  late final _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport =
+      _rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReportPtr
           .asFunction<void Function(ffi.Pointer<ffi.Void>)>();
 
   void
-  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     ffi.Pointer<ffi.Void> ptr,
   ) {
-    return _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+    return _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
       ptr,
     );
   }
 
-  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringPtr =
+  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReportPtr =
       _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<ffi.Void>)>>(
-        'frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString',
+        'frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport',
       );
-  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString =
-      _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringPtr
+  late final _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport =
+      _rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReportPtr
           .asFunction<void Function(ffi.Pointer<ffi.Void>)>();
 
   void
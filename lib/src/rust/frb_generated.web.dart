@@ -7,10 +7,13 @@
 // ignore_for_file: argument_type_not_assignable
 
 import 'api/astral_wfp.dart';
+import 'api/event.dart';
 import 'api/firewall.dart';
 import 'api/hops.dart';
 import 'api/nt.dart';
+import 'api/profile.dart';
 import 'api/simple.dart';
+import 'api/split_tunnel.dart';
 import 'api/utils.dart';
 import 'dart:async';
 import 'dart:convert';
@@ -26,10 +29,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     required super.portManager,
   });
 
-  CrossPlatformFinalizerArg
-  get rust_arc_decrement_strong_count_EventBusSubscriberPtr =>
-      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber;
-
   CrossPlatformFinalizerArg get rust_arc_decrement_strong_count_FilterRulePtr =>
       wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule;
 
@@ -42,12 +41,21 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   CrossPlatformFinalizerArg get rust_arc_decrement_strong_count_IpNetworkPtr =>
       wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerIpNetwork;
 
-  CrossPlatformFinalizerArg get rust_arc_decrement_strong_count_JoinHandlePtr =>
-      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle;
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_JoinHandleResultKvConfigApplyReportStringPtr =>
+      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString;
+
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_JoinHandleResultKvShutdownReportStringPtr =>
+      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString;
+
+  CrossPlatformFinalizerArg
+  get rust_arc_decrement_strong_count_JoinHandleResultStringStringPtr =>
+      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString;
 
   CrossPlatformFinalizerArg
-  get rust_arc_decrement_strong_count_JoinHandleResultStringPtr =>
-      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString;
+  get rust_arc_decrement_strong_count_JoinHandleVecKvShutdownReportPtr =>
+      wire.rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport;
 
   CrossPlatformFinalizerArg
   get rust_arc_decrement_strong_count_PeerRoutePairPtr =>
@@ -60,12 +68,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   AnyhowException dco_decode_AnyhowException(dynamic raw);
 
-  @protected
-  EventBusSubscriber
-  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    dynamic raw,
-  );
-
   @protected
   FilterRule
   dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -85,14 +87,26 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   );
 
   @protected
-  JoinHandle
-  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  JoinHandleResultKvConfigApplyReportString
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    dynamic raw,
+  );
+
+  @protected
+  JoinHandleResultKvShutdownReportString
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
     dynamic raw,
   );
 
   @protected
-  JoinHandleResultString
-  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  JoinHandleResultStringString
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    dynamic raw,
+  );
+
+  @protected
+  JoinHandleVecKvShutdownReport
+  dco_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     dynamic raw,
   );
 
@@ -150,12 +164,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     dynamic raw,
   );
 
-  @protected
-  EventBusSubscriber
-  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    dynamic raw,
-  );
-
   @protected
   FilterRule
   dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -181,14 +189,26 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   );
 
   @protected
-  JoinHandle
-  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  JoinHandleResultKvConfigApplyReportString
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
     dynamic raw,
   );
 
   @protected
-  JoinHandleResultString
-  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  JoinHandleResultKvShutdownReportString
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    dynamic raw,
+  );
+
+  @protected
+  JoinHandleResultStringString
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    dynamic raw,
+  );
+
+  @protected
+  JoinHandleVecKvShutdownReport
+  dco_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     dynamic raw,
   );
 
@@ -204,15 +224,29 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     dynamic raw,
   );
 
+  @protected
+  RustStreamSink<KVEventRecord> dco_decode_StreamSink_kv_event_record_Sse(
+    dynamic raw,
+  );
+
   @protected
   String dco_decode_String(dynamic raw);
 
   @protected
   bool dco_decode_bool(dynamic raw);
 
+  @protected
+  double dco_decode_box_autoadd_f_64(dynamic raw);
+
   @protected
   FlagsC dco_decode_box_autoadd_flags_c(dynamic raw);
 
+  @protected
+  NetworkProfile dco_decode_box_autoadd_network_profile(dynamic raw);
+
+  @protected
+  ProfileVpnPortal dco_decode_box_autoadd_profile_vpn_portal(dynamic raw);
+
   @protected
   Protocol dco_decode_box_autoadd_protocol(dynamic raw);
 
@@ -246,6 +280,30 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   int dco_decode_i_32(dynamic raw);
 
+  @protected
+  PlatformInt64 dco_decode_i_64(dynamic raw);
+
+  @protected
+  KVEvent dco_decode_kv_event(dynamic raw);
+
+  @protected
+  KVEventKind dco_decode_kv_event_kind(dynamic raw);
+
+  @protected
+  KVEventRecord dco_decode_kv_event_record(dynamic raw);
+
+  @protected
+  KVExitFlow dco_decode_kv_exit_flow(dynamic raw);
+
+  @protected
+  KVExitFlowInfo dco_decode_kv_exit_flow_info(dynamic raw);
+
+  @protected
+  KVExitNodeStatus dco_decode_kv_exit_node_status(dynamic raw);
+
+  @protected
+  KVInstanceInfo dco_decode_kv_instance_info(dynamic raw);
+
   @protected
   KVNetworkStatus dco_decode_kv_network_status(dynamic raw);
 
@@ -255,6 +313,12 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   KVNodeInfo dco_decode_kv_node_info(dynamic raw);
 
+  @protected
+  KVPeerConn dco_decode_kv_peer_conn(dynamic raw);
+
+  @protected
+  KVSplitTunnelStatus dco_decode_kv_split_tunnel_status(dynamic raw);
+
   @protected
   List<FilterRule>
   dco_decode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -273,6 +337,18 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   List<Forward> dco_decode_list_forward(dynamic raw);
 
+  @protected
+  List<KVEventKind> dco_decode_list_kv_event_kind(dynamic raw);
+
+  @protected
+  List<KVExitFlow> dco_decode_list_kv_exit_flow(dynamic raw);
+
+  @protected
+  List<KVExitNodeStatus> dco_decode_list_kv_exit_node_status(dynamic raw);
+
+  @protected
+  List<KVInstanceInfo> dco_decode_list_kv_instance_info(dynamic raw);
+
   @protected
   List<KVNodeConnectionStats> dco_decode_list_kv_node_connection_stats(
     dynamic raw,
@@ -293,15 +369,36 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   Uint8List dco_decode_list_prim_u_8_strict(dynamic raw);
 
+  @protected
+  List<ProfileChange> dco_decode_list_profile_change(dynamic raw);
+
+  @protected
+  List<ProfileFieldError> dco_decode_list_profile_field_error(dynamic raw);
+
+  @protected
+  List<ProfileProxyNetwork> dco_decode_list_profile_proxy_network(dynamic raw);
+
   @protected
   List<(String, int)> dco_decode_list_record_string_u_32(dynamic raw);
 
+  @protected
+  List<SplitTunnelRule> dco_decode_list_split_tunnel_rule(dynamic raw);
+
+  @protected
+  NetworkProfile dco_decode_network_profile(dynamic raw);
+
   @protected
   NodeHopStats dco_decode_node_hop_stats(dynamic raw);
 
   @protected
   String? dco_decode_opt_String(dynamic raw);
 
+  @protected
+  double? dco_decode_opt_box_autoadd_f_64(dynamic raw);
+
+  @protected
+  ProfileVpnPortal? dco_decode_opt_box_autoadd_profile_vpn_portal(dynamic raw);
+
   @protected
   Protocol? dco_decode_opt_box_autoadd_protocol(dynamic raw);
 
@@ -314,6 +411,21 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   int? dco_decode_opt_box_autoadd_u_32(dynamic raw);
 
+  @protected
+  List<String>? dco_decode_opt_list_String(dynamic raw);
+
+  @protected
+  ProfileChange dco_decode_profile_change(dynamic raw);
+
+  @protected
+  ProfileFieldError dco_decode_profile_field_error(dynamic raw);
+
+  @protected
+  ProfileProxyNetwork dco_decode_profile_proxy_network(dynamic raw);
+
+  @protected
+  ProfileVpnPortal dco_decode_profile_vpn_portal(dynamic raw);
+
   @protected
   Protocol dco_decode_protocol(dynamic raw);
 
@@ -323,6 +435,12 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   (int, int) dco_decode_record_u_16_u_16(dynamic raw);
 
+  @protected
+  SplitTunnelMode dco_decode_split_tunnel_mode(dynamic raw);
+
+  @protected
+  SplitTunnelRule dco_decode_split_tunnel_rule(dynamic raw);
+
   @protected
   int dco_decode_u_16(dynamic raw);
 
@@ -344,12 +462,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   AnyhowException sse_decode_AnyhowException(SseDeserializer deserializer);
 
-  @protected
-  EventBusSubscriber
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    SseDeserializer deserializer,
-  );
-
   @protected
   FilterRule
   sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -369,14 +481,26 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   );
 
   @protected
-  JoinHandle
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  JoinHandleResultKvConfigApplyReportString
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
     SseDeserializer deserializer,
   );
 
   @protected
-  JoinHandleResultString
-  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  JoinHandleResultKvShutdownReportString
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  JoinHandleResultStringString
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  JoinHandleVecKvShutdownReport
+  sse_decode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     SseDeserializer deserializer,
   );
 
@@ -434,12 +558,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseDeserializer deserializer,
   );
 
-  @protected
-  EventBusSubscriber
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    SseDeserializer deserializer,
-  );
-
   @protected
   FilterRule
   sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -465,14 +583,26 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   );
 
   @protected
-  JoinHandle
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  JoinHandleResultKvConfigApplyReportString
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  JoinHandleResultKvShutdownReportString
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
     SseDeserializer deserializer,
   );
 
   @protected
-  JoinHandleResultString
-  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  JoinHandleResultStringString
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  JoinHandleVecKvShutdownReport
+  sse_decode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     SseDeserializer deserializer,
   );
 
@@ -488,15 +618,33 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseDeserializer deserializer,
   );
 
+  @protected
+  RustStreamSink<KVEventRecord> sse_decode_StreamSink_kv_event_record_Sse(
+    SseDeserializer deserializer,
+  );
+
   @protected
   String sse_decode_String(SseDeserializer deserializer);
 
   @protected
   bool sse_decode_bool(SseDeserializer deserializer);
 
+  @protected
+  double sse_decode_box_autoadd_f_64(SseDeserializer deserializer);
+
   @protected
   FlagsC sse_decode_box_autoadd_flags_c(SseDeserializer deserializer);
 
+  @protected
+  NetworkProfile sse_decode_box_autoadd_network_profile(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProfileVpnPortal sse_decode_box_autoadd_profile_vpn_portal(
+    SseDeserializer deserializer,
+  );
+
   @protected
   Protocol sse_decode_box_autoadd_protocol(SseDeserializer deserializer);
 
@@ -532,6 +680,30 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   int sse_decode_i_32(SseDeserializer deserializer);
 
+  @protected
+  PlatformInt64 sse_decode_i_64(SseDeserializer deserializer);
+
+  @protected
+  KVEvent sse_decode_kv_event(SseDeserializer deserializer);
+
+  @protected
+  KVEventKind sse_decode_kv_event_kind(SseDeserializer deserializer);
+
+  @protected
+  KVEventRecord sse_decode_kv_event_record(SseDeserializer deserializer);
+
+  @protected
+  KVExitFlow sse_decode_kv_exit_flow(SseDeserializer deserializer);
+
+  @protected
+  KVExitFlowInfo sse_decode_kv_exit_flow_info(SseDeserializer deserializer);
+
+  @protected
+  KVExitNodeStatus sse_decode_kv_exit_node_status(SseDeserializer deserializer);
+
+  @protected
+  KVInstanceInfo sse_decode_kv_instance_info(SseDeserializer deserializer);
+
   @protected
   KVNetworkStatus sse_decode_kv_network_status(SseDeserializer deserializer);
 
@@ -543,6 +715,14 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   KVNodeInfo sse_decode_kv_node_info(SseDeserializer deserializer);
 
+  @protected
+  KVPeerConn sse_decode_kv_peer_conn(SseDeserializer deserializer);
+
+  @protected
+  KVSplitTunnelStatus sse_decode_kv_split_tunnel_status(
+    SseDeserializer deserializer,
+  );
+
   @protected
   List<FilterRule>
   sse_decode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -561,6 +741,22 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   List<Forward> sse_decode_list_forward(SseDeserializer deserializer);
 
+  @protected
+  List<KVEventKind> sse_decode_list_kv_event_kind(SseDeserializer deserializer);
+
+  @protected
+  List<KVExitFlow> sse_decode_list_kv_exit_flow(SseDeserializer deserializer);
+
+  @protected
+  List<KVExitNodeStatus> sse_decode_list_kv_exit_node_status(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<KVInstanceInfo> sse_decode_list_kv_instance_info(
+    SseDeserializer deserializer,
+  );
+
   @protected
   List<KVNodeConnectionStats> sse_decode_list_kv_node_connection_stats(
     SseDeserializer deserializer,
@@ -583,17 +779,48 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   Uint8List sse_decode_list_prim_u_8_strict(SseDeserializer deserializer);
 
+  @protected
+  List<ProfileChange> sse_decode_list_profile_change(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<ProfileFieldError> sse_decode_list_profile_field_error(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  List<ProfileProxyNetwork> sse_decode_list_profile_proxy_network(
+    SseDeserializer deserializer,
+  );
+
   @protected
   List<(String, int)> sse_decode_list_record_string_u_32(
     SseDeserializer deserializer,
   );
 
+  @protected
+  List<SplitTunnelRule> sse_decode_list_split_tunnel_rule(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  NetworkProfile sse_decode_network_profile(SseDeserializer deserializer);
+
   @protected
   NodeHopStats sse_decode_node_hop_stats(SseDeserializer deserializer);
 
   @protected
   String? sse_decode_opt_String(SseDeserializer deserializer);
 
+  @protected
+  double? sse_decode_opt_box_autoadd_f_64(SseDeserializer deserializer);
+
+  @protected
+  ProfileVpnPortal? sse_decode_opt_box_autoadd_profile_vpn_portal(
+    SseDeserializer deserializer,
+  );
+
   @protected
   Protocol? sse_decode_opt_box_autoadd_protocol(SseDeserializer deserializer);
 
@@ -608,6 +835,25 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   int? sse_decode_opt_box_autoadd_u_32(SseDeserializer deserializer);
 
+  @protected
+  List<String>? sse_decode_opt_list_String(SseDeserializer deserializer);
+
+  @protected
+  ProfileChange sse_decode_profile_change(SseDeserializer deserializer);
+
+  @protected
+  ProfileFieldError sse_decode_profile_field_error(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProfileProxyNetwork sse_decode_profile_proxy_network(
+    SseDeserializer deserializer,
+  );
+
+  @protected
+  ProfileVpnPortal sse_decode_profile_vpn_portal(SseDeserializer deserializer);
+
   @protected
   Protocol sse_decode_protocol(SseDeserializer deserializer);
 
@@ -617,6 +863,12 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   (int, int) sse_decode_record_u_16_u_16(SseDeserializer deserializer);
 
+  @protected
+  SplitTunnelMode sse_decode_split_tunnel_mode(SseDeserializer deserializer);
+
+  @protected
+  SplitTunnelRule sse_decode_split_tunnel_rule(SseDeserializer deserializer);
+
   @protected
   int sse_decode_u_16(SseDeserializer deserializer);
 
@@ -641,13 +893,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
-  @protected
-  void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    EventBusSubscriber self,
-    SseSerializer serializer,
-  );
-
   @protected
   void
   sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -671,15 +916,29 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
 
   @protected
   void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
-    JoinHandle self,
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    JoinHandleResultKvConfigApplyReportString self,
     SseSerializer serializer,
   );
 
   @protected
   void
-  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
-    JoinHandleResultString self,
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    JoinHandleResultKvShutdownReportString self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    JoinHandleResultStringString self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
+    JoinHandleVecKvShutdownReport self,
     SseSerializer serializer,
   );
 
@@ -746,13 +1005,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
-  @protected
-  void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    EventBusSubscriber self,
-    SseSerializer serializer,
-  );
-
   @protected
   void
   sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -783,15 +1035,29 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
 
   @protected
   void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
-    JoinHandle self,
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    JoinHandleResultKvConfigApplyReportString self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    JoinHandleResultKvShutdownReportString self,
     SseSerializer serializer,
   );
 
   @protected
   void
-  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
-    JoinHandleResultString self,
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+    JoinHandleResultStringString self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void
+  sse_encode_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
+    JoinHandleVecKvShutdownReport self,
     SseSerializer serializer,
   );
 
@@ -809,15 +1075,36 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_StreamSink_kv_event_record_Sse(
+    RustStreamSink<KVEventRecord> self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_String(String self, SseSerializer serializer);
 
   @protected
   void sse_encode_bool(bool self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_box_autoadd_f_64(double self, SseSerializer serializer);
+
   @protected
   void sse_encode_box_autoadd_flags_c(FlagsC self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_box_autoadd_network_profile(
+    NetworkProfile self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_box_autoadd_profile_vpn_portal(
+    ProfileVpnPortal self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_box_autoadd_protocol(Protocol self, SseSerializer serializer);
 
@@ -854,6 +1141,39 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   void sse_encode_i_32(int self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_i_64(PlatformInt64 self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_kv_event(KVEvent self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_kv_event_kind(KVEventKind self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_kv_event_record(KVEventRecord self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_kv_exit_flow(KVExitFlow self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_kv_exit_flow_info(
+    KVExitFlowInfo self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_kv_exit_node_status(
+    KVExitNodeStatus self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_kv_instance_info(
+    KVInstanceInfo self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_kv_network_status(
     KVNetworkStatus self,
@@ -869,6 +1189,15 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   void sse_encode_kv_node_info(KVNodeInfo self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_kv_peer_conn(KVPeerConn self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_kv_split_tunnel_status(
+    KVSplitTunnelStatus self,
+    SseSerializer serializer,
+  );
+
   @protected
   void
   sse_encode_list_Auto_Owned_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
@@ -889,6 +1218,30 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   void sse_encode_list_forward(List<Forward> self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_list_kv_event_kind(
+    List<KVEventKind> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_kv_exit_flow(
+    List<KVExitFlow> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_kv_exit_node_status(
+    List<KVExitNodeStatus> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_kv_instance_info(
+    List<KVInstanceInfo> self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_list_kv_node_connection_stats(
     List<KVNodeConnectionStats> self,
@@ -925,18 +1278,57 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_list_profile_change(
+    List<ProfileChange> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_profile_field_error(
+    List<ProfileFieldError> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_list_profile_proxy_network(
+    List<ProfileProxyNetwork> self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_list_record_string_u_32(
     List<(String, int)> self,
     SseSerializer serializer,
   );
 
+  @protected
+  void sse_encode_list_split_tunnel_rule(
+    List<SplitTunnelRule> self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_network_profile(
+    NetworkProfile self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_node_hop_stats(NodeHopStats self, SseSerializer serializer);
 
   @protected
   void sse_encode_opt_String(String? self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_opt_box_autoadd_f_64(double? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_box_autoadd_profile_vpn_portal(
+    ProfileVpnPortal? self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_opt_box_autoadd_protocol(
     Protocol? self,
@@ -955,6 +1347,30 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   void sse_encode_opt_box_autoadd_u_32(int? self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_opt_list_String(List<String>? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_profile_change(ProfileChange self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_profile_field_error(
+    ProfileFieldError self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_profile_proxy_network(
+    ProfileProxyNetwork self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_profile_vpn_portal(
+    ProfileVpnPortal self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_protocol(Protocol self, SseSerializer serializer);
 
@@ -967,6 +1383,18 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   void sse_encode_record_u_16_u_16((int, int) self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_split_tunnel_mode(
+    SplitTunnelMode self,
+    SseSerializer serializer,
+  );
+
+  @protected
+  void sse_encode_split_tunnel_rule(
+    SplitTunnelRule self,
+    SseSerializer serializer,
+  );
+
   @protected
   void sse_encode_u_16(int self, SseSerializer serializer);
 
@@ -991,22 +1419,6 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
 class RustLibWire implements BaseWire {
   RustLibWire.fromExternalLibrary(ExternalLibrary lib);
 
-  void
-  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    int ptr,
-  ) => wasmModule
-      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-        ptr,
-      );
-
-  void
-  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    int ptr,
-  ) => wasmModule
-      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-        ptr,
-      );
-
   void
   rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
     int ptr,
@@ -1072,34 +1484,66 @@ class RustLibWire implements BaseWire {
       );
 
   void
-  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+        ptr,
+      );
+
+  void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+        ptr,
+      );
+
+  void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+        ptr,
+      );
+
+  void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    int ptr,
+  ) => wasmModule
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+        ptr,
+      );
+
+  void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
     int ptr,
   ) => wasmModule
-      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
         ptr,
       );
 
   void
-  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
     int ptr,
   ) => wasmModule
-      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
         ptr,
       );
 
   void
-  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     int ptr,
   ) => wasmModule
-      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+      .rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
         ptr,
       );
 
   void
-  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     int ptr,
   ) => wasmModule
-      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+      .rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
         ptr,
       );
 
@@ -1147,11 +1591,6 @@ extension type RustLibWasmModule._(JSObject _) implements JSObject {
     int ptr,
   );
 
-  external void
-  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-    int ptr,
-  );
-
   external void
   rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
     int ptr,
@@ -1193,22 +1632,42 @@ extension type RustLibWasmModule._(JSObject _) implements JSObject {
   );
 
   external void
-  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    int ptr,
+  );
+
+  external void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+    int ptr,
+  );
+
+  external void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    int ptr,
+  );
+
+  external void
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+    int ptr,
+  );
+
+  external void
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
     int ptr,
   );
 
   external void
-  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
     int ptr,
   );
 
   external void
-  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     int ptr,
   );
 
   external void
-  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+  rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
     int ptr,
   );
 
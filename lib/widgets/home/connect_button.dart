@@ -30,6 +30,7 @@ class _ConnectButtonState extends State<ConnectButton>
   Timer? _connectionTimer;
   Timer? _timeoutTimer;
   int _connectionDuration = 0; // 连接持续时间（秒）
+  String _roomName = ''; // 当前连接的房间名称，用于查找网络实例
 
   // 添加通知插件（仅安卓平台）
   final FlutterLocalNotificationsPlugin? _notificationsPlugin =
@@ -93,7 +94,7 @@ class _ConnectButtonState extends State<ConnectButton>
 
       // 监听VPN服务启动事件
       vpnPlugin?.onVpnServiceStarted.listen((data) {
-        setTunFd(fd: data['fd']);
+        setTunFd(instanceId: Aps().instanceId.value, fd: data['fd']);
         // 在这里处理VPN启动后的逻辑
       });
       // 监听VPN服务停止事件
@@ -240,6 +241,9 @@ class _ConnectButtonState extends State<ConnectButton>
       vpnPlugin?.prepareVpn();
     }
 
+    aps.instanceId.value = '';
+    _roomName = rom.roomName;
+
     String currentIp = aps.ipv4.value;
     bool forceDhcp = false;
     String ipForServer = ""; // 默认为空，如果强制DHCP
@@ -349,6 +353,12 @@ class _ConnectButtonState extends State<ConnectButton>
     enableQuicProxy: aps.enableQuicProxy.value,
     disableQuicInput: aps.disableQuicInput.value,
     disableSymHolePunching: aps.disableSymHolePunching.value,
+    // 以下字段沿用 easytier 的默认值
+    enableRelayForeignNetworkKcp: false,
+    foreignRelayBpsLimit: (BigInt.one << 64) - BigInt.one, // 不限速
+    multiThreadCount: 2,
+    encryptionAlgorithm: 'aes-gcm',
+    enableMultipathBonding: false,
   );
 
   Future<void> _beginConnectionProcess() async {
@@ -402,8 +412,23 @@ class _ConnectButtonState extends State<ConnectButton>
     });
   }
 
+  /// 根据房间名称查找当前网络实例ID，实例尚未创建时返回空字符串
+  Future<String> _resolveInstanceId() async {
+    if (Aps().instanceId.value.isNotEmpty) return Aps().instanceId.value;
+    final instances = await listInstances();
+    for (final instance in instances) {
+      if (instance.networkName == _roomName && instance.running) {
+        Aps().instanceId.value = instance.instanceId;
+        break;
+      }
+    }
+    return Aps().instanceId.value;
+  }
+
   Future<bool> _checkAndUpdateConnectionStatus() async {
-    final runningInfo = await getRunningInfo();
+    final instanceId = await _resolveInstanceId();
+    if (instanceId.isEmpty) return false;
+    final runningInfo = await getRunningInfo(instanceId: instanceId);
     final data = jsonDecode(runningInfo);
 
     final ipv4Address = _extractIpv4Address(data);
@@ -463,11 +488,12 @@ class _ConnectButtonState extends State<ConnectButton>
     setState(() => _connectionDuration++);
 
     try {
-      final runningInfo = await getRunningInfo();
+      final instanceId = Aps().instanceId.value;
+      final runningInfo = await getRunningInfo(instanceId: instanceId);
       final data = jsonDecode(runningInfo);
 
       Aps().updateIpv4(_extractIpv4Address(data));
-      Aps().netStatus.value = await getNetworkStatus();
+      Aps().netStatus.value = await getNetworkStatus(instanceId: instanceId);
 
       // 在安卓平台更新通知
       if (Platform.isAndroid && Aps().Connec_state.value == CoState.connected) {
@@ -496,7 +522,14 @@ class _ConnectButtonState extends State<ConnectButton>
     // 取消计时器
     _connectionTimer?.cancel();
     _connectionTimer = null;
-    closeServer();
+    final instanceId = Aps().instanceId.value;
+    if (instanceId.isNotEmpty) {
+      closeServer(instanceId: instanceId);
+    } else {
+      // 实例ID尚未解析出来时（例如连接超时），关闭所有实例
+      closeAllServers();
+    }
+    Aps().instanceId.value = '';
     Aps().Connec_state.value = CoState.idle;
   }
 
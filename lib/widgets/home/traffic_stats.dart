@@ -61,7 +61,9 @@ class _TrafficStatsState extends State<TrafficStats> {
 
   Future<void> _loadTrafficData() async {
     try {
-      final status = await getNetworkStatus();
+      final instanceId = Aps().instanceId.value;
+      if (instanceId.isEmpty) return;
+      final status = await getNetworkStatus(instanceId: instanceId);
       if (mounted) {
         final (totalRx, totalTx) = _calculateTotalTrafficFromStatus(status);
 
@@ -180,6 +180,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .type_attribute("common.RpcDescriptor", "#[derive(Hash, Eq)]")
         .field_attribute(".web.NetworkConfig", "#[serde(default)]")
+        .field_attribute(".acl.Rule.time_windows", "#[serde(default)]")
+        .field_attribute(".acl.Chain.time_windows", "#[serde(default)]")
+        .field_attribute(".acl.Rule.source_hosts", "#[serde(default)]")
+        .field_attribute(".acl.Rule.destination_hosts", "#[serde(default)]")
         .service_generator(Box::new(rpc_build::ServiceGenerator::new()))
         .btree_map(["."])
         .skip_debug([".common.Ipv4Addr", ".common.Ipv6Addr", ".common.UUID"]);
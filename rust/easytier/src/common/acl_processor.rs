@@ -68,9 +68,95 @@ pub struct FastLookupRule {
     pub stateful: bool,
     pub rate_limit: u32,
     pub burst_limit: u32,
+    pub time_windows: Vec<TimeWindow>,
+    // windows of the chain this rule belongs to
+    pub chain_time_windows: Arc<Vec<TimeWindow>>,
+    // the rule only references hosts on one side and none of them is in the
+    // route table, so it must not match anything until the routes change
+    pub unresolved_hosts: bool,
     pub rule_stats: Arc<RuleStats>,
 }
 
+impl FastLookupRule {
+    /// Whether both the rule and its chain are inside one of their time windows.
+    pub fn is_active_at(&self, unix_secs: u64) -> bool {
+        AclProcessor::windows_contain(&self.time_windows, unix_secs)
+            && AclProcessor::windows_contain(&self.chain_time_windows, unix_secs)
+    }
+}
+
+// Default action of a chain, only used while the chain is inside its time windows
+#[derive(Debug, Clone)]
+pub struct ChainDefaultAction {
+    pub time_windows: Vec<TimeWindow>,
+    pub action: Action,
+}
+
+// Magic dns zones stripped from host names in rules, `as.net.` is used by the Astral frontend
+const ACL_HOST_DNS_ZONES: &[&str] = &[crate::instance::dns_server::DEFAULT_ET_DNS_ZONE, "as.net."];
+
+const ACL_HOST_GROUP_PREFIX: &str = "group:";
+
+/// Snapshot of the route table used to resolve `source_hosts` / `destination_hosts`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AclHostTable {
+    hosts: HashMap<String, Vec<IpAddr>>,
+    groups: HashMap<String, Vec<IpAddr>>,
+}
+
+impl AclHostTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lowercase the name and strip the trailing dot and magic dns zone.
+    pub fn normalize_hostname(name: &str) -> String {
+        let name = name.trim().trim_end_matches('.').to_ascii_lowercase();
+        for zone in ACL_HOST_DNS_ZONES {
+            let zone = zone.trim_end_matches('.');
+            if let Some(host) = name.strip_suffix(zone) {
+                if let Some(host) = host.strip_suffix('.') {
+                    if !host.is_empty() {
+                        return host.to_string();
+                    }
+                }
+            }
+        }
+        name
+    }
+
+    fn insert_addrs(map: &mut HashMap<String, Vec<IpAddr>>, key: String, addrs: &[IpAddr]) {
+        let entry = map.entry(key).or_default();
+        entry.extend_from_slice(addrs);
+        entry.sort();
+        entry.dedup();
+    }
+
+    /// Register a peer with its virtual addresses and the groups it belongs to.
+    pub fn add_peer(&mut self, hostname: &str, addrs: &[IpAddr], groups: &[String]) {
+        if addrs.is_empty() {
+            return;
+        }
+        let hostname = Self::normalize_hostname(hostname);
+        if !hostname.is_empty() {
+            Self::insert_addrs(&mut self.hosts, hostname, addrs);
+        }
+        for group in groups {
+            Self::insert_addrs(&mut self.groups, group.clone(), addrs);
+        }
+    }
+
+    /// Addresses of a rule host entry, empty if it is not in the route table.
+    pub fn resolve(&self, entry: &str) -> &[IpAddr] {
+        let entry = entry.trim();
+        let addrs = match entry.strip_prefix(ACL_HOST_GROUP_PREFIX) {
+            Some(group) => self.groups.get(group.trim()),
+            None => self.hosts.get(&Self::normalize_hostname(entry)),
+        };
+        addrs.map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
 // Cache key combining packet info and chain type
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct AclCacheKey {
@@ -111,6 +197,10 @@ pub struct AclCacheEntry {
     pub chain_type: ChainType,
     pub acl_result: Option<AclResult>,
     pub rule_stats_vec: Vec<Arc<RuleStats>>,
+    // Time range (unix seconds) in which no time window opens or closes,
+    // so the cached decision stays valid
+    pub valid_from: u64,
+    pub valid_until: u64,
 }
 
 // Packet info extracted for ACL processing
@@ -199,9 +289,13 @@ pub struct AclProcessor {
     outbound_rules: Vec<FastLookupRule>,
     forward_rules: Vec<FastLookupRule>,
 
-    default_inbound_action: Action,
-    default_outbound_action: Action,
-    default_forward_action: Action,
+    default_inbound_actions: Vec<ChainDefaultAction>,
+    default_outbound_actions: Vec<ChainDefaultAction>,
+    default_forward_actions: Vec<ChainDefaultAction>,
+
+    // Time windows of all chains and enabled rules, used to find when a
+    // cached decision expires
+    time_windows: Vec<TimeWindow>,
 
     default_rule_stats: Arc<RuleStats>,
 
@@ -237,9 +331,28 @@ impl AclProcessor {
         rate_limiters: Option<Arc<DashMap<RateLimitKey, Arc<TokenBucket>>>>,
         stats: Option<Arc<DashMap<AclStatKey, u64>>>,
     ) -> Self {
-        let (inbound_rules, outbound_rules, forward_rules) = Self::build_rules(&acl_config);
-        let (default_inbound_action, default_outbound_action, default_forward_action) =
+        Self::new_with_hosts(
+            acl_config,
+            &AclHostTable::default(),
+            conn_track,
+            rate_limiters,
+            stats,
+        )
+    }
+
+    /// Like `new_with_shared_state`, resolving host entries of the rules with `hosts`
+    /// A new processor has to be built when the route table changes
+    pub fn new_with_hosts(
+        acl_config: Acl,
+        hosts: &AclHostTable,
+        conn_track: Option<Arc<DashMap<String, ConnTrackEntry>>>,
+        rate_limiters: Option<Arc<DashMap<RateLimitKey, Arc<TokenBucket>>>>,
+        stats: Option<Arc<DashMap<AclStatKey, u64>>>,
+    ) -> Self {
+        let (inbound_rules, outbound_rules, forward_rules) = Self::build_rules(&acl_config, hosts);
+        let (default_inbound_actions, default_outbound_actions, default_forward_actions) =
             Self::build_default_actions(&acl_config);
+        let time_windows = Self::collect_time_windows(&acl_config);
         let tasks = JoinSet::new();
 
         let mut processor = Self {
@@ -247,9 +360,11 @@ impl AclProcessor {
             outbound_rules,
             forward_rules,
 
-            default_inbound_action,
-            default_outbound_action,
-            default_forward_action,
+            default_inbound_actions,
+            default_outbound_actions,
+            default_forward_actions,
+
+            time_windows,
 
             default_rule_stats: Arc::new(RuleStats {
                 rule: None,
@@ -271,50 +386,86 @@ impl AclProcessor {
         processor
     }
 
-    fn build_default_actions(acl_config: &Acl) -> (Action, Action, Action) {
-        let default_inbound_action = acl_config
-            .acl_v1
-            .as_ref()
-            .and_then(|v1| {
-                v1.chains
-                    .iter()
-                    .find(|c| c.chain_type == ChainType::Inbound as i32)
-            })
-            .map(|c| c.default_action())
-            .unwrap_or(Action::Allow);
-
-        let default_outbound_action = acl_config
-            .acl_v1
-            .as_ref()
-            .and_then(|v1| {
-                v1.chains
-                    .iter()
-                    .find(|c| c.chain_type == ChainType::Outbound as i32)
-            })
-            .map(|c| c.default_action())
-            .unwrap_or(Action::Allow);
-
-        let default_forward_action = acl_config
-            .acl_v1
-            .as_ref()
-            .and_then(|v1| {
-                v1.chains
-                    .iter()
-                    .find(|c| c.chain_type == ChainType::Forward as i32)
-            })
-            .map(|c| c.default_action())
-            .unwrap_or(Action::Allow);
+    fn build_default_actions(
+        acl_config: &Acl,
+    ) -> (
+        Vec<ChainDefaultAction>,
+        Vec<ChainDefaultAction>,
+        Vec<ChainDefaultAction>,
+    ) {
+        let defaults_of = |chain_type: ChainType| {
+            acl_config
+                .acl_v1
+                .as_ref()
+                .map(|v1| {
+                    v1.chains
+                        .iter()
+                        .filter(|c| c.chain_type == chain_type as i32)
+                        .map(|c| ChainDefaultAction {
+                            time_windows: c.time_windows.clone(),
+                            action: c.default_action(),
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
 
         (
-            default_inbound_action,
-            default_outbound_action,
-            default_forward_action,
+            defaults_of(ChainType::Inbound),
+            defaults_of(ChainType::Outbound),
+            defaults_of(ChainType::Forward),
         )
     }
 
+    /// The default action of the first chain inside its time windows, Allow if none.
+    fn resolve_default_action(defaults: &[ChainDefaultAction], unix_secs: u64) -> Action {
+        defaults
+            .iter()
+            .find(|d| Self::windows_contain(&d.time_windows, unix_secs))
+            .map(|d| d.action)
+            .unwrap_or(Action::Allow)
+    }
+
+    /// An empty window list means always active.
+    pub fn windows_contain(windows: &[TimeWindow], unix_secs: u64) -> bool {
+        windows.is_empty() || windows.iter().any(|w| w.contains(unix_secs))
+    }
+
+    fn collect_time_windows(acl_config: &Acl) -> Vec<TimeWindow> {
+        let mut windows = Vec::new();
+        if let Some(ref acl_v1) = acl_config.acl_v1 {
+            for chain in acl_v1.chains.iter() {
+                windows.extend(chain.time_windows.iter().cloned());
+                if !chain.enabled {
+                    continue;
+                }
+                for rule in chain.rules.iter().filter(|r| r.enabled) {
+                    windows.extend(rule.time_windows.iter().cloned());
+                }
+            }
+        }
+        windows
+    }
+
+    fn compute_next_time_boundary(windows: &[TimeWindow], unix_secs: u64) -> u64 {
+        windows
+            .iter()
+            .map(|w| w.next_boundary(unix_secs))
+            .min()
+            .unwrap_or(u64::MAX)
+    }
+
+    fn now_secs() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
     /// Build all rule vectors from configuration
     fn build_rules(
         acl_config: &Acl,
+        hosts: &AclHostTable,
     ) -> (
         Vec<FastLookupRule>,
         Vec<FastLookupRule>,
@@ -331,11 +482,16 @@ impl AclProcessor {
                     continue;
                 }
 
+                let chain_time_windows = Arc::new(chain.time_windows.clone());
                 let mut rules = chain
                     .rules
                     .iter()
                     .filter(|rule| rule.enabled)
-                    .map(Self::convert_to_fast_lookup_rule)
+                    .map(|rule| {
+                        let mut rule = Self::convert_to_fast_lookup_rule(rule, hosts);
+                        rule.chain_time_windows = chain_time_windows.clone();
+                        rule
+                    })
                     .collect::<Vec<_>>();
 
                 // Sort by priority (higher priority first)
@@ -466,19 +622,29 @@ impl AclProcessor {
 
     /// Process a packet through ACL rules - Now lock-free!
     pub fn process_packet(&self, packet_info: &PacketInfo, chain_type: ChainType) -> AclResult {
+        self.process_packet_at(packet_info, chain_type, Self::now_secs())
+    }
+
+    /// Process a packet as if the current time were `now` (unix seconds),
+    /// which decides the time windows in effect.
+    pub fn process_packet_at(
+        &self,
+        packet_info: &PacketInfo,
+        chain_type: ChainType,
+        now: u64,
+    ) -> AclResult {
         // Check cache first for performance
         let cache_key = AclCacheKey::from_packet_info(packet_info, chain_type);
 
         // If cache hit and can skip checks, return cached result
         if let Some(mut cached) = self.rule_cache.get_mut(&cache_key) {
-            // Update last access time for LRU
-            cached.last_access = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-
-            self.increment_stat(AclStatKey::CacheHits);
-            return self.process_packet_with_cache_entry(packet_info, &cached);
+            if cached.valid_from <= now && now < cached.valid_until {
+                // Update last access time for LRU
+                cached.last_access = now;
+
+                self.increment_stat(AclStatKey::CacheHits);
+                return self.process_packet_with_cache_entry(packet_info, &cached);
+            }
         }
 
         // Direct access to rules - no locks needed!
@@ -499,20 +665,19 @@ impl AclProcessor {
         let mut cache_entry = AclCacheEntry {
             action: Action::Allow,
             matched_rule: RuleId::Default,
-            last_access: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            last_access: now,
             conn_track_key: None,
             rate_limit_keys: vec![],
             chain_type,
             acl_result: None,
             rule_stats_vec: vec![],
+            valid_from: now,
+            valid_until: Self::compute_next_time_boundary(&self.time_windows, now),
         };
 
         // Process rules in priority order
         for rule in rules.iter() {
-            if !rule.enabled || !self.rule_matches(rule, packet_info) {
+            if !rule.enabled || !rule.is_active_at(now) || !self.rule_matches(rule, packet_info) {
                 continue;
             }
 
@@ -572,9 +737,11 @@ impl AclProcessor {
         }
 
         let default_action = match chain_type {
-            ChainType::Inbound => self.default_inbound_action,
-            ChainType::Outbound => self.default_outbound_action,
-            ChainType::Forward => self.default_forward_action,
+            ChainType::Inbound => Self::resolve_default_action(&self.default_inbound_actions, now),
+            ChainType::Outbound => {
+                Self::resolve_default_action(&self.default_outbound_actions, now)
+            }
+            ChainType::Forward => Self::resolve_default_action(&self.default_forward_actions, now),
             _ => Action::Allow,
         };
 
@@ -631,6 +798,10 @@ impl AclProcessor {
 
     /// Check if a rule matches the packet
     fn rule_matches(&self, rule: &FastLookupRule, packet_info: &PacketInfo) -> bool {
+        if rule.unresolved_hosts {
+            return false;
+        }
+
         // Protocol check
         if rule.protocol != Protocol::Any && rule.protocol as i32 != packet_info.protocol as i32 {
             return false;
@@ -790,18 +961,14 @@ impl AclProcessor {
     }
 
     /// Convert proto Rule to FastLookupRule
-    fn convert_to_fast_lookup_rule(rule: &Rule) -> FastLookupRule {
-        let src_ip_ranges = rule
-            .source_ips
-            .iter()
-            .filter_map(|x| Self::convert_ip_inet_to_cidr(x.as_str()))
-            .collect();
-
-        let dst_ip_ranges = rule
-            .destination_ips
-            .iter()
-            .filter_map(|x| Self::convert_ip_inet_to_cidr(x.as_str()))
-            .collect();
+    fn convert_to_fast_lookup_rule(rule: &Rule, hosts: &AclHostTable) -> FastLookupRule {
+        let (src_ip_ranges, src_unresolved) =
+            Self::build_ip_ranges(&rule.source_ips, &rule.source_hosts, hosts);
+        let (dst_ip_ranges, dst_unresolved) =
+            Self::build_ip_ranges(&rule.destination_ips, &rule.destination_hosts, hosts);
+        if src_unresolved || dst_unresolved {
+            tracing::debug!(rule = %rule.name, "acl rule hosts not in route table, rule inactive");
+        }
 
         let src_port_ranges = rule
             .source_ports
@@ -841,6 +1008,9 @@ impl AclProcessor {
             stateful: rule.stateful,
             rate_limit: rule.rate_limit,
             burst_limit: rule.burst_limit,
+            time_windows: rule.time_windows.clone(),
+            chain_time_windows: Arc::new(vec![]),
+            unresolved_hosts: src_unresolved || dst_unresolved,
             rule_stats: Arc::new(RuleStats {
                 rule: Some(rule.clone()),
                 stat: Some(StatItem {
@@ -851,6 +1021,29 @@ impl AclProcessor {
         }
     }
 
+    /// Merge CIDRs and resolved host addresses of one side of a rule
+    /// Returns true as second value if hosts are given but nothing could be matched
+    fn build_ip_ranges(
+        ips: &[String],
+        host_entries: &[String],
+        hosts: &AclHostTable,
+    ) -> (Vec<cidr::IpCidr>, bool) {
+        let mut ranges: Vec<cidr::IpCidr> = ips
+            .iter()
+            .filter_map(|x| Self::convert_ip_inet_to_cidr(x.as_str()))
+            .collect();
+        for entry in host_entries {
+            ranges.extend(
+                hosts
+                    .resolve(entry)
+                    .iter()
+                    .map(|addr| cidr::IpCidr::new_host(*addr)),
+            );
+        }
+        let unresolved = !host_entries.is_empty() && ranges.is_empty();
+        (ranges, unresolved)
+    }
+
     /// Convert IpInet to CIDR for fast lookup
     fn convert_ip_inet_to_cidr(input: &str) -> Option<cidr::IpCidr> {
         cidr::IpCidr::from_str(input).ok()
@@ -942,7 +1135,7 @@ fn parse_port_end(port_strs: &[String]) -> Option<u16> {
         .filter_map(|s| parse_port_range(s).map(|(_, end)| end))
         .max()
 }
-fn parse_port_range(s: &str) -> Option<(u16, u16)> {
+pub(crate) fn parse_port_range(s: &str) -> Option<(u16, u16)> {
     if let Some((start, end)) = s.split_once('-') {
         let start = start.trim().parse().ok()?;
         let end = end.trim().parse().ok()?;
@@ -1082,6 +1275,7 @@ impl AclRuleBuilder {
             enabled: true,
             rules: vec![],
             default_action: Action::Drop as i32, // Default deny
+            time_windows: vec![],
         };
 
         let mut rule_priority = self.whitelist_priority.unwrap_or(1000u32);
@@ -1105,6 +1299,9 @@ impl AclRuleBuilder {
                 stateful: true,
                 source_groups: vec![],
                 destination_groups: vec![],
+                time_windows: vec![],
+                source_hosts: vec![],
+                destination_hosts: vec![],
             };
             inbound_chain.rules.push(tcp_rule);
             rule_priority -= 1;
@@ -1129,6 +1326,9 @@ impl AclRuleBuilder {
                 stateful: false,
                 source_groups: vec![],
                 destination_groups: vec![],
+                time_windows: vec![],
+                source_hosts: vec![],
+                destination_hosts: vec![],
             };
             inbound_chain.rules.push(udp_rule);
         }
@@ -1630,4 +1830,213 @@ mod tests {
             Some(AclLogContext::RateLimitDrop)
         ));
     }
+
+    // 2024-01-01 00:00 UTC, a Monday; 08:00 in UTC+8
+    const MONDAY_0800_UTC8: u64 = 1704067200;
+    const HOUR: u64 = 3600;
+
+    fn evening_window(days_of_week: Vec<u32>) -> TimeWindow {
+        TimeWindow {
+            days_of_week,
+            start_time: 18 * 60,
+            end_time: 23 * 60,
+            timezone_offset: 8 * 60,
+        }
+    }
+
+    #[test]
+    fn test_time_window_contains_and_boundaries() {
+        let evening = evening_window(vec![1]);
+        assert!(!evening.contains(MONDAY_0800_UTC8));
+        assert_eq!(
+            evening.next_boundary(MONDAY_0800_UTC8),
+            MONDAY_0800_UTC8 + 10 * HOUR
+        );
+        assert!(evening.contains(MONDAY_0800_UTC8 + 11 * HOUR));
+        assert_eq!(
+            evening.next_boundary(MONDAY_0800_UTC8 + 11 * HOUR),
+            MONDAY_0800_UTC8 + 15 * HOUR
+        );
+        // Tuesday 00:00 local
+        assert!(!evening.contains(MONDAY_0800_UTC8 + 16 * HOUR));
+
+        // 22:00 - 02:00 belongs to the day it starts on
+        let overnight = TimeWindow {
+            days_of_week: vec![1],
+            start_time: 22 * 60,
+            end_time: 2 * 60,
+            timezone_offset: 8 * 60,
+        };
+        assert!(overnight.contains(MONDAY_0800_UTC8 + 17 * HOUR));
+        assert!(!overnight.contains(MONDAY_0800_UTC8 + 41 * HOUR));
+
+        // whole Sunday
+        let sunday = TimeWindow {
+            days_of_week: vec![0],
+            start_time: 0,
+            end_time: 0,
+            timezone_offset: 8 * 60,
+        };
+        assert!(!sunday.contains(MONDAY_0800_UTC8));
+        assert!(sunday.contains(MONDAY_0800_UTC8 - 9 * HOUR));
+        assert_eq!(
+            sunday.next_boundary(MONDAY_0800_UTC8),
+            MONDAY_0800_UTC8 + 16 * HOUR
+        );
+    }
+
+    #[tokio::test]
+    async fn test_time_window_rule_and_cache_invalidation() {
+        let mut chain = Chain {
+            name: "evening_games".to_string(),
+            chain_type: ChainType::Inbound as i32,
+            enabled: true,
+            default_action: Action::Drop as i32,
+            ..Default::default()
+        };
+        chain.rules.push(Rule {
+            name: "game_server".to_string(),
+            priority: 100,
+            enabled: true,
+            action: Action::Allow as i32,
+            protocol: Protocol::Tcp as i32,
+            ports: vec!["27015".to_string()],
+            time_windows: vec![evening_window(vec![])],
+            ..Default::default()
+        });
+        let acl_config = Acl {
+            acl_v1: Some(AclV1 {
+                chains: vec![chain],
+                ..Default::default()
+            }),
+        };
+
+        let processor = AclProcessor::new(acl_config);
+        let mut packet_info = create_test_packet_info();
+        packet_info.dst_port = Some(27015);
+
+        let result =
+            processor.process_packet_at(&packet_info, ChainType::Inbound, MONDAY_0800_UTC8);
+        assert_eq!(result.action, Action::Drop);
+        assert_eq!(result.matched_rule, Some(RuleId::Default));
+
+        let evening = MONDAY_0800_UTC8 + 11 * HOUR;
+        let result = processor.process_packet_at(&packet_info, ChainType::Inbound, evening);
+        assert_eq!(result.action, Action::Allow);
+        assert_eq!(result.matched_rule, Some(RuleId::Priority(100)));
+
+        // served from cache while the window stays open
+        let result = processor.process_packet_at(&packet_info, ChainType::Inbound, evening + 60);
+        assert_eq!(result.action, Action::Allow);
+        assert_eq!(processor.get_stats().get("CacheHits"), Some(&1));
+
+        // the window closes at 23:00, the cached allow must not survive it
+        let closed = MONDAY_0800_UTC8 + 15 * HOUR;
+        let result = processor.process_packet_at(&packet_info, ChainType::Inbound, closed);
+        assert_eq!(result.action, Action::Drop);
+        assert_eq!(result.matched_rule, Some(RuleId::Default));
+    }
+
+    #[tokio::test]
+    async fn test_time_window_chain_default_action() {
+        let chain = Chain {
+            name: "evening_lockdown".to_string(),
+            chain_type: ChainType::Inbound as i32,
+            enabled: true,
+            default_action: Action::Drop as i32,
+            time_windows: vec![evening_window(vec![])],
+            ..Default::default()
+        };
+        let acl_config = Acl {
+            acl_v1: Some(AclV1 {
+                chains: vec![chain],
+                ..Default::default()
+            }),
+        };
+
+        let processor = AclProcessor::new(acl_config);
+        let packet_info = create_test_packet_info();
+
+        // outside the chain's window no chain applies
+        let result =
+            processor.process_packet_at(&packet_info, ChainType::Inbound, MONDAY_0800_UTC8);
+        assert_eq!(result.action, Action::Allow);
+
+        let result = processor.process_packet_at(
+            &packet_info,
+            ChainType::Inbound,
+            MONDAY_0800_UTC8 + 11 * HOUR,
+        );
+        assert_eq!(result.action, Action::Drop);
+    }
+
+    #[test]
+    fn test_acl_host_table_resolve() {
+        let mut hosts = AclHostTable::new();
+        let laptop: IpAddr = "10.126.126.2".parse().unwrap();
+        hosts.add_peer("Laptop", &[laptop], &["admin".to_string()]);
+        hosts.add_peer("no-address", &[], &["admin".to_string()]);
+
+        assert_eq!(hosts.resolve("laptop"), &[laptop]);
+        assert_eq!(hosts.resolve("LAPTOP.et.net."), &[laptop]);
+        assert_eq!(hosts.resolve("laptop.as.net"), &[laptop]);
+        assert_eq!(hosts.resolve("group:admin"), &[laptop]);
+        assert!(hosts.resolve("laptop.example.com").is_empty());
+        assert!(hosts.resolve("no-address").is_empty());
+        assert!(hosts.resolve("group:user").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_host_based_rules_follow_route_table() {
+        let mut chain = Chain {
+            name: "hosts".to_string(),
+            chain_type: ChainType::Inbound as i32,
+            enabled: true,
+            default_action: Action::Drop as i32,
+            ..Default::default()
+        };
+        chain.rules.push(Rule {
+            name: "allow_laptop".to_string(),
+            priority: 100,
+            enabled: true,
+            action: Action::Allow as i32,
+            protocol: Protocol::Any as i32,
+            source_hosts: vec!["laptop.et.net".to_string()],
+            ..Default::default()
+        });
+        let acl_config = Acl {
+            acl_v1: Some(AclV1 {
+                chains: vec![chain],
+                ..Default::default()
+            }),
+        };
+        let packet_info = create_test_packet_info();
+
+        // an unresolved host must not turn into a match-all rule
+        let processor = AclProcessor::new(acl_config.clone());
+        let result = processor.process_packet(&packet_info, ChainType::Inbound);
+        assert_eq!(result.action, Action::Drop);
+
+        let mut hosts = AclHostTable::new();
+        hosts.add_peer("laptop", &[packet_info.src_ip], &[]);
+        let (conn_track, rate_limiters, stats) = processor.get_shared_state();
+        let processor = AclProcessor::new_with_hosts(
+            acl_config.clone(),
+            &hosts,
+            Some(conn_track),
+            Some(rate_limiters),
+            Some(stats),
+        );
+        let result = processor.process_packet(&packet_info, ChainType::Inbound);
+        assert_eq!(result.action, Action::Allow);
+        assert_eq!(result.matched_rule, Some(RuleId::Priority(100)));
+
+        // the laptop got a new address, the old one is no longer allowed
+        let mut hosts = AclHostTable::new();
+        let new_addr: IpAddr = "10.126.126.9".parse().unwrap();
+        hosts.add_peer("laptop", &[new_addr], &[]);
+        let processor = AclProcessor::new_with_hosts(acl_config, &hosts, None, None, None);
+        let result = processor.process_packet(&packet_info, ChainType::Inbound);
+        assert_eq!(result.action, Action::Drop);
+    }
 }
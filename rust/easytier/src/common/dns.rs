@@ -77,7 +77,7 @@ pub async fn socket_addrs(
     let port = match port {
         0 => match url.scheme() {
             "ws" => 80,
-            "wss" => 443,
+            "wss" | "tls" => 443,
             _ => port,
         },
         _ => port,
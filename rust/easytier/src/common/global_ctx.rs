@@ -6,6 +6,7 @@ use std::{
 };
 
 use crate::common::config::ProxyNetworkConfig;
+use crate::common::identity::NodeIdentityManager;
 use crate::common::stats_manager::StatsManager;
 use crate::common::token_bucket::TokenBucketManager;
 use crate::peers::acl_filter::AclFilter;
@@ -91,6 +92,8 @@ pub struct GlobalCtx {
     stats_manager: Arc<StatsManager>,
 
     acl_filter: Arc<AclFilter>,
+
+    node_identity: Option<Arc<NodeIdentityManager>>,
 }
 
 impl std::fmt::Debug for GlobalCtx {
@@ -142,6 +145,14 @@ impl GlobalCtx {
             ..Default::default()
         };
 
+        // an invalid config is reported by Instance::run, which refuses to start
+        let node_identity = config_fs.get_node_identity().and_then(|cfg| {
+            NodeIdentityManager::new(&cfg, &network.network_name)
+                .inspect_err(|e| tracing::error!(?e, "invalid node identity config"))
+                .ok()
+                .map(Arc::new)
+        });
+
         GlobalCtx {
             inst_name: config_fs.get_inst_name(),
             id,
@@ -177,6 +188,8 @@ impl GlobalCtx {
             stats_manager: Arc::new(StatsManager::new()),
 
             acl_filter: Arc::new(AclFilter::new()),
+
+            node_identity,
         }
     }
 
@@ -294,6 +307,10 @@ impl GlobalCtx {
         }
     }
 
+    pub fn clear_running_listeners(&self) {
+        self.running_listeners.lock().unwrap().clear();
+    }
+
     pub fn get_vpn_portal_cidr(&self) -> Option<cidr::Ipv4Cidr> {
         self.config.get_vpn_portal_config().map(|x| x.client_cidr)
     }
@@ -387,6 +404,11 @@ impl GlobalCtx {
         &self.acl_filter
     }
 
+    /// None if node identities are not enabled for this network.
+    pub fn get_node_identity(&self) -> Option<&Arc<NodeIdentityManager>> {
+        self.node_identity.as_ref()
+    }
+
     pub fn get_acl_groups(&self, peer_id: PeerId) -> Vec<PeerGroupInfo> {
         use std::collections::HashSet;
         self.config
@@ -422,7 +444,12 @@ impl GlobalCtx {
 #[cfg(test)]
 pub mod tests {
     use crate::{
-        common::{config::TomlConfigLoader, new_peer_id, stun::MockStunInfoCollector},
+        common::{
+            config::{NodeIdentityConfig, TomlConfigLoader},
+            identity::{self, IdentityKey},
+            new_peer_id,
+            stun::MockStunInfoCollector,
+        },
         proto::common::NatType,
     };
 
@@ -475,4 +502,32 @@ pub mod tests {
     pub fn get_mock_global_ctx() -> ArcGlobalCtx {
         get_mock_global_ctx_with_network(None)
     }
+
+    /// A member of the "default" network, whose certificate is signed by owner_key.
+    pub fn get_mock_global_ctx_with_identity(
+        owner_key: &ed25519_dalek::SigningKey,
+        revoked: &[IdentityKey],
+    ) -> ArcGlobalCtx {
+        let (private_key, _) = identity::generate_key_pair();
+        let node_key = identity::decode_signing_key(&private_key)
+            .unwrap()
+            .verifying_key();
+        let cert = identity::issue_membership_cert(owner_key, "default", &node_key, None);
+        let revocation_list = identity::sign_revocation_list(owner_key, "default", 1, revoked);
+
+        let config_fs = TomlConfigLoader::default();
+        config_fs.set_inst_name(format!("test_{}", config_fs.get_id()));
+        config_fs.set_node_identity(Some(NodeIdentityConfig {
+            owner_public_key: identity::encode_key(&owner_key.verifying_key().to_bytes()),
+            private_key,
+            certificate: identity::encode_message(&cert),
+            revocation_list: Some(identity::encode_message(&revocation_list)),
+        }));
+
+        let ctx = Arc::new(GlobalCtx::new(config_fs));
+        ctx.replace_stun_info_collector(Box::new(MockStunInfoCollector {
+            udp_nat_type: NatType::Unknown,
+        }));
+        ctx
+    }
 }
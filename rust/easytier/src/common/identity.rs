@@ -0,0 +1,487 @@
+//! Optional per-node Ed25519 identities.
+//!
+//! The network owner keeps an owner key offline and signs a membership certificate for the
+//! public key of every node. Nodes present the certificate in the handshake and prove they own
+//! the key by signing the nonce of the other side. A node can be kicked out by signing a
+//! revocation list, which is spread to all nodes through the route sync.
+//!
+//! Nodes also attach their certificate and a signature over their peer id to the route info
+//! they announce, so routes to nodes that are not verified members, e.g. announced through a
+//! node that does not check identities, or to revoked nodes are dropped everywhere.
+
+use std::{
+    collections::HashSet,
+    sync::RwLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use base64::{prelude::BASE64_STANDARD, Engine as _};
+use ed25519_dalek::{Signature, Signer as _, SigningKey, Verifier as _, VerifyingKey};
+use prost::Message;
+
+use crate::{
+    common::{config::NodeIdentityConfig, PeerId},
+    proto::peer_rpc::{MembershipCert, MembershipCertBody, RevocationList, RevocationListBody},
+};
+
+pub type IdentityKey = [u8; 32];
+
+pub const IDENTITY_NONCE_LEN: usize = 32;
+
+const CERT_SIGN_CONTEXT: &[u8] = b"easytier membership cert v1\0";
+const REVOCATION_SIGN_CONTEXT: &[u8] = b"easytier revocation list v1\0";
+const PROOF_SIGN_CONTEXT: &[u8] = b"easytier handshake proof v1\0";
+const ROUTE_INFO_SIGN_CONTEXT: &[u8] = b"easytier route info v1\0";
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn signed_data(context: &[u8], body: &[u8]) -> Vec<u8> {
+    let mut data = context.to_vec();
+    data.extend_from_slice(body);
+    data
+}
+
+fn decode_key_bytes(s: &str, what: &str) -> anyhow::Result<IdentityKey> {
+    let bytes = BASE64_STANDARD
+        .decode(s.trim())
+        .with_context(|| format!("{} is not valid base64", what))?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("{} should be 32 bytes", what))
+}
+
+pub fn decode_signing_key(s: &str) -> anyhow::Result<SigningKey> {
+    Ok(SigningKey::from_bytes(&decode_key_bytes(s, "private key")?))
+}
+
+pub fn decode_verifying_key(s: &str) -> anyhow::Result<VerifyingKey> {
+    VerifyingKey::from_bytes(&decode_key_bytes(s, "public key")?)
+        .with_context(|| "invalid ed25519 public key")
+}
+
+pub fn encode_key(key: &IdentityKey) -> String {
+    BASE64_STANDARD.encode(key)
+}
+
+/// Returns a new (private key, public key) pair, both base64 encoded.
+pub fn generate_key_pair() -> (String, String) {
+    let key = SigningKey::from_bytes(&rand::random());
+    (
+        encode_key(&key.to_bytes()),
+        encode_key(&key.verifying_key().to_bytes()),
+    )
+}
+
+/// Certificates and revocation lists are passed around as base64 encoded protobuf.
+pub fn encode_message(msg: &impl Message) -> String {
+    BASE64_STANDARD.encode(msg.encode_to_vec())
+}
+
+pub fn decode_message<M: Message + Default>(s: &str) -> anyhow::Result<M> {
+    let bytes = BASE64_STANDARD
+        .decode(s.trim())
+        .with_context(|| "not valid base64")?;
+    Ok(M::decode(bytes.as_slice())?)
+}
+
+pub fn issue_membership_cert(
+    owner_key: &SigningKey,
+    network_name: &str,
+    node_key: &VerifyingKey,
+    expires_at: Option<u64>,
+) -> MembershipCert {
+    let body = MembershipCertBody {
+        network_name: network_name.to_owned(),
+        node_public_key: node_key.to_bytes().to_vec(),
+        issued_at: now_secs(),
+        expires_at: expires_at.unwrap_or_default(),
+    }
+    .encode_to_vec();
+    let signature = owner_key.sign(&signed_data(CERT_SIGN_CONTEXT, &body));
+    MembershipCert {
+        body,
+        signature: signature.to_bytes().to_vec(),
+    }
+}
+
+/// The list replaces any older one, so it must contain every key that is still revoked.
+pub fn sign_revocation_list(
+    owner_key: &SigningKey,
+    network_name: &str,
+    version: u64,
+    revoked_keys: &[IdentityKey],
+) -> RevocationList {
+    let body = RevocationListBody {
+        network_name: network_name.to_owned(),
+        version,
+        revoked_keys: revoked_keys.iter().map(|k| k.to_vec()).collect(),
+    }
+    .encode_to_vec();
+    let signature = owner_key.sign(&signed_data(REVOCATION_SIGN_CONTEXT, &body));
+    RevocationList {
+        body,
+        signature: signature.to_bytes().to_vec(),
+    }
+}
+
+fn verify_owner_signature(
+    owner_key: &VerifyingKey,
+    context: &[u8],
+    body: &[u8],
+    signature: &[u8],
+) -> anyhow::Result<()> {
+    let signature = Signature::from_slice(signature).with_context(|| "malformed signature")?;
+    owner_key
+        .verify(&signed_data(context, body), &signature)
+        .with_context(|| "not signed by the network owner")
+}
+
+fn proof_data(network_name: &str, peer_id: PeerId, nonce: &[u8]) -> Vec<u8> {
+    let mut data = PROOF_SIGN_CONTEXT.to_vec();
+    data.extend_from_slice(network_name.as_bytes());
+    data.push(0x00);
+    data.extend_from_slice(&peer_id.to_be_bytes());
+    data.extend_from_slice(nonce);
+    data
+}
+
+fn route_info_data(network_name: &str, peer_id: PeerId, peer_route_id: u64) -> Vec<u8> {
+    let mut data = ROUTE_INFO_SIGN_CONTEXT.to_vec();
+    data.extend_from_slice(network_name.as_bytes());
+    data.push(0x00);
+    data.extend_from_slice(&peer_id.to_be_bytes());
+    data.extend_from_slice(&peer_route_id.to_be_bytes());
+    data
+}
+
+#[derive(Debug, Default)]
+struct RevocationState {
+    list: Option<RevocationList>,
+    version: u64,
+    revoked: HashSet<IdentityKey>,
+}
+
+pub struct NodeIdentityManager {
+    network_name: String,
+    owner_key: VerifyingKey,
+    signing_key: SigningKey,
+    cert: MembershipCert,
+
+    revocation: RwLock<RevocationState>,
+    revocation_version: tokio::sync::watch::Sender<u64>,
+}
+
+impl std::fmt::Debug for NodeIdentityManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeIdentityManager")
+            .field("network_name", &self.network_name)
+            .field("public_key", &encode_key(&self.public_key()))
+            .field("revocation_version", &self.revocation_version())
+            .finish()
+    }
+}
+
+impl NodeIdentityManager {
+    pub fn new(config: &NodeIdentityConfig, network_name: &str) -> anyhow::Result<Self> {
+        let owner_key = decode_verifying_key(&config.owner_public_key)
+            .with_context(|| "invalid owner_public_key")?;
+        let signing_key =
+            decode_signing_key(&config.private_key).with_context(|| "invalid private_key")?;
+        let cert = decode_message::<MembershipCert>(&config.certificate)
+            .with_context(|| "invalid certificate")?;
+
+        let ret = Self {
+            network_name: network_name.to_owned(),
+            owner_key,
+            signing_key,
+            cert,
+            revocation: RwLock::new(RevocationState::default()),
+            revocation_version: tokio::sync::watch::channel(0).0,
+        };
+
+        let my_key = ret
+            .verify_cert(&ret.cert)
+            .with_context(|| "certificate is not valid for this network")?;
+        if my_key != ret.public_key() {
+            anyhow::bail!("certificate is issued for another key");
+        }
+
+        if let Some(list) = &config.revocation_list {
+            let list = decode_message::<RevocationList>(list)
+                .with_context(|| "invalid revocation_list")?;
+            ret.update_revocation_list(&list)
+                .with_context(|| "invalid revocation_list")?;
+        }
+
+        Ok(ret)
+    }
+
+    pub fn public_key(&self) -> IdentityKey {
+        self.signing_key.verifying_key().to_bytes()
+    }
+
+    pub fn cert(&self) -> MembershipCert {
+        self.cert.clone()
+    }
+
+    pub fn new_nonce() -> Vec<u8> {
+        rand::random::<[u8; IDENTITY_NONCE_LEN]>().to_vec()
+    }
+
+    /// Checks the certificate of a peer and returns the key it was issued for.
+    pub fn verify_cert(&self, cert: &MembershipCert) -> anyhow::Result<IdentityKey> {
+        verify_owner_signature(
+            &self.owner_key,
+            CERT_SIGN_CONTEXT,
+            &cert.body,
+            &cert.signature,
+        )?;
+        let body = MembershipCertBody::decode(cert.body.as_slice())?;
+        if body.network_name != self.network_name {
+            anyhow::bail!("certificate is issued for network {}", body.network_name);
+        }
+        if body.expires_at != 0 && body.expires_at < now_secs() {
+            anyhow::bail!("certificate expired at {}", body.expires_at);
+        }
+        let key: IdentityKey = body
+            .node_public_key
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("invalid node public key in certificate"))?;
+        if self.is_revoked(&key) {
+            anyhow::bail!("node key {} is revoked", encode_key(&key));
+        }
+        Ok(key)
+    }
+
+    pub fn sign_proof(&self, my_peer_id: PeerId, nonce: &[u8]) -> Vec<u8> {
+        let data = proof_data(&self.network_name, my_peer_id, nonce);
+        self.signing_key.sign(&data).to_bytes().to_vec()
+    }
+
+    pub fn verify_proof(
+        &self,
+        key: &IdentityKey,
+        peer_id: PeerId,
+        nonce: &[u8],
+        proof: &[u8],
+    ) -> anyhow::Result<()> {
+        let key = VerifyingKey::from_bytes(key)?;
+        let signature = Signature::from_slice(proof).with_context(|| "malformed proof")?;
+        key.verify(&proof_data(&self.network_name, peer_id, nonce), &signature)
+            .with_context(|| "identity proof does not match the certificate")
+    }
+
+    pub fn sign_route_info(&self, my_peer_id: PeerId, peer_route_id: u64) -> Vec<u8> {
+        let data = route_info_data(&self.network_name, my_peer_id, peer_route_id);
+        self.signing_key.sign(&data).to_bytes().to_vec()
+    }
+
+    /// Checks the certificate and signature a node attached to its route info, returns the
+    /// key of the node. Fails for missing certificates and revoked or expired ones.
+    pub fn verify_route_info(
+        &self,
+        cert: Option<&MembershipCert>,
+        peer_id: PeerId,
+        peer_route_id: u64,
+        signature: &[u8],
+    ) -> anyhow::Result<IdentityKey> {
+        let Some(cert) = cert else {
+            anyhow::bail!("route info has no membership certificate");
+        };
+        let key = self.verify_cert(cert)?;
+        let signature = Signature::from_slice(signature).with_context(|| "malformed signature")?;
+        VerifyingKey::from_bytes(&key)?
+            .verify(
+                &route_info_data(&self.network_name, peer_id, peer_route_id),
+                &signature,
+            )
+            .with_context(|| "route info signature does not match the certificate")?;
+        Ok(key)
+    }
+
+    pub fn is_revoked(&self, key: &[u8]) -> bool {
+        let Ok(key) = IdentityKey::try_from(key) else {
+            return false;
+        };
+        self.revocation.read().unwrap().revoked.contains(&key)
+    }
+
+    pub fn revocation_version(&self) -> u64 {
+        self.revocation.read().unwrap().version
+    }
+
+    pub fn revocation_list(&self) -> Option<RevocationList> {
+        self.revocation.read().unwrap().list.clone()
+    }
+
+    /// Fires whenever a newer revocation list is accepted.
+    pub fn subscribe_revocation(&self) -> tokio::sync::watch::Receiver<u64> {
+        self.revocation_version.subscribe()
+    }
+
+    /// Returns false if the list is not newer than the current one.
+    pub fn update_revocation_list(&self, list: &RevocationList) -> anyhow::Result<bool> {
+        verify_owner_signature(
+            &self.owner_key,
+            REVOCATION_SIGN_CONTEXT,
+            &list.body,
+            &list.signature,
+        )?;
+        let body = RevocationListBody::decode(list.body.as_slice())?;
+        if body.network_name != self.network_name {
+            anyhow::bail!(
+                "revocation list is issued for network {}",
+                body.network_name
+            );
+        }
+
+        let mut state = self.revocation.write().unwrap();
+        if body.version <= state.version {
+            return Ok(false);
+        }
+        state.revoked = body
+            .revoked_keys
+            .into_iter()
+            .filter_map(|k| IdentityKey::try_from(k).ok())
+            .collect();
+        state.version = body.version;
+        state.list = Some(list.clone());
+        drop(state);
+
+        tracing::info!(version = body.version, "revocation list updated");
+        self.revocation_version.send_replace(body.version);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Owner {
+        key: SigningKey,
+        public_key: String,
+    }
+
+    fn new_owner() -> Owner {
+        let (private_key, public_key) = generate_key_pair();
+        Owner {
+            key: decode_signing_key(&private_key).unwrap(),
+            public_key,
+        }
+    }
+
+    fn new_node(owner: &Owner, network_name: &str) -> NodeIdentityManager {
+        let (private_key, _) = generate_key_pair();
+        let node_key = decode_signing_key(&private_key).unwrap().verifying_key();
+        let cert = issue_membership_cert(&owner.key, network_name, &node_key, None);
+        let config = NodeIdentityConfig {
+            owner_public_key: owner.public_key.clone(),
+            private_key,
+            certificate: encode_message(&cert),
+            revocation_list: None,
+        };
+        NodeIdentityManager::new(&config, network_name).unwrap()
+    }
+
+    #[test]
+    fn membership_cert_and_proof() {
+        let owner = new_owner();
+        let a = new_node(&owner, "net");
+        let b = new_node(&owner, "net");
+
+        let key = b.verify_cert(&a.cert()).unwrap();
+        assert_eq!(key, a.public_key());
+
+        let nonce = NodeIdentityManager::new_nonce();
+        let proof = a.sign_proof(1, &nonce);
+        b.verify_proof(&key, 1, &nonce, &proof).unwrap();
+        // bound to the peer id and the nonce
+        assert!(b.verify_proof(&key, 2, &nonce, &proof).is_err());
+        assert!(b
+            .verify_proof(&key, 1, &NodeIdentityManager::new_nonce(), &proof)
+            .is_err());
+    }
+
+    #[test]
+    fn route_info_signature() {
+        let owner = new_owner();
+        let a = new_node(&owner, "net");
+        let b = new_node(&owner, "net");
+
+        let cert = a.cert();
+        let signature = a.sign_route_info(1, 7);
+        let key = b.verify_route_info(Some(&cert), 1, 7, &signature).unwrap();
+        assert_eq!(key, a.public_key());
+
+        // bound to the peer id and route id, and the certificate is required
+        assert!(b.verify_route_info(Some(&cert), 2, 7, &signature).is_err());
+        assert!(b.verify_route_info(Some(&cert), 1, 8, &signature).is_err());
+        assert!(b.verify_route_info(None, 1, 7, &signature).is_err());
+        // the certificate of another member does not match the signature
+        assert!(b
+            .verify_route_info(Some(&b.cert()), 1, 7, &signature)
+            .is_err());
+
+        let list = sign_revocation_list(&owner.key, "net", 1, &[a.public_key()]);
+        b.update_revocation_list(&list).unwrap();
+        assert!(b.verify_route_info(Some(&cert), 1, 7, &signature).is_err());
+    }
+
+    #[test]
+    fn reject_foreign_certs() {
+        let owner = new_owner();
+        let other_owner = new_owner();
+        let a = new_node(&owner, "net");
+
+        // another owner, or the same owner for another network
+        assert!(a
+            .verify_cert(&new_node(&other_owner, "net").cert())
+            .is_err());
+        assert!(a.verify_cert(&new_node(&owner, "net2").cert()).is_err());
+
+        let node_key = SigningKey::from_bytes(&rand::random()).verifying_key();
+        let expired = issue_membership_cert(&owner.key, "net", &node_key, Some(1));
+        assert!(a.verify_cert(&expired).is_err());
+
+        let mut tampered = a.cert();
+        tampered.body[0] ^= 1;
+        assert!(a.verify_cert(&tampered).is_err());
+    }
+
+    #[test]
+    fn revocation_list_update() {
+        let owner = new_owner();
+        let a = new_node(&owner, "net");
+        let b = new_node(&owner, "net");
+        let recv = a.subscribe_revocation();
+
+        let list = sign_revocation_list(&owner.key, "net", 2, &[b.public_key()]);
+        assert!(a.update_revocation_list(&list).unwrap());
+        assert!(recv.has_changed().unwrap());
+        assert!(a.is_revoked(&b.public_key()));
+        assert!(a.verify_cert(&b.cert()).is_err());
+
+        // older or same versions are ignored
+        let old = sign_revocation_list(&owner.key, "net", 1, &[]);
+        assert!(!a.update_revocation_list(&old).unwrap());
+        assert!(!a.update_revocation_list(&list).unwrap());
+        assert!(a.is_revoked(&b.public_key()));
+
+        // only the owner can revoke
+        let other_owner = new_owner();
+        let forged = sign_revocation_list(&other_owner.key, "net", 3, &[]);
+        assert!(a.update_revocation_list(&forged).is_err());
+
+        let unrevoke = sign_revocation_list(&owner.key, "net", 3, &[]);
+        assert!(a.update_revocation_list(&unrevoke).unwrap());
+        assert!(a.verify_cert(&b.cert()).is_ok());
+        assert_eq!(a.revocation_list(), Some(unrevoke));
+    }
+}
@@ -312,6 +312,10 @@ impl NetlinkIfConfiger {
     }
 
     fn list_routes() -> Result<Vec<RouteMessage>, Error> {
+        Self::list_routes_of_family(AddressFamily::Inet)
+    }
+
+    fn list_routes_of_family(family: AddressFamily) -> Result<Vec<RouteMessage>, Error> {
         let mut message = RouteMessage::default();
 
         message.header.table = RouteHeader::RT_TABLE_UNSPEC;
@@ -320,7 +324,7 @@ impl NetlinkIfConfiger {
         message.header.scope = RouteScope::Universe;
         message.header.kind = RouteType::Unicast;
 
-        message.header.address_family = AddressFamily::Inet;
+        message.header.address_family = family;
         message.header.destination_prefix_length = 0;
         message.header.source_prefix_length = 0;
 
@@ -367,6 +371,66 @@ impl NetlinkIfConfiger {
 
         Ok(ret_vec)
     }
+
+    fn list_main_table_routes() -> Result<Vec<RouteMessage>, Error> {
+        let mut routes = Self::list_routes_of_family(AddressFamily::Inet)?;
+        routes.extend(Self::list_routes_of_family(AddressFamily::Inet6)?);
+        routes.retain(|msg| {
+            msg.header.table == RouteHeader::RT_TABLE_MAIN && msg.header.kind == RouteType::Unicast
+        });
+        Ok(routes)
+    }
+
+    /// linux has no per interface metric like windows, the lowest metric of the routes
+    /// through an interface is used instead. interfaces without routes get 0.
+    pub fn list_interface_metrics() -> Result<Vec<(String, u32)>, Error> {
+        let routes: Vec<Route> = Self::list_main_table_routes()?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        let interfaces =
+            nix::net::if_::if_nameindex().with_context(|| "failed to list interfaces")?;
+        let mut ret = vec![];
+        for iface in interfaces.iter() {
+            let metric = routes
+                .iter()
+                .filter(|r| r.ifindex == Some(iface.index()))
+                .filter_map(|r| r.metric)
+                .min()
+                .unwrap_or(0);
+            ret.push((iface.name().to_string_lossy().to_string(), metric));
+        }
+        Ok(ret)
+    }
+
+    /// change the metric of all main table routes through the interface. the metric is part
+    /// of the route key, so each route is added again with the new metric and the old one removed.
+    pub fn set_interface_metric(name: &str, metric: u32) -> Result<(), Error> {
+        let ifidx = Self::get_interface_index(name)?;
+
+        for msg in Self::list_main_table_routes()? {
+            let route: Route = msg.clone().into();
+            if route.ifindex != Some(ifidx) || route.metric == Some(metric) {
+                continue;
+            }
+
+            let mut new_msg = msg.clone();
+            new_msg
+                .attributes
+                .retain(|attr| !matches!(attr, RouteAttribute::Priority(_)));
+            new_msg.attributes.push(RouteAttribute::Priority(metric));
+            match send_netlink_req_and_wait_one_resp(RouteNetlinkMessage::NewRoute(new_msg), false)
+            {
+                Ok(_) => {}
+                Err(Error::IOError(e)) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e),
+            }
+            send_netlink_req_and_wait_one_resp(RouteNetlinkMessage::DelRoute(msg), true)?;
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -703,4 +767,37 @@ mod tests {
             .collect::<Vec<_>>();
         assert!(!routes.contains(&IpAddr::V4("10.5.5.0".parse().unwrap())));
     }
+
+    #[serial_test::serial]
+    #[tokio::test]
+    async fn interface_metric_test() {
+        let _prepare_env = PrepareEnv::new();
+        let ifcfg = NetlinkIfConfiger {};
+        ifcfg.set_link_status(DUMMY_IFACE_NAME, true).await.unwrap();
+        ifcfg
+            .add_ipv4_route(DUMMY_IFACE_NAME, "10.6.6.0".parse().unwrap(), 24, Some(300))
+            .await
+            .unwrap();
+
+        let metric_of_dummy = || {
+            NetlinkIfConfiger::list_interface_metrics()
+                .unwrap()
+                .into_iter()
+                .find(|(name, _)| name == DUMMY_IFACE_NAME)
+                .map(|(_, metric)| metric)
+        };
+        assert_eq!(metric_of_dummy(), Some(300));
+
+        NetlinkIfConfiger::set_interface_metric(DUMMY_IFACE_NAME, 5).unwrap();
+        assert_eq!(metric_of_dummy(), Some(5));
+
+        let routes = NetlinkIfConfiger::list_routes()
+            .unwrap()
+            .into_iter()
+            .map(Route::from)
+            .filter(|x| x.destination == IpAddr::V4("10.6.6.0".parse().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].metric, Some(5));
+    }
 }
@@ -68,6 +68,13 @@ pub enum MetricName {
     CompressionBytesTxAfter,
 
     TcpProxyConnect,
+
+    /// Socks5 portal connections and udp associations
+    Socks5Connections,
+    /// Socks5 portal bytes sent to destinations
+    Socks5BytesTx,
+    /// Socks5 portal bytes received from destinations
+    Socks5BytesRx,
 }
 
 impl fmt::Display for MetricName {
@@ -116,6 +123,10 @@ impl fmt::Display for MetricName {
             MetricName::CompressionBytesTxAfter => write!(f, "compression_bytes_tx_after"),
 
             MetricName::TcpProxyConnect => write!(f, "tcp_proxy_connect"),
+
+            MetricName::Socks5Connections => write!(f, "socks5_connections"),
+            MetricName::Socks5BytesTx => write!(f, "socks5_bytes_tx"),
+            MetricName::Socks5BytesRx => write!(f, "socks5_bytes_rx"),
         }
     }
 }
@@ -147,6 +158,8 @@ pub enum LabelType {
     DstIp(String),
     /// Mapped Dst Ip
     MappedDstIp(String),
+    /// User name
+    UserName(String),
 }
 
 impl fmt::Display for LabelType {
@@ -164,6 +177,7 @@ impl fmt::Display for LabelType {
             LabelType::Status(status) => write!(f, "status={}", status),
             LabelType::DstIp(ip) => write!(f, "dst_ip={}", ip),
             LabelType::MappedDstIp(ip) => write!(f, "mapped_dst_ip={}", ip),
+            LabelType::UserName(name) => write!(f, "user_name={}", name),
         }
     }
 }
@@ -183,6 +197,7 @@ impl LabelType {
             LabelType::Status(_) => "status",
             LabelType::DstIp(_) => "dst_ip",
             LabelType::MappedDstIp(_) => "mapped_dst_ip",
+            LabelType::UserName(_) => "user_name",
         }
     }
 
@@ -200,6 +215,7 @@ impl LabelType {
             LabelType::Status(status) => status.clone(),
             LabelType::DstIp(ip) => ip.clone(),
             LabelType::MappedDstIp(ip) => ip.clone(),
+            LabelType::UserName(name) => name.clone(),
         }
     }
 }
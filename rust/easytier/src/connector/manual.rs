@@ -23,7 +23,7 @@ use crate::{
         },
         rpc_types::{self, controller::BaseController},
     },
-    tunnel::{IpVersion, TunnelConnector},
+    tunnel::{proxy::redact_url, IpVersion, TunnelConnector},
 };
 
 use crate::{
@@ -44,6 +44,13 @@ use super::create_connector_by_url;
 
 type ConnectorMap = Arc<DashSet<String>>;
 
+// connector urls may carry the credentials of an upstream proxy, keep them out of logs and events
+fn url_for_log(url: &str) -> String {
+    url::Url::parse(url)
+        .map(|url| redact_url(&url).to_string())
+        .unwrap_or_else(|_| url.to_owned())
+}
+
 #[derive(Debug, Clone)]
 struct ReconnResult {
     dead_url: String,
@@ -102,7 +109,7 @@ impl ManualConnectorManager {
     where
         T: TunnelConnector + 'static,
     {
-        tracing::info!("add_connector: {}", connector.remote_url());
+        tracing::info!("add_connector: {}", redact_url(&connector.remote_url()));
         self.data.connectors.insert(connector.remote_url().into());
     }
 
@@ -112,7 +119,7 @@ impl ManualConnectorManager {
     }
 
     pub async fn remove_connector(&self, url: url::Url) -> Result<(), Error> {
-        tracing::info!("remove_connector: {}", url);
+        tracing::info!("remove_connector: {}", redact_url(&url));
         let url = url.into();
         if !self
             .list_connectors()
@@ -126,6 +133,22 @@ impl ManualConnectorManager {
         Ok(())
     }
 
+    /// remove all connectors so they are no longer reconnected, returns the number removed.
+    pub fn clear_connectors(&self) -> usize {
+        let urls: Vec<String> = self
+            .data
+            .connectors
+            .iter()
+            .map(|x| x.key().clone())
+            .chain(self.data.reconnecting.iter().map(|x| x.key().clone()))
+            .collect();
+        for url in urls.iter() {
+            self.data.removed_conn_urls.insert(url.clone());
+        }
+        Self::handle_remove_connector(self.data.clone());
+        urls.len()
+    }
+
     pub async fn list_connectors(&self) -> Vec<Connector> {
         let conn_urls: BTreeSet<String> = self
             .data
@@ -279,14 +302,17 @@ impl ManualConnectorManager {
         for it in data.removed_conn_urls.iter() {
             let url = it.key();
             if data.connectors.remove(url).is_some() {
-                tracing::warn!("connector: {}, removed", url);
+                tracing::warn!("connector: {}, removed", url_for_log(url));
                 continue;
             } else if data.reconnecting.contains(url) {
-                tracing::warn!("connector: {}, reconnecting, remove later.", url);
+                tracing::warn!(
+                    "connector: {}, reconnecting, remove later.",
+                    url_for_log(url)
+                );
                 remove_later.insert(url.clone());
                 continue;
             } else {
-                tracing::warn!("connector: {}, not found", url);
+                tracing::warn!("connector: {}, not found", url_for_log(url));
             }
         }
         data.removed_conn_urls.clear();
@@ -316,7 +342,9 @@ impl ManualConnectorManager {
             create_connector_by_url(&dead_url, &data.global_ctx.clone(), ip_version).await?;
 
         data.global_ctx
-            .issue_event(GlobalCtxEvent::Connecting(connector.remote_url().clone()));
+            .issue_event(GlobalCtxEvent::Connecting(redact_url(
+                &connector.remote_url(),
+            )));
         tracing::info!("reconnect try connect... conn: {:?}", connector);
         let Some(pm) = data.peer_manager.upgrade() else {
             return Err(Error::AnyhowError(anyhow::anyhow!(
@@ -325,9 +353,15 @@ impl ManualConnectorManager {
         };
 
         let (peer_id, conn_id) = pm.try_direct_connect(connector).await?;
-        tracing::info!("reconnect succ: {} {} {}", peer_id, conn_id, dead_url);
+        tracing::info!(
+            "reconnect succ: {} {} {}",
+            peer_id,
+            conn_id,
+            url_for_log(&dead_url)
+        );
         Ok(ReconnResult {
-            dead_url,
+            // only logged
+            dead_url: url_for_log(&dead_url),
             peer_id,
             conn_id,
         })
@@ -337,11 +371,12 @@ impl ManualConnectorManager {
         data: Arc<ConnectorManagerData>,
         dead_url: String,
     ) -> Result<ReconnResult, Error> {
-        tracing::info!("reconnect: {}", dead_url);
+        tracing::info!("reconnect: {}", url_for_log(&dead_url));
 
         let mut ip_versions = vec![];
-        let u = url::Url::parse(&dead_url)
-            .with_context(|| format!("failed to parse connector url {:?}", dead_url))?;
+        let u = url::Url::parse(&dead_url).with_context(|| {
+            format!("failed to parse connector url {:?}", url_for_log(&dead_url))
+        })?;
         if u.scheme() == "ring" || u.scheme() == "txt" || u.scheme() == "srv" {
             ip_versions.push(IpVersion::Both);
         } else {
@@ -349,7 +384,7 @@ impl ManualConnectorManager {
                 Ok(addrs) => addrs,
                 Err(e) => {
                     data.global_ctx.issue_event(GlobalCtxEvent::ConnectError(
-                        dead_url.clone(),
+                        redact_url(&u).to_string(),
                         format!("{:?}", IpVersion::Both),
                         format!("{:?}", e),
                     ));
@@ -359,7 +394,7 @@ impl ManualConnectorManager {
                     )));
                 }
             };
-            tracing::info!(?addrs, ?dead_url, "get ip from url done");
+            tracing::info!(?addrs, dead_url = %redact_url(&u), "get ip from url done");
             let mut has_ipv4 = false;
             let mut has_ipv6 = false;
             for addr in addrs {
@@ -390,7 +425,7 @@ impl ManualConnectorManager {
                 Self::conn_reconnect_with_ip_version(data.clone(), dead_url.clone(), ip_version),
             )
             .await;
-            tracing::info!("reconnect: {} done, ret: {:?}", dead_url, ret);
+            tracing::info!("reconnect: {} done, ret: {:?}", redact_url(&u), ret);
 
             match ret {
                 Ok(Ok(_)) => {
@@ -410,7 +445,7 @@ impl ManualConnectorManager {
 
             // 发送事件（只有在未 break 时才执行）
             data.global_ctx.issue_event(GlobalCtxEvent::ConnectError(
-                dead_url.clone(),
+                redact_url(&u).to_string(),
                 format!("{:?}", ip_version),
                 format!("{:?}", reconn_ret),
             ));
@@ -9,11 +9,14 @@ use http_connector::HttpTunnelConnector;
 use crate::tunnel::quic::QUICTunnelConnector;
 #[cfg(feature = "wireguard")]
 use crate::tunnel::wireguard::{WgConfig, WgTunnelConnector};
+#[cfg(unix)]
+use crate::tunnel::{stdio::StdioTunnelConnector, unix::UnixSocketTunnelConnector};
 use crate::{
     common::{error::Error, global_ctx::ArcGlobalCtx, network::IPCollector},
     tunnel::{
-        check_scheme_and_get_socket_addr, ring::RingTunnelConnector, tcp::TcpTunnelConnector,
-        udp::UdpTunnelConnector, IpVersion, TunnelConnector,
+        check_scheme_and_get_socket_addr, obfs::ObfsConfig, proxy::UpstreamProxy,
+        ring::RingTunnelConnector, tcp::TcpTunnelConnector, udp::UdpTunnelConnector, IpVersion,
+        TunnelConnector,
     },
 };
 
@@ -52,24 +55,41 @@ async fn set_bind_addr_for_peer_connector(
     let _ = connector;
 }
 
+fn get_upstream_proxy(
+    url: &url::Url,
+    global_ctx: &ArcGlobalCtx,
+) -> Result<Option<UpstreamProxy>, Error> {
+    Ok(UpstreamProxy::for_connector(
+        url,
+        global_ctx.config.get_upstream_proxy().as_ref(),
+    )?)
+}
+
 pub async fn create_connector_by_url(
     url: &str,
     global_ctx: &ArcGlobalCtx,
     ip_version: IpVersion,
 ) -> Result<Box<dyn TunnelConnector + 'static>, Error> {
     let url = url::Url::parse(url).map_err(|_| Error::InvalidUrl(url.to_owned()))?;
+    ObfsConfig::validate_url(&url)?;
     let mut connector: Box<dyn TunnelConnector + 'static> = match url.scheme() {
         "tcp" => {
-            let dst_addr =
-                check_scheme_and_get_socket_addr::<SocketAddr>(&url, "tcp", ip_version).await?;
-            let mut connector = TcpTunnelConnector::new(url);
-            if global_ctx.config.get_flags().bind_device {
-                set_bind_addr_for_peer_connector(
-                    &mut connector,
-                    dst_addr.is_ipv4(),
-                    &global_ctx.get_ip_collector(),
-                )
-                .await;
+            let proxy = get_upstream_proxy(&url, global_ctx)?;
+            let mut connector = TcpTunnelConnector::new(url.clone());
+            if proxy.is_some() {
+                // the proxy resolves the target, so skip resolving and binding here
+                connector.set_upstream_proxy(proxy);
+            } else {
+                let dst_addr =
+                    check_scheme_and_get_socket_addr::<SocketAddr>(&url, "tcp", ip_version).await?;
+                if global_ctx.config.get_flags().bind_device {
+                    set_bind_addr_for_peer_connector(
+                        &mut connector,
+                        dst_addr.is_ipv4(),
+                        &global_ctx.get_ip_collector(),
+                    )
+                    .await;
+                }
             }
             Box::new(connector)
         }
@@ -88,7 +108,9 @@ pub async fn create_connector_by_url(
             Box::new(connector)
         }
         "http" | "https" => {
-            let connector = HttpTunnelConnector::new(url, global_ctx.clone());
+            let proxy = get_upstream_proxy(&url, global_ctx)?;
+            let mut connector = HttpTunnelConnector::new(url, global_ctx.clone());
+            connector.set_upstream_proxy(proxy);
             Box::new(connector)
         }
         "ring" => {
@@ -101,6 +123,9 @@ pub async fn create_connector_by_url(
             let dst_addr =
                 check_scheme_and_get_socket_addr::<SocketAddr>(&url, "quic", ip_version).await?;
             let mut connector = QUICTunnelConnector::new(url);
+            if let Some(tls) = global_ctx.config.get_tls_config() {
+                connector.set_tls_config(tls);
+            }
             if global_ctx.config.get_flags().bind_device {
                 set_bind_addr_for_peer_connector(
                     &mut connector,
@@ -134,18 +159,53 @@ pub async fn create_connector_by_url(
         #[cfg(feature = "websocket")]
         "ws" | "wss" => {
             use crate::tunnel::FromUrl;
-            let dst_addr = SocketAddr::from_url(url.clone(), ip_version).await?;
-            let mut connector = crate::tunnel::websocket::WSTunnelConnector::new(url);
-            if global_ctx.config.get_flags().bind_device {
-                set_bind_addr_for_peer_connector(
-                    &mut connector,
-                    dst_addr.is_ipv4(),
-                    &global_ctx.get_ip_collector(),
-                )
-                .await;
+            let proxy = get_upstream_proxy(&url, global_ctx)?;
+            let mut connector = crate::tunnel::websocket::WSTunnelConnector::new(url.clone());
+            if let Some(tls) = global_ctx.config.get_tls_config() {
+                connector.set_tls_config(tls);
+            }
+            if proxy.is_some() {
+                connector.set_upstream_proxy(proxy);
+            } else {
+                let dst_addr = SocketAddr::from_url(url, ip_version).await?;
+                if global_ctx.config.get_flags().bind_device {
+                    set_bind_addr_for_peer_connector(
+                        &mut connector,
+                        dst_addr.is_ipv4(),
+                        &global_ctx.get_ip_collector(),
+                    )
+                    .await;
+                }
+            }
+            Box::new(connector)
+        }
+        #[cfg(feature = "tls")]
+        "tls" => {
+            let proxy = get_upstream_proxy(&url, global_ctx)?;
+            let mut connector = crate::tunnel::tcp_tls::TlsTunnelConnector::new(url.clone());
+            if let Some(tls) = global_ctx.config.get_tls_config() {
+                connector.set_tls_config(tls);
+            }
+            if proxy.is_some() {
+                connector.set_upstream_proxy(proxy);
+            } else {
+                let dst_addr =
+                    check_scheme_and_get_socket_addr::<SocketAddr>(&url, "tls", ip_version).await?;
+                if global_ctx.config.get_flags().bind_device {
+                    set_bind_addr_for_peer_connector(
+                        &mut connector,
+                        dst_addr.is_ipv4(),
+                        &global_ctx.get_ip_collector(),
+                    )
+                    .await;
+                }
             }
             Box::new(connector)
         }
+        #[cfg(unix)]
+        "unix" => Box::new(UnixSocketTunnelConnector::new(url)),
+        #[cfg(unix)]
+        "stdio" | "fd" => Box::new(StdioTunnelConnector::new(url)),
         "txt" | "srv" => {
             if url.host_str().is_none() {
                 return Err(Error::InvalidUrl(format!(
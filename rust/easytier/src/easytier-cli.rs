@@ -4,7 +4,7 @@ use std::{
     net::{IpAddr, SocketAddr},
     path::PathBuf,
     str::FromStr,
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
     vec,
 };
 
@@ -32,9 +32,9 @@ use easytier::{
             ConnectorManageRpc, ConnectorManageRpcClientFactory, DumpRouteRequest,
             GetAclStatsRequest, GetLoggerConfigRequest, GetPrometheusStatsRequest, GetStatsRequest,
             GetVpnPortalInfoRequest, GetWhitelistRequest, ListConnectorRequest,
-            ListForeignNetworkRequest, ListGlobalForeignNetworkRequest, ListMappedListenerRequest,
-            ListPeerRequest, ListPeerResponse, ListPortForwardRequest, ListRouteRequest,
-            ListRouteResponse, LogLevel, LoggerRpc, LoggerRpcClientFactory,
+            ListExitFlowRequest, ListForeignNetworkRequest, ListGlobalForeignNetworkRequest,
+            ListMappedListenerRequest, ListPeerRequest, ListPeerResponse, ListPortForwardRequest,
+            ListRouteRequest, ListRouteResponse, LogLevel, LoggerRpc, LoggerRpcClientFactory,
             ManageMappedListenerRequest, MappedListenerManageAction, MappedListenerManageRpc,
             MappedListenerManageRpcClientFactory, NodeInfo, PeerManageRpc,
             PeerManageRpcClientFactory, PortForwardManageRpc, PortForwardManageRpcClientFactory,
@@ -43,7 +43,7 @@ use easytier::{
             TcpProxyEntryTransportType, TcpProxyRpc, TcpProxyRpcClientFactory, VpnPortalRpc,
             VpnPortalRpcClientFactory,
         },
-        common::{NatType, SocketType},
+        common::NatType,
         peer_rpc::{GetGlobalPeerMapRequest, PeerCenterRpc, PeerCenterRpcClientFactory},
         rpc_impl::standalone::StandAloneClient,
         rpc_types::controller::BaseController,
@@ -109,6 +109,8 @@ enum SubCommand {
     Stats(StatsArgs),
     #[command(about = "manage logger configuration")]
     Logger(LoggerArgs),
+    #[command(about = "generate node identity keys, certificates and revocation lists")]
+    Identity(IdentityArgs),
     #[command(about = t!("core_clap.generate_completions").to_string())]
     GenAutocomplete { shell: Shell },
 }
@@ -144,6 +146,8 @@ struct RouteArgs {
 enum RouteSubCommand {
     List,
     Dump,
+    /// show exit node health and the flows pinned to them
+    Exit,
 }
 
 #[derive(Args, Debug)]
@@ -218,16 +222,31 @@ enum PortForwardSubCommand {
     Add {
         #[arg(help = "Protocol (tcp/udp)")]
         protocol: String,
-        #[arg(help = "Local bind address (e.g., 0.0.0.0:8080)")]
+        #[arg(
+            help = "Local bind address or port range (e.g., 0.0.0.0:8080 or 0.0.0.0:27015-27030)"
+        )]
         bind_addr: String,
-        #[arg(help = "Destination address (e.g., 10.1.1.1:80)")]
+        #[arg(
+            help = "Destination address, the start of the range for a port range (e.g., 10.1.1.1:80)"
+        )]
         dst_addr: String,
+        #[arg(
+            long,
+            help = "Close udp sessions idle for this many seconds (default 600)"
+        )]
+        udp_idle_timeout: Option<u64>,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            help = "Only accept clients from these cidrs (e.g., 10.126.126.0/24)"
+        )]
+        allow: Vec<String>,
     },
     /// Remove port forward rule
     Remove {
         #[arg(help = "Protocol (tcp/udp)")]
         protocol: String,
-        #[arg(help = "Local bind address (e.g., 0.0.0.0:8080)")]
+        #[arg(help = "Local bind address or port range (e.g., 0.0.0.0:8080)")]
         bind_addr: String,
         #[arg(help = "Optional Destination address (e.g., 10.1.1.1:80)")]
         dst_addr: Option<String>,
@@ -293,6 +312,40 @@ enum LoggerSubCommand {
     },
 }
 
+#[derive(Args, Debug)]
+struct IdentityArgs {
+    #[command(subcommand)]
+    sub_command: IdentitySubCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum IdentitySubCommand {
+    /// Generate a new ed25519 key pair, for the network owner or a node
+    GenKey,
+    /// Sign a membership certificate for a node with the owner key
+    IssueCert {
+        #[arg(long, help = "private key of the network owner")]
+        owner_key: String,
+        #[arg(long, help = "network name the certificate is valid for")]
+        network_name: String,
+        #[arg(long, help = "public key of the node")]
+        node_public_key: String,
+        #[arg(long, help = "days until the certificate expires, never by default")]
+        valid_days: Option<u64>,
+    },
+    /// Sign a revocation list with the owner key, it replaces all older lists
+    Revoke {
+        #[arg(long, help = "private key of the network owner")]
+        owner_key: String,
+        #[arg(long, help = "network name the list is valid for")]
+        network_name: String,
+        #[arg(long, help = "list version, must grow, current unix time by default")]
+        version: Option<u64>,
+        #[arg(help = "public keys of all revoked nodes")]
+        node_public_keys: Vec<String>,
+    },
+}
+
 #[derive(Args, Debug)]
 struct ServiceArgs {
     #[arg(short, long, default_value = env!("CARGO_PKG_NAME"), help = "service name")]
@@ -666,6 +719,67 @@ impl CommandHandler<'_> {
         Ok(())
     }
 
+    async fn handle_route_exit(&self) -> Result<(), Error> {
+        let client = self.get_peer_manager_client().await?;
+        let request = ListExitFlowRequest::default();
+        let response = client
+            .list_exit_flow(BaseController::default(), request)
+            .await?;
+
+        if self.verbose || *self.output_format == OutputFormat::Json {
+            println!("{}", serde_json::to_string_pretty(&response)?);
+            return Ok(());
+        }
+
+        #[derive(tabled::Tabled, serde::Serialize)]
+        struct ExitNodeTableItem {
+            exit_node: String,
+            reachable: bool,
+            healthy: bool,
+            rtt_ms: String,
+            failures: u32,
+        }
+
+        #[derive(tabled::Tabled, serde::Serialize)]
+        struct ExitFlowTableItem {
+            protocol: String,
+            src: String,
+            dst: String,
+            exit_node: String,
+            idle_secs: u64,
+        }
+
+        let exit_nodes: Vec<ExitNodeTableItem> = response
+            .exit_nodes
+            .into_iter()
+            .map(|node| ExitNodeTableItem {
+                exit_node: node.exit_node,
+                reachable: node.reachable,
+                healthy: node.healthy,
+                rtt_ms: node
+                    .rtt_us
+                    .map(|rtt| format!("{:.3}", rtt as f64 / 1000.0))
+                    .unwrap_or_else(|| "-".to_string()),
+                failures: node.consecutive_failures,
+            })
+            .collect();
+        print_output(&exit_nodes, self.output_format)?;
+
+        let flows: Vec<ExitFlowTableItem> = response
+            .flows
+            .into_iter()
+            .map(|flow| ExitFlowTableItem {
+                protocol: flow.protocol.to_uppercase(),
+                src: flow.src,
+                dst: flow.dst,
+                exit_node: flow.exit_node,
+                idle_secs: flow.idle_ms / 1000,
+            })
+            .collect();
+        print_output(&flows, self.output_format)?;
+        Ok(())
+    }
+
     async fn handle_foreign_network_list(&self) -> Result<(), Error> {
         let client = self.get_peer_manager_client().await?;
         let request = ListForeignNetworkRequest::default();
@@ -969,33 +1083,60 @@ impl CommandHandler<'_> {
         Ok(url)
     }
 
+    // parses `ip:port` or `ip:start-end`
+    fn parse_port_forward_bind_addr(bind_addr: &str) -> Result<(SocketAddr, Option<u16>), Error> {
+        if let Ok(addr) = bind_addr.parse() {
+            return Ok((addr, None));
+        }
+        let (addr, end) = bind_addr
+            .rsplit_once('-')
+            .ok_or_else(|| anyhow::anyhow!("Invalid bind address: {}", bind_addr))?;
+        let addr: SocketAddr = addr
+            .parse()
+            .with_context(|| format!("Invalid bind address: {}", bind_addr))?;
+        let end: u16 = end
+            .parse()
+            .with_context(|| format!("Invalid bind port range end: {}", bind_addr))?;
+        Ok((addr, Some(end)))
+    }
+
     async fn handle_port_forward_add(
         &self,
         protocol: &str,
         bind_addr: &str,
         dst_addr: &str,
+        udp_idle_timeout: Option<u64>,
+        allow: &[String],
     ) -> Result<(), Error> {
-        let bind_addr: std::net::SocketAddr = bind_addr
-            .parse()
-            .with_context(|| format!("Invalid bind address: {}", bind_addr))?;
+        let (bind_addr, bind_port_end) = Self::parse_port_forward_bind_addr(bind_addr)?;
         let dst_addr: std::net::SocketAddr = dst_addr
             .parse()
             .with_context(|| format!("Invalid destination address: {}", dst_addr))?;
+        let allowed_sources = allow
+            .iter()
+            .map(|s| {
+                s.parse()
+                    .with_context(|| format!("Invalid allowed source cidr: {}", s))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
         if protocol != "tcp" && protocol != "udp" {
             return Err(anyhow::anyhow!("Protocol must be 'tcp' or 'udp'"));
         }
 
+        let cfg = PortForwardConfig {
+            proto: protocol.to_string(),
+            bind_addr,
+            dst_addr,
+            bind_port_end,
+            udp_idle_timeout,
+            allowed_sources,
+        };
+        cfg.addr_pairs()?;
+
         let client = self.get_port_forward_manager_client().await?;
         let request = AddPortForwardRequest {
-            cfg: Some(
-                PortForwardConfig {
-                    proto: protocol.to_string(),
-                    bind_addr,
-                    dst_addr,
-                }
-                .into(),
-            ),
+            cfg: Some(cfg.into()),
         };
 
         client
@@ -1014,9 +1155,7 @@ impl CommandHandler<'_> {
         bind_addr: &str,
         dst_addr: Option<&str>,
     ) -> Result<(), Error> {
-        let bind_addr: std::net::SocketAddr = bind_addr
-            .parse()
-            .with_context(|| format!("Invalid bind address: {}", bind_addr))?;
+        let (bind_addr, _) = Self::parse_port_forward_bind_addr(bind_addr)?;
 
         if protocol != "tcp" && protocol != "udp" {
             return Err(anyhow::anyhow!("Protocol must be 'tcp' or 'udp'"));
@@ -1031,6 +1170,7 @@ impl CommandHandler<'_> {
                     dst_addr: dst_addr
                         .map(|s| s.parse::<SocketAddr>().unwrap())
                         .unwrap_or("0.0.0.0:0".parse::<SocketAddr>().unwrap()),
+                    ..Default::default()
                 }
                 .into(),
             ),
@@ -1060,26 +1200,56 @@ impl CommandHandler<'_> {
             protocol: String,
             bind_addr: String,
             dst_addr: String,
+            allowed_sources: String,
+            connections: String,
+            rx_bytes: String,
+            tx_bytes: String,
+            rejected: u64,
         }
 
+        let mut stats = response.stats.into_iter();
         let items: Vec<PortForwardTableItem> = response
             .cfgs
             .into_iter()
-            .map(|rule| PortForwardTableItem {
-                protocol: format!(
-                    "{:?}",
-                    SocketType::try_from(rule.socket_type).unwrap_or(SocketType::Tcp)
-                ),
-                bind_addr: rule
-                    .bind_addr
-                    .map(|addr| addr.to_string())
-                    .unwrap_or_default(),
-                dst_addr: rule
-                    .dst_addr
-                    .map(|addr| addr.to_string())
-                    .unwrap_or_default(),
+            .map(|rule| {
+                let stats = stats.next().unwrap_or_default();
+                let rule = PortForwardConfig::try_from(rule)?;
+                let (bind_addr, dst_addr) = match rule.bind_port_end {
+                    Some(end) => {
+                        let dst_end = rule
+                            .dst_addr
+                            .port()
+                            .saturating_add(end.saturating_sub(rule.bind_addr.port()));
+                        (
+                            format!("{}-{}", rule.bind_addr, end),
+                            format!("{}-{}", rule.dst_addr, dst_end),
+                        )
+                    }
+                    None => (rule.bind_addr.to_string(), rule.dst_addr.to_string()),
+                };
+                Ok(PortForwardTableItem {
+                    protocol: rule.proto.to_uppercase(),
+                    bind_addr,
+                    dst_addr,
+                    allowed_sources: if rule.allowed_sources.is_empty() {
+                        "*".to_string()
+                    } else {
+                        rule.allowed_sources
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    },
+                    connections: format!(
+                        "{}/{}",
+                        stats.active_connections, stats.total_connections
+                    ),
+                    rx_bytes: format_size(stats.rx_bytes, humansize::DECIMAL),
+                    tx_bytes: format_size(stats.tx_bytes, humansize::DECIMAL),
+                    rejected: stats.rejected,
+                })
             })
-            .collect();
+            .collect::<Result<_, Error>>()?;
 
         print_output(&items, self.output_format)?;
         Ok(())
@@ -1570,6 +1740,52 @@ where
     Ok(())
 }
 
+fn handle_identity_command(sub_command: IdentitySubCommand) -> Result<(), Error> {
+    use easytier::common::identity::{
+        decode_signing_key, decode_verifying_key, encode_message, generate_key_pair,
+        issue_membership_cert, sign_revocation_list,
+    };
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    match sub_command {
+        IdentitySubCommand::GenKey => {
+            let (private_key, public_key) = generate_key_pair();
+            println!("private_key = \"{}\"", private_key);
+            println!("public_key = \"{}\"", public_key);
+        }
+        IdentitySubCommand::IssueCert {
+            owner_key,
+            network_name,
+            node_public_key,
+            valid_days,
+        } => {
+            let owner_key = decode_signing_key(&owner_key).context("invalid owner key")?;
+            let node_key =
+                decode_verifying_key(&node_public_key).context("invalid node public key")?;
+            let expires_at = valid_days.map(|days| now + days * 24 * 3600);
+            let cert = issue_membership_cert(&owner_key, &network_name, &node_key, expires_at);
+            println!("certificate = \"{}\"", encode_message(&cert));
+        }
+        IdentitySubCommand::Revoke {
+            owner_key,
+            network_name,
+            version,
+            node_public_keys,
+        } => {
+            let owner_key = decode_signing_key(&owner_key).context("invalid owner key")?;
+            let revoked = node_public_keys
+                .iter()
+                .map(|k| decode_verifying_key(k).map(|k| k.to_bytes()))
+                .collect::<Result<Vec<_>, _>>()
+                .context("invalid node public key")?;
+            let list =
+                sign_revocation_list(&owner_key, &network_name, version.unwrap_or(now), &revoked);
+            println!("revocation_list = \"{}\"", encode_message(&list));
+        }
+    }
+    Ok(())
+}
+
 #[tokio::main]
 #[tracing::instrument]
 async fn main() -> Result<(), Error> {
@@ -1641,6 +1857,7 @@ async fn main() -> Result<(), Error> {
         SubCommand::Route(route_args) => match route_args.sub_command {
             Some(RouteSubCommand::List) | None => handler.handle_route_list().await?,
             Some(RouteSubCommand::Dump) => handler.handle_route_dump().await?,
+            Some(RouteSubCommand::Exit) => handler.handle_route_exit().await?,
         },
         SubCommand::Stun => {
             timeout(Duration::from_secs(25), async move {
@@ -1991,9 +2208,17 @@ async fn main() -> Result<(), Error> {
                 protocol,
                 bind_addr,
                 dst_addr,
+                udp_idle_timeout,
+                allow,
             }) => {
                 handler
-                    .handle_port_forward_add(protocol, bind_addr, dst_addr)
+                    .handle_port_forward_add(
+                        protocol,
+                        bind_addr,
+                        dst_addr,
+                        *udp_idle_timeout,
+                        allow,
+                    )
                     .await?;
             }
             Some(PortForwardSubCommand::Remove {
@@ -2097,6 +2322,9 @@ async fn main() -> Result<(), Error> {
                 handler.handle_logger_set(level).await?;
             }
         },
+        SubCommand::Identity(identity_args) => {
+            handle_identity_command(identity_args.sub_command)?;
+        }
         SubCommand::GenAutocomplete { shell } => {
             let mut cmd = Cli::command();
             easytier::print_completions(shell, &mut cmd, "easytier-cli");
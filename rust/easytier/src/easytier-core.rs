@@ -30,7 +30,7 @@ use easytier::{
     instance_manager::NetworkInstanceManager,
     launcher::{add_proxy_network_to_config, ConfigSource},
     proto::common::{CompressionAlgoPb, NatType},
-    tunnel::{IpVersion, PROTO_PORT_OFFSET},
+    tunnel::{proxy::UpstreamProxy, IpVersion, PROTO_PORT_OFFSET},
     utils::{init_logger, setup_panic_handler},
     web_client,
 };
@@ -248,6 +248,14 @@ struct NetworkOptions {
     )]
     no_listener: bool,
 
+    #[arg(
+        long,
+        env = "ET_STDIO",
+        help = t!("core_clap.stdio").to_string(),
+        default_value = "false",
+    )]
+    stdio: bool,
+
     #[arg(
         long,
         env = "ET_HOSTNAME",
@@ -436,6 +444,15 @@ struct NetworkOptions {
     )]
     disable_sym_hole_punching: Option<bool>,
 
+    #[arg(
+        long,
+        env = "ET_ENABLE_MULTIPATH_BONDING",
+        help = t!("core_clap.enable_multipath_bonding").to_string(),
+        num_args = 0..=1,
+        default_missing_value = "true"
+    )]
+    enable_multipath_bonding: Option<bool>,
+
     #[arg(
         long,
         env = "ET_RELAY_ALL_PEER_RPC",
@@ -453,6 +470,21 @@ struct NetworkOptions {
     )]
     socks5: Option<u16>,
 
+    #[cfg(feature = "socks5")]
+    #[arg(
+        long,
+        env = "ET_HTTP_PROXY",
+        help = t!("core_clap.http_proxy").to_string()
+    )]
+    http_proxy: Option<String>,
+
+    #[arg(
+        long,
+        env = "ET_UPSTREAM_PROXY",
+        help = t!("core_clap.upstream_proxy").to_string()
+    )]
+    upstream_proxy: Option<url::Url>,
+
     #[arg(
         long,
         env = "ET_COMPRESSION",
@@ -645,7 +677,8 @@ impl Cli {
 
         for l in &origin_listners {
             let proto_port: Vec<&str> = l.split(':').collect();
-            if proto_port.len() > 2 {
+            // urls without a port like unix:///path or stdio:// only split into two parts
+            if proto_port.len() > 2 || l.contains("://") {
                 if let Ok(url) = l.parse::<url::Url>() {
                     listeners.push(url.to_string());
                 } else {
@@ -737,7 +770,7 @@ impl NetworkOptions {
             cfg.set_peers(peers);
         }
 
-        if self.no_listener || !self.listeners.is_empty() {
+        if self.no_listener || self.stdio || !self.listeners.is_empty() {
             cfg.set_listeners(
                 Cli::parse_listeners(self.no_listener, self.listeners.clone())?
                     .into_iter()
@@ -753,6 +786,12 @@ impl NetworkOptions {
             );
         }
 
+        if self.stdio {
+            let mut listeners = cfg.get_listeners().unwrap_or_default();
+            listeners.push("stdio://".parse().unwrap());
+            cfg.set_listeners(listeners);
+        }
+
         if !self.mapped_listeners.is_empty() {
             let mut errs = Vec::new();
             cfg.set_mapped_listeners(Some(
@@ -839,7 +878,7 @@ impl NetworkOptions {
         }
 
         if let Some(manual_routes) = self.manual_routes.as_ref() {
-            let mut routes = Vec::<cidr::Ipv4Cidr>::with_capacity(manual_routes.len());
+            let mut routes = Vec::<cidr::IpCidr>::with_capacity(manual_routes.len());
             for r in manual_routes {
                 routes.push(
                     r.parse()
@@ -858,9 +897,36 @@ impl NetworkOptions {
             ));
         }
 
+        #[cfg(feature = "socks5")]
+        if let Some(http_proxy) = &self.http_proxy {
+            let portal = match http_proxy.parse::<u16>() {
+                Ok(port) => format!("http://0.0.0.0:{}", port).parse().unwrap(),
+                Err(_) => url::Url::parse(http_proxy)
+                    .with_context(|| format!("invalid http proxy portal: {}", http_proxy))?,
+            };
+            let is_ip_host = matches!(
+                portal.host(),
+                Some(url::Host::Ipv4(_)) | Some(url::Host::Ipv6(_))
+            );
+            if portal.scheme() != "http" || !is_ip_host || portal.port_or_known_default().is_none()
+            {
+                anyhow::bail!(
+                    "invalid http proxy portal: {}, expect http://ip:port",
+                    http_proxy
+                );
+            }
+            cfg.set_http_portal(Some(portal));
+        }
+
+        if let Some(upstream_proxy) = &self.upstream_proxy {
+            UpstreamProxy::from_url(upstream_proxy)
+                .with_context(|| format!("invalid upstream proxy: {}", upstream_proxy))?;
+            cfg.set_upstream_proxy(Some(upstream_proxy.clone()));
+        }
+
         #[cfg(feature = "socks5")]
         for port_forward in self.port_forward.iter() {
-            let example_str = ", example: udp://0.0.0.0:12345/10.126.126.1:12345";
+            let example_str = ", example: udp://0.0.0.0:12345/10.126.126.1:12345 or udp://0.0.0.0:27015/10.126.126.1:27015?bind_port_end=27030&udp_idle_timeout=60&allow=10.126.126.0/24";
 
             let bind_addr = format!(
                 "{}:{}",
@@ -881,11 +947,32 @@ impl NetworkOptions {
                     panic!("failed to parse remote destination addr {}", example_str)
                 });
 
-            let port_forward_item = PortForwardConfig {
+            let mut port_forward_item = PortForwardConfig {
                 bind_addr,
                 dst_addr,
                 proto: port_forward.scheme().to_string(),
+                ..Default::default()
             };
+            for (k, v) in port_forward.query_pairs() {
+                match k.as_ref() {
+                    "bind_port_end" => {
+                        port_forward_item.bind_port_end = Some(v.parse().unwrap_or_else(|_| {
+                            panic!("failed to parse bind_port_end {}", example_str)
+                        }))
+                    }
+                    "udp_idle_timeout" => {
+                        port_forward_item.udp_idle_timeout = Some(v.parse().unwrap_or_else(|_| {
+                            panic!("failed to parse udp_idle_timeout {}", example_str)
+                        }))
+                    }
+                    "allow" => port_forward_item
+                        .allowed_sources
+                        .push(v.parse().unwrap_or_else(|_| {
+                            panic!("failed to parse allowed source cidr {}", example_str)
+                        })),
+                    _ => panic!("unknown port forward option {} {}", k, example_str),
+                }
+            }
 
             let mut old = cfg.get_port_forwards();
             old.push(port_forward_item);
@@ -954,6 +1041,9 @@ impl NetworkOptions {
             .enable_relay_foreign_network_kcp
             .unwrap_or(f.enable_relay_foreign_network_kcp);
         f.disable_sym_hole_punching = self.disable_sym_hole_punching.unwrap_or(false);
+        f.enable_multipath_bonding = self
+            .enable_multipath_bonding
+            .unwrap_or(f.enable_multipath_bonding);
         cfg.set_flags(f);
 
         if !self.exit_nodes.is_empty() {
@@ -1125,6 +1215,14 @@ fn win_service_main(arg: Vec<std::ffi::OsString>) {
 }
 
 async fn run_main(cli: Cli) -> anyhow::Result<()> {
+    if cli.network_options.stdio {
+        // must happen before anything is printed, stdout carries the tunnel from now on
+        #[cfg(unix)]
+        easytier::tunnel::stdio::reserve_stdout_for_tunnel()
+            .with_context(|| "failed to reserve stdout for stdio tunnel")?;
+        #[cfg(not(unix))]
+        anyhow::bail!("--stdio is only supported on unix");
+    }
     init_logger(&cli.logging_options, true)?;
 
     if cli.config_server.is_some() {
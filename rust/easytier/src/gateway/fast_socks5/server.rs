@@ -34,6 +34,8 @@ pub struct Config<A: Authentication = DenyAuthentication> {
     execute_command: bool,
     /// Enable UDP support
     allow_udp: bool,
+    /// Enable BIND support, the command is accepted but must be executed by the caller
+    allow_bind: bool,
     /// For some complex scenarios, we may want to either accept Username/Password configuration
     /// or IP Whitelisting, in case the client send only 1-2 auth methods (no auth) rather than 3 (with auth)
     allow_no_auth: bool,
@@ -49,6 +51,7 @@ impl<A: Authentication> Default for Config<A> {
             dns_resolve: true,
             execute_command: true,
             allow_udp: false,
+            allow_bind: false,
             allow_no_auth: false,
             auth: None,
         }
@@ -150,6 +153,7 @@ impl<A: Authentication> Config<A> {
             dns_resolve: self.dns_resolve,
             execute_command: self.execute_command,
             allow_udp: self.allow_udp,
+            allow_bind: self.allow_bind,
             allow_no_auth: self.allow_no_auth,
             auth: Some(Arc::new(authentication)),
         }
@@ -179,6 +183,12 @@ impl<A: Authentication> Config<A> {
         self.allow_udp = value;
         self
     }
+
+    /// Set whether or not to accept the BIND command
+    pub fn set_bind_support(&mut self, value: bool) -> &mut Self {
+        self.allow_bind = value;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -486,9 +496,15 @@ impl<T: AsyncRead + AsyncWrite + Unpin, A: Authentication, C: AsyncTcpConnector>
     }
 
     /// Reply error to the client with the reply code according to the RFC.
-    async fn reply_error(&mut self, error: &ReplyError) -> Result<()> {
-        let reply = new_reply(error, "0.0.0.0:0".parse().unwrap());
-        debug!("reply error to be written: {:?}", &reply);
+    pub async fn reply_error(&mut self, error: &ReplyError) -> Result<()> {
+        self.reply(error, "0.0.0.0:0".parse().unwrap()).await
+    }
+
+    /// Write a reply with the given code and BND.ADDR / BND.PORT, used when the command is
+    /// executed by the caller.
+    pub async fn reply(&mut self, error: &ReplyError, sock_addr: SocketAddr) -> Result<()> {
+        let reply = new_reply(error, sock_addr);
+        debug!("reply to be written: {:?}", &reply);
 
         self.inner
             .write(&reply)
@@ -541,7 +557,12 @@ impl<T: AsyncRead + AsyncWrite + Unpin, A: Authentication, C: AsyncTcpConnector>
                     }
                     self.cmd = Some(cmd);
                 }
-                Socks5Command::TCPBind => return Err(ReplyError::CommandNotSupported.into()),
+                Socks5Command::TCPBind => {
+                    if !self.config.allow_bind {
+                        return Err(ReplyError::CommandNotSupported.into());
+                    }
+                    self.cmd = Some(cmd);
+                }
             },
         }
 
@@ -1,6 +1,6 @@
 use std::{
     mem::MaybeUninit,
-    net::{IpAddr, Ipv4Addr, SocketAddrV4},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6},
     sync::{Arc, Weak},
     thread,
     time::Duration,
@@ -9,8 +9,10 @@ use std::{
 use anyhow::Context;
 use pnet::packet::{
     icmp::{self, echo_reply::MutableEchoReplyPacket, IcmpCode, IcmpTypes, MutableIcmpPacket},
+    icmpv6::{self, Icmpv6Code, Icmpv6Types, MutableIcmpv6Packet},
     ip::IpNextHeaderProtocols,
     ipv4::Ipv4Packet,
+    ipv6::Ipv6Packet,
     Packet,
 };
 use socket2::Socket;
@@ -23,13 +25,13 @@ use tracing::Instrument;
 
 use crate::{
     common::{error::Error, global_ctx::ArcGlobalCtx, PeerId},
-    gateway::ip_reassembler::ComposeIpv4PacketArgs,
+    gateway::ip_reassembler::{ComposeIpv4PacketArgs, ComposeIpv6PacketArgs, IPV6_HEADER_RESERVED},
     peers::{peer_manager::PeerManager, PeerPacketFilter},
     tunnel::packet_def::{PacketType, ZCPacket},
 };
 
 use super::{
-    ip_reassembler::{compose_ipv4_packet, IpReassembler},
+    ip_reassembler::{compose_ipv4_packet, compose_ipv6_packet, IpReassembler},
     CidrSet,
 };
 
@@ -46,7 +48,7 @@ struct IcmpNatEntry {
     my_peer_id: PeerId,
     src_ip: IpAddr,
     start_time: std::time::Instant,
-    mapped_dst_ip: std::net::Ipv4Addr,
+    mapped_dst_ip: IpAddr,
 }
 
 impl IcmpNatEntry {
@@ -54,7 +56,7 @@ impl IcmpNatEntry {
         src_peer_id: PeerId,
         my_peer_id: PeerId,
         src_ip: IpAddr,
-        mapped_dst_ip: Ipv4Addr,
+        mapped_dst_ip: IpAddr,
     ) -> Result<Self, Error> {
         Ok(Self {
             src_peer_id,
@@ -77,6 +79,7 @@ pub struct IcmpProxy {
 
     cidr_set: CidrSet,
     socket: std::sync::Mutex<Option<Arc<socket2::Socket>>>,
+    socket_v6: std::sync::Mutex<Option<Arc<socket2::Socket>>>,
 
     nat_table: IcmpNatTable,
 
@@ -152,7 +155,7 @@ fn socket_recv_loop(
         };
 
         // send packet back to the peer where this request origin.
-        let IpAddr::V4(dest_ip) = v.src_ip else {
+        let (IpAddr::V4(dest_ip), IpAddr::V4(mapped_dst_ip)) = (v.src_ip, v.mapped_dst_ip) else {
             continue;
         };
 
@@ -161,7 +164,7 @@ fn socket_recv_loop(
         let _ = compose_ipv4_packet(
             ComposeIpv4PacketArgs {
                 buf: &mut buf[..],
-                src_v4: &v.mapped_dst_ip,
+                src_v4: &mapped_dst_ip,
                 dst_v4: &dest_ip,
                 next_protocol: IpNextHeaderProtocols::Icmp,
                 payload_len,
@@ -182,6 +185,95 @@ fn socket_recv_loop(
     }
 }
 
+// icmpv6 raw sockets deliver the icmp message without the ipv6 header
+fn socket_recv_loop_v6(
+    socket: Arc<Socket>,
+    nat_table: IcmpNatTable,
+    sender: UnboundedSender<ZCPacket>,
+) {
+    let mut buf = [0u8; 8192];
+
+    loop {
+        let data: &mut [MaybeUninit<u8>] =
+            unsafe { std::mem::transmute(&mut buf[IPV6_HEADER_RESERVED..]) };
+        let (len, peer_ip) = match socket_recv(&socket, data) {
+            Ok((len, peer_ip)) => (len, peer_ip),
+            Err(e) => {
+                tracing::error!("recv icmpv6 packet failed: {:?}", e);
+                if sender.is_closed() {
+                    break;
+                } else {
+                    continue;
+                }
+            }
+        };
+
+        if len == 0 {
+            tracing::error!("recv empty packet, len: {}", len);
+            return;
+        }
+
+        if !peer_ip.is_ipv6() {
+            continue;
+        }
+
+        let icmp_buf = &buf[IPV6_HEADER_RESERVED..IPV6_HEADER_RESERVED + len];
+        let Some(icmp_packet) = icmpv6::echo_reply::EchoReplyPacket::new(icmp_buf) else {
+            continue;
+        };
+
+        if icmp_packet.get_icmpv6_type() != Icmpv6Types::EchoReply {
+            continue;
+        }
+
+        let key = IcmpNatKey {
+            real_dst_ip: peer_ip,
+            icmp_id: icmp_packet.get_identifier(),
+            icmp_seq: icmp_packet.get_sequence_number(),
+        };
+
+        let Some((_, v)) = nat_table.remove(&key) else {
+            continue;
+        };
+
+        let (IpAddr::V6(dest_ip), IpAddr::V6(mapped_dst_ip)) = (v.src_ip, v.mapped_dst_ip) else {
+            continue;
+        };
+
+        // the pseudo header changed, so the checksum must be recomputed
+        let mut icmp_packet =
+            MutableIcmpv6Packet::new(&mut buf[IPV6_HEADER_RESERVED..IPV6_HEADER_RESERVED + len])
+                .unwrap();
+        icmp_packet.set_checksum(icmpv6::checksum(
+            &icmp_packet.to_immutable(),
+            &mapped_dst_ip,
+            &dest_ip,
+        ));
+
+        let _ = compose_ipv6_packet(
+            ComposeIpv6PacketArgs {
+                buf: &mut buf[..],
+                src_v6: &mapped_dst_ip,
+                dst_v6: &dest_ip,
+                next_protocol: IpNextHeaderProtocols::Icmpv6,
+                payload_len: len,
+                payload_mtu: 1200,
+                ip_id: rand::random(),
+            },
+            |buf| {
+                let mut p = ZCPacket::new_with_payload(buf);
+                p.fill_peer_manager_hdr(v.my_peer_id, v.src_peer_id, PacketType::Data as u8);
+                p.mut_peer_manager_header().unwrap().set_no_proxy(true);
+
+                if let Err(e) = sender.send(p) {
+                    tracing::error!("send icmpv6 packet to peer failed: {:?}, may exiting..", e);
+                }
+                Ok(())
+            },
+        );
+    }
+}
+
 #[async_trait::async_trait]
 impl PeerPacketFilter for IcmpProxy {
     async fn try_process_packet_from_peer(&self, packet: ZCPacket) -> Option<ZCPacket> {
@@ -204,6 +296,7 @@ impl IcmpProxy {
             peer_manager: Arc::downgrade(&peer_manager),
             cidr_set,
             socket: std::sync::Mutex::new(None),
+            socket_v6: std::sync::Mutex::new(None),
 
             nat_table: Arc::new(dashmap::DashMap::new()),
             tasks: Mutex::new(JoinSet::new()),
@@ -229,7 +322,33 @@ impl IcmpProxy {
         Ok(socket)
     }
 
+    fn create_raw_socket_v6(self: &Arc<Self>) -> Result<Socket, Error> {
+        let _g = self.global_ctx.net_ns.guard();
+        let socket = socket2::Socket::new(
+            socket2::Domain::IPV6,
+            socket2::Type::RAW,
+            Some(socket2::Protocol::ICMPV6),
+        )?;
+        socket.bind(&socket2::SockAddr::from(SocketAddrV6::new(
+            Ipv6Addr::UNSPECIFIED,
+            0,
+            0,
+            0,
+        )))?;
+        Ok(socket)
+    }
+
     pub async fn start(self: &Arc<Self>) -> Result<(), Error> {
+        // ipv6 may be disabled on the host, only ipv4 icmp is mandatory
+        match self.create_raw_socket_v6() {
+            Ok(socket) => {
+                self.socket_v6.lock().unwrap().replace(Arc::new(socket));
+            }
+            Err(e) => {
+                tracing::warn!("create icmpv6 socket failed: {:?}", e);
+            }
+        }
+
         let socket = self.create_raw_socket();
         match socket {
             Ok(socket) => {
@@ -268,10 +387,19 @@ impl IcmpProxy {
         if let Some(socket) = self.socket.lock().unwrap().as_ref() {
             let socket = socket.clone();
             let nat_table = self.nat_table.clone();
+            let sender = sender.clone();
             thread::spawn(|| {
                 socket_recv_loop(socket, nat_table, sender);
             });
         }
+        if let Some(socket) = self.socket_v6.lock().unwrap().as_ref() {
+            let socket = socket.clone();
+            let nat_table = self.nat_table.clone();
+            let sender = sender.clone();
+            thread::spawn(|| {
+                socket_recv_loop_v6(socket, nat_table, sender);
+            });
+        }
 
         let peer_manager = self.peer_manager.clone();
         let is_latency_first = self.global_ctx.get_flags().latency_first;
@@ -371,6 +499,76 @@ impl IcmpProxy {
         );
     }
 
+    fn send_icmpv6_packet(
+        &self,
+        dst_ip: Ipv6Addr,
+        icmp_packet: &icmpv6::echo_request::EchoRequestPacket,
+    ) -> Result<(), Error> {
+        // the kernel fills in the checksum of icmpv6 raw sockets, as it owns the source address
+        self.socket_v6
+            .lock()
+            .unwrap()
+            .as_ref()
+            .with_context(|| "icmpv6 socket not created")?
+            .send_to(
+                icmp_packet.packet(),
+                &SocketAddrV6::new(dst_ip, 0, 0, 0).into(),
+            )?;
+
+        Ok(())
+    }
+
+    async fn send_icmpv6_reply_to_peer(
+        &self,
+        src_ip: &Ipv6Addr,
+        dst_ip: &Ipv6Addr,
+        src_peer_id: PeerId,
+        dst_peer_id: PeerId,
+        icmp_packet: &icmpv6::echo_request::EchoRequestPacket<'_>,
+    ) {
+        let mut buf = vec![0u8; icmp_packet.packet().len() + IPV6_HEADER_RESERVED];
+        let mut reply_packet =
+            icmpv6::echo_reply::MutableEchoReplyPacket::new(&mut buf[IPV6_HEADER_RESERVED..])
+                .unwrap();
+        reply_packet.set_icmpv6_type(Icmpv6Types::EchoReply);
+        reply_packet.set_icmpv6_code(Icmpv6Code::new(0));
+        reply_packet.set_identifier(icmp_packet.get_identifier());
+        reply_packet.set_sequence_number(icmp_packet.get_sequence_number());
+        reply_packet.set_payload(icmp_packet.payload());
+
+        let mut icmp_packet = MutableIcmpv6Packet::new(&mut buf[IPV6_HEADER_RESERVED..]).unwrap();
+        icmp_packet.set_checksum(icmpv6::checksum(
+            &icmp_packet.to_immutable(),
+            src_ip,
+            dst_ip,
+        ));
+
+        let len = buf.len() - IPV6_HEADER_RESERVED;
+        let _ = compose_ipv6_packet(
+            ComposeIpv6PacketArgs {
+                buf: &mut buf[..],
+                src_v6: src_ip,
+                dst_v6: dst_ip,
+                next_protocol: IpNextHeaderProtocols::Icmpv6,
+                payload_len: len,
+                payload_mtu: 1200,
+                ip_id: rand::random(),
+            },
+            |buf| {
+                let mut packet = ZCPacket::new_with_payload(buf);
+                packet.fill_peer_manager_hdr(src_peer_id, dst_peer_id, PacketType::Data as u8);
+                let _ = self
+                    .icmp_sender
+                    .lock()
+                    .unwrap()
+                    .as_ref()
+                    .unwrap()
+                    .send(packet);
+                Ok(())
+            },
+        );
+    }
+
     async fn try_handle_peer_packet(&self, packet: &ZCPacket) -> Option<()> {
         if self.cidr_set.is_empty()
             && !self.global_ctx.enable_exit_node()
@@ -379,14 +577,92 @@ impl IcmpProxy {
             return None;
         }
 
-        let _ = self.global_ctx.get_ipv4()?;
+        let hdr = packet.peer_manager_header().unwrap();
+        if hdr.packet_type != PacketType::Data as u8 || hdr.is_no_proxy() {
+            return None;
+        };
+
+        match packet.payload().first()? >> 4 {
+            4 => self.try_handle_peer_ipv4_packet(packet).await,
+            6 => self.try_handle_peer_ipv6_packet(packet).await,
+            _ => None,
+        }
+    }
+
+    async fn try_handle_peer_ipv6_packet(&self, packet: &ZCPacket) -> Option<()> {
+        let my_ipv6 = self.global_ctx.get_ipv6().map(|x| x.address());
         let hdr = packet.peer_manager_header().unwrap();
         let is_exit_node = hdr.is_exit_node();
 
-        if hdr.packet_type != PacketType::Data as u8 || hdr.is_no_proxy() {
+        let ipv6 = Ipv6Packet::new(packet.payload())?;
+        if ipv6.get_version() != 6 || ipv6.get_next_header() != IpNextHeaderProtocols::Icmpv6 {
+            return None;
+        }
+
+        let mut real_dst_ip = ipv6.get_destination();
+        let is_to_self = self.global_ctx.no_tun() && Some(ipv6.get_destination()) == my_ipv6;
+
+        if !(self
+            .cidr_set
+            .contains_v6(ipv6.get_destination(), &mut real_dst_ip)
+            || is_exit_node
+            || is_to_self)
+        {
+            return None;
+        }
+
+        let icmp_packet = icmpv6::echo_request::EchoRequestPacket::new(ipv6.payload())?;
+        if icmp_packet.get_icmpv6_type() != Icmpv6Types::EchoRequest {
+            // neighbor discovery and other icmpv6 messages are left to the nic.
+            tracing::trace!(
+                "unsupported icmpv6 type: {:?}",
+                icmp_packet.get_icmpv6_type()
+            );
             return None;
+        }
+
+        if is_to_self {
+            self.send_icmpv6_reply_to_peer(
+                &ipv6.get_destination(),
+                &ipv6.get_source(),
+                hdr.to_peer_id.get(),
+                hdr.from_peer_id.get(),
+                &icmp_packet,
+            )
+            .await;
+            return Some(());
+        }
+
+        let key = IcmpNatKey {
+            real_dst_ip: real_dst_ip.into(),
+            icmp_id: icmp_packet.get_identifier(),
+            icmp_seq: icmp_packet.get_sequence_number(),
         };
 
+        let value = IcmpNatEntry::new(
+            hdr.from_peer_id.into(),
+            hdr.to_peer_id.into(),
+            ipv6.get_source().into(),
+            ipv6.get_destination().into(),
+        )
+        .ok()?;
+
+        if let Some(old) = self.nat_table.insert(key, value) {
+            tracing::info!("icmp nat table entry replaced: {:?}", old);
+        }
+
+        if let Err(e) = self.send_icmpv6_packet(real_dst_ip, &icmp_packet) {
+            tracing::error!("send icmpv6 packet failed: {:?}", e);
+        }
+
+        Some(())
+    }
+
+    async fn try_handle_peer_ipv4_packet(&self, packet: &ZCPacket) -> Option<()> {
+        let _ = self.global_ctx.get_ipv4()?;
+        let hdr = packet.peer_manager_header().unwrap();
+        let is_exit_node = hdr.is_exit_node();
+
         let ipv4 = Ipv4Packet::new(packet.payload())?;
 
         if ipv4.get_version() != 4 || ipv4.get_next_level_protocol() != IpNextHeaderProtocols::Icmp
@@ -460,7 +736,7 @@ impl IcmpProxy {
             hdr.from_peer_id.into(),
             hdr.to_peer_id.into(),
             ipv4.get_source().into(),
-            ipv4.get_destination(),
+            ipv4.get_destination().into(),
         )
         .ok()?;
 
@@ -486,5 +762,9 @@ impl Drop for IcmpProxy {
             tracing::info!("shutting down icmp socket");
             let _ = s.shutdown(std::net::Shutdown::Both);
         }
+        if let Some(s) = self.socket_v6.lock().unwrap().as_ref() {
+            tracing::info!("shutting down icmpv6 socket");
+            let _ = s.shutdown(std::net::Shutdown::Both);
+        }
     }
 }
@@ -1,8 +1,10 @@
 use dashmap::DashMap;
 use pnet::packet::ip::IpNextHeaderProtocol;
+use pnet::packet::ip::IpNextHeaderProtocols;
 use pnet::packet::ipv4::{self, Ipv4Flags, Ipv4Packet, MutableIpv4Packet};
+use pnet::packet::ipv6::MutableIpv6Packet;
 use pnet::packet::Packet;
-use std::net::Ipv4Addr;
+use std::net::{Ipv4Addr, Ipv6Addr};
 use std::time::{Duration, Instant};
 
 use crate::common::error::Error;
@@ -251,6 +253,95 @@ where
     Ok(())
 }
 
+pub const IPV6_HEADER_LEN: usize = 40;
+const IPV6_FRAGMENT_HEADER_LEN: usize = 8;
+// room reserved before the ip payload, for the ipv6 header and an optional fragment header
+pub const IPV6_HEADER_RESERVED: usize = IPV6_HEADER_LEN + IPV6_FRAGMENT_HEADER_LEN;
+
+pub struct ComposeIpv6PacketArgs<'a> {
+    pub buf: &'a mut [u8],
+    pub src_v6: &'a Ipv6Addr,
+    pub dst_v6: &'a Ipv6Addr,
+    pub next_protocol: IpNextHeaderProtocol,
+    pub payload_len: usize,
+    pub payload_mtu: usize,
+    pub ip_id: u32,
+}
+
+fn fill_ipv6_header(
+    ipv6_packet: &mut MutableIpv6Packet,
+    src_v6: &Ipv6Addr,
+    dst_v6: &Ipv6Addr,
+    next_header: IpNextHeaderProtocol,
+    payload_len: usize,
+) {
+    ipv6_packet.set_version(6);
+    ipv6_packet.set_traffic_class(0);
+    ipv6_packet.set_flow_label(0);
+    ipv6_packet.set_payload_length(payload_len as u16);
+    ipv6_packet.set_next_header(next_header);
+    ipv6_packet.set_hop_limit(64);
+    ipv6_packet.set_source(*src_v6);
+    ipv6_packet.set_destination(*dst_v6);
+}
+
+// ip payload should be in buf[IPV6_HEADER_RESERVED..]. payloads larger than payload_mtu are
+// split with a fragment extension header, as ipv6 has no in-header fragmentation.
+pub fn compose_ipv6_packet<F>(args: ComposeIpv6PacketArgs, cb: F) -> Result<(), Error>
+where
+    F: Fn(&[u8]) -> Result<(), Error>,
+{
+    if args.payload_len <= args.payload_mtu {
+        let start = IPV6_HEADER_RESERVED - IPV6_HEADER_LEN;
+        let end = IPV6_HEADER_RESERVED + args.payload_len;
+        let mut ipv6_packet = MutableIpv6Packet::new(&mut args.buf[start..end]).unwrap();
+        fill_ipv6_header(
+            &mut ipv6_packet,
+            args.src_v6,
+            args.dst_v6,
+            args.next_protocol,
+            args.payload_len,
+        );
+        tracing::trace!(?ipv6_packet, "compose ipv6 packet");
+        return cb(ipv6_packet.packet());
+    }
+
+    assert_eq!(0, args.payload_mtu % 8);
+    let mut fragment_offset = 0;
+    while fragment_offset < args.payload_len {
+        let next_fragment_offset =
+            std::cmp::min(fragment_offset + args.payload_mtu, args.payload_len);
+        let fragment_len = next_fragment_offset - fragment_offset;
+        // the header of this piece overwrites the tail of the previous (already sent) piece
+        let piece_len = IPV6_HEADER_RESERVED + fragment_len;
+        let piece = &mut args.buf[fragment_offset..fragment_offset + piece_len];
+
+        let mut ipv6_packet = MutableIpv6Packet::new(&mut piece[..]).unwrap();
+        fill_ipv6_header(
+            &mut ipv6_packet,
+            args.src_v6,
+            args.dst_v6,
+            IpNextHeaderProtocols::Ipv6Frag,
+            IPV6_FRAGMENT_HEADER_LEN + fragment_len,
+        );
+        drop(ipv6_packet);
+
+        let more_fragments = next_fragment_offset < args.payload_len;
+        // offset is in 8-byte units and stored in the upper 13 bits, so it is the byte offset
+        let offset_and_flags = fragment_offset as u16 | more_fragments as u16;
+        let fragment_header = &mut piece[IPV6_HEADER_LEN..IPV6_HEADER_RESERVED];
+        fragment_header[0] = args.next_protocol.0;
+        fragment_header[1] = 0;
+        fragment_header[2..4].copy_from_slice(&offset_and_flags.to_be_bytes());
+        fragment_header[4..8].copy_from_slice(&args.ip_id.to_be_bytes());
+
+        cb(piece)?;
+
+        fragment_offset = next_fragment_offset;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -308,4 +399,50 @@ mod tests {
         resembler.remove_expired_packets();
         assert_eq!(0, resembler.packets.len());
     }
+
+    #[test]
+    fn compose_ipv6_fragments() {
+        use pnet::packet::ipv6::Ipv6Packet;
+
+        let src: Ipv6Addr = "fd00::1".parse().unwrap();
+        let dst: Ipv6Addr = "fd00::2".parse().unwrap();
+        let payload: Vec<u8> = (0..100u8).collect();
+        let mut buf = vec![0u8; IPV6_HEADER_RESERVED + payload.len()];
+        buf[IPV6_HEADER_RESERVED..].copy_from_slice(&payload);
+
+        let pieces = std::sync::Mutex::new(vec![]);
+        compose_ipv6_packet(
+            ComposeIpv6PacketArgs {
+                buf: &mut buf[..],
+                src_v6: &src,
+                dst_v6: &dst,
+                next_protocol: IpNextHeaderProtocols::Udp,
+                payload_len: payload.len(),
+                payload_mtu: 40,
+                ip_id: 7,
+            },
+            |p| {
+                pieces.lock().unwrap().push(p.to_vec());
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        let pieces = pieces.into_inner().unwrap();
+        assert_eq!(3, pieces.len());
+        let mut reassembled = vec![];
+        for (idx, piece) in pieces.iter().enumerate() {
+            let ipv6 = Ipv6Packet::new(piece).unwrap();
+            assert_eq!(ipv6.get_next_header(), IpNextHeaderProtocols::Ipv6Frag);
+            assert_eq!(ipv6.get_destination(), dst);
+            let frag = ipv6.payload();
+            assert_eq!(frag[0], IpNextHeaderProtocols::Udp.0);
+            let offset_and_flags = u16::from_be_bytes([frag[2], frag[3]]);
+            assert_eq!((offset_and_flags & !0x7) as usize, idx * 40);
+            assert_eq!(offset_and_flags & 1 == 1, idx != 2);
+            assert_eq!(u32::from_be_bytes([frag[4], frag[5], frag[6], frag[7]]), 7);
+            reassembled.extend_from_slice(&frag[IPV6_FRAGMENT_HEADER_LEN..]);
+        }
+        assert_eq!(reassembled, payload);
+    }
 }
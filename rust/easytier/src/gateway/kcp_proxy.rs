@@ -1,5 +1,5 @@
 use std::{
-    net::{IpAddr, Ipv4Addr, SocketAddr},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     sync::{Arc, Weak},
     time::Duration,
 };
@@ -13,12 +13,6 @@ use kcp_sys::{
     packet_def::KcpPacket,
     stream::KcpStream,
 };
-use pnet::packet::{
-    ip::IpNextHeaderProtocols,
-    ipv4::Ipv4Packet,
-    tcp::{TcpFlags, TcpPacket},
-    Packet as _,
-};
 use prost::Message;
 use tokio::{
     io::{copy_bidirectional, AsyncRead, AsyncWrite},
@@ -28,7 +22,7 @@ use tokio::{
 use tokio_util::io::InspectReader;
 
 use super::{
-    tcp_proxy::{NatDstConnector, NatDstTcpConnector, TcpProxy},
+    tcp_proxy::{parse_tcp_ip_packet, NatDstConnector, NatDstTcpConnector, TcpProxy},
     CidrSet,
 };
 use crate::{
@@ -135,7 +129,7 @@ impl NatDstConnector for NatDstKcpConnector {
 
         let dst_peer_id = match nat_dst {
             SocketAddr::V4(addr) => peer_mgr.get_peer_map().get_peer_id_by_ipv4(addr.ip()).await,
-            SocketAddr::V6(_) => return Err(anyhow::anyhow!("ipv6 is not supported").into()),
+            SocketAddr::V6(addr) => peer_mgr.get_peer_map().get_peer_id_by_ipv6(addr.ip()).await,
         };
 
         let Some(dst_peer) = dst_peer_id else {
@@ -200,8 +194,8 @@ impl NatDstConnector for NatDstKcpConnector {
         _cidr_set: &CidrSet,
         _global_ctx: &GlobalCtx,
         hdr: &PeerManagerHeader,
-        _ipv4: &Ipv4Packet,
-        _real_dst_ip: &mut Ipv4Addr,
+        _dst_ip: IpAddr,
+        _real_dst_ip: &mut IpAddr,
     ) -> bool {
         hdr.from_peer_id == hdr.to_peer_id && hdr.is_kcp_src_modified()
     }
@@ -218,7 +212,7 @@ struct TcpProxyForKcpSrc(Arc<TcpProxy<NatDstKcpConnector>>);
 pub(crate) trait TcpProxyForKcpSrcTrait: Send + Sync + 'static {
     type Connector: NatDstConnector;
     fn get_tcp_proxy(&self) -> &Arc<TcpProxy<Self::Connector>>;
-    async fn check_dst_allow_kcp_input(&self, dst_ip: &Ipv4Addr) -> bool;
+    async fn check_dst_allow_kcp_input(&self, dst_ip: &IpAddr) -> bool;
 }
 
 #[async_trait::async_trait]
@@ -229,10 +223,10 @@ impl TcpProxyForKcpSrcTrait for TcpProxyForKcpSrc {
         &self.0
     }
 
-    async fn check_dst_allow_kcp_input(&self, dst_ip: &Ipv4Addr) -> bool {
+    async fn check_dst_allow_kcp_input(&self, dst_ip: &IpAddr) -> bool {
         self.0
             .get_peer_manager()
-            .check_allow_kcp_to_dst(&IpAddr::V4(*dst_ip))
+            .check_allow_kcp_to_dst(dst_ip)
             .await
     }
 }
@@ -248,56 +242,52 @@ impl<C: NatDstConnector, T: TcpProxyForKcpSrcTrait<Connector = C>> NicPacketFilt
             return true;
         }
 
-        let data = zc_packet.payload();
-        let ip_packet = Ipv4Packet::new(data).unwrap();
-        if ip_packet.get_version() != 4
-            || ip_packet.get_next_level_protocol() != IpNextHeaderProtocols::Tcp
+        let Some(info) = parse_tcp_ip_packet(zc_packet.payload()) else {
+            return false;
+        };
+
+        // the local tcp proxy must be able to accept the redirected connection
+        if self
+            .get_tcp_proxy()
+            .get_local_ip_for(&info.dst.ip())
+            .is_none()
         {
             return false;
         }
 
         // if no connection is established, only allow SYN packet
-        let tcp_packet = TcpPacket::new(ip_packet.payload()).unwrap();
-        let is_syn = tcp_packet.get_flags() & TcpFlags::SYN != 0
-            && tcp_packet.get_flags() & TcpFlags::ACK == 0;
-        if is_syn {
+        if info.is_syn() {
             // only check dst feature flag when SYN packet
-            if !self
-                .check_dst_allow_kcp_input(&ip_packet.get_destination())
-                .await
-            {
+            if !self.check_dst_allow_kcp_input(&info.dst.ip()).await {
                 tracing::warn!(
                     "{:?} proxy src: dst {} not allow kcp input",
                     self.get_tcp_proxy().get_transport_type(),
-                    ip_packet.get_destination()
+                    info.dst.ip()
                 );
                 return false;
             }
         } else {
             // if not syn packet, only allow established connection
-            if !self
-                .get_tcp_proxy()
-                .is_tcp_proxy_connection(SocketAddr::new(
-                    IpAddr::V4(ip_packet.get_source()),
-                    tcp_packet.get_source(),
-                ))
-            {
+            if !self.get_tcp_proxy().is_tcp_proxy_connection(info.src) {
                 return false;
             }
         }
 
-        if let Some(my_ipv4) = self.get_tcp_proxy().get_global_ctx().get_ipv4() {
+        let global_ctx = self.get_tcp_proxy().get_global_ctx();
+        let my_ip: Option<IpAddr> = match info.src.ip() {
+            IpAddr::V4(_) => global_ctx.get_ipv4().map(|x| x.address().into()),
+            IpAddr::V6(_) => global_ctx.get_ipv6().map(|x| x.address().into()),
+        };
+        if let Some(my_ip) = my_ip {
             // this is a net-to-net packet, only allow it when smoltcp is enabled
             // because the syn-ack packet will not be through and handled by the tun device when
             // the source ip is in the local network
-            if ip_packet.get_source() != my_ipv4.address()
-                && !self.get_tcp_proxy().is_smoltcp_enabled()
-            {
+            if info.src.ip() != my_ip && !self.get_tcp_proxy().is_smoltcp_enabled() {
                 tracing::warn!(
                     "{:?} nat 2 nat packet, src: {} dst: {} not allow kcp input",
                     self.get_tcp_proxy().get_transport_type(),
-                    ip_packet.get_source(),
-                    ip_packet.get_destination()
+                    info.src.ip(),
+                    info.dst.ip()
                 );
                 return false;
             }
@@ -471,12 +461,10 @@ impl KcpProxyDst {
             .into();
         let src_socket: SocketAddr = parsed_conn_data.src.unwrap_or_default().into();
 
-        if let IpAddr::V4(dst_v4_ip) = dst_socket.ip() {
-            let mut real_ip = dst_v4_ip;
-            if cidr_set.contains_v4(dst_v4_ip, &mut real_ip) {
-                dst_socket.set_ip(real_ip.into());
-            }
-        };
+        let mut real_ip = dst_socket.ip();
+        if cidr_set.contains(dst_socket.ip(), &mut real_ip) {
+            dst_socket.set_ip(real_ip);
+        }
 
         let conn_id = kcp_stream.conn_id();
         proxy_entries.insert(
@@ -500,11 +488,19 @@ impl KcpProxyDst {
             route.get_peer_groups_by_ip(&dst_ip)
         );
 
-        let send_to_self =
-            Some(dst_socket.ip()) == global_ctx.get_ipv4().map(|ip| IpAddr::V4(ip.address()));
+        let (send_to_self, loopback): (bool, IpAddr) = match dst_socket.ip() {
+            IpAddr::V4(ip) => (
+                Some(ip) == global_ctx.get_ipv4().map(|ip| ip.address()),
+                Ipv4Addr::LOCALHOST.into(),
+            ),
+            IpAddr::V6(ip) => (
+                Some(ip) == global_ctx.get_ipv6().map(|ip| ip.address()),
+                Ipv6Addr::LOCALHOST.into(),
+            ),
+        };
 
         if send_to_self && global_ctx.no_tun() {
-            dst_socket = format!("127.0.0.1:{}", dst_socket.port()).parse().unwrap();
+            dst_socket.set_ip(loopback);
         }
 
         let acl_handler = ProxyAclHandler {
@@ -1,4 +1,5 @@
 use dashmap::DashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::sync::{Arc, Mutex};
 use tokio::task::JoinSet;
 
@@ -14,6 +15,8 @@ pub mod udp_proxy;
 #[cfg(feature = "socks5")]
 pub mod fast_socks5;
 #[cfg(feature = "socks5")]
+pub mod http_proxy;
+#[cfg(feature = "socks5")]
 pub mod socks5;
 
 pub mod kcp_proxy;
@@ -23,10 +26,10 @@ pub mod quic_proxy;
 #[derive(Debug)]
 pub(crate) struct CidrSet {
     global_ctx: ArcGlobalCtx,
-    cidr_set: Arc<Mutex<Vec<cidr::Ipv4Cidr>>>,
+    cidr_set: Arc<Mutex<Vec<cidr::IpCidr>>>,
     tasks: JoinSet<()>,
 
-    mapped_to_real: Arc<DashMap<cidr::Ipv4Cidr, cidr::Ipv4Cidr>>,
+    mapped_to_real: Arc<DashMap<cidr::IpCidr, cidr::IpCidr>>,
 }
 
 impl CidrSet {
@@ -70,29 +73,120 @@ impl CidrSet {
     }
 
     pub fn contains_v4(&self, ipv4: std::net::Ipv4Addr, real_ip: &mut std::net::Ipv4Addr) -> bool {
-        let ip = ipv4;
+        let mut ip = IpAddr::V4(ipv4);
+        if !self.contains(IpAddr::V4(ipv4), &mut ip) {
+            return false;
+        }
+        let IpAddr::V4(ip) = ip else {
+            return false;
+        };
+        *real_ip = ip;
+        true
+    }
+
+    pub fn contains_v6(&self, ipv6: std::net::Ipv6Addr, real_ip: &mut std::net::Ipv6Addr) -> bool {
+        let mut ip = IpAddr::V6(ipv6);
+        if !self.contains(IpAddr::V6(ipv6), &mut ip) {
+            return false;
+        }
+        let IpAddr::V6(ip) = ip else {
+            return false;
+        };
+        *real_ip = ip;
+        true
+    }
+
+    /// check whether the ip is in one of the (mapped) proxy cidrs, and if so, translate it
+    /// into the address in the real cidr.
+    pub fn contains(&self, ip: IpAddr, real_ip: &mut IpAddr) -> bool {
         let s = self.cidr_set.lock().unwrap();
         for cidr in s.iter() {
-            if cidr.contains(&ip) {
-                if let Some(real_cidr) = self.mapped_to_real.get(cidr).map(|v| *v.value()) {
-                    let origin_network_bits = real_cidr.first().address().to_bits();
-                    let network_mask = cidr.mask().to_bits();
-
-                    let mut converted_ip = ipv4.to_bits();
-                    converted_ip &= !network_mask;
-                    converted_ip |= origin_network_bits;
-
-                    *real_ip = std::net::Ipv4Addr::from(converted_ip);
-                } else {
-                    *real_ip = ipv4;
-                }
-                return true;
+            if !cidr.contains(&ip) {
+                continue;
             }
+            *real_ip = match self.mapped_to_real.get(cidr).map(|v| *v.value()) {
+                Some(real_cidr) => Self::map_to_real(ip, cidr, &real_cidr),
+                None => ip,
+            };
+            return true;
         }
         false
     }
 
+    fn map_to_real(ip: IpAddr, mapped_cidr: &cidr::IpCidr, real_cidr: &cidr::IpCidr) -> IpAddr {
+        match (ip, mapped_cidr, real_cidr) {
+            (IpAddr::V4(ip), cidr::IpCidr::V4(mapped), cidr::IpCidr::V4(real)) => {
+                let network_mask = mapped.mask().to_bits();
+                let host_bits = ip.to_bits() & !network_mask;
+                Ipv4Addr::from_bits(real.first_address().to_bits() | host_bits).into()
+            }
+            (IpAddr::V6(ip), cidr::IpCidr::V6(mapped), cidr::IpCidr::V6(real)) => {
+                let network_mask = mapped.mask().to_bits();
+                let host_bits = ip.to_bits() & !network_mask;
+                Ipv6Addr::from_bits(real.first_address().to_bits() | host_bits).into()
+            }
+            // config loader rejects mapping between different address families
+            _ => ip,
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.cidr_set.lock().unwrap().is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    use crate::common::global_ctx::tests::get_mock_global_ctx;
+
+    use super::CidrSet;
+
+    #[tokio::test]
+    async fn cidr_set_maps_v4_and_v6() {
+        let global_ctx = get_mock_global_ctx();
+        global_ctx
+            .config
+            .add_proxy_cidr(
+                "10.1.2.0/24".parse().unwrap(),
+                Some("10.20.2.0/24".parse().unwrap()),
+            )
+            .unwrap();
+        global_ctx
+            .config
+            .add_proxy_cidr(
+                "fd00:1::/64".parse().unwrap(),
+                Some("fd00:2::/64".parse().unwrap()),
+            )
+            .unwrap();
+        global_ctx
+            .config
+            .add_proxy_cidr("fd00:3::/64".parse().unwrap(), None)
+            .unwrap();
+        assert!(global_ctx
+            .config
+            .add_proxy_cidr(
+                "fd00:4::/64".parse().unwrap(),
+                Some("10.30.0.0/24".parse().unwrap()),
+            )
+            .is_err());
+
+        let cidr_set = CidrSet::new(global_ctx);
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let mut real_v4 = Ipv4Addr::UNSPECIFIED;
+        assert!(cidr_set.contains_v4("10.20.2.7".parse().unwrap(), &mut real_v4));
+        assert_eq!(real_v4, "10.1.2.7".parse::<Ipv4Addr>().unwrap());
+
+        let mut real_v6 = Ipv6Addr::UNSPECIFIED;
+        assert!(cidr_set.contains_v6("fd00:2::1:7".parse().unwrap(), &mut real_v6));
+        assert_eq!(real_v6, "fd00:1::1:7".parse::<Ipv6Addr>().unwrap());
+        assert!(cidr_set.contains_v6("fd00:3::9".parse().unwrap(), &mut real_v6));
+        assert_eq!(real_v6, "fd00:3::9".parse::<Ipv6Addr>().unwrap());
+        assert!(!cidr_set.contains_v6("fd00:1::7".parse().unwrap(), &mut real_v6));
+
+        let mut real_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        assert!(!cidr_set.contains("10.1.2.7".parse().unwrap(), &mut real_ip));
+    }
+}
@@ -1,10 +1,9 @@
-use std::net::{IpAddr, Ipv4Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::sync::{Arc, Mutex, Weak};
 use std::{net::SocketAddr, pin::Pin};
 
 use anyhow::Context;
 use dashmap::DashMap;
-use pnet::packet::ipv4::Ipv4Packet;
 use prost::Message as _;
 use quinn::{Endpoint, Incoming};
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
@@ -92,11 +91,11 @@ impl NatDstConnector for NatDstQUICConnector {
             return Err(anyhow::anyhow!("peer manager is not available").into());
         };
 
-        let IpAddr::V4(dst_ipv4) = nat_dst.ip() else {
-            return Err(anyhow::anyhow!("src must be an IPv4 address").into());
+        let dst_peer = match nat_dst.ip() {
+            IpAddr::V4(ip) => peer_mgr.get_peer_map().get_peer_id_by_ipv4(&ip).await,
+            IpAddr::V6(ip) => peer_mgr.get_peer_map().get_peer_id_by_ipv6(&ip).await,
         };
-
-        let Some(dst_peer) = peer_mgr.get_peer_map().get_peer_id_by_ipv4(&dst_ipv4).await else {
+        let Some(dst_peer) = dst_peer else {
             return Err(anyhow::anyhow!("no peer found for dst: {}", nat_dst).into());
         };
 
@@ -169,8 +168,8 @@ impl NatDstConnector for NatDstQUICConnector {
         _cidr_set: &CidrSet,
         _global_ctx: &GlobalCtx,
         hdr: &PeerManagerHeader,
-        _ipv4: &Ipv4Packet,
-        _real_dst_ip: &mut Ipv4Addr,
+        _dst_ip: IpAddr,
+        _real_dst_ip: &mut IpAddr,
     ) -> bool {
         hdr.from_peer_id == hdr.to_peer_id && !hdr.is_kcp_src_modified()
     }
@@ -191,10 +190,14 @@ impl TcpProxyForKcpSrcTrait for TcpProxyForQUICSrc {
         &self.0
     }
 
-    async fn check_dst_allow_kcp_input(&self, dst_ip: &Ipv4Addr) -> bool {
+    async fn check_dst_allow_kcp_input(&self, dst_ip: &IpAddr) -> bool {
         let peer_map: Arc<crate::peers::peer_map::PeerMap> =
             self.0.get_peer_manager().get_peer_map();
-        let Some(dst_peer_id) = peer_map.get_peer_id_by_ipv4(dst_ip).await else {
+        let dst_peer_id = match dst_ip {
+            IpAddr::V4(ip) => peer_map.get_peer_id_by_ipv4(ip).await,
+            IpAddr::V6(ip) => peer_map.get_peer_id_by_ipv6(ip).await,
+        };
+        let Some(dst_peer_id) = dst_peer_id else {
             return false;
         };
         let Some(peer_info) = peer_map.get_route_peer_info(dst_peer_id).await else {
@@ -386,37 +389,42 @@ impl QUICProxyDst {
         let proxy_dst_info =
             ProxyDstInfo::decode(&buf[..]).with_context(|| "failed to decode proxy dst info")?;
 
-        let dst_socket: SocketAddr = proxy_dst_info
+        let mut dst_socket: SocketAddr = proxy_dst_info
             .dst_addr
             .map(Into::into)
             .ok_or_else(|| anyhow::anyhow!("no dst addr in proxy dst info"))?;
 
-        let SocketAddr::V4(mut dst_socket) = dst_socket else {
-            return Err(anyhow::anyhow!("NAT destination must be an IPv4 address").into());
-        };
-
-        let mut real_ip = *dst_socket.ip();
-        if cidr_set.contains_v4(*dst_socket.ip(), &mut real_ip) {
+        let mut real_ip = dst_socket.ip();
+        if cidr_set.contains(dst_socket.ip(), &mut real_ip) {
             dst_socket.set_ip(real_ip);
         }
 
         let src_ip = addr.ip();
-        let dst_ip = *dst_socket.ip();
+        let dst_ip = dst_socket.ip();
         let (src_groups, dst_groups) = tokio::join!(
             route.get_peer_groups_by_ip(&src_ip),
-            route.get_peer_groups_by_ipv4(&dst_ip)
+            route.get_peer_groups_by_ip(&dst_ip)
         );
 
-        let send_to_self = Some(*dst_socket.ip()) == ctx.get_ipv4().map(|ip| ip.address());
+        let (send_to_self, loopback): (bool, IpAddr) = match dst_ip {
+            IpAddr::V4(ip) => (
+                Some(ip) == ctx.get_ipv4().map(|ip| ip.address()),
+                Ipv4Addr::LOCALHOST.into(),
+            ),
+            IpAddr::V6(ip) => (
+                Some(ip) == ctx.get_ipv6().map(|ip| ip.address()),
+                Ipv6Addr::LOCALHOST.into(),
+            ),
+        };
         if send_to_self && ctx.no_tun() {
-            dst_socket = format!("127.0.0.1:{}", dst_socket.port()).parse().unwrap();
+            dst_socket.set_ip(loopback);
         }
 
         proxy_entries.insert(
             proxy_entry_key,
             TcpProxyEntry {
                 src: Some(addr.into()),
-                dst: Some(SocketAddr::V4(dst_socket).into()),
+                dst: Some(dst_socket.into()),
                 start_time: chrono::Local::now().timestamp() as u64,
                 state: TcpProxyEntryState::ConnectingDst.into(),
                 transport_type: TcpProxyEntryTransportType::Quic.into(),
@@ -427,7 +435,7 @@ impl QUICProxyDst {
             acl_filter: ctx.get_acl_filter().clone(),
             packet_info: PacketInfo {
                 src_ip,
-                dst_ip: dst_ip.into(),
+                dst_ip,
                 src_port: Some(addr.port()),
                 dst_port: Some(dst_socket.port()),
                 protocol: Protocol::Tcp,
@@ -448,7 +456,7 @@ impl QUICProxyDst {
         let dst_stream = {
             let _g = ctx.net_ns.guard();
             connector
-                .connect("0.0.0.0:0".parse().unwrap(), dst_socket.into())
+                .connect("0.0.0.0:0".parse().unwrap(), dst_socket)
                 .await?
         };
 
@@ -1,7 +1,8 @@
 use std::{
+    collections::HashMap,
     net::{IpAddr, Ipv4Addr, SocketAddr},
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc, Weak,
     },
     time::{Duration, Instant},
@@ -13,16 +14,21 @@ use tokio_util::sync::{CancellationToken, DropGuard};
 
 use crate::{
     common::{
-        config::PortForwardConfig, global_ctx::GlobalCtxEvent, join_joinset_background,
-        netns::NetNS, scoped_task::ScopedTask,
+        config::{PortForwardConfig, Socks5UserConfig},
+        global_ctx::GlobalCtxEvent,
+        join_joinset_background,
+        netns::NetNS,
+        scoped_task::ScopedTask,
+        stats_manager::{CounterHandle, LabelSet, LabelType, MetricName, StatsManager},
     },
     gateway::{
         fast_socks5::{
-            server::{
-                AcceptAuthentication, AsyncTcpConnector, Config, SimpleUserPassword, Socks5Socket,
-            },
-            util::stream::tcp_connect_with_timeout,
+            new_udp_header, parse_udp_request,
+            server::{AsyncTcpConnector, Authentication, Config, Socks5Socket},
+            util::{stream::tcp_connect_with_timeout, target_addr::TargetAddr},
+            ReplyError, Socks5Command, SocksError,
         },
+        http_proxy::{serve_http_proxy, HttpProxyAuth},
         ip_reassembler::IpReassembler,
         kcp_proxy::NatDstKcpConnector,
         tokio_smoltcp::{channel_device, BufferSize, Net, NetConfig},
@@ -38,7 +44,7 @@ use pnet::packet::{
     ip::IpNextHeaderProtocols, ipv4::Ipv4Packet, tcp::TcpPacket, udp::UdpPacket, Packet,
 };
 use tokio::{
-    io::{AsyncRead, AsyncWrite},
+    io::{AsyncRead, AsyncReadExt, AsyncWrite},
     net::{TcpListener, TcpSocket, UdpSocket},
     select,
     sync::{mpsc, Mutex, Notify},
@@ -49,6 +55,7 @@ use tokio::{
 use crate::{
     common::{error::Error, global_ctx::GlobalCtx},
     peers::{peer_manager::PeerManager, PeerPacketFilter},
+    proto::cli::PortForwardStats as PortForwardStatsPb,
 };
 
 use super::tcp_proxy::NatDstConnector as _;
@@ -137,11 +144,14 @@ impl AsyncWrite for SocksTcpStream {
 enum Socks5EntryData {
     Tcp(TcpListener), // hold a binded socket to hold the tcp port
     Udp((Arc<SocksUdpSocket>, UdpClientKey)), // hold the socket to send data to dst
+    UdpAssociate,     // the socket is owned by the socks5 udp associate task
 }
 
 const UDP_ENTRY: u8 = 1;
 const TCP_ENTRY: u8 = 2;
 
+const SOCKS5_BIND_TIMEOUT_SECS: u64 = 120;
+
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
 struct Socks5Entry {
     src: SocketAddr,
@@ -272,20 +282,400 @@ fn bind_udp_socket(addr: SocketAddr, net_ns: NetNS) -> Result<UdpSocket, Error>
     Ok(UdpSocket::from_std(socket2_socket.into())?)
 }
 
+// authenticates socks5 portal users against the users in config
+struct Socks5UserAuth {
+    users: HashMap<String, Arc<Socks5UserConfig>>,
+}
+
+#[async_trait::async_trait]
+impl Authentication for Socks5UserAuth {
+    type Item = Arc<Socks5UserConfig>;
+
+    async fn authenticate(&self, credentials: Option<(String, String)>) -> Option<Self::Item> {
+        let (username, password) = credentials?;
+        self.users
+            .get(&username)
+            .filter(|user| user.password == password)
+            .cloned()
+    }
+}
+
+type Socks5PortalSocket = Socks5Socket<tokio::net::TcpStream, Socks5UserAuth, SmolTcpConnector>;
+
+// removes the entries registered by a socks5 command when the command finishes
+struct Socks5EntryGuard {
+    entries: Socks5EntrySet,
+    keys: Vec<Socks5Entry>,
+}
+
+impl Drop for Socks5EntryGuard {
+    fn drop(&mut self) {
+        for key in self.keys.iter() {
+            self.entries.remove(key);
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Socks5CommandCtx {
+    ipv4_addr: cidr::Ipv4Inet,
+    smoltcp_net: Arc<Net>,
+    entries: Socks5EntrySet,
+    net_ns: NetNS,
+    stats_manager: Arc<StatsManager>,
+}
+
+impl Socks5CommandCtx {
+    fn is_local_ip(&self, ip: &IpAddr) -> bool {
+        *ip == IpAddr::V4(self.ipv4_addr.address())
+    }
+
+    fn counter(&self, name: MetricName, user: &str) -> CounterHandle {
+        self.stats_manager.get_counter(
+            name,
+            LabelSet::new().with_label_type(LabelType::UserName(user.to_string())),
+        )
+    }
+
+    async fn execute(
+        &self,
+        mut socket: Socks5PortalSocket,
+        local_ip: IpAddr,
+        client_ip: IpAddr,
+    ) -> Result<(), SocksError> {
+        let user = socket.take_credentials();
+        let user_name = user
+            .as_ref()
+            .map(|u| u.username.clone())
+            .unwrap_or_else(|| "anonymous".to_string());
+        let Some(TargetAddr::Ip(target)) = socket.target_addr().cloned() else {
+            socket
+                .reply_error(&ReplyError::AddressTypeNotSupported)
+                .await?;
+            return Err(ReplyError::AddressTypeNotSupported.into());
+        };
+
+        self.stats_manager
+            .get_counter(
+                MetricName::Socks5Connections,
+                LabelSet::new()
+                    .with_label_type(LabelType::UserName(user_name.clone()))
+                    .with_label_type(LabelType::Protocol(
+                        match socket.cmd() {
+                            Some(Socks5Command::TCPConnect) => "connect",
+                            Some(Socks5Command::TCPBind) => "bind",
+                            Some(Socks5Command::UDPAssociate) => "udp_associate",
+                            None => "unknown",
+                        }
+                        .to_string(),
+                    )),
+            )
+            .inc();
+
+        match socket.cmd() {
+            Some(Socks5Command::TCPConnect) => {
+                if user.as_ref().is_some_and(|u| !u.is_dst_allowed(&target)) {
+                    tracing::info!(?user_name, ?target, "socks5 connect not allowed for user");
+                    socket
+                        .reply_error(&ReplyError::ConnectionNotAllowed)
+                        .await?;
+                    return Err(ReplyError::ConnectionNotAllowed.into());
+                }
+                self.execute_connect(socket, target, &user_name).await
+            }
+            Some(Socks5Command::TCPBind) => {
+                if !target.ip().is_unspecified()
+                    && user
+                        .as_ref()
+                        .is_some_and(|u| !u.is_ip_allowed(&target.ip()))
+                {
+                    tracing::info!(?user_name, ?target, "socks5 bind not allowed for user");
+                    socket
+                        .reply_error(&ReplyError::ConnectionNotAllowed)
+                        .await?;
+                    return Err(ReplyError::ConnectionNotAllowed.into());
+                }
+                self.execute_bind(socket, target.ip(), user, &user_name)
+                    .await
+            }
+            Some(Socks5Command::UDPAssociate) => {
+                self.execute_udp_associate(socket, local_ip, client_ip, user, &user_name)
+                    .await
+            }
+            None => {
+                socket.reply_error(&ReplyError::CommandNotSupported).await?;
+                Err(ReplyError::CommandNotSupported.into())
+            }
+        }
+    }
+
+    async fn transfer<S: AsyncRead + AsyncWrite + Unpin>(
+        &self,
+        socket: &mut Socks5PortalSocket,
+        outbound: &mut S,
+        user_name: &str,
+    ) {
+        match tokio::io::copy_bidirectional(socket, outbound).await {
+            Ok((tx, rx)) => {
+                self.counter(MetricName::Socks5BytesTx, user_name).add(tx);
+                self.counter(MetricName::Socks5BytesRx, user_name).add(rx);
+                tracing::info!("socks5 transfer closed ({}, {})", tx, rx);
+            }
+            Err(e) => tracing::error!("socks5 transfer error: {:?}", e),
+        }
+    }
+
+    async fn execute_connect(
+        &self,
+        mut socket: Socks5PortalSocket,
+        target: SocketAddr,
+        user_name: &str,
+    ) -> Result<(), SocksError> {
+        let mut outbound = match socket.tcp_connector().tcp_connect(target, 10).await {
+            Ok(outbound) => outbound,
+            Err(e) => {
+                socket.reply_error(&ReplyError::HostUnreachable).await?;
+                return Err(e);
+            }
+        };
+
+        socket
+            .reply(
+                &ReplyError::Succeeded,
+                SocketAddr::new(self.ipv4_addr.address().into(), 0),
+            )
+            .await?;
+
+        // the socket holds the connector, which keeps the smoltcp entry alive during transfer
+        self.transfer(&mut socket, &mut outbound, user_name).await;
+        Ok(())
+    }
+
+    // RFC 1928 BIND: listen on the virtual ip, reply with the listening address, then reply
+    // again with the address of the peer that connected and relay the connection.
+    async fn execute_bind(
+        &self,
+        mut socket: Socks5PortalSocket,
+        expected_ip: IpAddr,
+        user: Option<Arc<Socks5UserConfig>>,
+        user_name: &str,
+    ) -> Result<(), SocksError> {
+        let port_holder = {
+            let _g = self.net_ns.guard();
+            TcpListener::bind("0.0.0.0:0").await?
+        };
+        let local_addr = SocketAddr::new(
+            self.ipv4_addr.address().into(),
+            port_holder.local_addr()?.port(),
+        );
+        let mut listener = self.smoltcp_net.tcp_bind(local_addr).await?;
+
+        // the remote port is not known before accept, so route packets of any port from the
+        // expected ip to smoltcp
+        let entry = Socks5Entry {
+            src: local_addr,
+            dst: SocketAddr::new(expected_ip, 0),
+            entry_type: TCP_ENTRY,
+        };
+        self.entries
+            .insert(entry.clone(), Socks5EntryData::Tcp(port_holder));
+        let _entry_guard = Socks5EntryGuard {
+            entries: self.entries.clone(),
+            keys: vec![entry],
+        };
+
+        socket.reply(&ReplyError::Succeeded, local_addr).await?;
+
+        let (mut inbound, peer_addr) = match timeout(
+            Duration::from_secs(SOCKS5_BIND_TIMEOUT_SECS),
+            listener.accept(),
+        )
+        .await
+        {
+            Ok(Ok(ret)) => ret,
+            Ok(Err(e)) => {
+                socket.reply_error(&ReplyError::GeneralFailure).await?;
+                return Err(e.into());
+            }
+            Err(_) => {
+                socket.reply_error(&ReplyError::ConnectionTimeout).await?;
+                return Err(ReplyError::ConnectionTimeout.into());
+            }
+        };
+
+        if !expected_ip.is_unspecified() && peer_addr.ip() != expected_ip {
+            tracing::info!(?peer_addr, ?expected_ip, "socks5 bind got unexpected peer");
+            socket
+                .reply_error(&ReplyError::ConnectionNotAllowed)
+                .await?;
+            return Err(ReplyError::ConnectionNotAllowed.into());
+        }
+
+        // an unspecified DST.ADDR accepts any peer, so the user policy is checked on the peer
+        if user
+            .as_ref()
+            .is_some_and(|u| !u.is_ip_allowed(&peer_addr.ip()))
+        {
+            tracing::info!(
+                ?peer_addr,
+                ?user_name,
+                "socks5 bind peer not allowed for user"
+            );
+            socket
+                .reply_error(&ReplyError::ConnectionNotAllowed)
+                .await?;
+            return Err(ReplyError::ConnectionNotAllowed.into());
+        }
+
+        socket.reply(&ReplyError::Succeeded, peer_addr).await?;
+        self.transfer(&mut socket, &mut inbound, user_name).await;
+        Ok(())
+    }
+
+    // RFC 1928 UDP ASSOCIATE: relay datagrams between a udp socket on the portal address and
+    // the virtual network until the control connection closes.
+    async fn execute_udp_associate(
+        &self,
+        mut socket: Socks5PortalSocket,
+        local_ip: IpAddr,
+        client_ip: IpAddr,
+        user: Option<Arc<Socks5UserConfig>>,
+        user_name: &str,
+    ) -> Result<(), SocksError> {
+        let (relay, port_holder) = {
+            let _g = self.net_ns.guard();
+            (
+                UdpSocket::bind(SocketAddr::new(local_ip, 0)).await?,
+                // reserve the port on the virtual ip, also used to reach services on this node
+                UdpSocket::bind("0.0.0.0:0").await?,
+            )
+        };
+        let local_port = port_holder.local_addr()?.port();
+        let local_addr = SocketAddr::new(self.ipv4_addr.address().into(), local_port);
+        let smol_udp = self.smoltcp_net.udp_bind(local_addr).await?;
+        let mut entry_guard = Socks5EntryGuard {
+            entries: self.entries.clone(),
+            keys: vec![],
+        };
+
+        socket
+            .reply(&ReplyError::Succeeded, relay.local_addr()?)
+            .await?;
+
+        let tx_counter = self.counter(MetricName::Socks5BytesTx, user_name);
+        let rx_counter = self.counter(MetricName::Socks5BytesRx, user_name);
+        let mut client_addr = None;
+        let mut relay_buf = vec![0u8; 65536];
+        let mut smol_buf = vec![0u8; 8192];
+        let mut local_buf = vec![0u8; 65536];
+        let mut control_buf = [0u8; 64];
+        loop {
+            let (payload, from) = select! {
+                ret = socket.read(&mut control_buf) => {
+                    // the association ends when the control connection closes
+                    if !matches!(ret, Ok(n) if n > 0) {
+                        break;
+                    }
+                    continue;
+                }
+                ret = relay.recv_from(&mut relay_buf) => {
+                    let (len, from) = ret?;
+                    if from.ip().to_canonical() != client_ip {
+                        tracing::debug!(?from, ?client_ip, "socks5 udp from unknown client");
+                        continue;
+                    }
+                    client_addr = Some(from);
+
+                    let Ok((frag, target, data)) = parse_udp_request(&relay_buf[..len]).await else {
+                        continue;
+                    };
+                    if frag != 0 {
+                        tracing::debug!("discard socks5 udp fragment");
+                        continue;
+                    }
+                    let Ok(TargetAddr::Ip(target)) = target.resolve_dns().await else {
+                        continue;
+                    };
+                    let target = SocketAddr::new(target.ip().to_canonical(), target.port());
+                    if !target.is_ipv4() {
+                        // the virtual network is ipv4 only, end the association instead of
+                        // leaving the client waiting for replies that never come
+                        tracing::info!(?target, ?user_name, "socks5 udp to ipv6 unsupported");
+                        socket
+                            .reply_error(&ReplyError::AddressTypeNotSupported)
+                            .await?;
+                        return Err(ReplyError::AddressTypeNotSupported.into());
+                    }
+                    if user.as_ref().is_some_and(|u| !u.is_dst_allowed(&target)) {
+                        tracing::debug!(?target, ?user_name, "socks5 udp target not allowed");
+                        continue;
+                    }
+
+                    let ret = if self.is_local_ip(&target.ip()) {
+                        port_holder
+                            .send_to(data, (Ipv4Addr::LOCALHOST, target.port()))
+                            .await
+                    } else {
+                        let entry = Socks5Entry {
+                            src: local_addr,
+                            dst: target,
+                            entry_type: UDP_ENTRY,
+                        };
+                        if !self.entries.contains_key(&entry) {
+                            self.entries
+                                .insert(entry.clone(), Socks5EntryData::UdpAssociate);
+                            entry_guard.keys.push(entry);
+                        }
+                        smol_udp.send_to(data, target).await
+                    };
+                    match ret {
+                        Ok(_) => tx_counter.add(data.len() as u64),
+                        Err(e) => tracing::debug!(?target, "socks5 udp send failed: {:?}", e),
+                    }
+                    continue;
+                }
+                ret = smol_udp.recv_from(&mut smol_buf) => {
+                    let (len, from) = ret?;
+                    (&smol_buf[..len], from)
+                }
+                ret = port_holder.recv_from(&mut local_buf) => {
+                    // replies from services on this node come from loopback, show them as
+                    // coming from the virtual ip
+                    let (len, from) = ret?;
+                    (&local_buf[..len], SocketAddr::new(local_addr.ip(), from.port()))
+                }
+            };
+
+            let Some(client_addr) = client_addr else {
+                continue;
+            };
+            let len = payload.len();
+            let mut data = new_udp_header(from)?;
+            data.extend_from_slice(payload);
+            if relay.send_to(&data, client_addr).await.is_ok() {
+                rx_counter.add(len as u64);
+            }
+        }
+
+        Ok(())
+    }
+}
+
 struct Socks5ServerNet {
     ipv4_addr: cidr::Ipv4Inet,
-    auth: Option<SimpleUserPassword>,
+    config: Arc<Config<Socks5UserAuth>>,
 
     smoltcp_net: Arc<Net>,
     forward_tasks: Arc<std::sync::Mutex<JoinSet<()>>>,
 
     entries: Socks5EntrySet,
+    cmd_ctx: Socks5CommandCtx,
 }
 
 impl Socks5ServerNet {
     pub fn new(
         ipv4_addr: cidr::Ipv4Inet,
-        auth: Option<SimpleUserPassword>,
+        global_ctx: &GlobalCtx,
         peer_manager: Arc<PeerManager>,
         packet_recv: Arc<Mutex<mpsc::Receiver<ZCPacket>>>,
         entries: Socks5EntrySet,
@@ -348,26 +738,52 @@ impl Socks5ServerNet {
             ),
         );
 
+        // commands are executed by Socks5CommandCtx, so they go through the virtual network
+        // and per user policies are applied
+        let users = global_ctx.config.get_socks5_users();
+        let mut config = Config::<Socks5UserAuth>::default();
+        config.set_request_timeout(10);
+        config.set_skip_auth(false);
+        config.set_allow_no_auth(users.is_empty());
+        config.set_execute_command(false);
+        config.set_udp_support(true);
+        config.set_bind_support(true);
+        if !users.is_empty() {
+            config = config.with_authentication(Socks5UserAuth {
+                users: users
+                    .into_iter()
+                    .map(|u| (u.username.clone(), Arc::new(u)))
+                    .collect(),
+            });
+        }
+
+        let smoltcp_net = Arc::new(net);
         Self {
             ipv4_addr,
-            auth,
+            config: Arc::new(config),
 
-            smoltcp_net: Arc::new(net),
+            smoltcp_net: smoltcp_net.clone(),
             forward_tasks: Arc::new(std::sync::Mutex::new(forward_tasks)),
 
-            entries,
+            entries: entries.clone(),
+            cmd_ctx: Socks5CommandCtx {
+                ipv4_addr,
+                smoltcp_net,
+                entries,
+                net_ns: global_ctx.net_ns.clone(),
+                stats_manager: global_ctx.stats_manager().clone(),
+            },
         }
     }
 
     fn handle_tcp_stream(&self, stream: tokio::net::TcpStream) {
-        let mut config = Config::<AcceptAuthentication>::default();
-        config.set_request_timeout(10);
-        config.set_skip_auth(false);
-        config.set_allow_no_auth(true);
+        let (Ok(local_addr), Ok(peer_addr)) = (stream.local_addr(), stream.peer_addr()) else {
+            return;
+        };
 
         let socket = Socks5Socket::new(
             stream,
-            Arc::new(config),
+            self.config.clone(),
             SmolTcpConnector {
                 net: self.smoltcp_net.clone(),
                 entries: self.entries.clone(),
@@ -375,13 +791,47 @@ impl Socks5ServerNet {
             },
         );
 
+        let cmd_ctx = self.cmd_ctx.clone();
         self.forward_tasks.lock().unwrap().spawn(async move {
-            match socket.upgrade_to_socks5().await {
+            let socket = match socket.upgrade_to_socks5().await {
+                Ok(socket) => socket,
+                Err(e) => {
+                    tracing::error!("socks5 handshake failed: {:?}", e);
+                    return;
+                }
+            };
+            let ret = cmd_ctx
+                .execute(
+                    socket,
+                    local_addr.ip().to_canonical(),
+                    peer_addr.ip().to_canonical(),
+                )
+                .await;
+            match ret {
                 Ok(_) => {
                     tracing::info!("socks5 handle success");
                 }
                 Err(e) => {
-                    tracing::error!("socks5 handshake failed: {:?}", e);
+                    tracing::error!("socks5 command failed: {:?}", e);
+                }
+            };
+        });
+    }
+
+    fn handle_http_stream(&self, stream: tokio::net::TcpStream, auth: Option<HttpProxyAuth>) {
+        let connector = SmolTcpConnector {
+            net: self.smoltcp_net.clone(),
+            entries: self.entries.clone(),
+            current_entry: std::sync::Mutex::new(None),
+        };
+
+        self.forward_tasks.lock().unwrap().spawn(async move {
+            match serve_http_proxy(stream, &connector, auth.as_ref()).await {
+                Ok((tx, rx)) => {
+                    tracing::info!("http proxy transfer closed ({}, {})", tx, rx);
+                }
+                Err(e) => {
+                    tracing::error!("http proxy handle failed: {:?}", e);
                 }
             };
         });
@@ -393,8 +843,17 @@ struct UdpClientInfo {
     port_holder_socket: Arc<UdpSocket>,
     local_addr: SocketAddr,
     last_active: AtomicCell<Instant>,
+    idle_timeout: Duration,
     entries: Socks5EntrySet,
     entry_key: Socks5Entry,
+    // also tells which rule the session belongs to, each rule has its own stats
+    conn_guard: PortForwardConnGuard,
+}
+
+impl UdpClientInfo {
+    fn is_of_rule(&self, stats: &Arc<PortForwardStats>) -> bool {
+        Arc::ptr_eq(&self.conn_guard.0, stats)
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
@@ -403,10 +862,105 @@ struct UdpClientKey {
     dst_addr: SocketAddr,
 }
 
+#[derive(Debug, Default)]
+pub struct PortForwardStats {
+    rx_bytes: AtomicU64,
+    tx_bytes: AtomicU64,
+    total_connections: AtomicU64,
+    active_connections: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl PortForwardStats {
+    fn new_connection(self: &Arc<Self>) -> PortForwardConnGuard {
+        self.total_connections.fetch_add(1, Ordering::Relaxed);
+        self.active_connections.fetch_add(1, Ordering::Relaxed);
+        PortForwardConnGuard(self.clone())
+    }
+}
+
+impl From<&PortForwardStats> for PortForwardStatsPb {
+    fn from(stats: &PortForwardStats) -> Self {
+        PortForwardStatsPb {
+            rx_bytes: stats.rx_bytes.load(Ordering::Relaxed),
+            tx_bytes: stats.tx_bytes.load(Ordering::Relaxed),
+            total_connections: stats.total_connections.load(Ordering::Relaxed),
+            active_connections: stats.active_connections.load(Ordering::Relaxed),
+            rejected: stats.rejected.load(Ordering::Relaxed),
+        }
+    }
+}
+
+// a tcp connection or udp session of a port forward rule, counted as active until dropped
+struct PortForwardConnGuard(Arc<PortForwardStats>);
+
+impl Drop for PortForwardConnGuard {
+    fn drop(&mut self) {
+        self.0.active_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+// the client side of a forwarded tcp connection, bytes read from it are rx and bytes written to
+// it are tx. counted as they flow, so connections that end with an error are accounted too.
+struct PortForwardCountedStream<S> {
+    inner: S,
+    stats: Arc<PortForwardStats>,
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for PortForwardCountedStream<S> {
+    fn poll_read(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let filled = buf.filled().len();
+        let ret = std::pin::Pin::new(&mut this.inner).poll_read(cx, buf);
+        if let std::task::Poll::Ready(Ok(())) = ret {
+            let len = (buf.filled().len() - filled) as u64;
+            this.stats.rx_bytes.fetch_add(len, Ordering::Relaxed);
+        }
+        ret
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for PortForwardCountedStream<S> {
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<Result<usize, std::io::Error>> {
+        let this = self.get_mut();
+        let ret = std::pin::Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let std::task::Poll::Ready(Ok(len)) = ret {
+            this.stats.tx_bytes.fetch_add(len as u64, Ordering::Relaxed);
+        }
+        ret
+    }
+
+    fn poll_flush(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), std::io::Error>> {
+        std::pin::Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), std::io::Error>> {
+        std::pin::Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+struct PortForwardHandle {
+    _cancel_guard: DropGuard,
+    stats: Arc<PortForwardStats>,
+}
+
 pub struct Socks5Server {
     global_ctx: Arc<GlobalCtx>,
     peer_manager: Arc<PeerManager>,
-    auth: Option<SimpleUserPassword>,
 
     tasks: Arc<std::sync::Mutex<JoinSet<()>>>,
     packet_sender: mpsc::Sender<ZCPacket>,
@@ -422,14 +976,14 @@ pub struct Socks5Server {
     kcp_endpoint: Mutex<Option<Weak<KcpEndpoint>>>,
 
     socks5_enabled: Arc<AtomicBool>,
-    cancel_tokens: Arc<DashMap<PortForwardConfig, DropGuard>>,
+    port_forwards: Arc<DashMap<PortForwardConfig, PortForwardHandle>>,
     port_forward_list_change_notifier: Arc<Notify>,
 }
 
 #[async_trait::async_trait]
 impl PeerPacketFilter for Socks5Server {
     async fn try_process_packet_from_peer(&self, packet: ZCPacket) -> Option<ZCPacket> {
-        if self.cancel_tokens.is_empty()
+        if self.port_forwards.is_empty()
             && self.entries.is_empty()
             && !self.socks5_enabled.load(Ordering::Relaxed)
         {
@@ -498,7 +1052,7 @@ impl PeerPacketFilter for Socks5Server {
             }
         };
 
-        if !self.entries.contains_key(&entry_key) {
+        if !self.entries.contains_key(&entry_key) && !self.is_bind_entry(&entry_key) {
             return Some(packet);
         }
 
@@ -511,16 +1065,27 @@ impl PeerPacketFilter for Socks5Server {
 }
 
 impl Socks5Server {
-    pub fn new(
-        global_ctx: Arc<GlobalCtx>,
-        peer_manager: Arc<PeerManager>,
-        auth: Option<SimpleUserPassword>,
-    ) -> Arc<Self> {
+    // a socks5 BIND listener registers its entry before the remote port is known, with port 0
+    // and the expected remote ip, or the unspecified ip if any remote is accepted
+    fn is_bind_entry(&self, entry_key: &Socks5Entry) -> bool {
+        if entry_key.entry_type != TCP_ENTRY {
+            return false;
+        }
+        [entry_key.dst.ip(), Ipv4Addr::UNSPECIFIED.into()]
+            .into_iter()
+            .any(|ip| {
+                self.entries.contains_key(&Socks5Entry {
+                    dst: SocketAddr::new(ip, 0),
+                    ..entry_key.clone()
+                })
+            })
+    }
+
+    pub fn new(global_ctx: Arc<GlobalCtx>, peer_manager: Arc<PeerManager>) -> Arc<Self> {
         let (packet_sender, packet_recv) = mpsc::channel(1024);
         Arc::new(Self {
             global_ctx,
             peer_manager,
-            auth,
 
             tasks: Arc::new(std::sync::Mutex::new(JoinSet::new())),
             packet_recv: Arc::new(Mutex::new(packet_recv)),
@@ -536,7 +1101,7 @@ impl Socks5Server {
             kcp_endpoint: Mutex::new(None),
 
             socks5_enabled: Arc::new(AtomicBool::new(false)),
-            cancel_tokens: Arc::new(DashMap::new()),
+            port_forwards: Arc::new(DashMap::new()),
             port_forward_list_change_notifier: Arc::new(Notify::new()),
         })
     }
@@ -549,13 +1114,13 @@ impl Socks5Server {
         let entries = self.entries.clone();
         let tcp_forward_task = self.tcp_forward_task.clone();
         let udp_client_map = self.udp_client_map.clone();
-        let cancel_tokens = self.cancel_tokens.clone();
+        let port_forwards = self.port_forwards.clone();
         let port_forward_list_change_notifier = self.port_forward_list_change_notifier.clone();
         let socks5_enabled = self.socks5_enabled.clone();
         self.tasks.lock().unwrap().spawn(async move {
             let mut prev_ipv4 = None;
             loop {
-                if cancel_tokens.is_empty() && !socks5_enabled.load(Ordering::Relaxed) {
+                if port_forwards.is_empty() && !socks5_enabled.load(Ordering::Relaxed) {
                     let _ = net.lock().await.take();
                     port_forward_list_change_notifier.notified().await;
                     continue;
@@ -574,7 +1139,7 @@ impl Socks5Server {
                     if let Some(cur_ipv4) = cur_ipv4 {
                         net.lock().await.replace(Socks5ServerNet::new(
                             cur_ipv4,
-                            None,
+                            &global_ctx,
                             peer_manager.clone(),
                             packet_recv.clone(),
                             entries.clone(),
@@ -598,16 +1163,7 @@ impl Socks5Server {
     ) -> Result<(), Error> {
         *self.kcp_endpoint.lock().await = kcp_endpoint;
         if let Some(proxy_url) = self.global_ctx.config.get_socks5_portal() {
-            let bind_addr = format!(
-                "{}:{}",
-                proxy_url.host_str().unwrap(),
-                proxy_url.port().unwrap()
-            );
-
-            let listener = bind_tcp_socket(
-                bind_addr.parse::<SocketAddr>().unwrap(),
-                self.global_ctx.net_ns.clone(),
-            )?;
+            let listener = self.bind_portal_listener(&proxy_url)?;
 
             let net = self.net.clone();
             self.tasks.lock().unwrap().spawn(async move {
@@ -625,9 +1181,35 @@ impl Socks5Server {
             });
 
             self.socks5_enabled.store(true, Ordering::Relaxed);
-            join_joinset_background(self.tasks.clone(), "socks5 server".to_string());
         };
 
+        if let Some(proxy_url) = self.global_ctx.config.get_http_portal() {
+            let listener = self.bind_portal_listener(&proxy_url)?;
+            let auth = HttpProxyAuth::from_url(&proxy_url);
+
+            let net = self.net.clone();
+            self.tasks.lock().unwrap().spawn(async move {
+                loop {
+                    match listener.accept().await {
+                        Ok((socket, _addr)) => {
+                            tracing::info!("accept a new http proxy connection, {:?}", socket);
+                            if let Some(net) = net.lock().await.as_ref() {
+                                net.handle_http_stream(socket, auth.clone());
+                            }
+                        }
+                        Err(err) => tracing::error!("accept error = {:?}", err),
+                    }
+                }
+            });
+
+            // the http portal dials through the same smoltcp net as the socks5 portal
+            self.socks5_enabled.store(true, Ordering::Relaxed);
+        };
+
+        if self.socks5_enabled.load(Ordering::Relaxed) {
+            join_joinset_background(self.tasks.clone(), "socks5 server".to_string());
+        }
+
         let cfgs = self.global_ctx.config.get_port_forwards();
         self.reload_port_forwards(&cfgs).await?;
 
@@ -640,9 +1222,30 @@ impl Socks5Server {
         Ok(())
     }
 
+    // portals listen on the ip of the url, host names are rejected instead of resolved so the
+    // listen address does not depend on dns
+    fn bind_portal_listener(&self, proxy_url: &url::Url) -> Result<TcpListener, Error> {
+        let ip: IpAddr = match proxy_url.host() {
+            Some(url::Host::Ipv4(ip)) => ip.into(),
+            Some(url::Host::Ipv6(ip)) => ip.into(),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "portal {} should listen on an ip address, e.g. 0.0.0.0",
+                    proxy_url
+                )
+                .into());
+            }
+        };
+        let Some(port) = proxy_url.port_or_known_default() else {
+            return Err(anyhow::anyhow!("portal {} has no port", proxy_url).into());
+        };
+
+        bind_tcp_socket(SocketAddr::new(ip, port), self.global_ctx.net_ns.clone())
+    }
+
     pub async fn reload_port_forwards(&self, cfgs: &Vec<PortForwardConfig>) -> Result<(), Error> {
         // remove entries not in new cfg
-        self.cancel_tokens.retain(|k, _| {
+        self.port_forwards.retain(|k, _| {
             cfgs.iter().any(|cfg| {
                 if cfg.dst_addr.ip().is_unspecified() {
                     k.bind_addr == cfg.bind_addr && k.proto == cfg.proto
@@ -653,7 +1256,7 @@ impl Socks5Server {
         });
         // add new ones
         for cfg in cfgs {
-            if !self.cancel_tokens.contains_key(cfg) {
+            if !self.port_forwards.contains_key(cfg) {
                 self.add_port_forward(cfg.clone()).await?;
             }
         }
@@ -662,10 +1265,12 @@ impl Socks5Server {
     }
 
     async fn handle_port_forward_connection(
-        mut incoming_socket: tokio::net::TcpStream,
+        incoming_socket: tokio::net::TcpStream,
         connector: Box<dyn AsyncTcpConnector<S = SocksTcpStream> + Send>,
         dst_addr: SocketAddr,
+        stats: Arc<PortForwardStats>,
     ) {
+        let _conn_guard = stats.new_connection();
         let outgoing_socket = match connector.tcp_connect(dst_addr, 10).await {
             Ok(socket) => socket,
             Err(e) => {
@@ -675,6 +1280,10 @@ impl Socks5Server {
         };
 
         let mut outgoing_socket = outgoing_socket;
+        let mut incoming_socket = PortForwardCountedStream {
+            inner: incoming_socket,
+            stats: stats.clone(),
+        };
         match tokio::io::copy_bidirectional(&mut incoming_socket, &mut outgoing_socket).await {
             Ok((from_client, from_server)) => {
                 tracing::info!(
@@ -689,12 +1298,17 @@ impl Socks5Server {
     }
 
     pub async fn add_port_forward(&self, cfg: PortForwardConfig) -> Result<(), Error> {
+        let addr_pairs = cfg.addr_pairs()?;
+        let cancel_token = CancellationToken::new();
+        let stats = Arc::new(PortForwardStats::default());
         match cfg.proto.to_lowercase().as_str() {
             "tcp" => {
-                self.add_tcp_port_forward(&cfg).await?;
+                self.add_tcp_port_forward(&cfg, addr_pairs, &cancel_token, &stats)
+                    .await?;
             }
             "udp" => {
-                self.add_udp_port_forward(&cfg).await?;
+                self.add_udp_port_forward(&cfg, addr_pairs, &cancel_token, &stats)
+                    .await?;
             }
             _ => {
                 return Err(anyhow::anyhow!(
@@ -704,268 +1318,356 @@ impl Socks5Server {
                 .into());
             }
         }
+        self.port_forwards.insert(
+            cfg.clone(),
+            PortForwardHandle {
+                _cancel_guard: cancel_token.drop_guard(),
+                stats,
+            },
+        );
         self.global_ctx
             .issue_event(GlobalCtxEvent::PortForwardAdded(cfg.clone().into()));
         Ok(())
     }
 
     pub fn remove_port_forward(&self, cfg: PortForwardConfig) {
-        let _ = self.cancel_tokens.remove(&cfg);
+        let _ = self.port_forwards.remove(&cfg);
     }
 
-    pub async fn add_tcp_port_forward(&self, cfg: &PortForwardConfig) -> Result<(), Error> {
-        let (bind_addr, dst_addr) = (cfg.bind_addr, cfg.dst_addr);
-        let listener = bind_tcp_socket(bind_addr, self.global_ctx.net_ns.clone())?;
+    /// returns the stats of a running port forward rule.
+    pub fn get_port_forward_stats(&self, cfg: &PortForwardConfig) -> Option<PortForwardStatsPb> {
+        self.port_forwards
+            .get(cfg)
+            .map(|handle| handle.stats.as_ref().into())
+    }
+
+    /// stop the socks5 portal and all port forwards, returns the number of port forwards stopped.
+    pub fn stop(&self) -> usize {
+        let forward_count = self.port_forwards.len();
+        self.port_forwards.clear();
+        self.socks5_enabled.store(false, Ordering::Relaxed);
+        self.tasks.lock().unwrap().abort_all();
+        self.tcp_forward_task.lock().unwrap().abort_all();
+        self.udp_client_map.clear();
+        self.udp_forward_task.clear();
+        self.entries.clear();
+        forward_count
+    }
+
+    async fn add_tcp_port_forward(
+        &self,
+        cfg: &PortForwardConfig,
+        addr_pairs: Vec<(SocketAddr, SocketAddr)>,
+        cancel_token: &CancellationToken,
+        stats: &Arc<PortForwardStats>,
+    ) -> Result<(), Error> {
+        // bind all ports of the range before spawning anything, so a failed bind leaves no
+        // half started rule behind
+        let listeners = addr_pairs
+            .into_iter()
+            .map(|(bind_addr, dst_addr)| {
+                bind_tcp_socket(bind_addr, self.global_ctx.net_ns.clone())
+                    .map(|listener| (listener, bind_addr, dst_addr))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let net = self.net.clone();
-        let entries = self.entries.clone();
-        let tasks = Arc::new(std::sync::Mutex::new(JoinSet::new()));
-        let forward_tasks = tasks.clone();
         let kcp_endpoint = self.kcp_endpoint.lock().await.clone();
-        let peer_mgr = Arc::downgrade(&self.peer_manager.clone());
-        let cancel_token = CancellationToken::new();
-        self.cancel_tokens
-            .insert(cfg.clone(), cancel_token.clone().drop_guard());
+        for (listener, bind_addr, dst_addr) in listeners {
+            let net = self.net.clone();
+            let entries = self.entries.clone();
+            let tasks = Arc::new(std::sync::Mutex::new(JoinSet::new()));
+            let forward_tasks = tasks.clone();
+            let kcp_endpoint = kcp_endpoint.clone();
+            let peer_mgr = Arc::downgrade(&self.peer_manager.clone());
+            let cancel_token = cancel_token.clone();
+            let stats = stats.clone();
+            let cfg = cfg.clone();
 
-        self.tasks.lock().unwrap().spawn(async move {
-            loop {
-                let (incoming_socket, addr) = select! {
-                    biased;
-                    _ = cancel_token.cancelled() => {
-                        tracing::info!("port forward for {:?} cancelled", bind_addr);
-                        break;
-                    }
-                    res = listener.accept() => {
-                        match res {
-                            Ok(result) => result,
-                            Err(err) => {
-                                tracing::error!("port forward accept error = {:?}", err);
-                                continue;
+            self.tasks.lock().unwrap().spawn(async move {
+                loop {
+                    let (incoming_socket, addr) = select! {
+                        biased;
+                        _ = cancel_token.cancelled() => {
+                            tracing::info!("port forward for {:?} cancelled", bind_addr);
+                            break;
+                        }
+                        res = listener.accept() => {
+                            match res {
+                                Ok(result) => result,
+                                Err(err) => {
+                                    tracing::error!("port forward accept error = {:?}", err);
+                                    continue;
+                                }
                             }
                         }
-                    }
-                };
+                    };
 
-                tracing::info!(
-                    "port forward: accept new connection from {:?} to {:?}",
-                    bind_addr,
-                    dst_addr
-                );
+                    if !cfg.is_source_allowed(&addr.ip()) {
+                        tracing::debug!(?addr, ?bind_addr, "port forward: source not allowed");
+                        stats.rejected.fetch_add(1, Ordering::Relaxed);
+                        continue;
+                    }
 
-                let net_guard = net.lock().await;
-                let Some(net) = net_guard.as_ref() else {
-                    tracing::error!("net is not ready");
-                    continue;
-                };
+                    tracing::info!(
+                        "port forward: accept new connection from {:?} to {:?}",
+                        bind_addr,
+                        dst_addr
+                    );
 
-                let Some(peer_mgr_arc) = peer_mgr.upgrade() else {
-                    tracing::error!("peer manager is dropped");
-                    continue;
-                };
+                    let net_guard = net.lock().await;
+                    let Some(net) = net_guard.as_ref() else {
+                        tracing::error!("net is not ready");
+                        continue;
+                    };
 
-                let dst_allow_kcp = peer_mgr_arc.check_allow_kcp_to_dst(&dst_addr.ip()).await;
-                tracing::debug!("dst_allow_kcp: {:?}", dst_allow_kcp);
-
-                let connector: Box<dyn AsyncTcpConnector<S = SocksTcpStream> + Send> =
-                    match (&kcp_endpoint, dst_allow_kcp) {
-                        (Some(kcp_endpoint), true) => Box::new(Socks5KcpConnector {
-                            kcp_endpoint: kcp_endpoint.clone(),
-                            peer_mgr: peer_mgr.clone(),
-                            src_addr: addr,
-                        }),
-                        (_, _) => Box::new(SmolTcpConnector {
-                            net: net.smoltcp_net.clone(),
-                            entries: entries.clone(),
-                            current_entry: std::sync::Mutex::new(None),
-                        }),
+                    let Some(peer_mgr_arc) = peer_mgr.upgrade() else {
+                        tracing::error!("peer manager is dropped");
+                        continue;
                     };
 
-                forward_tasks
-                    .lock()
-                    .unwrap()
-                    .spawn(Self::handle_port_forward_connection(
-                        incoming_socket,
-                        connector,
-                        dst_addr,
-                    ));
-            }
-        });
+                    let dst_allow_kcp = peer_mgr_arc.check_allow_kcp_to_dst(&dst_addr.ip()).await;
+                    tracing::debug!("dst_allow_kcp: {:?}", dst_allow_kcp);
+
+                    let connector: Box<dyn AsyncTcpConnector<S = SocksTcpStream> + Send> =
+                        match (&kcp_endpoint, dst_allow_kcp) {
+                            (Some(kcp_endpoint), true) => Box::new(Socks5KcpConnector {
+                                kcp_endpoint: kcp_endpoint.clone(),
+                                peer_mgr: peer_mgr.clone(),
+                                src_addr: addr,
+                            }),
+                            (_, _) => Box::new(SmolTcpConnector {
+                                net: net.smoltcp_net.clone(),
+                                entries: entries.clone(),
+                                current_entry: std::sync::Mutex::new(None),
+                            }),
+                        };
+
+                    forward_tasks
+                        .lock()
+                        .unwrap()
+                        .spawn(Self::handle_port_forward_connection(
+                            incoming_socket,
+                            connector,
+                            dst_addr,
+                            stats.clone(),
+                        ));
+                }
+            });
+        }
 
         Ok(())
     }
 
-    #[tracing::instrument(name = "add_udp_port_forward", skip(self))]
-    pub async fn add_udp_port_forward(&self, cfg: &PortForwardConfig) -> Result<(), Error> {
-        let (bind_addr, dst_addr) = (cfg.bind_addr, cfg.dst_addr);
-        let socket = Arc::new(bind_udp_socket(bind_addr, self.global_ctx.net_ns.clone())?);
+    #[tracing::instrument(name = "add_udp_port_forward", skip(self, cancel_token, stats))]
+    async fn add_udp_port_forward(
+        &self,
+        cfg: &PortForwardConfig,
+        addr_pairs: Vec<(SocketAddr, SocketAddr)>,
+        cancel_token: &CancellationToken,
+        stats: &Arc<PortForwardStats>,
+    ) -> Result<(), Error> {
+        let sockets = addr_pairs
+            .into_iter()
+            .map(|(bind_addr, dst_addr)| {
+                bind_udp_socket(bind_addr, self.global_ctx.net_ns.clone())
+                    .map(|socket| (Arc::new(socket), bind_addr, dst_addr))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let entries = self.entries.clone();
-        let net_ns = self.global_ctx.net_ns.clone();
-        let net = self.net.clone();
-        let udp_client_map = self.udp_client_map.clone();
-        let udp_forward_task = self.udp_forward_task.clone();
-        let cancel_token = CancellationToken::new();
-        self.cancel_tokens
-            .insert(cfg.clone(), cancel_token.clone().drop_guard());
+        let idle_timeout = cfg.get_udp_idle_timeout();
+        for (socket, bind_addr, dst_addr) in sockets {
+            let entries = self.entries.clone();
+            let net_ns = self.global_ctx.net_ns.clone();
+            let net = self.net.clone();
+            let udp_client_map = self.udp_client_map.clone();
+            let udp_forward_task = self.udp_forward_task.clone();
+            let cancel_token = cancel_token.clone();
+            let stats = stats.clone();
+            let cfg = cfg.clone();
 
-        self.tasks.lock().unwrap().spawn(async move {
-            loop {
-                // we set the max buffer size of smoltcp to 8192, so we need to use a buffer size that is less than 8192 here.
-                let mut buf = vec![0u8; 8192];
-                let (len, addr) = select! {
-                    biased;
-                    _ = cancel_token.cancelled() => {
-                        tracing::info!("udp port forward for {:?} cancelled", bind_addr);
-                        break;
-                    }
-                    res = socket.recv_from(&mut buf) => {
-                        match res {
-                            Ok(result) => result,
-                            Err(err) => {
-                                tracing::error!("udp port forward recv error = {:?}", err);
-                                continue;
+            self.tasks.lock().unwrap().spawn(async move {
+                loop {
+                    // we set the max buffer size of smoltcp to 8192, so we need to use a buffer size that is less than 8192 here.
+                    let mut buf = vec![0u8; 8192];
+                    let (len, addr) = select! {
+                        biased;
+                        _ = cancel_token.cancelled() => {
+                            tracing::info!("udp port forward for {:?} cancelled", bind_addr);
+                            break;
+                        }
+                        res = socket.recv_from(&mut buf) => {
+                            match res {
+                                Ok(result) => result,
+                                Err(err) => {
+                                    tracing::error!("udp port forward recv error = {:?}", err);
+                                    continue;
+                                }
                             }
                         }
-                    }
-                };
-
-                tracing::trace!(
-                    "udp port forward recv packet from {:?}, len = {}",
-                    addr,
-                    len
-                );
+                    };
 
-                let udp_client_key = UdpClientKey {
-                    client_addr: addr,
-                    dst_addr,
-                };
+                    tracing::trace!(
+                        "udp port forward recv packet from {:?}, len = {}",
+                        addr,
+                        len
+                    );
 
-                let binded_socket = udp_client_map.get(&udp_client_key);
-                let client_info = match binded_socket {
-                    Some(s) => s.clone(),
-                    None => {
-                        let _g = net_ns.guard();
-                        // reserve a port so os will not use it to connect to the virtual network
-                        let binded_socket = tokio::net::UdpSocket::bind("0.0.0.0:0").await;
-                        if binded_socket.is_err() {
-                            tracing::error!("udp port forward bind error = {:?}", binded_socket);
-                            continue;
-                        }
-                        let binded_socket = binded_socket.unwrap();
-                        let mut local_addr = binded_socket.local_addr().unwrap();
-                        let Some(cur_ipv4) = net.lock().await.as_ref().map(|net| net.ipv4_addr) else {
-                            continue;
-                        };
-                        local_addr.set_ip(cur_ipv4.address().into());
+                    if !cfg.is_source_allowed(&addr.ip()) {
+                        tracing::trace!(?addr, ?bind_addr, "udp port forward: source not allowed");
+                        stats.rejected.fetch_add(1, Ordering::Relaxed);
+                        continue;
+                    }
 
-                        let entry_key = Socks5Entry {
-                            src: local_addr,
-                            dst: dst_addr,
-                            entry_type: UDP_ENTRY,
-                        };
+                    let udp_client_key = UdpClientKey {
+                        client_addr: addr,
+                        dst_addr,
+                    };
 
-                        tracing::debug!("udp port forward binded socket = {:?}, entry_key = {:?}", local_addr, entry_key);
-
-                        let client_info = Arc::new(UdpClientInfo {
-                            client_addr: addr,
-                            port_holder_socket: Arc::new(binded_socket),
-                            local_addr,
-                            last_active: AtomicCell::new(Instant::now()),
-                            entries: entries.clone(),
-                            entry_key,
-                        });
-                        udp_client_map.insert(udp_client_key.clone(), client_info.clone());
-                        client_info
-                    }
-                };
+                    let binded_socket = udp_client_map.get(&udp_client_key);
+                    let client_info = match binded_socket {
+                        Some(s) => s.clone(),
+                        None => {
+                            let _g = net_ns.guard();
+                            // reserve a port so os will not use it to connect to the virtual network
+                            let binded_socket = tokio::net::UdpSocket::bind("0.0.0.0:0").await;
+                            if binded_socket.is_err() {
+                                tracing::error!("udp port forward bind error = {:?}", binded_socket);
+                                continue;
+                            }
+                            let binded_socket = binded_socket.unwrap();
+                            let mut local_addr = binded_socket.local_addr().unwrap();
+                            let Some(cur_ipv4) = net.lock().await.as_ref().map(|net| net.ipv4_addr) else {
+                                continue;
+                            };
+                            local_addr.set_ip(cur_ipv4.address().into());
+
+                            let entry_key = Socks5Entry {
+                                src: local_addr,
+                                dst: dst_addr,
+                                entry_type: UDP_ENTRY,
+                            };
+
+                            tracing::debug!("udp port forward binded socket = {:?}, entry_key = {:?}", local_addr, entry_key);
+
+                            let client_info = Arc::new(UdpClientInfo {
+                                client_addr: addr,
+                                port_holder_socket: Arc::new(binded_socket),
+                                local_addr,
+                                last_active: AtomicCell::new(Instant::now()),
+                                idle_timeout,
+                                entries: entries.clone(),
+                                entry_key,
+                                conn_guard: stats.new_connection(),
+                            });
+                            udp_client_map.insert(udp_client_key.clone(), client_info.clone());
+                            client_info
+                        }
+                    };
 
-                client_info.last_active.store(Instant::now());
+                    client_info.last_active.store(Instant::now());
 
-                let entry_data = match entries.get(&client_info.entry_key) {
-                    Some(data) => data,
-                    None => {
-                        let guard = net.lock().await;
-                        let Some(net) = guard.as_ref() else {
-                            continue;
-                        };
-                        let local_addr = net.ipv4_addr;
-                        let sokcs_udp = if dst_addr.ip() == local_addr.address() {
-                            SocksUdpSocket::UdpSocket(client_info.port_holder_socket.clone())
-                        } else {
-                            tracing::debug!("udp port forward bind new smol udp socket, {:?}", local_addr);
-                            SocksUdpSocket::SmolUdpSocket(
-                                net.smoltcp_net
-                                    .udp_bind(SocketAddr::new(
-                                        IpAddr::V4(local_addr.address()),
-                                        client_info.local_addr.port(),
-                                    ))
-                                    .await
-                                    .unwrap(),
-                            )
-                        };
-                        let socks_udp = Arc::new(sokcs_udp);
-                        entries.insert(
-                            client_info.entry_key.clone(),
-                            Socks5EntryData::Udp((socks_udp.clone(), udp_client_key.clone())),
-                        );
-
-                        let socks = socket.clone();
-                        let client_addr = addr;
-                        udp_forward_task.insert(
-                            udp_client_key.clone(),
-                            ScopedTask::from(tokio::spawn(async move {
-                                loop {
-                                    let mut buf = vec![0u8; 8192];
-                                    match socks_udp.recv_from(&mut buf).await {
-                                        Ok((len, dst_addr)) => {
-                                            tracing::trace!(
-                                                "udp port forward recv response packet from {:?}, len = {}, client_addr = {:?}",
-                                                dst_addr,
-                                                len,
-                                                client_addr
-                                            );
-                                            if let Err(e) = socks.send_to(&buf[..len], client_addr).await {
-                                                tracing::error!("udp forward send error = {:?}", e);
+                    let entry_data = match entries.get(&client_info.entry_key) {
+                        Some(data) => data,
+                        None => {
+                            let guard = net.lock().await;
+                            let Some(net) = guard.as_ref() else {
+                                continue;
+                            };
+                            let local_addr = net.ipv4_addr;
+                            let sokcs_udp = if dst_addr.ip() == local_addr.address() {
+                                SocksUdpSocket::UdpSocket(client_info.port_holder_socket.clone())
+                            } else {
+                                tracing::debug!("udp port forward bind new smol udp socket, {:?}", local_addr);
+                                SocksUdpSocket::SmolUdpSocket(
+                                    net.smoltcp_net
+                                        .udp_bind(SocketAddr::new(
+                                            IpAddr::V4(local_addr.address()),
+                                            client_info.local_addr.port(),
+                                        ))
+                                        .await
+                                        .unwrap(),
+                                )
+                            };
+                            let socks_udp = Arc::new(sokcs_udp);
+                            entries.insert(
+                                client_info.entry_key.clone(),
+                                Socks5EntryData::Udp((socks_udp.clone(), udp_client_key.clone())),
+                            );
+
+                            let socks = socket.clone();
+                            let client_addr = addr;
+                            let stats = stats.clone();
+                            udp_forward_task.insert(
+                                udp_client_key.clone(),
+                                ScopedTask::from(tokio::spawn(async move {
+                                    loop {
+                                        let mut buf = vec![0u8; 8192];
+                                        match socks_udp.recv_from(&mut buf).await {
+                                            Ok((len, dst_addr)) => {
+                                                tracing::trace!(
+                                                    "udp port forward recv response packet from {:?}, len = {}, client_addr = {:?}",
+                                                    dst_addr,
+                                                    len,
+                                                    client_addr
+                                                );
+                                                match socks.send_to(&buf[..len], client_addr).await {
+                                                    Ok(_) => {
+                                                        stats.tx_bytes.fetch_add(len as u64, Ordering::Relaxed);
+                                                    }
+                                                    Err(e) => {
+                                                        tracing::error!("udp forward send error = {:?}", e);
+                                                    }
+                                                }
+                                            }
+                                            Err(e) => {
+                                                tracing::error!("udp forward recv error = {:?}", e);
                                             }
-                                        }
-                                        Err(e) => {
-                                            tracing::error!("udp forward recv error = {:?}", e);
                                         }
                                     }
-                                }
-                            })),
-                        );
+                                })),
+                            );
 
-                        entries.get(&client_info.entry_key).unwrap()
-                    }
-                };
+                            entries.get(&client_info.entry_key).unwrap()
+                        }
+                    };
 
-                let s = match entry_data.value() {
-                    Socks5EntryData::Udp((s, _)) => s.clone(),
-                    _ => {
-                        panic!("udp entry data is not udp entry data");
-                    }
-                };
-                drop(entry_data);
+                    let s = match entry_data.value() {
+                        Socks5EntryData::Udp((s, _)) => s.clone(),
+                        _ => {
+                            panic!("udp entry data is not udp entry data");
+                        }
+                    };
+                    drop(entry_data);
 
-                if let Err(e) = s.send_to(&buf[..len], dst_addr).await {
-                    tracing::error!(?dst_addr, ?len, "udp port forward send error = {:?}", e);
-                } else {
-                    tracing::trace!(?dst_addr, ?len, "udp port forward send packet success");
+                    if let Err(e) = s.send_to(&buf[..len], dst_addr).await {
+                        tracing::error!(?dst_addr, ?len, "udp port forward send error = {:?}", e);
+                    } else {
+                        stats.rx_bytes.fetch_add(len as u64, Ordering::Relaxed);
+                        tracing::trace!(?dst_addr, ?len, "udp port forward send packet success");
+                    }
                 }
-            }
-        });
+            });
+        }
 
-        // clean up task
+        // clean up task, closes the udp sessions that are idle for longer than their timeout and
+        // all sessions of this rule once it is removed
         let udp_client_map = self.udp_client_map.clone();
         let udp_forward_task = self.udp_forward_task.clone();
         let entries = self.entries.clone();
+        let cancel_token = cancel_token.clone();
+        let stats = stats.clone();
+        let check_interval = idle_timeout.clamp(Duration::from_secs(1), Duration::from_secs(30));
         self.tasks.lock().unwrap().spawn(async move {
             loop {
-                tokio::time::sleep(Duration::from_secs(30)).await;
+                let removed = select! {
+                    _ = cancel_token.cancelled() => true,
+                    _ = tokio::time::sleep(check_interval) => false,
+                };
                 let now = Instant::now();
                 udp_client_map.retain(|_, client_info| {
-                    now.duration_since(client_info.last_active.load()).as_secs() < 600
+                    !(removed && client_info.is_of_rule(&stats))
+                        && now.duration_since(client_info.last_active.load())
+                            < client_info.idle_timeout
                 });
                 udp_forward_task.retain(|k, _| udp_client_map.contains_key(k));
                 entries.retain(|_, data| match data {
@@ -974,6 +1676,9 @@ impl Socks5Server {
                     }
                     _ => true,
                 });
+                if removed {
+                    break;
+                }
             }
         });
 
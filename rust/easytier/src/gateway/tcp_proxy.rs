@@ -1,15 +1,15 @@
 use anyhow::Context;
-use cidr::Ipv4Inet;
-use core::panic;
+use cidr::{Ipv4Inet, Ipv6Inet};
 use crossbeam::atomic::AtomicCell;
 use dashmap::DashMap;
 use pnet::packet::ip::IpNextHeaderProtocols;
 use pnet::packet::ipv4::{Ipv4Packet, MutableIpv4Packet};
-use pnet::packet::tcp::{ipv4_checksum, MutableTcpPacket, TcpPacket};
+use pnet::packet::ipv6::{Ipv6Packet, MutableIpv6Packet};
+use pnet::packet::tcp::{ipv4_checksum, ipv6_checksum, MutableTcpPacket, TcpPacket};
 use pnet::packet::MutablePacket;
 use pnet::packet::Packet;
 use socket2::{SockRef, TcpKeepalive};
-use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::sync::atomic::{AtomicBool, AtomicU16};
 use std::sync::{Arc, Weak};
 use std::time::{Duration, Instant};
@@ -51,12 +51,97 @@ pub(crate) trait NatDstConnector: Send + Sync + Clone + 'static {
         cidr_set: &CidrSet,
         global_ctx: &GlobalCtx,
         hdr: &PeerManagerHeader,
-        ipv4: &Ipv4Packet,
-        real_dst_ip: &mut Ipv4Addr,
+        dst_ip: IpAddr,
+        real_dst_ip: &mut IpAddr,
     ) -> bool;
     fn transport_type(&self) -> TcpProxyEntryTransportType;
 }
 
+/// Endpoints and flags of a tcp segment carried in an ipv4 or ipv6 packet.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TcpIpInfo {
+    pub src: SocketAddr,
+    pub dst: SocketAddr,
+    pub flags: u8,
+}
+
+impl TcpIpInfo {
+    pub fn is_syn(&self) -> bool {
+        self.flags & pnet::packet::tcp::TcpFlags::SYN != 0
+            && self.flags & pnet::packet::tcp::TcpFlags::ACK == 0
+    }
+}
+
+// ipv6 packets with extension headers are not recognized and left untouched.
+pub(crate) fn parse_tcp_ip_packet(buf: &[u8]) -> Option<TcpIpInfo> {
+    match buf.first()? >> 4 {
+        4 => {
+            let ipv4 = Ipv4Packet::new(buf)?;
+            if ipv4.get_next_level_protocol() != IpNextHeaderProtocols::Tcp {
+                return None;
+            }
+            let tcp = TcpPacket::new(ipv4.payload())?;
+            Some(TcpIpInfo {
+                src: SocketAddr::new(ipv4.get_source().into(), tcp.get_source()),
+                dst: SocketAddr::new(ipv4.get_destination().into(), tcp.get_destination()),
+                flags: tcp.get_flags(),
+            })
+        }
+        6 => {
+            let ipv6 = Ipv6Packet::new(buf)?;
+            if ipv6.get_next_header() != IpNextHeaderProtocols::Tcp {
+                return None;
+            }
+            let tcp = TcpPacket::new(ipv6.payload())?;
+            Some(TcpIpInfo {
+                src: SocketAddr::new(ipv6.get_source().into(), tcp.get_source()),
+                dst: SocketAddr::new(ipv6.get_destination().into(), tcp.get_destination()),
+                flags: tcp.get_flags(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Rewrite both endpoints of a tcp packet in place and update the checksums. The new
+/// endpoints must be of the same address family as the packet.
+pub(crate) fn rewrite_tcp_ip_packet(buf: &mut [u8], src: SocketAddr, dst: SocketAddr) {
+    match (src, dst) {
+        (SocketAddr::V4(src), SocketAddr::V4(dst)) => {
+            let mut ip_packet = MutableIpv4Packet::new(buf).unwrap();
+            ip_packet.set_source(*src.ip());
+            ip_packet.set_destination(*dst.ip());
+
+            let mut tcp_packet = MutableTcpPacket::new(ip_packet.payload_mut()).unwrap();
+            tcp_packet.set_source(src.port());
+            tcp_packet.set_destination(dst.port());
+            tcp_packet.set_checksum(ipv4_checksum(
+                &tcp_packet.to_immutable(),
+                src.ip(),
+                dst.ip(),
+            ));
+            drop(tcp_packet);
+
+            ip_packet.set_checksum(pnet::packet::ipv4::checksum(&ip_packet.to_immutable()));
+        }
+        (SocketAddr::V6(src), SocketAddr::V6(dst)) => {
+            let mut ip_packet = MutableIpv6Packet::new(buf).unwrap();
+            ip_packet.set_source(*src.ip());
+            ip_packet.set_destination(*dst.ip());
+
+            let mut tcp_packet = MutableTcpPacket::new(ip_packet.payload_mut()).unwrap();
+            tcp_packet.set_source(src.port());
+            tcp_packet.set_destination(dst.port());
+            tcp_packet.set_checksum(ipv6_checksum(
+                &tcp_packet.to_immutable(),
+                src.ip(),
+                dst.ip(),
+            ));
+        }
+        _ => panic!("tcp packet rewritten with mixed address family: {src} -> {dst}"),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct NatDstTcpConnector;
 
@@ -64,10 +149,14 @@ pub struct NatDstTcpConnector;
 impl NatDstConnector for NatDstTcpConnector {
     type DstStream = TcpStream;
     async fn connect(&self, _src: SocketAddr, nat_dst: SocketAddr) -> Result<Self::DstStream> {
-        let socket = match TcpSocket::new_v4() {
+        let socket = match if nat_dst.is_ipv4() {
+            TcpSocket::new_v4()
+        } else {
+            TcpSocket::new_v6()
+        } {
             Ok(s) => s,
             Err(e) => {
-                eprintln!("create v4 socket failed: {:?}", e);
+                eprintln!("create socket for {:?} failed: {:?}", nat_dst, e);
                 return Err(e.into());
             }
         };
@@ -90,16 +179,18 @@ impl NatDstConnector for NatDstTcpConnector {
         cidr_set: &CidrSet,
         global_ctx: &GlobalCtx,
         hdr: &PeerManagerHeader,
-        ipv4: &Ipv4Packet,
-        real_dst_ip: &mut Ipv4Addr,
+        dst_ip: IpAddr,
+        real_dst_ip: &mut IpAddr,
     ) -> bool {
         let is_exit_node = hdr.is_exit_node();
+        let is_my_ip = match dst_ip {
+            IpAddr::V4(ip) => Some(ip) == global_ctx.get_ipv4().as_ref().map(Ipv4Inet::address),
+            IpAddr::V6(ip) => Some(ip) == global_ctx.get_ipv6().as_ref().map(Ipv6Inet::address),
+        };
 
-        if !(cidr_set.contains_v4(ipv4.get_destination(), real_dst_ip)
+        if !(cidr_set.contains(dst_ip, real_dst_ip)
             || is_exit_node
-            || global_ctx.no_tun()
-                && Some(ipv4.get_destination())
-                    == global_ctx.get_ipv4().as_ref().map(Ipv4Inet::address))
+            || global_ctx.no_tun() && is_my_ip)
         {
             return false;
         }
@@ -286,6 +377,34 @@ fn prepare_kernel_tcp_socket(stream: &TcpStream) -> Result<()> {
     Ok(())
 }
 
+// listen on [::] with v6only disabled, so both v4 and v6 nat connections are accepted. falls
+// back to a v4 only listener when ipv6 is not available on the host.
+async fn bind_kernel_proxy_listener() -> std::io::Result<TcpListener> {
+    let dual_stack = || -> std::io::Result<TcpListener> {
+        let socket = socket2::Socket::new(
+            socket2::Domain::IPV6,
+            socket2::Type::STREAM,
+            Some(socket2::Protocol::TCP),
+        )?;
+        socket.set_only_v6(false)?;
+        socket.set_nonblocking(true)?;
+        socket.bind(&SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 0).into())?;
+        socket.listen(1024)?;
+        TcpListener::from_std(socket.into())
+    };
+
+    match dual_stack() {
+        Ok(listener) => Ok(listener),
+        Err(e) => {
+            tracing::warn!(
+                ?e,
+                "bind dual stack tcp proxy listener failed, use ipv4 only"
+            );
+            TcpListener::bind(SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0)).await
+        }
+    }
+}
+
 impl ProxyTcpListener {
     pub async fn accept(&mut self) -> Result<(ProxyTcpStream, SocketAddr)> {
         match self {
@@ -359,35 +478,23 @@ impl<C: NatDstConnector> PeerPacketFilter for TcpProxy<C> {
 #[async_trait::async_trait]
 impl<C: NatDstConnector> NicPacketFilter for TcpProxy<C> {
     async fn try_process_packet_from_nic(&self, zc_packet: &mut ZCPacket) -> bool {
-        let Some(my_ipv4_inet) = self.get_local_inet() else {
+        let Some(info) = parse_tcp_ip_packet(zc_packet.payload()) else {
             return false;
         };
-        let my_ipv4 = my_ipv4_inet.address();
-
-        let data = zc_packet.payload();
-        let ip_packet = Ipv4Packet::new(data).unwrap();
-        if ip_packet.get_version() != 4
-            || ip_packet.get_source() != my_ipv4
-            || ip_packet.get_next_level_protocol() != IpNextHeaderProtocols::Tcp
-        {
+        let Some(my_ip) = self.get_local_ip_for(&info.src.ip()) else {
             return false;
-        }
-
-        let tcp_packet = TcpPacket::new(ip_packet.payload()).unwrap();
-        if tcp_packet.get_source() != self.get_local_port() {
+        };
+        if info.src.ip() != my_ip || info.src.port() != self.get_local_port() {
             return false;
         }
 
-        let mut dst_addr = SocketAddr::V4(SocketAddrV4::new(
-            ip_packet.get_destination(),
-            tcp_packet.get_destination(),
-        ));
+        let mut dst_addr = info.dst;
         let mut need_transform_dst = false;
 
         // for kcp proxy, the src ip of nat entry will be converted from my ip to fake ip
         // here we need to convert it back
-        if !self.is_smoltcp_enabled() && dst_addr.ip() == Self::get_fake_local_ipv4(&my_ipv4_inet) {
-            dst_addr.set_ip(IpAddr::V4(my_ipv4));
+        if !self.is_smoltcp_enabled() && Some(dst_addr.ip()) == self.get_fake_local_ip_for(&my_ip) {
+            dst_addr.set_ip(my_ip);
             need_transform_dst = true;
         }
 
@@ -404,10 +511,6 @@ impl<C: NatDstConnector> NicPacketFilter for TcpProxy<C> {
         drop(entry);
         assert_eq!(nat_entry.src, dst_addr);
 
-        let IpAddr::V4(ip) = nat_entry.mapped_dst.ip() else {
-            panic!("v4 nat entry src ip is not v4");
-        };
-
         zc_packet
             .mut_peer_manager_header()
             .unwrap()
@@ -416,21 +519,15 @@ impl<C: NatDstConnector> NicPacketFilter for TcpProxy<C> {
             zc_packet.mut_peer_manager_header().unwrap().to_peer_id = self.get_my_peer_id().into();
         }
 
-        let mut ip_packet = MutableIpv4Packet::new(zc_packet.mut_payload()).unwrap();
-        ip_packet.set_source(ip);
-        if need_transform_dst {
-            ip_packet.set_destination(my_ipv4);
-        }
-        let dst = ip_packet.get_destination();
-
-        let mut tcp_packet = MutableTcpPacket::new(ip_packet.payload_mut()).unwrap();
-        tcp_packet.set_source(nat_entry.real_dst.port());
-
-        Self::update_tcp_packet_checksum(&mut tcp_packet, &ip, &dst);
-        drop(tcp_packet);
-        Self::update_ip_packet_checksum(&mut ip_packet);
+        let src = SocketAddr::new(nat_entry.mapped_dst.ip(), nat_entry.real_dst.port());
+        let dst = if need_transform_dst {
+            dst_addr
+        } else {
+            info.dst
+        };
+        rewrite_tcp_ip_packet(zc_packet.mut_payload(), src, dst);
 
-        tracing::trace!(dst_addr = ?dst_addr, nat_entry = ?nat_entry, packet = ?ip_packet, "tcp packet after modified");
+        tracing::trace!(dst_addr = ?dst_addr, nat_entry = ?nat_entry, packet = ?zc_packet, "tcp packet after modified");
 
         true
     }
@@ -467,22 +564,6 @@ impl<C: NatDstConnector> TcpProxy<C> {
         })
     }
 
-    fn update_tcp_packet_checksum(
-        tcp_packet: &mut MutableTcpPacket,
-        ipv4_src: &Ipv4Addr,
-        ipv4_dst: &Ipv4Addr,
-    ) {
-        tcp_packet.set_checksum(ipv4_checksum(
-            &tcp_packet.to_immutable(),
-            ipv4_src,
-            ipv4_dst,
-        ));
-    }
-
-    fn update_ip_packet_checksum(ip_packet: &mut MutableIpv4Packet) {
-        ip_packet.set_checksum(pnet::packet::ipv4::checksum(&ip_packet.to_immutable()));
-    }
-
     pub async fn start(self: &Arc<Self>, add_pipeline: bool) -> Result<()> {
         self.run_syn_map_cleaner().await?;
         self.run_listener().await?;
@@ -603,11 +684,8 @@ impl<C: NatDstConnector> TcpProxy<C> {
 
         {
             // use kernel network stack
-            let listen_addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
             let net_ns = self.global_ctx.net_ns.clone();
-            let tcp_listener = net_ns
-                .run_async(|| async { TcpListener::bind(&listen_addr).await })
-                .await?;
+            let tcp_listener = net_ns.run_async(bind_kernel_proxy_listener).await?;
             self.local_port.store(
                 tcp_listener.local_addr()?.port(),
                 std::sync::atomic::Ordering::Relaxed,
@@ -639,21 +717,12 @@ impl<C: NatDstConnector> TcpProxy<C> {
                     continue;
                 };
 
-                let my_ip_inet = global_ctx.get_ipv4();
-                let my_ip = my_ip_inet
-                    .as_ref()
-                    .map(Ipv4Inet::address)
-                    .unwrap_or(Ipv4Addr::UNSPECIFIED);
-
-                if my_ip_inet.is_some()
-                    && socket_addr.ip() == Self::get_fake_local_ipv4(&my_ip_inet.unwrap())
-                {
-                    socket_addr.set_ip(IpAddr::V4(my_ip));
-                }
+                // v4 connections accepted by the dual stack listener are v4-mapped
+                socket_addr.set_ip(socket_addr.ip().to_canonical());
+                Self::restore_fake_local_ip(&global_ctx, &mut socket_addr);
 
                 let Some(entry) = syn_map.get(&socket_addr) else {
                     tracing::error!(
-                        ?my_ip,
                         ?socket_addr,
                         "tcp connection from unknown source, ignore it"
                     );
@@ -720,14 +789,14 @@ impl<C: NatDstConnector> TcpProxy<C> {
             tracing::warn!("set_nodelay failed, ignore it: {:?}", e);
         }
 
-        let nat_dst = if Some(nat_entry.real_dst.ip())
-            == global_ctx.get_ipv4().map(|ip| IpAddr::V4(ip.address()))
-        {
-            format!("127.0.0.1:{}", nat_entry.real_dst.port())
-                .parse()
-                .unwrap()
-        } else {
-            nat_entry.real_dst
+        let nat_dst = match nat_entry.real_dst.ip() {
+            IpAddr::V4(ip) if Some(ip) == global_ctx.get_ipv4().map(|ip| ip.address()) => {
+                SocketAddr::new(Ipv4Addr::LOCALHOST.into(), nat_entry.real_dst.port())
+            }
+            IpAddr::V6(ip) if Some(ip) == global_ctx.get_ipv6().map(|ip| ip.address()) => {
+                SocketAddr::new(Ipv6Addr::LOCALHOST.into(), nat_entry.real_dst.port())
+            }
+            _ => nat_entry.real_dst,
         };
 
         global_ctx
@@ -829,10 +898,55 @@ impl<C: NatDstConnector> TcpProxy<C> {
             .load(std::sync::atomic::Ordering::Relaxed)
     }
 
+    /// The local address the proxy listener receives nat packets of `ip`'s family on. The
+    /// smoltcp stack only has an ipv4 address, so ipv6 is proxied with the kernel stack only.
+    pub fn get_local_ip_for(&self, ip: &IpAddr) -> Option<IpAddr> {
+        match ip {
+            IpAddr::V4(_) => self.get_local_ip().map(IpAddr::V4),
+            IpAddr::V6(_) if self.is_smoltcp_enabled() => None,
+            IpAddr::V6(_) => self.global_ctx.get_ipv6().map(|x| x.address().into()),
+        }
+    }
+
     pub fn get_fake_local_ipv4(local_ip: &Ipv4Inet) -> Ipv4Addr {
         local_ip.first_address()
     }
 
+    pub fn get_fake_local_ipv6(local_ip: &Ipv6Inet) -> Ipv6Addr {
+        local_ip.first_address()
+    }
+
+    fn get_fake_local_ip_for(&self, ip: &IpAddr) -> Option<IpAddr> {
+        match ip {
+            IpAddr::V4(_) => self
+                .get_local_inet()
+                .map(|inet| Self::get_fake_local_ipv4(&inet).into()),
+            IpAddr::V6(_) => self
+                .global_ctx
+                .get_ipv6()
+                .map(|inet| Self::get_fake_local_ipv6(&inet).into()),
+        }
+    }
+
+    fn restore_fake_local_ip(global_ctx: &GlobalCtx, addr: &mut SocketAddr) {
+        match addr.ip() {
+            IpAddr::V4(ip) => {
+                if let Some(inet) = global_ctx.get_ipv4() {
+                    if ip == Self::get_fake_local_ipv4(&inet) {
+                        addr.set_ip(inet.address().into());
+                    }
+                }
+            }
+            IpAddr::V6(ip) => {
+                if let Some(inet) = global_ctx.get_ipv6() {
+                    if ip == Self::get_fake_local_ipv6(&inet) {
+                        addr.set_ip(inet.address().into());
+                    }
+                }
+            }
+        }
+    }
+
     async fn try_handle_peer_packet(&self, packet: &mut ZCPacket) -> Option<()> {
         if !self
             .connector
@@ -841,49 +955,33 @@ impl<C: NatDstConnector> TcpProxy<C> {
             return None;
         }
 
-        let ipv4_inet = self.get_local_inet()?;
-        let ipv4_addr = ipv4_inet.address();
         let hdr = packet.peer_manager_header().unwrap().clone();
 
         if hdr.packet_type != PacketType::Data as u8 || hdr.is_no_proxy() {
             return None;
         };
 
-        let payload_bytes = packet.mut_payload();
-
-        let ipv4 = Ipv4Packet::new(payload_bytes)?;
-        if ipv4.get_version() != 4 || ipv4.get_next_level_protocol() != IpNextHeaderProtocols::Tcp {
-            return None;
-        }
+        let info = parse_tcp_ip_packet(packet.payload())?;
+        let local_ip = self.get_local_ip_for(&info.dst.ip())?;
 
-        let mut real_dst_ip = ipv4.get_destination();
+        let mut real_dst_ip = info.dst.ip();
 
         if !self.connector.check_packet_from_peer(
             &self.cidr_set,
             &self.global_ctx,
             &hdr,
-            &ipv4,
+            info.dst.ip(),
             &mut real_dst_ip,
         ) {
             return None;
         }
 
-        tracing::trace!(ipv4 = ?ipv4, cidr_set = ?self.cidr_set, "proxy tcp packet received");
+        tracing::trace!(?info, cidr_set = ?self.cidr_set, "proxy tcp packet received");
 
-        let ip_packet = Ipv4Packet::new(payload_bytes).unwrap();
-        let tcp_packet = TcpPacket::new(ip_packet.payload()).unwrap();
-
-        let source_ip = ip_packet.get_source();
-        let source_port = tcp_packet.get_source();
-        let src = SocketAddr::V4(SocketAddrV4::new(source_ip, source_port));
-
-        let is_tcp_syn = tcp_packet.get_flags() & pnet::packet::tcp::TcpFlags::SYN != 0;
-        let is_tcp_ack = tcp_packet.get_flags() & pnet::packet::tcp::TcpFlags::ACK != 0;
-        if is_tcp_syn && !is_tcp_ack {
-            let dest_ip = ip_packet.get_destination();
-            let dest_port = tcp_packet.get_destination();
-            let mapped_dst = SocketAddr::V4(SocketAddrV4::new(dest_ip, dest_port));
-            let real_dst = SocketAddr::V4(SocketAddrV4::new(real_dst_ip, dest_port));
+        let src = info.src;
+        if info.is_syn() {
+            let mapped_dst = info.dst;
+            let real_dst = SocketAddr::new(real_dst_ip, info.dst.port());
 
             let old_val = self
                 .syn_map
@@ -906,22 +1004,15 @@ impl<C: NatDstConnector> TcpProxy<C> {
             return None;
         }
 
-        let mut ip_packet = MutableIpv4Packet::new(payload_bytes).unwrap();
-        if !self.is_smoltcp_enabled() && source_ip == ipv4_addr {
+        let mut source = src;
+        if !self.is_smoltcp_enabled() && source.ip() == local_ip {
             // modify the source so the response packet can be handled by tun device
-            ip_packet.set_source(Self::get_fake_local_ipv4(&ipv4_inet));
+            source.set_ip(self.get_fake_local_ip_for(&local_ip)?);
         }
-        ip_packet.set_destination(ipv4_addr);
-        let source = ip_packet.get_source();
-
-        let mut tcp_packet = MutableTcpPacket::new(ip_packet.payload_mut()).unwrap();
-        tcp_packet.set_destination(self.get_local_port());
-
-        Self::update_tcp_packet_checksum(&mut tcp_packet, &source, &ipv4_addr);
-        drop(tcp_packet);
-        Self::update_ip_packet_checksum(&mut ip_packet);
+        let dst = SocketAddr::new(local_ip, self.get_local_port());
+        rewrite_tcp_ip_packet(packet.mut_payload(), source, dst);
 
-        tracing::trace!(?source, ?ipv4_addr, ?packet, "tcp packet after modified");
+        tracing::trace!(?source, ?dst, ?packet, "tcp packet after modified");
 
         Some(())
     }
@@ -14,9 +14,12 @@ use tokio::{sync::Mutex, task::JoinSet};
 use tokio_util::sync::CancellationToken;
 
 use crate::common::acl_processor::AclRuleBuilder;
-use crate::common::config::ConfigLoader;
+use crate::common::config::{
+    ConfigLoader, Flags, PeerConfig, PortForwardConfig, ProxyNetworkConfig, TomlConfigLoader,
+};
 use crate::common::error::Error;
 use crate::common::global_ctx::{ArcGlobalCtx, GlobalCtx, GlobalCtxEvent};
+use crate::common::identity::NodeIdentityManager;
 use crate::common::scoped_task::ScopedTask;
 use crate::common::PeerId;
 use crate::connector::direct::DirectConnectorManager;
@@ -27,6 +30,7 @@ use crate::gateway::kcp_proxy::{KcpProxyDst, KcpProxyDstRpcService, KcpProxySrc}
 use crate::gateway::quic_proxy::{QUICProxyDst, QUICProxyDstRpcService, QUICProxySrc};
 use crate::gateway::tcp_proxy::{NatDstTcpConnector, TcpProxy, TcpProxyRpcService};
 use crate::gateway::udp_proxy::UdpProxy;
+use crate::launcher::{ConfigApplyReport, ShutdownReport};
 use crate::peer_center::instance::PeerCenterInstance;
 use crate::peers::peer_conn::PeerConnId;
 use crate::peers::peer_manager::{PeerManager, RouteAlgoType};
@@ -57,6 +61,19 @@ use super::listeners::ListenerManager;
 #[cfg(feature = "socks5")]
 use crate::gateway::socks5::Socks5Server;
 
+// flags that are read on every use and can be changed on a running instance
+const LIVE_FLAGS: &[&str] = &[
+    "default_protocol",
+    "latency_first",
+    "relay_network_whitelist",
+    "disable_p2p",
+    "relay_all_peer_rpc",
+    "disable_udp_hole_punching",
+    "disable_sym_hole_punching",
+    "private_mode",
+    "enable_multipath_bonding",
+];
+
 #[derive(Clone)]
 struct IpProxy {
     tcp_proxy: Arc<TcpProxy<NatDstTcpConnector>>,
@@ -83,8 +100,13 @@ impl IpProxy {
     }
 
     async fn start(&self) -> Result<(), Error> {
-        if (self.global_ctx.config.get_proxy_cidrs().is_empty()
-            || self.started.load(Ordering::Relaxed))
+        // a running proxy picks up proxy cidr changes from the config, starting
+        // it again would register its pipelines and sockets a second time
+        if self.started.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        if self.global_ctx.config.get_proxy_cidrs().is_empty()
             && !self.global_ctx.enable_exit_node()
             && !self.global_ctx.no_tun()
         {
@@ -305,7 +327,7 @@ impl Instance {
         let vpn_portal_inst = vpn_portal::NullVpnPortal;
 
         #[cfg(feature = "socks5")]
-        let socks5_server = Socks5Server::new(global_ctx.clone(), peer_manager.clone(), None);
+        let socks5_server = Socks5Server::new(global_ctx.clone(), peer_manager.clone());
 
         let rpc_server = global_ctx.config.get_rpc_portal().map(|s| {
             StandAloneServer::new(TcpTunnelListener::new(
@@ -620,6 +642,11 @@ impl Instance {
     }
 
     pub async fn run(&mut self) -> Result<(), Error> {
+        if let Some(cfg) = self.global_ctx.config.get_node_identity() {
+            NodeIdentityManager::new(&cfg, &self.global_ctx.get_network_name())
+                .with_context(|| "invalid node identity config")?;
+        }
+
         self.listener_manager
             .lock()
             .await
@@ -875,7 +902,7 @@ impl Instance {
                 if let Some(cfg) = request.cfg {
                     tracing::info!("Port forward rule added: {:?}", cfg);
                     let mut current_forwards = self.global_ctx.config.get_port_forwards();
-                    current_forwards.push(cfg.into());
+                    current_forwards.push(cfg.try_into()?);
                     self.global_ctx
                         .config
                         .set_port_forwards(current_forwards.clone());
@@ -898,9 +925,14 @@ impl Instance {
                 let Some(cfg) = request.cfg else {
                     return Err(anyhow::anyhow!("port forward config is empty").into());
                 };
-                let cfg = cfg.into();
+                let cfg: PortForwardConfig = cfg.try_into()?;
                 let mut current_forwards = self.global_ctx.config.get_port_forwards();
-                current_forwards.retain(|e| *e != cfg);
+                // an unspecified destination removes every rule on the bind address
+                current_forwards.retain(|e| {
+                    !(e.proto == cfg.proto
+                        && e.bind_addr == cfg.bind_addr
+                        && (cfg.dst_addr.ip().is_unspecified() || e.dst_addr == cfg.dst_addr))
+                });
                 self.global_ctx
                     .config
                     .set_port_forwards(current_forwards.clone());
@@ -919,8 +951,18 @@ impl Instance {
                 _request: ListPortForwardRequest,
             ) -> Result<ListPortForwardResponse, rpc_types::error::Error> {
                 let forwards = self.global_ctx.config.get_port_forwards();
+                let socks5_server = self.socks5_server.upgrade();
+                let stats = forwards
+                    .iter()
+                    .map(|cfg| {
+                        socks5_server
+                            .as_ref()
+                            .and_then(|s| s.get_port_forward_stats(cfg))
+                            .unwrap_or_default()
+                    })
+                    .collect();
                 let cfgs: Vec<PortForwardConfigPb> = forwards.into_iter().map(Into::into).collect();
-                Ok(ListPortForwardResponse { cfgs })
+                Ok(ListPortForwardResponse { cfgs, stats })
             }
         }
 
@@ -1082,6 +1124,11 @@ impl Instance {
         self.vpn_portal.clone()
     }
 
+    #[cfg(feature = "socks5")]
+    pub fn get_socks5_server(&self) -> Arc<Socks5Server> {
+        self.socks5_server.clone()
+    }
+
     pub fn get_nic_ctx(&self) -> ArcNicCtx {
         self.nic_ctx.clone()
     }
@@ -1124,6 +1171,321 @@ impl Instance {
         Ok(())
     }
 
+    async fn close_all_peer_conns(&self) -> (usize, usize) {
+        let peer_maps = [
+            self.peer_manager.get_peer_map(),
+            self.peer_manager
+                .get_foreign_network_client()
+                .get_peer_map(),
+        ];
+
+        let mut closing = 0;
+        for peer_map in peer_maps.iter() {
+            for peer_id in peer_map.list_peers().await {
+                for conn in peer_map.list_peer_conns(peer_id).await.unwrap_or_default() {
+                    let Ok(conn_id) = conn.conn_id.parse::<PeerConnId>() else {
+                        continue;
+                    };
+                    if peer_map.close_peer_conn(peer_id, &conn_id).await.is_ok() {
+                        closing += 1;
+                    }
+                }
+            }
+        }
+
+        // wait for PeerConnRemoved of every conn, so the tunnels are really dropped
+        // and remote peers are notified (tcp fin / udp fin) instead of timing out.
+        let now = std::time::Instant::now();
+        while now.elapsed() < Duration::from_secs(3) {
+            let mut remaining = 0;
+            for peer_map in peer_maps.iter() {
+                remaining += peer_map.list_peers_with_conn().await.len();
+            }
+            if remaining == 0 {
+                return (closing, 0);
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        let mut remaining = 0;
+        for peer_map in peer_maps.iter() {
+            remaining += peer_map.list_peers_with_conn().await.len();
+        }
+        (closing, remaining)
+    }
+
+    /// gracefully stop the instance, every step is recorded in the returned report.
+    pub async fn shutdown(&mut self) -> ShutdownReport {
+        let mut report = ShutdownReport::default();
+
+        // stop accepting and reconnecting first, otherwise closed peers come back.
+        let running_listeners = self
+            .global_ctx
+            .get_running_listeners()
+            .into_iter()
+            .filter(|l| l.scheme() != "ring")
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>();
+        self.listener_manager.lock().await.stop().await;
+        report.succeeded(
+            "listeners",
+            format!("closed: [{}]", running_listeners.join(", ")),
+        );
+
+        let connectors = self.conn_manager.clear_connectors();
+        report.succeeded("connectors", format!("{} connectors removed", connectors));
+
+        let (closed, remaining) = self.close_all_peer_conns().await;
+        if remaining == 0 {
+            report.succeeded("peers", format!("{} peer conns closed", closed));
+        } else {
+            report.failed(
+                "peers",
+                format!(
+                    "{} peer conns closed, {} peers still connected after timeout",
+                    closed, remaining
+                ),
+            );
+        }
+
+        #[cfg(feature = "socks5")]
+        {
+            let forwards = self.socks5_server.stop();
+            report.succeeded(
+                "port_forwards",
+                format!("{} port forwards stopped", forwards),
+            );
+        }
+
+        if let Some(old_ctx) = self.nic_ctx.lock().await.take() {
+            if let Some(dns_runner) = old_ctx.magic_dns {
+                dns_runner.dns_runner_cancel_token.cancel();
+                // the runner calls clean_env before it returns
+                let ret =
+                    tokio::time::timeout(Duration::from_secs(5), dns_runner.dns_runner_task).await;
+                match ret {
+                    Ok(Ok(())) => report.succeeded("magic_dns", "dns config restored"),
+                    Ok(Err(e)) => report.failed("magic_dns", format!("dns runner failed: {:?}", e)),
+                    Err(_) => report.failed("magic_dns", "timeout waiting for dns clean up"),
+                }
+            }
+            drop(old_ctx.nic_ctx);
+            report.succeeded(
+                "tun_device",
+                format!("{} released", self.global_ctx.get_flags().dev_name),
+            );
+        }
+
+        self.clear_resources().await;
+        report.succeeded("rpc", "rpc services unregistered");
+
+        report
+    }
+
+    fn apply_flags(&self, new_flags: Flags, report: &mut ConfigApplyReport) {
+        let old_flags = self.global_ctx.get_flags();
+        let (Ok(serde_json::Value::Object(old_map)), Ok(serde_json::Value::Object(new_map))) = (
+            serde_json::to_value(&old_flags),
+            serde_json::to_value(&new_flags),
+        ) else {
+            report
+                .failed
+                .insert("flags".to_string(), "failed to compare flags".to_string());
+            return;
+        };
+
+        for (name, value) in new_map.iter() {
+            if old_map.get(name) == Some(value) {
+                continue;
+            }
+            let field = format!("flags.{}", name);
+            if LIVE_FLAGS.contains(&name.as_str()) {
+                report.applied.push(field);
+            } else {
+                report.restart_required.push(field);
+            }
+        }
+
+        // these flags are read on every use, so updating the config is enough
+        let mut flags = old_flags;
+        flags.default_protocol = new_flags.default_protocol;
+        flags.latency_first = new_flags.latency_first;
+        flags.relay_network_whitelist = new_flags.relay_network_whitelist;
+        flags.disable_p2p = new_flags.disable_p2p;
+        flags.relay_all_peer_rpc = new_flags.relay_all_peer_rpc;
+        flags.disable_udp_hole_punching = new_flags.disable_udp_hole_punching;
+        flags.disable_sym_hole_punching = new_flags.disable_sym_hole_punching;
+        flags.private_mode = new_flags.private_mode;
+        flags.enable_multipath_bonding = new_flags.enable_multipath_bonding;
+        self.global_ctx.set_flags(flags);
+    }
+
+    async fn apply_peers(&self, new_peers: Vec<PeerConfig>) -> Result<(), Error> {
+        let old_peers = self.global_ctx.config.get_peers();
+        for peer in old_peers.iter() {
+            if !new_peers.contains(peer) {
+                // the connector may be reconnecting and not listed, ignore not found
+                let _ = self.conn_manager.remove_connector(peer.uri.clone()).await;
+            }
+        }
+        for peer in new_peers.iter() {
+            if !old_peers.contains(peer) {
+                self.conn_manager
+                    .add_connector_by_url(peer.uri.as_str())
+                    .await?;
+            }
+        }
+        self.global_ctx.config.set_peers(new_peers);
+        Ok(())
+    }
+
+    fn apply_proxy_cidrs(&self, new_cidrs: Vec<ProxyNetworkConfig>) -> Result<(), Error> {
+        let config = &self.global_ctx.config;
+        for cidr in config.get_proxy_cidrs() {
+            config.remove_proxy_cidr(cidr.cidr);
+        }
+        for cidr in new_cidrs {
+            config.add_proxy_cidr(cidr.cidr, cidr.mapped_cidr)?;
+        }
+        Ok(())
+    }
+
+    /// apply `new_cfg` to the running instance as far as possible without a restart.
+    /// fields that are only read at startup are reported in `restart_required`.
+    pub async fn apply_config(&mut self, new_cfg: &TomlConfigLoader) -> ConfigApplyReport {
+        let mut report = ConfigApplyReport::default();
+        let global_ctx = self.global_ctx.clone();
+        let config = &global_ctx.config;
+
+        macro_rules! restart_required_if_changed {
+            ($field:literal, $getter:ident) => {
+                if config.$getter() != new_cfg.$getter() {
+                    report.restart_required.push($field.to_string());
+                }
+            };
+        }
+        restart_required_if_changed!("instance_name", get_inst_name);
+        restart_required_if_changed!("netns", get_netns);
+        restart_required_if_changed!("network_identity", get_network_identity);
+        restart_required_if_changed!("dhcp", get_dhcp);
+        restart_required_if_changed!("ipv4", get_ipv4);
+        restart_required_if_changed!("ipv6", get_ipv6);
+        restart_required_if_changed!("routes", get_routes);
+        restart_required_if_changed!("exit_nodes", get_exit_nodes);
+        restart_required_if_changed!("exit_node_policy", get_exit_node_policies);
+        // port 0 means any free port, which was resolved when the instance started
+        let rpc_portal_changed = match (config.get_rpc_portal(), new_cfg.get_rpc_portal()) {
+            (Some(old), Some(new)) if new.port() == 0 => old.ip() != new.ip(),
+            (old, new) => old != new,
+        };
+        if rpc_portal_changed {
+            report.restart_required.push("rpc_portal".to_string());
+        }
+        restart_required_if_changed!("rpc_portal_whitelist", get_rpc_portal_whitelist);
+        restart_required_if_changed!("vpn_portal_config", get_vpn_portal_config);
+        restart_required_if_changed!("socks5_proxy", get_socks5_portal);
+        restart_required_if_changed!("socks5_user", get_socks5_users);
+        restart_required_if_changed!("http_proxy", get_http_portal);
+        restart_required_if_changed!("stun_servers", get_stun_servers);
+        restart_required_if_changed!("stun_servers_v6", get_stun_servers_v6);
+
+        if config.get_hostname() != new_cfg.get_hostname() {
+            config.set_hostname(Some(new_cfg.get_hostname()));
+            self.global_ctx.set_hostname(new_cfg.get_hostname());
+            report.applied.push("hostname".to_string());
+        }
+
+        if config.get_peers() != new_cfg.get_peers() {
+            match self.apply_peers(new_cfg.get_peers()).await {
+                Ok(_) => report.applied.push("peer".to_string()),
+                Err(e) => {
+                    report.failed.insert("peer".to_string(), e.to_string());
+                }
+            }
+        }
+
+        // listeners load their certificate when they start, connectors on each connect
+        let tls_changed = config.get_tls_config() != new_cfg.get_tls_config();
+        if tls_changed {
+            config.set_tls_config(new_cfg.get_tls_config());
+            report.applied.push("tls".to_string());
+        }
+
+        if config.get_listeners() != new_cfg.get_listeners() || tls_changed {
+            config.set_listeners(new_cfg.get_listeners().unwrap_or_default());
+            match self.listener_manager.lock().await.reload().await {
+                Ok(_) => report.applied.push("listeners".to_string()),
+                Err(e) => {
+                    report.failed.insert("listeners".to_string(), e.to_string());
+                }
+            }
+        }
+
+        if config.get_mapped_listeners() != new_cfg.get_mapped_listeners() {
+            config.set_mapped_listeners(Some(new_cfg.get_mapped_listeners()));
+            report.applied.push("mapped_listeners".to_string());
+        }
+
+        if config.get_proxy_cidrs() != new_cfg.get_proxy_cidrs() {
+            let ret = self.apply_proxy_cidrs(new_cfg.get_proxy_cidrs());
+            // ip proxy is not started when there was no proxy cidr at startup
+            let ret = match (ret, self.ip_proxy.is_some()) {
+                (Ok(_), true) => self.run_ip_proxy().await,
+                (ret, _) => ret,
+            };
+            match ret {
+                Ok(_) => report.applied.push("proxy_network".to_string()),
+                Err(e) => {
+                    report
+                        .failed
+                        .insert("proxy_network".to_string(), e.to_string());
+                }
+            }
+        }
+
+        if config.get_acl() != new_cfg.get_acl()
+            || config.get_tcp_whitelist() != new_cfg.get_tcp_whitelist()
+            || config.get_udp_whitelist() != new_cfg.get_udp_whitelist()
+        {
+            config.set_acl(new_cfg.get_acl());
+            config.set_tcp_whitelist(new_cfg.get_tcp_whitelist());
+            config.set_udp_whitelist(new_cfg.get_udp_whitelist());
+            match AclRuleBuilder::build(&self.global_ctx) {
+                Ok(acl) => {
+                    self.global_ctx.get_acl_filter().reload_rules(acl.as_ref());
+                    report.applied.push("acl".to_string());
+                }
+                Err(e) => {
+                    report.failed.insert("acl".to_string(), e.to_string());
+                }
+            }
+        }
+
+        if config.get_port_forwards() != new_cfg.get_port_forwards() {
+            config.set_port_forwards(new_cfg.get_port_forwards());
+            #[cfg(feature = "socks5")]
+            match self
+                .socks5_server
+                .reload_port_forwards(&new_cfg.get_port_forwards())
+                .await
+            {
+                Ok(_) => report.applied.push("port_forward".to_string()),
+                Err(e) => {
+                    report
+                        .failed
+                        .insert("port_forward".to_string(), e.to_string());
+                }
+            }
+            #[cfg(not(feature = "socks5"))]
+            report.restart_required.push("port_forward".to_string());
+        }
+
+        self.apply_flags(new_cfg.get_flags(), &mut report);
+
+        tracing::info!(?report, "config applied to running instance");
+        report
+    }
+
     pub async fn clear_resources(&mut self) {
         self.peer_manager.clear_resources().await;
         let _ = self.nic_ctx.lock().await.take();
@@ -13,6 +13,8 @@ use tokio::task::JoinSet;
 use crate::tunnel::quic::QUICTunnelListener;
 #[cfg(feature = "wireguard")]
 use crate::tunnel::wireguard::{WgConfig, WgTunnelListener};
+#[cfg(unix)]
+use crate::tunnel::{stdio::StdioTunnelListener, unix::UnixSocketTunnelListener};
 use crate::{
     common::{
         error::Error,
@@ -21,8 +23,8 @@ use crate::{
     },
     peers::peer_manager::PeerManager,
     tunnel::{
-        ring::RingTunnelListener, tcp::TcpTunnelListener, udp::UdpTunnelListener, Tunnel,
-        TunnelListener,
+        obfs::ObfsConfig, ring::RingTunnelListener, tcp::TcpTunnelListener, udp::UdpTunnelListener,
+        Tunnel, TunnelListener,
     },
 };
 
@@ -30,6 +32,7 @@ pub fn get_listener_by_url(
     l: &url::Url,
     _ctx: ArcGlobalCtx,
 ) -> Result<Box<dyn TunnelListener>, Error> {
+    ObfsConfig::validate_url(l)?;
     Ok(match l.scheme() {
         "tcp" => Box::new(TcpTunnelListener::new(l.clone())),
         "udp" => Box::new(UdpTunnelListener::new(l.clone())),
@@ -43,12 +46,35 @@ pub fn get_listener_by_url(
             Box::new(WgTunnelListener::new(l.clone(), wg_config))
         }
         #[cfg(feature = "quic")]
-        "quic" => Box::new(QUICTunnelListener::new(l.clone())),
+        "quic" => {
+            let mut listener = QUICTunnelListener::new(l.clone());
+            if let Some(tls) = _ctx.config.get_tls_config() {
+                listener.set_tls_config(tls);
+            }
+            Box::new(listener)
+        }
         #[cfg(feature = "websocket")]
         "ws" | "wss" => {
             use crate::tunnel::websocket::WSTunnelListener;
-            Box::new(WSTunnelListener::new(l.clone()))
+            let mut listener = WSTunnelListener::new(l.clone());
+            if let Some(tls) = _ctx.config.get_tls_config() {
+                listener.set_tls_config(tls);
+            }
+            Box::new(listener)
         }
+        #[cfg(feature = "tls")]
+        "tls" => {
+            use crate::tunnel::tcp_tls::TlsTunnelListener;
+            let mut listener = TlsTunnelListener::new(l.clone());
+            if let Some(tls) = _ctx.config.get_tls_config() {
+                listener.set_tls_config(tls);
+            }
+            Box::new(listener)
+        }
+        #[cfg(unix)]
+        "unix" => Box::new(UnixSocketTunnelListener::new(l.clone())),
+        #[cfg(unix)]
+        "stdio" | "fd" => Box::new(StdioTunnelListener::new(l.clone())),
         _ => {
             return Err(Error::InvalidUrl(l.to_string()));
         }
@@ -271,6 +297,26 @@ impl<H: TunnelHandlerForListener + Send + Sync + 'static + Debug> ListenerManage
 
         Ok(())
     }
+
+    /// rebuild listeners from the current config, accepted connections are kept.
+    pub async fn reload(&mut self) -> Result<(), Error> {
+        self.stop().await;
+        self.listeners.clear();
+        self.global_ctx.clear_running_listeners();
+        self.prepare_listeners().await?;
+        self.run().await
+    }
+
+    /// stop all running listeners and wait until their sockets are closed,
+    /// returns the number of listener tasks stopped.
+    pub async fn stop(&mut self) -> usize {
+        self.tasks.abort_all();
+        let mut stopped = 0;
+        while self.tasks.join_next().await.is_some() {
+            stopped += 1;
+        }
+        stopped
+    }
 }
 
 #[cfg(test)]
@@ -302,6 +348,15 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn invalid_obfs_param_fails_listener() {
+        let url: url::Url = "tcp://0.0.0.0:11010?obfs=bad".parse().unwrap();
+        assert!(get_listener_by_url(&url, get_mock_global_ctx()).is_err());
+
+        let url: url::Url = "tcp://0.0.0.0:11010?obfs=pad".parse().unwrap();
+        assert!(get_listener_by_url(&url, get_mock_global_ctx()).is_ok());
+    }
+
     #[tokio::test]
     async fn handle_error_in_accept() {
         let handler = Arc::new(MockListenerHandler {});
@@ -810,7 +810,7 @@ impl NicCtx {
                 let routes = peer_mgr.list_routes().await;
                 for r in routes {
                     for cidr in r.proxy_cidrs {
-                        let Ok(cidr) = cidr.parse::<cidr::Ipv4Cidr>() else {
+                        let Ok(cidr) = cidr.parse::<cidr::IpCidr>() else {
                             continue;
                         };
                         proxy_cidrs.insert(cidr);
@@ -818,7 +818,7 @@ impl NicCtx {
                 }
                 // add vpn portal cidr to proxy_cidrs
                 if let Some(vpn_cfg) = global_ctx.config.get_vpn_portal_config() {
-                    proxy_cidrs.insert(vpn_cfg.client_cidr);
+                    proxy_cidrs.insert(vpn_cfg.client_cidr.into());
                 }
 
                 if let Some(routes) = global_ctx.config.get_routes() {
@@ -833,13 +833,26 @@ impl NicCtx {
                     }
 
                     let _g = net_ns.guard();
-                    let ret = ifcfg
-                        .remove_ipv4_route(
-                            ifname.as_str(),
-                            cidr.first_address(),
-                            cidr.network_length(),
-                        )
-                        .await;
+                    let ret = match cidr {
+                        cidr::IpCidr::V4(cidr) => {
+                            ifcfg
+                                .remove_ipv4_route(
+                                    ifname.as_str(),
+                                    cidr.first_address(),
+                                    cidr.network_length(),
+                                )
+                                .await
+                        }
+                        cidr::IpCidr::V6(cidr) => {
+                            ifcfg
+                                .remove_ipv6_route(
+                                    ifname.as_str(),
+                                    cidr.first_address(),
+                                    cidr.network_length(),
+                                )
+                                .await
+                        }
+                    };
 
                     if ret.is_err() {
                         tracing::trace!(
@@ -855,14 +868,28 @@ impl NicCtx {
                         continue;
                     }
                     let _g = net_ns.guard();
-                    let ret = ifcfg
-                        .add_ipv4_route(
-                            ifname.as_str(),
-                            cidr.first_address(),
-                            cidr.network_length(),
-                            None,
-                        )
-                        .await;
+                    let ret = match cidr {
+                        cidr::IpCidr::V4(cidr) => {
+                            ifcfg
+                                .add_ipv4_route(
+                                    ifname.as_str(),
+                                    cidr.first_address(),
+                                    cidr.network_length(),
+                                    None,
+                                )
+                                .await
+                        }
+                        cidr::IpCidr::V6(cidr) => {
+                            ifcfg
+                                .add_ipv6_route(
+                                    ifname.as_str(),
+                                    cidr.first_address(),
+                                    cidr.network_length(),
+                                    None,
+                                )
+                                .await
+                        }
+                    };
 
                     if ret.is_err() {
                         tracing::trace!(
@@ -8,7 +8,10 @@ use crate::{
         global_ctx::{EventBusSubscriber, GlobalCtxEvent},
         scoped_task::ScopedTask,
     },
-    launcher::{ConfigSource, NetworkInstance, NetworkInstanceRunningInfo},
+    launcher::{
+        ConfigApplyReport, ConfigSource, NetworkInstance, NetworkInstanceRunningInfo,
+        ShutdownReport,
+    },
     proto,
 };
 
@@ -93,17 +96,28 @@ impl NetworkInstanceManager {
         cfg: TomlConfigLoader,
         source: ConfigSource,
     ) -> Result<uuid::Uuid, anyhow::Error> {
+        self.run_network_instance_with_events(cfg, source)
+            .map(|(instance_id, _)| instance_id)
+    }
+
+    /// same as `run_network_instance`, but also returns an event subscriber created before
+    /// the instance starts, so no startup event is missed.
+    pub fn run_network_instance_with_events(
+        &self,
+        cfg: TomlConfigLoader,
+        source: ConfigSource,
+    ) -> Result<(uuid::Uuid, EventBusSubscriber), anyhow::Error> {
         let instance_id = cfg.get_id();
         if self.instance_map.contains_key(&instance_id) {
             anyhow::bail!("instance {} already exists", instance_id);
         }
 
         let mut instance = NetworkInstance::new(cfg, source);
-        instance.start()?;
+        let events = instance.start()?;
 
         self.instance_map.insert(instance_id, instance);
         self.start_instance_task(instance_id)?;
-        Ok(instance_id)
+        Ok((instance_id, events))
     }
 
     pub fn retain_network_instance(
@@ -122,6 +136,39 @@ impl NetworkInstanceManager {
         Ok(self.list_network_instance_ids())
     }
 
+    /// remove the instance and wait until it is fully stopped.
+    pub async fn stop_network_instance(
+        &self,
+        instance_id: &uuid::Uuid,
+        timeout: std::time::Duration,
+    ) -> Result<ShutdownReport, anyhow::Error> {
+        let (_, instance) = self
+            .instance_map
+            .remove(instance_id)
+            .ok_or_else(|| anyhow::anyhow!("instance {} not found", instance_id))?;
+        // the stop task would race with us on the stop notifier.
+        self.instance_stop_tasks.remove(instance_id);
+        let report = instance.stop(timeout).await;
+        self.stop_check_notifier.notify_one();
+        Ok(report)
+    }
+
+    /// apply a new config to a running instance without restarting it.
+    pub async fn apply_network_config(
+        &self,
+        instance_id: &uuid::Uuid,
+        cfg: TomlConfigLoader,
+    ) -> Result<ConfigApplyReport, anyhow::Error> {
+        let reply = self
+            .instance_map
+            .get_mut(instance_id)
+            .ok_or_else(|| anyhow::anyhow!("instance {} not found", instance_id))?
+            .apply_config(cfg)?;
+        reply
+            .await
+            .map_err(|_| anyhow::anyhow!("instance {} stopped before config applied", instance_id))
+    }
+
     pub fn collect_network_infos(
         &self,
     ) -> Result<BTreeMap<uuid::Uuid, NetworkInstanceRunningInfo>, anyhow::Error> {
@@ -150,6 +197,12 @@ impl NetworkInstanceManager {
             .map(|instance| instance.value().get_inst_name())
     }
 
+    pub fn subscribe_event(&self, instance_id: &uuid::Uuid) -> Option<EventBusSubscriber> {
+        self.instance_map
+            .get(instance_id)
+            .and_then(|instance| instance.value().subscribe_event())
+    }
+
     pub fn set_tun_fd(&self, instance_id: &uuid::Uuid, fd: i32) -> Result<(), anyhow::Error> {
         let mut instance = self
             .instance_map
@@ -414,6 +467,117 @@ mod tests {
         assert_eq!(manager.list_network_instance_ids().len(), 2);
     }
 
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_stop_network_instance() {
+        let manager = NetworkInstanceManager::new();
+        let port = crate::utils::find_free_tcp_port(10012..65534).expect("no free tcp port found");
+        let cfg = TomlConfigLoader::new_from_str("listeners = []").unwrap();
+        cfg.set_listeners(vec![format!("tcp://0.0.0.0:{}", port).parse().unwrap()]);
+        let mut flags = cfg.get_flags();
+        flags.enable_ipv6 = false;
+        cfg.set_flags(flags);
+
+        let instance_id = manager
+            .run_network_instance(cfg, ConfigSource::FFI)
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        assert!(!crate::utils::check_tcp_available(port));
+
+        let report = manager
+            .stop_network_instance(&instance_id, std::time::Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert!(report.is_clean(), "{:?}", report);
+        assert!(report.steps.iter().any(|s| s.component == "listeners"));
+        assert!(report.steps.iter().any(|s| s.component == "peers"));
+
+        // the listener is released once stop returns, a new instance can reuse it at once.
+        assert!(crate::utils::check_tcp_available(port));
+        assert!(manager.list_network_instance_ids().is_empty());
+        assert!(manager
+            .stop_network_instance(&instance_id, std::time::Duration::from_secs(1))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_run_network_instance_with_events() {
+        let manager = NetworkInstanceManager::new();
+        let port = crate::utils::find_free_tcp_port(10012..65534).expect("no free tcp port found");
+        let cfg = TomlConfigLoader::new_from_str("listeners = []").unwrap();
+        cfg.set_listeners(vec![format!("tcp://0.0.0.0:{}", port).parse().unwrap()]);
+
+        let (instance_id, mut events) = manager
+            .run_network_instance_with_events(cfg, ConfigSource::FFI)
+            .unwrap();
+
+        // the subscriber exists before the launcher starts, startup events are not lost
+        let listener_added = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                if let GlobalCtxEvent::ListenerAdded(url) = events.recv().await.unwrap() {
+                    if url.port() == Some(port) {
+                        break;
+                    }
+                }
+            }
+        })
+        .await;
+        assert!(listener_added.is_ok());
+
+        manager
+            .stop_network_instance(&instance_id, std::time::Duration::from_secs(10))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_apply_network_config() {
+        let manager = NetworkInstanceManager::new();
+        let cfg = TomlConfigLoader::new_from_str("listeners = []").unwrap();
+        cfg.set_hostname(Some("before".to_string()));
+        let instance_id = manager
+            .run_network_instance(cfg.clone(), ConfigSource::FFI)
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+        let new_cfg = TomlConfigLoader::new_from_str(cfg.dump().as_str()).unwrap();
+        new_cfg.set_hostname(Some("after".to_string()));
+        new_cfg.set_peers(vec![PeerConfig {
+            uri: "tcp://127.0.0.1:1".parse().unwrap(),
+        }]);
+        let mut flags = new_cfg.get_flags();
+        flags.latency_first = !flags.latency_first;
+        flags.mtu = 1300;
+        new_cfg.set_flags(flags);
+
+        let report = manager
+            .apply_network_config(&instance_id, new_cfg)
+            .await
+            .unwrap();
+        assert!(report.failed.is_empty(), "{:?}", report);
+        assert!(report.applied.contains(&"hostname".to_string()));
+        assert!(report.applied.contains(&"peer".to_string()));
+        assert!(report.applied.contains(&"flags.latency_first".to_string()));
+        assert_eq!(report.restart_required, vec!["flags.mtu".to_string()]);
+
+        // node info is refreshed every second
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        let node_info = manager
+            .get_network_info(&instance_id)
+            .and_then(|info| info.my_node_info)
+            .unwrap();
+        assert_eq!(node_info.hostname, "after");
+
+        // instance id can not be changed on the fly
+        assert!(manager
+            .apply_network_config(&instance_id, TomlConfigLoader::default())
+            .await
+            .is_err());
+    }
+
     #[test]
     #[serial_test::serial]
     fn test_no_tokio_runtime() {
@@ -1,4 +1,4 @@
-use crate::common::config::PortForwardConfig;
+use crate::common::config::{parse_allowed_sources, PortForwardConfig};
 use crate::proto::peer_rpc::RouteForeignNetworkSummary;
 use crate::proto::web;
 use crate::{
@@ -13,35 +13,90 @@ use crate::{
     },
     instance::instance::Instance,
     peers::rpc_service::PeerManagerRpcService,
-    proto::cli::{list_peer_route_pair, PeerInfo, Route},
+    proto::cli::{list_peer_route_pair, ListExitFlowResponse, PeerInfo, Route},
 };
 use anyhow::Context;
 use chrono::{DateTime, Local};
 use std::net::SocketAddr;
 use std::{
-    collections::VecDeque,
+    collections::{BTreeMap, VecDeque},
     sync::{atomic::AtomicBool, Arc, RwLock},
 };
-use tokio::{sync::broadcast, task::JoinSet};
+use tokio::{
+    sync::{broadcast, mpsc, oneshot},
+    task::JoinSet,
+};
 
 pub type MyNodeInfo = crate::proto::web::MyNodeInfo;
 
+// exit flows copied into the running info every second, the full list is available from the
+// ListExitFlow rpc
+const RUNNING_INFO_MAX_EXIT_FLOWS: usize = 256;
+
 #[derive(serde::Serialize, Clone)]
 pub struct Event {
     time: DateTime<Local>,
     event: GlobalCtxEvent,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShutdownStep {
+    pub component: String,
+    pub success: bool,
+    pub message: String,
+}
+
+/// what was cleaned up (or failed to) when an instance stopped.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShutdownReport {
+    pub steps: Vec<ShutdownStep>,
+}
+
+impl ShutdownReport {
+    pub fn succeeded(&mut self, component: &str, message: impl Into<String>) {
+        self.steps.push(ShutdownStep {
+            component: component.to_string(),
+            success: true,
+            message: message.into(),
+        });
+    }
+
+    pub fn failed(&mut self, component: &str, message: impl Into<String>) {
+        self.steps.push(ShutdownStep {
+            component: component.to_string(),
+            success: false,
+            message: message.into(),
+        });
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.steps.iter().all(|s| s.success)
+    }
+}
+
+/// result of applying a new config to a running instance. fields are named after the
+/// config keys, flags are reported as `flags.<name>`.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ConfigApplyReport {
+    pub applied: Vec<String>,
+    pub restart_required: Vec<String>,
+    pub failed: BTreeMap<String, String>,
+}
+
+type ConfigApplyRequest = (TomlConfigLoader, oneshot::Sender<ConfigApplyReport>);
+
 struct EasyTierData {
     events: RwLock<VecDeque<Event>>,
     my_node_info: RwLock<MyNodeInfo>,
     routes: RwLock<Vec<Route>>,
     peers: RwLock<Vec<PeerInfo>>,
     foreign_network_summary: RwLock<RouteForeignNetworkSummary>,
+    exit_flows: RwLock<ListExitFlowResponse>,
     tun_fd: Arc<RwLock<Option<i32>>>,
     tun_dev_name: RwLock<String>,
     event_subscriber: RwLock<broadcast::Sender<GlobalCtxEvent>>,
     instance_stop_notifier: Arc<tokio::sync::Notify>,
+    shutdown_report: RwLock<Option<ShutdownReport>>,
 }
 
 impl Default for EasyTierData {
@@ -54,9 +109,11 @@ impl Default for EasyTierData {
             routes: RwLock::new(Vec::new()),
             peers: RwLock::new(Vec::new()),
             foreign_network_summary: RwLock::new(RouteForeignNetworkSummary::default()),
+            exit_flows: RwLock::new(ListExitFlowResponse::default()),
             tun_fd: Arc::new(RwLock::new(None)),
             tun_dev_name: RwLock::new(String::new()),
             instance_stop_notifier: Arc::new(tokio::sync::Notify::new()),
+            shutdown_report: RwLock::new(None),
         }
     }
 }
@@ -65,6 +122,7 @@ pub struct EasyTierLauncher {
     instance_alive: Arc<AtomicBool>,
     stop_flag: Arc<AtomicBool>,
     thread_handle: Option<std::thread::JoinHandle<()>>,
+    config_apply_sender: Option<mpsc::Sender<ConfigApplyRequest>>,
     running_cfg: String,
     fetch_node_info: bool,
 
@@ -78,6 +136,7 @@ impl EasyTierLauncher {
         Self {
             instance_alive,
             thread_handle: None,
+            config_apply_sender: None,
             error_msg: Arc::new(RwLock::new(None)),
             running_cfg: String::new(),
             fetch_node_info,
@@ -136,6 +195,7 @@ impl EasyTierLauncher {
     async fn easytier_routine(
         cfg: TomlConfigLoader,
         stop_signal: Arc<tokio::sync::Notify>,
+        mut config_apply_recv: mpsc::Receiver<ConfigApplyRequest>,
         data: Arc<EasyTierData>,
         fetch_node_info: bool,
     ) -> Result<(), anyhow::Error> {
@@ -200,6 +260,8 @@ impl EasyTierLauncher {
                         PeerManagerRpcService::list_peers(&peer_mgr_c).await;
                     *data_c.foreign_network_summary.write().unwrap() =
                         peer_mgr_c.get_foreign_network_summary().await;
+                    *data_c.exit_flows.write().unwrap() =
+                        peer_mgr_c.list_exit_flows(RUNNING_INFO_MAX_EXIT_FLOWS);
                     tokio::time::sleep(std::time::Duration::from_secs(1)).await;
                 }
             });
@@ -209,12 +271,20 @@ impl EasyTierLauncher {
         Self::run_routine_for_android(&instance, &data, &mut tasks).await;
 
         instance.run().await?;
-        stop_signal.notified().await;
+        loop {
+            tokio::select! {
+                _ = stop_signal.notified() => break,
+                Some((cfg, reply)) = config_apply_recv.recv() => {
+                    let _ = reply.send(instance.apply_config(&cfg).await);
+                }
+            }
+        }
 
         tasks.abort_all();
         drop(tasks);
 
-        instance.clear_resources().await;
+        let report = instance.shutdown().await;
+        data.shutdown_report.write().unwrap().replace(report);
         drop(instance);
 
         Ok(())
@@ -259,6 +329,8 @@ impl EasyTierLauncher {
 
         let data = self.data.clone();
         let fetch_node_info = self.fetch_node_info;
+        let (config_apply_sender, config_apply_recv) = mpsc::channel(4);
+        self.config_apply_sender = Some(config_apply_sender);
 
         self.thread_handle = Some(std::thread::spawn(move || {
             let rt = if cfg.get_flags().multi_thread {
@@ -288,6 +360,7 @@ impl EasyTierLauncher {
             let ret = rt.block_on(Self::easytier_routine(
                 cfg,
                 stop_notifier.clone(),
+                config_apply_recv,
                 data,
                 fetch_node_info,
             ));
@@ -332,6 +405,10 @@ impl EasyTierLauncher {
     pub fn get_foreign_network_summary(&self) -> RouteForeignNetworkSummary {
         self.data.foreign_network_summary.read().unwrap().clone()
     }
+
+    pub fn get_exit_flows(&self) -> ListExitFlowResponse {
+        self.data.exit_flows.read().unwrap().clone()
+    }
 }
 
 impl Drop for EasyTierLauncher {
@@ -411,6 +488,7 @@ impl NetworkInstance {
             running: launcher.running(),
             error_msg: launcher.error_msg(),
             foreign_network_summary: Some(launcher.get_foreign_network_summary()),
+            exit_flows: Some(launcher.get_exit_flows()),
         })
     }
 
@@ -453,6 +531,79 @@ impl NetworkInstance {
             .map(|launcher| launcher.data.instance_stop_notifier.clone())
     }
 
+    /// stop the instance and wait until its resources are released, the returned report
+    /// tells which parts were cleaned up. gives up waiting after `timeout`.
+    pub async fn stop(mut self, timeout: std::time::Duration) -> ShutdownReport {
+        let Some(launcher) = self.launcher.take() else {
+            return ShutdownReport::default();
+        };
+
+        let notifier = launcher.data.instance_stop_notifier.clone();
+        launcher
+            .stop_flag
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+
+        let wait_stopped = async {
+            while launcher.running() {
+                // the stop flag is polled every 100ms, so do not rely on a single notify.
+                let _ = tokio::time::timeout(
+                    std::time::Duration::from_millis(200),
+                    notifier.notified(),
+                )
+                .await;
+            }
+        };
+        let stopped = tokio::time::timeout(timeout, wait_stopped).await.is_ok();
+
+        let mut report = launcher
+            .data
+            .shutdown_report
+            .write()
+            .unwrap()
+            .take()
+            .unwrap_or_default();
+        if !stopped {
+            report.failed("instance", format!("not stopped after {:?}", timeout));
+        } else if let Some(e) = launcher.error_msg() {
+            report.failed("instance", format!("stopped with error: {}", e));
+        }
+
+        // joining the instance thread blocks, keep it off the async runtime.
+        let join = tokio::task::spawn_blocking(move || drop(launcher));
+        if stopped {
+            let _ = join.await;
+        }
+
+        report
+    }
+
+    /// ask the running instance to apply `cfg` without restarting. the receiver yields
+    /// which fields were applied live and which only take effect after a restart.
+    /// `cfg` becomes the config used by the next start.
+    pub fn apply_config(
+        &mut self,
+        cfg: TomlConfigLoader,
+    ) -> Result<oneshot::Receiver<ConfigApplyReport>, anyhow::Error> {
+        if cfg.get_id() != self.config.get_id() {
+            anyhow::bail!("instance id can not be changed");
+        }
+        let Some(sender) = self
+            .launcher
+            .as_ref()
+            .filter(|l| l.running())
+            .and_then(|l| l.config_apply_sender.as_ref())
+        else {
+            anyhow::bail!("instance is not running");
+        };
+
+        let (reply, reply_recv) = oneshot::channel();
+        sender
+            .try_send((cfg.clone(), reply))
+            .map_err(|e| anyhow::anyhow!("failed to send config to instance: {}", e))?;
+        self.config = cfg;
+        Ok(reply_recv)
+    }
+
     pub fn get_latest_error_msg(&self) -> Option<String> {
         if let Some(launcher) = self.launcher.as_ref() {
             launcher.error_msg.read().unwrap().clone()
@@ -474,7 +625,7 @@ pub fn add_proxy_network_to_config(
     if parts.len() > 2 {
         return Err(anyhow::anyhow!(
                     "invalid proxy network format: {}, support format: <real_cidr> or <real_cidr>-><mapped_cidr>, example:
-                    10.0.0.0/24 or 10.0.0.0/24->192.168.0.0/24",
+                    10.0.0.0/24, 10.0.0.0/24->192.168.0.0/24 or fd00:1::/64->fd00:2::/64",
                     proxy_network
                 ));
     }
@@ -599,22 +750,35 @@ impl NetworkConfig {
                 self.port_forwards
                     .iter()
                     .filter(|pf| !pf.bind_ip.is_empty() && !pf.dst_ip.is_empty())
-                    .filter_map(|pf| {
+                    .map(|pf| {
                         let bind_addr =
                             format!("{}:{}", pf.bind_ip, pf.bind_port).parse::<SocketAddr>();
                         let dst_addr =
                             format!("{}:{}", pf.dst_ip, pf.dst_port).parse::<SocketAddr>();
 
-                        match (bind_addr, dst_addr) {
-                            (Ok(bind_addr), Ok(dst_addr)) => Some(PortForwardConfig {
-                                bind_addr,
-                                dst_addr,
-                                proto: pf.proto.clone(),
-                            }),
-                            _ => None,
-                        }
+                        let (Ok(bind_addr), Ok(dst_addr)) = (bind_addr, dst_addr) else {
+                            return Ok(None);
+                        };
+                        Ok(Some(PortForwardConfig {
+                            bind_addr,
+                            dst_addr,
+                            proto: pf.proto.clone(),
+                            bind_port_end: pf
+                                .bind_port_end
+                                .map(|p| {
+                                    u16::try_from(p).with_context(|| {
+                                        format!("invalid port forward bind port end: {}", p)
+                                    })
+                                })
+                                .transpose()?,
+                            udp_idle_timeout: pf.udp_idle_timeout,
+                            allowed_sources: parse_allowed_sources(&pf.allowed_sources)?,
+                        }))
                     })
-                    .collect::<Vec<_>>(),
+                    .collect::<Result<Vec<_>, anyhow::Error>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect(),
             );
         }
 
@@ -645,7 +809,7 @@ impl NetworkConfig {
         }
 
         if self.enable_manual_routes.unwrap_or_default() {
-            let mut routes = Vec::<cidr::Ipv4Cidr>::with_capacity(self.routes.len());
+            let mut routes = Vec::<cidr::IpCidr>::with_capacity(self.routes.len());
             for route in self.routes.iter() {
                 routes.push(
                     route
@@ -775,6 +939,10 @@ impl NetworkConfig {
             flags.disable_sym_hole_punching = disable_sym_hole_punching;
         }
 
+        if let Some(enable_multipath_bonding) = self.enable_multipath_bonding {
+            flags.enable_multipath_bonding = enable_multipath_bonding;
+        }
+
         if let Some(enable_magic_dns) = self.enable_magic_dns {
             flags.accept_dns = enable_magic_dns;
         }
@@ -866,6 +1034,9 @@ impl NetworkConfig {
                     bind_port: f.bind_addr.port() as u32,
                     dst_ip: f.dst_addr.ip().to_string(),
                     dst_port: f.dst_addr.port() as u32,
+                    bind_port_end: f.bind_port_end.map(Into::into),
+                    udp_idle_timeout: f.udp_idle_timeout,
+                    allowed_sources: f.allowed_sources.iter().map(|c| c.to_string()).collect(),
                 })
                 .collect();
         }
@@ -1039,7 +1210,7 @@ mod tests {
                         rng.gen_range(0..255),
                         rng.gen_range(24..30)
                     )
-                    .parse::<cidr::Ipv4Cidr>()
+                    .parse::<cidr::IpCidr>()
                     .unwrap();
 
                     let mapped_network = if rng.gen_bool(0.5) {
@@ -1051,7 +1222,7 @@ mod tests {
                                 rng.gen_range(0..255),
                                 network.network_length()
                             )
-                            .parse::<cidr::Ipv4Cidr>()
+                            .parse::<cidr::IpCidr>()
                             .unwrap(),
                         )
                     } else {
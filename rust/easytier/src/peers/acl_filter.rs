@@ -2,7 +2,7 @@ use std::net::{Ipv4Addr, Ipv6Addr};
 use std::sync::atomic::{AtomicU16, Ordering};
 use std::{
     net::IpAddr,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{atomic::AtomicBool, Arc, Mutex},
 };
 
 use arc_swap::ArcSwap;
@@ -14,7 +14,9 @@ use pnet::packet::{
 use crate::proto::acl::{AclStats, Protocol};
 use crate::tunnel::packet_def::PacketType;
 use crate::{
-    common::acl_processor::{AclProcessor, AclResult, AclStatKey, AclStatType, PacketInfo},
+    common::acl_processor::{
+        AclHostTable, AclProcessor, AclResult, AclStatKey, AclStatType, PacketInfo,
+    },
     proto::acl::{Acl, Action, ChainType},
     tunnel::packet_def::ZCPacket,
 };
@@ -26,6 +28,26 @@ pub struct AclFilter {
     acl_processor: ArcSwap<AclProcessor>,
     acl_enabled: Arc<AtomicBool>,
     quic_udp_port: AtomicU16,
+    // Current config and route table snapshot, kept to rebuild the processor
+    // when host names in the rules resolve to different addresses
+    acl_source: Mutex<AclSource>,
+}
+
+#[derive(Default)]
+struct AclSource {
+    acl_config: Option<Acl>,
+    hosts: AclHostTable,
+}
+
+fn acl_uses_hosts(acl_config: &Acl) -> bool {
+    acl_config.acl_v1.as_ref().is_some_and(|v1| {
+        v1.chains.iter().any(|chain| {
+            chain
+                .rules
+                .iter()
+                .any(|r| !r.source_hosts.is_empty() || !r.destination_hosts.is_empty())
+        })
+    })
 }
 
 impl Default for AclFilter {
@@ -40,6 +62,7 @@ impl AclFilter {
             acl_processor: ArcSwap::from(Arc::new(AclProcessor::new(Acl::default()))),
             acl_enabled: Arc::new(AtomicBool::new(false)),
             quic_udp_port: AtomicU16::new(0),
+            acl_source: Mutex::new(AclSource::default()),
         }
     }
 
@@ -47,18 +70,47 @@ impl AclFilter {
     /// Preserves connection tracking and rate limiting state across reloads
     /// Now lock-free and doesn't require &mut self!
     pub fn reload_rules(&self, acl_config: Option<&Acl>) {
+        let mut source = self.acl_source.lock().unwrap();
+        source.acl_config = acl_config.cloned();
         let Some(acl_config) = acl_config else {
             self.acl_enabled.store(false, Ordering::Relaxed);
             return;
         };
 
+        self.rebuild_processor(acl_config, &source.hosts);
+        self.acl_enabled.store(true, Ordering::Relaxed);
+
+        tracing::info!("ACL rules hot reloaded with preserved state (lock-free)");
+    }
+
+    /// Update the route table snapshot used to resolve host names in rules
+    /// Rules are only rebuilt if they reference hosts and the snapshot changed
+    pub fn update_hosts(&self, hosts: AclHostTable) {
+        let mut source = self.acl_source.lock().unwrap();
+        if source.hosts == hosts {
+            return;
+        }
+        source.hosts = hosts;
+
+        let Some(acl_config) = source.acl_config.as_ref() else {
+            return;
+        };
+        if !acl_uses_hosts(acl_config) {
+            return;
+        }
+        self.rebuild_processor(acl_config, &source.hosts);
+        tracing::info!("ACL host rules re-resolved after route table change");
+    }
+
+    fn rebuild_processor(&self, acl_config: &Acl, hosts: &AclHostTable) {
         // Get current processor to extract shared state
         let current_processor = self.acl_processor.load();
         let (conn_track, rate_limiters, stats) = current_processor.get_shared_state();
 
         // Create new processor with preserved state
-        let new_processor = AclProcessor::new_with_shared_state(
+        let new_processor = AclProcessor::new_with_hosts(
             acl_config.clone(),
+            hosts,
             Some(conn_track),
             Some(rate_limiters),
             Some(stats),
@@ -66,9 +118,6 @@ impl AclFilter {
 
         // Atomic replacement - this is completely lock-free!
         self.acl_processor.store(Arc::new(new_processor));
-        self.acl_enabled.store(true, Ordering::Relaxed);
-
-        tracing::info!("ACL rules hot reloaded with preserved state (lock-free)");
     }
 
     /// Get current processor for processing packets
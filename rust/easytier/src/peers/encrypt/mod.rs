@@ -14,6 +14,7 @@ pub mod aes_gcm;
 #[cfg(feature = "openssl-crypto")]
 pub mod openssl_cipher;
 
+pub mod session;
 pub mod xor_cipher;
 
 #[derive(thiserror::Error, Debug)]
@@ -26,11 +27,24 @@ pub enum Error {
     EncryptionFailed,
     #[error("invalid tag. tag: {0:?}")]
     InvalidTag(Vec<u8>),
+    #[error("key exchange failed: {0}")]
+    KeyExchangeFailed(String),
+    #[error("no session. peer id: {0}, session id: {1}")]
+    SessionNotFound(u32, u32),
+    #[error("replayed packet. counter: {0}")]
+    Replayed(u64),
 }
 
 pub trait Encryptor: Send + Sync + 'static {
     fn encrypt(&self, zc_packet: &mut ZCPacket) -> Result<(), Error>;
     fn decrypt(&self, zc_packet: &mut ZCPacket) -> Result<(), Error>;
+
+    /// Encrypt with a caller chosen nonce instead of a random one. The caller must never use
+    /// the same nonce twice with one key. Ciphers without a nonce just ignore it.
+    fn encrypt_with_nonce(&self, zc_packet: &mut ZCPacket, nonce: &[u8; 12]) -> Result<(), Error> {
+        let _ = nonce;
+        self.encrypt(zc_packet)
+    }
 }
 
 pub struct NullCipher;
@@ -50,6 +64,14 @@ impl Encryptor for NullCipher {
     }
 }
 
+/// Session keys need an AEAD cipher, the counter carried in the packet must be authenticated.
+pub fn supports_session_keys(algorithm: &str) -> bool {
+    !matches!(
+        EncryptionAlgorithm::try_from(algorithm),
+        Ok(EncryptionAlgorithm::Xor) | Err(_)
+    )
+}
+
 /// Create an encryptor based on the algorithm name
 pub fn create_encryptor(
     algorithm: &str,
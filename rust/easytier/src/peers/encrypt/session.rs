@@ -0,0 +1,641 @@
+//! Session keys negotiated per peer connection. Both ends do an ephemeral x25519 exchange in the
+//! handshake and mix it with the network key, like Noise NNpsk0, so a leaked network secret
+//! does not decrypt recorded traffic. Keys are ratcheted forward in epochs and old ones are
+//! dropped, every packet carries a counter which is used as nonce and checked against replay.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use dashmap::DashMap;
+use hkdf::Hkdf;
+use sha2::Sha256;
+use x25519_dalek::{EphemeralSecret, PublicKey};
+use zerocopy::{AsBytes, FromBytes};
+
+use crate::{
+    common::PeerId,
+    tunnel::packet_def::{SessionTail, ZCPacket, SESSION_TAIL_SIZE},
+};
+
+use super::{create_encryptor, Encryptor, Error};
+
+const SESSION_KDF_INFO: &[u8] = b"easytier peer session v1";
+const REKEY_KDF_INFO: &[u8] = b"easytier peer session rekey";
+
+// a sender moves to the next key epoch after this time or this many packets
+const REKEY_AFTER_TIME: Duration = Duration::from_secs(120);
+const REKEY_AFTER_PACKETS: u64 = 1 << 30;
+// how far a receiver follows the sender's epoch in one step
+const MAX_EPOCH_SKIP: u32 = 8;
+// every new connection to a peer adds a session, keep only the latest few
+const MAX_SESSIONS_PER_PEER: usize = 4;
+
+const COUNTER_SIZE: usize = std::mem::size_of::<u64>();
+
+const REPLAY_WINDOW_WORDS: usize = 32;
+const REPLAY_WINDOW_SIZE: u64 = (REPLAY_WINDOW_WORDS as u64 - 1) * 64;
+
+// sliding window of received counters (RFC 6479), packets reordered inside it are accepted once
+#[derive(Default)]
+struct ReplayWindow {
+    last: u64,
+    bitmap: [u64; REPLAY_WINDOW_WORDS],
+}
+
+impl ReplayWindow {
+    fn position(counter: u64) -> (usize, u64) {
+        let word = (counter / 64) % REPLAY_WINDOW_WORDS as u64;
+        (word as usize, 1 << (counter % 64))
+    }
+
+    fn update(&mut self, counter: u64) -> bool {
+        if counter > self.last {
+            let cur_word = self.last / 64;
+            let diff = (counter / 64 - cur_word).min(REPLAY_WINDOW_WORDS as u64);
+            for i in 1..=diff {
+                self.bitmap[((cur_word + i) % REPLAY_WINDOW_WORDS as u64) as usize] = 0;
+            }
+            self.last = counter;
+        } else if self.last - counter >= REPLAY_WINDOW_SIZE {
+            return false;
+        }
+
+        let (word, bit) = Self::position(counter);
+        if self.bitmap[word] & bit != 0 {
+            return false;
+        }
+        self.bitmap[word] |= bit;
+        true
+    }
+}
+
+// one way, so the key of an epoch does not reveal the keys of earlier ones
+fn ratchet(key: &[u8; 32]) -> [u8; 32] {
+    let hk = Hkdf::<Sha256>::from_prk(key).unwrap();
+    let mut next = [0u8; 32];
+    hk.expand(REKEY_KDF_INFO, &mut next).unwrap();
+    next
+}
+
+fn make_nonce(epoch: u32, counter: u64) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[..4].copy_from_slice(&epoch.to_be_bytes());
+    nonce[4..].copy_from_slice(&counter.to_be_bytes());
+    nonce
+}
+
+struct EpochKey {
+    epoch: u32,
+    key: [u8; 32],
+    cipher: Arc<dyn Encryptor>,
+}
+
+impl EpochKey {
+    fn new(algorithm: &str, epoch: u32, key: [u8; 32]) -> Self {
+        let mut key_128 = [0u8; 16];
+        key_128.copy_from_slice(&key[..16]);
+        EpochKey {
+            epoch,
+            key,
+            cipher: create_encryptor(algorithm, key_128, key),
+        }
+    }
+
+    fn advance(&self, algorithm: &str, epoch: u32) -> Self {
+        let mut key = self.key;
+        for _ in self.epoch..epoch {
+            key = ratchet(&key);
+        }
+        Self::new(algorithm, epoch, key)
+    }
+}
+
+struct SendState {
+    key: EpochKey,
+    counter: u64,
+    started_at: Instant,
+}
+
+struct RecvEpoch {
+    key: EpochKey,
+    replay: ReplayWindow,
+}
+
+// the current epoch and the one before it, packets of the old epoch may still be in flight
+struct RecvState {
+    epochs: Vec<RecvEpoch>,
+}
+
+impl RecvState {
+    fn find(&self, epoch: u32) -> Option<&RecvEpoch> {
+        self.epochs.iter().find(|e| e.key.epoch == epoch)
+    }
+
+    fn find_mut(&mut self, epoch: u32) -> Option<&mut RecvEpoch> {
+        self.epochs.iter_mut().find(|e| e.key.epoch == epoch)
+    }
+
+    fn newest(&self) -> &EpochKey {
+        &self.epochs.last().unwrap().key
+    }
+
+    fn push(&mut self, key: EpochKey) {
+        if key.epoch <= self.newest().epoch {
+            return;
+        }
+        self.epochs.push(RecvEpoch {
+            key,
+            replay: ReplayWindow::default(),
+        });
+        if self.epochs.len() > 2 {
+            self.epochs.remove(0);
+        }
+    }
+}
+
+pub struct PeerSession {
+    session_id: u32,
+    algorithm: String,
+    // set once the peer is known to have this session, only confirmed sessions are used to send
+    confirmed: AtomicBool,
+    send: Mutex<SendState>,
+    recv: Mutex<RecvState>,
+}
+
+impl PeerSession {
+    fn new(algorithm: &str, session_id: u32, send_key: [u8; 32], recv_key: [u8; 32]) -> Self {
+        PeerSession {
+            session_id,
+            algorithm: algorithm.to_owned(),
+            confirmed: AtomicBool::new(false),
+            send: Mutex::new(SendState {
+                key: EpochKey::new(algorithm, 0, send_key),
+                counter: 0,
+                started_at: Instant::now(),
+            }),
+            recv: Mutex::new(RecvState {
+                epochs: vec![RecvEpoch {
+                    key: EpochKey::new(algorithm, 0, recv_key),
+                    replay: ReplayWindow::default(),
+                }],
+            }),
+        }
+    }
+
+    pub fn session_id(&self) -> u32 {
+        self.session_id
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        self.confirmed.load(Ordering::Relaxed)
+    }
+
+    pub fn confirm(&self) {
+        self.confirmed.store(true, Ordering::Relaxed);
+    }
+
+    fn encrypt(&self, zc_packet: &mut ZCPacket) -> Result<(), Error> {
+        let (epoch, counter, cipher) = {
+            let mut send = self.send.lock().unwrap();
+            if send.counter >= REKEY_AFTER_PACKETS || send.started_at.elapsed() >= REKEY_AFTER_TIME
+            {
+                send.key = send.key.advance(&self.algorithm, send.key.epoch + 1);
+                send.counter = 0;
+                send.started_at = Instant::now();
+            }
+            let counter = send.counter;
+            send.counter += 1;
+            (send.key.epoch, counter, send.key.cipher.clone())
+        };
+
+        // the counter is encrypted with the payload so the receiver can trust it
+        zc_packet
+            .mut_inner()
+            .extend_from_slice(&counter.to_be_bytes());
+        cipher.encrypt_with_nonce(zc_packet, &make_nonce(epoch, counter))?;
+
+        let tail = SessionTail {
+            session_id: self.session_id.into(),
+            epoch: epoch.into(),
+        };
+        zc_packet.mut_inner().extend_from_slice(tail.as_bytes());
+        zc_packet
+            .mut_peer_manager_header()
+            .unwrap()
+            .set_session_key(true);
+        Ok(())
+    }
+
+    // the session tail is already removed
+    fn decrypt(&self, zc_packet: &mut ZCPacket, epoch: u32) -> Result<(), Error> {
+        let (cipher, new_key) = {
+            let recv = self.recv.lock().unwrap();
+            if let Some(e) = recv.find(epoch) {
+                (e.key.cipher.clone(), None)
+            } else {
+                let newest = recv.newest();
+                if epoch <= newest.epoch || epoch - newest.epoch > MAX_EPOCH_SKIP {
+                    return Err(Error::DecryptionFailed);
+                }
+                let key = newest.advance(&self.algorithm, epoch);
+                (key.cipher.clone(), Some(key))
+            }
+        };
+
+        cipher.decrypt(zc_packet)?;
+
+        let payload_len = zc_packet.payload().len();
+        if payload_len < COUNTER_SIZE {
+            return Err(Error::PacketTooShort(payload_len));
+        }
+        let counter = u64::from_be_bytes(
+            zc_packet.payload()[payload_len - COUNTER_SIZE..]
+                .try_into()
+                .unwrap(),
+        );
+
+        {
+            let mut recv = self.recv.lock().unwrap();
+            // only a packet which decrypted fine moves the receiver to a new epoch
+            if let Some(key) = new_key {
+                recv.push(key);
+            }
+            let Some(e) = recv.find_mut(epoch) else {
+                return Err(Error::DecryptionFailed);
+            };
+            if !e.replay.update(counter) {
+                return Err(Error::Replayed(counter));
+            }
+        }
+
+        let old_len = zc_packet.buf_len();
+        zc_packet.mut_inner().truncate(old_len - COUNTER_SIZE);
+        self.confirm();
+        Ok(())
+    }
+}
+
+/// Ephemeral key of one side of a connection handshake.
+pub struct SessionKeyExchange {
+    secret: Option<EphemeralSecret>,
+    public: PublicKey,
+}
+
+impl Default for SessionKeyExchange {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionKeyExchange {
+    pub fn new() -> Self {
+        let secret = EphemeralSecret::random_from_rng(rand::rngs::OsRng);
+        let public = PublicKey::from(&secret);
+        SessionKeyExchange {
+            secret: Some(secret),
+            public,
+        }
+    }
+
+    pub fn public_key(&self) -> Vec<u8> {
+        self.public.as_bytes().to_vec()
+    }
+
+    /// Consumes the ephemeral secret, so one exchange yields exactly one session.
+    pub fn derive_session(
+        &mut self,
+        algorithm: &str,
+        network_key: &[u8; 32],
+        my_peer_id: PeerId,
+        peer_id: PeerId,
+        peer_public_key: &[u8],
+    ) -> Result<PeerSession, Error> {
+        let peer_public: [u8; 32] = peer_public_key.try_into().map_err(|_| {
+            Error::KeyExchangeFailed(format!(
+                "invalid public key length: {}",
+                peer_public_key.len()
+            ))
+        })?;
+        let peer_public = PublicKey::from(peer_public);
+        let Some(secret) = self.secret.take() else {
+            return Err(Error::KeyExchangeFailed(
+                "ephemeral key already used".to_owned(),
+            ));
+        };
+        let shared = secret.diffie_hellman(&peer_public);
+        if !shared.was_contributory() {
+            return Err(Error::KeyExchangeFailed(
+                "peer sent a low order public key".to_owned(),
+            ));
+        }
+
+        // both ends must feed the kdf in the same order, the lower peer id goes first
+        let i_am_lower = my_peer_id < peer_id;
+        let (lower, higher) = if i_am_lower {
+            ((my_peer_id, self.public), (peer_id, peer_public))
+        } else {
+            ((peer_id, peer_public), (my_peer_id, self.public))
+        };
+        let mut info = SESSION_KDF_INFO.to_vec();
+        info.extend_from_slice(&lower.0.to_be_bytes());
+        info.extend_from_slice(&higher.0.to_be_bytes());
+        info.extend_from_slice(lower.1.as_bytes());
+        info.extend_from_slice(higher.1.as_bytes());
+
+        let hk = Hkdf::<Sha256>::new(Some(network_key), shared.as_bytes());
+        let mut okm = [0u8; 68];
+        hk.expand(&info, &mut okm).unwrap();
+
+        let lower_to_higher: [u8; 32] = okm[..32].try_into().unwrap();
+        let higher_to_lower: [u8; 32] = okm[32..64].try_into().unwrap();
+        let session_id = u32::from_be_bytes(okm[64..].try_into().unwrap());
+        let (send_key, recv_key) = if i_am_lower {
+            (lower_to_higher, higher_to_lower)
+        } else {
+            (higher_to_lower, lower_to_higher)
+        };
+        Ok(PeerSession::new(algorithm, session_id, send_key, recv_key))
+    }
+}
+
+/// Encrypts with the session negotiated with the destination peer, peers without a session
+/// (e.g. only reachable through relays) fall back to the network wide key.
+pub struct SessionEncryptor {
+    algorithm: String,
+    fallback: Arc<dyn Encryptor>,
+    sessions: DashMap<PeerId, Vec<Arc<PeerSession>>>,
+}
+
+impl SessionEncryptor {
+    pub fn new(algorithm: &str, fallback: Arc<dyn Encryptor>) -> Self {
+        SessionEncryptor {
+            algorithm: algorithm.to_owned(),
+            fallback,
+            sessions: DashMap::new(),
+        }
+    }
+
+    pub fn algorithm(&self) -> &str {
+        &self.algorithm
+    }
+
+    pub fn add_session(&self, peer_id: PeerId, session: PeerSession) {
+        let mut sessions = self.sessions.entry(peer_id).or_default();
+        sessions.push(Arc::new(session));
+        while sessions.len() > MAX_SESSIONS_PER_PEER {
+            // handshakes that never finished leave unconfirmed sessions, drop those first
+            let last = sessions.len() - 1;
+            let idx = sessions[..last]
+                .iter()
+                .position(|s| !s.is_confirmed())
+                .unwrap_or(0);
+            sessions.remove(idx);
+        }
+    }
+
+    pub fn remove_peer(&self, peer_id: PeerId) {
+        self.sessions.remove(&peer_id);
+    }
+
+    pub fn list_peers(&self) -> Vec<PeerId> {
+        self.sessions.iter().map(|e| *e.key()).collect()
+    }
+
+    pub fn has_session(&self, peer_id: PeerId) -> bool {
+        self.get_send_session(peer_id).is_some()
+    }
+
+    fn get_send_session(&self, peer_id: PeerId) -> Option<Arc<PeerSession>> {
+        let sessions = self.sessions.get(&peer_id)?;
+        sessions.iter().rev().find(|s| s.is_confirmed()).cloned()
+    }
+
+    fn get_session(&self, peer_id: PeerId, session_id: u32) -> Option<Arc<PeerSession>> {
+        let sessions = self.sessions.get(&peer_id)?;
+        sessions
+            .iter()
+            .find(|s| s.session_id() == session_id)
+            .cloned()
+    }
+}
+
+impl Encryptor for SessionEncryptor {
+    fn encrypt(&self, zc_packet: &mut ZCPacket) -> Result<(), Error> {
+        let pm_header = zc_packet.peer_manager_header().unwrap();
+        if pm_header.is_encrypted() {
+            return self.fallback.encrypt(zc_packet);
+        }
+        match self.get_send_session(pm_header.to_peer_id.get()) {
+            Some(session) => session.encrypt(zc_packet),
+            None => self.fallback.encrypt(zc_packet),
+        }
+    }
+
+    fn decrypt(&self, zc_packet: &mut ZCPacket) -> Result<(), Error> {
+        let pm_header = zc_packet.peer_manager_header().unwrap();
+        if !pm_header.is_encrypted() || !pm_header.is_session_key() {
+            return self.fallback.decrypt(zc_packet);
+        }
+
+        let from_peer_id = pm_header.from_peer_id.get();
+        let payload_len = zc_packet.payload().len();
+        if payload_len < SESSION_TAIL_SIZE {
+            return Err(Error::PacketTooShort(payload_len));
+        }
+        let tail = SessionTail::read_from_suffix(zc_packet.payload()).unwrap();
+        let session_id = tail.session_id.get();
+        let Some(session) = self.get_session(from_peer_id, session_id) else {
+            return Err(Error::SessionNotFound(from_peer_id, session_id));
+        };
+
+        let old_len = zc_packet.buf_len();
+        zc_packet.mut_inner().truncate(old_len - SESSION_TAIL_SIZE);
+        session.decrypt(zc_packet, tail.epoch.get())?;
+        zc_packet
+            .mut_peer_manager_header()
+            .unwrap()
+            .set_session_key(false);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NETWORK_KEY: [u8; 32] = [7u8; 32];
+
+    fn new_session_pair(algorithm: &str) -> (PeerSession, PeerSession) {
+        let mut a = SessionKeyExchange::new();
+        let mut b = SessionKeyExchange::new();
+        let a_pub = a.public_key();
+        let b_pub = b.public_key();
+        let sa = a
+            .derive_session(algorithm, &NETWORK_KEY, 1, 2, &b_pub)
+            .unwrap();
+        let sb = b
+            .derive_session(algorithm, &NETWORK_KEY, 2, 1, &a_pub)
+            .unwrap();
+        (sa, sb)
+    }
+
+    fn new_packet(from: PeerId, to: PeerId, text: &[u8]) -> ZCPacket {
+        let mut packet = ZCPacket::new_with_payload(text);
+        packet.fill_peer_manager_hdr(from, to, 0);
+        packet
+    }
+
+    fn new_encryptors() -> (SessionEncryptor, SessionEncryptor) {
+        let (sa, sb) = new_session_pair("aes-gcm");
+        sa.confirm();
+        let fallback = create_encryptor("aes-gcm", [1u8; 16], NETWORK_KEY);
+        let ea = SessionEncryptor::new("aes-gcm", fallback.clone());
+        let eb = SessionEncryptor::new("aes-gcm", fallback);
+        ea.add_session(2, sa);
+        eb.add_session(1, sb);
+        (ea, eb)
+    }
+
+    #[test]
+    fn session_encrypt_roundtrip() {
+        for algorithm in ["aes-gcm", "aes-256-gcm", "chacha20"] {
+            let (sa, sb) = new_session_pair(algorithm);
+            assert_eq!(sa.session_id(), sb.session_id());
+
+            let text = b"hello session";
+            let mut packet = new_packet(1, 2, text);
+            sa.encrypt(&mut packet).unwrap();
+            let hdr = packet.peer_manager_header().unwrap();
+            assert!(hdr.is_encrypted() && hdr.is_session_key());
+
+            let tail = SessionTail::read_from_suffix(packet.payload()).unwrap();
+            let old_len = packet.buf_len();
+            packet.mut_inner().truncate(old_len - SESSION_TAIL_SIZE);
+            sb.decrypt(&mut packet, tail.epoch.get()).unwrap();
+            assert_eq!(packet.payload(), text);
+            assert!(sb.is_confirmed());
+        }
+    }
+
+    #[test]
+    fn session_encryptor_replay_and_fallback() {
+        let (ea, eb) = new_encryptors();
+
+        let mut packet = new_packet(1, 2, b"data");
+        ea.encrypt(&mut packet).unwrap();
+        assert!(packet.peer_manager_header().unwrap().is_session_key());
+        let replayed = packet.clone();
+        eb.decrypt(&mut packet).unwrap();
+        assert_eq!(packet.payload(), b"data");
+        assert!(!packet.peer_manager_header().unwrap().is_session_key());
+
+        let mut replayed = replayed;
+        assert!(matches!(eb.decrypt(&mut replayed), Err(Error::Replayed(0))));
+
+        // b got a packet under the session, so it answers with it too
+        let mut packet = new_packet(2, 1, b"reply");
+        eb.encrypt(&mut packet).unwrap();
+        assert!(packet.peer_manager_header().unwrap().is_session_key());
+        ea.decrypt(&mut packet).unwrap();
+        assert_eq!(packet.payload(), b"reply");
+
+        // no session with peer 3, the network key is used
+        let mut packet = new_packet(1, 3, b"relay");
+        ea.encrypt(&mut packet).unwrap();
+        assert!(!packet.peer_manager_header().unwrap().is_session_key());
+        let mut packet = new_packet(3, 2, b"relay");
+        let fallback = create_encryptor("aes-gcm", [1u8; 16], NETWORK_KEY);
+        fallback.encrypt(&mut packet).unwrap();
+        eb.decrypt(&mut packet).unwrap();
+        assert_eq!(packet.payload(), b"relay");
+    }
+
+    #[test]
+    fn unconfirmed_session_not_used_to_send() {
+        let (_sa, sb) = new_session_pair("aes-gcm");
+        let fallback = create_encryptor("aes-gcm", [1u8; 16], NETWORK_KEY);
+        let eb = SessionEncryptor::new("aes-gcm", fallback);
+        eb.add_session(1, sb);
+        assert!(!eb.has_session(1));
+
+        let mut packet = new_packet(2, 1, b"data");
+        eb.encrypt(&mut packet).unwrap();
+        assert!(!packet.peer_manager_header().unwrap().is_session_key());
+    }
+
+    #[test]
+    fn session_rekey() {
+        let (ea, eb) = new_encryptors();
+        let send_rekey = |e: &SessionEncryptor| {
+            let session = e.get_send_session(2).unwrap();
+            let mut send = session.send.lock().unwrap();
+            send.counter = REKEY_AFTER_PACKETS;
+        };
+
+        let mut old_epoch = new_packet(1, 2, b"epoch 0");
+        ea.encrypt(&mut old_epoch).unwrap();
+        let mut stale = new_packet(1, 2, b"epoch 0");
+        ea.encrypt(&mut stale).unwrap();
+
+        send_rekey(&ea);
+        let mut packet = new_packet(1, 2, b"epoch 1");
+        ea.encrypt(&mut packet).unwrap();
+        let tail = SessionTail::read_from_suffix(packet.payload()).unwrap();
+        assert_eq!(tail.epoch.get(), 1);
+        eb.decrypt(&mut packet).unwrap();
+        assert_eq!(packet.payload(), b"epoch 1");
+
+        // packets of the previous epoch still in flight are accepted
+        eb.decrypt(&mut old_epoch).unwrap();
+        assert_eq!(old_epoch.payload(), b"epoch 0");
+
+        // two epochs later the keys of epoch 0 are gone
+        send_rekey(&ea);
+        let mut packet = new_packet(1, 2, b"epoch 2");
+        ea.encrypt(&mut packet).unwrap();
+        eb.decrypt(&mut packet).unwrap();
+        assert!(eb.decrypt(&mut stale).is_err());
+    }
+
+    #[test]
+    fn session_wrong_network_key() {
+        let mut a = SessionKeyExchange::new();
+        let mut b = SessionKeyExchange::new();
+        let a_pub = a.public_key();
+        let b_pub = b.public_key();
+        let sa = a
+            .derive_session("aes-gcm", &NETWORK_KEY, 1, 2, &b_pub)
+            .unwrap();
+        let sb = b
+            .derive_session("aes-gcm", &[8u8; 32], 2, 1, &a_pub)
+            .unwrap();
+        assert!(a
+            .derive_session("aes-gcm", &NETWORK_KEY, 1, 2, &b_pub)
+            .is_err());
+
+        let mut packet = new_packet(1, 2, b"data");
+        sa.encrypt(&mut packet).unwrap();
+        let old_len = packet.buf_len();
+        packet.mut_inner().truncate(old_len - SESSION_TAIL_SIZE);
+        assert!(sb.decrypt(&mut packet, 0).is_err());
+    }
+
+    #[test]
+    fn replay_window() {
+        let mut window = ReplayWindow::default();
+        assert!(window.update(0));
+        assert!(!window.update(0));
+        assert!(window.update(5));
+        assert!(window.update(3));
+        assert!(!window.update(3));
+        assert!(window.update(5000));
+        assert!(!window.update(5000 - REPLAY_WINDOW_SIZE));
+        assert!(window.update(5000 - REPLAY_WINDOW_SIZE + 1));
+        assert!(!window.update(5));
+    }
+}
@@ -0,0 +1,741 @@
+// Exit node policies: pick the exit node of a destination and fail over between exit nodes.
+//
+// Each policy maps destination cidrs (given inline or loaded from a country / ASN list file)
+// to exit nodes in order of preference, the most specific cidr wins. Destinations matching
+// no policy use the global exit nodes. The health of exit nodes is checked with icmp (or
+// icmpv6) echo probes sent through the overlay, an exit node is unhealthy after a few lost
+// probes and healthy again after one reply.
+//
+// Flows are sticky: a flow keeps its exit node as long as it stays healthy, even if a more
+// preferred exit node recovers, so connections through a nat on the exit node are not
+// broken. New flows always start on the most preferred healthy exit node.
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::atomic::{AtomicU16, Ordering},
+    time::{Duration, Instant},
+};
+
+use cidr::IpCidr;
+use crossbeam::atomic::AtomicCell;
+use dashmap::DashMap;
+use pnet::packet::{
+    icmp::{self, echo_reply::EchoReplyPacket, echo_request::MutableEchoRequestPacket, IcmpTypes},
+    icmpv6::{self, Icmpv6Types},
+    ip::{IpNextHeaderProtocol, IpNextHeaderProtocols},
+    ipv4::{self, Ipv4Packet, MutableIpv4Packet},
+    ipv6::{Ipv6Packet, MutableIpv6Packet},
+    tcp::TcpPacket,
+    udp::UdpPacket,
+    Packet as _,
+};
+
+use crate::{
+    common::config::ConfigLoader,
+    proto::cli::{ExitFlow as ExitFlowPb, ExitNodeStatus as ExitNodeStatusPb},
+};
+
+pub const EXIT_NODE_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+pub const EXIT_NODE_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+// icmp identifier of the probes, replies with other identifiers go to the nic as usual
+pub const EXIT_NODE_PROBE_ICMP_ID: u16 = 0x4554;
+// lost probes in a row after which an exit node is unhealthy
+const MAX_PROBE_FAILURES: u32 = 3;
+
+const EXIT_FLOW_IDLE_TIMEOUT: Duration = Duration::from_secs(180);
+// new flows are still routed but no longer pinned once the table is full
+const MAX_EXIT_FLOWS: usize = 65536;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExitFlowKey {
+    pub protocol: IpNextHeaderProtocol,
+    pub src: SocketAddr,
+    pub dst: SocketAddr,
+}
+
+fn transport_ports(protocol: IpNextHeaderProtocol, payload: &[u8]) -> (u16, u16) {
+    match protocol {
+        IpNextHeaderProtocols::Tcp => TcpPacket::new(payload)
+            .map(|p| (p.get_source(), p.get_destination()))
+            .unwrap_or_default(),
+        IpNextHeaderProtocols::Udp => UdpPacket::new(payload)
+            .map(|p| (p.get_source(), p.get_destination()))
+            .unwrap_or_default(),
+        _ => (0, 0),
+    }
+}
+
+fn protocol_name(protocol: IpNextHeaderProtocol) -> String {
+    match protocol {
+        IpNextHeaderProtocols::Tcp => "tcp".to_string(),
+        IpNextHeaderProtocols::Udp => "udp".to_string(),
+        IpNextHeaderProtocols::Icmp => "icmp".to_string(),
+        IpNextHeaderProtocols::Icmpv6 => "icmpv6".to_string(),
+        p => p.0.to_string(),
+    }
+}
+
+impl ExitFlowKey {
+    /// Flow of an ip packet, ports are 0 for protocols other than tcp and udp and for ipv4
+    /// fragments, so all fragments of a datagram take the same exit node.
+    pub fn from_ip_packet(packet: &[u8]) -> Option<Self> {
+        match packet.first()? >> 4 {
+            4 => {
+                let ipv4 = Ipv4Packet::new(packet)?;
+                let protocol = ipv4.get_next_level_protocol();
+                // only the first fragment holds the ports, later ones would read payload bytes
+                let fragmented = ipv4.get_fragment_offset() != 0
+                    || ipv4.get_flags() & ipv4::Ipv4Flags::MoreFragments != 0;
+                let (src_port, dst_port) = if fragmented {
+                    (0, 0)
+                } else {
+                    transport_ports(protocol, ipv4.payload())
+                };
+                Some(Self {
+                    protocol,
+                    src: SocketAddr::new(ipv4.get_source().into(), src_port),
+                    dst: SocketAddr::new(ipv4.get_destination().into(), dst_port),
+                })
+            }
+            6 => {
+                let ipv6 = Ipv6Packet::new(packet)?;
+                let protocol = ipv6.get_next_header();
+                let (src_port, dst_port) = transport_ports(protocol, ipv6.payload());
+                Some(Self {
+                    protocol,
+                    src: SocketAddr::new(ipv6.get_source().into(), src_port),
+                    dst: SocketAddr::new(ipv6.get_destination().into(), dst_port),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ExitFlowEntry {
+    exit_node: IpAddr,
+    last_active: AtomicCell<Instant>,
+}
+
+#[derive(Debug, Clone)]
+struct ExitNodeHealth {
+    reachable: bool,
+    healthy: bool,
+    rtt: Option<Duration>,
+    consecutive_failures: u32,
+}
+
+impl Default for ExitNodeHealth {
+    fn default() -> Self {
+        // exit nodes are usable until the probes say otherwise
+        Self {
+            reachable: true,
+            healthy: true,
+            rtt: None,
+            consecutive_failures: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ExitNodePolicy {
+    cidrs: Vec<IpCidr>,
+    exit_nodes: Vec<IpAddr>,
+}
+
+// longest prefix match over the cidrs of all policies. for every prefix length in use, longest
+// first, the networks of that length map to the index of the first policy listing them, so a
+// lookup costs one hash probe per distinct prefix length instead of a scan of every cidr.
+#[derive(Debug, Default)]
+struct PolicyTable {
+    v4: Vec<(u8, HashMap<u32, usize>)>,
+    v6: Vec<(u8, HashMap<u128, usize>)>,
+}
+
+fn insert_prefix<K: std::hash::Hash + Eq>(
+    table: &mut Vec<(u8, HashMap<K, usize>)>,
+    len: u8,
+    network: K,
+    policy: usize,
+) {
+    let pos = match table.iter().position(|(l, _)| *l == len) {
+        Some(pos) => pos,
+        None => {
+            table.push((len, HashMap::new()));
+            table.len() - 1
+        }
+    };
+    table[pos].1.entry(network).or_insert(policy);
+}
+
+impl PolicyTable {
+    fn new(policies: &[ExitNodePolicy]) -> Self {
+        let mut table = Self::default();
+        for (idx, policy) in policies.iter().enumerate() {
+            for cidr in policy.cidrs.iter() {
+                match cidr {
+                    IpCidr::V4(c) => insert_prefix(
+                        &mut table.v4,
+                        c.network_length(),
+                        u32::from(c.first_address()),
+                        idx,
+                    ),
+                    IpCidr::V6(c) => insert_prefix(
+                        &mut table.v6,
+                        c.network_length(),
+                        u128::from(c.first_address()),
+                        idx,
+                    ),
+                }
+            }
+        }
+        table.v4.sort_by_key(|(len, _)| std::cmp::Reverse(*len));
+        table.v6.sort_by_key(|(len, _)| std::cmp::Reverse(*len));
+        table
+    }
+
+    // index of the policy with the most specific cidr containing dst
+    fn lookup(&self, dst: &IpAddr) -> Option<usize> {
+        match dst {
+            IpAddr::V4(ip) => {
+                let ip = u32::from(*ip);
+                self.v4.iter().find_map(|(len, networks)| {
+                    let mask = u32::MAX.checked_shl(32 - *len as u32).unwrap_or(0);
+                    networks.get(&(ip & mask)).copied()
+                })
+            }
+            IpAddr::V6(ip) => {
+                let ip = u128::from(*ip);
+                self.v6.iter().find_map(|(len, networks)| {
+                    let mask = u128::MAX.checked_shl(128 - *len as u32).unwrap_or(0);
+                    networks.get(&(ip & mask)).copied()
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ExitNodeSelector {
+    policies: Vec<ExitNodePolicy>,
+    policy_table: PolicyTable,
+    default_exit_nodes: Vec<IpAddr>,
+
+    health: DashMap<IpAddr, ExitNodeHealth>,
+    flows: DashMap<ExitFlowKey, ExitFlowEntry>,
+
+    // (exit node, sequence) of the probes waiting for a reply
+    pending_probes: DashMap<(IpAddr, u16), Instant>,
+    next_probe_seq: AtomicU16,
+}
+
+impl ExitNodeSelector {
+    pub fn new(policies: Vec<(Vec<IpCidr>, Vec<IpAddr>)>, default_exit_nodes: Vec<IpAddr>) -> Self {
+        let policies = policies
+            .into_iter()
+            .map(|(cidrs, exit_nodes)| ExitNodePolicy { cidrs, exit_nodes })
+            .collect::<Vec<_>>();
+        Self {
+            policy_table: PolicyTable::new(&policies),
+            policies,
+            default_exit_nodes,
+            ..Default::default()
+        }
+    }
+
+    /// Build from the exit nodes and exit node policies in config. A policy whose cidr file
+    /// cannot be loaded is skipped, so its destinations use the global exit nodes.
+    pub fn from_config(config: &dyn ConfigLoader) -> Self {
+        let mut policies = vec![];
+        for policy in config.get_exit_node_policies() {
+            match policy.load_cidrs() {
+                Ok(cidrs) => policies.push((cidrs, policy.exit_nodes)),
+                Err(e) => tracing::error!(?policy, "skip exit node policy: {:?}", e),
+            }
+        }
+        Self::new(policies, config.get_exit_nodes())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.default_exit_nodes.is_empty() && self.policies.iter().all(|p| p.exit_nodes.is_empty())
+    }
+
+    /// Every exit node of the global list and the policies, without duplicates.
+    pub fn list_exit_node_addrs(&self) -> Vec<IpAddr> {
+        let mut addrs: Vec<IpAddr> = vec![];
+        for addr in self
+            .default_exit_nodes
+            .iter()
+            .chain(self.policies.iter().flat_map(|p| p.exit_nodes.iter()))
+        {
+            if !addrs.contains(addr) {
+                addrs.push(*addr);
+            }
+        }
+        addrs
+    }
+
+    /// Exit nodes for a destination in order of preference, only those of the same address
+    /// family as the destination.
+    pub fn candidates<'a>(&'a self, dst: &IpAddr) -> impl Iterator<Item = IpAddr> + 'a {
+        let is_ipv4 = dst.is_ipv4();
+        self.policy_table
+            .lookup(dst)
+            .map(|idx| &self.policies[idx].exit_nodes)
+            .unwrap_or(&self.default_exit_nodes)
+            .iter()
+            .filter(move |addr| addr.is_ipv4() == is_ipv4)
+            .copied()
+    }
+
+    fn is_healthy(&self, exit_node: &IpAddr) -> bool {
+        self.health
+            .get(exit_node)
+            .map(|h| h.healthy)
+            .unwrap_or(true)
+    }
+
+    /// Exit node the flow is pinned to if it is still healthy, refreshing the flow. Lets the
+    /// packets of an existing flow skip the policy lookup.
+    pub fn sticky_exit_node(&self, flow: &ExitFlowKey) -> Option<IpAddr> {
+        let entry = self.flows.get(flow)?;
+        if !self.is_healthy(&entry.exit_node) {
+            return None;
+        }
+        entry.last_active.store(Instant::now());
+        Some(entry.exit_node)
+    }
+
+    /// Pick the exit node of a packet, `reachable` are the candidates of the destination
+    /// that have a route.
+    /// An existing flow keeps its exit node while it is healthy. If no candidate is healthy
+    /// the first reachable one is used, dropping the traffic would not help either.
+    pub fn select(
+        &self,
+        dst: &IpAddr,
+        flow: Option<&ExitFlowKey>,
+        reachable: &[IpAddr],
+    ) -> Option<IpAddr> {
+        if let Some(entry) = flow.and_then(|f| self.flows.get(f)) {
+            if reachable.contains(&entry.exit_node) && self.is_healthy(&entry.exit_node) {
+                entry.last_active.store(Instant::now());
+                return Some(entry.exit_node);
+            }
+        }
+
+        let exit_node = self
+            .candidates(dst)
+            .filter(|c| reachable.contains(c))
+            .find(|c| self.is_healthy(c))
+            .or_else(|| reachable.first().copied())?;
+
+        if let Some(flow) = flow {
+            if self.flows.len() < MAX_EXIT_FLOWS || self.flows.contains_key(flow) {
+                let prev = self.flows.insert(
+                    *flow,
+                    ExitFlowEntry {
+                        exit_node,
+                        last_active: AtomicCell::new(Instant::now()),
+                    },
+                );
+                if let Some(prev) = prev.filter(|p| p.exit_node != exit_node) {
+                    tracing::info!(
+                        ?flow,
+                        from = ?prev.exit_node,
+                        to = ?exit_node,
+                        "exit flow failed over"
+                    );
+                }
+            }
+        }
+        Some(exit_node)
+    }
+
+    pub fn set_reachable(&self, exit_node: IpAddr, reachable: bool) {
+        self.health.entry(exit_node).or_default().reachable = reachable;
+    }
+
+    /// Register a probe to the exit node, returns the icmp sequence number to use.
+    pub fn start_probe(&self, exit_node: IpAddr) -> u16 {
+        let seq = self.next_probe_seq.fetch_add(1, Ordering::Relaxed);
+        self.pending_probes.insert((exit_node, seq), Instant::now());
+        seq
+    }
+
+    /// Handle a probe reply, returns false if no such probe is pending.
+    pub fn finish_probe(&self, exit_node: IpAddr, seq: u16) -> bool {
+        let Some((_, sent)) = self.pending_probes.remove(&(exit_node, seq)) else {
+            return false;
+        };
+        let mut health = self.health.entry(exit_node).or_default();
+        if !health.healthy {
+            tracing::info!(?exit_node, "exit node is healthy again");
+        }
+        health.reachable = true;
+        health.healthy = true;
+        health.rtt = Some(sent.elapsed());
+        health.consecutive_failures = 0;
+        true
+    }
+
+    /// Count the probes without a reply within the timeout as lost.
+    pub fn expire_probes(&self, timeout: Duration) {
+        let mut lost = vec![];
+        self.pending_probes.retain(|(exit_node, _), sent| {
+            if sent.elapsed() < timeout {
+                return true;
+            }
+            lost.push(*exit_node);
+            false
+        });
+        for exit_node in lost {
+            let mut health = self.health.entry(exit_node).or_default();
+            health.consecutive_failures += 1;
+            if health.healthy && health.consecutive_failures >= MAX_PROBE_FAILURES {
+                tracing::warn!(?exit_node, "exit node is unhealthy, failing over");
+                health.healthy = false;
+            }
+        }
+    }
+
+    pub fn expire_flows(&self) {
+        self.flows
+            .retain(|_, f| f.last_active.load().elapsed() < EXIT_FLOW_IDLE_TIMEOUT);
+    }
+
+    pub fn list_exit_nodes(&self) -> Vec<ExitNodeStatusPb> {
+        self.list_exit_node_addrs()
+            .into_iter()
+            .map(|addr| {
+                let health = self
+                    .health
+                    .get(&addr)
+                    .map(|h| h.clone())
+                    .unwrap_or_default();
+                ExitNodeStatusPb {
+                    exit_node: addr.to_string(),
+                    reachable: health.reachable,
+                    healthy: health.healthy,
+                    rtt_us: health.rtt.map(|rtt| rtt.as_micros() as u32),
+                    consecutive_failures: health.consecutive_failures,
+                }
+            })
+            .collect()
+    }
+
+    pub fn flow_count(&self) -> usize {
+        self.flows.len()
+    }
+
+    /// At most `limit` of the pinned flows, in no particular order.
+    pub fn list_flows(&self, limit: usize) -> Vec<ExitFlowPb> {
+        self.flows
+            .iter()
+            .take(limit)
+            .map(|f| ExitFlowPb {
+                protocol: protocol_name(f.key().protocol),
+                src: f.key().src.to_string(),
+                dst: f.key().dst.to_string(),
+                exit_node: f.exit_node.to_string(),
+                idle_ms: f.last_active.load().elapsed().as_millis() as u64,
+            })
+            .collect()
+    }
+}
+
+const ICMP_ECHO_LEN: usize = 8;
+
+/// Icmp echo request from `src` to the exit node, as an ip packet. None if the addresses are
+/// of different families.
+pub fn build_probe_packet(src: IpAddr, exit_node: IpAddr, seq: u16) -> Option<Vec<u8>> {
+    match (src, exit_node) {
+        (IpAddr::V4(src), IpAddr::V4(exit_node)) => {
+            Some(build_probe_packet_v4(src, exit_node, seq))
+        }
+        (IpAddr::V6(src), IpAddr::V6(exit_node)) => {
+            Some(build_probe_packet_v6(src, exit_node, seq))
+        }
+        _ => None,
+    }
+}
+
+fn build_probe_packet_v4(src: Ipv4Addr, exit_node: Ipv4Addr, seq: u16) -> Vec<u8> {
+    let mut buf = vec![0u8; 20 + ICMP_ECHO_LEN];
+
+    let mut echo = MutableEchoRequestPacket::new(&mut buf[20..]).unwrap();
+    echo.set_icmp_type(IcmpTypes::EchoRequest);
+    echo.set_identifier(EXIT_NODE_PROBE_ICMP_ID);
+    echo.set_sequence_number(seq);
+    let checksum = icmp::checksum(&icmp::IcmpPacket::new(echo.packet()).unwrap());
+    echo.set_checksum(checksum);
+
+    let mut ipv4_packet = MutableIpv4Packet::new(&mut buf).unwrap();
+    ipv4_packet.set_version(4);
+    ipv4_packet.set_header_length(5);
+    ipv4_packet.set_total_length((20 + ICMP_ECHO_LEN) as u16);
+    ipv4_packet.set_flags(ipv4::Ipv4Flags::DontFragment);
+    ipv4_packet.set_ttl(32);
+    ipv4_packet.set_next_level_protocol(IpNextHeaderProtocols::Icmp);
+    ipv4_packet.set_source(src);
+    ipv4_packet.set_destination(exit_node);
+    ipv4_packet.set_checksum(ipv4::checksum(&ipv4_packet.to_immutable()));
+
+    buf
+}
+
+fn build_probe_packet_v6(src: Ipv6Addr, exit_node: Ipv6Addr, seq: u16) -> Vec<u8> {
+    let mut buf = vec![0u8; 40 + ICMP_ECHO_LEN];
+
+    let mut echo = icmpv6::echo_request::MutableEchoRequestPacket::new(&mut buf[40..]).unwrap();
+    echo.set_icmpv6_type(Icmpv6Types::EchoRequest);
+    echo.set_identifier(EXIT_NODE_PROBE_ICMP_ID);
+    echo.set_sequence_number(seq);
+    let checksum = icmpv6::checksum(
+        &icmpv6::Icmpv6Packet::new(echo.packet()).unwrap(),
+        &src,
+        &exit_node,
+    );
+    echo.set_checksum(checksum);
+
+    let mut ipv6_packet = MutableIpv6Packet::new(&mut buf).unwrap();
+    ipv6_packet.set_version(6);
+    ipv6_packet.set_payload_length(ICMP_ECHO_LEN as u16);
+    ipv6_packet.set_next_header(IpNextHeaderProtocols::Icmpv6);
+    ipv6_packet.set_hop_limit(32);
+    ipv6_packet.set_source(src);
+    ipv6_packet.set_destination(exit_node);
+
+    buf
+}
+
+/// Source address and sequence number of a probe reply, None for any other packet.
+pub fn parse_probe_reply(packet: &[u8]) -> Option<(IpAddr, u16)> {
+    if packet.first()? >> 4 == 6 {
+        return parse_probe_reply_v6(packet);
+    }
+    let ipv4 = Ipv4Packet::new(packet)?;
+    if ipv4.get_version() != 4 || ipv4.get_next_level_protocol() != IpNextHeaderProtocols::Icmp {
+        return None;
+    }
+    let reply = EchoReplyPacket::new(ipv4.payload())?;
+    if reply.get_icmp_type() != IcmpTypes::EchoReply
+        || reply.get_identifier() != EXIT_NODE_PROBE_ICMP_ID
+    {
+        return None;
+    }
+    Some((ipv4.get_source().into(), reply.get_sequence_number()))
+}
+
+fn parse_probe_reply_v6(packet: &[u8]) -> Option<(IpAddr, u16)> {
+    let ipv6 = Ipv6Packet::new(packet)?;
+    if ipv6.get_next_header() != IpNextHeaderProtocols::Icmpv6 {
+        return None;
+    }
+    let reply = icmpv6::echo_reply::EchoReplyPacket::new(ipv6.payload())?;
+    if reply.get_icmpv6_type() != Icmpv6Types::EchoReply
+        || reply.get_identifier() != EXIT_NODE_PROBE_ICMP_ID
+    {
+        return None;
+    }
+    Some((ipv6.get_source().into(), reply.get_sequence_number()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn flow(dst: &str, src_port: u16) -> ExitFlowKey {
+        ExitFlowKey {
+            protocol: IpNextHeaderProtocols::Tcp,
+            src: SocketAddr::new(ip("10.126.126.2"), src_port),
+            dst: SocketAddr::new(ip(dst), 443),
+        }
+    }
+
+    fn selector() -> ExitNodeSelector {
+        ExitNodeSelector::new(
+            vec![
+                (
+                    vec!["1.0.0.0/8".parse().unwrap()],
+                    vec![ip("10.126.126.10"), ip("10.126.126.11")],
+                ),
+                (
+                    vec!["1.1.0.0/16".parse().unwrap()],
+                    vec![ip("10.126.126.12")],
+                ),
+            ],
+            vec![ip("10.126.126.1"), ip("fd00::1")],
+        )
+    }
+
+    fn candidates(s: &ExitNodeSelector, dst: &str) -> Vec<IpAddr> {
+        s.candidates(&ip(dst)).collect()
+    }
+
+    #[test]
+    fn candidates_use_most_specific_policy() {
+        let s = selector();
+        assert_eq!(vec![ip("10.126.126.12")], candidates(&s, "1.1.1.1"));
+        assert_eq!(
+            vec![ip("10.126.126.10"), ip("10.126.126.11")],
+            candidates(&s, "1.2.3.4")
+        );
+        assert_eq!(vec![ip("10.126.126.1")], candidates(&s, "8.8.8.8"));
+        assert_eq!(vec![ip("fd00::1")], candidates(&s, "2001:db8::1"));
+        assert_eq!(5, s.list_exit_node_addrs().len());
+    }
+
+    #[test]
+    fn candidates_longest_prefix_match() {
+        let s = ExitNodeSelector::new(
+            vec![
+                (
+                    vec![
+                        "0.0.0.0/0".parse().unwrap(),
+                        "2001:db8::/32".parse().unwrap(),
+                    ],
+                    vec![ip("10.126.126.10"), ip("fd00::10")],
+                ),
+                (
+                    vec!["1.2.3.0/24".parse().unwrap(), "9.9.9.9/32".parse().unwrap()],
+                    vec![ip("10.126.126.11")],
+                ),
+                // same /24 as the second one, the first policy listing it wins
+                (
+                    vec![
+                        "1.2.3.0/24".parse().unwrap(),
+                        "2001:db8:1::/48".parse().unwrap(),
+                    ],
+                    vec![ip("10.126.126.12"), ip("fd00::12")],
+                ),
+            ],
+            vec![ip("fd00::1")],
+        );
+        assert_eq!(vec![ip("10.126.126.11")], candidates(&s, "1.2.3.4"));
+        assert_eq!(vec![ip("10.126.126.11")], candidates(&s, "9.9.9.9"));
+        assert_eq!(vec![ip("10.126.126.10")], candidates(&s, "9.9.9.8"));
+        assert_eq!(vec![ip("fd00::12")], candidates(&s, "2001:db8:1::1"));
+        assert_eq!(vec![ip("fd00::10")], candidates(&s, "2001:db8:2::1"));
+        assert_eq!(vec![ip("fd00::1")], candidates(&s, "2001:db9::1"));
+    }
+
+    #[test]
+    fn flows_are_sticky_and_fail_over() {
+        let s = selector();
+        let reachable = [ip("10.126.126.10"), ip("10.126.126.11")];
+        let dst = ip("1.2.3.4");
+        let f1 = flow("1.2.3.4", 1000);
+
+        assert_eq!(Some(reachable[0]), s.select(&dst, Some(&f1), &reachable));
+
+        // lose probes of the preferred exit node, the flow fails over
+        for _ in 0..MAX_PROBE_FAILURES {
+            s.start_probe(reachable[0]);
+        }
+        s.expire_probes(Duration::ZERO);
+        assert_eq!(Some(reachable[1]), s.select(&dst, Some(&f1), &reachable));
+
+        // the preferred exit node recovers, the existing flow stays, new flows use it
+        let seq = s.start_probe(reachable[0]);
+        assert!(s.finish_probe(reachable[0], seq));
+        assert!(!s.finish_probe(reachable[0], seq));
+        assert_eq!(Some(reachable[1]), s.select(&dst, Some(&f1), &reachable));
+        let f2 = flow("1.2.3.4", 1001);
+        assert_eq!(Some(reachable[0]), s.select(&dst, Some(&f2), &reachable));
+        assert_eq!(2, s.list_flows(usize::MAX).len());
+        assert_eq!(1, s.list_flows(1).len());
+        assert_eq!(2, s.flow_count());
+
+        // the sticky lookup follows the flow until its exit node is unhealthy
+        assert_eq!(Some(reachable[1]), s.sticky_exit_node(&f1));
+        assert_eq!(None, s.sticky_exit_node(&flow("1.2.3.4", 1002)));
+
+        // unreachable exit nodes are skipped
+        assert_eq!(
+            Some(reachable[1]),
+            s.select(&dst, Some(&f2), &reachable[1..])
+        );
+        assert_eq!(None, s.select(&dst, None, &[]));
+    }
+
+    #[test]
+    fn probe_packet_round_trip() {
+        let packet = build_probe_packet(ip("10.126.126.2"), ip("10.126.126.1"), 7).unwrap();
+        let key = ExitFlowKey::from_ip_packet(&packet).unwrap();
+        assert_eq!(IpNextHeaderProtocols::Icmp, key.protocol);
+        assert_eq!(ip("10.126.126.1"), key.dst.ip());
+
+        // requests are not mistaken for replies
+        assert_eq!(None, parse_probe_reply(&packet));
+
+        let mut reply = packet.clone();
+        reply[20] = IcmpTypes::EchoReply.0;
+        let mut ipv4_packet = MutableIpv4Packet::new(&mut reply).unwrap();
+        ipv4_packet.set_source("10.126.126.1".parse().unwrap());
+        assert_eq!(Some((ip("10.126.126.1"), 7)), parse_probe_reply(&reply));
+
+        assert_eq!(
+            None,
+            build_probe_packet(ip("10.126.126.2"), ip("fd00::1"), 7)
+        );
+    }
+
+    #[test]
+    fn probe_packet_round_trip_v6() {
+        let packet = build_probe_packet(ip("fd00::2"), ip("fd00::1"), 9).unwrap();
+        let key = ExitFlowKey::from_ip_packet(&packet).unwrap();
+        assert_eq!(IpNextHeaderProtocols::Icmpv6, key.protocol);
+        assert_eq!(ip("fd00::1"), key.dst.ip());
+        let echo = icmpv6::Icmpv6Packet::new(&packet[40..]).unwrap();
+        assert_eq!(
+            icmpv6::checksum(
+                &echo,
+                &"fd00::2".parse().unwrap(),
+                &"fd00::1".parse().unwrap()
+            ),
+            echo.get_checksum()
+        );
+
+        assert_eq!(None, parse_probe_reply(&packet));
+
+        let mut reply = packet.clone();
+        reply[40] = Icmpv6Types::EchoReply.0;
+        let mut ipv6_packet = MutableIpv6Packet::new(&mut reply).unwrap();
+        ipv6_packet.set_source("fd00::1".parse().unwrap());
+        assert_eq!(Some((ip("fd00::1"), 9)), parse_probe_reply(&reply));
+    }
+
+    #[test]
+    fn ipv4_fragments_have_no_ports() {
+        let mut buf = vec![0u8; 20 + 8];
+        let mut udp = pnet::packet::udp::MutableUdpPacket::new(&mut buf[20..]).unwrap();
+        udp.set_source(1234);
+        udp.set_destination(53);
+        let mut ipv4_packet = MutableIpv4Packet::new(&mut buf).unwrap();
+        ipv4_packet.set_version(4);
+        ipv4_packet.set_header_length(5);
+        ipv4_packet.set_total_length(28);
+        ipv4_packet.set_next_level_protocol(IpNextHeaderProtocols::Udp);
+        ipv4_packet.set_source("10.126.126.2".parse().unwrap());
+        ipv4_packet.set_destination("1.1.1.1".parse().unwrap());
+
+        let key = ExitFlowKey::from_ip_packet(&buf).unwrap();
+        assert_eq!((1234, 53), (key.src.port(), key.dst.port()));
+
+        // first fragment and a later one map to the same flow
+        let mut ipv4_packet = MutableIpv4Packet::new(&mut buf).unwrap();
+        ipv4_packet.set_flags(ipv4::Ipv4Flags::MoreFragments);
+        let first = ExitFlowKey::from_ip_packet(&buf).unwrap();
+        assert_eq!((0, 0), (first.src.port(), first.dst.port()));
+        let mut ipv4_packet = MutableIpv4Packet::new(&mut buf).unwrap();
+        ipv4_packet.set_flags(0);
+        ipv4_packet.set_fragment_offset(185);
+        assert_eq!(first, ExitFlowKey::from_ip_packet(&buf).unwrap());
+    }
+}
@@ -1,7 +1,9 @@
 mod graph_algo;
 
 pub mod acl_filter;
+pub mod exit_node_policy;
 pub mod peer;
+pub mod peer_bonding;
 // pub mod peer_conn;
 pub mod peer_conn;
 pub mod peer_conn_ping;
@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 use crossbeam::atomic::AtomicCell;
 use dashmap::{DashMap, DashSet};
@@ -8,6 +11,7 @@ use tokio::{select, sync::mpsc};
 use tracing::Instrument;
 
 use super::{
+    peer_bonding::{BondingConfig, BondingScheduler, PathStats},
     peer_conn::{PeerConn, PeerConnId},
     PacketRecvChan,
 };
@@ -18,7 +22,7 @@ use crate::{
         global_ctx::{ArcGlobalCtx, GlobalCtxEvent},
         PeerId,
     },
-    tunnel::packet_def::ZCPacket,
+    tunnel::packet_def::{PacketType, ZCPacket},
 };
 
 type ArcPeerConn = Arc<PeerConn>;
@@ -38,6 +42,10 @@ pub struct Peer {
 
     default_conn_id: Arc<AtomicCell<PeerConnId>>,
     default_conn_id_clear_task: ScopedTask<()>,
+
+    bonding_enabled: Arc<AtomicBool>,
+    bonding: Arc<std::sync::Mutex<BondingScheduler>>,
+    bonding_update_task: ScopedTask<()>,
 }
 
 impl Peer {
@@ -103,6 +111,16 @@ impl Peer {
             }
         }));
 
+        let bonding_enabled = Arc::new(AtomicBool::new(false));
+        let bonding = Arc::new(std::sync::Mutex::new(BondingScheduler::new()));
+        let bonding_update_task = ScopedTask::from(tokio::spawn(Self::run_bonding_update(
+            peer_node_id,
+            conns.clone(),
+            global_ctx.clone(),
+            bonding_enabled.clone(),
+            bonding.clone(),
+        )));
+
         Peer {
             peer_node_id,
             conns: conns.clone(),
@@ -115,6 +133,49 @@ impl Peer {
             shutdown_notifier,
             default_conn_id,
             default_conn_id_clear_task,
+
+            bonding_enabled,
+            bonding,
+            bonding_update_task,
+        }
+    }
+
+    async fn run_bonding_update(
+        peer_node_id: PeerId,
+        conns: ConnMap,
+        global_ctx: ArcGlobalCtx,
+        bonding_enabled: Arc<AtomicBool>,
+        bonding: Arc<std::sync::Mutex<BondingScheduler>>,
+    ) {
+        let config = BondingConfig::default();
+        loop {
+            let enabled = global_ctx.get_flags().enable_multipath_bonding;
+            bonding_enabled.store(enabled, Ordering::Relaxed);
+
+            if enabled {
+                let stats = conns
+                    .iter()
+                    .map(|conn| PathStats {
+                        conn_id: conn.get_conn_id(),
+                        latency_us: conn.get_stats().latency_us,
+                        jitter_us: conn.get_jitter_us(),
+                        loss_rate: conn.get_loss_rate(),
+                        closed: conn.is_closed(),
+                    })
+                    .collect::<Vec<_>>();
+                let mut bonding = bonding.lock().unwrap();
+                if bonding.update(&stats, &config) {
+                    tracing::info!(
+                        ?peer_node_id,
+                        conns = ?bonding.conn_ids(),
+                        "multipath bonding paths changed"
+                    );
+                }
+            } else {
+                bonding.lock().unwrap().clear();
+            }
+
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
         }
     }
 
@@ -162,7 +223,48 @@ impl Peer {
             .map(|conn| conn.clone())
     }
 
+    fn is_bondable(msg: &ZCPacket) -> bool {
+        // control packets stay on the default conn so that their ordering is kept
+        let Some(hdr) = msg.peer_manager_header() else {
+            return false;
+        };
+        hdr.packet_type == PacketType::Data as u8
+            || hdr.packet_type == PacketType::KcpSrc as u8
+            || hdr.packet_type == PacketType::KcpDst as u8
+    }
+
+    fn select_bonded_conn(&self) -> Option<ArcPeerConn> {
+        let mut bonding = self.bonding.lock().unwrap();
+        while let Some(conn_id) = bonding.next() {
+            match self.conns.get(&conn_id) {
+                Some(conn) if !conn.is_closed() => return Some(conn.clone()),
+                _ => bonding.remove(&conn_id),
+            }
+        }
+        None
+    }
+
     pub async fn send_msg(&self, msg: ZCPacket) -> Result<(), Error> {
+        if self.bonding_enabled.load(Ordering::Relaxed) && Self::is_bondable(&msg) {
+            if let Some(conn) = self.select_bonded_conn() {
+                // keep a copy so the packet can be resent on the default conn
+                let Err(e) = conn.send_msg(msg.clone()).await else {
+                    return Ok(());
+                };
+                let conn_id = conn.get_conn_id();
+                tracing::warn!(
+                    peer_node_id = ?self.peer_node_id,
+                    ?conn_id,
+                    ?e,
+                    "send on bonded conn failed, drop it from the bond"
+                );
+                self.bonding.lock().unwrap().remove(&conn_id);
+                if self.default_conn_id.load() == conn_id {
+                    return Err(e);
+                }
+            }
+        }
+
         let Some(conn) = self.select_conn().await else {
             return Err(Error::PeerNoConnectionError(self.peer_node_id));
         };
@@ -217,6 +319,10 @@ impl Peer {
     pub fn get_default_conn_id(&self) -> PeerConnId {
         self.default_conn_id.load()
     }
+
+    pub fn get_bonded_conn_ids(&self) -> Vec<PeerConnId> {
+        self.bonding.lock().unwrap().conn_ids()
+    }
 }
 
 // pritn on drop
@@ -250,9 +356,9 @@ mod tests {
 
         let (local_tunnel, remote_tunnel) = create_ring_tunnel_pair();
         let mut local_peer_conn =
-            PeerConn::new(local_peer.peer_node_id, global_ctx.clone(), local_tunnel);
+            PeerConn::new(local_peer.peer_node_id, global_ctx.clone(), local_tunnel).unwrap();
         let mut remote_peer_conn =
-            PeerConn::new(remote_peer.peer_node_id, global_ctx.clone(), remote_tunnel);
+            PeerConn::new(remote_peer.peer_node_id, global_ctx.clone(), remote_tunnel).unwrap();
 
         assert!(!local_peer_conn.handshake_done());
         assert!(!remote_peer_conn.handshake_done());
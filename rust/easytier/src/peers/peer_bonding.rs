@@ -0,0 +1,230 @@
+// Multipath bonding: spread data packets of one peer across all of its healthy conns.
+//
+// Paths are scheduled with smooth weighted round-robin. The weight of a path is derived
+// from the latency and loss rate measured by peer_conn_ping, so a fast and clean link
+// carries more packets than a slow or lossy one. Paths that are much slower than the
+// best path would reorder packets beyond what the receiver tolerates, and paths with
+// too much loss only waste bandwidth, so both are removed from the bond until they
+// recover. The reordering tolerance is per path: the jitter of a path eats into the
+// allowed latency gap, as its packets may arrive that much later than its average.
+
+use super::peer_conn::PeerConnId;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathStats {
+    pub conn_id: PeerConnId,
+    pub latency_us: u64,
+    pub jitter_us: u64,
+    // 0.0 ~ 1.0
+    pub loss_rate: f32,
+    pub closed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BondingConfig {
+    // paths with a higher loss rate are considered degraded
+    pub max_loss_rate: f32,
+    // max latency difference to the best path, larger gaps reorder too many packets.
+    // each path gets this budget minus twice its jitter
+    pub max_reorder_us: u64,
+}
+
+impl Default for BondingConfig {
+    fn default() -> Self {
+        Self {
+            max_loss_rate: 0.2,
+            max_reorder_us: 50_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct BondedPath {
+    conn_id: PeerConnId,
+    weight: i64,
+    current: i64,
+}
+
+#[derive(Debug, Default)]
+pub struct BondingScheduler {
+    paths: Vec<BondedPath>,
+}
+
+impl BondingScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn path_weight(stats: &PathStats) -> i64 {
+        // latency in ms, at least 1ms so that local links do not dominate
+        let latency_ms = (stats.latency_us as f64 / 1000.0).max(1.0);
+        let weight = 1000.0 / latency_ms * (1.0 - stats.loss_rate as f64);
+        (weight as i64).max(1)
+    }
+
+    fn reorder_tolerance_us(stats: &PathStats, config: &BondingConfig) -> u64 {
+        config
+            .max_reorder_us
+            .saturating_sub(stats.jitter_us.saturating_mul(2))
+    }
+
+    fn is_healthy(stats: &PathStats, best_latency_us: u64, config: &BondingConfig) -> bool {
+        // latency 0 means the conn has not been measured yet
+        !stats.closed
+            && stats.latency_us > 0
+            && stats.loss_rate <= config.max_loss_rate
+            && stats.latency_us
+                <= best_latency_us.saturating_add(Self::reorder_tolerance_us(stats, config))
+    }
+
+    /// Rebuild the bond from the latest path stats. Returns true if the set of bonded
+    /// paths changed. Fewer than two healthy paths leave the bond empty, the caller
+    /// should fall back to the default conn in that case.
+    pub fn update(&mut self, stats: &[PathStats], config: &BondingConfig) -> bool {
+        let best_latency_us = stats
+            .iter()
+            .filter(|s| !s.closed && s.latency_us > 0 && s.loss_rate <= config.max_loss_rate)
+            .map(|s| s.latency_us)
+            .min()
+            .unwrap_or(0);
+
+        let mut paths: Vec<BondedPath> = stats
+            .iter()
+            .filter(|s| Self::is_healthy(s, best_latency_us, config))
+            .map(|s| BondedPath {
+                conn_id: s.conn_id,
+                weight: Self::path_weight(s),
+                // keep the scheduling state of existing paths to avoid bursts
+                current: self
+                    .paths
+                    .iter()
+                    .find(|p| p.conn_id == s.conn_id)
+                    .map(|p| p.current)
+                    .unwrap_or(0),
+            })
+            .collect();
+
+        if paths.len() < 2 {
+            paths.clear();
+        }
+
+        let changed = self.conn_ids() != paths.iter().map(|p| p.conn_id).collect::<Vec<_>>();
+        self.paths = paths;
+        changed
+    }
+
+    /// Pick the conn for the next packet.
+    pub fn next(&mut self) -> Option<PeerConnId> {
+        let total: i64 = self.paths.iter().map(|p| p.weight).sum();
+        let mut selected: Option<&mut BondedPath> = None;
+        for path in self.paths.iter_mut() {
+            path.current += path.weight;
+            if selected.as_ref().map_or(true, |s| path.current > s.current) {
+                selected = Some(path);
+            }
+        }
+        let selected = selected?;
+        selected.current -= total;
+        Some(selected.conn_id)
+    }
+
+    /// Drop a path immediately, e.g. when sending on it failed.
+    pub fn remove(&mut self, conn_id: &PeerConnId) {
+        self.paths.retain(|p| p.conn_id != *conn_id);
+        if self.paths.len() < 2 {
+            self.paths.clear();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.paths.clear();
+    }
+
+    pub fn conn_ids(&self) -> Vec<PeerConnId> {
+        self.paths.iter().map(|p| p.conn_id).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(latency_ms: u64, loss_rate: f32) -> PathStats {
+        PathStats {
+            conn_id: PeerConnId::new_v4(),
+            latency_us: latency_ms * 1000,
+            jitter_us: 0,
+            loss_rate,
+            closed: false,
+        }
+    }
+
+    #[test]
+    fn weighted_round_robin() {
+        let fast = path(10, 0.0);
+        let slow = path(40, 0.0);
+        let mut scheduler = BondingScheduler::new();
+        assert!(scheduler.update(&[fast, slow], &BondingConfig::default()));
+
+        let mut fast_count = 0;
+        let mut slow_count = 0;
+        for _ in 0..250 {
+            let id = scheduler.next().unwrap();
+            if id == fast.conn_id {
+                fast_count += 1;
+            } else if id == slow.conn_id {
+                slow_count += 1;
+            }
+        }
+        assert_eq!(fast_count, 200);
+        assert_eq!(slow_count, 50);
+
+        // unchanged stats do not change the bond
+        assert!(!scheduler.update(&[fast, slow], &BondingConfig::default()));
+    }
+
+    #[test]
+    fn degraded_paths_are_removed() {
+        let config = BondingConfig::default();
+        let good = path(10, 0.0);
+        let other = path(20, 0.05);
+        let lossy = path(10, 0.5);
+        let laggy = path(200, 0.0);
+        let unmeasured = path(0, 0.0);
+        let mut closed = path(10, 0.0);
+        closed.closed = true;
+
+        let mut scheduler = BondingScheduler::new();
+        scheduler.update(&[good, other, lossy, laggy, unmeasured, closed], &config);
+        assert_eq!(scheduler.conn_ids(), vec![good.conn_id, other.conn_id]);
+
+        // a single healthy path is not a bond
+        scheduler.update(&[good, lossy, laggy], &config);
+        assert!(scheduler.conn_ids().is_empty());
+        assert!(scheduler.next().is_none());
+
+        scheduler.update(&[good, other], &config);
+        scheduler.remove(&other.conn_id);
+        assert!(scheduler.next().is_none());
+    }
+
+    #[test]
+    fn reorder_tolerance_is_per_path() {
+        let config = BondingConfig::default();
+        let best = path(10, 0.0);
+        let steady = path(40, 0.0);
+        // same average latency, but its late packets would reorder beyond the budget
+        let mut jittery = path(40, 0.0);
+        jittery.jitter_us = 15_000;
+
+        let mut scheduler = BondingScheduler::new();
+        scheduler.update(&[best, steady, jittery], &config);
+        assert_eq!(scheduler.conn_ids(), vec![best.conn_id, steady.conn_id]);
+
+        // the best path itself is never dropped for its jitter
+        let mut best_jittery = best;
+        best_jittery.jitter_us = 40_000;
+        scheduler.update(&[best_jittery, steady], &config);
+        assert_eq!(scheduler.conn_ids(), vec![best.conn_id, steady.conn_id]);
+    }
+}
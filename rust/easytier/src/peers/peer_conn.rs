@@ -28,6 +28,7 @@ use crate::{
         defer,
         error::Error,
         global_ctx::ArcGlobalCtx,
+        identity::{IdentityKey, NodeIdentityManager},
         stats_manager::{CounterHandle, LabelSet, LabelType, MetricName},
         PeerId,
     },
@@ -39,13 +40,18 @@ use crate::{
     tunnel::{
         filter::{StatsRecorderTunnelFilter, TunnelFilter, TunnelWithFilter},
         mpsc::{MpscTunnel, MpscTunnelSender},
+        obfs::{ObfsConfig, ObfsTunnelFilter},
         packet_def::{PacketType, ZCPacket},
         stats::{Throughput, WindowLatency},
         Tunnel, TunnelError, ZCPacketStream,
     },
 };
 
-use super::{peer_conn_ping::PeerConnPinger, PacketRecvChan};
+use super::{
+    encrypt::session::{SessionEncryptor, SessionKeyExchange},
+    peer_conn_ping::PeerConnPinger,
+    PacketRecvChan,
+};
 
 pub type PeerConnId = uuid::Uuid;
 
@@ -78,6 +84,23 @@ impl PeerConnCloseNotify {
         None
     }
 
+    // an invalid obfs config fails the conn, it must never fall back to a plain tunnel
+    fn wrap_obfs_tunnel(
+        tunnel: Box<dyn Tunnel>,
+        tunnel_info: Option<&TunnelInfo>,
+        global_ctx: &ArcGlobalCtx,
+    ) -> Result<Box<dyn Tunnel>, TunnelError> {
+        let Some(tunnel_info) = tunnel_info else {
+            return Ok(tunnel);
+        };
+        let identity = global_ctx.get_network_identity();
+        let secret = identity.network_secret.unwrap_or(identity.network_name);
+        Ok(match ObfsConfig::from_tunnel_info(tunnel_info, &secret)? {
+            Some(config) => Box::new(TunnelWithFilter::new(tunnel, ObfsTunnelFilter::new(config))),
+            None => tunnel,
+        })
+    }
+
     pub fn get_conn_id(&self) -> PeerConnId {
         self.conn_id
     }
@@ -110,6 +133,14 @@ pub struct PeerConn {
     info: Option<HandshakeRequest>,
     is_client: Option<bool>,
 
+    // nonce the peer signs to prove it owns the key in its membership cert
+    identity_nonce: Vec<u8>,
+    identity_key: Option<IdentityKey>,
+
+    // set when session keys are negotiated with the peer
+    session_encryptor: Option<Arc<SessionEncryptor>>,
+    session_kx: Option<SessionKeyExchange>,
+
     // remote or local
     is_hole_punched: bool,
 
@@ -135,10 +166,16 @@ impl Debug for PeerConn {
 }
 
 impl PeerConn {
-    pub fn new(my_peer_id: PeerId, global_ctx: ArcGlobalCtx, tunnel: Box<dyn Tunnel>) -> Self {
+    pub fn new(
+        my_peer_id: PeerId,
+        global_ctx: ArcGlobalCtx,
+        tunnel: Box<dyn Tunnel>,
+    ) -> Result<Self, Error> {
         let tunnel_info = tunnel.info();
         let (ctrl_sender, _ctrl_receiver) = broadcast::channel(8);
 
+        let tunnel = Self::wrap_obfs_tunnel(tunnel, tunnel_info.as_ref(), &global_ctx)?;
+
         let peer_conn_tunnel_filter = StatsRecorderTunnelFilter::new();
         let throughput = peer_conn_tunnel_filter.filter_output();
         let peer_conn_tunnel = TunnelWithFilter::new(tunnel, peer_conn_tunnel_filter);
@@ -148,7 +185,7 @@ impl PeerConn {
 
         let conn_id = PeerConnId::new_v4();
 
-        PeerConn {
+        Ok(PeerConn {
             conn_id,
 
             my_peer_id,
@@ -166,6 +203,12 @@ impl PeerConn {
             info: None,
             is_client: None,
 
+            identity_nonce: Vec::new(),
+            identity_key: None,
+
+            session_encryptor: None,
+            session_kx: None,
+
             is_hole_punched: true,
 
             close_event_notifier: Arc::new(PeerConnCloseNotify::new(conn_id)),
@@ -177,7 +220,7 @@ impl PeerConn {
             loss_rate_stats: Arc::new(AtomicU32::new(0)),
 
             counters: ArcSwapOption::new(None),
-        }
+        })
     }
 
     pub fn get_conn_id(&self) -> PeerConnId {
@@ -192,6 +235,12 @@ impl PeerConn {
         self.is_hole_punched
     }
 
+    /// Negotiate session keys during the handshake and install them into the encryptor.
+    pub fn set_session_encryptor(&mut self, encryptor: Arc<SessionEncryptor>) {
+        self.session_encryptor = Some(encryptor);
+        self.session_kx = Some(SessionKeyExchange::new());
+    }
+
     async fn wait_handshake(&mut self, need_retry: &mut bool) -> Result<HandshakeRequest, Error> {
         *need_retry = false;
 
@@ -275,6 +324,10 @@ impl PeerConn {
         if send_secret_digest {
             req.network_secret_digrest
                 .extend_from_slice(&network.network_secret_digest.unwrap_or_default());
+            self.fill_identity(&mut req);
+            if let Some(kx) = &self.session_kx {
+                req.session_public_key = kx.public_key();
+            }
         } else {
             // fill zero
             req.network_secret_digrest
@@ -300,6 +353,123 @@ impl PeerConn {
         Ok(())
     }
 
+    fn fill_identity(&mut self, req: &mut HandshakeRequest) {
+        let Some(identity) = self.global_ctx.get_node_identity() else {
+            return;
+        };
+        if self.identity_nonce.is_empty() {
+            self.identity_nonce = NodeIdentityManager::new_nonce();
+        }
+        req.membership_cert = Some(identity.cert());
+        req.identity_nonce = self.identity_nonce.clone();
+        // sign the nonce of the peer once we got its handshake
+        if let Some(info) = &self.info {
+            req.identity_proof = identity.sign_proof(self.my_peer_id, &info.identity_nonce);
+        }
+    }
+
+    // peers of older versions send no key, their traffic keeps using the network key.
+    // the server installs the session before its response, so the client can use it right away
+    fn try_derive_session(&mut self) -> Result<(), Error> {
+        let Some(encryptor) = self.session_encryptor.clone() else {
+            return Ok(());
+        };
+        let info = self.info.as_ref().unwrap();
+        if info.session_public_key.is_empty()
+            || self.get_network_identity() != self.global_ctx.get_network_identity()
+        {
+            return Ok(());
+        }
+        let peer_id = info.my_peer_id;
+        let peer_public_key = info.session_public_key.clone();
+        let Some(kx) = self.session_kx.as_mut() else {
+            return Ok(());
+        };
+        let session = kx
+            .derive_session(
+                encryptor.algorithm(),
+                &self.global_ctx.get_256_key(),
+                self.my_peer_id,
+                peer_id,
+                &peer_public_key,
+            )
+            .map_err(|e| Error::SecretKeyError(format!("session key exchange failed: {:?}", e)))?;
+        if self.is_client == Some(true) {
+            session.confirm();
+        }
+        tracing::debug!(
+            ?peer_id,
+            session_id = session.session_id(),
+            "peer session created"
+        );
+        encryptor.add_session(peer_id, session);
+        Ok(())
+    }
+
+    // node identities are only checked between nodes of our own network
+    fn get_identity_for_peer(&self) -> Option<Arc<NodeIdentityManager>> {
+        let info = self.info.as_ref()?;
+        if info.network_name != self.global_ctx.get_network_name() {
+            return None;
+        }
+        self.global_ctx.get_node_identity().cloned()
+    }
+
+    fn verify_peer_cert(&self, identity: &NodeIdentityManager) -> Result<IdentityKey, Error> {
+        let info = self.info.as_ref().unwrap();
+        let Some(cert) = &info.membership_cert else {
+            return Err(Error::SecretKeyError(
+                "peer has no membership certificate".to_owned(),
+            ));
+        };
+        identity
+            .verify_cert(cert)
+            .map_err(|e| Error::SecretKeyError(format!("invalid membership certificate: {:?}", e)))
+    }
+
+    fn verify_peer_proof(
+        &self,
+        identity: &NodeIdentityManager,
+        key: &IdentityKey,
+        proof: &[u8],
+    ) -> Result<(), Error> {
+        identity
+            .verify_proof(key, self.get_peer_id(), &self.identity_nonce, proof)
+            .map_err(|e| Error::SecretKeyError(format!("invalid identity proof: {:?}", e)))
+    }
+
+    // the client proves its identity in an extra handshake packet, it only knows our nonce now
+    async fn wait_identity_proof_as_server(&mut self) -> Result<(), Error> {
+        let Some(identity) = self.get_identity_for_peer() else {
+            return Ok(());
+        };
+        let key = self.identity_key.unwrap();
+        let rsp = self.wait_handshake_loop().await?;
+        self.verify_peer_proof(&identity, &key, &rsp.identity_proof)
+    }
+
+    async fn handshake_recved_as_server(&mut self, rsp: HandshakeRequest) -> Result<(), Error> {
+        tracing::info!("handshake request: {:?}", rsp);
+        self.info = Some(rsp);
+        self.is_client = Some(false);
+
+        if let Some(identity) = self.get_identity_for_peer() {
+            self.identity_key = Some(self.verify_peer_cert(&identity)?);
+        }
+
+        let send_digest = self.get_network_identity() == self.global_ctx.get_network_identity();
+        if send_digest {
+            self.try_derive_session()?;
+        }
+        self.send_handshake(send_digest).await?;
+
+        // a wrong network secret is rejected later, the client will not send a proof then
+        if send_digest {
+            self.wait_identity_proof_as_server().await?;
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(skip(handshake_recved))]
     pub async fn do_handshake_as_server_ext<Fn>(
         &mut self,
@@ -312,12 +482,7 @@ impl PeerConn {
 
         handshake_recved(self, &rsp)?;
 
-        tracing::info!("handshake request: {:?}", rsp);
-        self.info = Some(rsp);
-        self.is_client = Some(false);
-
-        let send_digest = self.get_network_identity() == self.global_ctx.get_network_identity();
-        self.send_handshake(send_digest).await?;
+        self.handshake_recved_as_server(rsp).await?;
 
         if self.get_peer_id() == self.my_peer_id {
             Err(Error::WaitRespError("peer id conflict".to_owned()))
@@ -329,12 +494,7 @@ impl PeerConn {
     #[tracing::instrument]
     pub async fn do_handshake_as_server(&mut self) -> Result<(), Error> {
         let rsp = self.wait_handshake_loop().await?;
-        tracing::info!("handshake request: {:?}", rsp);
-        self.info = Some(rsp);
-        self.is_client = Some(false);
-
-        let send_digest = self.get_network_identity() == self.global_ctx.get_network_identity();
-        self.send_handshake(send_digest).await?;
+        self.handshake_recved_as_server(rsp).await?;
 
         if self.get_peer_id() == self.my_peer_id {
             Err(Error::WaitRespError(
@@ -354,6 +514,17 @@ impl PeerConn {
         self.info = Some(rsp);
         self.is_client = Some(true);
 
+        if let Some(identity) = self.get_identity_for_peer() {
+            let key = self.verify_peer_cert(&identity)?;
+            let proof = self.info.as_ref().unwrap().identity_proof.clone();
+            self.verify_peer_proof(&identity, &key, &proof)?;
+            self.identity_key = Some(key);
+            // now sign the nonce of the server
+            self.send_handshake(true).await?;
+        }
+
+        self.try_derive_session()?;
+
         if self.get_peer_id() == self.my_peer_id {
             Err(Error::WaitRespError(
                 "peer id conflict, are you connecting to yourself?".to_owned(),
@@ -493,6 +664,11 @@ impl PeerConn {
         ret
     }
 
+    /// The verified key of the peer's membership cert, if node identities are enabled.
+    pub fn get_identity_key(&self) -> Option<IdentityKey> {
+        self.identity_key
+    }
+
     pub fn get_close_notifier(&self) -> Arc<PeerConnCloseNotify> {
         self.close_event_notifier.clone()
     }
@@ -509,6 +685,18 @@ impl PeerConn {
         }
     }
 
+    pub fn get_jitter_us(&self) -> u64 {
+        self.latency_stats.get_jitter_us()
+    }
+
+    pub fn get_loss_rate(&self) -> f32 {
+        (f64::from(self.loss_rate_stats.load(Ordering::Relaxed)) / 100.0) as f32
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.close_event_notifier.is_closed()
+    }
+
     pub fn get_conn_info(&self) -> PeerConnInfo {
         let info = self.info.as_ref().unwrap();
         PeerConnInfo {
@@ -518,10 +706,11 @@ impl PeerConn {
             features: info.features.clone(),
             tunnel: self.tunnel_info.clone(),
             stats: Some(self.get_stats()),
-            loss_rate: (f64::from(self.loss_rate_stats.load(Ordering::Relaxed)) / 100.0) as f32,
+            loss_rate: self.get_loss_rate(),
             is_client: self.is_client.unwrap_or_default(),
             network_name: info.network_name.clone(),
-            is_closed: self.close_event_notifier.is_closed(),
+            is_closed: self.is_closed(),
+            identity_key: self.identity_key.map(|k| k.to_vec()).unwrap_or_default(),
         }
     }
 
@@ -549,7 +738,9 @@ mod tests {
     use std::sync::Arc;
 
     use super::*;
-    use crate::common::global_ctx::tests::get_mock_global_ctx;
+    use crate::common::global_ctx::tests::{
+        get_mock_global_ctx, get_mock_global_ctx_with_identity,
+    };
     use crate::common::new_peer_id;
     use crate::common::scoped_task::ScopedTask;
     use crate::peers::create_packet_recv_chan;
@@ -563,8 +754,8 @@ mod tests {
         let c_peer_id = new_peer_id();
         let s_peer_id = c_peer_id;
 
-        let mut c_peer = PeerConn::new(c_peer_id, get_mock_global_ctx(), Box::new(c));
-        let mut s_peer = PeerConn::new(s_peer_id, get_mock_global_ctx(), Box::new(s));
+        let mut c_peer = PeerConn::new(c_peer_id, get_mock_global_ctx(), Box::new(c)).unwrap();
+        let mut s_peer = PeerConn::new(s_peer_id, get_mock_global_ctx(), Box::new(s)).unwrap();
 
         let (c_ret, s_ret) = tokio::join!(
             c_peer.do_handshake_as_client(),
@@ -588,9 +779,9 @@ mod tests {
         let c_peer_id = new_peer_id();
         let s_peer_id = new_peer_id();
 
-        let mut c_peer = PeerConn::new(c_peer_id, get_mock_global_ctx(), Box::new(c));
+        let mut c_peer = PeerConn::new(c_peer_id, get_mock_global_ctx(), Box::new(c)).unwrap();
 
-        let mut s_peer = PeerConn::new(s_peer_id, get_mock_global_ctx(), Box::new(s));
+        let mut s_peer = PeerConn::new(s_peer_id, get_mock_global_ctx(), Box::new(s)).unwrap();
 
         let (c_ret, s_ret) = tokio::join!(
             c_peer.do_handshake_as_client(),
@@ -612,6 +803,44 @@ mod tests {
         assert_eq!(c_peer.get_network_identity(), NetworkIdentity::default());
     }
 
+    async fn identity_handshake(
+        c_ctx: ArcGlobalCtx,
+        s_ctx: ArcGlobalCtx,
+    ) -> (Result<PeerConn, Error>, Result<PeerConn, Error>) {
+        let (c, s) = create_ring_tunnel_pair();
+        let mut c_peer = PeerConn::new(new_peer_id(), c_ctx, Box::new(c)).unwrap();
+        let mut s_peer = PeerConn::new(new_peer_id(), s_ctx, Box::new(s)).unwrap();
+        let (c_ret, s_ret) = tokio::join!(
+            c_peer.do_handshake_as_client(),
+            s_peer.do_handshake_as_server()
+        );
+        (c_ret.map(|_| c_peer), s_ret.map(|_| s_peer))
+    }
+
+    #[tokio::test]
+    async fn peer_conn_handshake_with_node_identity() {
+        let owner_key = ed25519_dalek::SigningKey::from_bytes(&rand::random());
+        let c_ctx = get_mock_global_ctx_with_identity(&owner_key, &[]);
+        let s_ctx = get_mock_global_ctx_with_identity(&owner_key, &[]);
+        let c_key = c_ctx.get_node_identity().unwrap().public_key();
+        let s_key = s_ctx.get_node_identity().unwrap().public_key();
+
+        let (c_ret, s_ret) = identity_handshake(c_ctx.clone(), s_ctx.clone()).await;
+        assert_eq!(c_ret.unwrap().get_identity_key(), Some(s_key));
+        assert_eq!(s_ret.unwrap().get_identity_key(), Some(c_key));
+
+        // a node without identity can not join
+        let (c_ret, s_ret) = identity_handshake(get_mock_global_ctx(), s_ctx.clone()).await;
+        assert!(c_ret.is_err());
+        assert!(s_ret.is_err());
+
+        // and neither can a revoked one
+        let s_ctx = get_mock_global_ctx_with_identity(&owner_key, &[c_key]);
+        let (c_ret, s_ret) = identity_handshake(c_ctx, s_ctx).await;
+        assert!(c_ret.is_err());
+        assert!(s_ret.is_err());
+    }
+
     async fn peer_conn_pingpong_test_common(
         drop_start: u32,
         drop_end: u32,
@@ -627,8 +856,8 @@ mod tests {
         let c_peer_id = new_peer_id();
         let s_peer_id = new_peer_id();
 
-        let mut c_peer = PeerConn::new(c_peer_id, get_mock_global_ctx(), Box::new(c));
-        let mut s_peer = PeerConn::new(s_peer_id, get_mock_global_ctx(), Box::new(s));
+        let mut c_peer = PeerConn::new(c_peer_id, get_mock_global_ctx(), Box::new(c)).unwrap();
+        let mut s_peer = PeerConn::new(s_peer_id, get_mock_global_ctx(), Box::new(s)).unwrap();
 
         let (c_ret, s_ret) = tokio::join!(
             c_peer.do_handshake_as_client(),
@@ -684,7 +913,7 @@ mod tests {
     #[tokio::test]
     async fn close_tunnel_during_handshake() {
         let (c, s) = create_ring_tunnel_pair();
-        let mut c_peer = PeerConn::new(new_peer_id(), get_mock_global_ctx(), Box::new(c));
+        let mut c_peer = PeerConn::new(new_peer_id(), get_mock_global_ctx(), Box::new(c)).unwrap();
         let j = tokio::spawn(async move {
             tokio::time::sleep(Duration::from_secs(1)).await;
             drop(s);
@@ -20,6 +20,7 @@ use tokio::{
 
 use crate::{
     common::{
+        acl_processor::AclHostTable,
         compressor::{Compressor as _, DefaultCompressor},
         constants::EASYTIER_VERSION,
         error::Error,
@@ -53,7 +54,11 @@ use crate::{
 
 use super::{
     create_packet_recv_chan,
-    encrypt::{Encryptor, NullCipher},
+    encrypt::{session::SessionEncryptor, supports_session_keys, Encryptor, NullCipher},
+    exit_node_policy::{
+        build_probe_packet, parse_probe_reply, ExitFlowKey, ExitNodeSelector,
+        EXIT_NODE_PROBE_INTERVAL, EXIT_NODE_PROBE_TIMEOUT,
+    },
     foreign_network_client::ForeignNetworkClient,
     foreign_network_manager::{ForeignNetworkManager, GlobalForeignNetworkAccessor},
     peer_conn::PeerConnId,
@@ -150,9 +155,10 @@ pub struct PeerManager {
     foreign_network_client: Arc<ForeignNetworkClient>,
 
     encryptor: Arc<dyn Encryptor + 'static>,
+    session_encryptor: Option<Arc<SessionEncryptor>>,
     data_compress_algo: CompressorAlgo,
 
-    exit_nodes: Vec<IpAddr>,
+    exit_node_selector: Arc<ExitNodeSelector>,
 
     reserved_my_peer_id_map: DashMap<String, PeerId>,
 
@@ -186,14 +192,23 @@ impl PeerManager {
             my_peer_id,
         ));
 
+        let mut session_encryptor = None;
         let encryptor = if global_ctx.get_flags().enable_encryption {
             // 只有在启用加密时才使用工厂函数选择算法
             let algorithm = &global_ctx.get_flags().encryption_algorithm;
-            super::encrypt::create_encryptor(
+            let encryptor = super::encrypt::create_encryptor(
                 algorithm,
                 global_ctx.get_128_key(),
                 global_ctx.get_256_key(),
-            )
+            );
+            // directly connected peers negotiate session keys, the network key is the fallback
+            if supports_session_keys(algorithm) {
+                let e = Arc::new(SessionEncryptor::new(algorithm, encryptor));
+                session_encryptor = Some(e.clone());
+                e
+            } else {
+                encryptor
+            }
         } else {
             // disable_encryption = true 时使用 NullCipher
             Arc::new(NullCipher)
@@ -252,7 +267,7 @@ impl PeerManager {
             .try_into()
             .expect("invalid data compress algo, maybe some features not enabled");
 
-        let exit_nodes = global_ctx.config.get_exit_nodes();
+        let exit_node_selector = Arc::new(ExitNodeSelector::from_config(&global_ctx.config));
 
         let stats_manager = global_ctx.stats_manager();
         let self_tx_counters = SelfTxCounters {
@@ -302,9 +317,10 @@ impl PeerManager {
             foreign_network_client,
 
             encryptor,
+            session_encryptor,
             data_compress_algo,
 
-            exit_nodes,
+            exit_node_selector,
 
             reserved_my_peer_id_map: DashMap::new(),
 
@@ -362,8 +378,11 @@ impl PeerManager {
         tunnel: Box<dyn Tunnel>,
         is_directly_connected: bool,
     ) -> Result<(PeerId, PeerConnId), Error> {
-        let mut peer = PeerConn::new(self.my_peer_id, self.global_ctx.clone(), tunnel);
+        let mut peer = PeerConn::new(self.my_peer_id, self.global_ctx.clone(), tunnel)?;
         peer.set_is_hole_punched(!is_directly_connected);
+        if let Some(session_encryptor) = &self.session_encryptor {
+            peer.set_session_encryptor(session_encryptor.clone());
+        }
         peer.do_handshake_as_client().await?;
         let conn_id = peer.get_conn_id();
         let peer_id = peer.get_peer_id();
@@ -468,7 +487,10 @@ impl PeerManager {
         tracing::info!("add tunnel as server start");
         self.check_remote_addr_not_from_virtual_network(&tunnel)?;
 
-        let mut conn = PeerConn::new(self.my_peer_id, self.global_ctx.clone(), tunnel);
+        let mut conn = PeerConn::new(self.my_peer_id, self.global_ctx.clone(), tunnel)?;
+        if let Some(session_encryptor) = &self.session_encryptor {
+            conn.set_session_encryptor(session_encryptor.clone());
+        }
         conn.do_handshake_as_server_ext(|peer, msg| {
             if msg.network_name
                 == self.global_ctx.get_network_identity().network_name
@@ -1042,7 +1064,42 @@ impl PeerManager {
         }
     }
 
-    pub async fn get_msg_dst_peer(&self, ipv4_addr: &Ipv4Addr) -> (Vec<PeerId>, bool) {
+    async fn get_exit_peer_id(&self, exit_node: IpAddr) -> Option<PeerId> {
+        match exit_node {
+            IpAddr::V4(ip) => self.peers.get_peer_id_by_ipv4(&ip).await,
+            IpAddr::V6(ip) => self.peers.get_peer_id_by_ipv6(&ip).await,
+        }
+    }
+
+    async fn select_exit_peer(&self, dst: IpAddr, flow: Option<&ExitFlowKey>) -> Option<PeerId> {
+        // fast path, packets of a pinned flow only need the route of its exit node
+        if let Some(exit_node) = flow.and_then(|f| self.exit_node_selector.sticky_exit_node(f)) {
+            if let Some(peer_id) = self.get_exit_peer_id(exit_node).await {
+                return Some(peer_id);
+            }
+        }
+
+        let mut reachable = vec![];
+        for exit_node in self.exit_node_selector.candidates(&dst) {
+            if let Some(peer_id) = self.get_exit_peer_id(exit_node).await {
+                reachable.push((exit_node, peer_id));
+            }
+        }
+        let reachable_addrs = reachable.iter().map(|(ip, _)| *ip).collect::<Vec<_>>();
+        let exit_node = self
+            .exit_node_selector
+            .select(&dst, flow, &reachable_addrs)?;
+        reachable
+            .into_iter()
+            .find(|(ip, _)| *ip == exit_node)
+            .map(|(_, peer_id)| peer_id)
+    }
+
+    pub async fn get_msg_dst_peer(
+        &self,
+        ipv4_addr: &Ipv4Addr,
+        flow: Option<&ExitFlowKey>,
+    ) -> (Vec<PeerId>, bool) {
         let mut is_exit_node = false;
         let mut dst_peers = vec![];
         let network_length = self
@@ -1068,15 +1125,9 @@ impl PeerManager {
             .global_ctx
             .is_ip_in_same_network(&std::net::IpAddr::V4(*ipv4_addr))
         {
-            for exit_node in &self.exit_nodes {
-                let IpAddr::V4(exit_node) = exit_node else {
-                    continue;
-                };
-                if let Some(peer_id) = self.peers.get_peer_id_by_ipv4(exit_node).await {
-                    dst_peers.push(peer_id);
-                    is_exit_node = true;
-                    break;
-                }
+            if let Some(peer_id) = self.select_exit_peer(IpAddr::V4(*ipv4_addr), flow).await {
+                dst_peers.push(peer_id);
+                is_exit_node = true;
             }
         }
         #[cfg(target_env = "ohos")]
@@ -1094,7 +1145,11 @@ impl PeerManager {
         (dst_peers, is_exit_node)
     }
 
-    pub async fn get_msg_dst_peer_ipv6(&self, ipv6_addr: &Ipv6Addr) -> (Vec<PeerId>, bool) {
+    pub async fn get_msg_dst_peer_ipv6(
+        &self,
+        ipv6_addr: &Ipv6Addr,
+        flow: Option<&ExitFlowKey>,
+    ) -> (Vec<PeerId>, bool) {
         let mut is_exit_node = false;
         let mut dst_peers = vec![];
         let network_length = self
@@ -1109,15 +1164,9 @@ impl PeerManager {
             dst_peers.push(peer_id);
         } else if !ipv6_addr.is_unicast_link_local() {
             // NOTE: never route link local address to exit node.
-            for exit_node in &self.exit_nodes {
-                let IpAddr::V6(exit_node) = exit_node else {
-                    continue;
-                };
-                if let Some(peer_id) = self.peers.get_peer_id_by_ipv6(exit_node).await {
-                    dst_peers.push(peer_id);
-                    is_exit_node = true;
-                    break;
-                }
+            if let Some(peer_id) = self.select_exit_peer(IpAddr::V6(*ipv6_addr), flow).await {
+                dst_peers.push(peer_id);
+                is_exit_node = true;
             }
         }
 
@@ -1162,9 +1211,15 @@ impl PeerManager {
             .await;
         }
 
+        // flows are only tracked for exit node stickiness
+        let flow = if self.exit_node_selector.is_empty() {
+            None
+        } else {
+            ExitFlowKey::from_ip_packet(msg.payload())
+        };
         let (dst_peers, is_exit_node) = match ip_addr {
-            IpAddr::V4(ipv4_addr) => self.get_msg_dst_peer(&ipv4_addr).await,
-            IpAddr::V6(ipv6_addr) => self.get_msg_dst_peer_ipv6(&ipv6_addr).await,
+            IpAddr::V4(ipv4_addr) => self.get_msg_dst_peer(&ipv4_addr, flow.as_ref()).await,
+            IpAddr::V6(ipv6_addr) => self.get_msg_dst_peer_ipv6(&ipv6_addr, flow.as_ref()).await,
         };
 
         if dst_peers.is_empty() {
@@ -1176,7 +1231,11 @@ impl PeerManager {
             .compress_tx_bytes_before
             .add(msg.buf_len() as u64);
 
-        Self::try_compress_and_encrypt(self.data_compress_algo, &self.encryptor, &mut msg).await?;
+        let compressor = DefaultCompressor {};
+        compressor
+            .compress(&mut msg, self.data_compress_algo)
+            .await
+            .with_context(|| "compress failed")?;
 
         self.self_tx_counters
             .compress_tx_bytes_after
@@ -1203,6 +1262,12 @@ impl PeerManager {
                 .to_peer_id
                 .set(*peer_id);
 
+            // encrypt per destination, each peer may have its own session keys
+            if let Err(e) = self.encryptor.encrypt(&mut msg) {
+                errs.push(anyhow::anyhow!("encrypt failed: {:?}", e).into());
+                continue;
+            }
+
             self.self_tx_counters
                 .self_tx_bytes
                 .add(msg.buf_len() as u64);
@@ -1228,14 +1293,177 @@ impl PeerManager {
 
     async fn run_clean_peer_without_conn_routine(&self) {
         let peer_map = self.peers.clone();
+        let session_encryptor = self.session_encryptor.clone();
         self.tasks.lock().await.spawn(async move {
             loop {
                 peer_map.clean_peer_without_conn().await;
+                // sessions also serve relayed traffic, drop them once the peer is gone entirely
+                if let Some(session_encryptor) = &session_encryptor {
+                    for peer_id in session_encryptor.list_peers() {
+                        if !peer_map.has_peer(peer_id)
+                            && peer_map.get_route_peer_info(peer_id).await.is_none()
+                        {
+                            session_encryptor.remove_peer(peer_id);
+                        }
+                    }
+                }
                 tokio::time::sleep(std::time::Duration::from_secs(3)).await;
             }
         });
     }
 
+    /// Close connections of nodes whose identity key got revoked
+    async fn run_revocation_routine(&self) {
+        let Some(identity) = self.global_ctx.get_node_identity().cloned() else {
+            return;
+        };
+        let peer_map = self.peers.clone();
+        let mut revocation_recv = identity.subscribe_revocation();
+        self.tasks.lock().await.spawn(async move {
+            while revocation_recv.changed().await.is_ok() {
+                for peer_id in peer_map.list_peers_with_conn().await {
+                    let Some(conns) = peer_map.list_peer_conns(peer_id).await else {
+                        continue;
+                    };
+                    for conn in conns {
+                        if !identity.is_revoked(&conn.identity_key) {
+                            continue;
+                        }
+                        tracing::warn!(?peer_id, ?conn.conn_id, "close conn of revoked node");
+                        let Ok(conn_id) = conn.conn_id.parse() else {
+                            continue;
+                        };
+                        let _ = peer_map.close_peer_conn(peer_id, &conn_id).await;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Re-resolve host names used by ACL rules whenever the route table changes
+    async fn run_acl_host_update_routine(&self) {
+        let RouteAlgoInst::Ospf(route) = &self.route_algo_inst else {
+            return;
+        };
+        let route = route.clone();
+        let global_ctx = self.global_ctx.clone();
+        let my_peer_id = self.my_peer_id;
+        self.tasks.lock().await.spawn(async move {
+            let mut prev_last_update = None;
+            loop {
+                let last_update = route.get_peer_info_last_update_time().await;
+                if Some(last_update) == prev_last_update {
+                    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+                    continue;
+                }
+                prev_last_update = Some(last_update);
+
+                let mut hosts = AclHostTable::new();
+                for r in route.list_routes().await {
+                    let mut addrs = vec![];
+                    if let Some(addr) = r.ipv4_addr.and_then(|inet| inet.address) {
+                        addrs.push(IpAddr::V4(addr.into()));
+                    }
+                    if let Some(addr) = r.ipv6_addr.and_then(|inet| inet.address) {
+                        addrs.push(IpAddr::V6(addr.into()));
+                    }
+                    hosts.add_peer(&r.hostname, &addrs, &route.get_peer_groups(r.peer_id));
+                }
+
+                // add self, same as the magic dns records
+                let mut addrs = vec![];
+                if let Some(inet) = global_ctx.get_ipv4() {
+                    addrs.push(IpAddr::V4(inet.address()));
+                }
+                if let Some(inet) = global_ctx.get_ipv6() {
+                    addrs.push(IpAddr::V6(inet.address()));
+                }
+                hosts.add_peer(
+                    &global_ctx.get_hostname(),
+                    &addrs,
+                    &route.get_peer_groups(my_peer_id),
+                );
+
+                global_ctx.get_acl_filter().update_hosts(hosts);
+            }
+        });
+    }
+
+    /// Probe exit nodes with icmp echo through the overlay, replies are taken off the
+    /// packet pipeline before they reach the nic.
+    async fn run_exit_node_probe_routine(&self) {
+        if self.exit_node_selector.is_empty() {
+            return;
+        }
+
+        struct ExitNodeProbeReplyFilter {
+            selector: Arc<ExitNodeSelector>,
+        }
+
+        #[async_trait::async_trait]
+        impl PeerPacketFilter for ExitNodeProbeReplyFilter {
+            async fn try_process_packet_from_peer(&self, packet: ZCPacket) -> Option<ZCPacket> {
+                let hdr = packet.peer_manager_header().unwrap();
+                if hdr.packet_type != PacketType::Data as u8 {
+                    return Some(packet);
+                }
+                match parse_probe_reply(packet.payload()) {
+                    Some((exit_node, seq)) if self.selector.finish_probe(exit_node, seq) => None,
+                    _ => Some(packet),
+                }
+            }
+        }
+        self.add_packet_process_pipeline(Box::new(ExitNodeProbeReplyFilter {
+            selector: self.exit_node_selector.clone(),
+        }))
+        .await;
+
+        let selector = self.exit_node_selector.clone();
+        let peers = self.peers.clone();
+        let foreign_network_client = self.foreign_network_client.clone();
+        let encryptor = self.encryptor.clone();
+        let global_ctx = self.global_ctx.clone();
+        let my_peer_id = self.my_peer_id;
+        self.tasks.lock().await.spawn(async move {
+            loop {
+                tokio::time::sleep(EXIT_NODE_PROBE_INTERVAL).await;
+                selector.expire_probes(EXIT_NODE_PROBE_TIMEOUT);
+                selector.expire_flows();
+
+                for exit_node in selector.list_exit_node_addrs() {
+                    let peer_id = match exit_node {
+                        IpAddr::V4(ip) => peers.get_peer_id_by_ipv4(&ip).await,
+                        IpAddr::V6(ip) => peers.get_peer_id_by_ipv6(&ip).await,
+                    };
+                    selector.set_reachable(exit_node, peer_id.is_some());
+
+                    let my_ip = match exit_node {
+                        IpAddr::V4(_) => global_ctx.get_ipv4().map(|ip| IpAddr::V4(ip.address())),
+                        IpAddr::V6(_) => global_ctx.get_ipv6().map(|ip| IpAddr::V6(ip.address())),
+                    };
+                    let (Some(peer_id), Some(my_ip)) = (peer_id, my_ip) else {
+                        continue;
+                    };
+                    let seq = selector.start_probe(exit_node);
+                    let Some(probe) = build_probe_packet(my_ip, exit_node, seq) else {
+                        continue;
+                    };
+                    let mut msg = ZCPacket::new_with_payload(&probe);
+                    msg.fill_peer_manager_hdr(my_peer_id, peer_id, PacketType::Data as u8);
+                    if let Err(e) = encryptor.encrypt(&mut msg) {
+                        tracing::warn!(?exit_node, "encrypt exit node probe failed: {:?}", e);
+                        continue;
+                    }
+                    if let Err(e) =
+                        Self::send_msg_internal(&peers, &foreign_network_client, msg, peer_id).await
+                    {
+                        tracing::debug!(?exit_node, "send exit node probe failed: {:?}", e);
+                    }
+                }
+            }
+        });
+    }
+
     async fn run_foriegn_network(&self) {
         self.peer_rpc_tspt
             .foreign_peers
@@ -1257,6 +1485,9 @@ impl PeerManager {
 
         self.start_peer_recv().await;
         self.run_clean_peer_without_conn_routine().await;
+        self.run_acl_host_update_routine().await;
+        self.run_revocation_routine().await;
+        self.run_exit_node_probe_routine().await;
 
         self.run_foriegn_network().await;
 
@@ -1295,6 +1526,15 @@ impl PeerManager {
         self.foreign_network_client.clone()
     }
 
+    /// Exit node health and at most `max_flows` of the pinned flows.
+    pub fn list_exit_flows(&self, max_flows: usize) -> cli::ListExitFlowResponse {
+        cli::ListExitFlowResponse {
+            exit_nodes: self.exit_node_selector.list_exit_nodes(),
+            flows: self.exit_node_selector.list_flows(max_flows),
+            total_flows: self.exit_node_selector.flow_count() as u64,
+        }
+    }
+
     pub async fn get_my_info(&self) -> cli::NodeInfo {
         cli::NodeInfo {
             peer_id: self.my_peer_id,
@@ -1439,8 +1679,8 @@ mod tests {
             peer_rpc::tests::register_service,
             route_trait::NextHopPolicy,
             tests::{
-                connect_peer_manager, create_mock_peer_manager_with_name, wait_route_appear,
-                wait_route_appear_with_cost,
+                connect_peer_manager, create_mock_peer_manager_with_identity,
+                create_mock_peer_manager_with_name, wait_route_appear, wait_route_appear_with_cost,
             },
         },
         proto::common::{CompressionAlgoPb, NatType, PeerFeatureFlag},
@@ -1486,6 +1726,60 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn revoked_node_is_disconnected() {
+        use crate::common::identity::sign_revocation_list;
+
+        let owner_key = ed25519_dalek::SigningKey::from_bytes(&rand::random());
+        let peer_mgr_a = create_mock_peer_manager_with_identity(&owner_key).await;
+        let peer_mgr_b = create_mock_peer_manager_with_identity(&owner_key).await;
+        let peer_mgr_c = create_mock_peer_manager_with_identity(&owner_key).await;
+        connect_peer_manager(peer_mgr_a.clone(), peer_mgr_b.clone()).await;
+        connect_peer_manager(peer_mgr_b.clone(), peer_mgr_c.clone()).await;
+
+        wait_route_appear(peer_mgr_a.clone(), peer_mgr_c.clone())
+            .await
+            .unwrap();
+
+        // only a gets the list, b learns it through route sync and kicks c
+        let c_key = peer_mgr_c
+            .get_global_ctx()
+            .get_node_identity()
+            .unwrap()
+            .public_key();
+        let list = sign_revocation_list(&owner_key, "default", 2, &[c_key]);
+        let a_identity = peer_mgr_a.get_global_ctx().get_node_identity().cloned();
+        assert!(a_identity.unwrap().update_revocation_list(&list).unwrap());
+
+        let c_peer_id = peer_mgr_c.my_peer_id();
+        wait_for_condition(
+            || async {
+                !peer_mgr_b
+                    .get_peer_map()
+                    .list_peers_with_conn()
+                    .await
+                    .contains(&c_peer_id)
+            },
+            std::time::Duration::from_secs(10),
+        )
+        .await;
+        let b_identity = peer_mgr_b.get_global_ctx().get_node_identity().cloned();
+        assert_eq!(b_identity.unwrap().revocation_version(), 2);
+
+        // the route to c is gone too, not only the direct conn
+        wait_for_condition(
+            || async {
+                !peer_mgr_a
+                    .list_routes()
+                    .await
+                    .iter()
+                    .any(|r| r.peer_id == c_peer_id)
+            },
+            std::time::Duration::from_secs(10),
+        )
+        .await;
+    }
+
     async fn connect_peer_manager_with<C: TunnelConnector + Debug + 'static, L: TunnelListener>(
         client_mgr: Arc<PeerManager>,
         server_mgr: &Arc<PeerManager>,
@@ -1585,6 +1879,63 @@ mod tests {
         assert_eq!(ret.greeting, "hello c abc!");
     }
 
+    #[tokio::test]
+    async fn peer_session_keys_for_direct_peers() {
+        use crate::proto::{
+            rpc_impl::RpcController,
+            tests::{GreetingClientFactory, SayHelloRequest},
+        };
+
+        let peer_mgr_a = create_mock_peer_manager_with_mock_stun(NatType::Unknown).await;
+        let peer_mgr_b = create_mock_peer_manager_with_mock_stun(NatType::Unknown).await;
+        register_service(&peer_mgr_b.peer_rpc_mgr, "", 0, "hello b");
+        let peer_mgr_c = create_mock_peer_manager_with_mock_stun(NatType::Unknown).await;
+        register_service(&peer_mgr_c.peer_rpc_mgr, "", 0, "hello c");
+
+        connect_peer_manager(peer_mgr_a.clone(), peer_mgr_b.clone()).await;
+        connect_peer_manager(peer_mgr_b.clone(), peer_mgr_c.clone()).await;
+        wait_route_appear(peer_mgr_a.clone(), peer_mgr_c.clone())
+            .await
+            .unwrap();
+
+        // the server side starts sending with its session once the client used it
+        let a_sessions = peer_mgr_a.session_encryptor.clone().unwrap();
+        let b_sessions = peer_mgr_b.session_encryptor.clone().unwrap();
+        wait_for_condition(
+            || async {
+                a_sessions.has_session(peer_mgr_b.my_peer_id())
+                    && b_sessions.has_session(peer_mgr_a.my_peer_id())
+            },
+            Duration::from_secs(5),
+        )
+        .await;
+        // c is only reachable through b, it keeps using the network key
+        assert!(!a_sessions.has_session(peer_mgr_c.my_peer_id()));
+
+        for (dst_peer_mgr, greeting) in
+            [(&peer_mgr_b, "hello b abc!"), (&peer_mgr_c, "hello c abc!")]
+        {
+            let stub = peer_mgr_a
+                .peer_rpc_mgr
+                .rpc_client()
+                .scoped_client::<GreetingClientFactory<RpcController>>(
+                    peer_mgr_a.my_peer_id,
+                    dst_peer_mgr.my_peer_id,
+                    "".to_string(),
+                );
+            let ret = stub
+                .say_hello(
+                    RpcController::default(),
+                    SayHelloRequest {
+                        name: "abc".to_string(),
+                    },
+                )
+                .await
+                .unwrap();
+            assert_eq!(ret.greeting, greeting);
+        }
+    }
+
     #[tokio::test]
     async fn communicate_between_enc_and_non_enc() {
         let create_mgr = |enable_encryption| async move {
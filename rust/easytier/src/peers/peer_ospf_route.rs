@@ -3,9 +3,9 @@ use std::{
         HashMap, {BTreeMap, BTreeSet},
     },
     fmt::Debug,
-    net::{Ipv4Addr, Ipv6Addr},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
     sync::{
-        atomic::{AtomicBool, AtomicU32, Ordering},
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
         Arc, Weak,
     },
     time::{Duration, Instant, SystemTime},
@@ -31,7 +31,7 @@ use tokio::{
 use crate::{
     common::{
         config::NetworkIdentity, constants::EASYTIER_VERSION, global_ctx::ArcGlobalCtx,
-        stun::StunInfoCollectorTrait, PeerId,
+        identity::NodeIdentityManager, stun::StunInfoCollectorTrait, PeerId,
     },
     peers::route_trait::{Route, RouteInterfaceBox},
     proto::{
@@ -40,9 +40,9 @@ use crate::{
         peer_rpc::{
             route_foreign_network_infos, route_foreign_network_summary,
             ForeignNetworkRouteInfoEntry, ForeignNetworkRouteInfoKey, OspfRouteRpc,
-            OspfRouteRpcClientFactory, OspfRouteRpcServer, PeerIdVersion, RouteForeignNetworkInfos,
-            RouteForeignNetworkSummary, RoutePeerInfo, RoutePeerInfos, SyncRouteInfoError,
-            SyncRouteInfoRequest, SyncRouteInfoResponse,
+            OspfRouteRpcClientFactory, OspfRouteRpcServer, PeerIdVersion, RevocationList,
+            RouteForeignNetworkInfos, RouteForeignNetworkSummary, RoutePeerInfo, RoutePeerInfos,
+            SyncRouteInfoError, SyncRouteInfoRequest, SyncRouteInfoResponse,
         },
         rpc_types::{
             self,
@@ -131,6 +131,8 @@ impl RoutePeerInfo {
             quic_port: None,
             ipv6_addr: None,
             groups: Vec::new(),
+            membership_cert: None,
+            identity_signature: Vec::new(),
         }
     }
 
@@ -150,7 +152,7 @@ impl RoutePeerInfo {
                 .get_proxy_cidrs()
                 .iter()
                 .map(|x| x.mapped_cidr.unwrap_or(x.cidr))
-                .chain(global_ctx.get_vpn_portal_cidr())
+                .chain(global_ctx.get_vpn_portal_cidr().map(Into::into))
                 .map(|x| x.to_string())
                 .collect(),
             hostname: Some(global_ctx.get_hostname()),
@@ -174,6 +176,12 @@ impl RoutePeerInfo {
             ipv6_addr: global_ctx.get_ipv6().map(|x| x.into()),
 
             groups: global_ctx.get_acl_groups(my_peer_id),
+
+            membership_cert: global_ctx.get_node_identity().map(|i| i.cert()),
+            identity_signature: global_ctx
+                .get_node_identity()
+                .map(|i| i.sign_route_info(my_peer_id, peer_route_id))
+                .unwrap_or_default(),
         };
 
         let need_update_periodically = if let Ok(Ok(d)) =
@@ -413,6 +421,7 @@ impl SyncedRouteInfo {
         dst_peer_id: PeerId,
         peer_infos: &[RoutePeerInfo],
         raw_peer_infos: &[DynamicMessage],
+        identity: Option<&NodeIdentityManager>,
     ) -> Result<(), Error> {
         let mut need_inc_version = false;
         for (idx, route_info) in peer_infos.iter().enumerate() {
@@ -437,6 +446,26 @@ impl SyncedRouteInfo {
                 .unwrap();
             assert_eq!(peer_id_raw, route_info.peer_id);
 
+            // only newer infos are verified, older ones are dropped below anyway
+            let is_newer = self
+                .peer_infos
+                .get(&route_info.peer_id)
+                .map(|x| route_info.version > x.version)
+                .unwrap_or(true);
+            if let Some(identity) =
+                identity.filter(|_| is_newer && route_info.peer_id != my_peer_id)
+            {
+                if let Err(e) = Self::verify_peer_identity(identity, &route_info) {
+                    tracing::warn!(
+                        ?e,
+                        peer_id = route_info.peer_id,
+                        ?dst_peer_id,
+                        "reject route info of unverified peer"
+                    );
+                    continue;
+                }
+            }
+
             // time between peers may not be synchronized, so update last_update to local now.
             // note only last_update with larger version will be updated to local saved peer info.
             route_info.last_update = Some(SystemTime::now().into());
@@ -464,6 +493,39 @@ impl SyncedRouteInfo {
         Ok(())
     }
 
+    fn verify_peer_identity(
+        identity: &NodeIdentityManager,
+        info: &RoutePeerInfo,
+    ) -> anyhow::Result<()> {
+        identity.verify_route_info(
+            info.membership_cert.as_ref(),
+            info.peer_id,
+            info.peer_route_id,
+            &info.identity_signature,
+        )?;
+        Ok(())
+    }
+
+    /// Remove peers whose certificate got revoked or expired, returns true if any is removed.
+    /// Their routes stay out until they announce a valid certificate again.
+    fn remove_unverified_peers(&self, my_peer_id: PeerId, identity: &NodeIdentityManager) -> bool {
+        let to_remove: Vec<PeerId> = self
+            .peer_infos
+            .iter()
+            .filter(|item| *item.key() != my_peer_id && item.value().version != 0)
+            .filter(|item| Self::verify_peer_identity(identity, item.value()).is_err())
+            .map(|item| *item.key())
+            .collect();
+        for peer_id in to_remove.iter() {
+            tracing::warn!(
+                ?peer_id,
+                "remove route info of peer without a valid identity"
+            );
+            self.remove_peer(*peer_id);
+        }
+        !to_remove.is_empty()
+    }
+
     fn update_conn_map(&self, conn_bitmap: &RouteConnBitmap) {
         self.fill_empty_peer_info(&conn_bitmap.peer_ids.iter().map(|x| x.0).collect());
 
@@ -1015,11 +1077,10 @@ impl RouteTable {
         }
     }
 
-    fn get_peer_id_for_proxy(&self, ipv4: &Ipv4Addr) -> Option<PeerId> {
-        let ipv4 = std::net::IpAddr::V4(*ipv4);
+    fn get_peer_id_for_proxy(&self, ip: &IpAddr) -> Option<PeerId> {
         for item in self.cidr_peer_id_map.iter() {
             let (k, v) = item.pair();
-            if k.contains(&ipv4) {
+            if k.contains(ip) {
                 return Some(v.peer_id);
             }
         }
@@ -1084,6 +1145,7 @@ struct SyncRouteSession {
     dst_saved_peer_info_versions: DashMap<PeerId, AtomicVersion>,
     dst_saved_conn_bitmap_version: DashMap<PeerId, AtomicVersion>,
     dst_saved_foreign_network_versions: DashMap<ForeignNetworkRouteInfoKey, AtomicVersion>,
+    dst_saved_revocation_list_version: AtomicU64,
 
     my_session_id: AtomicSessionId,
     dst_session_id: AtomicSessionId,
@@ -1108,6 +1170,7 @@ impl SyncRouteSession {
             dst_saved_peer_info_versions: DashMap::new(),
             dst_saved_conn_bitmap_version: DashMap::new(),
             dst_saved_foreign_network_versions: DashMap::new(),
+            dst_saved_revocation_list_version: AtomicU64::new(0),
 
             my_session_id: AtomicSessionId::new(rand::random()),
             dst_session_id: AtomicSessionId::new(0),
@@ -1173,6 +1236,8 @@ impl SyncRouteSession {
             self.dst_session_id.store(session_id, Ordering::Relaxed);
             self.dst_saved_conn_bitmap_version.clear();
             self.dst_saved_peer_info_versions.clear();
+            self.dst_saved_revocation_list_version
+                .store(0, Ordering::Relaxed);
         }
     }
 
@@ -1582,6 +1647,18 @@ impl PeerRouteServiceImpl {
         my_peer_info_updated || my_conn_info_updated || my_foreign_network_updated
     }
 
+    fn build_revocation_list(&self, session: &SyncRouteSession) -> Option<RevocationList> {
+        let identity = self.global_ctx.get_node_identity()?;
+        if identity.revocation_version()
+            <= session
+                .dst_saved_revocation_list_version
+                .load(Ordering::Relaxed)
+        {
+            return None;
+        }
+        identity.revocation_list()
+    }
+
     fn build_sync_request(
         &self,
         session: &SyncRouteSession,
@@ -1589,12 +1666,14 @@ impl PeerRouteServiceImpl {
         Option<Vec<RoutePeerInfo>>,
         Option<RouteConnBitmap>,
         Option<RouteForeignNetworkInfos>,
+        Option<RevocationList>,
     ) {
         let route_infos = self.build_route_info(session);
         let conn_bitmap = self.build_conn_bitmap(session);
         let foreign_network = self.build_foreign_network_info(session);
+        let revocation_list = self.build_revocation_list(session);
 
-        (route_infos, conn_bitmap, foreign_network)
+        (route_infos, conn_bitmap, foreign_network, revocation_list)
     }
 
     fn clear_expired_peer(&self) {
@@ -1613,6 +1692,12 @@ impl PeerRouteServiceImpl {
             self.synced_route_info.remove_peer(*p);
         }
 
+        // certificates may have expired since their route infos were accepted
+        if let Some(identity) = self.global_ctx.get_node_identity() {
+            self.synced_route_info
+                .remove_unverified_peers(self.my_peer_id, identity);
+        }
+
         // clear expired foreign network info
         let mut to_remove = Vec::new();
         for item in self.synced_route_info.foreign_network.iter() {
@@ -1683,10 +1768,12 @@ impl PeerRouteServiceImpl {
 
         let my_peer_id = self.my_peer_id;
 
-        let (peer_infos, conn_bitmap, foreign_network) = self.build_sync_request(&session);
+        let (peer_infos, conn_bitmap, foreign_network, revocation_list) =
+            self.build_sync_request(&session);
         if peer_infos.is_none()
             && conn_bitmap.is_none()
             && foreign_network.is_none()
+            && revocation_list.is_none()
             && !session.need_sync_initiator_info.load(Ordering::Relaxed)
             && !(sync_as_initiator && session.we_are_initiator.load(Ordering::Relaxed))
         {
@@ -1715,6 +1802,7 @@ impl PeerRouteServiceImpl {
             peer_infos: peer_infos.clone().map(|x| RoutePeerInfos { items: x }),
             conn_bitmap: conn_bitmap.clone().map(Into::into),
             foreign_network_infos: foreign_network.clone(),
+            revocation_list: revocation_list.clone(),
         };
 
         let mut ctrl = BaseController::default();
@@ -1776,6 +1864,12 @@ impl PeerRouteServiceImpl {
                 if let Some(foreign_network) = &foreign_network {
                     session.update_dst_saved_foreign_network_version(foreign_network);
                 }
+
+                if let Some(revocation_list) = &revocation_list {
+                    session
+                        .dst_saved_revocation_list_version
+                        .fetch_max(revocation_list.version(), Ordering::Relaxed);
+                }
             }
         }
         false
@@ -1858,6 +1952,7 @@ impl OspfRouteRpc for RouteSessionManager {
         let peer_infos = request.peer_infos.map(|x| x.items);
         let conn_bitmap = request.conn_bitmap.map(Into::into);
         let foreign_network = request.foreign_network_infos;
+        let revocation_list = request.revocation_list;
         let raw_peer_infos = if peer_infos.is_some() {
             let r = get_raw_peer_infos(&mut ctrl.get_raw_input().unwrap()).unwrap();
             assert_eq!(r.len(), peer_infos.as_ref().unwrap().len());
@@ -1875,6 +1970,7 @@ impl OspfRouteRpc for RouteSessionManager {
                 raw_peer_infos,
                 conn_bitmap,
                 foreign_network,
+                revocation_list,
             )
             .await;
 
@@ -2117,6 +2213,7 @@ impl RouteSessionManager {
         raw_peer_infos: Option<Vec<DynamicMessage>>,
         conn_bitmap: Option<RouteConnBitmap>,
         foreign_network: Option<RouteForeignNetworkInfos>,
+        revocation_list: Option<RevocationList>,
     ) -> Result<SyncRouteInfoResponse, Error> {
         let Some(service_impl) = self.service_impl.upgrade() else {
             return Err(Error::Stopped);
@@ -2138,6 +2235,10 @@ impl RouteSessionManager {
                 from_peer_id,
                 peer_infos,
                 raw_peer_infos.as_ref().unwrap(),
+                service_impl
+                    .global_ctx
+                    .get_node_identity()
+                    .map(|x| x.as_ref()),
             )?;
             service_impl
                 .synced_route_info
@@ -2170,6 +2271,19 @@ impl RouteSessionManager {
             service_impl.update_foreign_network_owner_map();
         }
 
+        if let (Some(revocation_list), Some(identity)) = (
+            &revocation_list,
+            service_impl.global_ctx.get_node_identity(),
+        ) {
+            // the sender has this version now, even if it is not newer than ours
+            session
+                .dst_saved_revocation_list_version
+                .fetch_max(revocation_list.version(), Ordering::Relaxed);
+            if let Err(e) = identity.update_revocation_list(revocation_list) {
+                tracing::warn!(?e, ?from_peer_id, "ignore invalid revocation list");
+            }
+        }
+
         tracing::info!(
             "handling sync_route_info rpc: from_peer_id: {:?}, is_initiator: {:?}, peer_infos: {:?}, conn_bitmap: {:?}, synced_route_info: {:?} session: {:?}, new_route_table: {:?}",
             from_peer_id, is_initiator, peer_infos, conn_bitmap, service_impl.synced_route_info, session, service_impl.route_table);
@@ -2241,6 +2355,27 @@ impl PeerRoute {
         }
     }
 
+    // drop the routes of revoked nodes as soon as a newer revocation list is accepted
+    async fn remove_revoked_peers_routine(
+        service_impl: Arc<PeerRouteServiceImpl>,
+        session_mgr: RouteSessionManager,
+    ) {
+        let Some(identity) = service_impl.global_ctx.get_node_identity().cloned() else {
+            return;
+        };
+        let mut revocation_recv = identity.subscribe_revocation();
+        while revocation_recv.changed().await.is_ok() {
+            if service_impl
+                .synced_route_info
+                .remove_unverified_peers(service_impl.my_peer_id, &identity)
+            {
+                service_impl.update_route_table_and_cached_local_conn_bitmap();
+                service_impl.update_foreign_network_owner_map();
+                session_mgr.sync_now("remove_revoked_peers");
+            }
+        }
+    }
+
     async fn maintain_session_tasks(
         session_mgr: RouteSessionManager,
         service_impl: Arc<PeerRouteServiceImpl>,
@@ -2306,6 +2441,14 @@ impl PeerRoute {
             .lock()
             .unwrap()
             .spawn(Self::clear_expired_peer(self.service_impl.clone()));
+
+        self.tasks
+            .lock()
+            .unwrap()
+            .spawn(Self::remove_revoked_peers_routine(
+                self.service_impl.clone(),
+                self.session_mgr.clone(),
+            ));
     }
 }
 
@@ -2399,13 +2542,13 @@ impl Route for PeerRoute {
         // only get peer id for proxy when the dst ipv4 is not in same network with us
         if self
             .global_ctx
-            .is_ip_in_same_network(&std::net::IpAddr::V4(*ipv4_addr))
+            .is_ip_in_same_network(&IpAddr::V4(*ipv4_addr))
         {
             tracing::trace!(?ipv4_addr, "ipv4 addr is in same network with us");
             return None;
         }
 
-        if let Some(peer_id) = route_table.get_peer_id_for_proxy(ipv4_addr) {
+        if let Some(peer_id) = route_table.get_peer_id_for_proxy(&IpAddr::V4(*ipv4_addr)) {
             return Some(peer_id);
         }
 
@@ -2419,10 +2562,18 @@ impl Route for PeerRoute {
             return Some(p.peer_id);
         }
 
-        // TODO: Add proxy support for IPv6 similar to IPv4
-        // if let Some(peer_id) = route_table.get_peer_id_for_proxy_ipv6(ipv6_addr) {
-        //     return Some(peer_id);
-        // }
+        // only get peer id for proxy when the dst ipv6 is not in same network with us
+        if self
+            .global_ctx
+            .is_ip_in_same_network(&IpAddr::V6(*ipv6_addr))
+        {
+            tracing::trace!(?ipv6_addr, "ipv6 addr is in same network with us");
+            return None;
+        }
+
+        if let Some(peer_id) = route_table.get_peer_id_for_proxy(&IpAddr::V6(*ipv6_addr)) {
+            return Some(peer_id);
+        }
 
         tracing::debug!(?ipv6_addr, "no peer id for ipv6");
         None
@@ -2518,7 +2669,7 @@ mod tests {
         time::Duration,
     };
 
-    use cidr::{Ipv4Cidr, Ipv4Inet, Ipv6Inet};
+    use cidr::{Ipv4Cidr, Ipv4Inet, Ipv6Cidr, Ipv6Inet};
     use dashmap::DashMap;
     use prost_reflect::{DynamicMessage, ReflectMessage};
 
@@ -3003,6 +3154,7 @@ mod tests {
         let ip: Ipv4Inet = "10.0.0.1/24".parse().unwrap();
         let ipv6: Ipv6Inet = "2001:db8::1/64".parse().unwrap();
         let proxy: Ipv4Cidr = "10.3.0.0/24".parse().unwrap();
+        let proxy_v6: Ipv6Cidr = "fd00:3::/64".parse().unwrap();
         let check_route_peer_id = async |p: Arc<PeerManager>| {
             let p = p.clone();
             wait_for_condition(
@@ -3015,6 +3167,11 @@ mod tests {
                             .get_peer_id_by_ipv4(&proxy.first_address())
                             .await
                             == Some(p.my_peer_id())
+                        && p_a
+                            .get_route()
+                            .get_peer_id_by_ipv6(&proxy_v6.first_address())
+                            .await
+                            == Some(p.my_peer_id())
                 },
                 Duration::from_secs(5),
             )
@@ -3025,7 +3182,11 @@ mod tests {
         p_c.get_global_ctx().set_ipv6(Some(ipv6));
         p_c.get_global_ctx()
             .config
-            .add_proxy_cidr(proxy, None)
+            .add_proxy_cidr(proxy.into(), None)
+            .unwrap();
+        p_c.get_global_ctx()
+            .config
+            .add_proxy_cidr(proxy_v6.into(), None)
             .unwrap();
         check_route_peer_id(p_c.clone()).await;
 
@@ -3033,7 +3194,11 @@ mod tests {
         p_b.get_global_ctx().set_ipv6(Some(ipv6));
         p_b.get_global_ctx()
             .config
-            .add_proxy_cidr(proxy, None)
+            .add_proxy_cidr(proxy.into(), None)
+            .unwrap();
+        p_b.get_global_ctx()
+            .config
+            .add_proxy_cidr(proxy_v6.into(), None)
             .unwrap();
         check_route_peer_id(p_b.clone()).await;
 
@@ -3041,7 +3206,10 @@ mod tests {
             .set_ipv4(Some("10.0.0.2/24".parse().unwrap()));
         p_b.get_global_ctx()
             .set_ipv6(Some("2001:db8::2/64".parse().unwrap()));
-        p_b.get_global_ctx().config.remove_proxy_cidr(proxy);
+        p_b.get_global_ctx().config.remove_proxy_cidr(proxy.into());
+        p_b.get_global_ctx()
+            .config
+            .remove_proxy_cidr(proxy_v6.into());
         check_route_peer_id(p_c.clone()).await;
     }
 }
@@ -5,11 +5,11 @@ use crate::{
     proto::{
         cli::{
             AclManageRpc, DumpRouteRequest, DumpRouteResponse, GetAclStatsRequest,
-            GetAclStatsResponse, GetWhitelistRequest, GetWhitelistResponse,
-            ListForeignNetworkRequest, ListForeignNetworkResponse, ListGlobalForeignNetworkRequest,
-            ListGlobalForeignNetworkResponse, ListPeerRequest, ListPeerResponse, ListRouteRequest,
-            ListRouteResponse, PeerInfo, PeerManageRpc, SetWhitelistRequest, SetWhitelistResponse,
-            ShowNodeInfoRequest, ShowNodeInfoResponse,
+            GetAclStatsResponse, GetWhitelistRequest, GetWhitelistResponse, ListExitFlowRequest,
+            ListExitFlowResponse, ListForeignNetworkRequest, ListForeignNetworkResponse,
+            ListGlobalForeignNetworkRequest, ListGlobalForeignNetworkResponse, ListPeerRequest,
+            ListPeerResponse, ListRouteRequest, ListRouteResponse, PeerInfo, PeerManageRpc,
+            SetWhitelistRequest, SetWhitelistResponse, ShowNodeInfoRequest, ShowNodeInfoResponse,
         },
         rpc_types::{self, controller::BaseController},
     },
@@ -142,6 +142,14 @@ impl PeerManageRpc for PeerManagerRpcService {
             node_info: Some(self.peer_manager.get_my_info().await),
         })
     }
+
+    async fn list_exit_flow(
+        &self,
+        _: BaseController,
+        _request: ListExitFlowRequest,
+    ) -> Result<ListExitFlowResponse, rpc_types::error::Error> {
+        Ok(self.peer_manager.list_exit_flows(usize::MAX))
+    }
 }
 
 #[async_trait::async_trait]
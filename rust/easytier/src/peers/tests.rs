@@ -4,7 +4,10 @@ use crate::{
     common::{
         error::Error,
         global_ctx::{
-            tests::{get_mock_global_ctx, get_mock_global_ctx_with_network},
+            tests::{
+                get_mock_global_ctx, get_mock_global_ctx_with_identity,
+                get_mock_global_ctx_with_network,
+            },
             NetworkIdentity,
         },
         PeerId,
@@ -37,6 +40,16 @@ pub async fn create_mock_peer_manager_with_name(network_name: String) -> Arc<Pee
     peer_mgr
 }
 
+pub async fn create_mock_peer_manager_with_identity(
+    owner_key: &ed25519_dalek::SigningKey,
+) -> Arc<PeerManager> {
+    let (s, _r) = create_packet_recv_chan();
+    let g = get_mock_global_ctx_with_identity(owner_key, &[]);
+    let peer_mgr = Arc::new(PeerManager::new(RouteAlgoType::Ospf, g, s));
+    peer_mgr.run().await.unwrap();
+    peer_mgr
+}
+
 pub async fn connect_peer_manager(client: Arc<PeerManager>, server: Arc<PeerManager>) {
     let (a_ring, b_ring) = create_ring_tunnel_pair();
     let a_mgr_copy = client.clone();
@@ -39,7 +39,7 @@ impl Display for Rule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "[name: '{}', prio: {}, action: {:?}, enabled: {}, proto: {:?}, ports: {:?}, src_ports: {:?}, src_ips: {:?}, dst_ips: {:?}, stateful: {}, rate: {}, burst: {}]",
+            "[name: '{}', prio: {}, action: {:?}, enabled: {}, proto: {:?}, ports: {:?}, src_ports: {:?}, src_ips: {:?}, dst_ips: {:?}, src_hosts: {:?}, dst_hosts: {:?}, stateful: {}, rate: {}, burst: {}]",
             self.name,
             self.priority,
             Action::try_from(self.action).unwrap_or(Action::Noop),
@@ -49,6 +49,8 @@ impl Display for Rule {
             self.source_ports,
             self.source_ips,
             self.destination_ips,
+            self.source_hosts,
+            self.destination_hosts,
             self.stateful,
             self.rate_limit,
             self.burst_limit
@@ -56,6 +58,65 @@ impl Display for Rule {
     }
 }
 
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+impl TimeWindow {
+    /// Local (minute of day, day of week) at `unix_secs`, with 0 = Sunday.
+    fn local_time(&self, unix_secs: u64) -> (i64, i64) {
+        let local_minutes = (unix_secs / 60) as i64 + self.timezone_offset as i64;
+        let days = local_minutes.div_euclid(MINUTES_PER_DAY);
+        // 1970-01-01 was a Thursday
+        (
+            local_minutes.rem_euclid(MINUTES_PER_DAY),
+            (days + 4).rem_euclid(7),
+        )
+    }
+
+    fn includes_day(&self, day: i64) -> bool {
+        self.days_of_week.is_empty() || self.days_of_week.iter().any(|d| *d as i64 == day)
+    }
+
+    /// Whether the window is open at `unix_secs`.
+    ///
+    /// Equal start and end times cover the whole day. An end time before the
+    /// start time spans midnight and belongs to the day the window starts on.
+    pub fn contains(&self, unix_secs: u64) -> bool {
+        let (minute, day) = self.local_time(unix_secs);
+        let start = self.start_time as i64;
+        let end = self.end_time as i64;
+        match start.cmp(&end) {
+            std::cmp::Ordering::Equal => self.includes_day(day),
+            std::cmp::Ordering::Less => self.includes_day(day) && minute >= start && minute < end,
+            std::cmp::Ordering::Greater => {
+                (self.includes_day(day) && minute >= start)
+                    || (self.includes_day((day + 6) % 7) && minute < end)
+            }
+        }
+    }
+
+    /// The first minute boundary after `unix_secs` at which `contains` may change.
+    pub fn next_boundary(&self, unix_secs: u64) -> u64 {
+        let (minute, _) = self.local_time(unix_secs);
+        let mut boundaries = vec![
+            self.start_time as i64 % MINUTES_PER_DAY,
+            self.end_time as i64 % MINUTES_PER_DAY,
+        ];
+        if !self.days_of_week.is_empty() {
+            // day of week changes at local midnight
+            boundaries.push(0);
+        }
+        let delta = boundaries
+            .into_iter()
+            .map(|b| match (b - minute).rem_euclid(MINUTES_PER_DAY) {
+                0 => MINUTES_PER_DAY,
+                d => d,
+            })
+            .min()
+            .unwrap();
+        (unix_secs / 60 + delta as u64) * 60
+    }
+}
+
 impl Display for StatItem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
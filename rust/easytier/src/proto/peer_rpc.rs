@@ -38,6 +38,16 @@ impl PeerGroupInfo {
     }
 }
 
+impl RevocationList {
+    /// Version of the list without checking the signature, 0 if the body is malformed.
+    pub fn version(&self) -> u64 {
+        use prost::Message as _;
+        RevocationListBody::decode(self.body.as_slice())
+            .map(|body| body.version)
+            .unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
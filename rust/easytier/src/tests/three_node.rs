@@ -1055,6 +1055,212 @@ pub async fn socks5_vpn_portal(#[values("10.144.144.1", "10.144.144.3")] dst_add
     drop_insts(_insts).await;
 }
 
+// udp associate over a raw control connection, tokio_socks only speaks the tcp commands.
+// returns the control stream, which keeps the association open, and the udp relay address.
+#[cfg(feature = "socks5")]
+async fn socks5_udp_associate(
+    portal: &str,
+    user: &str,
+    password: &str,
+) -> (tokio::net::TcpStream, std::net::SocketAddr) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut stream = tokio::net::TcpStream::connect(portal).await.unwrap();
+    stream.write_all(&[5, 1, 2]).await.unwrap();
+    let mut method = [0u8; 2];
+    stream.read_exact(&mut method).await.unwrap();
+    assert_eq!(method, [5, 2]);
+
+    let mut auth = vec![1, user.len() as u8];
+    auth.extend_from_slice(user.as_bytes());
+    auth.push(password.len() as u8);
+    auth.extend_from_slice(password.as_bytes());
+    stream.write_all(&auth).await.unwrap();
+    let mut auth_reply = [0u8; 2];
+    stream.read_exact(&mut auth_reply).await.unwrap();
+    assert_eq!(auth_reply, [1, 0]);
+
+    stream
+        .write_all(&[5, 3, 0, 1, 0, 0, 0, 0, 0, 0])
+        .await
+        .unwrap();
+    let mut reply = [0u8; 10];
+    stream.read_exact(&mut reply).await.unwrap();
+    assert_eq!(reply[..4], [5, 0, 0, 1]);
+    let relay_ip = std::net::Ipv4Addr::new(reply[4], reply[5], reply[6], reply[7]);
+    let relay_port = u16::from_be_bytes([reply[8], reply[9]]);
+    (stream, (relay_ip, relay_port).into())
+}
+
+#[cfg(feature = "socks5")]
+fn socks5_udp_datagram(target: std::net::SocketAddrV4, data: &[u8]) -> Vec<u8> {
+    let mut buf = vec![0, 0, 0, 1];
+    buf.extend_from_slice(&target.ip().octets());
+    buf.extend_from_slice(&target.port().to_be_bytes());
+    buf.extend_from_slice(data);
+    buf
+}
+
+#[cfg(feature = "socks5")]
+#[tokio::test]
+#[serial_test::serial]
+pub async fn socks5_portal_commands_and_policy() {
+    use crate::common::config::Socks5UserConfig;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+    use tokio_socks::{
+        tcp::socks5::{Socks5Listener, Socks5Stream},
+        TargetAddr,
+    };
+
+    let insts = init_three_node_ex(
+        "tcp",
+        |cfg| {
+            if cfg.get_inst_name() == "inst1" {
+                cfg.set_socks5_users(vec![
+                    Socks5UserConfig {
+                        username: "alice".to_string(),
+                        password: "alice-pass".to_string(),
+                        allowed_cidrs: vec![],
+                        allowed_ports: vec![],
+                    },
+                    Socks5UserConfig {
+                        username: "bob".to_string(),
+                        password: "bob-pass".to_string(),
+                        allowed_cidrs: vec!["10.144.144.1/32".parse().unwrap()],
+                        allowed_ports: vec!["22230-22231".to_string()],
+                    },
+                ]);
+            }
+            cfg
+        },
+        false,
+    )
+    .await;
+
+    let net_a = NetNS::new(Some("net_a".into()));
+    let net_c = NetNS::new(Some("net_c".into()));
+    let portal = "127.0.0.1:12345";
+    let connect_portal = || net_a.run_async(|| TcpStream::connect(portal));
+
+    // a wrong password is refused before any command runs
+    let ret = Socks5Stream::connect_with_password_and_socket(
+        connect_portal().await.unwrap(),
+        "10.144.144.3:22232",
+        "alice",
+        "wrong",
+    )
+    .await;
+    assert!(ret.is_err());
+
+    // bob may only reach 10.144.144.1 on ports 22230-22231
+    for target in ["10.144.144.3:22230", "10.144.144.1:22232"] {
+        let ret = Socks5Stream::connect_with_password_and_socket(
+            connect_portal().await.unwrap(),
+            target,
+            "bob",
+            "bob-pass",
+        )
+        .await;
+        assert!(
+            matches!(ret, Err(tokio_socks::Error::ConnectionNotAllowedByRuleset)),
+            "{}: {:?}",
+            target,
+            ret.map(|_| ())
+        );
+    }
+
+    // udp associate round trip to a service on inst3
+    let echo = net_c
+        .run_async(|| UdpSocket::bind("0.0.0.0:22233"))
+        .await
+        .unwrap();
+    let echo_task = tokio::spawn(async move {
+        let mut buf = [0u8; 1024];
+        let (len, from) = echo.recv_from(&mut buf).await.unwrap();
+        echo.send_to(&buf[..len], from).await.unwrap();
+    });
+    let (_control, relay_addr) = net_a
+        .run_async(|| socks5_udp_associate(portal, "alice", "alice-pass"))
+        .await;
+    let client = net_a
+        .run_async(|| UdpSocket::bind("127.0.0.1:0"))
+        .await
+        .unwrap();
+    let target = "10.144.144.3:22233".parse().unwrap();
+    let datagram = socks5_udp_datagram(target, b"udp-hello");
+    let mut buf = [0u8; 1024];
+    let len = tokio::time::timeout(Duration::from_secs(5), async {
+        // the first datagram may arrive before the route to inst3 is ready, so resend it
+        loop {
+            client.send_to(&datagram, relay_addr).await.unwrap();
+            if let Ok(ret) =
+                tokio::time::timeout(Duration::from_millis(500), client.recv_from(&mut buf)).await
+            {
+                return ret.unwrap().0;
+            }
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(buf[..len], socks5_udp_datagram(target, b"udp-hello")[..]);
+    echo_task.await.unwrap();
+
+    // bind with the expected peer ip, inst3 connects to the address from the first reply
+    let listener = Socks5Listener::bind_with_password_and_socket(
+        connect_portal().await.unwrap(),
+        "10.144.144.3:0",
+        "alice",
+        "alice-pass",
+    )
+    .await
+    .unwrap();
+    let TargetAddr::Ip(bind_addr) = listener.bind_addr() else {
+        panic!("bind reply should carry an ip");
+    };
+    let peer_task = tokio::spawn(async move {
+        let _g = NetNS::new(Some("net_c".into())).guard();
+        let mut stream = TcpStream::connect(bind_addr).await.unwrap();
+        stream.write_all(b"bind-hello").await.unwrap();
+        let mut buf = [0u8; 8];
+        stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"bind-ack");
+    });
+    let mut inbound = listener.accept().await.unwrap();
+    let mut buf = [0u8; 10];
+    inbound.read_exact(&mut buf).await.unwrap();
+    assert_eq!(&buf, b"bind-hello");
+    inbound.write_all(b"bind-ack").await.unwrap();
+    peer_task.await.unwrap();
+
+    // bob binds for any peer, but inst3 is outside his cidrs so the accepted peer is refused
+    let listener = Socks5Listener::bind_with_password_and_socket(
+        connect_portal().await.unwrap(),
+        "0.0.0.0:0",
+        "bob",
+        "bob-pass",
+    )
+    .await
+    .unwrap();
+    let TargetAddr::Ip(bind_addr) = listener.bind_addr() else {
+        panic!("bind reply should carry an ip");
+    };
+    let peer_task = tokio::spawn(async move {
+        let _g = NetNS::new(Some("net_c".into())).guard();
+        let _ = TcpStream::connect(bind_addr).await;
+    });
+    let ret = listener.accept().await;
+    assert!(matches!(
+        ret,
+        Err(tokio_socks::Error::ConnectionNotAllowedByRuleset)
+    ));
+    peer_task.await.unwrap();
+
+    drop_insts(insts).await;
+}
+
 #[tokio::test]
 #[serial_test::serial]
 pub async fn foreign_network_functional_cluster() {
@@ -1230,24 +1436,28 @@ pub async fn port_forward_test(
                         bind_addr: "0.0.0.0:23456".parse().unwrap(),
                         dst_addr: "10.144.144.3:23456".parse().unwrap(),
                         proto: "tcp".to_string(),
+                        ..Default::default()
                     },
                     // test port forward to subnet proxy
                     PortForwardConfig {
                         bind_addr: "0.0.0.0:23457".parse().unwrap(),
                         dst_addr: "10.1.2.4:23457".parse().unwrap(),
                         proto: "tcp".to_string(),
+                        ..Default::default()
                     },
                     // test udp port forward to other virtual node
                     PortForwardConfig {
                         bind_addr: "0.0.0.0:23458".parse().unwrap(),
                         dst_addr: "10.144.144.3:23458".parse().unwrap(),
                         proto: "udp".to_string(),
+                        ..Default::default()
                     },
                     // test udp port forward to subnet proxy
                     PortForwardConfig {
                         bind_addr: "0.0.0.0:23459".parse().unwrap(),
                         dst_addr: "10.1.2.4:23459".parse().unwrap(),
                         proto: "udp".to_string(),
+                        ..Default::default()
                     },
                 ]);
 
@@ -1341,6 +1551,117 @@ pub async fn port_forward_test(
     drop_insts(_insts).await;
 }
 
+#[cfg(feature = "socks5")]
+#[tokio::test]
+#[serial_test::serial]
+pub async fn port_forward_range_and_policy_test() {
+    use tokio::{io::AsyncReadExt as _, net::TcpStream};
+
+    use crate::tunnel::common::tests::_tunnel_pingpong_netns;
+
+    prepare_linux_namespaces();
+
+    let range_cfg = PortForwardConfig {
+        bind_addr: "0.0.0.0:23460".parse().unwrap(),
+        dst_addr: "10.144.144.3:23460".parse().unwrap(),
+        proto: "tcp".to_string(),
+        bind_port_end: Some(23461),
+        ..Default::default()
+    };
+    let denied_cfg = PortForwardConfig {
+        bind_addr: "0.0.0.0:23462".parse().unwrap(),
+        dst_addr: "10.144.144.3:23462".parse().unwrap(),
+        proto: "tcp".to_string(),
+        allowed_sources: vec!["10.144.144.0/24".parse().unwrap()],
+        ..Default::default()
+    };
+    let udp_cfg = PortForwardConfig {
+        bind_addr: "0.0.0.0:23463".parse().unwrap(),
+        dst_addr: "10.144.144.3:23463".parse().unwrap(),
+        proto: "udp".to_string(),
+        udp_idle_timeout: Some(1),
+        ..Default::default()
+    };
+    let cfgs = vec![range_cfg.clone(), denied_cfg.clone(), udp_cfg.clone()];
+
+    let insts = init_three_node_ex(
+        "udp",
+        |cfg| {
+            if cfg.get_inst_name() == "inst1" {
+                cfg.set_port_forwards(cfgs.clone());
+            }
+            cfg
+        },
+        false,
+    )
+    .await;
+    let socks5_server = insts[0].get_socks5_server();
+    let stats = |cfg: &PortForwardConfig| socks5_server.get_port_forward_stats(cfg).unwrap();
+
+    // every port of the range is bound and forwarded to the port at the same offset
+    for port in [23460, 23461] {
+        let tcp_listener =
+            TcpTunnelListener::new(format!("tcp://0.0.0.0:{}", port).parse().unwrap());
+        let tcp_connector =
+            TcpTunnelConnector::new(format!("tcp://127.0.0.1:{}", port).parse().unwrap());
+        _tunnel_pingpong_netns(
+            tcp_listener,
+            tcp_connector,
+            NetNS::new(Some("net_c".into())),
+            NetNS::new(Some("net_a".into())),
+            vec![7; 64],
+        )
+        .await;
+    }
+    wait_for_condition(
+        || async {
+            let s = stats(&range_cfg);
+            s.total_connections == 2 && s.rx_bytes > 0 && s.tx_bytes > 0
+        },
+        Duration::from_secs(5),
+    )
+    .await;
+
+    // the client connects from loopback, which is not in allowed_sources
+    let mut stream = NetNS::new(Some("net_a".into()))
+        .run_async(|| TcpStream::connect("127.0.0.1:23462"))
+        .await
+        .unwrap();
+    let mut buf = [0u8; 16];
+    let ret = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf)).await;
+    assert!(matches!(ret, Ok(Ok(0)) | Ok(Err(_))));
+    let s = stats(&denied_cfg);
+    assert_eq!(s.rejected, 1);
+    assert_eq!(s.total_connections, 0);
+
+    // a udp session counts as active until it is idle for longer than udp_idle_timeout
+    let _server_socket = NetNS::new(Some("net_c".into()))
+        .run_async(|| UdpSocket::bind("0.0.0.0:23463"))
+        .await
+        .unwrap();
+    let client_socket = NetNS::new(Some("net_a".into()))
+        .run_async(|| UdpSocket::bind("0.0.0.0:0"))
+        .await
+        .unwrap();
+    client_socket
+        .send_to(b"hello", "127.0.0.1:23463")
+        .await
+        .unwrap();
+    wait_for_condition(
+        || async { stats(&udp_cfg).active_connections == 1 },
+        Duration::from_secs(5),
+    )
+    .await;
+    wait_for_condition(
+        || async { stats(&udp_cfg).active_connections == 0 },
+        Duration::from_secs(5),
+    )
+    .await;
+    assert_eq!(stats(&udp_cfg).total_connections, 1);
+
+    drop_insts(insts).await;
+}
+
 #[rstest::rstest]
 #[serial_test::serial]
 #[tokio::test]
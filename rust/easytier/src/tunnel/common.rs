@@ -439,6 +439,70 @@ pub(crate) fn setup_sokcet2(
     )
 }
 
+/// Returns the local ip the os would send from to reach `dst`, no packet is sent.
+pub(crate) async fn get_route_src_ip(dst: SocketAddr) -> Result<IpAddr, std::io::Error> {
+    let bind_addr = if dst.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+    let socket = tokio::net::UdpSocket::bind(bind_addr).await?;
+    socket.connect(dst).await?;
+    Ok(socket.local_addr()?.ip())
+}
+
+pub(crate) const LOCAL_ADDR_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Detects local address changes that break a udp based tunnel to `dst`.
+///
+/// For sockets bound to a wildcard address the route source ip to `dst` is tracked, for sockets
+/// bound to a specific ip the ip disappearing from the host counts as a change.
+#[derive(Debug)]
+pub(crate) struct LocalAddrWatcher {
+    dst: SocketAddr,
+    bound_ip: IpAddr,
+    route_ip: Option<IpAddr>,
+}
+
+impl LocalAddrWatcher {
+    pub async fn new(dst: SocketAddr, bound_ip: IpAddr) -> Self {
+        let route_ip = if bound_ip.is_unspecified() {
+            get_route_src_ip(dst).await.ok()
+        } else {
+            None
+        };
+        Self {
+            dst,
+            bound_ip,
+            route_ip,
+        }
+    }
+
+    async fn check(&self) -> Option<Option<IpAddr>> {
+        if !self.bound_ip.is_unspecified() {
+            // binding fails with EADDRNOTAVAIL once the ip is removed from the host
+            let ret = std::net::UdpSocket::bind((self.bound_ip, 0));
+            return matches!(ret, Err(e) if e.kind() == std::io::ErrorKind::AddrNotAvailable)
+                .then_some(None);
+        }
+
+        // no route at all means we are offline, wait for a new route instead of migrating
+        let ip = get_route_src_ip(self.dst).await.ok()?;
+        (self.route_ip != Some(ip)).then_some(Some(ip))
+    }
+
+    /// Waits until the local address changes. Returns the new route source ip for wildcard
+    /// bound sockets, which should be passed to `accept` once the tunnel moved to it.
+    pub async fn changed(&self) -> Option<IpAddr> {
+        loop {
+            tokio::time::sleep(LOCAL_ADDR_CHECK_INTERVAL).await;
+            if let Some(ret) = self.check().await {
+                return ret;
+            }
+        }
+    }
+
+    pub fn accept(&mut self, route_ip: Option<IpAddr>) {
+        self.route_ip = route_ip;
+    }
+}
+
 pub fn reserve_buf(buf: &mut BytesMut, min_size: usize, max_size: usize) {
     if buf.capacity() < min_size {
         buf.reserve(max_size);
@@ -0,0 +1,583 @@
+// Forward error correction for the udp tunnel.
+//
+// Packets are grouped and protected with Reed-Solomon parity shards. Data shards are sent
+// immediately so FEC adds no latency when nothing is lost, parity shards follow once the
+// group is full or the flush interval expires. The receiver delivers data shards as they
+// arrive and rebuilds missing ones as soon as enough shards of a group are received.
+//
+// The tunnel can not see the loss rate measured by PeerConn, so both ends measure loss
+// from the shard sequence numbers and report it back in every shard header. In adaptive
+// mode the sender derives the parity shard count from the loss reported by the remote.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use reed_solomon_erasure::galois_8::ReedSolomon;
+use tokio::time::Instant;
+use zerocopy::{AsBytes, FromBytes};
+
+use super::{
+    packet_def::{FecShardHeader, FEC_SHARD_HEADER_SIZE},
+    TunnelError,
+};
+
+pub const DEFAULT_FEC_DATA_SHARDS: u8 = 8;
+pub const DEFAULT_FEC_PARITY_SHARDS: u8 = 2;
+const MAX_FEC_SHARDS: u8 = 64;
+
+// a partial group is closed after this interval, keeps the recovery delay bounded
+const FEC_FLUSH_INTERVAL: Duration = Duration::from_millis(5);
+// number of recent groups the decoder keeps for recovery
+const FEC_DECODER_GROUPS: usize = 64;
+// number of sequence numbers in one loss measurement window
+const FEC_LOSS_WINDOW: u32 = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FecConfig {
+    pub data_shards: u8,
+    pub parity_shards: u8,
+    // derive the parity shard count from the loss rate reported by the remote
+    pub adaptive: bool,
+}
+
+impl Default for FecConfig {
+    fn default() -> Self {
+        Self {
+            data_shards: DEFAULT_FEC_DATA_SHARDS,
+            parity_shards: DEFAULT_FEC_PARITY_SHARDS,
+            adaptive: true,
+        }
+    }
+}
+
+impl FecConfig {
+    /// Parse the `fec` query param of a udp url.
+    ///
+    /// Accepts `auto`, `<data>:auto` or `<data>:<parity>`, returns None for `off`.
+    pub fn parse(s: &str) -> Result<Option<Self>, TunnelError> {
+        let invalid = || TunnelError::InvalidAddr(format!("invalid fec param: {}", s));
+        let s = s.trim();
+        if s == "off" {
+            return Ok(None);
+        }
+        if s == "auto" || s.is_empty() {
+            return Ok(Some(Self::default()));
+        }
+
+        let (data, parity) = s.split_once(':').ok_or_else(invalid)?;
+        let data_shards: u8 = data.parse().map_err(|_| invalid())?;
+        let (parity_shards, adaptive) = if parity == "auto" {
+            (DEFAULT_FEC_PARITY_SHARDS.min(data_shards), true)
+        } else {
+            (parity.parse().map_err(|_| invalid())?, false)
+        };
+        if data_shards == 0 || data_shards > MAX_FEC_SHARDS || parity_shards > MAX_FEC_SHARDS {
+            return Err(invalid());
+        }
+
+        Ok(Some(Self {
+            data_shards,
+            parity_shards,
+            adaptive,
+        }))
+    }
+
+    pub fn url_param(url: &url::Url) -> Option<String> {
+        url.query_pairs()
+            .find(|(k, _)| k == "fec")
+            .map(|(_, v)| v.into_owned())
+    }
+
+    fn parity_for_loss(&self, loss_percent: u8) -> u8 {
+        if !self.adaptive {
+            return self.parity_shards;
+        }
+        // twice the expected number of lost shards, at least one so short bursts are covered
+        let expected = (self.data_shards as u32 * loss_percent as u32 * 2).div_ceil(100);
+        (expected as u8).clamp(1, self.data_shards)
+    }
+}
+
+/// Loss rates shared by the encoder and decoder of one connection.
+#[derive(Debug, Default)]
+pub struct FecLinkState {
+    // loss in percent we observe on packets from the remote
+    local_loss: AtomicU8,
+    // loss in percent the remote observes on our packets
+    remote_loss: AtomicU8,
+}
+
+impl FecLinkState {
+    pub fn local_loss(&self) -> u8 {
+        self.local_loss.load(Ordering::Relaxed)
+    }
+
+    pub fn remote_loss(&self) -> u8 {
+        self.remote_loss.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Default)]
+struct CodecCache(HashMap<(usize, usize), ReedSolomon>);
+
+impl CodecCache {
+    fn get(&mut self, data_shards: usize, parity_shards: usize) -> Option<&ReedSolomon> {
+        if data_shards == 0 || parity_shards == 0 {
+            return None;
+        }
+        if !self.0.contains_key(&(data_shards, parity_shards)) {
+            let codec = ReedSolomon::new(data_shards, parity_shards).ok()?;
+            self.0.insert((data_shards, parity_shards), codec);
+        }
+        self.0.get(&(data_shards, parity_shards))
+    }
+}
+
+fn build_shard(header: &FecShardHeader, body: &[u8]) -> Vec<u8> {
+    let mut shard = Vec::with_capacity(FEC_SHARD_HEADER_SIZE + body.len());
+    shard.extend_from_slice(header.as_bytes());
+    shard.extend_from_slice(body);
+    shard
+}
+
+pub struct FecEncoder {
+    config: FecConfig,
+    state: Arc<FecLinkState>,
+    codecs: CodecCache,
+
+    seq: u32,
+    group_id: u32,
+    // length prefixed payloads of the current group
+    pending: Vec<Vec<u8>>,
+    group_deadline: Option<Instant>,
+}
+
+impl FecEncoder {
+    pub fn new(config: FecConfig, state: Arc<FecLinkState>) -> Self {
+        Self {
+            config,
+            state,
+            codecs: CodecCache::default(),
+            seq: 0,
+            group_id: rand::random(),
+            pending: Vec::new(),
+            group_deadline: None,
+        }
+    }
+
+    fn next_header(&mut self, index: u8) -> FecShardHeader {
+        let mut header = FecShardHeader::default();
+        header.seq.set(self.seq);
+        header.group_id.set(self.group_id);
+        header.index = index;
+        header.peer_loss = self.state.local_loss();
+        self.seq = self.seq.wrapping_add(1);
+        header
+    }
+
+    pub fn parity_shards(&self) -> u8 {
+        self.config.parity_for_loss(self.state.remote_loss())
+    }
+
+    /// Time at which the current partial group has to be flushed.
+    pub fn flush_deadline(&self) -> Option<Instant> {
+        self.group_deadline
+    }
+
+    /// Wrap a udp payload into a data shard. The shard and the parity shards of the
+    /// group it completes are appended to `out`.
+    pub fn encode(&mut self, payload: &[u8], out: &mut Vec<Vec<u8>>) {
+        let mut body = Vec::with_capacity(2 + payload.len());
+        body.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+        body.extend_from_slice(payload);
+
+        let header = self.next_header(self.pending.len() as u8);
+        out.push(build_shard(&header, &body));
+        self.pending.push(body);
+
+        if self.group_deadline.is_none() {
+            self.group_deadline = Some(Instant::now() + FEC_FLUSH_INTERVAL);
+        }
+        if self.pending.len() >= self.config.data_shards as usize {
+            self.flush(out);
+        }
+    }
+
+    /// Close the current group and append its parity shards to `out`.
+    pub fn flush(&mut self, out: &mut Vec<Vec<u8>>) {
+        self.group_deadline = None;
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let mut pending = std::mem::take(&mut self.pending);
+        let data_shards = pending.len();
+        let parity_shards = self.parity_shards() as usize;
+        let shard_len = pending.iter().map(|s| s.len()).max().unwrap_or(0);
+
+        if let Some(codec) = self.codecs.get(data_shards, parity_shards) {
+            for shard in pending.iter_mut() {
+                shard.resize(shard_len, 0);
+            }
+            pending.resize(data_shards + parity_shards, vec![0; shard_len]);
+            match codec.encode(&mut pending) {
+                Ok(()) => {
+                    for (i, parity) in pending.iter().enumerate().skip(data_shards) {
+                        let mut header = self.next_header(i as u8);
+                        header.data_shards = data_shards as u8;
+                        header.parity_shards = parity_shards as u8;
+                        out.push(build_shard(&header, parity));
+                    }
+                }
+                Err(e) => tracing::warn!(?e, "fec encode parity shards failed"),
+            }
+        }
+
+        self.group_id = self.group_id.wrapping_add(1);
+    }
+}
+
+#[derive(Debug, Default)]
+struct LossEstimator {
+    window_start: Option<u32>,
+    highest: u32,
+    received: u32,
+    loss: f32,
+}
+
+impl LossEstimator {
+    fn record(&mut self, seq: u32) {
+        let Some(start) = self.window_start else {
+            self.window_start = Some(seq);
+            self.highest = seq;
+            self.received = 1;
+            return;
+        };
+        // late packets of a finished window
+        if (seq.wrapping_sub(start) as i32) < 0 {
+            return;
+        }
+        self.received += 1;
+        if (seq.wrapping_sub(self.highest) as i32) > 0 {
+            self.highest = seq;
+        }
+
+        let span = self.highest.wrapping_sub(start).wrapping_add(1);
+        if span >= FEC_LOSS_WINDOW {
+            let sample = 1.0 - (self.received.min(span) as f32 / span as f32);
+            self.loss = self.loss * 0.5 + sample * 0.5;
+            self.window_start = Some(self.highest.wrapping_add(1));
+            self.received = 0;
+        }
+    }
+
+    fn loss_percent(&self) -> u8 {
+        (self.loss * 100.0).round().clamp(0.0, 100.0) as u8
+    }
+}
+
+#[derive(Debug, Default)]
+struct FecGroup {
+    // known once a parity shard is received
+    data_shards: usize,
+    parity_shards: usize,
+    shards: HashMap<usize, Vec<u8>>,
+    delivered: Vec<bool>,
+    done: bool,
+}
+
+impl FecGroup {
+    fn mark_delivered(&mut self, index: usize) -> bool {
+        if self.delivered.len() <= index {
+            self.delivered.resize(index + 1, false);
+        }
+        !std::mem::replace(&mut self.delivered[index], true)
+    }
+}
+
+fn unwrap_data_shard(body: &[u8]) -> Option<Vec<u8>> {
+    let len = u16::from_le_bytes(body.get(..2)?.try_into().unwrap()) as usize;
+    body.get(2..2 + len).map(|v| v.to_vec())
+}
+
+pub struct FecDecoder {
+    state: Arc<FecLinkState>,
+    codecs: CodecCache,
+    groups: HashMap<u32, FecGroup>,
+    group_order: VecDeque<u32>,
+    loss: LossEstimator,
+}
+
+impl FecDecoder {
+    pub fn new(state: Arc<FecLinkState>) -> Self {
+        Self {
+            state,
+            codecs: CodecCache::default(),
+            groups: HashMap::new(),
+            group_order: VecDeque::new(),
+            loss: LossEstimator::default(),
+        }
+    }
+
+    fn is_expired(&self, group_id: u32) -> bool {
+        let Some(oldest) = self.group_order.front() else {
+            return false;
+        };
+        (group_id.wrapping_sub(*oldest) as i32) < 0
+    }
+
+    fn get_group(&mut self, group_id: u32) -> Option<&mut FecGroup> {
+        if !self.groups.contains_key(&group_id) {
+            if self.is_expired(group_id) {
+                return None;
+            }
+            while self.group_order.len() >= FEC_DECODER_GROUPS {
+                let oldest = self.group_order.pop_front().unwrap();
+                self.groups.remove(&oldest);
+            }
+            self.group_order.push_back(group_id);
+            self.groups.insert(group_id, FecGroup::default());
+        }
+        self.groups.get_mut(&group_id)
+    }
+
+    /// Handle one fec packet, the payloads that can be delivered are appended to `out`.
+    pub fn decode(&mut self, packet: &[u8], out: &mut Vec<Vec<u8>>) -> Result<(), TunnelError> {
+        let header = FecShardHeader::ref_from_prefix(packet)
+            .ok_or_else(|| TunnelError::InvalidPacket("fec packet too short".to_owned()))?;
+        let body = &packet[FEC_SHARD_HEADER_SIZE..];
+
+        self.loss.record(header.seq.get());
+        self.state
+            .local_loss
+            .store(self.loss.loss_percent(), Ordering::Relaxed);
+        self.state
+            .remote_loss
+            .store(header.peer_loss.min(100), Ordering::Relaxed);
+
+        let index = header.index as usize;
+        let data_shards = header.data_shards as usize;
+        let parity_shards = header.parity_shards as usize;
+        let is_parity = data_shards > 0;
+        if is_parity && (index < data_shards || index >= data_shards + parity_shards) {
+            return Err(TunnelError::InvalidPacket(
+                "fec parity shard index out of range".to_owned(),
+            ));
+        }
+        if index >= (MAX_FEC_SHARDS as usize) * 2 {
+            return Err(TunnelError::InvalidPacket(
+                "fec shard index out of range".to_owned(),
+            ));
+        }
+
+        let Some(group) = self.get_group(header.group_id.get()) else {
+            return Ok(());
+        };
+        if group.done {
+            return Ok(());
+        }
+
+        if is_parity {
+            group.data_shards = data_shards;
+            group.parity_shards = parity_shards;
+        } else {
+            if !group.mark_delivered(index) {
+                return Ok(());
+            }
+            if let Some(payload) = unwrap_data_shard(body) {
+                out.push(payload);
+            }
+        }
+        group.shards.insert(index, body.to_vec());
+
+        let group_id = header.group_id.get();
+        self.try_recover(group_id, out);
+        Ok(())
+    }
+
+    fn try_recover(&mut self, group_id: u32, out: &mut Vec<Vec<u8>>) {
+        let Some(group) = self.groups.get_mut(&group_id) else {
+            return;
+        };
+        let (data_shards, parity_shards) = (group.data_shards, group.parity_shards);
+        if data_shards == 0 {
+            return;
+        }
+
+        let received_data = (0..data_shards)
+            .filter(|i| group.shards.contains_key(i))
+            .count();
+        if received_data == data_shards {
+            group.done = true;
+            group.shards.clear();
+            return;
+        }
+        if group.shards.len() < data_shards {
+            return;
+        }
+
+        // parity shards carry the padded length of the group
+        let Some(shard_len) = (data_shards..data_shards + parity_shards)
+            .find_map(|i| group.shards.get(&i))
+            .map(|s| s.len())
+        else {
+            return;
+        };
+        let mut shards: Vec<Option<Vec<u8>>> = (0..data_shards + parity_shards)
+            .map(|i| {
+                group
+                    .shards
+                    .get(&i)
+                    .filter(|s| s.len() <= shard_len)
+                    .map(|s| {
+                        let mut s = s.clone();
+                        s.resize(shard_len, 0);
+                        s
+                    })
+            })
+            .collect();
+
+        group.done = true;
+        group.shards.clear();
+
+        let Some(codec) = self.codecs.get(data_shards, parity_shards) else {
+            return;
+        };
+        if let Err(e) = codec.reconstruct_data(&mut shards) {
+            tracing::debug!(?e, ?group_id, "fec reconstruct data shards failed");
+            return;
+        }
+
+        let group = self.groups.get_mut(&group_id).unwrap();
+        for (i, shard) in shards.iter().enumerate().take(data_shards) {
+            if !group.mark_delivered(i) {
+                continue;
+            }
+            if let Some(payload) = shard.as_deref().and_then(unwrap_data_shard) {
+                tracing::trace!(?group_id, index = i, "fec recovered lost shard");
+                out.push(payload);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_pair(config: FecConfig) -> (FecEncoder, FecDecoder) {
+        (
+            FecEncoder::new(config, Arc::new(FecLinkState::default())),
+            FecDecoder::new(Arc::new(FecLinkState::default())),
+        )
+    }
+
+    #[test]
+    fn parse_fec_config() {
+        assert_eq!(FecConfig::parse("off").unwrap(), None);
+        assert_eq!(
+            FecConfig::parse("auto").unwrap(),
+            Some(FecConfig::default())
+        );
+        assert_eq!(
+            FecConfig::parse("10:3").unwrap(),
+            Some(FecConfig {
+                data_shards: 10,
+                parity_shards: 3,
+                adaptive: false,
+            })
+        );
+        assert!(FecConfig::parse("4:auto").unwrap().unwrap().adaptive);
+        assert!(FecConfig::parse("0:1").is_err());
+        assert!(FecConfig::parse("abc").is_err());
+    }
+
+    #[test]
+    fn recover_lost_data_shards() {
+        let (mut encoder, mut decoder) = new_pair(FecConfig::parse("4:2").unwrap().unwrap());
+        let payloads: Vec<Vec<u8>> = (0..4u8).map(|i| vec![i; 10 + i as usize * 7]).collect();
+
+        let mut shards = Vec::new();
+        for p in payloads.iter() {
+            encoder.encode(p, &mut shards);
+        }
+        // 4 data + 2 parity
+        assert_eq!(shards.len(), 6);
+
+        // lose two data shards
+        let mut out = Vec::new();
+        for (i, shard) in shards.iter().enumerate() {
+            if i == 1 || i == 2 {
+                continue;
+            }
+            decoder.decode(shard, &mut out).unwrap();
+        }
+        out.sort();
+        assert_eq!(out, payloads);
+
+        // late and duplicated shards are not delivered again
+        let mut out = Vec::new();
+        for shard in shards.iter() {
+            decoder.decode(shard, &mut out).unwrap();
+        }
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn flush_partial_group() {
+        let (mut encoder, mut decoder) = new_pair(FecConfig::parse("8:1").unwrap().unwrap());
+        let mut shards = Vec::new();
+        encoder.encode(b"hello", &mut shards);
+        encoder.encode(b"world!", &mut shards);
+        assert!(encoder.flush_deadline().is_some());
+        encoder.flush(&mut shards);
+        assert!(encoder.flush_deadline().is_none());
+        assert_eq!(shards.len(), 3);
+
+        let mut out = Vec::new();
+        decoder.decode(&shards[1], &mut out).unwrap();
+        decoder.decode(&shards[2], &mut out).unwrap();
+        assert_eq!(out, vec![b"world!".to_vec(), b"hello".to_vec()]);
+    }
+
+    #[test]
+    fn adaptive_parity_follows_reported_loss() {
+        let config = FecConfig::default();
+        let tx_state = Arc::new(FecLinkState::default());
+        let rx_state = Arc::new(FecLinkState::default());
+        let mut encoder = FecEncoder::new(config, tx_state.clone());
+        let mut decoder = FecDecoder::new(rx_state.clone());
+        let mut reverse_encoder = FecEncoder::new(config, rx_state.clone());
+        let mut reverse_decoder = FecDecoder::new(tx_state.clone());
+
+        assert_eq!(encoder.parity_shards(), 1);
+
+        // drop every 5th packet
+        let mut shards = Vec::new();
+        for i in 0..1024u32 {
+            encoder.encode(&i.to_le_bytes(), &mut shards);
+        }
+        let mut out = Vec::new();
+        for (i, shard) in shards.iter().enumerate() {
+            if i % 5 != 0 {
+                decoder.decode(shard, &mut out).unwrap();
+            }
+        }
+        assert!((15..=25).contains(&rx_state.local_loss()));
+
+        // the receiver reports the loss back in its own packets
+        let mut shards = Vec::new();
+        reverse_encoder.encode(b"pong", &mut shards);
+        for shard in shards.iter() {
+            reverse_decoder.decode(shard, &mut out).unwrap();
+        }
+        assert_eq!(tx_state.remote_loss(), rx_state.local_loss());
+        assert!(encoder.parity_shards() >= 3);
+    }
+}
@@ -15,9 +15,12 @@ use self::packet_def::ZCPacket;
 
 pub mod buf;
 pub mod common;
+pub mod fec;
 pub mod filter;
 pub mod mpsc;
+pub mod obfs;
 pub mod packet_def;
+pub mod proxy;
 pub mod ring;
 pub mod stats;
 pub mod tcp;
@@ -35,9 +38,21 @@ pub mod quic;
 #[cfg(feature = "websocket")]
 pub mod websocket;
 
-#[cfg(any(feature = "quic", feature = "websocket"))]
+#[cfg(feature = "tls")]
+pub mod tcp_tls;
+
+#[cfg(any(feature = "quic", feature = "websocket", feature = "tls"))]
 pub mod insecure_tls;
 
+#[cfg(any(feature = "quic", feature = "websocket", feature = "tls"))]
+pub mod tls;
+
+#[cfg(unix)]
+pub mod stdio;
+
+#[cfg(unix)]
+pub mod unix;
+
 #[derive(thiserror::Error, Debug)]
 pub enum TunnelError {
     #[error("io error")]
@@ -78,6 +93,16 @@ pub enum TunnelError {
 
     #[error("tunnel error: {0}")]
     TunError(String),
+
+    #[error("tls config error: {0}")]
+    TlsConfigError(String),
+    #[error("server certificate verification failed: {0}")]
+    CertVerifyFailed(String),
+    #[error("server certificate does not match pin, got {0}")]
+    CertPinMismatch(String),
+
+    #[error("upstream proxy error: {0}")]
+    ProxyError(String),
 }
 
 pub type StreamT = packet_def::ZCPacket;
@@ -159,7 +184,7 @@ impl std::fmt::Debug for dyn Tunnel {
 impl std::fmt::Debug for dyn TunnelConnector {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TunnelConnector")
-            .field("remote_url", &self.remote_url())
+            .field("remote_url", &proxy::redact_url(&self.remote_url()))
             .finish()
     }
 }
@@ -201,6 +226,7 @@ fn default_port(scheme: &str) -> Option<u16> {
         "ws" => Some(11011),
         "wss" => Some(11012),
         "quic" => Some(11012),
+        "tls" => Some(11013),
         "wg" => Some(11011),
         _ => None,
     }
@@ -0,0 +1,324 @@
+// Traffic obfuscation filter.
+//
+// Every packet is rewritten before it reaches the transport:
+//
+//   nonce (8) | masked [ padding len (2) | peer manager header | payload ] | padding
+//
+// The first OBFS_MASK_LEN bytes after the nonce are xor-ed with a keystream derived from
+// a shared key and the per packet nonce, which hides the peer manager header and the
+// handshake magic. Random padding and optional size shaping break up the packet length
+// distribution. The key comes from the network secret, or from `obfs_key` in the url for
+// listeners shared by several networks.
+//
+// The filter runs in the tunnel filter chain, above the transport. The tunnel's own
+// framing (the tcp length prefix, the udp tunnel header and the udp syn / sack handshake
+// with its magic) is written by the transport after the filter and stays in clear, and
+// packet timing is not shaped. Hiding those needs a transport of its own, e.g. wss or
+// quic. An invalid `obfs` param fails the listener / connector, it never falls back to
+// a plain tunnel.
+
+use bytes::BytesMut;
+use rand::{Rng, RngCore};
+use sha2::{Digest, Sha256};
+
+use super::{
+    filter::TunnelFilter,
+    packet_def::{ZCPacket, ZCPacketType},
+    SinkItem, StreamItem, TunnelError,
+};
+use crate::proto::common::TunnelInfo;
+
+const OBFS_NONCE_LEN: usize = 8;
+const OBFS_PAD_LEN_SIZE: usize = 2;
+// enough to cover the peer manager header and the start of the handshake
+const OBFS_MASK_LEN: usize = 64;
+const OBFS_DEFAULT_MAX_PADDING: usize = 32;
+// padding never grows a packet beyond this, to stay below the path mtu
+const OBFS_MAX_PADDED_LEN: usize = 1400;
+const OBFS_SHAPE_BUCKETS: &[usize] = &[128, 256, 512, 1024, OBFS_MAX_PADDED_LEN];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObfsConfig {
+    key: [u8; 32],
+    max_padding: usize,
+    // pad packets up to the next size bucket
+    shape: bool,
+}
+
+impl ObfsConfig {
+    pub fn new(secret: &str, max_padding: usize, shape: bool) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(b"easytier-obfs");
+        hasher.update(secret.as_bytes());
+        Self {
+            key: hasher.finalize().into(),
+            max_padding,
+            shape,
+        }
+    }
+
+    /// Read the obfuscation settings of a listener or connector url.
+    ///
+    /// `obfs` is one of `mask` (no padding), `pad` (alias `on`) or `shape`, `obfs_key`
+    /// overrides the network secret as key.
+    pub fn from_url(url: &url::Url, network_secret: &str) -> Result<Option<Self>, TunnelError> {
+        let mut mode = None;
+        let mut key = None;
+        for (k, v) in url.query_pairs() {
+            match k.as_ref() {
+                "obfs" => mode = Some(v.into_owned()),
+                "obfs_key" => key = Some(v.into_owned()),
+                _ => {}
+            }
+        }
+
+        let Some(mode) = mode else {
+            return Ok(None);
+        };
+        let (max_padding, shape) = match mode.as_str() {
+            "off" => return Ok(None),
+            "mask" => (0, false),
+            "on" | "pad" => (OBFS_DEFAULT_MAX_PADDING, false),
+            "shape" => (OBFS_DEFAULT_MAX_PADDING, true),
+            _ => {
+                return Err(TunnelError::InvalidAddr(format!(
+                    "invalid obfs mode: {}",
+                    mode
+                )))
+            }
+        };
+
+        let secret = key.as_deref().unwrap_or(network_secret);
+        Ok(Some(Self::new(secret, max_padding, shape)))
+    }
+
+    /// Check the obfuscation settings of a url without deriving a key, so listeners and
+    /// connectors with a bad `obfs` param fail when they are created.
+    pub fn validate_url(url: &url::Url) -> Result<(), TunnelError> {
+        Self::from_url(url, "").map(|_| ())
+    }
+
+    /// Find the obfuscation settings of a tunnel. Connectors carry them in the remote
+    /// url, listeners in the local url.
+    pub fn from_tunnel_info(
+        info: &TunnelInfo,
+        network_secret: &str,
+    ) -> Result<Option<Self>, TunnelError> {
+        for addr in [&info.remote_addr, &info.local_addr].into_iter().flatten() {
+            let Ok(url) = url::Url::parse(&addr.url) else {
+                continue;
+            };
+            if let Some(config) = Self::from_url(&url, network_secret)? {
+                return Ok(Some(config));
+            }
+        }
+        Ok(None)
+    }
+
+    fn padding_len(&self, len: usize) -> usize {
+        let room = OBFS_MAX_PADDED_LEN.saturating_sub(len);
+        let mut padding = 0;
+        if self.shape {
+            if let Some(bucket) = OBFS_SHAPE_BUCKETS.iter().find(|b| **b >= len) {
+                padding = bucket - len;
+            }
+        }
+        if self.max_padding > 0 {
+            padding += rand::thread_rng().gen_range(0..=self.max_padding);
+        }
+        padding.min(room).min(u16::MAX as usize)
+    }
+
+    fn apply_mask(&self, nonce: &[u8], data: &mut [u8]) {
+        let len = data.len().min(OBFS_MASK_LEN);
+        for (counter, chunk) in data[..len].chunks_mut(32).enumerate() {
+            let mut hasher = Sha256::new();
+            hasher.update(self.key);
+            hasher.update(nonce);
+            hasher.update([counter as u8]);
+            let block = hasher.finalize();
+            for (b, k) in chunk.iter_mut().zip(block.iter()) {
+                *b ^= k;
+            }
+        }
+    }
+}
+
+pub struct ObfsTunnelFilter {
+    config: ObfsConfig,
+}
+
+impl ObfsTunnelFilter {
+    pub fn new(config: ObfsConfig) -> Self {
+        Self { config }
+    }
+
+    fn obfuscate(&self, packet: ZCPacket) -> ZCPacket {
+        let tunnel_payload = packet.tunnel_payload();
+        let body_len = OBFS_PAD_LEN_SIZE + tunnel_payload.len();
+        let padding = self.config.padding_len(OBFS_NONCE_LEN + body_len);
+
+        // keep the header room of nic packets so transports can prepend their header in place
+        let header_room = ZCPacketType::NIC
+            .get_packet_offsets()
+            .peer_manager_header_offset;
+        let mut buf = BytesMut::with_capacity(header_room + OBFS_NONCE_LEN + body_len + padding);
+        buf.resize(header_room + OBFS_NONCE_LEN, 0);
+        rand::thread_rng().fill_bytes(&mut buf[header_room..]);
+        buf.extend_from_slice(&(padding as u16).to_le_bytes());
+        buf.extend_from_slice(tunnel_payload);
+        let pad_start = buf.len();
+        buf.resize(pad_start + padding, 0);
+        rand::thread_rng().fill_bytes(&mut buf[pad_start..]);
+
+        let (nonce, body) = buf[header_room..].split_at_mut(OBFS_NONCE_LEN);
+        self.config.apply_mask(nonce, &mut body[..body_len]);
+
+        ZCPacket::new_from_buf(buf, ZCPacketType::NIC)
+    }
+
+    fn deobfuscate(&self, packet: ZCPacket) -> Result<ZCPacket, TunnelError> {
+        let packet_type = packet.packet_type();
+        let mut blob = packet.tunnel_payload_bytes();
+        if blob.len() < OBFS_NONCE_LEN + OBFS_PAD_LEN_SIZE {
+            return Err(TunnelError::InvalidPacket(
+                "obfs packet too short".to_owned(),
+            ));
+        }
+
+        let (nonce, body) = blob.split_at_mut(OBFS_NONCE_LEN);
+        self.config.apply_mask(nonce, body);
+        let padding = u16::from_le_bytes([body[0], body[1]]) as usize;
+        let Some(payload_len) = body.len().checked_sub(OBFS_PAD_LEN_SIZE + padding) else {
+            return Err(TunnelError::InvalidPacket(
+                "obfs padding exceeds packet".to_owned(),
+            ));
+        };
+
+        let header_room = packet_type.get_packet_offsets().peer_manager_header_offset;
+        let mut buf = BytesMut::with_capacity(header_room + payload_len);
+        buf.resize(header_room, 0);
+        buf.extend_from_slice(&body[OBFS_PAD_LEN_SIZE..OBFS_PAD_LEN_SIZE + payload_len]);
+        Ok(ZCPacket::new_from_buf(buf, packet_type))
+    }
+}
+
+impl TunnelFilter for ObfsTunnelFilter {
+    type FilterOutput = ();
+
+    fn before_send(&self, data: SinkItem) -> Option<SinkItem> {
+        Some(self.obfuscate(data))
+    }
+
+    fn after_received(&self, data: StreamItem) -> Option<StreamItem> {
+        Some(data.and_then(|packet| self.deobfuscate(packet)))
+    }
+
+    fn filter_output(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{SinkExt, StreamExt};
+
+    use super::*;
+    use crate::tunnel::{
+        filter::TunnelWithFilter,
+        packet_def::{PacketType, PEER_MANAGER_HEADER_SIZE},
+        ring::create_ring_tunnel_pair,
+        Tunnel,
+    };
+
+    fn config(mode: &str) -> ObfsConfig {
+        let url: url::Url = format!("tcp://127.0.0.1:11010?obfs={}", mode)
+            .parse()
+            .unwrap();
+        ObfsConfig::from_url(&url, "secret").unwrap().unwrap()
+    }
+
+    #[test]
+    fn obfs_config_from_url() {
+        let url: url::Url = "udp://0.0.0.0:11010".parse().unwrap();
+        assert!(ObfsConfig::from_url(&url, "secret").unwrap().is_none());
+
+        let url: url::Url = "udp://0.0.0.0:11010?obfs=off".parse().unwrap();
+        assert!(ObfsConfig::from_url(&url, "secret").unwrap().is_none());
+
+        let url: url::Url = "udp://0.0.0.0:11010?obfs=bad".parse().unwrap();
+        assert!(ObfsConfig::from_url(&url, "secret").is_err());
+
+        // obfs_key overrides the network secret
+        let url: url::Url = "udp://0.0.0.0:11010?obfs=pad&obfs_key=abc".parse().unwrap();
+        assert_eq!(
+            ObfsConfig::from_url(&url, "secret").unwrap(),
+            ObfsConfig::from_url(&url, "other").unwrap()
+        );
+        assert_ne!(
+            Some(config("pad")),
+            ObfsConfig::from_url(&url, "secret").unwrap()
+        );
+    }
+
+    #[test]
+    fn obfs_roundtrip_hides_header() {
+        for mode in ["mask", "pad", "shape"] {
+            let filter = ObfsTunnelFilter::new(config(mode));
+            let mut packet = ZCPacket::new_with_payload(b"easytier handshake payload");
+            packet.fill_peer_manager_hdr(1, 2, PacketType::HandShake as u8);
+            let plain = packet.tunnel_payload().to_vec();
+
+            let obfuscated = filter.obfuscate(packet);
+            let wire = obfuscated.tunnel_payload().to_vec();
+            assert!(wire.len() >= plain.len() + OBFS_NONCE_LEN + OBFS_PAD_LEN_SIZE);
+            assert!(!wire
+                .windows(PEER_MANAGER_HEADER_SIZE)
+                .any(|w| w == &plain[..PEER_MANAGER_HEADER_SIZE]));
+            if mode == "shape" {
+                assert!(OBFS_SHAPE_BUCKETS[0] <= wire.len());
+            }
+
+            // as received by a tcp tunnel
+            let received = obfuscated.convert_type(ZCPacketType::TCP);
+            let restored = filter.deobfuscate(received).unwrap();
+            assert_eq!(restored.tunnel_payload(), &plain[..]);
+            assert_eq!(
+                restored.peer_manager_header().unwrap().packet_type,
+                PacketType::HandShake as u8
+            );
+        }
+    }
+
+    #[test]
+    fn obfs_wrong_key_is_rejected_or_garbled() {
+        let filter = ObfsTunnelFilter::new(config("pad"));
+        let other = ObfsTunnelFilter::new(ObfsConfig::new("other", 32, false));
+        let mut packet = ZCPacket::new_with_payload(b"hello");
+        packet.fill_peer_manager_hdr(1, 2, PacketType::Data as u8);
+        let plain = packet.tunnel_payload().to_vec();
+
+        let obfuscated = filter.obfuscate(packet);
+        match other.deobfuscate(obfuscated) {
+            Ok(p) => assert_ne!(p.tunnel_payload(), &plain[..]),
+            Err(e) => assert!(matches!(e, TunnelError::InvalidPacket(_))),
+        }
+    }
+
+    #[tokio::test]
+    async fn obfs_filter_over_tunnel() {
+        let (a, b) = create_ring_tunnel_pair();
+        let a = TunnelWithFilter::new(a, ObfsTunnelFilter::new(config("shape")));
+        let b = TunnelWithFilter::new(b, ObfsTunnelFilter::new(config("shape")));
+
+        let (_a_stream, mut a_sink) = a.split();
+        let (mut b_stream, _b_sink) = b.split();
+
+        for i in 0..16u8 {
+            let mut packet = ZCPacket::new_with_payload(&vec![i; i as usize * 50]);
+            packet.fill_peer_manager_hdr(1, 2, PacketType::Data as u8);
+            a_sink.send(packet).await.unwrap();
+
+            let received = b_stream.next().await.unwrap().unwrap();
+            assert_eq!(received.payload(), &vec![i; i as usize * 50][..]);
+        }
+    }
+}
@@ -28,10 +28,23 @@ pub enum UdpPacketType {
     Data = 3,
     Fin = 4,
     HolePunch = 5,
-    V6HolePunch = 6, // when receiving v6 hole punch packet, the packet contains a socket addr of other peer, we
-                     // will send a hole punch packet to that peer. we only accept this packet from lookback interface.
+    // when receiving v6 hole punch packet, the packet contains a socket addr of other peer, we
+    // will send a hole punch packet to that peer. we only accept this packet from lookback interface.
+    V6HolePunch = 6,
+    Fec = 7,
+    // sent by the connector from its new source address, carries the syn magic and a migrate
+    // seq so the listener can move the connection found by conn id to the new address.
+    Migrate = 8,
+    MigrateAck = 9,
+    // sent by the listener to the new address of a migrate, the connection only moves after the
+    // connector echoes its nonce back in another migrate from that address.
+    MigrateChallenge = 10,
 }
 
+// bits of UDPTunnelHeader::padding in syn / sack packets, used to negotiate optional features.
+// old peers leave padding zero so they always fall back to plain udp.
+pub const UDP_TUNNEL_FLAG_FEC: u8 = 0b0000_0001;
+
 #[repr(C, packed)]
 #[derive(AsBytes, FromBytes, FromZeroes, Clone, Debug, Default)]
 pub struct V6HolePunchPacket {
@@ -49,6 +62,21 @@ pub struct UDPTunnelHeader {
 }
 pub const UDP_TUNNEL_HEADER_SIZE: usize = std::mem::size_of::<UDPTunnelHeader>();
 
+#[repr(C, packed)]
+#[derive(AsBytes, FromBytes, FromZeroes, Clone, Debug, Default)]
+pub struct FecShardHeader {
+    // increases on every fec packet, used to measure the loss rate
+    pub seq: U32<DefaultEndian>,
+    pub group_id: U32<DefaultEndian>,
+    pub index: u8,
+    // zero for data shards, which are sent before the group is complete
+    pub data_shards: u8,
+    pub parity_shards: u8,
+    // loss rate in percent the sender observes on packets from the receiver
+    pub peer_loss: u8,
+}
+pub const FEC_SHARD_HEADER_SIZE: usize = std::mem::size_of::<FecShardHeader>();
+
 #[repr(C, packed)]
 #[derive(AsBytes, FromBytes, FromZeroes, Clone, Debug, Default)]
 pub struct WGTunnelHeader {
@@ -82,6 +110,8 @@ bitflags::bitflags! {
         const NO_PROXY = 0b0000_1000;
         const COMPRESSED = 0b0001_0000;
         const KCP_SRC_MODIFIED = 0b0010_0000;
+        // encrypted with the session keys negotiated with the sender, see SessionTail
+        const SESSION_KEY = 0b0100_0000;
 
         const _ = !0;
     }
@@ -201,6 +231,23 @@ impl PeerManagerHeader {
             .unwrap()
             .contains(PeerManagerHeaderFlags::KCP_SRC_MODIFIED)
     }
+
+    pub fn set_session_key(&mut self, session_key: bool) -> &mut Self {
+        let mut flags = PeerManagerHeaderFlags::from_bits(self.flags).unwrap();
+        if session_key {
+            flags.insert(PeerManagerHeaderFlags::SESSION_KEY);
+        } else {
+            flags.remove(PeerManagerHeaderFlags::SESSION_KEY);
+        }
+        self.flags = flags.bits();
+        self
+    }
+
+    pub fn is_session_key(&self) -> bool {
+        PeerManagerHeaderFlags::from_bits(self.flags)
+            .unwrap()
+            .contains(PeerManagerHeaderFlags::SESSION_KEY)
+    }
 }
 
 #[repr(C, packed)]
@@ -252,6 +299,15 @@ pub struct AesGcmTail {
 }
 pub const AES_GCM_ENCRYPTION_RESERVED: usize = std::mem::size_of::<AesGcmTail>();
 
+// appended after the cipher tail, selects the session and key epoch used to decrypt
+#[repr(C, packed)]
+#[derive(AsBytes, FromBytes, FromZeroes, Clone, Debug, Default)]
+pub struct SessionTail {
+    pub session_id: U32<DefaultEndian>,
+    pub epoch: U32<DefaultEndian>,
+}
+pub const SESSION_TAIL_SIZE: usize = std::mem::size_of::<SessionTail>();
+
 #[derive(AsBytes, FromZeroes, Clone, Debug, Copy, PartialEq, Hash, Eq)]
 #[repr(u8)]
 pub enum CompressorAlgo {
@@ -0,0 +1,559 @@
+// Dial tcp based tunnels through an upstream HTTP CONNECT or SOCKS5 proxy.
+//
+// The proxy is given per connector with the `proxy` url param, e.g.
+// `tcp://host:11010?proxy=socks5://user:pass@gw:1080`, or globally with the
+// upstream proxy config. `proxy=direct` bypasses the global proxy for one connector.
+// The target host is always resolved by the proxy, hosts behind a corporate proxy
+// are often not resolvable from the LAN.
+
+use std::net::IpAddr;
+
+use base64::{prelude::BASE64_STANDARD, Engine as _};
+use percent_encoding::percent_decode_str;
+use tokio::{
+    io::{AsyncReadExt as _, AsyncWriteExt as _},
+    net::TcpStream,
+};
+
+use super::TunnelError;
+
+// max size of the http response header of a CONNECT request
+const MAX_HTTP_HEADER_SIZE: usize = 8192;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpstreamProxyKind {
+    Http,
+    Socks5,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct UpstreamProxy {
+    pub kind: UpstreamProxyKind,
+    pub host: String,
+    pub port: u16,
+    pub auth: Option<(String, String)>,
+}
+
+// connectors are logged with their proxy, so the password is never printed
+impl std::fmt::Debug for UpstreamProxy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UpstreamProxy")
+            .field("kind", &self.kind)
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("auth", &self.auth.as_ref().map(|(user, _)| (user, "***")))
+            .finish()
+    }
+}
+
+fn proxy_error(msg: impl Into<String>) -> TunnelError {
+    TunnelError::ProxyError(msg.into())
+}
+
+fn decode_url_part(s: &str) -> String {
+    percent_decode_str(s).decode_utf8_lossy().to_string()
+}
+
+fn strip_userinfo(url: &mut url::Url) {
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+}
+
+/// The url without the credentials of itself and of its `proxy` param. Use it for logs and for
+/// tunnel infos, which are shown to peers.
+pub fn redact_url(url: &url::Url) -> url::Url {
+    let mut url = url.clone();
+    strip_userinfo(&mut url);
+    if !url.query_pairs().any(|(k, _)| k == "proxy") {
+        return url;
+    }
+
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| {
+            let v = match (k.as_ref(), url::Url::parse(&v)) {
+                ("proxy", Ok(mut proxy)) => {
+                    strip_userinfo(&mut proxy);
+                    proxy.to_string()
+                }
+                _ => v.to_string(),
+            };
+            (k.to_string(), v)
+        })
+        .collect();
+    url.query_pairs_mut().clear().extend_pairs(pairs);
+    url
+}
+
+impl UpstreamProxy {
+    pub fn from_url(url: &url::Url) -> Result<Self, TunnelError> {
+        let (kind, default_port) = match url.scheme() {
+            "http" => (UpstreamProxyKind::Http, 8080),
+            "socks5" | "socks5h" => (UpstreamProxyKind::Socks5, 1080),
+            scheme => {
+                return Err(proxy_error(format!(
+                    "unsupported proxy scheme: {}, expect http or socks5",
+                    scheme
+                )))
+            }
+        };
+        let host = match url.host() {
+            Some(url::Host::Ipv6(ip)) => ip.to_string(),
+            Some(host) => host.to_string(),
+            None => return Err(proxy_error(format!("proxy host is missing: {}", url))),
+        };
+        let auth = if url.username().is_empty() {
+            None
+        } else {
+            Some((
+                decode_url_part(url.username()),
+                decode_url_part(url.password().unwrap_or_default()),
+            ))
+        };
+        Ok(Self {
+            kind,
+            host,
+            port: url.port().unwrap_or(default_port),
+            auth,
+        })
+    }
+
+    /// Proxy for a connector url, the `proxy` url param takes precedence over the global proxy.
+    pub fn for_connector(
+        url: &url::Url,
+        global: Option<&url::Url>,
+    ) -> Result<Option<Self>, TunnelError> {
+        let param = url
+            .query_pairs()
+            .find(|(k, _)| k == "proxy")
+            .map(|(_, v)| v.to_string());
+        match param.as_deref() {
+            Some("direct") | Some("none") => Ok(None),
+            Some(proxy) => {
+                let proxy = url::Url::parse(proxy)
+                    .map_err(|e| proxy_error(format!("invalid proxy url {}: {}", proxy, e)))?;
+                Ok(Some(Self::from_url(&proxy)?))
+            }
+            None => global.map(Self::from_url).transpose(),
+        }
+    }
+
+    /// Host and port of the tunnel endpoint that the proxy should connect to.
+    pub fn target_of(url: &url::Url) -> Result<(String, u16), TunnelError> {
+        let host = match url.host_str() {
+            Some(host) if !host.is_empty() => host.trim_start_matches('[').trim_end_matches(']'),
+            _ => return Err(TunnelError::InvalidAddr(url.to_string())),
+        };
+        let port = url
+            .port()
+            .or_else(|| super::default_port(url.scheme()))
+            .or_else(|| url.port_or_known_default())
+            .ok_or_else(|| TunnelError::InvalidAddr(format!("port is missing: {}", url)))?;
+        Ok((host.to_string(), port))
+    }
+
+    pub async fn connect(&self, host: &str, port: u16) -> Result<TcpStream, TunnelError> {
+        tracing::info!(proxy = ?self.kind, proxy_host = ?self.host, proxy_port = self.port, ?host, port, "connect through upstream proxy");
+        let mut stream = TcpStream::connect((self.host.as_str(), self.port)).await?;
+        if let Err(e) = stream.set_nodelay(true) {
+            tracing::warn!(?e, "set_nodelay fail in proxy connect");
+        }
+        match self.kind {
+            UpstreamProxyKind::Http => self.http_connect(&mut stream, host, port).await?,
+            UpstreamProxyKind::Socks5 => self.socks5_connect(&mut stream, host, port).await?,
+        }
+        Ok(stream)
+    }
+
+    async fn http_connect(
+        &self,
+        stream: &mut TcpStream,
+        host: &str,
+        port: u16,
+    ) -> Result<(), TunnelError> {
+        let authority = if host.parse::<std::net::Ipv6Addr>().is_ok() {
+            format!("[{}]:{}", host, port)
+        } else {
+            format!("{}:{}", host, port)
+        };
+        let mut req = format!(
+            "CONNECT {0} HTTP/1.1\r\nHost: {0}\r\nProxy-Connection: Keep-Alive\r\n",
+            authority
+        );
+        if let Some((user, pass)) = &self.auth {
+            let cred = BASE64_STANDARD.encode(format!("{}:{}", user, pass));
+            req.push_str(&format!("Proxy-Authorization: Basic {}\r\n", cred));
+        }
+        req.push_str("\r\n");
+        stream.write_all(req.as_bytes()).await?;
+
+        // read byte by byte so that no tunnel data after the header is consumed
+        let mut header = Vec::with_capacity(256);
+        while !header.ends_with(b"\r\n\r\n") {
+            if header.len() >= MAX_HTTP_HEADER_SIZE {
+                return Err(proxy_error("http proxy response header too large"));
+            }
+            let b = stream.read_u8().await?;
+            header.push(b);
+        }
+
+        let header = String::from_utf8_lossy(&header);
+        let status_line = header.lines().next().unwrap_or_default();
+        let status = status_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|s| s.parse::<u16>().ok())
+            .ok_or_else(|| proxy_error(format!("invalid http proxy response: {}", status_line)))?;
+        if !(200..300).contains(&status) {
+            return Err(proxy_error(format!(
+                "http proxy refused CONNECT to {}: {}",
+                authority, status_line
+            )));
+        }
+        Ok(())
+    }
+
+    async fn socks5_connect(
+        &self,
+        stream: &mut TcpStream,
+        host: &str,
+        port: u16,
+    ) -> Result<(), TunnelError> {
+        // method negotiation, 0x00: no auth, 0x02: username/password
+        let methods: &[u8] = if self.auth.is_some() {
+            &[0x00, 0x02]
+        } else {
+            &[0x00]
+        };
+        let mut req = vec![0x05, methods.len() as u8];
+        req.extend_from_slice(methods);
+        stream.write_all(&req).await?;
+
+        let mut resp = [0u8; 2];
+        stream.read_exact(&mut resp).await?;
+        if resp[0] != 0x05 {
+            return Err(proxy_error(format!("invalid socks5 version: {}", resp[0])));
+        }
+        match (resp[1], &self.auth) {
+            (0x00, _) => {}
+            (0x02, Some((user, pass))) => {
+                if user.len() > 255 || pass.len() > 255 {
+                    return Err(proxy_error("socks5 username or password too long"));
+                }
+                let mut req = vec![0x01, user.len() as u8];
+                req.extend_from_slice(user.as_bytes());
+                req.push(pass.len() as u8);
+                req.extend_from_slice(pass.as_bytes());
+                stream.write_all(&req).await?;
+
+                let mut resp = [0u8; 2];
+                stream.read_exact(&mut resp).await?;
+                if resp[1] != 0x00 {
+                    return Err(proxy_error("socks5 authentication failed"));
+                }
+            }
+            (method, _) => {
+                return Err(proxy_error(format!(
+                    "socks5 proxy does not accept our auth methods, selected: {}",
+                    method
+                )))
+            }
+        }
+
+        let mut req = vec![0x05, 0x01, 0x00];
+        match host.parse::<IpAddr>() {
+            Ok(IpAddr::V4(ip)) => {
+                req.push(0x01);
+                req.extend_from_slice(&ip.octets());
+            }
+            Ok(IpAddr::V6(ip)) => {
+                req.push(0x04);
+                req.extend_from_slice(&ip.octets());
+            }
+            Err(_) => {
+                if host.len() > 255 {
+                    return Err(proxy_error(format!("socks5 host too long: {}", host)));
+                }
+                req.push(0x03);
+                req.push(host.len() as u8);
+                req.extend_from_slice(host.as_bytes());
+            }
+        }
+        req.extend_from_slice(&port.to_be_bytes());
+        stream.write_all(&req).await?;
+
+        let mut resp = [0u8; 4];
+        stream.read_exact(&mut resp).await?;
+        if resp[1] != 0x00 {
+            return Err(proxy_error(format!(
+                "socks5 proxy refused CONNECT to {}:{}, reply: {}",
+                host, port, resp[1]
+            )));
+        }
+        // skip the bound address
+        let addr_len = match resp[3] {
+            0x01 => 4,
+            0x04 => 16,
+            0x03 => stream.read_u8().await? as usize,
+            atyp => {
+                return Err(proxy_error(format!(
+                    "invalid socks5 reply address type: {}",
+                    atyp
+                )))
+            }
+        };
+        let mut bound = vec![0u8; addr_len + 2];
+        stream.read_exact(&mut bound).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use tokio::{
+        io::{AsyncBufReadExt as _, AsyncReadExt as _, AsyncWriteExt as _, BufReader},
+        net::{TcpListener, TcpStream},
+        task::JoinHandle,
+    };
+
+    use super::*;
+
+    async fn relay(mut client: TcpStream, host: &str, port: u16) {
+        let Ok(mut upstream) = TcpStream::connect((host, port)).await else {
+            return;
+        };
+        let _ = tokio::io::copy_bidirectional(&mut client, &mut upstream).await;
+    }
+
+    async fn handle_http_proxy_conn(stream: TcpStream, auth: Option<String>) {
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).await.unwrap();
+        let mut authorized = auth.is_none();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).await.unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let (Some(auth), Some(v)) = (&auth, line.strip_prefix("Proxy-Authorization: ")) {
+                authorized |= v.trim() == format!("Basic {}", BASE64_STANDARD.encode(auth));
+            }
+        }
+        let mut stream = reader.into_inner();
+        if !authorized {
+            let _ = stream
+                .write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n")
+                .await;
+            return;
+        }
+
+        let target = request_line
+            .strip_prefix("CONNECT ")
+            .and_then(|r| r.split_whitespace().next())
+            .unwrap()
+            .to_string();
+        let (host, port) = target.rsplit_once(':').unwrap();
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        stream
+            .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+            .await
+            .unwrap();
+        relay(stream, host, port.parse().unwrap()).await;
+    }
+
+    /// In-process stand-in for an http proxy, `auth` is "user:pass".
+    pub async fn run_http_proxy(auth: Option<&str>) -> (u16, JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let auth = auth.map(|a| a.to_string());
+        let task = tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(handle_http_proxy_conn(stream, auth.clone()));
+            }
+        });
+        (port, task)
+    }
+
+    async fn handle_socks5_proxy_conn(mut stream: TcpStream, auth: Option<(String, String)>) {
+        let ver = stream.read_u8().await.unwrap();
+        assert_eq!(ver, 0x05);
+        let n = stream.read_u8().await.unwrap();
+        let mut methods = vec![0u8; n as usize];
+        stream.read_exact(&mut methods).await.unwrap();
+
+        if let Some((user, pass)) = auth {
+            if !methods.contains(&0x02) {
+                stream.write_all(&[0x05, 0xff]).await.unwrap();
+                return;
+            }
+            stream.write_all(&[0x05, 0x02]).await.unwrap();
+            let _ver = stream.read_u8().await.unwrap();
+            let ulen = stream.read_u8().await.unwrap();
+            let mut u = vec![0u8; ulen as usize];
+            stream.read_exact(&mut u).await.unwrap();
+            let plen = stream.read_u8().await.unwrap();
+            let mut p = vec![0u8; plen as usize];
+            stream.read_exact(&mut p).await.unwrap();
+            if u != user.as_bytes() || p != pass.as_bytes() {
+                stream.write_all(&[0x01, 0x01]).await.unwrap();
+                return;
+            }
+            stream.write_all(&[0x01, 0x00]).await.unwrap();
+        } else {
+            stream.write_all(&[0x05, 0x00]).await.unwrap();
+        }
+
+        let mut req = [0u8; 4];
+        stream.read_exact(&mut req).await.unwrap();
+        assert_eq!(req[1], 0x01);
+        let host = match req[3] {
+            0x01 => {
+                let mut ip = [0u8; 4];
+                stream.read_exact(&mut ip).await.unwrap();
+                std::net::Ipv4Addr::from(ip).to_string()
+            }
+            0x04 => {
+                let mut ip = [0u8; 16];
+                stream.read_exact(&mut ip).await.unwrap();
+                std::net::Ipv6Addr::from(ip).to_string()
+            }
+            _ => {
+                let len = stream.read_u8().await.unwrap();
+                let mut host = vec![0u8; len as usize];
+                stream.read_exact(&mut host).await.unwrap();
+                String::from_utf8(host).unwrap()
+            }
+        };
+        let port = stream.read_u16().await.unwrap();
+        stream
+            .write_all(&[0x05, 0x00, 0x00, 0x01, 0, 0, 0, 0, 0, 0])
+            .await
+            .unwrap();
+        relay(stream, &host, port).await;
+    }
+
+    /// In-process stand-in for a socks5 proxy, `auth` is "user:pass".
+    pub async fn run_socks5_proxy(auth: Option<&str>) -> (u16, JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let auth = auth.map(|a| {
+            let (u, p) = a.split_once(':').unwrap();
+            (u.to_string(), p.to_string())
+        });
+        let task = tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(handle_socks5_proxy_conn(stream, auth.clone()));
+            }
+        });
+        (port, task)
+    }
+
+    #[test]
+    fn upstream_proxy_from_url() {
+        let url: url::Url = "tcp://1.2.3.4:11010?proxy=socks5://us%40er:p%3Ass@gw:1081"
+            .parse()
+            .unwrap();
+        let proxy = UpstreamProxy::for_connector(&url, None).unwrap().unwrap();
+        assert_eq!(
+            proxy,
+            UpstreamProxy {
+                kind: UpstreamProxyKind::Socks5,
+                host: "gw".to_string(),
+                port: 1081,
+                auth: Some(("us@er".to_string(), "p:ss".to_string())),
+            }
+        );
+
+        let global: url::Url = "http://[::1]".parse().unwrap();
+        let url: url::Url = "tcp://1.2.3.4:11010".parse().unwrap();
+        let proxy = UpstreamProxy::for_connector(&url, Some(&global))
+            .unwrap()
+            .unwrap();
+        assert_eq!(proxy.kind, UpstreamProxyKind::Http);
+        assert_eq!(proxy.host, "::1");
+        assert_eq!(proxy.port, 8080);
+        assert_eq!(proxy.auth, None);
+
+        let url: url::Url = "tcp://1.2.3.4:11010?proxy=direct".parse().unwrap();
+        assert_eq!(
+            UpstreamProxy::for_connector(&url, Some(&global)).unwrap(),
+            None
+        );
+
+        let url: url::Url = "tcp://1.2.3.4:11010?proxy=ftp://gw".parse().unwrap();
+        assert!(UpstreamProxy::for_connector(&url, None).is_err());
+    }
+
+    #[test]
+    fn proxy_credentials_are_not_printed() {
+        let url: url::Url = "tcp://1.2.3.4:11010?proxy=socks5://user:secret@gw:1081&a=b"
+            .parse()
+            .unwrap();
+        let proxy = UpstreamProxy::for_connector(&url, None).unwrap().unwrap();
+        assert!(!format!("{:?}", proxy).contains("secret"));
+
+        let redacted = redact_url(&url);
+        assert!(!redacted.as_str().contains("secret"));
+        assert!(!redacted.as_str().contains("user"));
+        let proxy = UpstreamProxy::for_connector(&redacted, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (proxy.host.as_str(), proxy.port, proxy.auth),
+            ("gw", 1081, None)
+        );
+        assert!(redacted.query_pairs().any(|(k, v)| k == "a" && v == "b"));
+
+        let url: url::Url = "tcp://1.2.3.4:11010?proxy=direct".parse().unwrap();
+        assert_eq!(redact_url(&url), url);
+    }
+
+    async fn echo_once() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let (mut r, mut w) = stream.split();
+            let _ = tokio::io::copy(&mut r, &mut w).await;
+        });
+        port
+    }
+
+    async fn check_echo(proxy: &UpstreamProxy, host: &str) {
+        let port = echo_once().await;
+        let mut stream = proxy.connect(host, port).await.unwrap();
+        stream.write_all(b"hello").await.unwrap();
+        let mut buf = [0u8; 5];
+        stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"hello");
+    }
+
+    #[tokio::test]
+    async fn proxy_connect_with_auth() {
+        let (http_port, http_task) = run_http_proxy(Some("user:pass")).await;
+        let (socks_port, socks_task) = run_socks5_proxy(Some("user:pass")).await;
+
+        for (scheme, port) in [("http", http_port), ("socks5", socks_port)] {
+            let url = format!("{}://user:pass@127.0.0.1:{}", scheme, port);
+            let proxy = UpstreamProxy::from_url(&url.parse().unwrap()).unwrap();
+            check_echo(&proxy, "127.0.0.1").await;
+            check_echo(&proxy, "localhost").await;
+
+            let url = format!("{}://user:wrong@127.0.0.1:{}", scheme, port);
+            let proxy = UpstreamProxy::from_url(&url.parse().unwrap()).unwrap();
+            let ret = proxy.connect("127.0.0.1", echo_once().await).await;
+            assert!(matches!(ret, Err(TunnelError::ProxyError(_))), "{:?}", ret);
+
+            let url = format!("{}://127.0.0.1:{}", scheme, port);
+            let proxy = UpstreamProxy::from_url(&url.parse().unwrap()).unwrap();
+            let ret = proxy.connect("127.0.0.1", echo_once().await).await;
+            assert!(matches!(ret, Err(TunnelError::ProxyError(_))), "{:?}", ret);
+        }
+
+        http_task.abort();
+        socks_task.abort();
+    }
+}
@@ -6,9 +6,12 @@ use std::{
     error::Error, io::IoSliceMut, net::SocketAddr, pin::Pin, sync::Arc, task::Poll, time::Duration,
 };
 
-use crate::tunnel::{
-    common::{setup_sokcet2, FramedReader, FramedWriter, TunnelWrapper},
-    TunnelInfo,
+use crate::{
+    common::config::TlsConfig,
+    tunnel::{
+        common::{setup_sokcet2, FramedReader, FramedWriter, LocalAddrWatcher, TunnelWrapper},
+        TunnelInfo,
+    },
 };
 use anyhow::Context;
 
@@ -16,15 +19,29 @@ use quinn::{
     congestion::BbrConfig, crypto::rustls::QuicClientConfig, udp::RecvMeta, AsyncUdpSocket,
     ClientConfig, Connection, Endpoint, EndpointConfig, ServerConfig, TransportConfig, UdpPoller,
 };
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
 
 use super::{
     check_scheme_and_get_socket_addr,
     insecure_tls::{get_insecure_tls_cert, get_insecure_tls_client_config},
+    tls::{get_server_cert, CertVerifyFailure, ServerCertVerify},
     IpVersion, Tunnel, TunnelConnector, TunnelError, TunnelListener,
 };
 
 pub fn configure_client() -> ClientConfig {
-    let client_crypto = QuicClientConfig::try_from(get_insecure_tls_client_config()).unwrap();
+    configure_client_with_crypto(get_insecure_tls_client_config())
+}
+
+/// Client config verifying the server certificate as requested by `cert_verify`
+pub fn configure_verifying_client(
+    cert_verify: &ServerCertVerify,
+) -> Result<(ClientConfig, CertVerifyFailure), TunnelError> {
+    let (crypto, failure) = cert_verify.client_config()?;
+    Ok((configure_client_with_crypto(crypto), failure))
+}
+
+fn configure_client_with_crypto(crypto: rustls::ClientConfig) -> ClientConfig {
+    let client_crypto = QuicClientConfig::try_from(crypto).unwrap();
     let mut client_config = ClientConfig::new(Arc::new(client_crypto));
 
     // // Create a new TransportConfig and set BBR
@@ -88,7 +105,17 @@ impl AsyncUdpSocket for NoGroAsyncUdpSocket {
 /// - server certificate serialized into DER format
 #[allow(unused)]
 pub fn make_server_endpoint(bind_addr: SocketAddr) -> Result<(Endpoint, Vec<u8>), Box<dyn Error>> {
-    let (server_config, server_cert) = configure_server()?;
+    let (certs, key) = get_insecure_tls_cert();
+    make_server_endpoint_with_cert(bind_addr, certs, key)
+}
+
+/// Same as `make_server_endpoint`, presenting the given certificate chain
+pub fn make_server_endpoint_with_cert(
+    bind_addr: SocketAddr,
+    certs: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+) -> Result<(Endpoint, Vec<u8>), Box<dyn Error>> {
+    let (server_config, server_cert) = configure_server_with_cert(certs, key)?;
 
     let socket2_socket = socket2::Socket::new(
         socket2::Domain::for_address(bind_addr),
@@ -117,8 +144,16 @@ pub fn make_server_endpoint(bind_addr: SocketAddr) -> Result<(Endpoint, Vec<u8>)
 /// Returns default server configuration along with its certificate.
 pub fn configure_server() -> Result<(ServerConfig, Vec<u8>), Box<dyn Error>> {
     let (certs, key) = get_insecure_tls_cert();
+    configure_server_with_cert(certs, key)
+}
 
+fn configure_server_with_cert(
+    certs: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+) -> Result<(ServerConfig, Vec<u8>), Box<dyn Error>> {
     let mut server_config = ServerConfig::with_single_cert(certs.clone(), key)?;
+    // follow clients that change their address, see `monitor_local_addr`
+    server_config.migration(true);
     let transport_config = Arc::get_mut(&mut server_config.transport).unwrap();
     transport_config.max_concurrent_uni_streams(10_u8.into());
     transport_config.max_concurrent_bidi_streams(10_u8.into());
@@ -131,6 +166,30 @@ pub fn configure_server() -> Result<(ServerConfig, Vec<u8>), Box<dyn Error>> {
 #[allow(unused)]
 pub const ALPN_QUIC_HTTP: &[&[u8]] = &[b"hq-29"];
 
+/// Rebinds the client endpoint when the local address used to reach the server changes. quinn
+/// validates the new path and migrates the connection, so the tunnel stays up.
+async fn monitor_local_addr(endpoint: Endpoint, conn: Connection) {
+    let Ok(local_addr) = endpoint.local_addr() else {
+        return;
+    };
+    let mut watcher = LocalAddrWatcher::new(conn.remote_address(), local_addr.ip()).await;
+    loop {
+        tokio::select! {
+            _ = conn.closed() => return,
+            route_ip = watcher.changed() => {
+                let bind_addr = SocketAddr::new(local_addr.ip(), 0);
+                match std::net::UdpSocket::bind(bind_addr).and_then(|s| endpoint.rebind(s)) {
+                    Ok(()) => {
+                        tracing::info!(?route_ip, new_local_addr = ?endpoint.local_addr(), "quic endpoint rebound");
+                        watcher.accept(route_ip);
+                    }
+                    Err(e) => tracing::warn!(?e, "quic endpoint rebind failed"),
+                }
+            }
+        }
+    }
+}
+
 struct ConnWrapper {
     conn: Connection,
 }
@@ -145,6 +204,7 @@ pub struct QUICTunnelListener {
     addr: url::Url,
     endpoint: Option<Endpoint>,
     server_cert: Option<Vec<u8>>,
+    tls_config: Option<TlsConfig>,
 }
 
 impl QUICTunnelListener {
@@ -153,9 +213,15 @@ impl QUICTunnelListener {
             addr,
             endpoint: None,
             server_cert: None,
+            tls_config: None,
         }
     }
 
+    /// Use the configured certificate instead of a throwaway one
+    pub fn set_tls_config(&mut self, tls_config: TlsConfig) {
+        self.tls_config = Some(tls_config);
+    }
+
     async fn do_accept(&mut self) -> Result<Box<dyn Tunnel>, super::TunnelError> {
         // accept a single connection
         let conn = self
@@ -193,7 +259,8 @@ impl TunnelListener for QUICTunnelListener {
         let addr =
             check_scheme_and_get_socket_addr::<SocketAddr>(&self.addr, "quic", IpVersion::Both)
                 .await?;
-        let (endpoint, server_cert) = make_server_endpoint(addr)
+        let (certs, key) = get_server_cert(self.tls_config.as_ref())?;
+        let (endpoint, server_cert) = make_server_endpoint_with_cert(addr, certs, key)
             .map_err(|e| anyhow::anyhow!("make server endpoint error: {:?}", e))?;
         self.endpoint = Some(endpoint);
         self.server_cert = Some(server_cert);
@@ -226,6 +293,7 @@ pub struct QUICTunnelConnector {
     addr: url::Url,
     endpoint: Option<Endpoint>,
     ip_version: IpVersion,
+    tls_config: Option<TlsConfig>,
 }
 
 impl QUICTunnelConnector {
@@ -234,8 +302,14 @@ impl QUICTunnelConnector {
             addr,
             endpoint: None,
             ip_version: IpVersion::Both,
+            tls_config: None,
         }
     }
+
+    /// Provides the default CA for verifying servers
+    pub fn set_tls_config(&mut self, tls_config: TlsConfig) {
+        self.tls_config = Some(tls_config);
+    }
 }
 
 #[async_trait::async_trait]
@@ -250,19 +324,26 @@ impl TunnelConnector for QUICTunnelConnector {
             "[::]:0"
         };
 
+        let cert_verify = ServerCertVerify::from_url(&self.addr, self.tls_config.as_ref())?;
+        let (client_config, verify_failure) = configure_verifying_client(&cert_verify)?;
+        let server_name = cert_verify.server_name_str(&self.addr);
+
         let mut endpoint = Endpoint::client(local_addr.parse().unwrap())?;
-        endpoint.set_default_client_config(configure_client());
+        endpoint.set_default_client_config(client_config);
 
         // connect to server
         let connection = endpoint
-            .connect(addr, "localhost")
-            .unwrap()
+            .connect(addr, &server_name)
+            .map_err(|e| anyhow::anyhow!("connect failed: {:?}", e))?
             .await
-            .with_context(|| "connect failed")?;
+            .map_err(|e| {
+                verify_failure.or(anyhow::Error::new(e).context("connect failed").into())
+            })?;
         tracing::info!("[client] connected: addr={}", connection.remote_address());
 
         let local_addr = endpoint.local_addr()?;
 
+        tokio::spawn(monitor_local_addr(endpoint.clone(), connection.clone()));
         self.endpoint = Some(endpoint);
 
         let (w, r) = connection
@@ -340,6 +421,35 @@ mod tests {
         _tunnel_pingpong(listener, connector).await;
     }
 
+    #[tokio::test]
+    async fn quic_pinned_cert() {
+        use crate::tunnel::tls::{format_spki_pin, tests::gen_test_pki};
+
+        let pki = gen_test_pki();
+        let mut listener = QUICTunnelListener::new("quic://0.0.0.0:21013".parse().unwrap());
+        listener.set_tls_config(pki.tls.clone());
+
+        let mut url: url::Url = "quic://127.0.0.1:21013".parse().unwrap();
+        url.query_pairs_mut()
+            .append_pair("pin", &format_spki_pin(&pki.pin));
+        _tunnel_pingpong(listener, QUICTunnelConnector::new(url)).await;
+
+        let mut listener = QUICTunnelListener::new("quic://0.0.0.0:21014".parse().unwrap());
+        listener.set_tls_config(pki.tls);
+        listener.listen().await.unwrap();
+        let j = tokio::spawn(async move {
+            let _ = listener.accept().await;
+        });
+
+        let mut url: url::Url = "quic://127.0.0.1:21014".parse().unwrap();
+        url.query_pairs_mut()
+            .append_pair("pin", &format_spki_pin(&[0u8; 32]));
+        let ret = QUICTunnelConnector::new(url).connect().await;
+        assert!(matches!(ret, Err(TunnelError::CertPinMismatch(_))));
+
+        j.abort();
+    }
+
     #[tokio::test]
     async fn test_alloc_port() {
         // v4
@@ -59,6 +59,24 @@ impl WindowLatency {
             (T::from(sum)) / T::from(count)
         }
     }
+
+    /// Mean absolute deviation of the latencies in the window.
+    pub fn get_jitter_us(&self) -> u64 {
+        let count = self.count.load(Relaxed).min(self.latency_us_window_size) as usize;
+        if count == 0 {
+            return 0;
+        }
+        let samples = self.latency_us_window[..count]
+            .iter()
+            .map(|l| l.load(Relaxed) as i64)
+            .collect::<Vec<_>>();
+        let mean = samples.iter().sum::<i64>() / count as i64;
+        samples
+            .iter()
+            .map(|l| (l - mean).unsigned_abs())
+            .sum::<u64>()
+            / count as u64
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,379 @@
+//! Tunnels over byte streams that are already open when easytier starts.
+//!
+//! `stdio://` uses stdin and stdout of the process, `fd://3` an inherited socket and
+//! `fd://3?write=4` a pair of pipes. This chains easytier over anything that carries bytes, e.g.
+//! `socat EXEC:"easytier-core -p stdio://" EXEC:"ssh host easytier-core --stdio"`.
+//!
+//! A fd is closed together with its tunnel and can not be opened again, so fd:// and stdio://
+//! connectors do not reconnect.
+
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{Read as _, Write as _},
+    os::fd::{AsRawFd as _, FromRawFd as _, OwnedFd, RawFd},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, OnceLock,
+    },
+    task::{ready, Context, Poll},
+};
+
+use async_trait::async_trait;
+use nix::libc;
+use once_cell::sync::Lazy;
+use tokio::io::{unix::AsyncFd, AsyncRead, AsyncWrite, ReadBuf};
+
+use super::{
+    common::{FramedReader, FramedWriter, TunnelWrapper},
+    Tunnel, TunnelError, TunnelInfo, TunnelListener,
+};
+
+const STDIO_MTU_BYTES: usize = 2000;
+
+static STDOUT_FOR_TUNNEL: OnceLock<RawFd> = OnceLock::new();
+// stdin / stdout can not be reopened, so stdio is used by one tunnel per process
+static STDIO_TAKEN: AtomicBool = AtomicBool::new(false);
+// fds handed to a tunnel. they are never released: the tunnel closes them, after that the number
+// may belong to an unrelated file of this process
+static TAKEN_FDS: Lazy<Mutex<HashSet<RawFd>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Keeps the current stdout for the stdio tunnel and points fd 1 to stderr, so nothing printed
+/// later ends up in the tunnel. Call it before printing anything, calling it again is a no-op.
+pub fn reserve_stdout_for_tunnel() -> std::io::Result<RawFd> {
+    if let Some(fd) = STDOUT_FOR_TUNNEL.get() {
+        return Ok(*fd);
+    }
+    let fd = unsafe { libc::fcntl(libc::STDOUT_FILENO, libc::F_DUPFD_CLOEXEC, 3) };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    if unsafe { libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) } < 0 {
+        let err = std::io::Error::last_os_error();
+        unsafe { libc::close(fd) };
+        return Err(err);
+    }
+    Ok(*STDOUT_FOR_TUNNEL.get_or_init(|| fd))
+}
+
+fn parse_fd(s: &str, url: &url::Url) -> Result<RawFd, TunnelError> {
+    s.parse::<RawFd>()
+        .ok()
+        .filter(|fd| *fd >= 0)
+        .ok_or_else(|| TunnelError::InvalidAddr(format!("invalid fd {:?} in {}", s, url)))
+}
+
+// returns the (read fd, write fd) of a fd:// url
+fn parse_fd_url(url: &url::Url) -> Result<(RawFd, RawFd), TunnelError> {
+    match url.scheme() {
+        "stdio" => Ok((libc::STDIN_FILENO, libc::STDOUT_FILENO)),
+        "fd" => {
+            let read_fd = parse_fd(url.host_str().unwrap_or_default(), url)?;
+            let write_fd = match url.query_pairs().find(|(k, _)| k == "write") {
+                Some((_, v)) => parse_fd(&v, url)?,
+                None => read_fd,
+            };
+            Ok((read_fd, write_fd))
+        }
+        scheme => Err(TunnelError::InvalidProtocol(scheme.to_string())),
+    }
+}
+
+// holds STDIO_TAKEN while the stdio tunnel is opened, released again if opening fails on the way.
+struct StdioReservation;
+
+impl StdioReservation {
+    fn take() -> Result<Self, TunnelError> {
+        if STDIO_TAKEN.swap(true, Ordering::SeqCst) {
+            return Err(TunnelError::InvalidAddr(
+                "stdio is used by another tunnel".to_owned(),
+            ));
+        }
+        Ok(Self)
+    }
+
+    // the built tunnel keeps stdio for the rest of the process
+    fn keep(self) {
+        std::mem::forget(self);
+    }
+}
+
+impl Drop for StdioReservation {
+    fn drop(&mut self) {
+        STDIO_TAKEN.store(false, Ordering::SeqCst);
+    }
+}
+
+fn new_async_fd(fd: OwnedFd) -> Result<AsyncFd<File>, TunnelError> {
+    let raw = fd.as_raw_fd();
+    let flags = unsafe { libc::fcntl(raw, libc::F_GETFL) };
+    if flags < 0 || unsafe { libc::fcntl(raw, libc::F_SETFL, flags | libc::O_NONBLOCK) } < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    // epoll refuses regular files, only pipes, sockets and ttys work here
+    AsyncFd::new(File::from(fd))
+        .map_err(|e| TunnelError::InvalidAddr(format!("fd {} can not be polled: {}", raw, e)))
+}
+
+#[derive(Debug)]
+struct FdReader {
+    fd: AsyncFd<File>,
+}
+
+impl AsyncRead for FdReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        loop {
+            let mut guard = ready!(self.fd.poll_read_ready(cx))?;
+            let unfilled = buf.initialize_unfilled();
+            match guard.try_io(|inner| inner.get_ref().read(unfilled)) {
+                Ok(Ok(n)) => {
+                    buf.advance(n);
+                    return Poll::Ready(Ok(()));
+                }
+                Ok(Err(e)) => return Poll::Ready(Err(e)),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FdWriter {
+    // taken on shutdown, closing a pipe is the only way to signal eof
+    fd: Option<AsyncFd<File>>,
+}
+
+impl AsyncWrite for FdWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let Some(fd) = self.fd.as_ref() else {
+            return Poll::Ready(Err(std::io::ErrorKind::BrokenPipe.into()));
+        };
+        loop {
+            let mut guard = ready!(fd.poll_write_ready(cx))?;
+            match guard.try_io(|inner| inner.get_ref().write(buf)) {
+                Ok(ret) => return Poll::Ready(ret),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        if let Some(fd) = self.fd.take() {
+            // the reader may hold a dup of the same socket, so closing alone would not send fin
+            unsafe { libc::shutdown(fd.as_raw_fd(), libc::SHUT_WR) };
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn open_tunnel(url: &url::Url) -> Result<Box<dyn Tunnel>, TunnelError> {
+    let (read_fd, write_fd) = parse_fd_url(url)?;
+    let (write_fd, stdio) = if url.scheme() == "stdio" {
+        let stdio = StdioReservation::take()?;
+        (reserve_stdout_for_tunnel()?, Some(stdio))
+    } else {
+        (write_fd, None)
+    };
+
+    let mut taken = TAKEN_FDS.lock().unwrap();
+    for fd in [read_fd, write_fd] {
+        if stdio.is_none() && taken.contains(&fd) {
+            return Err(TunnelError::InvalidAddr(format!(
+                "fd {} was already used by a tunnel and is closed, {} can not be reopened",
+                fd, url
+            )));
+        }
+        if unsafe { libc::fcntl(fd, libc::F_GETFD) } < 0 {
+            return Err(TunnelError::InvalidAddr(format!("fd {} is not open", fd)));
+        }
+    }
+    if stdio.is_none() {
+        taken.extend([read_fd, write_fd]);
+    }
+    drop(taken);
+
+    // SAFETY: the fds are open and taken above for good, so this tunnel is their only owner
+    let write = unsafe { OwnedFd::from_raw_fd(write_fd) };
+    let read = if read_fd == write_fd {
+        write.try_clone()?
+    } else {
+        unsafe { OwnedFd::from_raw_fd(read_fd) }
+    };
+
+    let reader = FdReader {
+        fd: new_async_fd(read)?,
+    };
+    let writer = FdWriter {
+        fd: Some(new_async_fd(write)?),
+    };
+
+    let info = TunnelInfo {
+        tunnel_type: url.scheme().to_owned(),
+        local_addr: Some(url.clone().into()),
+        remote_addr: Some(url.clone().into()),
+    };
+    if let Some(stdio) = stdio {
+        stdio.keep();
+    }
+    Ok(Box::new(TunnelWrapper::new(
+        FramedReader::new(reader, STDIO_MTU_BYTES),
+        FramedWriter::new(writer),
+        Some(info),
+    )))
+}
+
+/// Accepts the stream given by the url once, later accepts wait forever.
+#[derive(Debug)]
+pub struct StdioTunnelListener {
+    addr: url::Url,
+    accepted: bool,
+}
+
+impl StdioTunnelListener {
+    pub fn new(addr: url::Url) -> Self {
+        StdioTunnelListener {
+            addr,
+            accepted: false,
+        }
+    }
+}
+
+#[async_trait]
+impl TunnelListener for StdioTunnelListener {
+    async fn listen(&mut self) -> Result<(), TunnelError> {
+        parse_fd_url(&self.addr)?;
+        Ok(())
+    }
+
+    async fn accept(&mut self) -> Result<Box<dyn Tunnel>, TunnelError> {
+        if self.accepted {
+            std::future::pending::<()>().await;
+        }
+        self.accepted = true;
+        open_tunnel(&self.addr)
+    }
+
+    fn local_url(&self) -> url::Url {
+        self.addr.clone()
+    }
+}
+
+#[derive(Debug)]
+pub struct StdioTunnelConnector {
+    addr: url::Url,
+}
+
+impl StdioTunnelConnector {
+    pub fn new(addr: url::Url) -> Self {
+        StdioTunnelConnector { addr }
+    }
+}
+
+#[async_trait]
+impl super::TunnelConnector for StdioTunnelConnector {
+    async fn connect(&mut self) -> Result<Box<dyn Tunnel>, TunnelError> {
+        open_tunnel(&self.addr)
+    }
+
+    fn remote_url(&self) -> url::Url {
+        self.addr.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::fd::IntoRawFd as _;
+
+    use crate::tunnel::{
+        common::tests::{_tunnel_bench, _tunnel_pingpong},
+        TunnelConnector,
+    };
+
+    use super::*;
+
+    fn socket_pair_urls() -> (url::Url, url::Url) {
+        let (a, b) = std::os::unix::net::UnixStream::pair().unwrap();
+        (
+            format!("fd://{}", a.into_raw_fd()).parse().unwrap(),
+            format!("fd://{}", b.into_raw_fd()).parse().unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn fd_socket_pingpong() {
+        let (l, c) = socket_pair_urls();
+        _tunnel_pingpong(StdioTunnelListener::new(l), StdioTunnelConnector::new(c)).await
+    }
+
+    #[tokio::test]
+    async fn fd_socket_bench() {
+        let (l, c) = socket_pair_urls();
+        _tunnel_bench(StdioTunnelListener::new(l), StdioTunnelConnector::new(c)).await
+    }
+
+    #[tokio::test]
+    async fn fd_pipe_pingpong() {
+        let (r1, w1) = nix::unistd::pipe().unwrap();
+        let (r2, w2) = nix::unistd::pipe().unwrap();
+        let l = format!("fd://{}?write={}", r1.into_raw_fd(), w2.into_raw_fd());
+        let c = format!("fd://{}?write={}", r2.into_raw_fd(), w1.into_raw_fd());
+        _tunnel_pingpong(
+            StdioTunnelListener::new(l.parse().unwrap()),
+            StdioTunnelConnector::new(c.parse().unwrap()),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn fd_used_once() {
+        let (_, c) = socket_pair_urls();
+        let fd = parse_fd_url(&c).unwrap().0;
+        let mut connector = StdioTunnelConnector::new(c);
+        let tunnel = connector.connect().await.unwrap();
+        assert!(connector.connect().await.is_err());
+
+        // the tunnel closed the fd, its number may now belong to an unrelated file
+        drop(tunnel);
+        let _reused = File::open("/dev/null").unwrap();
+        assert!(TAKEN_FDS.lock().unwrap().contains(&fd));
+        assert!(connector.connect().await.is_err());
+    }
+
+    #[test]
+    fn stdio_released_on_open_error() {
+        let stdio = StdioReservation::take().unwrap();
+        assert!(StdioReservation::take().is_err());
+
+        // dropped on an error path before the tunnel is built, so a later open can retry
+        drop(stdio);
+        assert!(!STDIO_TAKEN.load(Ordering::SeqCst));
+
+        StdioReservation::take().unwrap().keep();
+        assert!(STDIO_TAKEN.swap(false, Ordering::SeqCst));
+    }
+
+    #[test]
+    fn fd_url_parse() {
+        let parse = |s: &str| parse_fd_url(&s.parse().unwrap());
+        assert_eq!(parse("stdio://").unwrap(), (0, 1));
+        assert_eq!(parse("fd://3").unwrap(), (3, 3));
+        assert_eq!(parse("fd://3?write=4").unwrap(), (3, 4));
+        assert!(parse("fd://-1").is_err());
+        assert!(parse("fd://3?write=x").is_err());
+        assert!(parse("fd://").is_err());
+        assert!(parse("tcp://127.0.0.1:1").is_err());
+    }
+}
@@ -4,7 +4,10 @@ use async_trait::async_trait;
 use futures::stream::FuturesUnordered;
 use tokio::net::{TcpListener, TcpSocket, TcpStream};
 
-use super::TunnelInfo;
+use super::{
+    proxy::{redact_url, UpstreamProxy},
+    TunnelInfo,
+};
 use crate::tunnel::common::setup_sokcet2;
 
 use super::{
@@ -131,12 +134,23 @@ fn get_tunnel_with_tcp_stream(
     )))
 }
 
-#[derive(Debug)]
 pub struct TcpTunnelConnector {
     addr: url::Url,
 
     bind_addrs: Vec<SocketAddr>,
     ip_version: IpVersion,
+    proxy: Option<UpstreamProxy>,
+}
+
+impl std::fmt::Debug for TcpTunnelConnector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcpTunnelConnector")
+            .field("addr", &redact_url(&self.addr))
+            .field("bind_addrs", &self.bind_addrs)
+            .field("ip_version", &self.ip_version)
+            .field("proxy", &self.proxy)
+            .finish()
+    }
 }
 
 impl TcpTunnelConnector {
@@ -145,9 +159,25 @@ impl TcpTunnelConnector {
             addr,
             bind_addrs: vec![],
             ip_version: IpVersion::Both,
+            proxy: None,
         }
     }
 
+    /// Dial through an upstream proxy, bind addrs and ip version are ignored in this case.
+    pub fn set_upstream_proxy(&mut self, proxy: Option<UpstreamProxy>) {
+        self.proxy = proxy;
+    }
+
+    async fn connect_with_proxy(
+        &mut self,
+        proxy: UpstreamProxy,
+    ) -> Result<Box<dyn Tunnel>, super::TunnelError> {
+        let (host, port) = UpstreamProxy::target_of(&self.addr)?;
+        let stream = proxy.connect(&host, port).await?;
+        tracing::info!(url = %redact_url(&self.addr), ?proxy, "connect tcp through proxy succ");
+        get_tunnel_with_tcp_stream(stream, self.addr.clone())
+    }
+
     async fn connect_with_default_bind(
         &mut self,
         addr: SocketAddr,
@@ -190,6 +220,9 @@ impl TcpTunnelConnector {
 #[async_trait]
 impl super::TunnelConnector for TcpTunnelConnector {
     async fn connect(&mut self) -> Result<Box<dyn Tunnel>, super::TunnelError> {
+        if let Some(proxy) = self.proxy.clone() {
+            return self.connect_with_proxy(proxy).await;
+        }
         let addr =
             check_scheme_and_get_socket_addr::<SocketAddr>(&self.addr, "tcp", self.ip_version)
                 .await?;
@@ -283,6 +316,29 @@ mod tests {
         _tunnel_pingpong(listener, connector).await;
     }
 
+    #[rstest::rstest]
+    #[tokio::test]
+    async fn tcp_pingpong_through_proxy(#[values("http", "socks5")] proto: &str) {
+        use crate::tunnel::proxy::tests::{run_http_proxy, run_socks5_proxy};
+
+        let (proxy_port, proxy_task) = if proto == "http" {
+            run_http_proxy(Some("user:pass")).await
+        } else {
+            run_socks5_proxy(Some("user:pass")).await
+        };
+        let port = if proto == "http" { 31016 } else { 31017 };
+        let listener = TcpTunnelListener::new(format!("tcp://127.0.0.1:{}", port).parse().unwrap());
+        // the per connector proxy is given with the url param
+        let url = format!(
+            "tcp://localhost:{}?proxy={}://user:pass@127.0.0.1:{}",
+            port, proto, proxy_port
+        );
+        let mut connector = TcpTunnelConnector::new(url.parse().unwrap());
+        connector.set_upstream_proxy(UpstreamProxy::for_connector(&connector.addr, None).unwrap());
+        _tunnel_pingpong(listener, connector).await;
+        proxy_task.abort();
+    }
+
     #[tokio::test]
     async fn test_alloc_port() {
         // v4
@@ -0,0 +1,396 @@
+// tls:// tunnel, the tcp length delimited framing inside a plain TLS stream.
+//
+// Listener url params:
+// - `alpn=<proto>[,<proto>]`: ALPN protocols accepted by the listener
+//
+// Connector url params:
+// - `alpn=<proto>[,<proto>]`: ALPN protocols offered to the server
+// - `sni=<name>`: server name to send and verify, defaults to the url host, useful when
+//   dialing an ip behind a load balancer that routes by SNI
+// - the certificate options of `tls::ServerCertVerify` and `proxy` of `proxy::UpstreamProxy`
+
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use futures::stream::FuturesUnordered;
+use rustls::pki_types::ServerName;
+use tokio::{
+    net::{TcpListener, TcpSocket, TcpStream},
+    time::timeout,
+};
+use tokio_rustls::{TlsAcceptor, TlsConnector};
+
+use crate::common::config::TlsConfig;
+
+use super::{
+    check_scheme_and_get_socket_addr,
+    common::{setup_sokcet2, wait_for_connect_futures, FramedReader, FramedWriter, TunnelWrapper},
+    insecure_tls::init_crypto_provider,
+    proxy::{redact_url, UpstreamProxy},
+    tls::{get_server_cert, ServerCertVerify},
+    IpVersion, Tunnel, TunnelConnector, TunnelError, TunnelInfo, TunnelListener,
+};
+
+const TLS_MTU_BYTES: usize = 2000;
+
+fn parse_alpn(url: &url::Url) -> Vec<Vec<u8>> {
+    url.query_pairs()
+        .filter(|(k, _)| k == "alpn")
+        .flat_map(|(_, v)| {
+            v.split(',')
+                .map(|p| p.trim().as_bytes().to_vec())
+                .filter(|p| !p.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn get_tunnel_with_tls_stream<S>(
+    stream: S,
+    info: TunnelInfo,
+) -> Result<Box<dyn Tunnel>, TunnelError>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + 'static,
+{
+    let (r, w) = tokio::io::split(stream);
+    Ok(Box::new(TunnelWrapper::new(
+        FramedReader::new(r, TLS_MTU_BYTES),
+        FramedWriter::new(w),
+        Some(info),
+    )))
+}
+
+pub struct TlsTunnelListener {
+    addr: url::Url,
+    listener: Option<TcpListener>,
+    tls_config: Option<TlsConfig>,
+    acceptor: Option<TlsAcceptor>,
+}
+
+impl std::fmt::Debug for TlsTunnelListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsTunnelListener")
+            .field("addr", &self.addr)
+            .field("listener", &self.listener)
+            .finish()
+    }
+}
+
+impl TlsTunnelListener {
+    pub fn new(addr: url::Url) -> Self {
+        TlsTunnelListener {
+            addr,
+            listener: None,
+            tls_config: None,
+            acceptor: None,
+        }
+    }
+
+    /// Use the configured certificate instead of a throwaway one
+    pub fn set_tls_config(&mut self, tls_config: TlsConfig) {
+        self.tls_config = Some(tls_config);
+    }
+
+    async fn try_accept(&self, stream: TcpStream) -> Result<Box<dyn Tunnel>, TunnelError> {
+        let info = TunnelInfo {
+            tunnel_type: "tls".to_owned(),
+            local_addr: Some(self.local_url().into()),
+            remote_addr: Some(
+                super::build_url_from_socket_addr(&stream.peer_addr()?.to_string(), "tls").into(),
+            ),
+        };
+
+        let stream = self.acceptor.as_ref().unwrap().accept(stream).await?;
+        let (_, conn) = stream.get_ref();
+        tracing::info!(
+            sni = ?conn.server_name(),
+            alpn = ?conn.alpn_protocol().map(String::from_utf8_lossy),
+            ?info,
+            "tls tunnel accepted"
+        );
+
+        get_tunnel_with_tls_stream(stream, info)
+    }
+}
+
+#[async_trait]
+impl TunnelListener for TlsTunnelListener {
+    async fn listen(&mut self) -> Result<(), TunnelError> {
+        self.listener = None;
+        let addr =
+            check_scheme_and_get_socket_addr::<SocketAddr>(&self.addr, "tls", IpVersion::Both)
+                .await?;
+
+        init_crypto_provider();
+        let (certs, key) = get_server_cert(self.tls_config.as_ref())?;
+        let mut config = rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .with_context(|| "Failed to create server config")?;
+        config.alpn_protocols = parse_alpn(&self.addr);
+        self.acceptor = Some(TlsAcceptor::from(Arc::new(config)));
+
+        let socket2_socket = socket2::Socket::new(
+            socket2::Domain::for_address(addr),
+            socket2::Type::STREAM,
+            Some(socket2::Protocol::TCP),
+        )?;
+        setup_sokcet2(&socket2_socket, &addr)?;
+        let socket = TcpSocket::from_std_stream(socket2_socket.into());
+
+        if let Err(e) = socket.set_nodelay(true) {
+            tracing::warn!(?e, "set_nodelay fail in listen");
+        }
+
+        self.addr
+            .set_port(Some(socket.local_addr()?.port()))
+            .unwrap();
+
+        self.listener = Some(socket.listen(1024)?);
+        Ok(())
+    }
+
+    async fn accept(&mut self) -> Result<Box<dyn Tunnel>, TunnelError> {
+        loop {
+            let listener = self.listener.as_ref().unwrap();
+            // only fail on tcp accept error
+            let (stream, _) = listener.accept().await?;
+            if let Err(e) = stream.set_nodelay(true) {
+                tracing::warn!(?e, "set_nodelay fail in accept");
+            }
+            match timeout(Duration::from_secs(3), self.try_accept(stream)).await {
+                Ok(Ok(tunnel)) => return Ok(tunnel),
+                e => {
+                    tracing::error!(?e, ?self, "Failed to accept tls tunnel");
+                    continue;
+                }
+            }
+        }
+    }
+
+    fn local_url(&self) -> url::Url {
+        self.addr.clone()
+    }
+}
+
+pub struct TlsTunnelConnector {
+    addr: url::Url,
+
+    bind_addrs: Vec<SocketAddr>,
+    ip_version: IpVersion,
+    tls_config: Option<TlsConfig>,
+    proxy: Option<UpstreamProxy>,
+}
+
+impl std::fmt::Debug for TlsTunnelConnector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsTunnelConnector")
+            .field("addr", &redact_url(&self.addr))
+            .field("bind_addrs", &self.bind_addrs)
+            .field("ip_version", &self.ip_version)
+            .field("tls_config", &self.tls_config)
+            .field("proxy", &self.proxy)
+            .finish()
+    }
+}
+
+impl TlsTunnelConnector {
+    pub fn new(addr: url::Url) -> Self {
+        TlsTunnelConnector {
+            addr,
+            bind_addrs: vec![],
+            ip_version: IpVersion::Both,
+            tls_config: None,
+            proxy: None,
+        }
+    }
+
+    /// Provides the default CA for verifying tls servers
+    pub fn set_tls_config(&mut self, tls_config: TlsConfig) {
+        self.tls_config = Some(tls_config);
+    }
+
+    /// Dial through an upstream proxy, bind addrs and ip version are ignored in this case.
+    pub fn set_upstream_proxy(&mut self, proxy: Option<UpstreamProxy>) {
+        self.proxy = proxy;
+    }
+
+    fn server_name(
+        &self,
+        cert_verify: &ServerCertVerify,
+    ) -> Result<ServerName<'static>, TunnelError> {
+        match self.addr.query_pairs().find(|(k, _)| k == "sni") {
+            Some((_, sni)) => ServerName::try_from(sni.to_string())
+                .map_err(|_| TunnelError::InvalidProtocol(format!("Invalid SNI: {}", sni))),
+            None => cert_verify.server_name(&self.addr),
+        }
+    }
+
+    async fn connect_tcp(&self) -> Result<TcpStream, TunnelError> {
+        if let Some(proxy) = &self.proxy {
+            let (host, port) = UpstreamProxy::target_of(&self.addr)?;
+            return proxy.connect(&host, port).await;
+        }
+
+        let addr =
+            check_scheme_and_get_socket_addr::<SocketAddr>(&self.addr, "tls", self.ip_version)
+                .await?;
+        if self.bind_addrs.is_empty() {
+            return Ok(TcpStream::connect(addr).await?);
+        }
+
+        let futures = FuturesUnordered::new();
+        for bind_addr in self.bind_addrs.iter() {
+            tracing::info!(bind_addr = ?bind_addr, ?addr, "bind addr");
+
+            let socket2_socket = socket2::Socket::new(
+                socket2::Domain::for_address(addr),
+                socket2::Type::STREAM,
+                Some(socket2::Protocol::TCP),
+            )?;
+
+            if let Err(e) = setup_sokcet2(&socket2_socket, bind_addr) {
+                tracing::error!(bind_addr = ?bind_addr, ?addr, "bind addr fail: {:?}", e);
+                continue;
+            }
+
+            let socket = TcpSocket::from_std_stream(socket2_socket.into());
+            futures.push(socket.connect(addr));
+        }
+        wait_for_connect_futures(futures).await
+    }
+}
+
+#[async_trait]
+impl TunnelConnector for TlsTunnelConnector {
+    async fn connect(&mut self) -> Result<Box<dyn Tunnel>, TunnelError> {
+        let cert_verify = ServerCertVerify::from_url(&self.addr, self.tls_config.as_ref())?;
+        let server_name = self.server_name(&cert_verify)?;
+        let (mut config, verify_failure) = cert_verify.client_config()?;
+        config.alpn_protocols = parse_alpn(&self.addr);
+
+        // the url may carry the proxy credentials
+        let url = redact_url(&self.addr);
+        tracing::info!(%url, ?server_name, "connect tls start, bind addrs: {:?}", self.bind_addrs);
+        let stream = self.connect_tcp().await?;
+        if let Err(e) = stream.set_nodelay(true) {
+            tracing::warn!(?e, "set_nodelay fail in connect");
+        }
+
+        let info = TunnelInfo {
+            tunnel_type: "tls".to_owned(),
+            local_addr: Some(
+                super::build_url_from_socket_addr(&stream.local_addr()?.to_string(), "tls").into(),
+            ),
+            remote_addr: Some(self.addr.clone().into()),
+        };
+
+        let stream = TlsConnector::from(Arc::new(config))
+            .connect(server_name, stream)
+            .await
+            .map_err(|e| verify_failure.or(e.into()))?;
+        tracing::info!(%url, "connect tls succ");
+
+        get_tunnel_with_tls_stream(stream, info)
+    }
+
+    fn remote_url(&self) -> url::Url {
+        self.addr.clone()
+    }
+
+    fn set_bind_addrs(&mut self, addrs: Vec<SocketAddr>) {
+        self.bind_addrs = addrs;
+    }
+
+    fn set_ip_version(&mut self, ip_version: IpVersion) {
+        self.ip_version = ip_version;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tunnel::{
+        common::tests::{_tunnel_bench, _tunnel_pingpong},
+        tls::tests::gen_test_pki,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn tls_pingpong() {
+        let listener = TlsTunnelListener::new("tls://0.0.0.0:31021".parse().unwrap());
+        let connector = TlsTunnelConnector::new("tls://127.0.0.1:31021".parse().unwrap());
+        _tunnel_pingpong(listener, connector).await
+    }
+
+    #[tokio::test]
+    async fn tls_bench() {
+        let listener = TlsTunnelListener::new("tls://0.0.0.0:31022".parse().unwrap());
+        let connector = TlsTunnelConnector::new("tls://127.0.0.1:31022".parse().unwrap());
+        _tunnel_bench(listener, connector).await
+    }
+
+    #[tokio::test]
+    async fn tls_pingpong_with_bind() {
+        let listener = TlsTunnelListener::new("tls://127.0.0.1:31023".parse().unwrap());
+        let mut connector = TlsTunnelConnector::new("tls://127.0.0.1:31023".parse().unwrap());
+        connector.set_bind_addrs(vec!["127.0.0.1:0".parse().unwrap()]);
+        _tunnel_pingpong(listener, connector).await
+    }
+
+    #[tokio::test]
+    async fn tls_sni_and_alpn() {
+        let pki = gen_test_pki();
+        let mut listener =
+            TlsTunnelListener::new("tls://127.0.0.1:31024?alpn=easytier".parse().unwrap());
+        listener.set_tls_config(pki.tls.clone());
+        listener.listen().await.unwrap();
+        let j = tokio::spawn(async move {
+            loop {
+                let _ = listener.accept().await;
+            }
+        });
+
+        let connect = |query: &str| {
+            let mut connector = TlsTunnelConnector::new(
+                format!("tls://127.0.0.1:31024?verify=ca&{}", query)
+                    .parse()
+                    .unwrap(),
+            );
+            connector.set_tls_config(TlsConfig {
+                ca: Some(pki.ca.clone()),
+                ..Default::default()
+            });
+            async move { connector.connect().await }
+        };
+
+        // the certificate is issued for localhost, so the sni is verified against it
+        connect("alpn=h2,easytier&sni=localhost").await.unwrap();
+        connect("alpn=easytier").await.unwrap();
+        let ret = connect("alpn=easytier&sni=example.com").await;
+        assert!(matches!(ret, Err(TunnelError::CertVerifyFailed(_))));
+        // no common alpn protocol
+        connect("alpn=h2&sni=localhost").await.unwrap_err();
+
+        j.abort();
+    }
+
+    #[tokio::test]
+    async fn tls_pingpong_through_proxy() {
+        use crate::tunnel::proxy::tests::run_socks5_proxy;
+
+        let (proxy_port, proxy_task) = run_socks5_proxy(None).await;
+        let listener = TlsTunnelListener::new("tls://127.0.0.1:31025".parse().unwrap());
+        let url: url::Url = format!(
+            "tls://localhost:31025?proxy=socks5://127.0.0.1:{}",
+            proxy_port
+        )
+        .parse()
+        .unwrap();
+        let mut connector = TlsTunnelConnector::new(url.clone());
+        connector.set_upstream_proxy(UpstreamProxy::for_connector(&url, None).unwrap());
+        _tunnel_pingpong(listener, connector).await;
+        proxy_task.abort();
+    }
+}
@@ -0,0 +1,510 @@
+//! Server certificates for wss/quic/tls listeners and certificate verification for
+//! the corresponding connectors.
+//!
+//! Connectors pick the verification from the peer url query:
+//! - `pin=sha256/<base64>`: the SPKI hash of the server certificate must match one of the pins
+//! - `verify=system`: verify the chain against the system roots and the host name
+//! - `verify=ca` or `ca=<pem path>`: verify the chain against a custom CA, `ca` falls back
+//!   to `[tls] ca` in the config
+//! - `verify=none`: accept any certificate
+//!
+//! Without any of them, the configured `[tls] ca` is used if present, otherwise any
+//! certificate is accepted to stay compatible with listeners using throwaway certs.
+
+use std::sync::{Arc, Mutex};
+
+use base64::{
+    prelude::{BASE64_STANDARD, BASE64_STANDARD_NO_PAD},
+    Engine as _,
+};
+use rustls::{
+    client::{
+        danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        WebPkiServerVerifier,
+    },
+    crypto::CryptoProvider,
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+    CertificateError, RootCertStore,
+};
+use sha2::{Digest, Sha256};
+
+use crate::common::config::TlsConfig;
+
+use super::{
+    insecure_tls::{get_insecure_tls_cert, get_insecure_tls_client_config, init_crypto_provider},
+    TunnelError,
+};
+
+const PIN_PREFIX: &str = "sha256/";
+
+pub type SpkiPin = [u8; 32];
+
+/// Read a PEM value which is either inline PEM or a path to a PEM file.
+fn load_pem(value: &str) -> Result<Vec<u8>, TunnelError> {
+    if value.contains("-----BEGIN") {
+        return Ok(value.as_bytes().to_vec());
+    }
+    std::fs::read(value)
+        .map_err(|e| TunnelError::TlsConfigError(format!("failed to read {}: {}", value, e)))
+}
+
+pub fn load_certs(value: &str) -> Result<Vec<CertificateDer<'static>>, TunnelError> {
+    let pem = load_pem(value)?;
+    let certs = CertificateDer::pem_slice_iter(&pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| TunnelError::TlsConfigError(format!("invalid certificate: {:?}", e)))?;
+    if certs.is_empty() {
+        return Err(TunnelError::TlsConfigError(
+            "no certificate found in pem".to_string(),
+        ));
+    }
+    Ok(certs)
+}
+
+pub fn load_private_key(value: &str) -> Result<PrivateKeyDer<'static>, TunnelError> {
+    let pem = load_pem(value)?;
+    PrivateKeyDer::from_pem_slice(&pem)
+        .map_err(|e| TunnelError::TlsConfigError(format!("invalid private key: {:?}", e)))
+}
+
+/// Certificate chain and key for listeners, a throwaway self-signed cert if none is configured.
+pub fn get_server_cert(
+    tls: Option<&TlsConfig>,
+) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), TunnelError> {
+    match tls.map(|tls| (tls.cert.as_deref(), tls.key.as_deref())) {
+        Some((Some(cert), Some(key))) => Ok((load_certs(cert)?, load_private_key(key)?)),
+        Some((Some(_), None)) | Some((None, Some(_))) => Err(TunnelError::TlsConfigError(
+            "tls cert and key must be set together".to_string(),
+        )),
+        _ => Ok(get_insecure_tls_cert()),
+    }
+}
+
+/// Split the first DER element off `input`, returns (tag, content, rest).
+fn der_split(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&len, rest) = rest.split_first()?;
+    let (len, rest) = if len < 0x80 {
+        (len as usize, rest)
+    } else {
+        let n = (len & 0x7f) as usize;
+        if n == 0 || n > 4 || rest.len() < n {
+            return None;
+        }
+        let len = rest[..n]
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        (len, &rest[n..])
+    };
+    if rest.len() < len {
+        return None;
+    }
+    Some((tag, &rest[..len], &rest[len..]))
+}
+
+/// SHA-256 of the DER encoded SubjectPublicKeyInfo of a certificate.
+pub fn spki_sha256(cert_der: &[u8]) -> Option<SpkiPin> {
+    const SEQUENCE: u8 = 0x30;
+    const EXPLICIT_VERSION: u8 = 0xa0;
+
+    let (tag, cert, _) = der_split(cert_der)?;
+    if tag != SEQUENCE {
+        return None;
+    }
+    let (tag, mut tbs, _) = der_split(cert)?;
+    if tag != SEQUENCE {
+        return None;
+    }
+    if tbs.first() == Some(&EXPLICIT_VERSION) {
+        tbs = der_split(tbs)?.2;
+    }
+    // serial number, signature algorithm, issuer, validity, subject
+    for _ in 0..5 {
+        tbs = der_split(tbs)?.2;
+    }
+    let (tag, _, rest) = der_split(tbs)?;
+    if tag != SEQUENCE {
+        return None;
+    }
+    let spki = &tbs[..tbs.len() - rest.len()];
+    Some(Sha256::digest(spki).into())
+}
+
+pub fn format_spki_pin(pin: &SpkiPin) -> String {
+    format!("{}{}", PIN_PREFIX, BASE64_STANDARD.encode(pin))
+}
+
+/// Parse `sha256/<base64>`, both standard and url-safe base64 are accepted.
+pub fn parse_spki_pin(pin: &str) -> Result<SpkiPin, TunnelError> {
+    let invalid = || TunnelError::TlsConfigError(format!("invalid certificate pin: {}", pin));
+    let encoded = pin.trim().strip_prefix(PIN_PREFIX).ok_or_else(invalid)?;
+    // '+' turns into a space when the query is decoded
+    let encoded: String = encoded
+        .trim_end_matches('=')
+        .chars()
+        .map(|c| match c {
+            ' ' | '-' => '+',
+            '_' => '/',
+            c => c,
+        })
+        .collect();
+    BASE64_STANDARD_NO_PAD
+        .decode(encoded)
+        .ok()
+        .and_then(|hash| SpkiPin::try_from(hash).ok())
+        .ok_or_else(invalid)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrustRoots {
+    System,
+    // inline pem or pem file path
+    Ca(String),
+}
+
+impl TrustRoots {
+    fn root_store(&self) -> Result<RootCertStore, TunnelError> {
+        let mut store = RootCertStore::empty();
+        match self {
+            TrustRoots::System => {
+                let native = rustls_native_certs::load_native_certs();
+                if !native.errors.is_empty() {
+                    tracing::warn!(errors = ?native.errors, "failed to load some system certs");
+                }
+                store.add_parsable_certificates(native.certs);
+                // some platforms (e.g. android) have no readable cert store
+                if store.is_empty() {
+                    store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+                }
+            }
+            TrustRoots::Ca(ca) => {
+                for cert in load_certs(ca)? {
+                    store
+                        .add(cert)
+                        .map_err(|e| TunnelError::TlsConfigError(format!("invalid ca: {}", e)))?;
+                }
+            }
+        }
+        Ok(store)
+    }
+}
+
+/// How a connector verifies the server certificate, both checks apply if both are set.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerCertVerify {
+    pub roots: Option<TrustRoots>,
+    pub pins: Vec<SpkiPin>,
+}
+
+impl ServerCertVerify {
+    pub fn from_url(url: &url::Url, tls: Option<&TlsConfig>) -> Result<Self, TunnelError> {
+        let mut pins = vec![];
+        let mut verify = None;
+        let mut ca = None;
+        for (k, v) in url.query_pairs() {
+            match k.as_ref() {
+                "pin" => {
+                    for pin in v.split(',').filter(|p| !p.trim().is_empty()) {
+                        pins.push(parse_spki_pin(pin)?);
+                    }
+                }
+                "verify" => verify = Some(v.to_string()),
+                "ca" => ca = Some(v.to_string()),
+                _ => {}
+            }
+        }
+        let config_ca = tls.and_then(|tls| tls.ca.clone());
+
+        let roots = match verify.as_deref() {
+            Some("none") => None,
+            Some("system") => Some(TrustRoots::System),
+            Some("ca") => Some(TrustRoots::Ca(ca.or(config_ca).ok_or_else(|| {
+                TunnelError::TlsConfigError("verify=ca but no ca configured".to_string())
+            })?)),
+            Some(other) => {
+                return Err(TunnelError::TlsConfigError(format!(
+                    "unknown verify mode: {}",
+                    other
+                )))
+            }
+            None => match ca {
+                Some(ca) => Some(TrustRoots::Ca(ca)),
+                // a pin alone is enough for self-signed certs
+                None if pins.is_empty() => config_ca.map(TrustRoots::Ca),
+                None => None,
+            },
+        };
+
+        Ok(Self { roots, pins })
+    }
+
+    pub fn is_insecure(&self) -> bool {
+        self.roots.is_none() && self.pins.is_empty()
+    }
+
+    /// Server name to send for `url`, ip hosts are only used as name when the chain is verified.
+    pub fn server_name_str(&self, url: &url::Url) -> String {
+        let host = url
+            .host_str()
+            .unwrap_or_default()
+            .trim_start_matches('[')
+            .trim_end_matches(']');
+        // non special schemes like quic:// and tls:// keep ip hosts as opaque domains
+        match host.parse::<std::net::IpAddr>() {
+            Ok(ip) if self.roots.is_some() => ip.to_string(),
+            Err(_) if !host.is_empty() => host.to_string(),
+            // use "localhost" as SNI for url without domain to avoid IP blocking.
+            _ => "localhost".to_string(),
+        }
+    }
+
+    pub fn server_name(&self, url: &url::Url) -> Result<ServerName<'static>, TunnelError> {
+        ServerName::try_from(self.server_name_str(url))
+            .map_err(|_| TunnelError::InvalidProtocol("Invalid SNI".to_string()))
+    }
+
+    /// Build the rustls client config, verification failures are recorded in the returned slot
+    /// so the connector can report them instead of a generic handshake error.
+    pub fn client_config(&self) -> Result<(rustls::ClientConfig, CertVerifyFailure), TunnelError> {
+        let failure = CertVerifyFailure::default();
+        if self.is_insecure() {
+            return Ok((get_insecure_tls_client_config(), failure));
+        }
+
+        init_crypto_provider();
+        let provider = CryptoProvider::get_default().unwrap().clone();
+        let webpki = match &self.roots {
+            Some(roots) => Some(
+                WebPkiServerVerifier::builder_with_provider(
+                    Arc::new(roots.root_store()?),
+                    provider.clone(),
+                )
+                .build()
+                .map_err(|e| TunnelError::TlsConfigError(e.to_string()))?,
+            ),
+            None => None,
+        };
+        let verifier = TunnelCertVerifier {
+            webpki,
+            pins: self.pins.clone(),
+            provider,
+            failure: failure.clone(),
+        };
+
+        let mut config = rustls::ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(verifier))
+            .with_no_client_auth();
+        config.enable_sni = true;
+        config.enable_early_data = false;
+        Ok((config, failure))
+    }
+}
+
+/// Reason of the last rejected server certificate.
+#[derive(Debug, Clone, Default)]
+pub struct CertVerifyFailure(Arc<Mutex<Option<TunnelError>>>);
+
+impl CertVerifyFailure {
+    fn set(&self, e: TunnelError) {
+        *self.0.lock().unwrap() = Some(e);
+    }
+
+    /// Prefer the verification failure over `e` if the certificate was rejected.
+    pub fn or(&self, e: TunnelError) -> TunnelError {
+        self.0.lock().unwrap().take().unwrap_or(e)
+    }
+}
+
+#[derive(Debug)]
+struct TunnelCertVerifier {
+    webpki: Option<Arc<WebPkiServerVerifier>>,
+    pins: Vec<SpkiPin>,
+    provider: Arc<CryptoProvider>,
+    failure: CertVerifyFailure,
+}
+
+impl ServerCertVerifier for TunnelCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if let Some(webpki) = &self.webpki {
+            if let Err(e) =
+                webpki.verify_server_cert(end_entity, intermediates, server_name, ocsp, now)
+            {
+                self.failure
+                    .set(TunnelError::CertVerifyFailed(e.to_string()));
+                return Err(e);
+            }
+        }
+
+        if !self.pins.is_empty() {
+            let Some(pin) = spki_sha256(end_entity) else {
+                self.failure.set(TunnelError::CertVerifyFailed(
+                    "malformed server certificate".to_string(),
+                ));
+                return Err(rustls::Error::InvalidCertificate(
+                    CertificateError::BadEncoding,
+                ));
+            };
+            if !self.pins.contains(&pin) {
+                self.failure
+                    .set(TunnelError::CertPinMismatch(format_spki_pin(&pin)));
+                return Err(rustls::Error::InvalidCertificate(
+                    CertificateError::ApplicationVerificationFailure,
+                ));
+            }
+        }
+
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// A CA and a leaf cert for `localhost` / `127.0.0.1` signed by it, all as PEM.
+    pub struct TestPki {
+        pub ca: String,
+        pub tls: TlsConfig,
+        pub pin: SpkiPin,
+    }
+
+    pub fn gen_test_pki() -> TestPki {
+        let mut ca_params = rcgen::CertificateParams::new(vec![]);
+        ca_params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+        let ca = rcgen::Certificate::from_params(ca_params).unwrap();
+
+        let mut params = rcgen::CertificateParams::new(vec!["localhost".to_string()]);
+        params
+            .subject_alt_names
+            .push(rcgen::SanType::IpAddress("127.0.0.1".parse().unwrap()));
+        let leaf = rcgen::Certificate::from_params(params).unwrap();
+        let leaf_der = leaf.serialize_der_with_signer(&ca).unwrap();
+
+        TestPki {
+            ca: ca.serialize_pem().unwrap(),
+            tls: TlsConfig {
+                cert: Some(leaf.serialize_pem_with_signer(&ca).unwrap()),
+                key: Some(leaf.serialize_private_key_pem()),
+                ca: None,
+            },
+            pin: spki_sha256(&leaf_der).unwrap(),
+        }
+    }
+
+    #[test]
+    fn spki_pin_matches_public_key() {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let pin = spki_sha256(&cert.serialize_der().unwrap()).unwrap();
+        let expected: SpkiPin = Sha256::digest(cert.get_key_pair().public_key_der()).into();
+        assert_eq!(pin, expected);
+
+        let formatted = format_spki_pin(&pin);
+        assert_eq!(parse_spki_pin(&formatted).unwrap(), pin);
+        // decoded from a query string where '+' became a space
+        assert_eq!(parse_spki_pin(&formatted.replace('+', " ")).unwrap(), pin);
+        assert!(parse_spki_pin("sha256/AAAA").is_err());
+        assert!(parse_spki_pin("md5/AAAA").is_err());
+
+        assert!(spki_sha256(b"not a certificate").is_none());
+    }
+
+    #[test]
+    fn verify_mode_from_url() {
+        let pin = format_spki_pin(&[7u8; 32]);
+        let tls = TlsConfig {
+            ca: Some("/etc/easytier/ca.pem".to_string()),
+            ..Default::default()
+        };
+
+        let url: url::Url = "wss://example.com:443".parse().unwrap();
+        assert!(ServerCertVerify::from_url(&url, None)
+            .unwrap()
+            .is_insecure());
+        assert_eq!(
+            ServerCertVerify::from_url(&url, Some(&tls)).unwrap().roots,
+            Some(TrustRoots::Ca("/etc/easytier/ca.pem".to_string()))
+        );
+
+        let mut url: url::Url = "quic://1.2.3.4:11012".parse().unwrap();
+        url.query_pairs_mut().append_pair("pin", &pin);
+        let verify = ServerCertVerify::from_url(&url, Some(&tls)).unwrap();
+        assert_eq!(verify.roots, None);
+        assert_eq!(verify.pins, vec![[7u8; 32]]);
+        assert_eq!(
+            verify.server_name(&url).unwrap(),
+            ServerName::try_from("localhost").unwrap()
+        );
+
+        url.query_pairs_mut().append_pair("verify", "system");
+        let verify = ServerCertVerify::from_url(&url, None).unwrap();
+        assert_eq!(verify.roots, Some(TrustRoots::System));
+        assert_eq!(
+            verify.server_name(&url).unwrap(),
+            ServerName::try_from("1.2.3.4").unwrap()
+        );
+
+        let url: url::Url = "wss://example.com?verify=ca".parse().unwrap();
+        assert!(matches!(
+            ServerCertVerify::from_url(&url, None),
+            Err(TunnelError::TlsConfigError(_))
+        ));
+        let url: url::Url = "wss://example.com?verify=bogus".parse().unwrap();
+        assert!(ServerCertVerify::from_url(&url, None).is_err());
+    }
+
+    #[test]
+    fn load_inline_server_cert() {
+        let pki = gen_test_pki();
+        let (certs, _) = get_server_cert(Some(&pki.tls)).unwrap();
+        assert_eq!(spki_sha256(&certs[0]), Some(pki.pin));
+
+        let missing_key = TlsConfig {
+            key: None,
+            ..pki.tls.clone()
+        };
+        assert!(get_server_cert(Some(&missing_key)).is_err());
+        assert!(get_server_cert(None).is_ok());
+    }
+}
@@ -5,6 +5,7 @@ use std::{
 };
 
 use anyhow::Context;
+use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use bytes::BytesMut;
 use dashmap::DashMap;
@@ -15,13 +16,20 @@ use zerocopy::{AsBytes, FromBytes};
 use std::net::SocketAddr;
 use tokio::{
     net::UdpSocket,
-    sync::mpsc::{Receiver, Sender, UnboundedReceiver, UnboundedSender},
+    sync::{
+        mpsc::{Receiver, Sender, UnboundedReceiver, UnboundedSender},
+        watch,
+    },
     task::JoinSet,
 };
 
 use tracing::{instrument, Instrument};
 
-use super::{packet_def::V6HolePunchPacket, TunnelInfo};
+use super::{
+    fec::{FecConfig, FecDecoder, FecEncoder, FecLinkState},
+    packet_def::{V6HolePunchPacket, UDP_TUNNEL_FLAG_FEC},
+    TunnelInfo,
+};
 use crate::{
     common::{join_joinset_background, scoped_task::ScopedTask},
     tunnel::{
@@ -33,7 +41,7 @@ use crate::{
 };
 
 use super::{
-    common::{setup_sokcet2, setup_sokcet2_ext, wait_for_connect_futures},
+    common::{setup_sokcet2, setup_sokcet2_ext, wait_for_connect_futures, LocalAddrWatcher},
     packet_def::{UDPTunnelHeader, UDP_TUNNEL_HEADER_SIZE},
     ring::{RingSink, RingStream},
     IpVersion, Tunnel, TunnelConnCounter, TunnelError, TunnelListener, TunnelUrl,
@@ -61,10 +69,11 @@ where
     ret
 }
 
-fn new_syn_packet(conn_id: u32, magic: u64) -> ZCPacket {
+fn new_syn_packet(conn_id: u32, magic: u64, flags: u8) -> ZCPacket {
     new_udp_packet(
         |header| {
             header.msg_type = UdpPacketType::Syn as u8;
+            header.padding = flags;
             header.conn_id.set(conn_id);
             header.len.set(8);
         },
@@ -72,10 +81,11 @@ fn new_syn_packet(conn_id: u32, magic: u64) -> ZCPacket {
     )
 }
 
-fn new_sack_packet(conn_id: u32, magic: u64) -> ZCPacket {
+fn new_sack_packet(conn_id: u32, magic: u64, flags: u8) -> ZCPacket {
     new_udp_packet(
         |header| {
             header.msg_type = UdpPacketType::Sack as u8;
+            header.padding = flags;
             header.conn_id.set(conn_id);
             header.len.set(8);
         },
@@ -83,6 +93,53 @@ fn new_sack_packet(conn_id: u32, magic: u64) -> ZCPacket {
     )
 }
 
+const UDP_MIGRATE_PAYLOAD_LEN: usize = 20;
+
+// nonce is zero in the first migrate of a seq and echoes the challenge nonce afterwards.
+fn new_migrate_packet(
+    msg_type: UdpPacketType,
+    conn_id: u32,
+    magic: u64,
+    seq: u32,
+    nonce: u64,
+) -> ZCPacket {
+    let mut body = [0u8; UDP_MIGRATE_PAYLOAD_LEN];
+    body[..8].copy_from_slice(&magic.to_le_bytes());
+    body[8..12].copy_from_slice(&seq.to_le_bytes());
+    body[12..].copy_from_slice(&nonce.to_le_bytes());
+    new_udp_packet(
+        |header| {
+            header.msg_type = msg_type as u8;
+            header.conn_id.set(conn_id);
+            header.len.set(UDP_MIGRATE_PAYLOAD_LEN as u16);
+        },
+        Some(&body),
+    )
+}
+
+// returns the (magic, seq, nonce) of a migrate / migrate ack / migrate challenge packet
+fn parse_migrate_packet(zc_packet: &ZCPacket) -> Option<(u64, u32, u64)> {
+    let payload = zc_packet.udp_payload();
+    if payload.len() != UDP_MIGRATE_PAYLOAD_LEN {
+        return None;
+    }
+    let magic = u64::from_le_bytes(payload[..8].try_into().unwrap());
+    let seq = u32::from_le_bytes(payload[8..12].try_into().unwrap());
+    let nonce = u64::from_le_bytes(payload[12..].try_into().unwrap());
+    Some((magic, seq, nonce))
+}
+
+fn new_fin_packet(conn_id: u32) -> ZCPacket {
+    new_udp_packet(
+        |header| {
+            header.msg_type = UdpPacketType::Fin as u8;
+            header.conn_id.set(conn_id);
+            header.len.set(0);
+        },
+        Some(&[]),
+    )
+}
+
 pub fn new_hole_punch_packet(tid: u32, buf_len: u16) -> ZCPacket {
     // generate a 128 bytes vec with random data
     let mut rng = rand::rngs::StdRng::from_entropy();
@@ -230,16 +287,54 @@ fn get_zcpacket_from_buf(buf: BytesMut, allow_stun: bool) -> Result<ZCPacket, Tu
     Ok(zc_packet)
 }
 
-#[instrument]
+async fn send_fec_shards(
+    path: &UdpPath,
+    conn_id: u32,
+    shards: &mut Vec<Vec<u8>>,
+) -> Option<TunnelError> {
+    for shard in shards.drain(..) {
+        let packet = new_udp_packet(
+            |header| {
+                header.msg_type = UdpPacketType::Fec as u8;
+                header.conn_id.set(conn_id);
+                header.len.set(shard.len() as u16);
+            },
+            Some(&shard),
+        );
+        if let Err(e) = path
+            .socket
+            .send_to(&packet.into_bytes(), path.dst_addr)
+            .await
+        {
+            return Some(TunnelError::IOError(e));
+        }
+    }
+    None
+}
+
+#[instrument(skip(fec_encoder))]
 async fn forward_from_ring_to_udp(
     mut ring_recv: RingStream,
-    socket: &Arc<UdpSocket>,
-    addr: &SocketAddr,
+    path: &ArcSwap<UdpPath>,
     conn_id: u32,
+    mut fec_encoder: Option<FecEncoder>,
 ) -> Option<TunnelError> {
     tracing::debug!("udp forward from ring to udp");
+    let mut fec_shards = Vec::new();
     loop {
-        let buf = ring_recv.next().await?;
+        let flush_deadline = fec_encoder.as_ref().and_then(|e| e.flush_deadline());
+        let buf = tokio::select! {
+            buf = ring_recv.next() => buf?,
+            _ = tokio::time::sleep_until(flush_deadline.unwrap_or_else(tokio::time::Instant::now)),
+                if flush_deadline.is_some() =>
+            {
+                fec_encoder.as_mut().unwrap().flush(&mut fec_shards);
+                if let Some(e) = send_fec_shards(&path.load_full(), conn_id, &mut fec_shards).await {
+                    return Some(e);
+                }
+                continue;
+            }
+        };
         let packet = match buf {
             Ok(v) => v,
             Err(e) => {
@@ -248,6 +343,14 @@ async fn forward_from_ring_to_udp(
         };
 
         let mut packet = packet.convert_type(ZCPacketType::UDP);
+        if let Some(fec_encoder) = fec_encoder.as_mut() {
+            fec_encoder.encode(packet.udp_payload(), &mut fec_shards);
+            if let Some(e) = send_fec_shards(&path.load_full(), conn_id, &mut fec_shards).await {
+                return Some(e);
+            }
+            continue;
+        }
+
         let udp_payload_len = packet.udp_payload().len();
         let header = packet.mut_udp_tunnel_header().unwrap();
         header.conn_id.set(conn_id);
@@ -256,7 +359,8 @@ async fn forward_from_ring_to_udp(
 
         let buf = packet.into_bytes();
         tracing::trace!(?udp_payload_len, ?buf, "udp forward from ring to udp");
-        let ret = socket.send_to(&buf, &addr).await;
+        let path = path.load_full();
+        let ret = path.socket.send_to(&buf, path.dst_addr).await;
         if ret.is_err() {
             return Some(TunnelError::IOError(ret.unwrap_err()));
         } else if ret.unwrap() == 0 {
@@ -298,48 +402,126 @@ where
     }
 }
 
-struct UdpConnection {
+// the socket and remote address a udp connection sends with, replaced as a whole on migration.
+#[derive(Debug)]
+struct UdpPath {
     socket: Arc<UdpSocket>,
-    conn_id: u32,
     dst_addr: SocketAddr,
+}
 
-    ring_sender: RingSink,
+struct UdpConnection {
+    path: Arc<ArcSwap<UdpPath>>,
+    conn_id: u32,
+    // random secret of the syn, a migrate packet must carry it to move the connection.
+    magic: u64,
+    // highest migrate seq accepted so far, lower ones are replays.
+    migrate_seq: u32,
+    // (new addr, seq, nonce) of the migrate waiting for its challenge to be echoed.
+    migrate_challenge: Option<(SocketAddr, u32, u64)>,
+
+    // set to None once the remote sends fin, which ends the local stream.
+    ring_sender: Option<RingSink>,
     forward_task: ScopedTask<()>,
+
+    // set if fec is negotiated in syn / sack
+    fec_decoder: Option<FecDecoder>,
 }
 
 impl UdpConnection {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         socket: Arc<UdpSocket>,
         conn_id: u32,
+        magic: u64,
         dst_addr: SocketAddr,
         ring_sender: RingSink,
         ring_recv: RingStream,
         close_event_sender: UdpCloseEventSender,
+        fec_config: Option<FecConfig>,
     ) -> Self {
-        let s = socket.clone();
+        let fec_state = Arc::new(FecLinkState::default());
+        let fec_encoder = fec_config.map(|config| FecEncoder::new(config, fec_state.clone()));
+        let fec_decoder = fec_config.map(|_| FecDecoder::new(fec_state));
+
+        let path = Arc::new(ArcSwap::from_pointee(UdpPath { socket, dst_addr }));
+        let p = path.clone();
         let forward_task = tokio::spawn(async move {
             let close_event_sender = close_event_sender;
-            let err = forward_from_ring_to_udp(ring_recv, &s, &dst_addr, conn_id).await;
-            if let Err(e) = close_event_sender.send((dst_addr, err)) {
+            let err = forward_from_ring_to_udp(ring_recv, &p, conn_id, fec_encoder).await;
+            let path = p.load_full();
+            if err.is_none() {
+                // local side closed the tunnel, tell the remote so it does not wait for timeout.
+                let fin_buf = new_fin_packet(conn_id).into_bytes();
+                if let Err(e) = path.socket.send_to(&fin_buf, path.dst_addr).await {
+                    tracing::debug!(?e, "udp send fin packet error");
+                }
+            }
+            if let Err(e) = close_event_sender.send((path.dst_addr, err)) {
                 tracing::error!(?e, "udp send close event error");
             }
         })
         .into();
 
         Self {
-            socket,
+            path,
             conn_id,
-            dst_addr,
-            ring_sender,
+            magic,
+            migrate_seq: 0,
+            migrate_challenge: None,
+            ring_sender: Some(ring_sender),
             forward_task,
+            fec_decoder,
+        }
+    }
+
+    fn forward_to_ring(&mut self, zc_packet: ZCPacket) -> Result<(), TunnelError> {
+        let Some(ring_sender) = self.ring_sender.as_mut() else {
+            return Err(TunnelError::Shutdown);
+        };
+
+        if zc_packet.is_lossy() {
+            if let Err(e) = ring_sender.try_send(zc_packet) {
+                tracing::trace!(?e, "ring sender full, drop lossy packet");
+            }
+        } else if let Err(e) = ring_sender.force_send(zc_packet) {
+            tracing::trace!(?e, "ring sender full, drop non-lossy packet");
+        }
+
+        Ok(())
+    }
+
+    fn handle_fec_packet(&mut self, zc_packet: ZCPacket) -> Result<(), TunnelError> {
+        let Some(fec_decoder) = self.fec_decoder.as_mut() else {
+            return Err(TunnelError::InvalidPacket(
+                "fec packet on connection without fec".to_owned(),
+            ));
+        };
+
+        let mut payloads = Vec::new();
+        fec_decoder.decode(zc_packet.udp_payload(), &mut payloads)?;
+        for payload in payloads {
+            let conn_id = self.conn_id;
+            let packet = new_udp_packet(
+                |header| {
+                    header.msg_type = UdpPacketType::Data as u8;
+                    header.conn_id.set(conn_id);
+                    header.len.set(payload.len() as u16);
+                },
+                Some(&payload),
+            );
+            self.forward_to_ring(packet)?;
         }
+        Ok(())
     }
 
     pub fn handle_packet_from_remote(&mut self, zc_packet: ZCPacket) -> Result<(), TunnelError> {
         let header = zc_packet.udp_tunnel_header().unwrap();
         let conn_id = header.conn_id.get();
 
-        if header.msg_type != UdpPacketType::Data as u8 {
+        if header.msg_type != UdpPacketType::Data as u8
+            && header.msg_type != UdpPacketType::Fin as u8
+            && header.msg_type != UdpPacketType::Fec as u8
+        {
             return Err(TunnelError::InvalidPacket("not data packet".to_owned()));
         }
 
@@ -347,15 +529,17 @@ impl UdpConnection {
             return Err(TunnelError::ConnIdNotMatch(self.conn_id, conn_id));
         }
 
-        if zc_packet.is_lossy() {
-            if let Err(e) = self.ring_sender.try_send(zc_packet) {
-                tracing::trace!(?e, "ring sender full, drop lossy packet");
-            }
-        } else if let Err(e) = self.ring_sender.force_send(zc_packet) {
-            tracing::trace!(?e, "ring sender full, drop non-lossy packet");
+        if header.msg_type == UdpPacketType::Fin as u8 {
+            tracing::info!(?conn_id, dst_addr = ?self.path.load().dst_addr, "udp connection closed by remote");
+            self.ring_sender.take();
+            return Ok(());
         }
 
-        Ok(())
+        if header.msg_type == UdpPacketType::Fec as u8 {
+            return self.handle_fec_packet(zc_packet);
+        }
+
+        self.forward_to_ring(zc_packet)
     }
 }
 
@@ -366,6 +550,8 @@ struct UdpTunnelListenerData {
     sock_map: Arc<DashMap<SocketAddr, UdpConnection>>,
     conn_send: Sender<Box<dyn Tunnel>>,
     close_event_sender: UdpCloseEventSender,
+    // fec config used for connectors that request fec, None refuses fec
+    fec_config: Option<FecConfig>,
 }
 
 impl UdpTunnelListenerData {
@@ -380,6 +566,7 @@ impl UdpTunnelListenerData {
             sock_map: Arc::new(DashMap::new()),
             conn_send,
             close_event_sender,
+            fec_config: Some(FecConfig::default()),
         }
     }
 
@@ -394,12 +581,28 @@ impl UdpTunnelListenerData {
             return;
         }
         let magic = u64::from_le_bytes(udp_payload[..8].try_into().unwrap());
-        let conn_id = zc_packet.udp_tunnel_header().unwrap().conn_id.get();
+        let header = zc_packet.udp_tunnel_header().unwrap();
+        let conn_id = header.conn_id.get();
+        let fec_config = if header.padding & UDP_TUNNEL_FLAG_FEC != 0 {
+            self.fec_config
+        } else {
+            None
+        };
 
-        tracing::info!(?conn_id, ?remote_addr, "udp connection accept handling",);
+        tracing::info!(
+            ?conn_id,
+            ?remote_addr,
+            ?fec_config,
+            "udp connection accept handling",
+        );
         let socket = self.socket.as_ref().unwrap().clone();
 
-        let sack_buf = new_sack_packet(conn_id, magic).into_bytes();
+        let sack_flags = if fec_config.is_some() {
+            UDP_TUNNEL_FLAG_FEC
+        } else {
+            0
+        };
+        let sack_buf = new_sack_packet(conn_id, magic, sack_flags).into_bytes();
         if let Err(e) = socket.send_to(&sack_buf, remote_addr).await {
             tracing::error!(?e, "udp send sack packet error");
             return;
@@ -416,10 +619,12 @@ impl UdpTunnelListenerData {
         let internal_conn = UdpConnection::new(
             socket.clone(),
             conn_id,
+            magic,
             remote_addr,
             RingSink::new(ring_for_recv_udp.clone()),
             RingStream::new(ring_for_send_udp.clone()),
             self.close_event_sender.clone(),
+            fec_config,
         );
         self.sock_map.insert(remote_addr, internal_conn);
 
@@ -442,10 +647,97 @@ impl UdpTunnelListenerData {
         }
     }
 
+    // the connector moved to a new source address, find its connection by conn id and re-key it.
+    // the magic went over the wire in clear, so the new address must first prove it receives our
+    // packets by echoing a challenge nonce, otherwise anyone who saw the syn could redirect the
+    // connection to an address of their choice.
+    fn handle_migrate(&self, remote_addr: SocketAddr, zc_packet: ZCPacket) {
+        let conn_id = zc_packet.udp_tunnel_header().unwrap().conn_id.get();
+        let Some((magic, seq, nonce)) = parse_migrate_packet(&zc_packet) else {
+            tracing::warn!(?remote_addr, "invalid udp migrate packet");
+            return;
+        };
+
+        let Some((old_addr, last_seq)) = self
+            .sock_map
+            .iter()
+            .find(|conn| conn.conn_id == conn_id && conn.magic == magic)
+            .map(|conn| (*conn.key(), conn.migrate_seq))
+        else {
+            tracing::debug!(?conn_id, ?remote_addr, "udp migrate for unknown connection");
+            return;
+        };
+
+        let socket = self.socket.as_ref().unwrap();
+        if old_addr == remote_addr && seq == last_seq {
+            // our ack got lost, the connection is already at this address
+        } else if seq <= last_seq {
+            tracing::warn!(?conn_id, ?seq, ?last_seq, "udp migrate seq replayed");
+            return;
+        } else if self.sock_map.contains_key(&remote_addr) && old_addr != remote_addr {
+            tracing::warn!(
+                ?conn_id,
+                ?remote_addr,
+                "udp migrate to addr of another connection"
+            );
+            return;
+        } else {
+            let Some(mut conn) = self.sock_map.get_mut(&old_addr) else {
+                return;
+            };
+            let echoed = nonce != 0 && conn.migrate_challenge == Some((remote_addr, seq, nonce));
+            if !echoed {
+                // resend the pending nonce on a retry so an echo already in flight still matches
+                let challenge_nonce = match conn.migrate_challenge {
+                    Some((addr, s, n)) if addr == remote_addr && s == seq => n,
+                    _ => rand::random::<u64>().max(1),
+                };
+                conn.migrate_challenge = Some((remote_addr, seq, challenge_nonce));
+                drop(conn);
+                let challenge = new_migrate_packet(
+                    UdpPacketType::MigrateChallenge,
+                    conn_id,
+                    magic,
+                    seq,
+                    challenge_nonce,
+                );
+                if let Err(e) = socket.try_send_to(&challenge.into_bytes(), remote_addr) {
+                    tracing::error!(?e, "udp send migrate challenge error");
+                }
+                return;
+            }
+            drop(conn);
+
+            let Some((_, mut conn)) = self.sock_map.remove(&old_addr) else {
+                return;
+            };
+            if conn.forward_task.is_finished() {
+                // closing, the close event removes it with the old addr so do not re-insert.
+                return;
+            }
+            let conn_socket = conn.path.load().socket.clone();
+            conn.path.store(Arc::new(UdpPath {
+                socket: conn_socket,
+                dst_addr: remote_addr,
+            }));
+            conn.migrate_seq = seq;
+            conn.migrate_challenge = None;
+            self.sock_map.insert(remote_addr, conn);
+            tracing::info!(?conn_id, ?old_addr, ?remote_addr, "udp connection migrated");
+        }
+
+        let ack = new_migrate_packet(UdpPacketType::MigrateAck, conn_id, magic, seq, 0);
+        if let Err(e) = socket.try_send_to(&ack.into_bytes(), remote_addr) {
+            tracing::error!(?e, "udp send migrate ack error");
+        }
+    }
+
     fn do_forward_one_packet_to_conn(&self, zc_packet: ZCPacket, addr: SocketAddr) {
         let header = zc_packet.udp_tunnel_header().unwrap();
         if header.msg_type == UdpPacketType::Syn as u8 {
             tokio::spawn(Self::handle_new_connect(self.clone(), addr, zc_packet));
+        } else if header.msg_type == UdpPacketType::Migrate as u8 {
+            self.handle_migrate(addr, zc_packet);
         } else if is_stun_packet(header.as_bytes()) {
             // ignore stun packet
             tracing::debug!("udp forward packet ignore stun packet");
@@ -537,6 +829,10 @@ impl TunnelListener for UdpTunnelListener {
         )
         .await?;
 
+        if let Some(fec) = FecConfig::url_param(&self.addr) {
+            self.data.fec_config = FecConfig::parse(&fec)?;
+        }
+
         let socket2_socket = socket2::Socket::new(
             socket2::Domain::for_address(addr),
             socket2::Type::DGRAM,
@@ -617,6 +913,128 @@ impl TunnelListener for UdpTunnelListener {
     }
 }
 
+const UDP_MIGRATE_RETRY: usize = 3;
+
+// echo the nonce of a challenge for our latest migrate back to the listener from the new socket.
+fn answer_migrate_challenge(
+    path: &UdpPath,
+    zc_packet: &ZCPacket,
+    addr: SocketAddr,
+    magic: u64,
+    current_seq: u32,
+) {
+    let conn_id = zc_packet.udp_tunnel_header().unwrap().conn_id.get();
+    let Some((challenge_magic, seq, nonce)) = parse_migrate_packet(zc_packet) else {
+        return;
+    };
+    if challenge_magic != magic || seq != current_seq || addr != path.dst_addr {
+        tracing::debug!(?addr, ?seq, ?current_seq, "udp ignore migrate challenge");
+        return;
+    }
+    let echo = new_migrate_packet(UdpPacketType::Migrate, conn_id, magic, seq, nonce);
+    if let Err(e) = path.socket.try_send_to(&echo.into_bytes(), path.dst_addr) {
+        tracing::warn!(?e, "udp send migrate echo error");
+    }
+}
+
+/// Moves a connector side udp connection to a new local socket or source ip, keeping its conn id
+/// so the tunnel above it survives.
+struct UdpMigrator {
+    path: Arc<ArcSwap<UdpPath>>,
+    // switches the recv loop of the connection to the new socket
+    socket_sender: Arc<watch::Sender<Arc<UdpSocket>>>,
+    // highest migrate seq acked by the listener
+    acked_recv: watch::Receiver<u32>,
+    conn_id: u32,
+    magic: u64,
+    seq: Arc<std::sync::atomic::AtomicU32>,
+}
+
+impl UdpMigrator {
+    async fn migrate(&self, socket: Arc<UdpSocket>) -> Result<(), TunnelError> {
+        let seq = self.seq.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+        let old_path = self.path.load_full();
+        let dst_addr = old_path.dst_addr;
+        self.path.store(Arc::new(UdpPath {
+            socket: socket.clone(),
+            dst_addr,
+        }));
+        self.socket_sender.send_replace(socket.clone());
+
+        // the listener answers with a challenge, which the recv loop echoes before the ack arrives
+        let packet = new_migrate_packet(UdpPacketType::Migrate, self.conn_id, self.magic, seq, 0);
+        let packet = packet.into_bytes();
+        let mut acked_recv = self.acked_recv.clone();
+        for _ in 0..UDP_MIGRATE_RETRY {
+            socket.send_to(&packet, dst_addr).await?;
+            let wait_ack = acked_recv.wait_for(|acked| *acked >= seq);
+            if let Ok(Ok(_)) =
+                tokio::time::timeout(tokio::time::Duration::from_secs(1), wait_ack).await
+            {
+                tracing::info!(conn_id = ?self.conn_id, local_addr = ?socket.local_addr(), "udp connection migrated");
+                return Ok(());
+            }
+        }
+
+        // listener is gone or does not know migration, keep the old path and let the tunnel time out
+        self.socket_sender.send_replace(old_path.socket.clone());
+        self.path.store(old_path);
+        Err(TunnelError::InternalError(
+            "udp migrate not acked by remote".to_owned(),
+        ))
+    }
+
+    async fn monitor_local_addr(self) {
+        let socket_sender = self.socket_sender.clone();
+        tokio::select! {
+            // all receivers dropped means the connection is closed
+            _ = socket_sender.closed() => {}
+            _ = self.do_monitor_local_addr() => {}
+        }
+    }
+
+    async fn do_monitor_local_addr(&self) {
+        let mut watcher: Option<(SocketAddr, LocalAddrWatcher)> = None;
+        loop {
+            let path = self.path.load_full();
+            let Ok(local_addr) = path.socket.local_addr() else {
+                return;
+            };
+            if watcher.as_ref().map(|(addr, _)| *addr) != Some(local_addr) {
+                let w = LocalAddrWatcher::new(path.dst_addr, local_addr.ip()).await;
+                watcher = Some((local_addr, w));
+            }
+            let (_, w) = watcher.as_mut().unwrap();
+            let route_ip = w.changed().await;
+            tracing::info!(?local_addr, ?route_ip, conn_id = ?self.conn_id, "udp local addr changed");
+
+            // a wildcard bound socket already sends from the new ip, otherwise fall back to one
+            let socket = if local_addr.ip().is_unspecified() {
+                path.socket.clone()
+            } else {
+                let bind_addr = if local_addr.is_ipv4() {
+                    "0.0.0.0:0"
+                } else {
+                    "[::]:0"
+                };
+                match UdpSocket::bind(bind_addr).await {
+                    Ok(s) => Arc::new(s),
+                    Err(e) => {
+                        tracing::warn!(?e, "udp bind socket for migration failed");
+                        continue;
+                    }
+                }
+            };
+
+            // on failure the change is not accepted, so the next check retries
+            match self.migrate(socket).await {
+                Ok(()) => w.accept(route_ip),
+                Err(e) => tracing::warn!(?e, "udp migrate failed"),
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct UdpTunnelConnector {
     addr: url::Url,
@@ -638,7 +1056,7 @@ impl UdpTunnelConnector {
         addr: SocketAddr,
         conn_id: u32,
         magic: u64,
-    ) -> Result<SocketAddr, TunnelError> {
+    ) -> Result<(SocketAddr, u8), TunnelError> {
         let mut buf = BytesMut::new();
         buf.reserve(UDP_DATA_MTU);
 
@@ -679,7 +1097,7 @@ impl UdpTunnelConnector {
             ));
         }
 
-        Ok(recv_addr)
+        Ok((recv_addr, header.padding))
     }
 
     async fn wait_sack_loop(
@@ -687,7 +1105,7 @@ impl UdpTunnelConnector {
         addr: SocketAddr,
         conn_id: u32,
         magic: u64,
-    ) -> Result<SocketAddr, super::TunnelError> {
+    ) -> Result<(SocketAddr, u8), super::TunnelError> {
         loop {
             let ret = Self::wait_sack(socket, addr, conn_id, magic).await;
             if ret.is_err() {
@@ -704,7 +1122,9 @@ impl UdpTunnelConnector {
         socket: Arc<UdpSocket>,
         dst_addr: SocketAddr,
         conn_id: u32,
-    ) -> Result<Box<dyn super::Tunnel>, super::TunnelError> {
+        magic: u64,
+        fec_config: Option<FecConfig>,
+    ) -> Result<(Box<dyn super::Tunnel>, UdpMigrator), super::TunnelError> {
         let ring_for_send_udp = Arc::new(RingTunnel::new(128));
         let ring_for_recv_udp = Arc::new(RingTunnel::new(128));
         tracing::debug!(
@@ -720,26 +1140,66 @@ impl UdpTunnelConnector {
         let mut udp_conn = UdpConnection::new(
             socket.clone(),
             conn_id,
+            magic,
             dst_addr,
             ring_sender,
             ring_recv,
             close_event_sender,
+            fec_config,
         );
 
-        let socket_clone = socket.clone();
+        let (socket_sender, mut socket_recv) = watch::channel(socket.clone());
+        let (acked_sender, acked_recv) = watch::channel(0);
+        let migrator = UdpMigrator {
+            path: udp_conn.path.clone(),
+            socket_sender: Arc::new(socket_sender),
+            acked_recv,
+            conn_id,
+            magic,
+            seq: Arc::new(std::sync::atomic::AtomicU32::new(0)),
+        };
+
+        // keeps socket_recv open even after the migrator is dropped
+        let socket_sender = migrator.socket_sender.clone();
+        let migrate_seq = migrator.seq.clone();
         tokio::spawn(
             async move {
-                tokio::select! {
-                    _ = close_event_recv.recv() => {
-                        tracing::debug!("connector udp close event");
-                    }
-                    _ = udp_recv_from_socket_forward_task(socket_clone,false, |zc_packet, addr| {
-                        tracing::trace!(?addr, "connector udp forward task done");
-                        if let Err(e) = udp_conn.handle_packet_from_remote(zc_packet) {
-                            tracing::trace!(?e, ?addr, "udp forward packet error");
+                let _socket_sender = socket_sender;
+                // the socket changes when the connection migrates, restart recv on the new one
+                loop {
+                    let socket = socket_recv.borrow_and_update().clone();
+                    tokio::select! {
+                        _ = close_event_recv.recv() => {
+                            tracing::debug!("connector udp close event");
+                            break;
+                        }
+                        _ = socket_recv.changed() => {}
+                        _ = udp_recv_from_socket_forward_task(socket, false, |zc_packet, addr| {
+                            tracing::trace!(?addr, "connector udp forward task done");
+                            let header = zc_packet.udp_tunnel_header().unwrap();
+                            if header.msg_type == UdpPacketType::MigrateAck as u8 {
+                                if let Some((ack_magic, seq, _)) = parse_migrate_packet(&zc_packet) {
+                                    if header.conn_id.get() == conn_id && ack_magic == magic {
+                                        acked_sender.send_if_modified(|v| {
+                                            let newer = seq > *v;
+                                            *v = (*v).max(seq);
+                                            newer
+                                        });
+                                    }
+                                }
+                            } else if header.msg_type == UdpPacketType::MigrateChallenge as u8
+                                && header.conn_id.get() == conn_id
+                            {
+                                let path = udp_conn.path.load();
+                                let seq = migrate_seq.load(std::sync::atomic::Ordering::Relaxed);
+                                answer_migrate_challenge(&path, &zc_packet, addr, magic, seq);
+                            } else if let Err(e) = udp_conn.handle_packet_from_remote(zc_packet) {
+                                tracing::trace!(?e, ?addr, "udp forward packet error");
+                            }
+                        }) => {
+                            tracing::debug!("connector udp forward task done");
+                            break;
                         }
-                    }) => {
-                        tracing::debug!("connector udp forward task done");
                     }
                 }
             }
@@ -750,7 +1210,7 @@ impl UdpTunnelConnector {
             )),
         );
 
-        Ok(Box::new(TunnelWrapper::new(
+        let tunnel = Box::new(TunnelWrapper::new(
             Box::new(RingStream::new(ring_for_recv_udp)),
             Box::new(RingSink::new(ring_for_send_udp)),
             Some(TunnelInfo {
@@ -760,28 +1220,40 @@ impl UdpTunnelConnector {
                 ),
                 remote_addr: Some(self.addr.clone().into()),
             }),
-        )))
+        ));
+        Ok((tunnel, migrator))
     }
 
-    pub async fn try_connect_with_socket(
+    async fn do_connect_with_socket(
         &self,
         socket: Arc<UdpSocket>,
         addr: SocketAddr,
-    ) -> Result<Box<dyn super::Tunnel>, super::TunnelError> {
+    ) -> Result<(Box<dyn super::Tunnel>, UdpMigrator), super::TunnelError> {
         tracing::warn!("udp connect: {:?}", self.addr);
 
         #[cfg(target_os = "windows")]
         crate::arch::windows::disable_connection_reset(socket.as_ref())?;
 
+        // fec is opt-in on the connector side
+        let fec_config = match FecConfig::url_param(&self.addr) {
+            Some(fec) => FecConfig::parse(&fec)?,
+            None => None,
+        };
+        let syn_flags = if fec_config.is_some() {
+            UDP_TUNNEL_FLAG_FEC
+        } else {
+            0
+        };
+
         // send syn
         let conn_id = rand::random();
         let magic = rand::random();
-        let udp_packet = new_syn_packet(conn_id, magic).into_bytes();
+        let udp_packet = new_syn_packet(conn_id, magic, syn_flags).into_bytes();
         let ret = socket.send_to(&udp_packet, &addr).await?;
         tracing::warn!(?udp_packet, ?ret, "udp send syn");
 
         // wait sack
-        let recv_addr = tokio::time::timeout(
+        let (recv_addr, sack_flags) = tokio::time::timeout(
             tokio::time::Duration::from_secs(3),
             Self::wait_sack_loop(&socket, addr, conn_id, magic),
         )
@@ -791,7 +1263,24 @@ impl UdpTunnelConnector {
             tracing::debug!(?recv_addr, ?addr, "udp connect addr not match");
         }
 
-        self.build_tunnel(socket, addr, conn_id).await
+        // listeners without fec support leave the flag unset
+        let fec_config = fec_config.filter(|_| sack_flags & UDP_TUNNEL_FLAG_FEC != 0);
+        if syn_flags & UDP_TUNNEL_FLAG_FEC != 0 {
+            tracing::info!(?fec_config, "udp fec negotiated");
+        }
+
+        self.build_tunnel(socket, addr, conn_id, magic, fec_config)
+            .await
+    }
+
+    pub async fn try_connect_with_socket(
+        &self,
+        socket: Arc<UdpSocket>,
+        addr: SocketAddr,
+    ) -> Result<Box<dyn super::Tunnel>, super::TunnelError> {
+        let (tunnel, migrator) = self.do_connect_with_socket(socket, addr).await?;
+        tokio::spawn(migrator.monitor_local_addr());
+        Ok(tunnel)
     }
 
     async fn connect_with_default_bind(
@@ -1146,4 +1635,195 @@ mod tests {
             .expect("Timeout waiting for v6 hole punch packet")
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn udp_fec_pingpong() {
+        let listener = UdpTunnelListener::new("udp://0.0.0.0:5560".parse().unwrap());
+        let connector = UdpTunnelConnector::new("udp://127.0.0.1:5560?fec=4:2".parse().unwrap());
+        _tunnel_pingpong(listener, connector).await;
+    }
+
+    #[tokio::test]
+    async fn udp_fec_negotiation() {
+        // listener refuses fec, connector falls back to plain udp
+        let mut listener = UdpTunnelListener::new("udp://127.0.0.1:5561?fec=off".parse().unwrap());
+        listener.listen().await.unwrap();
+        let mut connector =
+            UdpTunnelConnector::new("udp://127.0.0.1:5561?fec=auto".parse().unwrap());
+        let _client = connector.connect().await.unwrap();
+        let _server = listener.accept().await.unwrap();
+        assert_eq!(listener.data.sock_map.len(), 1);
+        assert!(listener
+            .data
+            .sock_map
+            .iter()
+            .all(|conn| conn.fec_decoder.is_none()));
+
+        // fec is only used when the connector asks for it
+        let mut listener = UdpTunnelListener::new("udp://127.0.0.1:5562".parse().unwrap());
+        listener.listen().await.unwrap();
+        let mut plain_connector = UdpTunnelConnector::new("udp://127.0.0.1:5562".parse().unwrap());
+        let mut fec_connector =
+            UdpTunnelConnector::new("udp://127.0.0.1:5562?fec=auto".parse().unwrap());
+        let _plain_client = plain_connector.connect().await.unwrap();
+        let _plain_server = listener.accept().await.unwrap();
+        let _fec_client = fec_connector.connect().await.unwrap();
+        let _fec_server = listener.accept().await.unwrap();
+        let fec_conns = listener
+            .data
+            .sock_map
+            .iter()
+            .filter(|conn| conn.fec_decoder.is_some())
+            .count();
+        assert_eq!(listener.data.sock_map.len(), 2);
+        assert_eq!(fec_conns, 1);
+    }
+
+    async fn udp_pingpong_once(
+        client: &mut crate::tunnel::SplitTunnel,
+        server: &mut crate::tunnel::SplitTunnel,
+        msg: &str,
+    ) {
+        let packet = || ZCPacket::new_with_payload(msg.as_bytes());
+        client.1.send(packet()).await.unwrap();
+        let recv = timeout(Duration::from_secs(1), server.0.next())
+            .await
+            .unwrap();
+        assert_eq!(recv.unwrap().unwrap().payload(), msg.as_bytes());
+        server.1.send(packet()).await.unwrap();
+        let recv = timeout(Duration::from_secs(1), client.0.next())
+            .await
+            .unwrap();
+        assert_eq!(recv.unwrap().unwrap().payload(), msg.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn udp_migrate_keeps_connection() {
+        let mut listener = UdpTunnelListener::new("udp://127.0.0.1:5563".parse().unwrap());
+        listener.listen().await.unwrap();
+        let connector = UdpTunnelConnector::new("udp://127.0.0.1:5563".parse().unwrap());
+
+        let socket = Arc::new(UdpSocket::bind("127.0.0.1:0").await.unwrap());
+        let (client, migrator) = connector
+            .do_connect_with_socket(socket, "127.0.0.1:5563".parse().unwrap())
+            .await
+            .unwrap();
+        let server = listener.accept().await.unwrap();
+        let mut client = client.split();
+        let mut server = server.split();
+
+        udp_pingpong_once(&mut client, &mut server, "before").await;
+
+        let new_socket = Arc::new(UdpSocket::bind("127.0.0.1:0").await.unwrap());
+        let new_addr = new_socket.local_addr().unwrap();
+        migrator.migrate(new_socket).await.unwrap();
+        assert_eq!(listener.data.sock_map.len(), 1);
+        assert!(listener.data.sock_map.contains_key(&new_addr));
+        udp_pingpong_once(&mut client, &mut server, "after").await;
+
+        // a replayed migrate from another address must not move the connection
+        let attacker = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let replay = new_migrate_packet(
+            UdpPacketType::Migrate,
+            migrator.conn_id,
+            migrator.magic,
+            1,
+            0,
+        );
+        attacker
+            .send_to(&replay.into_bytes(), "127.0.0.1:5563")
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(listener.data.sock_map.contains_key(&new_addr));
+        udp_pingpong_once(&mut client, &mut server, "replay").await;
+    }
+
+    #[tokio::test]
+    async fn udp_forged_migrate_is_rejected() {
+        let mut listener = UdpTunnelListener::new("udp://127.0.0.1:5564".parse().unwrap());
+        listener.listen().await.unwrap();
+        let connector = UdpTunnelConnector::new("udp://127.0.0.1:5564".parse().unwrap());
+
+        let socket = Arc::new(UdpSocket::bind("127.0.0.1:0").await.unwrap());
+        let client_addr = socket.local_addr().unwrap();
+        let (client, migrator) = connector
+            .do_connect_with_socket(socket, "127.0.0.1:5564".parse().unwrap())
+            .await
+            .unwrap();
+        let server = listener.accept().await.unwrap();
+        let mut client = client.split();
+        let mut server = server.split();
+
+        // the attacker saw the syn so it knows the magic and picks a fresh seq, but it stands in
+        // for a spoofed source that never sees the challenge, so it can only guess the nonce.
+        let attacker = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        for nonce in [0, 1, u64::MAX] {
+            let forged = new_migrate_packet(
+                UdpPacketType::Migrate,
+                migrator.conn_id,
+                migrator.magic,
+                100,
+                nonce,
+            );
+            attacker
+                .send_to(&forged.into_bytes(), "127.0.0.1:5564")
+                .await
+                .unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(listener.data.sock_map.len(), 1);
+        assert!(listener.data.sock_map.contains_key(&client_addr));
+        udp_pingpong_once(&mut client, &mut server, "forged").await;
+
+        // the real connector still migrates afterwards
+        let new_socket = Arc::new(UdpSocket::bind("127.0.0.1:0").await.unwrap());
+        let new_addr = new_socket.local_addr().unwrap();
+        migrator.migrate(new_socket).await.unwrap();
+        assert!(listener.data.sock_map.contains_key(&new_addr));
+        udp_pingpong_once(&mut client, &mut server, "after").await;
+    }
+
+    #[tokio::test]
+    async fn udp_migrate_without_ack_keeps_old_path() {
+        // a plain socket that never acks stands in for a listener without migration support
+        let remote = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let socket = Arc::new(UdpSocket::bind("127.0.0.1:0").await.unwrap());
+        let (_acked_sender, acked_recv) = watch::channel(0);
+        let (socket_sender, _socket_recv) = watch::channel(socket.clone());
+        let migrator = UdpMigrator {
+            path: Arc::new(ArcSwap::from_pointee(UdpPath {
+                socket: socket.clone(),
+                dst_addr: remote.local_addr().unwrap(),
+            })),
+            socket_sender: Arc::new(socket_sender),
+            acked_recv,
+            conn_id: 1,
+            magic: 2,
+            seq: Default::default(),
+        };
+
+        let new_socket = Arc::new(UdpSocket::bind("127.0.0.1:0").await.unwrap());
+        assert!(migrator.migrate(new_socket).await.is_err());
+        assert!(Arc::ptr_eq(&migrator.path.load().socket, &socket));
+        assert!(Arc::ptr_eq(&migrator.socket_sender.borrow(), &socket));
+    }
+
+    #[tokio::test]
+    async fn udp_fin_closes_remote_stream() {
+        let mut listener = UdpTunnelListener::new("udp://127.0.0.1:5558".parse().unwrap());
+        listener.listen().await.unwrap();
+        let mut connector = UdpTunnelConnector::new("udp://127.0.0.1:5558".parse().unwrap());
+
+        let client = connector.connect().await.unwrap();
+        let server = listener.accept().await.unwrap();
+        let (mut server_stream, _server_sink) = server.split();
+
+        // dropping the client tunnel should send fin, ending the server stream without timeout.
+        drop(client);
+        let ret = timeout(Duration::from_secs(2), server_stream.next())
+            .await
+            .expect("server stream should be closed by fin");
+        assert!(ret.is_none());
+    }
 }
@@ -0,0 +1,213 @@
+//! Tunnel over unix domain stream sockets. The url path is the socket file, e.g.
+//! `unix:///run/easytier.sock`, which is handy for sidecars and tests without binding ports.
+
+use std::{
+    os::unix::fs::FileTypeExt as _,
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use tokio::net::{UnixListener, UnixStream};
+
+use super::{
+    common::{FramedReader, FramedWriter, TunnelWrapper},
+    Tunnel, TunnelError, TunnelInfo, TunnelListener,
+};
+
+const UNIX_MTU_BYTES: usize = 2000;
+
+fn get_socket_path(url: &url::Url) -> Result<PathBuf, TunnelError> {
+    if url.scheme() != "unix" {
+        return Err(TunnelError::InvalidProtocol(url.scheme().to_string()));
+    }
+    let path = percent_encoding::percent_decode_str(url.path()).decode_utf8_lossy();
+    if url.host_str().is_some_and(|h| !h.is_empty()) || path.is_empty() || path == "/" {
+        return Err(TunnelError::InvalidAddr(format!(
+            "unix socket url should look like unix:///path/to/socket, got: {}",
+            url
+        )));
+    }
+    Ok(PathBuf::from(path.as_ref()))
+}
+
+fn get_tunnel_with_unix_stream(
+    stream: UnixStream,
+    local_url: url::Url,
+    remote_url: url::Url,
+) -> Box<dyn Tunnel> {
+    let info = TunnelInfo {
+        tunnel_type: "unix".to_owned(),
+        local_addr: Some(local_url.into()),
+        remote_addr: Some(remote_url.into()),
+    };
+
+    let (r, w) = stream.into_split();
+    Box::new(TunnelWrapper::new(
+        FramedReader::new(r, UNIX_MTU_BYTES),
+        FramedWriter::new(w),
+        Some(info),
+    ))
+}
+
+// a socket file left behind by a crashed process blocks bind, remove it unless someone listens.
+async fn remove_stale_socket(path: &Path) -> Result<(), TunnelError> {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return Ok(());
+    };
+    if !meta.file_type().is_socket() {
+        return Err(TunnelError::InvalidAddr(format!(
+            "{} exists and is not a unix socket",
+            path.display()
+        )));
+    }
+    if UnixStream::connect(path).await.is_ok() {
+        return Err(TunnelError::IOError(std::io::Error::new(
+            std::io::ErrorKind::AddrInUse,
+            format!("{} is in use by another listener", path.display()),
+        )));
+    }
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct UnixSocketTunnelListener {
+    addr: url::Url,
+    listener: Option<UnixListener>,
+}
+
+impl UnixSocketTunnelListener {
+    pub fn new(addr: url::Url) -> Self {
+        UnixSocketTunnelListener {
+            addr,
+            listener: None,
+        }
+    }
+}
+
+impl Drop for UnixSocketTunnelListener {
+    fn drop(&mut self) {
+        if self.listener.take().is_some() {
+            if let Ok(path) = get_socket_path(&self.addr) {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl TunnelListener for UnixSocketTunnelListener {
+    async fn listen(&mut self) -> Result<(), TunnelError> {
+        self.listener = None;
+        let path = get_socket_path(&self.addr)?;
+        remove_stale_socket(&path).await?;
+        self.listener = Some(UnixListener::bind(&path)?);
+        Ok(())
+    }
+
+    async fn accept(&mut self) -> Result<Box<dyn Tunnel>, TunnelError> {
+        let listener = self.listener.as_ref().unwrap();
+        let (stream, _) = listener.accept().await?;
+        // client sockets are unnamed, the socket path is the only address of both ends
+        Ok(get_tunnel_with_unix_stream(
+            stream,
+            self.local_url(),
+            self.local_url(),
+        ))
+    }
+
+    fn local_url(&self) -> url::Url {
+        self.addr.clone()
+    }
+}
+
+#[derive(Debug)]
+pub struct UnixSocketTunnelConnector {
+    addr: url::Url,
+}
+
+impl UnixSocketTunnelConnector {
+    pub fn new(addr: url::Url) -> Self {
+        UnixSocketTunnelConnector { addr }
+    }
+}
+
+#[async_trait]
+impl super::TunnelConnector for UnixSocketTunnelConnector {
+    async fn connect(&mut self) -> Result<Box<dyn Tunnel>, TunnelError> {
+        let path = get_socket_path(&self.addr)?;
+        let stream = UnixStream::connect(&path).await?;
+        tracing::info!(url = ?self.addr, "connect unix socket succ");
+        Ok(get_tunnel_with_unix_stream(
+            stream,
+            self.addr.clone(),
+            self.addr.clone(),
+        ))
+    }
+
+    fn remote_url(&self) -> url::Url {
+        self.addr.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tunnel::{
+        common::tests::{_tunnel_bench, _tunnel_pingpong},
+        TunnelConnector,
+    };
+
+    use super::*;
+
+    fn temp_socket_url(name: &str) -> url::Url {
+        let path = std::env::temp_dir().join(format!("et-{}-{}.sock", name, rand::random::<u32>()));
+        format!("unix://{}", path.display()).parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn unix_pingpong() {
+        let url = temp_socket_url("pingpong");
+        let listener = UnixSocketTunnelListener::new(url.clone());
+        let connector = UnixSocketTunnelConnector::new(url);
+        _tunnel_pingpong(listener, connector).await
+    }
+
+    #[tokio::test]
+    async fn unix_bench() {
+        let url = temp_socket_url("bench");
+        let listener = UnixSocketTunnelListener::new(url.clone());
+        let connector = UnixSocketTunnelConnector::new(url);
+        _tunnel_bench(listener, connector).await
+    }
+
+    #[tokio::test]
+    async fn unix_stale_socket_file() {
+        let url = temp_socket_url("stale");
+        let path = get_socket_path(&url).unwrap();
+
+        // a live listener keeps its socket file
+        let mut listener = UnixSocketTunnelListener::new(url.clone());
+        listener.listen().await.unwrap();
+        let mut listener2 = UnixSocketTunnelListener::new(url.clone());
+        assert!(listener2.listen().await.is_err());
+
+        // closing the socket without removing its file leaves a stale one, which is replaced
+        drop(listener.listener.take());
+        let mut listener3 = UnixSocketTunnelListener::new(url.clone());
+        listener3.listen().await.unwrap();
+        drop(listener3);
+        assert!(!path.exists());
+
+        let mut connector = UnixSocketTunnelConnector::new(url);
+        assert!(connector.connect().await.is_err());
+    }
+
+    #[test]
+    fn unix_socket_path_from_url() {
+        let path = get_socket_path(&"unix:///run/easytier%20a.sock".parse().unwrap()).unwrap();
+        assert_eq!(path, PathBuf::from("/run/easytier a.sock"));
+        assert!(get_socket_path(&"unix://host/run/et.sock".parse().unwrap()).is_err());
+        assert!(get_socket_path(&"unix://".parse().unwrap()).is_err());
+        assert!(get_socket_path(&"tcp://127.0.0.1:1".parse().unwrap()).is_err());
+    }
+}
@@ -12,12 +12,14 @@ use tokio_websockets::{ClientBuilder, Limits, MaybeTlsStream, Message};
 use zerocopy::AsBytes;
 
 use super::TunnelInfo;
-use crate::tunnel::insecure_tls::get_insecure_tls_client_config;
+use crate::common::config::TlsConfig;
 
 use super::{
     common::{setup_sokcet2, wait_for_connect_futures, TunnelWrapper},
-    insecure_tls::{get_insecure_tls_cert, init_crypto_provider},
+    insecure_tls::init_crypto_provider,
     packet_def::{ZCPacket, ZCPacketType},
+    proxy::{redact_url, UpstreamProxy},
+    tls::{get_server_cert, ServerCertVerify},
     FromUrl, IpVersion, Tunnel, TunnelConnector, TunnelError, TunnelListener,
 };
 
@@ -59,10 +61,20 @@ async fn map_from_ws_message(
     )))
 }
 
-#[derive(Debug)]
 pub struct WSTunnelListener {
     addr: url::Url,
     listener: Option<TcpListener>,
+    tls_config: Option<TlsConfig>,
+    tls_acceptor: Option<TlsAcceptor>,
+}
+
+impl std::fmt::Debug for WSTunnelListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WSTunnelListener")
+            .field("addr", &self.addr)
+            .field("listener", &self.listener)
+            .finish()
+    }
 }
 
 impl WSTunnelListener {
@@ -70,9 +82,16 @@ impl WSTunnelListener {
         WSTunnelListener {
             addr,
             listener: None,
+            tls_config: None,
+            tls_acceptor: None,
         }
     }
 
+    /// Use the configured certificate instead of a throwaway one for wss
+    pub fn set_tls_config(&mut self, tls_config: TlsConfig) {
+        self.tls_config = Some(tls_config);
+    }
+
     async fn try_accept(&mut self, stream: TcpStream) -> Result<Box<dyn Tunnel>, TunnelError> {
         let info = TunnelInfo {
             tunnel_type: self.addr.scheme().to_owned(),
@@ -88,15 +107,7 @@ impl WSTunnelListener {
 
         let server_bulder = tokio_websockets::ServerBuilder::new().limits(Limits::unlimited());
 
-        let ret: Box<dyn Tunnel> = if is_wss(&self.addr)? {
-            init_crypto_provider();
-            let (certs, key) = get_insecure_tls_cert();
-            let config = rustls::ServerConfig::builder()
-                .with_no_client_auth()
-                .with_single_cert(certs, key)
-                .with_context(|| "Failed to create server config")?;
-            let acceptor = TlsAcceptor::from(Arc::new(config));
-
+        let ret: Box<dyn Tunnel> = if let Some(acceptor) = &self.tls_acceptor {
             let stream = acceptor.accept(stream).await?;
             let (write, read) = server_bulder.accept(stream).await?.split();
 
@@ -121,6 +132,16 @@ impl WSTunnelListener {
 #[async_trait::async_trait]
 impl TunnelListener for WSTunnelListener {
     async fn listen(&mut self) -> Result<(), TunnelError> {
+        if is_wss(&self.addr)? {
+            init_crypto_provider();
+            let (certs, key) = get_server_cert(self.tls_config.as_ref())?;
+            let config = rustls::ServerConfig::builder()
+                .with_no_client_auth()
+                .with_single_cert(certs, key)
+                .with_context(|| "Failed to create server config")?;
+            self.tls_acceptor = Some(TlsAcceptor::from(Arc::new(config)));
+        }
+
         let addr = SocketAddr::from_url(self.addr.clone(), IpVersion::Both).await?;
         let socket2_socket = socket2::Socket::new(
             socket2::Domain::for_address(addr),
@@ -164,6 +185,8 @@ pub struct WSTunnelConnector {
     ip_version: IpVersion,
 
     bind_addrs: Vec<SocketAddr>,
+    tls_config: Option<TlsConfig>,
+    proxy: Option<UpstreamProxy>,
 }
 
 impl WSTunnelConnector {
@@ -173,18 +196,47 @@ impl WSTunnelConnector {
             ip_version: IpVersion::Both,
 
             bind_addrs: vec![],
+            tls_config: None,
+            proxy: None,
         }
     }
 
+    /// Provides the default CA for verifying wss servers
+    pub fn set_tls_config(&mut self, tls_config: TlsConfig) {
+        self.tls_config = Some(tls_config);
+    }
+
+    /// Dial through an upstream proxy, bind addrs and ip version are ignored in this case.
+    pub fn set_upstream_proxy(&mut self, proxy: Option<UpstreamProxy>) {
+        self.proxy = proxy;
+    }
+
+    async fn connect_with_proxy(
+        &mut self,
+        proxy: UpstreamProxy,
+    ) -> Result<Box<dyn Tunnel>, super::TunnelError> {
+        let (host, port) = UpstreamProxy::target_of(&self.addr)?;
+        let stream = proxy.connect(&host, port).await?;
+        Self::connect_on_stream(self.addr.clone(), self.cert_verify()?, stream).await
+    }
+
     async fn connect_with(
         addr: url::Url,
         ip_version: IpVersion,
+        cert_verify: ServerCertVerify,
         tcp_socket: TcpSocket,
     ) -> Result<Box<dyn Tunnel>, TunnelError> {
-        let is_wss = is_wss(&addr)?;
         let socket_addr = SocketAddr::from_url(addr.clone(), ip_version).await?;
         let stream = tcp_socket.connect(socket_addr).await?;
+        Self::connect_on_stream(addr, cert_verify, stream).await
+    }
 
+    async fn connect_on_stream(
+        addr: url::Url,
+        cert_verify: ServerCertVerify,
+        stream: TcpStream,
+    ) -> Result<Box<dyn Tunnel>, TunnelError> {
+        let is_wss = is_wss(&addr)?;
         let info = TunnelInfo {
             tunnel_type: addr.scheme().to_owned(),
             local_addr: Some(
@@ -197,19 +249,18 @@ impl WSTunnelConnector {
             remote_addr: Some(addr.clone().into()),
         };
 
-        let c = ClientBuilder::from_uri(http::Uri::try_from(addr.to_string()).unwrap());
+        // the server must not see the credentials of the proxy url param
+        let c =
+            ClientBuilder::from_uri(http::Uri::try_from(redact_url(&addr).to_string()).unwrap());
         let stream: MaybeTlsStream<TcpStream> = if is_wss {
             init_crypto_provider();
-            let tls_conn =
-                tokio_rustls::TlsConnector::from(Arc::new(get_insecure_tls_client_config()));
-            // Modify SNI logic: use "localhost" as SNI for url without domain to avoid IP blocking.
-            let sni = match addr.domain() {
-                None => "localhost".to_string(),
-                Some(domain) => domain.to_string(),
-            };
-            let server_name = rustls::pki_types::ServerName::try_from(sni)
-                .map_err(|_| TunnelError::InvalidProtocol("Invalid SNI".to_string()))?;
-            let stream = tls_conn.connect(server_name, stream).await?;
+            let (config, verify_failure) = cert_verify.client_config()?;
+            let tls_conn = tokio_rustls::TlsConnector::from(Arc::new(config));
+            let server_name = cert_verify.server_name(&addr)?;
+            let stream = tls_conn
+                .connect(server_name, stream)
+                .await
+                .map_err(|e| verify_failure.or(e.into()))?;
             MaybeTlsStream::Rustls(stream)
         } else {
             MaybeTlsStream::Plain(stream)
@@ -231,7 +282,20 @@ impl WSTunnelConnector {
         } else {
             TcpSocket::new_v6()?
         };
-        Self::connect_with(self.addr.clone(), self.ip_version, socket).await
+        Self::connect_with(
+            self.addr.clone(),
+            self.ip_version,
+            self.cert_verify()?,
+            socket,
+        )
+        .await
+    }
+
+    fn cert_verify(&self) -> Result<ServerCertVerify, TunnelError> {
+        if !is_wss(&self.addr)? {
+            return Ok(ServerCertVerify::default());
+        }
+        ServerCertVerify::from_url(&self.addr, self.tls_config.as_ref())
     }
 
     async fn connect_with_custom_bind(
@@ -239,6 +303,7 @@ impl WSTunnelConnector {
         addr: SocketAddr,
     ) -> Result<Box<dyn Tunnel>, super::TunnelError> {
         let futures = FuturesUnordered::new();
+        let cert_verify = self.cert_verify()?;
 
         for bind_addr in self.bind_addrs.iter() {
             tracing::info!(bind_addr = ?bind_addr, ?addr, "bind addr");
@@ -258,6 +323,7 @@ impl WSTunnelConnector {
             futures.push(Self::connect_with(
                 self.addr.clone(),
                 self.ip_version,
+                cert_verify.clone(),
                 socket,
             ))
         }
@@ -269,6 +335,9 @@ impl WSTunnelConnector {
 #[async_trait::async_trait]
 impl TunnelConnector for WSTunnelConnector {
     async fn connect(&mut self) -> Result<Box<dyn Tunnel>, super::TunnelError> {
+        if let Some(proxy) = self.proxy.clone() {
+            return self.connect_with_proxy(proxy).await;
+        }
         let addr = SocketAddr::from_url(self.addr.clone(), self.ip_version).await?;
         if self.bind_addrs.is_empty() || addr.is_ipv6() {
             self.connect_with_default_bind(addr).await
@@ -317,6 +386,35 @@ pub mod tests {
         _tunnel_pingpong(listener, connector).await
     }
 
+    #[rstest::rstest]
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn ws_pingpong_through_proxy(
+        #[values("ws", "wss")] proto: &str,
+        #[values("http", "socks5")] proxy: &str,
+    ) {
+        use crate::tunnel::proxy::{
+            tests::{run_http_proxy, run_socks5_proxy},
+            UpstreamProxy,
+        };
+
+        let (proxy_port, proxy_task) = if proxy == "http" {
+            run_http_proxy(Some("user:pass")).await
+        } else {
+            run_socks5_proxy(Some("user:pass")).await
+        };
+        let listener = WSTunnelListener::new(format!("{}://0.0.0.0:25560", proto).parse().unwrap());
+        // the global proxy is used when the url has no proxy param
+        let global: url::Url = format!("{}://user:pass@127.0.0.1:{}", proxy, proxy_port)
+            .parse()
+            .unwrap();
+        let url: url::Url = format!("{}://127.0.0.1:25560", proto).parse().unwrap();
+        let mut connector = WSTunnelConnector::new(url.clone());
+        connector.set_upstream_proxy(UpstreamProxy::for_connector(&url, Some(&global)).unwrap());
+        _tunnel_pingpong(listener, connector).await;
+        proxy_task.abort();
+    }
+
     // TODO: tokio-websockets cannot correctly handle close, benchmark case is disabled
     // #[rstest::rstest]
     // #[tokio::test]
@@ -345,4 +443,52 @@ pub mod tests {
 
         j.abort();
     }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn wss_verify_server_cert() {
+        use crate::common::config::TlsConfig;
+        use crate::tunnel::tls::{format_spki_pin, tests::gen_test_pki};
+        use crate::tunnel::TunnelError;
+
+        let pki = gen_test_pki();
+        let mut listener = WSTunnelListener::new("wss://0.0.0.0:25559".parse().unwrap());
+        listener.set_tls_config(pki.tls.clone());
+        listener.listen().await.unwrap();
+        let j = tokio::spawn(async move {
+            loop {
+                let _ = listener.accept().await;
+            }
+        });
+
+        let connect = |query: &[(&str, String)], ca: Option<String>| {
+            let mut url: url::Url = "wss://127.0.0.1:25559".parse().unwrap();
+            for (k, v) in query {
+                url.query_pairs_mut().append_pair(k, v);
+            }
+            let mut connector = WSTunnelConnector::new(url);
+            connector.set_tls_config(TlsConfig {
+                ca,
+                ..Default::default()
+            });
+            async move { connector.connect().await }
+        };
+
+        connect(&[("pin", format_spki_pin(&pki.pin))], None)
+            .await
+            .unwrap();
+        let ret = connect(&[("pin", format_spki_pin(&[0u8; 32]))], None).await;
+        assert!(matches!(ret, Err(TunnelError::CertPinMismatch(_))));
+
+        connect(&[("verify", "ca".to_string())], Some(pki.ca.clone()))
+            .await
+            .unwrap();
+        let other_ca = gen_test_pki().ca;
+        let ret = connect(&[], Some(other_ca)).await;
+        assert!(matches!(ret, Err(TunnelError::CertVerifyFailed(_))));
+        let ret = connect(&[("verify", "system".to_string())], None).await;
+        assert!(matches!(ret, Err(TunnelError::CertVerifyFailed(_))));
+
+        j.abort();
+    }
 }
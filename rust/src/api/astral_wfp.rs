@@ -2,12 +2,20 @@
 //! 
 //! 本模块提供了网络流量过滤功能：
 //! - Windows: 基于 Windows Filtering Platform (WFP) 的完整实现
-//! - 其他平台: 提供模拟接口防止编译错误
+//! - Linux: 基于 nftables 的实现，按程序过滤通过 cgroup v2 完成
+//! - 其他平台: 规则只记录在内存中
 
 pub use std::net::IpAddr;
 use std::fmt;
 use std::str::FromStr;
 use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+
+use crate::packet_filter::FilterBackend;
+#[cfg(target_os = "linux")]
+use crate::packet_filter::NftablesBackend;
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+use crate::packet_filter::MemoryBackend;
 
 // Windows 平台特定导入
 #[cfg(target_os = "windows")]
@@ -98,7 +106,7 @@ impl IpNetwork {
 }
 
 /// 网络过滤规则结构体
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[frb(opaque)]
 pub struct FilterRule {
     pub name: String,
@@ -113,11 +121,13 @@ pub struct FilterRule {
     pub direction: Direction,
     pub action: FilterAction,
     pub priority: u32,
+    // 过滤器 ID 只在本次运行中有效，不随规则保存
+    #[serde(skip)]
     pub filter_ids: Vec<u64>,
 }
 
 /// 网络协议类型
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Protocol {
     Tcp,
     Udp,
@@ -145,7 +155,7 @@ impl FromStr for Protocol {
 }
 
 /// 网络流量方向
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Direction {
     Inbound,
     Outbound,
@@ -153,7 +163,7 @@ pub enum Direction {
 }
 
 /// 过滤动作类型
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FilterAction {
     Allow,
     Block,
@@ -227,53 +237,266 @@ pub fn to_wide_string(s: &str) -> Vec<u16> {
     s.encode_utf16().chain(std::iter::once(0)).collect()
 }
 
+
 /// 跨平台网络流量过滤控制器
-#[derive(Clone)]
+///
+/// 控制器只维护规则模型，规则通过 `FilterBackend` 下发到当前平台：
+/// Windows 使用 WFP，Linux 使用 nftables，其他平台只在内存中记录。
 #[frb(opaque)]
 pub struct WfpController {
-    engine_handle: PlatformHandle,
+    backend: Box<dyn FilterBackend>,
     pub filter_ids: Vec<u64>,
-    #[cfg(not(target_os = "windows"))]
-    platform_name: String,
+    // 已下发的规则，每条规则的 filter_ids 记录了它在后端中对应的过滤器
+    rules: Vec<FilterRule>,
 }
 
 impl std::fmt::Debug for WfpController {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("WfpController")
-            .field("engine_handle", &"PlatformHandle")
+            .field("backend", &self.backend.name())
             .field("filter_ids", &self.filter_ids)
             .finish()
     }
 }
 
+// 当前平台的默认过滤后端
+fn default_backend() -> Box<dyn FilterBackend> {
+    #[cfg(target_os = "windows")]
+    {
+        Box::new(WfpBackend::new())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Box::new(NftablesBackend::new())
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        Box::new(MemoryBackend::new())
+    }
+}
+
 impl WfpController {
     pub fn new() -> anyhow::Result<Self> {
-        Ok(Self {
-            #[cfg(target_os = "windows")]
-            engine_handle: HANDLE::default(),
-            #[cfg(not(target_os = "windows"))]
-            engine_handle: 0,
+        Ok(Self::with_backend(default_backend()))
+    }
+
+    // 使用指定的后端创建控制器
+    pub(crate) fn with_backend(backend: Box<dyn FilterBackend>) -> Self {
+        Self {
+            backend,
             filter_ids: Vec::new(),
-            #[cfg(not(target_os = "windows"))]
-            platform_name: std::env::consts::OS.to_string(),
-        })
+            rules: Vec::new(),
+        }
+    }
+
+    // 当前使用的过滤后端名称
+    pub fn backend_name(&self) -> String {
+        self.backend.name().to_string()
     }
 
     // 初始化过滤引擎
     pub fn initialize(&mut self) -> anyhow::Result<()> {
-        #[cfg(target_os = "windows")]
-        {
-            self.initialize_windows()
+        println!("正在初始化网络过滤器 (后端: {})...", self.backend.name());
+        self.backend.initialize()
+    }
+
+    // 添加过滤器规则
+    pub fn add_filters(&mut self, rules: &[FilterRule]) -> anyhow::Result<Vec<u64>> {
+        let mut added_ids = Vec::new();
+
+        for rule in rules {
+            println!("🔍 处理规则: {}", rule.name);
+
+            if let Err(e) = rule.validate() {
+                println!("❌ 规则验证失败: {}", e);
+                continue;
+            }
+
+            match self.backend.add_rule(rule) {
+                Ok(ids) => {
+                    println!("✅ 规则 '{}' 添加成功 (ID: {:?})", rule.name, ids);
+                    self.filter_ids.extend(&ids);
+                    added_ids.extend(&ids);
+                    let mut installed = rule.clone();
+                    installed.filter_ids = ids;
+                    self.rules.push(installed);
+                }
+                Err(e) => {
+                    println!("❌ 规则 '{}' 添加失败: {:?}", rule.name, e);
+                }
+            }
         }
-        #[cfg(not(target_os = "windows"))]
-        {
-            self.initialize_non_windows()
+
+        if !added_ids.is_empty() || rules.is_empty() {
+            println!("🔍 网络流量控制已启动，共添加了 {} 个过滤器", added_ids.len());
+            Ok(added_ids)
+        } else {
+            println!("❌ 没有成功添加任何过滤器");
+            Err(anyhow::anyhow!("没有成功添加任何过滤器"))
         }
     }
 
-    // Windows 平台初始化
+    // 列出已下发的规则，filter_ids 为规则对应的过滤器
+    pub fn list_rules(&self) -> Vec<FilterRule> {
+        self.rules.clone()
+    }
+
+    // 清理过滤器
+    pub fn cleanup(&mut self) -> anyhow::Result<()> {
+        println!("🛑 停止过滤器，正在清理...");
+        self.backend.cleanup()?;
+        self.filter_ids.clear();
+        self.rules.clear();
+        Ok(())
+    }
+
+    // 获取层的名称用于调试
+    pub fn get_layer_name(&self, layer_key: &GUID) -> &'static str {
+        layer_name(layer_key)
+    }
+
+    // 删除过滤器
+    pub fn delete_filters(&mut self, filter_ids: &[u64]) -> anyhow::Result<u32> {
+        let mut deleted_count = 0;
+
+        for &filter_id in filter_ids {
+            if !self.filter_ids.contains(&filter_id) {
+                println!("⚠️ 未找到过滤器 {}", filter_id);
+                continue;
+            }
+            if let Err(e) = self.backend.delete_filter(filter_id) {
+                println!("⚠️ 删除过滤器 {} 失败: {:?}", filter_id, e);
+                continue;
+            }
+            self.filter_ids.retain(|&id| id != filter_id);
+            for rule in &mut self.rules {
+                rule.filter_ids.retain(|&id| id != filter_id);
+            }
+            deleted_count += 1;
+            println!("✓ 过滤器 {} 已删除", filter_id);
+        }
+        // 过滤器全部删除后规则也随之移除
+        self.rules.retain(|rule| !rule.filter_ids.is_empty());
+
+        if deleted_count > 0 || filter_ids.is_empty() {
+            Ok(deleted_count)
+        } else {
+            Err(anyhow::anyhow!("没有删除任何过滤器"))
+        }
+    }
+
+    // 按名称删除规则及其所有过滤器
+    pub fn delete_rule(&mut self, name: &str) -> anyhow::Result<u32> {
+        let ids: Vec<u64> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.name == name)
+            .flat_map(|rule| rule.filter_ids.iter().copied())
+            .collect();
+        if ids.is_empty() {
+            return Err(anyhow::anyhow!("未找到规则: {}", name));
+        }
+        self.delete_filters(&ids)
+    }
+
+    // 将已下发的规则保存为 JSON 文件
+    pub fn save_rules(&self, path: &str) -> anyhow::Result<()> {
+        let json = serde_json::to_string_pretty(&self.rules)?;
+        std::fs::write(path, json)?;
+        println!("✓ 已保存 {} 条规则到 {}", self.rules.len(), path);
+        Ok(())
+    }
+
+    // 从 JSON 文件读取规则并下发
+    pub fn load_rules(&mut self, path: &str) -> anyhow::Result<Vec<u64>> {
+        let json = std::fs::read_to_string(path)?;
+        let rules: Vec<FilterRule> = serde_json::from_str(&json)?;
+        println!("📂 从 {} 读取了 {} 条规则", path, rules.len());
+        self.add_filters(&rules)
+    }
+}
+
+// 获取层的名称
+fn layer_name(layer_key: &GUID) -> &'static str {
     #[cfg(target_os = "windows")]
-    fn initialize_windows(&mut self) -> anyhow::Result<()> {
+    {
+        match *layer_key {
+            // ALE (Application Layer Enforcement) 层
+            FWPM_LAYER_ALE_AUTH_CONNECT_V4 => "ALE_AUTH_CONNECT_V4",
+            FWPM_LAYER_ALE_AUTH_CONNECT_V6 => "ALE_AUTH_CONNECT_V6",
+            FWPM_LAYER_ALE_AUTH_RECV_ACCEPT_V4 => "ALE_AUTH_RECV_ACCEPT_V4",
+            FWPM_LAYER_ALE_AUTH_RECV_ACCEPT_V6 => "ALE_AUTH_RECV_ACCEPT_V6",
+            FWPM_LAYER_ALE_AUTH_LISTEN_V4 => "ALE_AUTH_LISTEN_V4",
+            FWPM_LAYER_ALE_AUTH_LISTEN_V6 => "ALE_AUTH_LISTEN_V6",
+            FWPM_LAYER_ALE_FLOW_ESTABLISHED_V4 => "ALE_FLOW_ESTABLISHED_V4",
+            FWPM_LAYER_ALE_FLOW_ESTABLISHED_V6 => "ALE_FLOW_ESTABLISHED_V6",
+            FWPM_LAYER_ALE_ENDPOINT_CLOSURE_V4 => "ALE_ENDPOINT_CLOSURE_V4",
+            FWPM_LAYER_ALE_ENDPOINT_CLOSURE_V6 => "ALE_ENDPOINT_CLOSURE_V6",
+            FWPM_LAYER_ALE_RESOURCE_ASSIGNMENT_V4 => "ALE_RESOURCE_ASSIGNMENT_V4",
+            FWPM_LAYER_ALE_RESOURCE_ASSIGNMENT_V6 => "ALE_RESOURCE_ASSIGNMENT_V6",
+            
+            // 传输层
+            FWPM_LAYER_OUTBOUND_TRANSPORT_V4 => "OUTBOUND_TRANSPORT_V4",
+            FWPM_LAYER_OUTBOUND_TRANSPORT_V6 => "OUTBOUND_TRANSPORT_V6",
+            FWPM_LAYER_INBOUND_TRANSPORT_V4 => "INBOUND_TRANSPORT_V4",
+            FWPM_LAYER_INBOUND_TRANSPORT_V6 => "INBOUND_TRANSPORT_V6",
+            
+            // 网络层
+            FWPM_LAYER_OUTBOUND_IPPACKET_V4 => "OUTBOUND_IPPACKET_V4",
+            FWPM_LAYER_OUTBOUND_IPPACKET_V6 => "OUTBOUND_IPPACKET_V6",
+            FWPM_LAYER_INBOUND_IPPACKET_V4 => "INBOUND_IPPACKET_V4",
+            FWPM_LAYER_INBOUND_IPPACKET_V6 => "INBOUND_IPPACKET_V6",
+            
+            // DISCARD 层 - 用于检查被丢弃的数据包
+            FWPM_LAYER_ALE_AUTH_CONNECT_DISCARD_V4 => "ALE_AUTH_CONNECT_DISCARD_V4",
+            FWPM_LAYER_ALE_AUTH_CONNECT_DISCARD_V6 => "ALE_AUTH_CONNECT_DISCARD_V6",
+            FWPM_LAYER_ALE_AUTH_RECV_ACCEPT_DISCARD_V4 => "ALE_AUTH_RECV_ACCEPT_DISCARD_V4",
+            FWPM_LAYER_ALE_AUTH_RECV_ACCEPT_DISCARD_V6 => "ALE_AUTH_RECV_ACCEPT_DISCARD_V6",
+            FWPM_LAYER_ALE_AUTH_LISTEN_DISCARD_V4 => "ALE_AUTH_LISTEN_DISCARD_V4",
+            FWPM_LAYER_ALE_AUTH_LISTEN_DISCARD_V6 => "ALE_AUTH_LISTEN_DISCARD_V6",
+            
+            // STREAM 层 - 用于流数据检查
+            FWPM_LAYER_STREAM_V4 => "STREAM_V4",
+            FWPM_LAYER_STREAM_V6 => "STREAM_V6",
+            FWPM_LAYER_STREAM_V4_DISCARD => "STREAM_V4_DISCARD",
+            FWPM_LAYER_STREAM_V6_DISCARD => "STREAM_V6_DISCARD",
+            
+            // ICMP 错误层
+            FWPM_LAYER_OUTBOUND_ICMP_ERROR_V4 => "OUTBOUND_ICMP_ERROR_V4",
+            FWPM_LAYER_OUTBOUND_ICMP_ERROR_V6 => "OUTBOUND_ICMP_ERROR_V6",
+            FWPM_LAYER_INBOUND_ICMP_ERROR_V4 => "INBOUND_ICMP_ERROR_V4",
+            FWPM_LAYER_INBOUND_ICMP_ERROR_V6 => "INBOUND_ICMP_ERROR_V6",
+            
+            _ => "UNKNOWN_LAYER",
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = layer_key;
+        "MOCK_LAYER"
+    }
+}
+
+/// 基于 Windows Filtering Platform 的过滤后端
+#[cfg(target_os = "windows")]
+struct WfpBackend {
+    engine_handle: PlatformHandle,
+    filter_ids: Vec<u64>,
+}
+
+// WFP 引擎句柄可以在线程间传递
+#[cfg(target_os = "windows")]
+unsafe impl Send for WfpBackend {}
+#[cfg(target_os = "windows")]
+unsafe impl Sync for WfpBackend {}
+
+#[cfg(target_os = "windows")]
+impl FilterBackend for WfpBackend {
+    fn name(&self) -> &'static str {
+        "wfp"
+    }
+
+    fn initialize(&mut self) -> anyhow::Result<()> {
         unsafe {
             println!("正在初始化 Windows Filtering Platform...");
 
@@ -312,86 +535,88 @@ impl WfpController {
         }
     }
 
-    // 非Windows平台初始化
-    #[cfg(not(target_os = "windows"))]
-    fn initialize_non_windows(&mut self) -> anyhow::Result<()> {
-        println!("正在初始化网络过滤器 (平台: {})...", self.platform_name);
-        println!("⚠️ 当前平台不支持网络过滤功能，使用模拟模式");
-        println!("✓ 模拟模式已启用");
-        Ok(())
-    }
-
-    // 添加过滤器规则
-    pub fn add_filters(&mut self, rules: &[FilterRule]) -> anyhow::Result<Vec<u64>> {
-        #[cfg(target_os = "windows")]
-        {
-            self.add_filters_windows(rules)
-        }
-        #[cfg(not(target_os = "windows"))]
-        {
-            self.add_filters_non_windows(rules)
-        }
-    }
-
-    // Windows 平台添加过滤器
-    #[cfg(target_os = "windows")]
-    fn add_filters_windows(&mut self, rules: &[FilterRule]) -> anyhow::Result<Vec<u64>> {
+    fn add_rule(&mut self, rule: &FilterRule) -> anyhow::Result<Vec<u64>> {
         let mut added_ids = Vec::new();
-        let mut added_count = 0;
-        
-        for rule in rules {
-            println!("🔍 处理规则: {}", rule.name);
-            
-            if let Err(e) = rule.validate() {
-                println!("❌ 规则验证失败: {}", e);
-                continue;
-            }
-            
-            let layers = self.get_layers_for_rule(rule);
-            for layer in layers {
-                match self.add_network_filter(rule, layer) {
-                    Ok(filter_id) => {
-                        self.filter_ids.push(filter_id);
-                        added_ids.push(filter_id);
-                        added_count += 1;
-                        println!("✅ 过滤器在层 {} 上添加成功 (ID: {})", self.get_layer_name(&layer), filter_id);
-                    },
-                    Err(e) => {
-                        println!("❌ 过滤器在层 {} 上添加失败: {:?}", self.get_layer_name(&layer), e);
+
+        let layers = self.get_layers_for_rule(rule);
+        for layer in layers {
+            match self.add_network_filter(rule, layer) {
+                Ok(filter_id) => {
+                    self.filter_ids.push(filter_id);
+                    added_ids.push(filter_id);
+                    println!("✅ 过滤器在层 {} 上添加成功 (ID: {})", self.get_layer_name(&layer), filter_id);
+                },
+                Err(e) => {
+                    println!("❌ 过滤器在层 {} 上添加失败: {:?}", self.get_layer_name(&layer), e);
+                    // 撤销已下发的部分，避免规则只在部分层上生效
+                    for filter_id in added_ids {
+                        if let Err(e) = self.delete_filter(filter_id) {
+                            println!("⚠️ 撤销过滤器 {} 失败: {:?}", filter_id, e);
+                        }
                     }
+                    return Err(anyhow::anyhow!(
+                        "规则 {} 在层 {} 上添加失败: {:?}",
+                        rule.name,
+                        self.get_layer_name(&layer),
+                        e
+                    ));
                 }
             }
         }
 
-        if added_count > 0 {
-            println!("🔍 网络流量控制已启动，共添加了 {} 个过滤器", added_count);
+        if added_ids.is_empty() {
+            Err(anyhow::anyhow!("规则 {} 没有可用的 WFP 层", rule.name))
+        } else {
             Ok(added_ids)
+        }
+    }
+
+    fn delete_filter(&mut self, filter_id: u64) -> anyhow::Result<()> {
+        let delete_result = unsafe { FwpmFilterDeleteById0(self.engine_handle, filter_id) };
+        if WIN32_ERROR(delete_result) == ERROR_SUCCESS {
+            self.filter_ids.retain(|&id| id != filter_id);
+            Ok(())
         } else {
-            println!("❌ 没有成功添加任何过滤器");
-            Err(anyhow::anyhow!("没有成功添加任何过滤器"))
+            Err(anyhow::anyhow!("删除过滤器 {} 失败: {}", filter_id, delete_result))
         }
     }
 
-    // 非Windows平台添加过滤器（模拟）
-    #[cfg(not(target_os = "windows"))]
-    fn add_filters_non_windows(&mut self, rules: &[FilterRule]) -> anyhow::Result<Vec<u64>> {
-        let mut added_ids = Vec::new();
-        
-        for (i, rule) in rules.iter().enumerate() {
-            if let Err(e) = rule.validate() {
-                println!("❌ 规则验证失败: {}", e);
-                continue;
+    fn cleanup(&mut self) -> anyhow::Result<()> {
+        unsafe {
+            for filter_id in &self.filter_ids {
+                let delete_result = FwpmFilterDeleteById0(self.engine_handle, *filter_id);
+                if WIN32_ERROR(delete_result) == ERROR_SUCCESS {
+                    println!("✓ 过滤器 {} 已删除", filter_id);
+                } else {
+                    println!("⚠️ 删除过滤器 {} 失败: {}", filter_id, delete_result);
+                }
             }
-            
-            let mock_id = (i + 1) as u64;
-            self.filter_ids.push(mock_id);
-            added_ids.push(mock_id);
-            println!("🔍 模拟添加规则 '{}' (ID: {})", rule.name, mock_id);
+            self.filter_ids.clear();
+
+            let result = FwpmEngineClose0(self.engine_handle);
+            if WIN32_ERROR(result) != ERROR_SUCCESS {
+                println!("❌ 关闭WFP引擎失败: {}", result);
+                return Err(anyhow::anyhow!("关闭WFP引擎失败"));
+            }
+            println!("✓ WFP引擎已关闭");
+            Ok(())
         }
-        
-        println!("✓ 模拟模式：已添加 {} 个过滤器", added_ids.len());
-        Ok(added_ids)
     }
+}
+
+#[cfg(target_os = "windows")]
+impl WfpBackend {
+    fn new() -> Self {
+        Self {
+            engine_handle: HANDLE::default(),
+            filter_ids: Vec::new(),
+        }
+    }
+
+    fn get_layer_name(&self, layer_key: &GUID) -> &'static str {
+        layer_name(layer_key)
+    }
+
     /// 获取适用于规则的WFP层
     /// 
     /// 根据过滤规则的特性选择合适的WFP层：
@@ -523,55 +748,6 @@ impl WfpController {
         layers
     }
 
-    // 清理过滤器
-    pub fn cleanup(&mut self) -> anyhow::Result<()> {
-        #[cfg(target_os = "windows")]
-        {
-            self.cleanup_windows()
-        }
-        #[cfg(not(target_os = "windows"))]
-        {
-            self.cleanup_non_windows()
-        }
-    }
-
-    // Windows平台清理
-    #[cfg(target_os = "windows")]
-    fn cleanup_windows(&mut self) -> anyhow::Result<()> {
-        unsafe {
-            println!("🛑 停止过滤器，正在清理...");
-
-            for filter_id in &self.filter_ids {
-                let delete_result = FwpmFilterDeleteById0(self.engine_handle, *filter_id);
-                if WIN32_ERROR(delete_result) == ERROR_SUCCESS {
-                    println!("✓ 过滤器 {} 已删除", filter_id);
-                } else {
-                    println!("⚠️ 删除过滤器 {} 失败: {}", filter_id, delete_result);
-                }
-            }
-
-            let result = FwpmEngineClose0(self.engine_handle);
-            if WIN32_ERROR(result) != ERROR_SUCCESS {
-                println!("❌ 关闭WFP引擎失败: {}", result);
-                return Err(anyhow::anyhow!("关闭WFP引擎失败"));
-            }
-            println!("✓ WFP引擎已关闭");
-            Ok(())
-        }
-    }
-
-    // 非Windows平台清理（模拟）
-    #[cfg(not(target_os = "windows"))]
-    fn cleanup_non_windows(&mut self) -> anyhow::Result<()> {
-        println!("🛑 停止过滤器，正在清理...");
-        for filter_id in &self.filter_ids {
-            println!("✓ 模拟删除过滤器 {}", filter_id);
-        }
-        self.filter_ids.clear();
-        println!("✓ 模拟模式清理完成");
-        Ok(())
-    }
-
     // 添加网络过滤器的内部方法 - 仅Windows
     #[cfg(target_os = "windows")]
     fn add_network_filter(
@@ -1073,123 +1249,6 @@ impl WfpController {
         }
     }
 
-    // 获取层的名称用于调试
-    pub fn get_layer_name(&self, layer_key: &GUID) -> &'static str {
-        #[cfg(target_os = "windows")]
-        {
-            match *layer_key {
-                // ALE (Application Layer Enforcement) 层
-                FWPM_LAYER_ALE_AUTH_CONNECT_V4 => "ALE_AUTH_CONNECT_V4",
-                FWPM_LAYER_ALE_AUTH_CONNECT_V6 => "ALE_AUTH_CONNECT_V6",
-                FWPM_LAYER_ALE_AUTH_RECV_ACCEPT_V4 => "ALE_AUTH_RECV_ACCEPT_V4",
-                FWPM_LAYER_ALE_AUTH_RECV_ACCEPT_V6 => "ALE_AUTH_RECV_ACCEPT_V6",
-                FWPM_LAYER_ALE_AUTH_LISTEN_V4 => "ALE_AUTH_LISTEN_V4",
-                FWPM_LAYER_ALE_AUTH_LISTEN_V6 => "ALE_AUTH_LISTEN_V6",
-                FWPM_LAYER_ALE_FLOW_ESTABLISHED_V4 => "ALE_FLOW_ESTABLISHED_V4",
-                FWPM_LAYER_ALE_FLOW_ESTABLISHED_V6 => "ALE_FLOW_ESTABLISHED_V6",
-                FWPM_LAYER_ALE_ENDPOINT_CLOSURE_V4 => "ALE_ENDPOINT_CLOSURE_V4",
-                FWPM_LAYER_ALE_ENDPOINT_CLOSURE_V6 => "ALE_ENDPOINT_CLOSURE_V6",
-                FWPM_LAYER_ALE_RESOURCE_ASSIGNMENT_V4 => "ALE_RESOURCE_ASSIGNMENT_V4",
-                FWPM_LAYER_ALE_RESOURCE_ASSIGNMENT_V6 => "ALE_RESOURCE_ASSIGNMENT_V6",
-                
-                // 传输层
-                FWPM_LAYER_OUTBOUND_TRANSPORT_V4 => "OUTBOUND_TRANSPORT_V4",
-                FWPM_LAYER_OUTBOUND_TRANSPORT_V6 => "OUTBOUND_TRANSPORT_V6",
-                FWPM_LAYER_INBOUND_TRANSPORT_V4 => "INBOUND_TRANSPORT_V4",
-                FWPM_LAYER_INBOUND_TRANSPORT_V6 => "INBOUND_TRANSPORT_V6",
-                
-                // 网络层
-                FWPM_LAYER_OUTBOUND_IPPACKET_V4 => "OUTBOUND_IPPACKET_V4",
-                FWPM_LAYER_OUTBOUND_IPPACKET_V6 => "OUTBOUND_IPPACKET_V6",
-                FWPM_LAYER_INBOUND_IPPACKET_V4 => "INBOUND_IPPACKET_V4",
-                FWPM_LAYER_INBOUND_IPPACKET_V6 => "INBOUND_IPPACKET_V6",
-                
-                // DISCARD 层 - 用于检查被丢弃的数据包
-                FWPM_LAYER_ALE_AUTH_CONNECT_DISCARD_V4 => "ALE_AUTH_CONNECT_DISCARD_V4",
-                FWPM_LAYER_ALE_AUTH_CONNECT_DISCARD_V6 => "ALE_AUTH_CONNECT_DISCARD_V6",
-                FWPM_LAYER_ALE_AUTH_RECV_ACCEPT_DISCARD_V4 => "ALE_AUTH_RECV_ACCEPT_DISCARD_V4",
-                FWPM_LAYER_ALE_AUTH_RECV_ACCEPT_DISCARD_V6 => "ALE_AUTH_RECV_ACCEPT_DISCARD_V6",
-                FWPM_LAYER_ALE_AUTH_LISTEN_DISCARD_V4 => "ALE_AUTH_LISTEN_DISCARD_V4",
-                FWPM_LAYER_ALE_AUTH_LISTEN_DISCARD_V6 => "ALE_AUTH_LISTEN_DISCARD_V6",
-                
-                // STREAM 层 - 用于流数据检查
-                FWPM_LAYER_STREAM_V4 => "STREAM_V4",
-                FWPM_LAYER_STREAM_V6 => "STREAM_V6",
-                FWPM_LAYER_STREAM_V4_DISCARD => "STREAM_V4_DISCARD",
-                FWPM_LAYER_STREAM_V6_DISCARD => "STREAM_V6_DISCARD",
-                
-                // ICMP 错误层
-                FWPM_LAYER_OUTBOUND_ICMP_ERROR_V4 => "OUTBOUND_ICMP_ERROR_V4",
-                FWPM_LAYER_OUTBOUND_ICMP_ERROR_V6 => "OUTBOUND_ICMP_ERROR_V6",
-                FWPM_LAYER_INBOUND_ICMP_ERROR_V4 => "INBOUND_ICMP_ERROR_V4",
-                FWPM_LAYER_INBOUND_ICMP_ERROR_V6 => "INBOUND_ICMP_ERROR_V6",
-                
-                _ => "UNKNOWN_LAYER",
-            }
-        }
-        #[cfg(not(target_os = "windows"))]
-        {
-            "MOCK_LAYER"
-        }
-    }
-
-    // 删除过滤器
-    pub fn delete_filters(&mut self, filter_ids: &[u64]) -> anyhow::Result<u32> {
-        #[cfg(target_os = "windows")]
-        {
-            self.delete_filters_windows(filter_ids)
-        }
-        #[cfg(not(target_os = "windows"))]
-        {
-            self.delete_filters_non_windows(filter_ids)
-        }
-    }
-
-    // Windows平台删除过滤器
-    #[cfg(target_os = "windows")]
-    fn delete_filters_windows(&mut self, filter_ids: &[u64]) -> anyhow::Result<u32> {
-        unsafe {
-            let mut deleted_count = 0;
-            
-            for &filter_id in filter_ids {
-                let delete_result = FwpmFilterDeleteById0(self.engine_handle, filter_id);
-                if WIN32_ERROR(delete_result) == ERROR_SUCCESS {
-                    if let Some(pos) = self.filter_ids.iter().position(|&id| id == filter_id) {
-                        self.filter_ids.remove(pos);
-                    }
-                    deleted_count += 1;
-                    println!("✓ 过滤器 {} 已删除", filter_id);
-                } else {
-                    println!("⚠️ 删除过滤器 {} 失败: {}", filter_id, delete_result);
-                }
-            }
-            
-            if deleted_count > 0 {
-                Ok(deleted_count)
-            } else {
-                Err(anyhow::anyhow!("没有删除任何过滤器"))
-            }
-        }
-    }
-
-    // 非Windows平台删除过滤器（模拟）
-    #[cfg(not(target_os = "windows"))]
-    fn delete_filters_non_windows(&mut self, filter_ids: &[u64]) -> anyhow::Result<u32> {
-        let mut deleted_count = 0;
-        
-        for &filter_id in filter_ids {
-            if let Some(pos) = self.filter_ids.iter().position(|&id| id == filter_id) {
-                self.filter_ids.remove(pos);
-                deleted_count += 1;
-                println!("✓ 模拟删除过滤器 {}", filter_id);
-            } else {
-                println!("⚠️ 未找到过滤器 {}", filter_id);
-            }
-        }
-        
-        Ok(deleted_count)
-    }
-
     /// 验证过滤规则与WFP层的兼容性
     /// 
     /// 检查指定的过滤规则是否与给定的WFP层兼容，
@@ -1229,5 +1288,4 @@ impl WfpController {
             Err(anyhow::anyhow!("规则与层不兼容: {}", errors.join("; ")))
         }
     }
-
-}
\ No newline at end of file
+}
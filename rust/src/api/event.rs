@@ -0,0 +1,571 @@
+use crate::frb_generated::StreamSink;
+use easytier::{
+    common::global_ctx::{EventBusSubscriber, GlobalCtxEvent},
+    proto,
+};
+use lazy_static::lazy_static;
+use tokio::sync::broadcast;
+
+// 事件中转通道的容量，订阅者落后超过该数量时会收到 Lagged 事件
+const EVENT_HUB_CAPACITY: usize = 1024;
+
+lazy_static! {
+    // 所有实例的事件都汇总到这里，再分发给 Dart 侧的订阅者
+    static ref EVENT_HUB: broadcast::Sender<KVEventRecord> =
+        broadcast::channel(EVENT_HUB_CAPACITY).0;
+}
+
+/// 事件类型，用于订阅时按类型过滤
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KVEventKind {
+    TunDeviceReady,
+    TunDeviceError,
+    PeerAdded,
+    PeerRemoved,
+    PeerConnAdded,
+    PeerConnRemoved,
+    ListenerAdded,
+    ListenerAddFailed,
+    ListenerAcceptFailed,
+    ConnectionAccepted,
+    ConnectionError,
+    Connecting,
+    ConnectError,
+    VpnPortalStarted,
+    VpnPortalClientConnected,
+    VpnPortalClientDisconnected,
+    DhcpIpv4Changed,
+    DhcpIpv4Conflicted,
+    PortForwardAdded,
+    Lagged,
+    Closed,
+}
+
+/// 节点连接信息
+#[derive(Debug, Clone)]
+pub struct KVPeerConn {
+    pub conn_id: String,
+    pub my_peer_id: u32,
+    pub peer_id: u32,
+    pub tunnel_type: String,
+    pub local_addr: String,
+    pub remote_addr: String,
+    pub is_client: bool,
+    pub network_name: String,
+}
+
+impl From<proto::cli::PeerConnInfo> for KVPeerConn {
+    fn from(p: proto::cli::PeerConnInfo) -> Self {
+        let tunnel = p.tunnel.unwrap_or_default();
+        Self {
+            conn_id: p.conn_id,
+            my_peer_id: p.my_peer_id,
+            peer_id: p.peer_id,
+            tunnel_type: tunnel.tunnel_type,
+            local_addr: tunnel.local_addr.map(|u| u.url).unwrap_or_default(),
+            remote_addr: tunnel.remote_addr.map(|u| u.url).unwrap_or_default(),
+            is_client: p.is_client,
+            network_name: p.network_name,
+        }
+    }
+}
+
+/// 与 GlobalCtxEvent 一一对应的结构化事件
+#[derive(Debug, Clone)]
+pub enum KVEvent {
+    TunDeviceReady {
+        dev_name: String,
+    },
+    TunDeviceError {
+        error: String,
+    },
+    PeerAdded {
+        peer_id: u32,
+    },
+    PeerRemoved {
+        peer_id: u32,
+    },
+    PeerConnAdded {
+        conn: KVPeerConn,
+    },
+    PeerConnRemoved {
+        conn: KVPeerConn,
+    },
+    ListenerAdded {
+        url: String,
+    },
+    ListenerAddFailed {
+        url: String,
+        error: String,
+    },
+    ListenerAcceptFailed {
+        url: String,
+        error: String,
+    },
+    ConnectionAccepted {
+        local_url: String,
+        remote_url: String,
+    },
+    ConnectionError {
+        local_url: String,
+        remote_url: String,
+        error: String,
+    },
+    Connecting {
+        url: String,
+    },
+    ConnectError {
+        dst: String,
+        ip_version: String,
+        error: String,
+    },
+    VpnPortalStarted {
+        portal: String,
+    },
+    VpnPortalClientConnected {
+        portal: String,
+        client_addr: String,
+    },
+    VpnPortalClientDisconnected {
+        portal: String,
+        client_addr: String,
+    },
+    DhcpIpv4Changed {
+        old_cidr: Option<String>,
+        new_cidr: Option<String>,
+    },
+    DhcpIpv4Conflicted {
+        cidr: Option<String>,
+    },
+    PortForwardAdded {
+        bind_addr: String,
+        dst_addr: String,
+        proto: String,
+    },
+    /// 事件处理落后，期间丢失了 missed 个事件
+    Lagged {
+        missed: u64,
+    },
+    /// 事件源已关闭，不会再有新的事件
+    Closed,
+}
+
+impl KVEvent {
+    fn kind(&self) -> KVEventKind {
+        match self {
+            KVEvent::TunDeviceReady { .. } => KVEventKind::TunDeviceReady,
+            KVEvent::TunDeviceError { .. } => KVEventKind::TunDeviceError,
+            KVEvent::PeerAdded { .. } => KVEventKind::PeerAdded,
+            KVEvent::PeerRemoved { .. } => KVEventKind::PeerRemoved,
+            KVEvent::PeerConnAdded { .. } => KVEventKind::PeerConnAdded,
+            KVEvent::PeerConnRemoved { .. } => KVEventKind::PeerConnRemoved,
+            KVEvent::ListenerAdded { .. } => KVEventKind::ListenerAdded,
+            KVEvent::ListenerAddFailed { .. } => KVEventKind::ListenerAddFailed,
+            KVEvent::ListenerAcceptFailed { .. } => KVEventKind::ListenerAcceptFailed,
+            KVEvent::ConnectionAccepted { .. } => KVEventKind::ConnectionAccepted,
+            KVEvent::ConnectionError { .. } => KVEventKind::ConnectionError,
+            KVEvent::Connecting { .. } => KVEventKind::Connecting,
+            KVEvent::ConnectError { .. } => KVEventKind::ConnectError,
+            KVEvent::VpnPortalStarted { .. } => KVEventKind::VpnPortalStarted,
+            KVEvent::VpnPortalClientConnected { .. } => KVEventKind::VpnPortalClientConnected,
+            KVEvent::VpnPortalClientDisconnected { .. } => KVEventKind::VpnPortalClientDisconnected,
+            KVEvent::DhcpIpv4Changed { .. } => KVEventKind::DhcpIpv4Changed,
+            KVEvent::DhcpIpv4Conflicted { .. } => KVEventKind::DhcpIpv4Conflicted,
+            KVEvent::PortForwardAdded { .. } => KVEventKind::PortForwardAdded,
+            KVEvent::Lagged { .. } => KVEventKind::Lagged,
+            KVEvent::Closed => KVEventKind::Closed,
+        }
+    }
+
+    // 生成日志文本，供控制台和旧的 UDP 日志监听使用
+    fn describe(&self) -> String {
+        match self {
+            KVEvent::TunDeviceReady { dev_name } => format!("TUN 设备就绪。设备: {}", dev_name),
+            KVEvent::TunDeviceError { error } => format!("TUN 设备错误。错误: {}", error),
+            KVEvent::PeerAdded { peer_id } => format!("新节点已添加。节点ID: {}", peer_id),
+            KVEvent::PeerRemoved { peer_id } => format!("节点已移除。节点ID: {}", peer_id),
+            KVEvent::PeerConnAdded { conn } => format!(
+                "新节点连接已添加。连接信息: my_peer_id: {}, dst_peer_id: {}, tunnel: {} {} -> {}",
+                conn.my_peer_id, conn.peer_id, conn.tunnel_type, conn.local_addr, conn.remote_addr
+            ),
+            KVEvent::PeerConnRemoved { conn } => format!(
+                "节点连接已移除。连接信息: my_peer_id: {}, dst_peer_id: {}, tunnel: {} {} -> {}",
+                conn.my_peer_id, conn.peer_id, conn.tunnel_type, conn.local_addr, conn.remote_addr
+            ),
+            KVEvent::ListenerAdded { url } => format!("新监听器已添加。监听器: {}", url),
+            KVEvent::ListenerAddFailed { url, error } => {
+                format!("监听器添加失败。监听器: {}, 消息: {}", url, error)
+            }
+            KVEvent::ListenerAcceptFailed { url, error } => {
+                format!("监听器接受失败。监听器: {}, 消息: {}", url, error)
+            }
+            KVEvent::ConnectionAccepted {
+                local_url,
+                remote_url,
+            } => format!("新连接已接受。本地: {}, 远程: {}", local_url, remote_url),
+            KVEvent::ConnectionError {
+                local_url,
+                remote_url,
+                error,
+            } => format!(
+                "连接错误。本地: {}, 远程: {}, 错误: {}",
+                local_url, remote_url, error
+            ),
+            KVEvent::Connecting { url } => format!("正在连接到节点。目标: {}", url),
+            KVEvent::ConnectError {
+                dst,
+                ip_version,
+                error,
+            } => format!(
+                "连接到节点错误。目标: {}, IP版本: {}, 错误: {}",
+                dst, ip_version, error
+            ),
+            KVEvent::VpnPortalStarted { portal } => format!("VPN 门户已启动。门户: {}", portal),
+            KVEvent::VpnPortalClientConnected {
+                portal,
+                client_addr,
+            } => format!(
+                "VPN 门户客户端已连接。门户: {}, 客户端地址: {}",
+                portal, client_addr
+            ),
+            KVEvent::VpnPortalClientDisconnected {
+                portal,
+                client_addr,
+            } => format!(
+                "VPN 门户客户端已断开连接。门户: {}, 客户端地址: {}",
+                portal, client_addr
+            ),
+            KVEvent::DhcpIpv4Changed { old_cidr, new_cidr } => {
+                format!("DHCP IP 已更改。旧: {:?}, 新: {:?}", old_cidr, new_cidr)
+            }
+            KVEvent::DhcpIpv4Conflicted { cidr } => format!("DHCP IP 冲突。IP: {:?}", cidr),
+            KVEvent::PortForwardAdded {
+                bind_addr,
+                dst_addr,
+                proto,
+            } => format!(
+                "端口转发已添加。本地: {}, 远程: {}, 协议: {}",
+                bind_addr, dst_addr, proto
+            ),
+            KVEvent::Lagged { missed } => format!("事件处理滞后，丢失了 {} 个事件。", missed),
+            KVEvent::Closed => "事件通道已关闭，停止事件处理。".to_string(),
+        }
+    }
+}
+
+impl From<GlobalCtxEvent> for KVEvent {
+    fn from(e: GlobalCtxEvent) -> Self {
+        match e {
+            GlobalCtxEvent::TunDeviceReady(dev_name) => KVEvent::TunDeviceReady { dev_name },
+            GlobalCtxEvent::TunDeviceError(error) => KVEvent::TunDeviceError { error },
+            GlobalCtxEvent::PeerAdded(peer_id) => KVEvent::PeerAdded { peer_id },
+            GlobalCtxEvent::PeerRemoved(peer_id) => KVEvent::PeerRemoved { peer_id },
+            GlobalCtxEvent::PeerConnAdded(p) => KVEvent::PeerConnAdded { conn: p.into() },
+            GlobalCtxEvent::PeerConnRemoved(p) => KVEvent::PeerConnRemoved { conn: p.into() },
+            GlobalCtxEvent::ListenerAdded(url) => KVEvent::ListenerAdded {
+                url: url.to_string(),
+            },
+            GlobalCtxEvent::ListenerAddFailed(url, error) => KVEvent::ListenerAddFailed {
+                url: url.to_string(),
+                error,
+            },
+            GlobalCtxEvent::ListenerAcceptFailed(url, error) => KVEvent::ListenerAcceptFailed {
+                url: url.to_string(),
+                error,
+            },
+            GlobalCtxEvent::ConnectionAccepted(local_url, remote_url) => {
+                KVEvent::ConnectionAccepted {
+                    local_url,
+                    remote_url,
+                }
+            }
+            GlobalCtxEvent::ConnectionError(local_url, remote_url, error) => {
+                KVEvent::ConnectionError {
+                    local_url,
+                    remote_url,
+                    error,
+                }
+            }
+            GlobalCtxEvent::Connecting(url) => KVEvent::Connecting {
+                url: url.to_string(),
+            },
+            GlobalCtxEvent::ConnectError(dst, ip_version, error) => KVEvent::ConnectError {
+                dst,
+                ip_version,
+                error,
+            },
+            GlobalCtxEvent::VpnPortalStarted(portal) => KVEvent::VpnPortalStarted { portal },
+            GlobalCtxEvent::VpnPortalClientConnected(portal, client_addr) => {
+                KVEvent::VpnPortalClientConnected {
+                    portal,
+                    client_addr,
+                }
+            }
+            GlobalCtxEvent::VpnPortalClientDisconnected(portal, client_addr) => {
+                KVEvent::VpnPortalClientDisconnected {
+                    portal,
+                    client_addr,
+                }
+            }
+            GlobalCtxEvent::DhcpIpv4Changed(old, new) => KVEvent::DhcpIpv4Changed {
+                old_cidr: old.map(|c| c.to_string()),
+                new_cidr: new.map(|c| c.to_string()),
+            },
+            GlobalCtxEvent::DhcpIpv4Conflicted(cidr) => KVEvent::DhcpIpv4Conflicted {
+                cidr: cidr.map(|c| c.to_string()),
+            },
+            GlobalCtxEvent::PortForwardAdded(cfg) => KVEvent::PortForwardAdded {
+                bind_addr: cfg.bind_addr.map(|a| a.to_string()).unwrap_or_default(),
+                dst_addr: cfg.dst_addr.map(|a| a.to_string()).unwrap_or_default(),
+                proto: cfg.socket_type().as_str_name().to_lowercase(),
+            },
+        }
+    }
+}
+
+/// 带时间戳的事件记录
+#[derive(Debug, Clone)]
+pub struct KVEventRecord {
+    // 产生事件的实例ID，订阅端自身的滞后事件为空字符串
+    pub instance_id: String,
+    // 毫秒级 Unix 时间戳
+    pub timestamp_ms: i64,
+    pub event: KVEvent,
+}
+
+impl KVEventRecord {
+    fn new(instance_id: String, event: KVEvent) -> Self {
+        Self {
+            instance_id,
+            timestamp_ms: chrono::Local::now().timestamp_millis(),
+            event,
+        }
+    }
+}
+
+pub(crate) fn publish_event(instance_id: uuid::Uuid, event: KVEvent) {
+    let msg = format!("[{}] {}", instance_id, event.describe());
+    println!("{}", msg);
+    let _ = crate::api::simple::send_udp_to_localhost(&msg);
+    // 没有订阅者时发送会失败，直接忽略
+    let _ = EVENT_HUB.send(KVEventRecord::new(instance_id.to_string(), event));
+}
+
+// 把实例的事件总线转换为结构化事件并发布到中转通道
+pub(crate) fn forward_instance_events(
+    instance_id: uuid::Uuid,
+    mut events: EventBusSubscriber,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(GlobalCtxEvent::ListenerAdded(url)) if url.scheme() == "ring" => {}
+                Ok(e) => publish_event(instance_id, e.into()),
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    publish_event(instance_id, KVEvent::Lagged { missed: n });
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    publish_event(instance_id, KVEvent::Closed);
+                    break;
+                }
+            }
+        }
+    })
+}
+
+// 判断事件是否需要推送给订阅者
+fn should_deliver(
+    record: &KVEventRecord,
+    kinds: &[KVEventKind],
+    instance_id: Option<&str>,
+) -> bool {
+    if let Some(id) = instance_id {
+        if !record.instance_id.is_empty() && record.instance_id != id {
+            return false;
+        }
+    }
+
+    let kind = record.event.kind();
+    let always_deliver = matches!(kind, KVEventKind::Lagged | KVEventKind::Closed);
+    always_deliver || kinds.is_empty() || kinds.contains(&kind)
+}
+
+/// 订阅结构化事件流
+///
+/// kinds 为空时接收全部事件，否则只接收指定类型的事件；
+/// instance_id 不为空时只接收该实例的事件。
+/// Lagged 和 Closed 事件总是会被推送，以便调用方感知丢失的事件。
+pub fn subscribe_events(
+    sink: StreamSink<KVEventRecord>,
+    kinds: Vec<KVEventKind>,
+    instance_id: Option<String>,
+) -> Result<(), String> {
+    let mut receiver = EVENT_HUB.subscribe();
+    crate::api::simple::RT.spawn(async move {
+        loop {
+            let record = match receiver.recv().await {
+                Ok(record) => record,
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    KVEventRecord::new(String::new(), KVEvent::Lagged { missed: n })
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+
+            if !should_deliver(&record, &kinds, instance_id.as_deref()) {
+                continue;
+            }
+
+            // Dart 侧关闭了 Stream，停止转发
+            if sink.add(record).is_err() {
+                break;
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(instance_id: &str, event: KVEvent) -> KVEventRecord {
+        KVEventRecord::new(instance_id.to_string(), event)
+    }
+
+    #[test]
+    fn test_event_mapping() {
+        let event: KVEvent = GlobalCtxEvent::ListenerAddFailed(
+            "tcp://0.0.0.0:11010".parse().unwrap(),
+            "busy".into(),
+        )
+        .into();
+        assert_eq!(KVEventKind::ListenerAddFailed, event.kind());
+        let KVEvent::ListenerAddFailed { url, error } = event else {
+            panic!("unexpected event");
+        };
+        assert_eq!("tcp://0.0.0.0:11010", url);
+        assert_eq!("busy", error);
+
+        let event: KVEvent = GlobalCtxEvent::PeerConnAdded(proto::cli::PeerConnInfo {
+            conn_id: "c1".to_string(),
+            my_peer_id: 1,
+            peer_id: 2,
+            tunnel: Some(proto::common::TunnelInfo {
+                tunnel_type: "udp".to_string(),
+                local_addr: Some(proto::common::Url {
+                    url: "udp://10.0.0.1:11010".to_string(),
+                }),
+                remote_addr: None,
+            }),
+            is_client: true,
+            network_name: "net".to_string(),
+            ..Default::default()
+        })
+        .into();
+        let KVEvent::PeerConnAdded { conn } = event else {
+            panic!("unexpected event");
+        };
+        assert_eq!((1, 2), (conn.my_peer_id, conn.peer_id));
+        assert_eq!("udp", conn.tunnel_type);
+        assert_eq!("udp://10.0.0.1:11010", conn.local_addr);
+        assert_eq!("", conn.remote_addr);
+        assert!(conn.is_client);
+
+        let event: KVEvent =
+            GlobalCtxEvent::DhcpIpv4Changed(None, Some("10.126.126.2/24".parse().unwrap())).into();
+        let KVEvent::DhcpIpv4Changed { old_cidr, new_cidr } = event else {
+            panic!("unexpected event");
+        };
+        assert_eq!(None, old_cidr);
+        assert_eq!(Some("10.126.126.2/24".to_string()), new_cidr);
+
+        let event: KVEvent = GlobalCtxEvent::PortForwardAdded(proto::common::PortForwardConfigPb {
+            bind_addr: Some(
+                "0.0.0.0:8080"
+                    .parse::<std::net::SocketAddr>()
+                    .unwrap()
+                    .into(),
+            ),
+            dst_addr: Some(
+                "10.126.126.3:80"
+                    .parse::<std::net::SocketAddr>()
+                    .unwrap()
+                    .into(),
+            ),
+            socket_type: proto::common::SocketType::Udp as i32,
+            ..Default::default()
+        })
+        .into();
+        let KVEvent::PortForwardAdded {
+            bind_addr,
+            dst_addr,
+            proto,
+        } = event
+        else {
+            panic!("unexpected event");
+        };
+        assert_eq!("0.0.0.0:8080", bind_addr);
+        assert_eq!("10.126.126.3:80", dst_addr);
+        assert_eq!("udp", proto);
+    }
+
+    #[test]
+    fn test_event_filter() {
+        let peer_added = record("a", KVEvent::PeerAdded { peer_id: 1 });
+        assert!(should_deliver(&peer_added, &[], None));
+        assert!(should_deliver(
+            &peer_added,
+            &[KVEventKind::PeerAdded],
+            Some("a")
+        ));
+        assert!(!should_deliver(
+            &peer_added,
+            &[KVEventKind::PeerRemoved],
+            None
+        ));
+        assert!(!should_deliver(&peer_added, &[], Some("b")));
+
+        // lagged and closed ignore the kind filter
+        let lagged = record("a", KVEvent::Lagged { missed: 3 });
+        assert!(should_deliver(
+            &lagged,
+            &[KVEventKind::PeerAdded],
+            Some("a")
+        ));
+        assert!(!should_deliver(&lagged, &[], Some("b")));
+        let closed = record("a", KVEvent::Closed);
+        assert!(should_deliver(&closed, &[KVEventKind::PeerAdded], None));
+
+        // lagged of the subscriber itself has no instance
+        let own_lagged = record("", KVEvent::Lagged { missed: 1 });
+        assert!(should_deliver(&own_lagged, &[], Some("b")));
+    }
+
+    #[tokio::test]
+    async fn test_forward_instance_events() {
+        let instance_id = uuid::Uuid::new_v4();
+        let mut hub = EVENT_HUB.subscribe();
+        let (tx, rx) = broadcast::channel(8);
+        let task = forward_instance_events(instance_id, rx);
+
+        // ring listeners are internal and not published
+        tx.send(GlobalCtxEvent::ListenerAdded(
+            "ring://test".parse().unwrap(),
+        ))
+        .unwrap();
+        tx.send(GlobalCtxEvent::TunDeviceReady("tun0".to_string()))
+            .unwrap();
+        drop(tx);
+        task.await.unwrap();
+
+        let mut events = vec![];
+        while let Ok(record) = hub.try_recv() {
+            if record.instance_id == instance_id.to_string() {
+                events.push(record.event);
+            }
+        }
+        assert_eq!(2, events.len());
+        assert!(matches!(&events[0], KVEvent::TunDeviceReady { dev_name } if dev_name == "tun0"));
+        assert!(matches!(events[1], KVEvent::Closed));
+    }
+}
@@ -30,8 +30,107 @@ pub fn get_firewall_status(profile_index: u32) -> Result<bool> {
         Ok(enabled.as_bool())
     }
 }
+/// Linux 上 Astral 插入的放行规则的注释，用于识别和删除
+#[cfg(target_os = "linux")]
+const NFT_BYPASS_COMMENT: &str = "astral-firewall-off";
+
+#[cfg(target_os = "linux")]
+pub(crate) fn run_nft(args: &[&str]) -> Result<String, std::io::Error> {
+    let output = std::process::Command::new("nft").args(args).output()?;
+    if !output.status.success() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!(
+                "nft {} 执行失败: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr)
+            ),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn run_nft_json(args: &[&str]) -> Result<Vec<serde_json::Value>, std::io::Error> {
+    let output = run_nft(args)?;
+    let json: serde_json::Value = serde_json::from_str(&output)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    Ok(json["nftables"].as_array().cloned().unwrap_or_default())
+}
+
+/// 挂在 input 钩子上的 filter 基础链，即入站防火墙规则所在的链
+#[cfg(target_os = "linux")]
+struct NftInputChain {
+    family: String,
+    table: String,
+    name: String,
+}
+
+#[cfg(target_os = "linux")]
+fn list_input_chains() -> Result<Vec<NftInputChain>, std::io::Error> {
+    let items = match run_nft_json(&["-j", "list", "chains"]) {
+        Ok(items) => items,
+        // 没有安装 nft 视为没有防火墙
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    Ok(items
+        .iter()
+        .filter_map(|item| item.get("chain"))
+        .filter(|chain| chain["type"] == "filter" && chain["hook"] == "input")
+        .map(|chain| NftInputChain {
+            family: chain["family"].as_str().unwrap_or_default().to_string(),
+            table: chain["table"].as_str().unwrap_or_default().to_string(),
+            name: chain["name"].as_str().unwrap_or_default().to_string(),
+        })
+        .collect())
+}
+
+/// 返回链中由 Astral 插入的放行规则的 handle
+#[cfg(target_os = "linux")]
+fn list_bypass_rules(chain: &NftInputChain) -> Result<Vec<u64>, std::io::Error> {
+    let items = run_nft_json(&[
+        "-j",
+        "list",
+        "chain",
+        &chain.family,
+        &chain.table,
+        &chain.name,
+    ])?;
+    Ok(items
+        .iter()
+        .filter_map(|item| item.get("rule"))
+        .filter(|rule| rule["comment"] == NFT_BYPASS_COMMENT)
+        .filter_map(|rule| rule["handle"].as_u64())
+        .collect())
+}
+
+#[cfg(target_os = "linux")]
+fn check_profile_index(profile_index: u32) -> Result<(), std::io::Error> {
+    match profile_index {
+        1..=3 => Ok(()),
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Invalid profile index",
+        )),
+    }
+}
+
+/// Linux 没有域/专用/公用配置文件之分，三个配置文件共用 nftables 的入站规则。
+/// 只要有一条入站链没有被 Astral 放行，就认为防火墙处于开启状态
+#[cfg(target_os = "linux")]
+pub fn get_firewall_status(profile_index: u32) -> Result<bool, std::io::Error> {
+    check_profile_index(profile_index)?;
+    for chain in list_input_chains()? {
+        if list_bypass_rules(&chain)?.is_empty() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 /// 不是window就返回false
-#[cfg(not(target_os = "windows"))]
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
 pub fn get_firewall_status(_profile_index: u32) -> Result<bool, std::io::Error> {
     Ok(false)
 }
@@ -54,8 +153,44 @@ pub fn set_firewall_status(profile_index: u32, enable: bool) -> Result<()> {
     }
 }
 
+/// 关闭防火墙时在每条入站链最前面插入一条带注释的放行规则，开启时删除这些规则。
+/// 不会修改用户自己的规则和链策略
+#[cfg(target_os = "linux")]
+pub fn set_firewall_status(profile_index: u32, enable: bool) -> Result<(), std::io::Error> {
+    check_profile_index(profile_index)?;
+    let comment = format!("\"{}\"", NFT_BYPASS_COMMENT);
+    for chain in list_input_chains()? {
+        let handles = list_bypass_rules(&chain)?;
+        if enable {
+            for handle in handles {
+                run_nft(&[
+                    "delete",
+                    "rule",
+                    &chain.family,
+                    &chain.table,
+                    &chain.name,
+                    "handle",
+                    &handle.to_string(),
+                ])?;
+            }
+        } else if handles.is_empty() {
+            run_nft(&[
+                "insert",
+                "rule",
+                &chain.family,
+                &chain.table,
+                &chain.name,
+                "accept",
+                "comment",
+                &comment,
+            ])?;
+        }
+    }
+    Ok(())
+}
+
 /// 不是window就返回false
-#[cfg(not(target_os = "windows"))]
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
 pub fn set_firewall_status(_profile_index: u32, _enable: bool) -> Result<(), std::io::Error> {
     Ok(())
 }
@@ -43,7 +43,14 @@ pub fn get_all_interfaces_metrics() -> io::Result<Vec<(String, u32)>> {
 
     Ok(interfaces)
 }
-#[cfg(not(target_os = "windows"))]
+#[cfg(target_os = "linux")]
+pub fn get_all_interfaces_metrics() -> io::Result<Vec<(String, u32)>> {
+    // Linux 没有网卡跃点，取经过该网卡的路由中最小的 metric
+    easytier::common::ifcfg::IfConfiger::list_interface_metrics()
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("{:?}", e)))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
 pub fn get_all_interfaces_metrics() -> io::Result<Vec<(String, u32)>> {
     Ok(Vec::new())
 }
@@ -85,7 +92,18 @@ pub fn set_interface_metric(interface_name: &str, metric: u32) -> io::Result<()>
     Ok(())
 }
 
-#[cfg(not(target_os = "windows"))]
+#[cfg(target_os = "linux")]
+pub fn set_interface_metric(interface_name: &str, metric: u32) -> io::Result<()> {
+    // 通过 netlink 修改该网卡上所有 IPv4/IPv6 路由的 metric
+    easytier::common::ifcfg::IfConfiger::set_interface_metric(interface_name, metric).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::Other,
+            format!("Failed to set metric of {}: {:?}", interface_name, e),
+        )
+    })
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
 pub fn set_interface_metric(_interface_name: &str, _metric: u32) -> io::Result<()> {
     Ok(())
 }
\ No newline at end of file
@@ -1,6 +1,9 @@
 pub mod simple;
+pub mod event;
+pub mod profile;
 pub mod firewall;
 pub mod hops;
 pub mod astral_wfp;
 pub mod nt;
-pub mod utils;
\ No newline at end of file
+pub mod utils;
+pub mod split_tunnel;
\ No newline at end of file
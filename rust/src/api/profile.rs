@@ -0,0 +1,645 @@
+use crate::api::simple::{FlagsC, Forward};
+use easytier::{
+    common::config::{
+        gen_default_flags, get_avaliable_encrypt_methods, ConfigLoader, Flags, NetworkIdentity,
+        PeerConfig, PortForwardConfig, TomlConfigLoader, VpnPortalConfig,
+    },
+    proto::{acl::Acl, common::CompressionAlgoPb},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+/// 子网代理配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileProxyNetwork {
+    pub cidr: String,
+    // 将代理网段映射为另一个网段，为空则不映射
+    pub mapped_cidr: Option<String>,
+}
+
+/// WireGuard VPN 门户配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileVpnPortal {
+    pub client_cidr: String,
+    pub wireguard_listen: String,
+}
+
+/// 网络配置档案，与 TomlConfigLoader 的字段一一对应
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkProfile {
+    pub instance_id: Option<String>,
+    pub instance_name: String,
+    pub hostname: Option<String>,
+    pub network_name: String,
+    pub network_secret: String,
+    pub dhcp: bool,
+    // 形如 10.126.126.1/24，省略前缀长度时默认为 /24
+    pub ipv4: Option<String>,
+    pub ipv6: Option<String>,
+    pub peers: Vec<String>,
+    pub listeners: Vec<String>,
+    pub mapped_listeners: Vec<String>,
+    pub proxy_networks: Vec<ProfileProxyNetwork>,
+    pub exit_nodes: Vec<String>,
+    pub routes: Option<Vec<String>>,
+    pub socks5_portal: Option<String>,
+    pub port_forwards: Vec<Forward>,
+    pub vpn_portal: Option<ProfileVpnPortal>,
+    pub rpc_portal: Option<String>,
+    pub rpc_portal_whitelist: Option<Vec<String>>,
+    pub stun_servers: Option<Vec<String>>,
+    pub stun_servers_v6: Option<Vec<String>>,
+    pub tcp_whitelist: Vec<String>,
+    pub udp_whitelist: Vec<String>,
+    // ACL 规则，使用 JSON 表示
+    pub acl_json: Option<String>,
+    pub flags: FlagsC,
+}
+
+/// 单个字段的校验错误
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileFieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// 两个配置档案之间的单个字段差异
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileChange {
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+impl Default for FlagsC {
+    fn default() -> Self {
+        gen_default_flags().into()
+    }
+}
+
+impl From<Flags> for FlagsC {
+    fn from(f: Flags) -> Self {
+        Self {
+            default_protocol: f.default_protocol,
+            dev_name: f.dev_name,
+            enable_encryption: f.enable_encryption,
+            enable_ipv6: f.enable_ipv6,
+            mtu: f.mtu,
+            latency_first: f.latency_first,
+            enable_exit_node: f.enable_exit_node,
+            no_tun: f.no_tun,
+            use_smoltcp: f.use_smoltcp,
+            relay_network_whitelist: f.relay_network_whitelist,
+            disable_p2p: f.disable_p2p,
+            relay_all_peer_rpc: f.relay_all_peer_rpc,
+            disable_udp_hole_punching: f.disable_udp_hole_punching,
+            multi_thread: f.multi_thread,
+            data_compress_algo: f.data_compress_algo,
+            bind_device: f.bind_device,
+            enable_kcp_proxy: f.enable_kcp_proxy,
+            disable_kcp_input: f.disable_kcp_input,
+            disable_relay_kcp: f.disable_relay_kcp,
+            proxy_forward_by_system: f.proxy_forward_by_system,
+            accept_dns: f.accept_dns,
+            private_mode: f.private_mode,
+            enable_quic_proxy: f.enable_quic_proxy,
+            disable_quic_input: f.disable_quic_input,
+            disable_sym_hole_punching: f.disable_sym_hole_punching,
+            enable_relay_foreign_network_kcp: f.enable_relay_foreign_network_kcp,
+            foreign_relay_bps_limit: f.foreign_relay_bps_limit,
+            multi_thread_count: f.multi_thread_count,
+            encryption_algorithm: f.encryption_algorithm,
+            enable_multipath_bonding: f.enable_multipath_bonding,
+        }
+    }
+}
+
+impl From<FlagsC> for Flags {
+    fn from(f: FlagsC) -> Self {
+        Self {
+            default_protocol: f.default_protocol,
+            dev_name: f.dev_name,
+            enable_encryption: f.enable_encryption,
+            enable_ipv6: f.enable_ipv6,
+            mtu: f.mtu,
+            latency_first: f.latency_first,
+            enable_exit_node: f.enable_exit_node,
+            no_tun: f.no_tun,
+            use_smoltcp: f.use_smoltcp,
+            relay_network_whitelist: f.relay_network_whitelist,
+            disable_p2p: f.disable_p2p,
+            relay_all_peer_rpc: f.relay_all_peer_rpc,
+            disable_udp_hole_punching: f.disable_udp_hole_punching,
+            multi_thread: f.multi_thread,
+            data_compress_algo: f.data_compress_algo,
+            bind_device: f.bind_device,
+            enable_kcp_proxy: f.enable_kcp_proxy,
+            disable_kcp_input: f.disable_kcp_input,
+            disable_relay_kcp: f.disable_relay_kcp,
+            proxy_forward_by_system: f.proxy_forward_by_system,
+            accept_dns: f.accept_dns,
+            private_mode: f.private_mode,
+            enable_quic_proxy: f.enable_quic_proxy,
+            disable_quic_input: f.disable_quic_input,
+            disable_sym_hole_punching: f.disable_sym_hole_punching,
+            enable_relay_foreign_network_kcp: f.enable_relay_foreign_network_kcp,
+            foreign_relay_bps_limit: f.foreign_relay_bps_limit,
+            multi_thread_count: f.multi_thread_count,
+            encryption_algorithm: f.encryption_algorithm,
+            enable_multipath_bonding: f.enable_multipath_bonding,
+        }
+    }
+}
+
+// 收集校验过程中遇到的所有字段错误，而不是遇到第一个错误就返回
+#[derive(Default)]
+struct FieldErrors(Vec<ProfileFieldError>);
+
+impl FieldErrors {
+    fn push(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.0.push(ProfileFieldError {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    fn parse<T>(&mut self, field: &str, value: &str) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match value.trim().parse() {
+            Ok(v) => Some(v),
+            Err(e) => {
+                self.push(field, format!("无效的值 \"{}\": {}", value, e));
+                None
+            }
+        }
+    }
+
+    fn parse_list<T>(&mut self, field: &str, values: &[String]) -> Vec<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        values
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| !v.trim().is_empty())
+            .filter_map(|(i, v)| self.parse(&format!("{}[{}]", field, i), v))
+            .collect()
+    }
+}
+
+impl NetworkProfile {
+    fn to_config(&self) -> Result<TomlConfigLoader, Vec<ProfileFieldError>> {
+        let mut errs = FieldErrors::default();
+        let cfg = TomlConfigLoader::default();
+
+        if let Some(id) = &self.instance_id {
+            if let Some(id) = errs.parse::<uuid::Uuid>("instance_id", id) {
+                cfg.set_id(id);
+            }
+        }
+
+        if self.network_name.is_empty() {
+            errs.push("network_name", "网络名称不能为空");
+        }
+        if self.instance_name.is_empty() {
+            cfg.set_inst_name(self.network_name.clone());
+        } else {
+            cfg.set_inst_name(self.instance_name.clone());
+        }
+        cfg.set_hostname(self.hostname.clone());
+        cfg.set_network_identity(NetworkIdentity::new(
+            self.network_name.clone(),
+            self.network_secret.clone(),
+        ));
+
+        cfg.set_dhcp(self.dhcp);
+        if !self.dhcp {
+            if let Some(ipv4) = self.ipv4.as_ref().filter(|ip| !ip.is_empty()) {
+                let ipv4 = if ipv4.contains('/') {
+                    ipv4.clone()
+                } else {
+                    format!("{}/24", ipv4)
+                };
+                if let Some(inet) = errs.parse::<cidr::Ipv4Inet>("ipv4", &ipv4) {
+                    cfg.set_ipv4(Some(inet));
+                }
+            }
+        }
+        if let Some(ipv6) = self.ipv6.as_ref().filter(|ip| !ip.is_empty()) {
+            if let Some(inet) = errs.parse::<cidr::Ipv6Inet>("ipv6", ipv6) {
+                cfg.set_ipv6(Some(inet));
+            }
+        }
+
+        cfg.set_peers(
+            errs.parse_list::<url::Url>("peers", &self.peers)
+                .into_iter()
+                .map(|uri| PeerConfig { uri })
+                .collect(),
+        );
+        cfg.set_listeners(errs.parse_list::<url::Url>("listeners", &self.listeners));
+
+        if !self.mapped_listeners.is_empty() {
+            let mut mapped_listeners = Vec::new();
+            for (i, l) in self.mapped_listeners.iter().enumerate() {
+                let field = format!("mapped_listeners[{}]", i);
+                let Some(url) = errs.parse::<url::Url>(&field, l) else {
+                    continue;
+                };
+                if url.port().is_none() {
+                    errs.push(field, format!("映射监听器缺少端口: {}", l));
+                    continue;
+                }
+                mapped_listeners.push(url);
+            }
+            cfg.set_mapped_listeners(Some(mapped_listeners));
+        }
+
+        for (i, n) in self.proxy_networks.iter().enumerate() {
+            let field = format!("proxy_networks[{}]", i);
+            let Some(real) = errs.parse::<cidr::IpCidr>(&format!("{}.cidr", field), &n.cidr) else {
+                continue;
+            };
+            let mapped = match n.mapped_cidr.as_ref().filter(|c| !c.is_empty()) {
+                Some(c) => match errs.parse::<cidr::IpCidr>(&format!("{}.mapped_cidr", field), c) {
+                    Some(c) => Some(c),
+                    None => continue,
+                },
+                None => None,
+            };
+            if let Err(e) = cfg.add_proxy_cidr(real, mapped) {
+                errs.push(field, e.to_string());
+            }
+        }
+
+        let exit_nodes = errs.parse_list::<std::net::IpAddr>("exit_nodes", &self.exit_nodes);
+        if !exit_nodes.is_empty() {
+            cfg.set_exit_nodes(exit_nodes);
+        }
+
+        if let Some(routes) = &self.routes {
+            cfg.set_routes(Some(errs.parse_list::<cidr::IpCidr>("routes", routes)));
+        }
+
+        if let Some(portal) = self.socks5_portal.as_ref().filter(|p| !p.is_empty()) {
+            cfg.set_socks5_portal(errs.parse::<url::Url>("socks5_portal", portal));
+        }
+
+        let mut port_forwards = Vec::new();
+        for (i, f) in self.port_forwards.iter().enumerate() {
+            let field = format!("port_forwards[{}]", i);
+            let bind_addr = errs.parse(&format!("{}.bind_addr", field), &f.bind_addr);
+            let dst_addr = errs.parse(&format!("{}.dst_addr", field), &f.dst_addr);
+            let proto = f.proto.to_lowercase();
+            if proto != "tcp" && proto != "udp" {
+                errs.push(
+                    format!("{}.proto", field),
+                    format!("不支持的协议: {}", f.proto),
+                );
+                continue;
+            }
+            if let (Some(bind_addr), Some(dst_addr)) = (bind_addr, dst_addr) {
+                port_forwards.push(PortForwardConfig {
+                    bind_addr,
+                    dst_addr,
+                    proto,
+                    ..Default::default()
+                });
+            }
+        }
+        if !port_forwards.is_empty() {
+            cfg.set_port_forwards(port_forwards);
+        }
+
+        if let Some(portal) = &self.vpn_portal {
+            let client_cidr = errs.parse("vpn_portal.client_cidr", &portal.client_cidr);
+            let wireguard_listen =
+                errs.parse("vpn_portal.wireguard_listen", &portal.wireguard_listen);
+            if let (Some(client_cidr), Some(wireguard_listen)) = (client_cidr, wireguard_listen) {
+                cfg.set_vpn_portal_config(VpnPortalConfig {
+                    client_cidr,
+                    wireguard_listen,
+                });
+            }
+        }
+
+        if let Some(portal) = self.rpc_portal.as_ref().filter(|p| !p.is_empty()) {
+            if let Some(addr) = errs.parse("rpc_portal", portal) {
+                cfg.set_rpc_portal(addr);
+            }
+        }
+        if let Some(whitelist) = &self.rpc_portal_whitelist {
+            cfg.set_rpc_portal_whitelist(Some(errs.parse_list("rpc_portal_whitelist", whitelist)));
+        }
+
+        cfg.set_stun_servers(self.stun_servers.clone());
+        cfg.set_stun_servers_v6(self.stun_servers_v6.clone());
+        cfg.set_tcp_whitelist(self.tcp_whitelist.clone());
+        cfg.set_udp_whitelist(self.udp_whitelist.clone());
+
+        if let Some(acl) = self.acl_json.as_ref().filter(|a| !a.is_empty()) {
+            match serde_json::from_str::<Acl>(acl) {
+                Ok(acl) => cfg.set_acl(Some(acl)),
+                Err(e) => errs.push("acl_json", format!("无效的 ACL 配置: {}", e)),
+            }
+        }
+
+        Self::validate_flags(&self.flags, &mut errs);
+        cfg.set_flags(self.flags.clone().into());
+
+        if errs.0.is_empty() {
+            Ok(cfg)
+        } else {
+            Err(errs.0)
+        }
+    }
+
+    fn validate_flags(flags: &FlagsC, errs: &mut FieldErrors) {
+        if !(576..=9000).contains(&flags.mtu) {
+            errs.push("flags.mtu", format!("MTU 超出范围 576-9000: {}", flags.mtu));
+        }
+        if CompressionAlgoPb::try_from(flags.data_compress_algo).is_err() {
+            errs.push(
+                "flags.data_compress_algo",
+                format!("不支持的压缩算法: {}", flags.data_compress_algo),
+            );
+        }
+        // 空字符串表示使用默认算法
+        if !flags.encryption_algorithm.is_empty()
+            && !get_avaliable_encrypt_methods().contains(&flags.encryption_algorithm.as_str())
+        {
+            errs.push(
+                "flags.encryption_algorithm",
+                format!("不支持的加密算法: {}", flags.encryption_algorithm),
+            );
+        }
+    }
+
+    fn from_config(cfg: &TomlConfigLoader) -> Self {
+        let default_config = TomlConfigLoader::default();
+        let network_identity = cfg.get_network_identity();
+
+        Self {
+            instance_id: Some(cfg.get_id().to_string()),
+            instance_name: cfg.get_inst_name(),
+            hostname: Some(cfg.get_hostname()).filter(|h| *h != default_config.get_hostname()),
+            network_name: network_identity.network_name,
+            network_secret: network_identity.network_secret.unwrap_or_default(),
+            dhcp: cfg.get_dhcp(),
+            ipv4: cfg.get_ipv4().map(|ip| ip.to_string()),
+            ipv6: cfg.get_ipv6().map(|ip| ip.to_string()),
+            peers: cfg.get_peers().iter().map(|p| p.uri.to_string()).collect(),
+            listeners: cfg
+                .get_listeners()
+                .unwrap_or_default()
+                .iter()
+                .map(|l| l.to_string())
+                .collect(),
+            mapped_listeners: cfg
+                .get_mapped_listeners()
+                .iter()
+                .map(|l| l.to_string())
+                .collect(),
+            proxy_networks: cfg
+                .get_proxy_cidrs()
+                .iter()
+                .map(|c| ProfileProxyNetwork {
+                    cidr: c.cidr.to_string(),
+                    mapped_cidr: c.mapped_cidr.map(|m| m.to_string()),
+                })
+                .collect(),
+            exit_nodes: cfg.get_exit_nodes().iter().map(|n| n.to_string()).collect(),
+            routes: cfg
+                .get_routes()
+                .map(|routes| routes.iter().map(|r| r.to_string()).collect()),
+            socks5_portal: cfg.get_socks5_portal().map(|p| p.to_string()),
+            port_forwards: cfg
+                .get_port_forwards()
+                .into_iter()
+                .map(|f| Forward {
+                    bind_addr: f.bind_addr.to_string(),
+                    dst_addr: f.dst_addr.to_string(),
+                    proto: f.proto,
+                })
+                .collect(),
+            vpn_portal: cfg.get_vpn_portal_config().map(|c| ProfileVpnPortal {
+                client_cidr: c.client_cidr.to_string(),
+                wireguard_listen: c.wireguard_listen.to_string(),
+            }),
+            rpc_portal: cfg.get_rpc_portal().map(|p| p.to_string()),
+            rpc_portal_whitelist: cfg
+                .get_rpc_portal_whitelist()
+                .map(|w| w.iter().map(|c| c.to_string()).collect()),
+            stun_servers: cfg.get_stun_servers(),
+            stun_servers_v6: cfg.get_stun_servers_v6(),
+            tcp_whitelist: cfg.get_tcp_whitelist(),
+            udp_whitelist: cfg.get_udp_whitelist(),
+            acl_json: cfg
+                .get_acl()
+                .and_then(|acl| serde_json::to_string(&acl).ok()),
+            flags: cfg.get_flags().into(),
+        }
+    }
+}
+
+pub(crate) fn profile_to_config(profile: &NetworkProfile) -> Result<TomlConfigLoader, String> {
+    profile.to_config().map_err(|errs| {
+        errs.iter()
+            .map(|e| format!("{}: {}", e.field, e.message))
+            .collect::<Vec<_>>()
+            .join("; ")
+    })
+}
+
+// 把配置档案展开为 "字段路径 -> 值" 的形式，便于逐字段比较
+fn flatten_profile(profile: &NetworkProfile) -> BTreeMap<String, String> {
+    fn walk(prefix: &str, value: &serde_json::Value, out: &mut BTreeMap<String, String>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (k, v) in map {
+                    let key = if prefix.is_empty() {
+                        k.clone()
+                    } else {
+                        format!("{}.{}", prefix, k)
+                    };
+                    walk(&key, v, out);
+                }
+            }
+            serde_json::Value::Null => {}
+            serde_json::Value::String(s) => {
+                out.insert(prefix.to_string(), s.clone());
+            }
+            v => {
+                out.insert(prefix.to_string(), v.to_string());
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    if let Ok(value) = serde_json::to_value(profile) {
+        walk("", &value, &mut out);
+    }
+    out
+}
+
+/// 返回一个默认的配置档案
+pub fn default_profile() -> NetworkProfile {
+    let mut profile = NetworkProfile::from_config(&TomlConfigLoader::default());
+    profile.instance_id = None;
+    profile
+}
+
+/// 校验配置档案，返回所有字段错误，为空表示校验通过
+pub fn validate_profile(profile: NetworkProfile) -> Vec<ProfileFieldError> {
+    profile.to_config().err().unwrap_or_default()
+}
+
+/// 从 TOML 文本导入配置档案
+pub fn import_profile_toml(toml: String) -> Result<NetworkProfile, String> {
+    let cfg = TomlConfigLoader::new_from_str(&toml)
+        .map_err(|e| format!("解析 TOML 配置失败: {:?}", e))?;
+    Ok(NetworkProfile::from_config(&cfg))
+}
+
+/// 把配置档案导出为 TOML 文本
+pub fn export_profile_toml(profile: NetworkProfile) -> Result<String, String> {
+    Ok(profile_to_config(&profile)?.dump())
+}
+
+/// 比较两个配置档案，返回发生变化的字段
+pub fn diff_profiles(
+    old_profile: NetworkProfile,
+    new_profile: NetworkProfile,
+) -> Vec<ProfileChange> {
+    let old = flatten_profile(&old_profile);
+    let new = flatten_profile(&new_profile);
+
+    let mut fields: Vec<&String> = old.keys().chain(new.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter(|f| old.get(*f) != new.get(*f))
+        .map(|f| ProfileChange {
+            field: f.clone(),
+            old_value: old.get(f).cloned(),
+            new_value: new.get(f).cloned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> NetworkProfile {
+        let mut profile = default_profile();
+        profile.network_name = "net".to_string();
+        profile.network_secret = "secret".to_string();
+        profile
+    }
+
+    fn error_fields(profile: NetworkProfile) -> Vec<String> {
+        validate_profile(profile)
+            .into_iter()
+            .map(|e| e.field)
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_profile() {
+        assert!(validate_profile(profile()).is_empty());
+
+        let mut p = profile();
+        p.network_name = String::new();
+        p.ipv4 = Some("10.126.126.300".to_string());
+        p.peers = vec!["tcp://1.2.3.4:11010".to_string(), "not a url".to_string()];
+        p.mapped_listeners = vec!["tcp://1.2.3.4".to_string()];
+        p.port_forwards = vec![Forward {
+            bind_addr: "0.0.0.0:80".to_string(),
+            dst_addr: "10.126.126.2:80".to_string(),
+            proto: "sctp".to_string(),
+        }];
+        p.acl_json = Some("{".to_string());
+        p.flags.mtu = 100;
+        assert_eq!(
+            vec![
+                "network_name",
+                "ipv4",
+                "peers[1]",
+                "mapped_listeners[0]",
+                "port_forwards[0].proto",
+                "acl_json",
+                "flags.mtu",
+            ],
+            error_fields(p)
+        );
+
+        // ipv4 without prefix defaults to /24 and is ignored with dhcp
+        let mut p = profile();
+        p.ipv4 = Some("10.126.126.1".to_string());
+        assert!(validate_profile(p.clone()).is_empty());
+        p.dhcp = true;
+        p.ipv4 = Some("bad".to_string());
+        assert!(validate_profile(p).is_empty());
+    }
+
+    #[test]
+    fn test_profile_toml_round_trip() {
+        let mut p = profile();
+        p.instance_id = Some(uuid::Uuid::new_v4().to_string());
+        p.instance_name = "office".to_string();
+        p.ipv4 = Some("10.126.126.1/24".to_string());
+        p.peers = vec!["tcp://1.2.3.4:11010".to_string()];
+        p.listeners = vec!["udp://0.0.0.0:11010".to_string()];
+        p.proxy_networks = vec![ProfileProxyNetwork {
+            cidr: "192.168.1.0/24".to_string(),
+            mapped_cidr: Some("10.1.1.0/24".to_string()),
+        }];
+        p.exit_nodes = vec!["10.126.126.9".to_string()];
+        p.flags.enable_multipath_bonding = true;
+        p.port_forwards = vec![Forward {
+            bind_addr: "0.0.0.0:8080".to_string(),
+            dst_addr: "10.126.126.2:80".to_string(),
+            proto: "tcp".to_string(),
+        }];
+
+        let toml = export_profile_toml(p.clone()).unwrap();
+        assert_eq!(p, import_profile_toml(toml).unwrap());
+
+        assert!(import_profile_toml("ipv4 = [".to_string()).is_err());
+        assert!(export_profile_toml(NetworkProfile {
+            network_name: String::new(),
+            ..p
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_diff_profiles() {
+        let old = profile();
+        assert!(diff_profiles(old.clone(), old.clone()).is_empty());
+
+        let mut new = old.clone();
+        new.hostname = Some("node-a".to_string());
+        new.peers = vec!["tcp://1.2.3.4:11010".to_string()];
+        new.flags.mtu = 1300;
+
+        let changes = diff_profiles(old.clone(), new);
+        let fields: Vec<_> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(vec!["flags.mtu", "hostname", "peers"], fields);
+        assert_eq!(None, changes[1].old_value);
+        assert_eq!(Some("node-a".to_string()), changes[1].new_value);
+        assert_eq!(Some("[]".to_string()), changes[2].old_value);
+        assert_eq!(
+            Some("[\"tcp://1.2.3.4:11010\"]".to_string()),
+            changes[2].new_value
+        );
+        assert_eq!(Some(old.flags.mtu.to_string()), changes[0].old_value);
+    }
+}
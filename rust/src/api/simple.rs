@@ -1,4 +1,10 @@
-use easytier::{common::config::PortForwardConfig, launcher::ConfigSource};
+use crate::api::profile::{
+    default_profile, profile_to_config, NetworkProfile, ProfileFieldError, ProfileProxyNetwork,
+};
+use easytier::{
+    instance_manager::NetworkInstanceManager,
+    launcher::{ConfigSource, NetworkInstanceRunningInfo},
+};
 pub use easytier::{
     common::{
         self,
@@ -26,25 +32,31 @@ pub use easytier::{
     utils::cost_to_str,
 };
 use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 pub use std::collections::BTreeMap;
-use std::sync::Mutex;
 use tokio::runtime::Runtime;
 pub use tokio::task::JoinHandle;
 
 pub static DEFAULT_ET_DNS_ZONE: &str = "as.net.";
 
-static INSTANCE: Mutex<Option<NetworkInstance>> = Mutex::new(None);
-// 创建一个 NetworkInstance 类型变量 储存当前服务器
 lazy_static! {
-    static ref RT: Runtime = Runtime::new().expect("创建 Tokio 运行时失败");
+    pub(crate) static ref RT: Runtime = Runtime::new().expect("创建 Tokio 运行时失败");
+    // 管理所有正在运行的网络实例，以实例 UUID 区分
+    static ref INSTANCE_MANAGER: NetworkInstanceManager = NetworkInstanceManager::new();
+}
+
+fn parse_instance_id(instance_id: &str) -> Result<uuid::Uuid, String> {
+    instance_id
+        .parse()
+        .map_err(|e| format!("无效的实例ID: {}, 错误: {}", instance_id, e))
 }
 
-fn peer_conn_info_to_string(p: proto::cli::PeerConnInfo) -> String {
-    format!(
-        "my_peer_id: {}, dst_peer_id: {}, tunnel_info: {:?}",
-        p.my_peer_id, p.peer_id, p.tunnel
-    )
+pub(crate) fn get_instance_running_info(instance_id: &str) -> Result<NetworkInstanceRunningInfo, String> {
+    let id = parse_instance_id(instance_id)?;
+    INSTANCE_MANAGER
+        .get_network_info(&id)
+        .ok_or_else(|| format!("没有找到实例: {}", instance_id))
 }
 
 pub fn send_udp_to_localhost(message: &str) -> Result<(), String> {
@@ -61,173 +73,20 @@ pub fn send_udp_to_localhost(message: &str) -> Result<(), String> {
     }
 }
 
-pub fn handle_event(mut events: EventBusSubscriber) -> tokio::task::JoinHandle<()> {
-    tokio::spawn(async move {
-        loop {
-            match events.recv().await {
-                Ok(e) => {
-                    //  println!("Received event: {:?}", e);
-                    match e {
-                        GlobalCtxEvent::PeerAdded(p) => {
-                            println!("{}", format!("新节点已添加。节点ID: {}", p));
-                            let _ = send_udp_to_localhost(&format!("新节点已添加。节点ID: {}", p));
-                        }
-                        GlobalCtxEvent::PeerRemoved(p) => {
-                            println!("{}", format!("节点已移除。节点ID: {}", p));
-                            let _ = send_udp_to_localhost(&format!("节点已移除。节点ID: {}", p));
-                        }
-                        GlobalCtxEvent::PeerConnAdded(p) => {
-                            let conn_info = peer_conn_info_to_string(p);
-                            let msg = format!("新节点连接已添加。连接信息: {}", conn_info);
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::PeerConnRemoved(p) => {
-                            let msg = format!(
-                                "节点连接已移除。连接信息: {}",
-                                peer_conn_info_to_string(p)
-                            );
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::ListenerAddFailed(p, msg) => {
-                            let msg = format!("监听器添加失败。监听器: {}, 消息: {}", p, msg);
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::ListenerAcceptFailed(p, msg) => {
-                            let msg = format!("监听器接受失败。监听器: {}, 消息: {}", p, msg);
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::ListenerAdded(p) => {
-                            if p.scheme() == "ring" {
-                                continue;
-                            }
-                            let msg = format!("新监听器已添加。监听器: {}", p);
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::ConnectionAccepted(local, remote) => {
-                            let msg = format!("新连接已接受。本地: {}, 远程: {}", local, remote);
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::ConnectionError(local, remote, err) => {
-                            let msg = format!(
-                                "连接错误。本地: {}, 远程: {}, 错误: {}",
-                                local, remote, err
-                            );
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::TunDeviceReady(dev) => {
-                            let msg = format!("TUN 设备就绪。设备: {}", dev);
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::TunDeviceError(err) => {
-                            let msg = format!("TUN 设备错误。错误: {}", err);
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::Connecting(dst) => {
-                            let msg = format!("正在连接到节点。目标: {}", dst);
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::ConnectError(dst, ip_version, err) => {
-                            let msg = format!(
-                                "连接到节点错误。目标: {}, IP版本: {}, 错误: {}",
-                                dst, ip_version, err
-                            );
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::VpnPortalStarted(portal) => {
-                            let msg = format!("VPN 门户已启动。门户: {}", portal);
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::VpnPortalClientConnected(portal, client_addr) => {
-                            let msg = format!(
-                                "VPN 门户客户端已连接。门户: {}, 客户端地址: {}",
-                                portal, client_addr
-                            );
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::VpnPortalClientDisconnected(portal, client_addr) => {
-                            let msg = format!(
-                                "VPN 门户客户端已断开连接。门户: {}, 客户端地址: {}",
-                                portal, client_addr
-                            );
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::DhcpIpv4Changed(old, new) => {
-                            let msg = format!("DHCP IP 已更改。旧: {:?}, 新: {:?}", old, new);
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::DhcpIpv4Conflicted(ip) => {
-                            let msg = format!("DHCP IP 冲突。IP: {:?}", ip);
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                        GlobalCtxEvent::PortForwardAdded(port_forward_config_pb) => {
-                            let msg = format!("端口转发已添加。配置: {:?}", port_forward_config_pb);
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                        }
-                    }
-                }
-                Err(err) => {
-                    eprintln!("接收事件错误: {:?}", err);
-                    // 根据错误类型决定是否中断循环
-                    match err {
-                        tokio::sync::broadcast::error::RecvError::Closed => {
-                            let msg = "事件通道已关闭，停止事件处理。";
-                            println!("{}", msg);
-                            let _ = send_udp_to_localhost(msg);
-                            break; // Exit the loop if the channel is closed
-                        }
-                        tokio::sync::broadcast::error::RecvError::Lagged(n) => {
-                            let msg = format!("事件处理滞后，丢失了 {} 个事件。", n);
-                            eprintln!("{}", msg);
-                            let _ = send_udp_to_localhost(&msg);
-                            // Decide if lagging is critical enough to break or just log
-                        }
-                    }
-                }
-            }
-        }
-    })
+// 将实例事件转换为结构化事件，发布给 subscribe_events 的订阅者
+fn handle_event(instance_id: uuid::Uuid, events: EventBusSubscriber) -> JoinHandle<()> {
+    crate::api::event::forward_instance_events(instance_id, events)
 }
 
-async fn create_and_store_network_instance(cfg: TomlConfigLoader) -> Result<(), String> {
-    println!("{:?}", cfg);
-
-    // 在移动 cfg 之前先获取 ID
-    let name = cfg.get_id().to_string();
-    // 创建网络实例
-    let mut network = NetworkInstance::new(cfg, ConfigSource::FFI);
-    // 启动网络实例，并处理可能的错误
-    handle_event(network.start().unwrap());
-    println!("instance {} started", name);
-    // 将实例存储到 INSTANCE 中
-    let mut instance_guard = INSTANCE
-        .lock()
-        .map_err(|e| format!("获取互斥锁失败: {}", e))?;
-    if instance_guard.is_none() {
-        *instance_guard = Some(network);
-        println!("实例已成功储存");
-    } else {
-        println!("网络实例已存在");
-    }
-    print!("成功储存");
+async fn create_and_store_network_instance(cfg: TomlConfigLoader) -> Result<String, String> {
+    // 启动网络实例并交给实例管理器保存，事件订阅在实例启动前创建，避免丢失启动阶段的事件
+    let (instance_id, events) = INSTANCE_MANAGER
+        .run_network_instance_with_events(cfg, ConfigSource::FFI)
+        .map_err(|e| format!("启动网络实例失败: {:?}", e))?;
+    handle_event(instance_id, events);
+    println!("instance {} started", instance_id);
 
-    Ok(())
+    Ok(instance_id.to_string())
 }
 
 // 返回EasyTier的版本号
@@ -235,10 +94,46 @@ pub fn easytier_version() -> Result<String, String> {
     Ok(easytier::VERSION.to_string())
 }
 
-// 是否在运行
+// 是否有任意实例在运行
 pub fn is_easytier_running() -> bool {
-    let instance = INSTANCE.lock().unwrap();
-    instance.is_some()
+    INSTANCE_MANAGER
+        .collect_network_infos()
+        .map(|infos| infos.values().any(|info| info.running))
+        .unwrap_or(false)
+}
+
+// 指定实例是否在运行
+pub fn is_instance_running(instance_id: String) -> bool {
+    get_instance_running_info(&instance_id)
+        .map(|info| info.running)
+        .unwrap_or(false)
+}
+
+// 定义实例概要信息结构体
+pub struct KVInstanceInfo {
+    pub instance_id: String,
+    pub network_name: String,
+    pub running: bool,
+    pub error_msg: Option<String>,
+}
+
+// 列出所有网络实例
+pub fn list_instances() -> Vec<KVInstanceInfo> {
+    INSTANCE_MANAGER
+        .list_network_instance_ids()
+        .into_iter()
+        .map(|id| {
+            let info = INSTANCE_MANAGER.get_network_info(&id);
+            KVInstanceInfo {
+                instance_id: id.to_string(),
+                network_name: INSTANCE_MANAGER
+                    .get_network_instance_name(&id)
+                    .unwrap_or_default(),
+                running: info.as_ref().map(|i| i.running).unwrap_or(false),
+                error_msg: info.and_then(|i| i.error_msg),
+            }
+        })
+        .collect()
 }
 // 定义节点跳跃统计信息结构体
 pub struct NodeHopStats {
@@ -282,30 +177,25 @@ pub struct KVNetworkStatus {
 }
 
 // 获取网络中所有节点的IP地址列表
-pub fn get_ips() -> Vec<String> {
+pub fn get_ips(instance_id: String) -> Vec<String> {
     let mut result = Vec::new();
 
-    // Lock the mutex and access the instance if it exists
-    let instance = INSTANCE.lock().unwrap();
-
-    if let Some(instance) = instance.as_ref() {
-        if let Some(info) = instance.get_running_info() {
-            // Add all remote node IPs
-            for route in &info.routes {
-                if let Some(ipv4_addr) = &route.ipv4_addr {
-                    if let Some(addr) = &ipv4_addr.address {
-                        let ip = format!(
-                            "{}.{}.{}.{}/{}",
-                            (addr.addr >> 24) & 0xFF,
-                            (addr.addr >> 16) & 0xFF,
-                            (addr.addr >> 8) & 0xFF,
-                            addr.addr & 0xFF,
-                            ipv4_addr.network_length
-                        );
-                        // Avoid duplicates
-                        if !result.contains(&ip) {
-                            result.push(ip);
-                        }
+    if let Ok(info) = get_instance_running_info(&instance_id) {
+        // Add all remote node IPs
+        for route in &info.routes {
+            if let Some(ipv4_addr) = &route.ipv4_addr {
+                if let Some(addr) = &ipv4_addr.address {
+                    let ip = format!(
+                        "{}.{}.{}.{}/{}",
+                        (addr.addr >> 24) & 0xFF,
+                        (addr.addr >> 16) & 0xFF,
+                        (addr.addr >> 8) & 0xFF,
+                        addr.addr & 0xFF,
+                        ipv4_addr.network_length
+                    );
+                    // Avoid duplicates
+                    if !result.contains(&ip) {
+                        result.push(ip);
                     }
                 }
             }
@@ -315,22 +205,16 @@ pub fn get_ips() -> Vec<String> {
 }
 
 // 设置TUN设备的文件描述符
-pub fn set_tun_fd(fd: i32) -> Result<(), String> {
-    let mut instance = INSTANCE.lock().unwrap();
-    if let Some(instance) = instance.as_mut() {
-        instance.set_tun_fd(fd);
-        Ok(())
-    } else {
-        Err("No instance available".to_string())
-    }
+pub fn set_tun_fd(instance_id: String, fd: i32) -> Result<(), String> {
+    let id = parse_instance_id(&instance_id)?;
+    INSTANCE_MANAGER
+        .set_tun_fd(&id, fd)
+        .map_err(|e| format!("设置TUN文件描述符失败: {}", e))
 }
 
-pub fn get_running_info() -> String {
-    INSTANCE
-        .lock()
-        .unwrap()
-        .as_ref()
-        .and_then(|instance| instance.get_running_info())
+pub fn get_running_info(instance_id: String) -> String {
+    get_instance_running_info(&instance_id)
+        .ok()
         .and_then(|info| {
             // 获取并打印节点路由对信息
             serde_json::to_string(&json!({
@@ -492,6 +376,7 @@ pub fn get_running_info() -> String {
         .unwrap_or_else(|| "{}".to_string())
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FlagsC {
     pub default_protocol: String,
     pub dev_name: String,
@@ -518,8 +403,14 @@ pub struct FlagsC {
     pub enable_quic_proxy: bool,
     pub disable_quic_input: bool,
     pub disable_sym_hole_punching: bool,
+    pub enable_relay_foreign_network_kcp: bool,
+    pub foreign_relay_bps_limit: u64,
+    pub multi_thread_count: u32,
+    pub encryption_algorithm: String,
+    pub enable_multipath_bonding: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Forward {
     pub bind_addr: String,
     pub dst_addr: String,
@@ -538,212 +429,299 @@ pub fn create_server(
     cidrs: Vec<String>,
     forwards: Vec<Forward>,
     flag: FlagsC,
-) -> JoinHandle<Result<(), String>> {
+) -> JoinHandle<Result<String, String>> {
     print!("{}", format!("创建服务器: {}，启用DHCP: {}, 指定IP: {}, 房间名称: {}, 房间密码: {}, 服务器URL: {:?}, 监听器URL: {:?}", username, enable_dhcp, specified_ip, room_name, room_password, severurl, onurl));
+    let profile = NetworkProfile {
+        instance_name: room_name.clone(),
+        hostname: Some(username),
+        network_name: room_name,
+        network_secret: room_password,
+        dhcp: enable_dhcp,
+        ipv4: Some(specified_ip).filter(|ip| !ip.is_empty()),
+        peers: severurl,
+        listeners: onurl,
+        proxy_networks: cidrs
+            .into_iter()
+            .map(|cidr| ProfileProxyNetwork {
+                cidr,
+                mapped_cidr: None,
+            })
+            .collect(),
+        port_forwards: forwards,
+        flags: flag,
+        ..default_profile()
+    };
+    create_server_with_profile(profile)
+}
+
+// 使用配置档案创建服务器，返回实例ID
+pub fn create_server_with_profile(profile: NetworkProfile) -> JoinHandle<Result<String, String>> {
     RT.spawn(async move {
-        // Create config with better error handling
-        let cfg = TomlConfigLoader::default();
-
-        // Set listeners with proper error handling
-        let mut listeners = Vec::new();
-        for url in onurl {
-            match url.parse() {
-                Ok(parsed) => listeners.push(parsed),
-                Err(e) => return Err(format!("Invalid listener URL: {}, error: {}", url, e)),
-            }
-        }
-        cfg.set_listeners(listeners);
+        let cfg = profile_to_config(&profile)?;
 
-        // Set hostname and other settings
-        cfg.set_hostname(Some(username));
-        cfg.set_dhcp(enable_dhcp);
-        for c in cidrs {
-            cfg.add_proxy_cidr(c.parse().unwrap(), None);
-        }
-        let mut old = cfg.get_port_forwards();
-
-        for c in forwards {
-            // 打印
-            println!(
-                "{}",
-                format!(
-                    "添加端口转发: {} -> {} -{}",
-                    c.bind_addr, c.dst_addr, c.proto
-                )
-            );
-            let port_forward_item = PortForwardConfig {
-                bind_addr: c.bind_addr.parse().unwrap(),
-                dst_addr: c.dst_addr.parse().unwrap(),
-                proto: c.proto,
-            };
-            old.push(port_forward_item);
+        // 未指定网卡名时自动分配一个未被占用的名称
+        let mut flags = cfg.get_flags();
+        if flags.dev_name.is_empty() {
+            flags.dev_name = pick_dev_name();
+            cfg.set_flags(flags);
         }
 
-        cfg.set_port_forwards(old);
-        // Set flags more efficiently by directly mapping from input
+        create_and_store_network_instance(cfg).await
+    })
+}
+
+// 配置热更新结果：applied 为已即时生效的字段，restart_required 为需要重启实例才能生效的字段
+#[derive(Debug, Clone)]
+pub struct KVConfigApplyReport {
+    pub applied: Vec<String>,
+    pub restart_required: Vec<String>,
+    pub failed: Vec<ProfileFieldError>,
+}
+
+// 在不重启实例的情况下应用新的配置，已建立的隧道不会断开
+pub fn apply_profile(
+    instance_id: String,
+    profile: NetworkProfile,
+) -> JoinHandle<Result<KVConfigApplyReport, String>> {
+    RT.spawn(async move {
+        let id = parse_instance_id(&instance_id)?;
+        let cfg = profile_to_config(&profile)?;
+        cfg.set_id(id);
+
+        // 未指定网卡名时沿用当前实例的网卡，避免被误判为需要重启
         let mut flags = cfg.get_flags();
-        flags.default_protocol = flag.default_protocol;
-        flags.dev_name = "astral".to_string();
-        flags.enable_encryption = flag.enable_encryption;
-        flags.enable_ipv6 = flag.enable_ipv6;
-        flags.mtu = flag.mtu;
-        flags.latency_first = flag.latency_first;
-        flags.enable_exit_node = flag.enable_exit_node;
-        flags.no_tun = flag.no_tun;
-        flags.use_smoltcp = flag.use_smoltcp;
-        flags.relay_network_whitelist = flag.relay_network_whitelist;
-        flags.disable_p2p = flag.disable_p2p;
-        flags.relay_all_peer_rpc = flag.relay_all_peer_rpc;
-        flags.disable_udp_hole_punching = flag.disable_udp_hole_punching;
-        flags.multi_thread = flag.multi_thread;
-        flags.data_compress_algo = flag.data_compress_algo;
-        flags.bind_device = flag.bind_device;
-        flags.enable_kcp_proxy = flag.enable_kcp_proxy;
-        flags.disable_kcp_input = flag.disable_kcp_input;
-        flags.disable_relay_kcp = flag.disable_relay_kcp;
-        flags.proxy_forward_by_system = flag.proxy_forward_by_system;
-        flags.accept_dns = flag.accept_dns;
-
-        flags.private_mode = flag.private_mode;
-        flags.enable_quic_proxy = flag.enable_quic_proxy;
-        flags.disable_quic_input = flag.disable_quic_input;
-        flags.disable_sym_hole_punching = flag.disable_sym_hole_punching;
-        cfg.set_flags(flags);
-        // Configure peer connections with proper error handling
-        let mut peer_configs = Vec::new();
-        for url in severurl {
-            match url.parse() {
-                Ok(uri) => peer_configs.push(PeerConfig { uri }),
-                Err(e) => return Err(format!("Invalid server URL: {}, error: {}", url, e)),
-            }
+        if flags.dev_name.is_empty() {
+            flags.dev_name = get_instance_running_info(&instance_id)?.dev_name;
+            cfg.set_flags(flags);
         }
-        cfg.set_peers(peer_configs);
-
-        // Set IP if DHCP is disabled
-        if !enable_dhcp && !specified_ip.is_empty() {
-            let ip_str = format!("{}/24", specified_ip);
-            match ip_str.parse() {
-                Ok(ip) => cfg.set_ipv4(Some(ip)),
-                Err(e) => {
-                    return Err(format!(
-                        "Invalid IP address: {}, error: {}",
-                        specified_ip, e
-                    ))
-                }
+
+        let report = INSTANCE_MANAGER
+            .apply_network_config(&id, cfg)
+            .await
+            .map_err(|e| format!("应用配置失败: {}", e))?;
+        Ok(KVConfigApplyReport {
+            applied: report.applied,
+            restart_required: report.restart_required,
+            failed: report
+                .failed
+                .into_iter()
+                .map(|(field, message)| ProfileFieldError { field, message })
+                .collect(),
+        })
+    })
+}
+
+// 多个实例同时运行时为每个实例分配不同的 TUN 设备名
+fn pick_dev_name() -> String {
+    let used: Vec<String> = INSTANCE_MANAGER
+        .collect_network_infos()
+        .map(|infos| infos.into_values().map(|info| info.dev_name).collect())
+        .unwrap_or_default();
+    (0..)
+        .map(|i| {
+            if i == 0 {
+                "astral".to_string()
+            } else {
+                format!("astral{}", i)
             }
-        }
+        })
+        .find(|name| !used.contains(name))
+        .unwrap()
+}
 
-        // Set network identity
-        cfg.set_network_identity(NetworkIdentity::new(room_name, room_password));
+// 实例关闭过程中单个清理步骤的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KVShutdownStep {
+    pub component: String,
+    pub success: bool,
+    pub message: String,
+}
 
-        // 直接启动网络实例，无需嵌套 spawn
-        create_and_store_network_instance(cfg).await
+// 实例关闭报告，clean 为 true 表示所有资源均已释放
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KVShutdownReport {
+    pub instance_id: String,
+    pub clean: bool,
+    pub steps: Vec<KVShutdownStep>,
+}
+
+// 等待实例退出的最长时间
+const STOP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+async fn stop_instance(id: uuid::Uuid) -> Result<KVShutdownReport, String> {
+    // 分应用路由依赖实例的 TUN 网卡，需要在网卡释放前撤销
+    let split_tunnel = super::split_tunnel::release_for_instance(&id.to_string());
+    let mut report = INSTANCE_MANAGER
+        .stop_network_instance(&id, STOP_TIMEOUT)
+        .await
+        .map_err(|e| format!("关闭实例 {} 失败: {}", id, e))?;
+    match split_tunnel {
+        Ok(true) => report.succeeded("split_tunnel", "分应用路由已撤销"),
+        Ok(false) => {}
+        Err(e) => report.failed("split_tunnel", e),
+    }
+    for step in report.steps.iter() {
+        println!(
+            "实例 {} 关闭 {}: {} {}",
+            id,
+            step.component,
+            if step.success { "成功" } else { "失败" },
+            step.message
+        );
+    }
+    Ok(KVShutdownReport {
+        instance_id: id.to_string(),
+        clean: report.is_clean(),
+        steps: report
+            .steps
+            .into_iter()
+            .map(|step| KVShutdownStep {
+                component: step.component,
+                success: step.success,
+                message: step.message,
+            })
+            .collect(),
     })
 }
 
-// 关闭服务器实例
-pub fn close_server() {
-    RT.spawn(async {
-        // 获取mutex锁
-        let mut locked_instance = INSTANCE.lock().unwrap();
+// 关闭指定的服务器实例，等待 TUN、DNS、监听器和端口转发全部释放后返回关闭报告
+pub fn close_server(instance_id: String) -> JoinHandle<Result<KVShutdownReport, String>> {
+    RT.spawn(async move {
+        let id = parse_instance_id(&instance_id)?;
+        stop_instance(id).await
+    })
+}
 
-        println!(
-            "关闭前实例状态: {}",
-            if locked_instance.is_some() {
-                "存在"
-            } else {
-                "不存在"
+// 关闭所有服务器实例，返回每个实例的关闭报告
+pub fn close_all_servers() -> JoinHandle<Vec<KVShutdownReport>> {
+    RT.spawn(async {
+        let mut reports = Vec::new();
+        for id in INSTANCE_MANAGER.list_network_instance_ids() {
+            match stop_instance(id).await {
+                Ok(report) => reports.push(report),
+                // 实例可能已被其他调用关闭
+                Err(e) => println!("{}", e),
             }
-        ); // 添加关闭前日志
-
-        // 如果实例存在，则丢弃它
-        if let Some(instance) = locked_instance.take() {
-            println!("正在关闭实例");
-            // 丢弃实例
-            drop(instance);
-            println!("实例已成功关闭");
-        } else {
-            println!("没有找到需要关闭的实例");
         }
+        println!("所有实例已关闭");
+        reports
+    })
+}
 
-        println!(
-            "关闭后实例状态: {}",
-            if locked_instance.is_some() {
-                "存在"
-            } else {
-                "不存在"
-            }
-        ); // 添加关闭后日志
-    });
+// 出口节点健康状态
+pub struct KVExitNodeStatus {
+    pub exit_node: String,
+    pub reachable: bool,
+    pub healthy: bool,
+    pub rtt_ms: Option<f64>, // 最近一次探测的延迟(毫秒)
+    pub consecutive_failures: u32,
+}
+
+// 固定在出口节点上的流
+pub struct KVExitFlow {
+    pub protocol: String,
+    pub src: String,
+    pub dst: String,
+    pub exit_node: String,
+    pub idle_ms: u64, // 空闲时长(毫秒)
+}
+
+pub struct KVExitFlowInfo {
+    pub exit_nodes: Vec<KVExitNodeStatus>,
+    pub flows: Vec<KVExitFlow>,
+    pub total_flows: u64, // 流的总数, flows 最多只包含其中一部分
+}
+
+// 获取出口节点状态及其上的流
+pub fn get_exit_flows(instance_id: String) -> Result<KVExitFlowInfo, String> {
+    let info = get_instance_running_info(&instance_id)?;
+    let exit_flows = info.exit_flows.unwrap_or_default();
+    Ok(KVExitFlowInfo {
+        exit_nodes: exit_flows
+            .exit_nodes
+            .into_iter()
+            .map(|node| KVExitNodeStatus {
+                exit_node: node.exit_node,
+                reachable: node.reachable,
+                healthy: node.healthy,
+                rtt_ms: node.rtt_us.map(|rtt| rtt as f64 / 1000.0),
+                consecutive_failures: node.consecutive_failures,
+            })
+            .collect(),
+        flows: exit_flows
+            .flows
+            .into_iter()
+            .map(|flow| KVExitFlow {
+                protocol: flow.protocol,
+                src: flow.src,
+                dst: flow.dst,
+                exit_node: flow.exit_node,
+                idle_ms: flow.idle_ms,
+            })
+            .collect(),
+        total_flows: exit_flows.total_flows,
+    })
 }
 
 // 创建一个网卡跃点数据结构
 // 网卡跃点数据结构
 
-pub fn get_peer_route_pairs() -> Result<Vec<PeerRoutePair>, String> {
-    let instance_guard = INSTANCE
-        .lock()
-        .map_err(|e| format!("获取互斥锁失败: {}", e))?;
-
-    if let Some(instance) = instance_guard.as_ref() {
-        // 获取运行信息
-        if let Some(info) = instance.get_running_info() {
-            let mut pairs = info.peer_route_pairs;
-            // 如果存在本地节点信息，添加到结果中
-            if let Some(my_node_info) = &info.my_node_info {
-                // 获取本地节点ID
-                // 注意：这里的逻辑可能需要根据单例模式调整，假设本地节点信息可以直接从 info 中获取
-                // 使用原始逻辑查找本地 peer_id
-                let my_peer_id = info
-                    .peers
-                    .iter()
-                    .find(|p| p.conns.iter().any(|c| !c.is_client))
-                    .map(|p| p.peer_id)
-                    .unwrap_or(0);
-
-                // 创建一个表示本地节点的Route
-                let my_route = proto::cli::Route {
-                    peer_id: my_peer_id,
-                    ipv4_addr: my_node_info.virtual_ipv4.clone(),
-                    ipv6_addr: None, // 添加ipv6地址字段,目前暂不支持ipv6
-                    next_hop_peer_id: my_peer_id, // 指向自己
-                    cost: 0,                      // 到自己的成本为0
-                    path_latency: 0,              // 到自己的延迟为0
-                    proxy_cidrs: vec![],
-                    hostname: my_node_info.hostname.clone(),
-                    stun_info: my_node_info.stun_info.clone(),
-                    inst_id: "local".to_string(), // 标记为本地实例
-                    version: my_node_info.version.clone(),
-                    feature_flag: None, // 本地节点通常没有特性标志
-                    next_hop_peer_id_latency_first: None,
-                    cost_latency_first: None,
-                    path_latency_latency_first: None,
-                };
-
-                // 创建一个表示本地节点的PeerInfo，包含网络统计信息
-                // 注意：本地节点的PeerInfo可能需要特殊处理或从其他地方获取
-                let my_peer_info = info.peers.iter().find(|p| p.peer_id == my_peer_id).cloned();
-
-                // 创建一个表示本地节点的PeerRoutePair
-                let my_pair = proto::cli::PeerRoutePair {
-                    route: Some(my_route),
-                    peer: my_peer_info, // 使用找到的PeerInfo或None
-                };
-
-                // 添加到结果中
-                pairs.push(my_pair);
-            }
-
-            return Ok(pairs);
-        }
-        return Err("无法获取运行信息".to_string());
+pub fn get_peer_route_pairs(instance_id: String) -> Result<Vec<PeerRoutePair>, String> {
+    // 获取运行信息
+    let info = get_instance_running_info(&instance_id)?;
+    let mut pairs = info.peer_route_pairs;
+    // 如果存在本地节点信息，添加到结果中
+    if let Some(my_node_info) = &info.my_node_info {
+        // 获取本地节点ID
+        // 注意：这里的逻辑可能需要根据单例模式调整，假设本地节点信息可以直接从 info 中获取
+        // 使用原始逻辑查找本地 peer_id
+        let my_peer_id = info
+            .peers
+            .iter()
+            .find(|p| p.conns.iter().any(|c| !c.is_client))
+            .map(|p| p.peer_id)
+            .unwrap_or(0);
+
+        // 创建一个表示本地节点的Route
+        let my_route = proto::cli::Route {
+            peer_id: my_peer_id,
+            ipv4_addr: my_node_info.virtual_ipv4.clone(),
+            ipv6_addr: None, // 添加ipv6地址字段,目前暂不支持ipv6
+            next_hop_peer_id: my_peer_id, // 指向自己
+            cost: 0,                      // 到自己的成本为0
+            path_latency: 0,              // 到自己的延迟为0
+            proxy_cidrs: vec![],
+            hostname: my_node_info.hostname.clone(),
+            stun_info: my_node_info.stun_info.clone(),
+            inst_id: "local".to_string(), // 标记为本地实例
+            version: my_node_info.version.clone(),
+            feature_flag: None, // 本地节点通常没有特性标志
+            next_hop_peer_id_latency_first: None,
+            cost_latency_first: None,
+            path_latency_latency_first: None,
+        };
+
+        // 创建一个表示本地节点的PeerInfo，包含网络统计信息
+        // 注意：本地节点的PeerInfo可能需要特殊处理或从其他地方获取
+        let my_peer_info = info.peers.iter().find(|p| p.peer_id == my_peer_id).cloned();
+
+        // 创建一个表示本地节点的PeerRoutePair
+        let my_pair = proto::cli::PeerRoutePair {
+            route: Some(my_route),
+            peer: my_peer_info, // 使用找到的PeerInfo或None
+        };
+
+        // 添加到结果中
+        pairs.push(my_pair);
     }
-    Err("没有运行中的网络实例".to_string())
+
+    Ok(pairs)
 }
 
 // 获取网络状态信息
-pub fn get_network_status() -> KVNetworkStatus {
-    let pairs = get_peer_route_pairs().unwrap_or_default();
+pub fn get_network_status(instance_id: String) -> KVNetworkStatus {
+    let pairs = get_peer_route_pairs(instance_id.clone()).unwrap_or_default();
+    let local_info = get_instance_running_info(&instance_id).ok();
     let mut nodes = Vec::new();
     for pair in pairs.iter() {
         if let Some(route) = &pair.route {
@@ -851,14 +829,55 @@ pub fn get_network_status() -> KVNetworkStatus {
 
                         // 从当前节点开始，收集到目标节点的完整路径
                         // 先添加本地节点信息
-                        let instance_guard = INSTANCE.lock().unwrap(); // 使用单例 INSTANCE
-                        if let Some(instance) = instance_guard.as_ref() {
-                            if let Some(info) = instance.get_running_info() {
-                                if let Some(local_node) = &info.my_node_info {
-                                    // 添加本地节点作为起点
-                                    hops.push(NodeHopStats {
-                                        target_ip: local_node
-                                            .virtual_ipv4
+                        if let Some(info) = local_info.as_ref() {
+                            if let Some(local_node) = &info.my_node_info {
+                                // 添加本地节点作为起点
+                                hops.push(NodeHopStats {
+                                    target_ip: local_node
+                                        .virtual_ipv4
+                                        .as_ref()
+                                        .and_then(|addr| addr.address.as_ref())
+                                        .map(|a| {
+                                            format!(
+                                                "{}.{}.{}.{}",
+                                                (a.addr >> 24) & 0xFF,
+                                                (a.addr >> 16) & 0xFF,
+                                                (a.addr >> 8) & 0xFF,
+                                                a.addr & 0xFF
+                                            )
+                                        })
+                                        .unwrap_or_else(|| local_node.hostname.clone()),
+                                    latency_ms: 0.0,
+                                    packet_loss: 0.0,
+                                    node_name: local_node.hostname.clone(),
+                                });
+
+                                // 查找从本地到目标节点的路由
+                                if let Some(local_route) =
+                                    info.routes.iter().find(|r| r.peer_id == route.peer_id)
+                                {
+                                    // 收集中间节点
+                                    let mut next_hops = collect_hops(
+                                        pairs.as_slice(),
+                                        local_route.next_hop_peer_id,
+                                        Vec::new(),
+                                        &mut visited,
+                                    );
+                                    hops.append(&mut next_hops);
+
+                                    // 确保目标节点被添加到路径中
+                                    // 检查最后一个节点是否是目标节点
+                                    let last_node_is_target =
+                                        hops.last().map_or(false, |last| {
+                                            // 比较 hostname
+                                            last.node_name == route.hostname
+                                        });
+
+                                    // 如果最后一个节点不是目标节点，则添加目标节点
+                                    if !last_node_is_target && !visited.contains(&route.peer_id)
+                                    {
+                                        let ip = route
+                                            .ipv4_addr
                                             .as_ref()
                                             .and_then(|addr| addr.address.as_ref())
                                             .map(|a| {
@@ -870,81 +889,37 @@ pub fn get_network_status() -> KVNetworkStatus {
                                                     a.addr & 0xFF
                                                 )
                                             })
-                                            .unwrap_or_else(|| local_node.hostname.clone()),
-                                        latency_ms: 0.0,
-                                        packet_loss: 0.0,
-                                        node_name: local_node.hostname.clone(),
-                                    });
-
-                                    // 查找从本地到目标节点的路由
-                                    if let Some(local_route) =
-                                        info.routes.iter().find(|r| r.peer_id == route.peer_id)
-                                    {
-                                        // 收集中间节点
-                                        let mut next_hops = collect_hops(
-                                            pairs.as_slice(),
-                                            local_route.next_hop_peer_id,
-                                            Vec::new(),
-                                            &mut visited,
-                                        );
-                                        hops.append(&mut next_hops);
-
-                                        // 确保目标节点被添加到路径中
-                                        // 检查最后一个节点是否是目标节点
-                                        let last_node_is_target =
-                                            hops.last().map_or(false, |last| {
-                                                // 比较 hostname
-                                                last.node_name == route.hostname
+                                            .unwrap_or_default();
+
+                                        let (latency, loss) =
+                                            pair.peer.as_ref().map_or((0.0, 0.0), |p| {
+                                                let min_latency = p
+                                                    .conns
+                                                    .iter()
+                                                    .filter_map(|c| {
+                                                        c.stats.as_ref().map(|s| s.latency_us)
+                                                    })
+                                                    .min()
+                                                    .unwrap_or(0)
+                                                    as f64
+                                                    / 1000.0;
+
+                                                let avg_loss = p
+                                                    .conns
+                                                    .iter()
+                                                    .map(|c| c.loss_rate)
+                                                    .sum::<f32>()
+                                                    / p.conns.len().max(1) as f32;
+
+                                                (min_latency, avg_loss as f64)
                                             });
 
-                                        // 如果最后一个节点不是目标节点，则添加目标节点
-                                        if !last_node_is_target && !visited.contains(&route.peer_id)
-                                        {
-                                            let ip = route
-                                                .ipv4_addr
-                                                .as_ref()
-                                                .and_then(|addr| addr.address.as_ref())
-                                                .map(|a| {
-                                                    format!(
-                                                        "{}.{}.{}.{}",
-                                                        (a.addr >> 24) & 0xFF,
-                                                        (a.addr >> 16) & 0xFF,
-                                                        (a.addr >> 8) & 0xFF,
-                                                        a.addr & 0xFF
-                                                    )
-                                                })
-                                                .unwrap_or_default();
-
-                                            let (latency, loss) =
-                                                pair.peer.as_ref().map_or((0.0, 0.0), |p| {
-                                                    let min_latency = p
-                                                        .conns
-                                                        .iter()
-                                                        .filter_map(|c| {
-                                                            c.stats.as_ref().map(|s| s.latency_us)
-                                                        })
-                                                        .min()
-                                                        .unwrap_or(0)
-                                                        as f64
-                                                        / 1000.0;
-
-                                                    let avg_loss = p
-                                                        .conns
-                                                        .iter()
-                                                        .map(|c| c.loss_rate)
-                                                        .sum::<f32>()
-                                                        / p.conns.len().max(1) as f32;
-
-                                                    (min_latency, avg_loss as f64)
-                                                });
-
-                                            hops.push(NodeHopStats {
-                                                target_ip: ip,
-                                                latency_ms: latency,
-                                                packet_loss: loss as f32,
-                                                node_name: route.hostname.clone(),
-                                            });
-                                        }
+                                        hops.push(NodeHopStats {
+                                            target_ip: ip,
+                                            latency_ms: latency,
+                                            packet_loss: loss as f32,
+                                            node_name: route.hostname.clone(),
+                                        });
                                     }
                                 }
                             }
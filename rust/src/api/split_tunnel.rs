@@ -0,0 +1,626 @@
+//! 分应用路由（Split Tunneling）
+//!
+//! 按程序决定哪些流量进入虚拟网络：
+//! - Include: 只有列表中的程序可以使用虚拟网络
+//! - Exclude: 除列表中的程序外都可以使用虚拟网络，列表中的程序绕过 TUN
+//!
+//! Linux 上把程序的进程移入 cgroup，由 nftables 给这些进程的流量打上 fwmark，
+//! 再通过策略路由把带标记的流量送入（或绕开）EasyTier 的 TUN 网卡。
+//! 其他平台目前只维护规则列表。
+
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+/// 分应用路由模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SplitTunnelMode {
+    /// 只有列表中的程序走虚拟网络
+    Include,
+    /// 列表中的程序不走虚拟网络
+    Exclude,
+}
+
+/// 单条分应用路由规则
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SplitTunnelRule {
+    pub app_path: String,
+    pub enabled: bool,
+}
+
+/// 分应用路由当前的生效状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KVSplitTunnelStatus {
+    pub active: bool,
+    pub instance_id: String,
+    pub dev_name: String,
+    pub mode: SplitTunnelMode,
+    pub app_count: u32,
+}
+
+// 已生效的分应用路由
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+struct ActiveSplitTunnel {
+    instance_id: String,
+    dev_name: String,
+    #[cfg(target_os = "linux")]
+    apps: Vec<(String, String)>,
+    #[cfg(target_os = "linux")]
+    refresh_task: tokio::task::JoinHandle<()>,
+}
+
+struct SplitTunnelState {
+    mode: SplitTunnelMode,
+    rules: Vec<SplitTunnelRule>,
+    active: Option<ActiveSplitTunnel>,
+}
+
+lazy_static! {
+    static ref SPLIT_TUNNEL: Mutex<SplitTunnelState> = Mutex::new(SplitTunnelState {
+        mode: SplitTunnelMode::Include,
+        rules: Vec::new(),
+        active: None,
+    });
+}
+
+pub fn get_split_tunnel_rules() -> Vec<SplitTunnelRule> {
+    SPLIT_TUNNEL.lock().unwrap().rules.clone()
+}
+
+// 替换整个规则列表，分应用路由已生效时立即重新应用
+pub fn set_split_tunnel_rules(rules: Vec<SplitTunnelRule>) -> Result<(), String> {
+    let mut state = SPLIT_TUNNEL.lock().unwrap();
+    state.rules = rules;
+    reapply(&mut state)
+}
+
+pub fn add_split_tunnel_rule(app_path: String) -> Result<(), String> {
+    let mut state = SPLIT_TUNNEL.lock().unwrap();
+    if state.rules.iter().any(|r| r.app_path == app_path) {
+        return Err(format!("规则已存在: {}", app_path));
+    }
+    state.rules.push(SplitTunnelRule {
+        app_path,
+        enabled: true,
+    });
+    reapply(&mut state)
+}
+
+// 删除规则，返回是否找到该规则
+pub fn remove_split_tunnel_rule(app_path: String) -> Result<bool, String> {
+    let mut state = SPLIT_TUNNEL.lock().unwrap();
+    let len = state.rules.len();
+    state.rules.retain(|r| r.app_path != app_path);
+    if state.rules.len() == len {
+        return Ok(false);
+    }
+    reapply(&mut state)?;
+    Ok(true)
+}
+
+pub fn get_split_tunnel_mode() -> SplitTunnelMode {
+    SPLIT_TUNNEL.lock().unwrap().mode
+}
+
+pub fn set_split_tunnel_mode(mode: SplitTunnelMode) -> Result<(), String> {
+    let mut state = SPLIT_TUNNEL.lock().unwrap();
+    state.mode = mode;
+    reapply(&mut state)
+}
+
+// 在指定实例的 TUN 网卡上启用分应用路由
+pub fn enable_split_tunnel(instance_id: String) -> Result<(), String> {
+    let info = super::simple::get_instance_running_info(&instance_id)?;
+    if info.dev_name.is_empty() {
+        return Err(format!("实例 {} 没有 TUN 网卡", instance_id));
+    }
+    let mut state = SPLIT_TUNNEL.lock().unwrap();
+    disable(&mut state)?;
+    enable(&mut state, instance_id, info.dev_name)
+}
+
+pub fn disable_split_tunnel() -> Result<(), String> {
+    disable(&mut SPLIT_TUNNEL.lock().unwrap())
+}
+
+pub fn get_split_tunnel_status() -> KVSplitTunnelStatus {
+    let state = SPLIT_TUNNEL.lock().unwrap();
+    let app_count = state.rules.iter().filter(|r| r.enabled).count() as u32;
+    match &state.active {
+        Some(active) => KVSplitTunnelStatus {
+            active: true,
+            instance_id: active.instance_id.clone(),
+            dev_name: active.dev_name.clone(),
+            mode: state.mode,
+            app_count,
+        },
+        None => KVSplitTunnelStatus {
+            active: false,
+            instance_id: String::new(),
+            dev_name: String::new(),
+            mode: state.mode,
+            app_count,
+        },
+    }
+}
+
+// 实例关闭前调用，如果分应用路由作用在该实例上则撤销，返回是否执行了撤销
+pub(crate) fn release_for_instance(instance_id: &str) -> Result<bool, String> {
+    let mut state = SPLIT_TUNNEL.lock().unwrap();
+    match &state.active {
+        Some(active) if active.instance_id == instance_id => {
+            disable(&mut state)?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+// 规则或模式变化后，如果分应用路由已生效则按新配置重新应用
+fn reapply(state: &mut SplitTunnelState) -> Result<(), String> {
+    let Some(active) = &state.active else {
+        return Ok(());
+    };
+    let (instance_id, dev_name) = (active.instance_id.clone(), active.dev_name.clone());
+    disable(state)?;
+    enable(state, instance_id, dev_name)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn enable(
+    _state: &mut SplitTunnelState,
+    _instance_id: String,
+    _dev_name: String,
+) -> Result<(), String> {
+    Err("当前平台不支持分应用路由".to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn disable(state: &mut SplitTunnelState) -> Result<(), String> {
+    state.active = None;
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn enable(
+    state: &mut SplitTunnelState,
+    instance_id: String,
+    dev_name: String,
+) -> Result<(), String> {
+    let apps: Vec<(String, String)> = state
+        .rules
+        .iter()
+        .filter(|r| r.enabled)
+        .map(|r| {
+            let cgroup = crate::packet_filter::cgroup::app_cgroup("split", &r.app_path);
+            (r.app_path.clone(), cgroup)
+        })
+        .collect();
+
+    if let Err(e) = linux::setup(state.mode, &dev_name, &apps) {
+        // 半途失败时撤销已经做出的修改
+        linux::teardown(&apps);
+        return Err(e);
+    }
+
+    let mode = state.mode;
+    let refresh_apps = apps.clone();
+    let refresh_dev = dev_name.clone();
+    let refresh_task = super::simple::RT.spawn(async move {
+        loop {
+            tokio::time::sleep(linux::REFRESH_INTERVAL).await;
+            linux::refresh(mode, &refresh_dev, &refresh_apps);
+        }
+    });
+
+    println!(
+        "✓ 分应用路由已在 {} 上启用 ({:?}, {} 个程序)",
+        dev_name,
+        mode,
+        apps.len()
+    );
+    state.active = Some(ActiveSplitTunnel {
+        instance_id,
+        dev_name,
+        apps,
+        refresh_task,
+    });
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn disable(state: &mut SplitTunnelState) -> Result<(), String> {
+    if let Some(active) = state.active.take() {
+        active.refresh_task.abort();
+        linux::teardown(&active.apps);
+        println!("✓ 分应用路由已在 {} 上停用", active.dev_name);
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::SplitTunnelMode;
+    use crate::api::firewall::run_nft;
+    use crate::packet_filter::cgroup;
+
+    /// 分应用流量的 fwmark 和策略路由表号（"AS"）
+    const MARK: u32 = 0x4153;
+    const ROUTE_TABLE: u32 = 0x4153;
+    /// 策略路由规则优先级，需要排在 main 表（32766）之前
+    const RULE_PREF: u32 = 10300;
+
+    const NFT_FAMILY: &str = "inet";
+    const NFT_TABLE: &str = "astral_split";
+
+    /// 重新扫描新启动的进程和同步路由的间隔
+    pub(super) const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+    fn run_ip(args: &[&str]) -> Result<String, String> {
+        let output = std::process::Command::new("ip")
+            .args(args)
+            .output()
+            .map_err(|e| format!("执行 ip {} 失败: {}", args.join(" "), e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "ip {} 执行失败: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    fn nft(args: &[&str]) -> Result<String, String> {
+        run_nft(args).map_err(|e| e.to_string())
+    }
+
+    /// 路由类型，出现在目的地址之前
+    const ROUTE_TYPES: [&str; 10] = [
+        "unicast",
+        "local",
+        "broadcast",
+        "multicast",
+        "throw",
+        "unreachable",
+        "prohibit",
+        "blackhole",
+        "nat",
+        "anycast",
+    ];
+
+    /// 把 `ip route show` 的一行拆成可以重新传给 `ip route replace` 的参数
+    fn route_tokens(line: &str) -> Vec<&str> {
+        line.split_whitespace()
+            // 这些是状态标记，不能作为参数重新添加
+            .filter(|t| !matches!(*t, "linkdown" | "dead"))
+            .collect()
+    }
+
+    /// 路由在表中的唯一标识：类型、目的地址和 metric
+    fn route_key<'a>(tokens: &[&'a str]) -> Vec<&'a str> {
+        let mut key = Vec::new();
+        let mut rest = tokens;
+        if let Some((first, tail)) = rest.split_first() {
+            if ROUTE_TYPES.contains(first) {
+                key.push(*first);
+                rest = tail;
+            }
+        }
+        if let Some(dst) = rest.first() {
+            key.push(*dst);
+        }
+        if let Some(w) = rest.windows(2).find(|w| w[0] == "metric") {
+            key.extend(w);
+        }
+        key
+    }
+
+    /// 从 main 表中选出需要复制到分应用路由表的路由
+    ///
+    /// Include 模式选择经过 TUN 的路由，使标记流量进入虚拟网络；
+    /// Exclude 模式选择不经过 TUN 的路由，使标记流量绕开虚拟网络
+    fn select_routes<'a>(
+        mode: SplitTunnelMode,
+        dev_name: &str,
+        main_routes: &'a str,
+    ) -> Vec<Vec<&'a str>> {
+        main_routes
+            .lines()
+            .map(route_tokens)
+            .filter(|tokens| !tokens.is_empty())
+            .filter(|tokens| {
+                let via_tun = tokens.windows(2).any(|w| w[0] == "dev" && w[1] == dev_name);
+                match mode {
+                    SplitTunnelMode::Include => via_tun,
+                    SplitTunnelMode::Exclude => !via_tun,
+                }
+            })
+            .collect()
+    }
+
+    /// 分应用路由表中已不在 main 表里的路由，返回它们的标识
+    fn stale_routes<'a>(current_routes: &'a str, wanted: &[Vec<&str>]) -> Vec<Vec<&'a str>> {
+        let wanted_keys: Vec<Vec<&str>> = wanted.iter().map(|r| route_key(r)).collect();
+        current_routes
+            .lines()
+            .map(route_tokens)
+            .filter(|tokens| !tokens.is_empty())
+            .map(|tokens| route_key(&tokens))
+            .filter(|key| !wanted_keys.contains(key))
+            .collect()
+    }
+
+    /// IPv6 未启用（如内核参数 `ipv6.disable=1`）时 `ip -6` 的失败只记录警告
+    fn ipv6_non_fatal<T>(family: &str, result: Result<T, String>) -> Result<Option<T>, String> {
+        match result {
+            Ok(v) => Ok(Some(v)),
+            Err(e) if family == "-6" => {
+                println!("⚠️ IPv6 分应用路由不可用，已跳过: {}", e);
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 把 main 表中的路由同步到分应用路由表
+    ///
+    /// 只替换需要的路由并删除过期的路由，从不清空路由表，
+    /// 否则清空期间标记流量会落入 main 表
+    fn sync_routes(mode: SplitTunnelMode, dev_name: &str) -> Result<(), String> {
+        let table = ROUTE_TABLE.to_string();
+        for family in ["-4", "-6"] {
+            let Some(main_routes) =
+                ipv6_non_fatal(family, run_ip(&[family, "route", "show", "table", "main"]))?
+            else {
+                continue;
+            };
+            let wanted = select_routes(mode, dev_name, &main_routes);
+            for route in &wanted {
+                let mut args = vec![family, "route", "replace"];
+                args.extend(route.iter());
+                args.extend(["table", table.as_str()]);
+                if let Err(e) = run_ip(&args) {
+                    println!("⚠️ 复制路由失败: {}", e);
+                }
+            }
+
+            let Some(current_routes) =
+                ipv6_non_fatal(family, run_ip(&[family, "route", "show", "table", &table]))?
+            else {
+                continue;
+            };
+            for key in stale_routes(&current_routes, &wanted) {
+                let mut args = vec![family, "route", "del"];
+                args.extend(key.iter());
+                args.extend(["table", table.as_str()]);
+                if let Err(e) = run_ip(&args) {
+                    println!("⚠️ 删除过期路由失败: {}", e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn setup_nft(
+        mode: SplitTunnelMode,
+        dev_name: &str,
+        apps: &[(String, String)],
+    ) -> Result<(), String> {
+        let mark = format!("0x{:x}", MARK);
+        let dev = format!("\"{}\"", dev_name);
+
+        nft(&["add", "table", NFT_FAMILY, NFT_TABLE])?;
+        // route 类型的链在修改 mark 后会让内核重新选路
+        nft(&[
+            "add",
+            "chain",
+            NFT_FAMILY,
+            NFT_TABLE,
+            "mark",
+            "{ type route hook output priority mangle; policy accept; }",
+        ])?;
+        nft(&[
+            "add",
+            "chain",
+            NFT_FAMILY,
+            NFT_TABLE,
+            "guard",
+            "{ type filter hook output priority filter; policy accept; }",
+        ])?;
+        nft(&[
+            "add",
+            "chain",
+            NFT_FAMILY,
+            NFT_TABLE,
+            "nat",
+            "{ type nat hook postrouting priority srcnat; policy accept; }",
+        ])?;
+
+        for (_, cgroup_path) in apps {
+            let level = cgroup::cgroup_level(cgroup_path).to_string();
+            let path = format!("\"{}\"", cgroup_path);
+            nft(&[
+                "add", "rule", NFT_FAMILY, NFT_TABLE, "mark", "socket", "cgroupv2", "level",
+                &level, &path, "meta", "mark", "set", &mark,
+            ])?;
+        }
+
+        // 虚拟网络中其他节点主动发起的连接，其回复不受限制
+        nft(&[
+            "add",
+            "rule",
+            NFT_FAMILY,
+            NFT_TABLE,
+            "guard",
+            "oifname",
+            &dev,
+            "ct",
+            "direction",
+            "reply",
+            "accept",
+        ])?;
+        let mark_cmp = match mode {
+            SplitTunnelMode::Include => "!=",
+            SplitTunnelMode::Exclude => "==",
+        };
+        nft(&[
+            "add", "rule", NFT_FAMILY, NFT_TABLE, "guard", "oifname", &dev, "meta", "mark",
+            mark_cmp, &mark, "reject",
+        ])?;
+
+        // 重新选路后源地址可能仍属于原出口网卡
+        nft(&[
+            "add",
+            "rule",
+            NFT_FAMILY,
+            NFT_TABLE,
+            "nat",
+            "meta",
+            "mark",
+            &mark,
+            "masquerade",
+        ])?;
+        Ok(())
+    }
+
+    pub(super) fn setup(
+        mode: SplitTunnelMode,
+        dev_name: &str,
+        apps: &[(String, String)],
+    ) -> Result<(), String> {
+        for (app_path, cgroup_path) in apps {
+            let moved = cgroup::attach_app(cgroup_path, app_path)
+                .map_err(|e| format!("创建 cgroup {} 失败: {}", cgroup_path, e))?;
+            println!(
+                "🎯 已将 {} 个 {} 进程移入 cgroup {}",
+                moved, app_path, cgroup_path
+            );
+        }
+
+        sync_routes(mode, dev_name)?;
+
+        let mark = format!("0x{:x}", MARK);
+        let table = ROUTE_TABLE.to_string();
+        let pref = RULE_PREF.to_string();
+        for family in ["-4", "-6"] {
+            ipv6_non_fatal(
+                family,
+                run_ip(&[
+                    family, "rule", "add", "pref", &pref, "fwmark", &mark, "lookup", &table,
+                ]),
+            )?;
+        }
+
+        setup_nft(mode, dev_name, apps)
+    }
+
+    /// 定时把新启动的进程移入 cgroup，并同步 main 表中变化的路由
+    pub(super) fn refresh(mode: SplitTunnelMode, dev_name: &str, apps: &[(String, String)]) {
+        for (app_path, cgroup_path) in apps {
+            let _ = cgroup::attach_app(cgroup_path, app_path);
+        }
+        if let Err(e) = sync_routes(mode, dev_name) {
+            println!("⚠️ 同步分应用路由表失败: {}", e);
+        }
+    }
+
+    /// 撤销所有修改，单个步骤失败不影响后续步骤
+    pub(super) fn teardown(apps: &[(String, String)]) {
+        let _ = nft(&["delete", "table", NFT_FAMILY, NFT_TABLE]);
+
+        let mark = format!("0x{:x}", MARK);
+        let table = ROUTE_TABLE.to_string();
+        let pref = RULE_PREF.to_string();
+        for family in ["-4", "-6"] {
+            // 同一条规则可能被重复添加过
+            while run_ip(&[
+                family, "rule", "del", "pref", &pref, "fwmark", &mark, "lookup", &table,
+            ])
+            .is_ok()
+            {}
+            let _ = run_ip(&[family, "route", "flush", "table", &table]);
+        }
+
+        for (_, cgroup_path) in apps {
+            if let Err(e) = cgroup::remove(cgroup_path) {
+                println!("⚠️ 删除 cgroup {} 失败: {}", cgroup_path, e);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const MAIN_ROUTES: &str = "\
+default via 192.168.1.1 dev eth0 proto dhcp metric 100
+10.126.126.0/24 dev tun0 proto kernel scope link src 10.126.126.1
+192.168.1.0/24 dev eth0 proto kernel scope link src 192.168.1.10 metric 100 linkdown
+192.168.100.0/24 dev tun0 scope link
+unreachable 10.9.0.0/16
+";
+
+        #[test]
+        fn test_select_routes() {
+            let include = select_routes(SplitTunnelMode::Include, "tun0", MAIN_ROUTES);
+            assert_eq!(
+                vec!["10.126.126.0/24", "192.168.100.0/24"],
+                include.iter().map(|r| r[0]).collect::<Vec<_>>()
+            );
+
+            let exclude = select_routes(SplitTunnelMode::Exclude, "tun0", MAIN_ROUTES);
+            assert_eq!(3, exclude.len());
+            assert_eq!(
+                vec![
+                    "default",
+                    "via",
+                    "192.168.1.1",
+                    "dev",
+                    "eth0",
+                    "proto",
+                    "dhcp",
+                    "metric",
+                    "100"
+                ],
+                exclude[0]
+            );
+            // 状态标记被去掉
+            assert!(!exclude[1].contains(&"linkdown"));
+            assert_eq!(vec!["unreachable", "10.9.0.0/16"], exclude[2]);
+
+            // 只匹配完整的网卡名
+            assert_eq!(
+                5,
+                select_routes(SplitTunnelMode::Exclude, "tun", MAIN_ROUTES).len()
+            );
+        }
+
+        #[test]
+        fn test_stale_routes() {
+            let wanted = select_routes(SplitTunnelMode::Exclude, "tun0", MAIN_ROUTES);
+            let current = "\
+default via 192.168.1.1 dev eth0 proto dhcp metric 100
+default via 192.168.2.1 dev wlan0 proto dhcp metric 600
+172.17.0.0/16 dev docker0 proto kernel scope link src 172.17.0.1 linkdown
+unreachable 10.9.0.0/16
+";
+            assert_eq!(
+                vec![vec!["default", "metric", "600"], vec!["172.17.0.0/16"]],
+                stale_routes(current, &wanted)
+            );
+            assert!(stale_routes("", &wanted).is_empty());
+        }
+
+        #[test]
+        fn test_ipv6_non_fatal() {
+            assert_eq!(Ok(Some(1)), ipv6_non_fatal("-6", Ok(1)));
+            assert_eq!(
+                Ok(None),
+                ipv6_non_fatal::<()>("-6", Err("disabled".to_string()))
+            );
+            assert!(ipv6_non_fatal::<()>("-4", Err("failed".to_string())).is_err());
+        }
+    }
+}
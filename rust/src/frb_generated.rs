@@ -40,7 +40,7 @@ flutter_rust_bridge::frb_generated_boilerplate!(
     default_rust_auto_opaque = RustAutoOpaqueMoi,
 );
 pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_VERSION: &str = "2.11.1";
-pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_CONTENT_HASH: i32 = 294099415;
+pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_CONTENT_HASH: i32 = 896517624;
 
 // Section: executor
 
@@ -1799,6 +1799,57 @@ fn wire__crate__api__astral_wfp__WfpController_auto_accessor_set_filter_ids_impl
         },
     )
 }
+fn wire__crate__api__astral_wfp__WfpController_backend_name_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "WfpController_backend_name",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<WfpController>,
+            >>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, false,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let api_that_guard = api_that_guard.unwrap();
+                    let output_ok = Result::<_, ()>::Ok(
+                        crate::api::astral_wfp::WfpController::backend_name(&*api_that_guard),
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
 fn wire__crate__api__astral_wfp__WfpController_cleanup_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
@@ -1906,6 +1957,61 @@ fn wire__crate__api__astral_wfp__WfpController_delete_filters_impl(
         },
     )
 }
+fn wire__crate__api__astral_wfp__WfpController_delete_rule_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "WfpController_delete_rule",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<WfpController>,
+            >>::sse_decode(&mut deserializer);
+            let api_name = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
+                    (move || {
+                        let mut api_that_guard = None;
+                        let decode_indices_ =
+                            flutter_rust_bridge::for_generated::lockable_compute_decode_order(
+                                vec![flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                    &api_that, 0, true,
+                                )],
+                            );
+                        for i in decode_indices_ {
+                            match i {
+                                0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                                _ => unreachable!(),
+                            }
+                        }
+                        let mut api_that_guard = api_that_guard.unwrap();
+                        let output_ok = crate::api::astral_wfp::WfpController::delete_rule(
+                            &mut *api_that_guard,
+                            &api_name,
+                        )?;
+                        Ok(output_ok)
+                    })(),
+                )
+            }
+        },
+    )
+}
 fn wire__crate__api__astral_wfp__WfpController_get_layer_name_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
@@ -2026,7 +2132,7 @@ fn wire__crate__api__astral_wfp__WfpController_initialize_impl(
         },
     )
 }
-fn wire__crate__api__astral_wfp__WfpController_new_impl(
+fn wire__crate__api__astral_wfp__WfpController_list_rules_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2034,7 +2140,7 @@ fn wire__crate__api__astral_wfp__WfpController_new_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "WfpController_new",
+            debug_name: "WfpController_list_rules",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2048,19 +2154,36 @@ fn wire__crate__api__astral_wfp__WfpController_new_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<WfpController>,
+            >>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
-                    (move || {
-                        let output_ok = crate::api::astral_wfp::WfpController::new()?;
-                        Ok(output_ok)
-                    })(),
-                )
+                transform_result_sse::<_, ()>((move || {
+                    let mut api_that_guard = None;
+                    let decode_indices_ =
+                        flutter_rust_bridge::for_generated::lockable_compute_decode_order(vec![
+                            flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                &api_that, 0, false,
+                            ),
+                        ]);
+                    for i in decode_indices_ {
+                        match i {
+                            0 => api_that_guard = Some(api_that.lockable_decode_sync_ref()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    let api_that_guard = api_that_guard.unwrap();
+                    let output_ok = Result::<_, ()>::Ok(
+                        crate::api::astral_wfp::WfpController::list_rules(&*api_that_guard),
+                    )?;
+                    Ok(output_ok)
+                })())
             }
         },
     )
 }
-fn wire__crate__api__utils__check_sudo_impl(
+fn wire__crate__api__astral_wfp__WfpController_load_rules_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2068,7 +2191,7 @@ fn wire__crate__api__utils__check_sudo_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "check_sudo",
+            debug_name: "WfpController_load_rules",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2082,17 +2205,40 @@ fn wire__crate__api__utils__check_sudo_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<WfpController>,
+            >>::sse_decode(&mut deserializer);
+            let api_path = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::utils::check_sudo())?;
-                    Ok(output_ok)
-                })())
+                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
+                    (move || {
+                        let mut api_that_guard = None;
+                        let decode_indices_ =
+                            flutter_rust_bridge::for_generated::lockable_compute_decode_order(
+                                vec![flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                    &api_that, 0, true,
+                                )],
+                            );
+                        for i in decode_indices_ {
+                            match i {
+                                0 => api_that_guard = Some(api_that.lockable_decode_sync_ref_mut()),
+                                _ => unreachable!(),
+                            }
+                        }
+                        let mut api_that_guard = api_that_guard.unwrap();
+                        let output_ok = crate::api::astral_wfp::WfpController::load_rules(
+                            &mut *api_that_guard,
+                            &api_path,
+                        )?;
+                        Ok(output_ok)
+                    })(),
+                )
             }
         },
     )
 }
-fn wire__crate__api__simple__close_server_impl(
+fn wire__crate__api__astral_wfp__WfpController_new_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2100,7 +2246,7 @@ fn wire__crate__api__simple__close_server_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "close_server",
+            debug_name: "WfpController_new",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2116,17 +2262,17 @@ fn wire__crate__api__simple__close_server_impl(
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok({
-                        crate::api::simple::close_server();
-                    })?;
-                    Ok(output_ok)
-                })())
+                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
+                    (move || {
+                        let output_ok = crate::api::astral_wfp::WfpController::new()?;
+                        Ok(output_ok)
+                    })(),
+                )
             }
         },
     )
 }
-fn wire__crate__api__simple__create_server_impl(
+fn wire__crate__api__astral_wfp__WfpController_save_rules_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2134,7 +2280,7 @@ fn wire__crate__api__simple__create_server_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "create_server",
+            debug_name: "WfpController_save_rules",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2148,38 +2294,40 @@ fn wire__crate__api__simple__create_server_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_username = <String>::sse_decode(&mut deserializer);
-            let api_enable_dhcp = <bool>::sse_decode(&mut deserializer);
-            let api_specified_ip = <String>::sse_decode(&mut deserializer);
-            let api_room_name = <String>::sse_decode(&mut deserializer);
-            let api_room_password = <String>::sse_decode(&mut deserializer);
-            let api_severurl = <Vec<String>>::sse_decode(&mut deserializer);
-            let api_onurl = <Vec<String>>::sse_decode(&mut deserializer);
-            let api_cidrs = <Vec<String>>::sse_decode(&mut deserializer);
-            let api_forwards = <Vec<crate::api::simple::Forward>>::sse_decode(&mut deserializer);
-            let api_flag = <crate::api::simple::FlagsC>::sse_decode(&mut deserializer);
+            let api_that = <RustOpaqueMoi<
+                flutter_rust_bridge::for_generated::RustAutoOpaqueInner<WfpController>,
+            >>::sse_decode(&mut deserializer);
+            let api_path = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::create_server(
-                        api_username,
-                        api_enable_dhcp,
-                        api_specified_ip,
-                        api_room_name,
-                        api_room_password,
-                        api_severurl,
-                        api_onurl,
-                        api_cidrs,
-                        api_forwards,
-                        api_flag,
-                    ))?;
-                    Ok(output_ok)
-                })())
+                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
+                    (move || {
+                        let mut api_that_guard = None;
+                        let decode_indices_ =
+                            flutter_rust_bridge::for_generated::lockable_compute_decode_order(
+                                vec![flutter_rust_bridge::for_generated::LockableOrderInfo::new(
+                                    &api_that, 0, false,
+                                )],
+                            );
+                        for i in decode_indices_ {
+                            match i {
+                                0 => api_that_guard = Some(api_that.lockable_decode_sync_ref()),
+                                _ => unreachable!(),
+                            }
+                        }
+                        let api_that_guard = api_that_guard.unwrap();
+                        let output_ok = crate::api::astral_wfp::WfpController::save_rules(
+                            &*api_that_guard,
+                            &api_path,
+                        )?;
+                        Ok(output_ok)
+                    })(),
+                )
             }
         },
     )
 }
-fn wire__crate__api__simple__easytier_version_impl(
+fn wire__crate__api__split_tunnel__add_split_tunnel_rule_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2187,7 +2335,7 @@ fn wire__crate__api__simple__easytier_version_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "easytier_version",
+            debug_name: "add_split_tunnel_rule",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2201,17 +2349,18 @@ fn wire__crate__api__simple__easytier_version_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_app_path = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, String>((move || {
-                    let output_ok = crate::api::simple::easytier_version()?;
+                    let output_ok = crate::api::split_tunnel::add_split_tunnel_rule(api_app_path)?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__hops__get_all_interfaces_metrics_impl(
+fn wire__crate__api__simple__apply_profile_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2219,7 +2368,7 @@ fn wire__crate__api__hops__get_all_interfaces_metrics_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_all_interfaces_metrics",
+            debug_name: "apply_profile",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2233,19 +2382,22 @@ fn wire__crate__api__hops__get_all_interfaces_metrics_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_instance_id = <String>::sse_decode(&mut deserializer);
+            let api_profile = <crate::api::profile::NetworkProfile>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
-                    (move || {
-                        let output_ok = crate::api::hops::get_all_interfaces_metrics()?;
-                        Ok(output_ok)
-                    })(),
-                )
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::apply_profile(
+                        api_instance_id,
+                        api_profile,
+                    ))?;
+                    Ok(output_ok)
+                })())
             }
         },
     )
 }
-fn wire__crate__api__firewall__get_firewall_status_impl(
+fn wire__crate__api__utils__check_sudo_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2253,7 +2405,7 @@ fn wire__crate__api__firewall__get_firewall_status_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_firewall_status",
+            debug_name: "check_sudo",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2267,21 +2419,17 @@ fn wire__crate__api__firewall__get_firewall_status_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_profile_index = <u32>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
-                    (move || {
-                        let output_ok =
-                            crate::api::firewall::get_firewall_status(api_profile_index)?;
-                        Ok(output_ok)
-                    })(),
-                )
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok = Result::<_, ()>::Ok(crate::api::utils::check_sudo())?;
+                    Ok(output_ok)
+                })())
             }
         },
     )
 }
-fn wire__crate__api__simple__get_ips_impl(
+fn wire__crate__api__simple__close_all_servers_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2289,7 +2437,7 @@ fn wire__crate__api__simple__get_ips_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_ips",
+            debug_name: "close_all_servers",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2306,14 +2454,14 @@ fn wire__crate__api__simple__get_ips_impl(
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::get_ips())?;
+                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::close_all_servers())?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__get_network_status_impl(
+fn wire__crate__api__simple__close_server_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2321,7 +2469,7 @@ fn wire__crate__api__simple__get_network_status_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_network_status",
+            debug_name: "close_server",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2335,17 +2483,647 @@ fn wire__crate__api__simple__get_network_status_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_instance_id = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::get_network_status())?;
+                    let output_ok =
+                        Result::<_, ()>::Ok(crate::api::simple::close_server(api_instance_id))?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__create_server_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "create_server",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_username = <String>::sse_decode(&mut deserializer);
+            let api_enable_dhcp = <bool>::sse_decode(&mut deserializer);
+            let api_specified_ip = <String>::sse_decode(&mut deserializer);
+            let api_room_name = <String>::sse_decode(&mut deserializer);
+            let api_room_password = <String>::sse_decode(&mut deserializer);
+            let api_severurl = <Vec<String>>::sse_decode(&mut deserializer);
+            let api_onurl = <Vec<String>>::sse_decode(&mut deserializer);
+            let api_cidrs = <Vec<String>>::sse_decode(&mut deserializer);
+            let api_forwards = <Vec<crate::api::simple::Forward>>::sse_decode(&mut deserializer);
+            let api_flag = <crate::api::simple::FlagsC>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::create_server(
+                        api_username,
+                        api_enable_dhcp,
+                        api_specified_ip,
+                        api_room_name,
+                        api_room_password,
+                        api_severurl,
+                        api_onurl,
+                        api_cidrs,
+                        api_forwards,
+                        api_flag,
+                    ))?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__create_server_with_profile_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "create_server_with_profile",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_profile = <crate::api::profile::NetworkProfile>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok = Result::<_, ()>::Ok(
+                        crate::api::simple::create_server_with_profile(api_profile),
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__profile__default_profile_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "default_profile",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok = Result::<_, ()>::Ok(crate::api::profile::default_profile())?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__profile__diff_profiles_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "diff_profiles",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_old_profile =
+                <crate::api::profile::NetworkProfile>::sse_decode(&mut deserializer);
+            let api_new_profile =
+                <crate::api::profile::NetworkProfile>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok = Result::<_, ()>::Ok(crate::api::profile::diff_profiles(
+                        api_old_profile,
+                        api_new_profile,
+                    ))?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__split_tunnel__disable_split_tunnel_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "disable_split_tunnel",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, String>((move || {
+                    let output_ok = crate::api::split_tunnel::disable_split_tunnel()?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__easytier_version_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "easytier_version",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, String>((move || {
+                    let output_ok = crate::api::simple::easytier_version()?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__split_tunnel__enable_split_tunnel_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "enable_split_tunnel",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_instance_id = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, String>((move || {
+                    let output_ok = crate::api::split_tunnel::enable_split_tunnel(api_instance_id)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__profile__export_profile_toml_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "export_profile_toml",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_profile = <crate::api::profile::NetworkProfile>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, String>((move || {
+                    let output_ok = crate::api::profile::export_profile_toml(api_profile)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__hops__get_all_interfaces_metrics_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_all_interfaces_metrics",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
+                    (move || {
+                        let output_ok = crate::api::hops::get_all_interfaces_metrics()?;
+                        Ok(output_ok)
+                    })(),
+                )
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__get_exit_flows_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_exit_flows",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_instance_id = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, String>((move || {
+                    let output_ok = crate::api::simple::get_exit_flows(api_instance_id)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__firewall__get_firewall_status_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_firewall_status",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_profile_index = <u32>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
+                    (move || {
+                        let output_ok =
+                            crate::api::firewall::get_firewall_status(api_profile_index)?;
+                        Ok(output_ok)
+                    })(),
+                )
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__get_ips_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_ips",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_instance_id = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok =
+                        Result::<_, ()>::Ok(crate::api::simple::get_ips(api_instance_id))?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__get_network_status_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_network_status",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_instance_id = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::get_network_status(
+                        api_instance_id,
+                    ))?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__nt__get_nt_path_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_nt_path",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_dos_path = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok =
+                        Result::<_, ()>::Ok(crate::api::nt::get_nt_path(&api_dos_path))?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__get_peer_route_pairs_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_peer_route_pairs",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_instance_id = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, String>((move || {
+                    let output_ok = crate::api::simple::get_peer_route_pairs(api_instance_id)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__get_running_info_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_running_info",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_instance_id = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok =
+                        Result::<_, ()>::Ok(crate::api::simple::get_running_info(api_instance_id))?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__split_tunnel__get_split_tunnel_mode_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_split_tunnel_mode",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok =
+                        Result::<_, ()>::Ok(crate::api::split_tunnel::get_split_tunnel_mode())?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__split_tunnel__get_split_tunnel_rules_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_split_tunnel_rules",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok =
+                        Result::<_, ()>::Ok(crate::api::split_tunnel::get_split_tunnel_rules())?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__nt__get_nt_path_impl(
+fn wire__crate__api__split_tunnel__get_split_tunnel_status_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2353,7 +3131,7 @@ fn wire__crate__api__nt__get_nt_path_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_nt_path",
+            debug_name: "get_split_tunnel_status",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2367,19 +3145,18 @@ fn wire__crate__api__nt__get_nt_path_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_dos_path = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, ()>((move || {
                     let output_ok =
-                        Result::<_, ()>::Ok(crate::api::nt::get_nt_path(&api_dos_path))?;
+                        Result::<_, ()>::Ok(crate::api::split_tunnel::get_split_tunnel_status())?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__get_peer_route_pairs_impl(
+fn wire__crate__api__profile__import_profile_toml_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2387,7 +3164,7 @@ fn wire__crate__api__simple__get_peer_route_pairs_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_peer_route_pairs",
+            debug_name: "import_profile_toml",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2401,17 +3178,18 @@ fn wire__crate__api__simple__get_peer_route_pairs_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_toml = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, String>((move || {
-                    let output_ok = crate::api::simple::get_peer_route_pairs()?;
+                    let output_ok = crate::api::profile::import_profile_toml(api_toml)?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__get_running_info_impl(
+fn wire__crate__api__simple__init_app_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2419,7 +3197,7 @@ fn wire__crate__api__simple__get_running_info_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_running_info",
+            debug_name: "init_app",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2436,14 +3214,16 @@ fn wire__crate__api__simple__get_running_info_impl(
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::get_running_info())?;
+                    let output_ok = Result::<_, ()>::Ok({
+                        crate::api::simple::init_app();
+                    })?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__handle_event_impl(
+fn wire__crate__api__simple__is_easytier_running_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2451,7 +3231,7 @@ fn wire__crate__api__simple__handle_event_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "handle_event",
+            debug_name: "is_easytier_running",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2465,19 +3245,17 @@ fn wire__crate__api__simple__handle_event_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_events = <EventBusSubscriber>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, ()>((move || {
-                    let output_ok =
-                        Result::<_, ()>::Ok(crate::api::simple::handle_event(api_events))?;
+                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::is_easytier_running())?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__init_app_impl(
+fn wire__crate__api__simple__is_instance_running_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2485,7 +3263,7 @@ fn wire__crate__api__simple__init_app_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "init_app",
+            debug_name: "is_instance_running",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2499,19 +3277,20 @@ fn wire__crate__api__simple__init_app_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_instance_id = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok({
-                        crate::api::simple::init_app();
-                    })?;
+                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::is_instance_running(
+                        api_instance_id,
+                    ))?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__is_easytier_running_impl(
+fn wire__crate__api__simple__list_instances_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -2519,7 +3298,7 @@ fn wire__crate__api__simple__is_easytier_running_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "is_easytier_running",
+            debug_name: "list_instances",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -2536,7 +3315,41 @@ fn wire__crate__api__simple__is_easytier_running_impl(
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::is_easytier_running())?;
+                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::list_instances())?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__split_tunnel__remove_split_tunnel_rule_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "remove_split_tunnel_rule",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_app_path = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, String>((move || {
+                    let output_ok =
+                        crate::api::split_tunnel::remove_split_tunnel_rule(api_app_path)?;
                     Ok(output_ok)
                 })())
             }
@@ -2654,6 +3467,74 @@ fn wire__crate__api__hops__set_interface_metric_impl(
         },
     )
 }
+fn wire__crate__api__split_tunnel__set_split_tunnel_mode_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_split_tunnel_mode",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_mode =
+                <crate::api::split_tunnel::SplitTunnelMode>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, String>((move || {
+                    let output_ok = crate::api::split_tunnel::set_split_tunnel_mode(api_mode)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__split_tunnel__set_split_tunnel_rules_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_split_tunnel_rules",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_rules =
+                <Vec<crate::api::split_tunnel::SplitTunnelRule>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, String>((move || {
+                    let output_ok = crate::api::split_tunnel::set_split_tunnel_rules(api_rules)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
 fn wire__crate__api__simple__set_tun_fd_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
@@ -2676,11 +3557,51 @@ fn wire__crate__api__simple__set_tun_fd_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_instance_id = <String>::sse_decode(&mut deserializer);
             let api_fd = <i32>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, String>((move || {
-                    let output_ok = crate::api::simple::set_tun_fd(api_fd)?;
+                    let output_ok = crate::api::simple::set_tun_fd(api_instance_id, api_fd)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__event__subscribe_events_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "subscribe_events",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_sink = <StreamSink<
+                crate::api::event::KVEventRecord,
+                flutter_rust_bridge::for_generated::SseCodec,
+            >>::sse_decode(&mut deserializer);
+            let api_kinds = <Vec<crate::api::event::KVEventKind>>::sse_decode(&mut deserializer);
+            let api_instance_id = <Option<String>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, String>((move || {
+                    let output_ok =
+                        crate::api::event::subscribe_events(api_sink, api_kinds, api_instance_id)?;
                     Ok(output_ok)
                 })())
             }
@@ -2721,12 +3642,43 @@ fn wire__crate__api__astral_wfp__to_wide_string_impl(
         },
     )
 }
+fn wire__crate__api__profile__validate_profile_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "validate_profile",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_profile = <crate::api::profile::NetworkProfile>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok =
+                        Result::<_, ()>::Ok(crate::api::profile::validate_profile(api_profile))?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
 
 // Section: related_funcs
 
-flutter_rust_bridge::frb_generated_moi_arc_impl_value!(
-    flutter_rust_bridge::for_generated::RustAutoOpaqueInner<EventBusSubscriber>
-);
 flutter_rust_bridge::frb_generated_moi_arc_impl_value!(
     flutter_rust_bridge::for_generated::RustAutoOpaqueInner<FilterRule>
 );
@@ -2740,10 +3692,20 @@ flutter_rust_bridge::frb_generated_moi_arc_impl_value!(
     flutter_rust_bridge::for_generated::RustAutoOpaqueInner<IpNetwork>
 );
 flutter_rust_bridge::frb_generated_moi_arc_impl_value!(
-    flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<()>>
+    flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+        JoinHandle<Result<KVConfigApplyReport, String>>,
+    >
+);
+flutter_rust_bridge::frb_generated_moi_arc_impl_value!(
+    flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+        JoinHandle<Result<KVShutdownReport, String>>,
+    >
+);
+flutter_rust_bridge::frb_generated_moi_arc_impl_value!(
+    flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Result<String, String>>>
 );
 flutter_rust_bridge::frb_generated_moi_arc_impl_value!(
-    flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Result<(), String>>>
+    flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Vec<KVShutdownReport>>>
 );
 flutter_rust_bridge::frb_generated_moi_arc_impl_value!(
     flutter_rust_bridge::for_generated::RustAutoOpaqueInner<PeerRoutePair>
@@ -2762,61 +3724,79 @@ impl SseDecode for flutter_rust_bridge::for_generated::anyhow::Error {
     }
 }
 
-impl SseDecode for EventBusSubscriber {
+impl SseDecode for FilterRule {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut inner = <RustOpaqueMoi<
-            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<EventBusSubscriber>,
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<FilterRule>,
         >>::sse_decode(deserializer);
         return flutter_rust_bridge::for_generated::rust_auto_opaque_decode_owned(inner);
     }
 }
 
-impl SseDecode for FilterRule {
+impl SseDecode for IpAddr {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut inner = <RustOpaqueMoi<
-            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<FilterRule>,
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<IpAddr>,
         >>::sse_decode(deserializer);
         return flutter_rust_bridge::for_generated::rust_auto_opaque_decode_owned(inner);
     }
 }
 
-impl SseDecode for IpAddr {
+impl SseDecode for IpNetwork {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut inner = <RustOpaqueMoi<
-            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<IpAddr>,
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<IpNetwork>,
         >>::sse_decode(deserializer);
         return flutter_rust_bridge::for_generated::rust_auto_opaque_decode_owned(inner);
     }
 }
 
-impl SseDecode for IpNetwork {
+impl SseDecode for JoinHandle<Result<KVConfigApplyReport, String>> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut inner = <RustOpaqueMoi<
-            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<IpNetwork>,
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<KVConfigApplyReport, String>>,
+            >,
+        >>::sse_decode(deserializer);
+        return flutter_rust_bridge::for_generated::rust_auto_opaque_decode_owned(inner);
+    }
+}
+
+impl SseDecode for JoinHandle<Result<KVShutdownReport, String>> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <RustOpaqueMoi<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<KVShutdownReport, String>>,
+            >,
         >>::sse_decode(deserializer);
         return flutter_rust_bridge::for_generated::rust_auto_opaque_decode_owned(inner);
     }
 }
 
-impl SseDecode for JoinHandle<()> {
+impl SseDecode for JoinHandle<Result<String, String>> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut inner = <RustOpaqueMoi<
-            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<()>>,
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<String, String>>,
+            >,
         >>::sse_decode(deserializer);
         return flutter_rust_bridge::for_generated::rust_auto_opaque_decode_owned(inner);
     }
 }
 
-impl SseDecode for JoinHandle<Result<(), String>> {
+impl SseDecode for JoinHandle<Vec<KVShutdownReport>> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut inner = <RustOpaqueMoi<
-            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Result<(), String>>>,
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Vec<KVShutdownReport>>,
+            >,
         >>::sse_decode(deserializer);
         return flutter_rust_bridge::for_generated::rust_auto_opaque_decode_owned(inner);
     }
@@ -2843,7 +3823,7 @@ impl SseDecode for WfpController {
 }
 
 impl SseDecode
-    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<EventBusSubscriber>>
+    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<FilterRule>>
 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -2852,9 +3832,7 @@ impl SseDecode
     }
 }
 
-impl SseDecode
-    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<FilterRule>>
-{
+impl SseDecode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<GUID>> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut inner = <usize>::sse_decode(deserializer);
@@ -2862,7 +3840,7 @@ impl SseDecode
     }
 }
 
-impl SseDecode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<GUID>> {
+impl SseDecode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<IpAddr>> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut inner = <usize>::sse_decode(deserializer);
@@ -2870,7 +3848,9 @@ impl SseDecode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpa
     }
 }
 
-impl SseDecode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<IpAddr>> {
+impl SseDecode
+    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<IpNetwork>>
+{
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut inner = <usize>::sse_decode(deserializer);
@@ -2879,7 +3859,25 @@ impl SseDecode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpa
 }
 
 impl SseDecode
-    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<IpNetwork>>
+    for RustOpaqueMoi<
+        flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+            JoinHandle<Result<KVConfigApplyReport, String>>,
+        >,
+    >
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <usize>::sse_decode(deserializer);
+        return decode_rust_opaque_moi(inner);
+    }
+}
+
+impl SseDecode
+    for RustOpaqueMoi<
+        flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+            JoinHandle<Result<KVShutdownReport, String>>,
+        >,
+    >
 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -2889,7 +3887,9 @@ impl SseDecode
 }
 
 impl SseDecode
-    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<()>>>
+    for RustOpaqueMoi<
+        flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Result<String, String>>>,
+    >
 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -2900,7 +3900,7 @@ impl SseDecode
 
 impl SseDecode
     for RustOpaqueMoi<
-        flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Result<(), String>>>,
+        flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Vec<KVShutdownReport>>>,
     >
 {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -2930,6 +3930,16 @@ impl SseDecode
     }
 }
 
+impl SseDecode
+    for StreamSink<crate::api::event::KVEventRecord, flutter_rust_bridge::for_generated::SseCodec>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <String>::sse_decode(deserializer);
+        return StreamSink::deserialize(inner);
+    }
+}
+
 impl SseDecode for String {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -3012,6 +4022,11 @@ impl SseDecode for crate::api::simple::FlagsC {
         let mut var_enableQuicProxy = <bool>::sse_decode(deserializer);
         let mut var_disableQuicInput = <bool>::sse_decode(deserializer);
         let mut var_disableSymHolePunching = <bool>::sse_decode(deserializer);
+        let mut var_enableRelayForeignNetworkKcp = <bool>::sse_decode(deserializer);
+        let mut var_foreignRelayBpsLimit = <u64>::sse_decode(deserializer);
+        let mut var_multiThreadCount = <u32>::sse_decode(deserializer);
+        let mut var_encryptionAlgorithm = <String>::sse_decode(deserializer);
+        let mut var_enableMultipathBonding = <bool>::sse_decode(deserializer);
         return crate::api::simple::FlagsC {
             default_protocol: var_defaultProtocol,
             dev_name: var_devName,
@@ -3038,6 +4053,11 @@ impl SseDecode for crate::api::simple::FlagsC {
             enable_quic_proxy: var_enableQuicProxy,
             disable_quic_input: var_disableQuicInput,
             disable_sym_hole_punching: var_disableSymHolePunching,
+            enable_relay_foreign_network_kcp: var_enableRelayForeignNetworkKcp,
+            foreign_relay_bps_limit: var_foreignRelayBpsLimit,
+            multi_thread_count: var_multiThreadCount,
+            encryption_algorithm: var_encryptionAlgorithm,
+            enable_multipath_bonding: var_enableMultipathBonding,
         };
     }
 }
@@ -3063,6 +4083,268 @@ impl SseDecode for i32 {
     }
 }
 
+impl SseDecode for i64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_i64::<NativeEndian>().unwrap()
+    }
+}
+
+impl SseDecode for crate::api::event::KVEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut tag_ = <i32>::sse_decode(deserializer);
+        match tag_ {
+            0 => {
+                let mut var_devName = <String>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::TunDeviceReady {
+                    dev_name: var_devName,
+                };
+            }
+            1 => {
+                let mut var_error = <String>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::TunDeviceError { error: var_error };
+            }
+            2 => {
+                let mut var_peerId = <u32>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::PeerAdded {
+                    peer_id: var_peerId,
+                };
+            }
+            3 => {
+                let mut var_peerId = <u32>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::PeerRemoved {
+                    peer_id: var_peerId,
+                };
+            }
+            4 => {
+                let mut var_conn = <crate::api::event::KVPeerConn>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::PeerConnAdded { conn: var_conn };
+            }
+            5 => {
+                let mut var_conn = <crate::api::event::KVPeerConn>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::PeerConnRemoved { conn: var_conn };
+            }
+            6 => {
+                let mut var_url = <String>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::ListenerAdded { url: var_url };
+            }
+            7 => {
+                let mut var_url = <String>::sse_decode(deserializer);
+                let mut var_error = <String>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::ListenerAddFailed {
+                    url: var_url,
+                    error: var_error,
+                };
+            }
+            8 => {
+                let mut var_url = <String>::sse_decode(deserializer);
+                let mut var_error = <String>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::ListenerAcceptFailed {
+                    url: var_url,
+                    error: var_error,
+                };
+            }
+            9 => {
+                let mut var_localUrl = <String>::sse_decode(deserializer);
+                let mut var_remoteUrl = <String>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::ConnectionAccepted {
+                    local_url: var_localUrl,
+                    remote_url: var_remoteUrl,
+                };
+            }
+            10 => {
+                let mut var_localUrl = <String>::sse_decode(deserializer);
+                let mut var_remoteUrl = <String>::sse_decode(deserializer);
+                let mut var_error = <String>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::ConnectionError {
+                    local_url: var_localUrl,
+                    remote_url: var_remoteUrl,
+                    error: var_error,
+                };
+            }
+            11 => {
+                let mut var_url = <String>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::Connecting { url: var_url };
+            }
+            12 => {
+                let mut var_dst = <String>::sse_decode(deserializer);
+                let mut var_ipVersion = <String>::sse_decode(deserializer);
+                let mut var_error = <String>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::ConnectError {
+                    dst: var_dst,
+                    ip_version: var_ipVersion,
+                    error: var_error,
+                };
+            }
+            13 => {
+                let mut var_portal = <String>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::VpnPortalStarted { portal: var_portal };
+            }
+            14 => {
+                let mut var_portal = <String>::sse_decode(deserializer);
+                let mut var_clientAddr = <String>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::VpnPortalClientConnected {
+                    portal: var_portal,
+                    client_addr: var_clientAddr,
+                };
+            }
+            15 => {
+                let mut var_portal = <String>::sse_decode(deserializer);
+                let mut var_clientAddr = <String>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::VpnPortalClientDisconnected {
+                    portal: var_portal,
+                    client_addr: var_clientAddr,
+                };
+            }
+            16 => {
+                let mut var_oldCidr = <Option<String>>::sse_decode(deserializer);
+                let mut var_newCidr = <Option<String>>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::DhcpIpv4Changed {
+                    old_cidr: var_oldCidr,
+                    new_cidr: var_newCidr,
+                };
+            }
+            17 => {
+                let mut var_cidr = <Option<String>>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::DhcpIpv4Conflicted { cidr: var_cidr };
+            }
+            18 => {
+                let mut var_bindAddr = <String>::sse_decode(deserializer);
+                let mut var_dstAddr = <String>::sse_decode(deserializer);
+                let mut var_proto = <String>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::PortForwardAdded {
+                    bind_addr: var_bindAddr,
+                    dst_addr: var_dstAddr,
+                    proto: var_proto,
+                };
+            }
+            19 => {
+                let mut var_missed = <u64>::sse_decode(deserializer);
+                return crate::api::event::KVEvent::Lagged { missed: var_missed };
+            }
+            20 => {
+                return crate::api::event::KVEvent::Closed;
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseDecode for crate::api::event::KVEventKind {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::api::event::KVEventKind::TunDeviceReady,
+            1 => crate::api::event::KVEventKind::TunDeviceError,
+            2 => crate::api::event::KVEventKind::PeerAdded,
+            3 => crate::api::event::KVEventKind::PeerRemoved,
+            4 => crate::api::event::KVEventKind::PeerConnAdded,
+            5 => crate::api::event::KVEventKind::PeerConnRemoved,
+            6 => crate::api::event::KVEventKind::ListenerAdded,
+            7 => crate::api::event::KVEventKind::ListenerAddFailed,
+            8 => crate::api::event::KVEventKind::ListenerAcceptFailed,
+            9 => crate::api::event::KVEventKind::ConnectionAccepted,
+            10 => crate::api::event::KVEventKind::ConnectionError,
+            11 => crate::api::event::KVEventKind::Connecting,
+            12 => crate::api::event::KVEventKind::ConnectError,
+            13 => crate::api::event::KVEventKind::VpnPortalStarted,
+            14 => crate::api::event::KVEventKind::VpnPortalClientConnected,
+            15 => crate::api::event::KVEventKind::VpnPortalClientDisconnected,
+            16 => crate::api::event::KVEventKind::DhcpIpv4Changed,
+            17 => crate::api::event::KVEventKind::DhcpIpv4Conflicted,
+            18 => crate::api::event::KVEventKind::PortForwardAdded,
+            19 => crate::api::event::KVEventKind::Lagged,
+            20 => crate::api::event::KVEventKind::Closed,
+            _ => unreachable!("Invalid variant for KVEventKind: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for crate::api::event::KVEventRecord {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_instanceId = <String>::sse_decode(deserializer);
+        let mut var_timestampMs = <i64>::sse_decode(deserializer);
+        let mut var_event = <crate::api::event::KVEvent>::sse_decode(deserializer);
+        return crate::api::event::KVEventRecord {
+            instance_id: var_instanceId,
+            timestamp_ms: var_timestampMs,
+            event: var_event,
+        };
+    }
+}
+
+impl SseDecode for crate::api::simple::KVExitFlow {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_protocol = <String>::sse_decode(deserializer);
+        let mut var_src = <String>::sse_decode(deserializer);
+        let mut var_dst = <String>::sse_decode(deserializer);
+        let mut var_exitNode = <String>::sse_decode(deserializer);
+        let mut var_idleMs = <u64>::sse_decode(deserializer);
+        return crate::api::simple::KVExitFlow {
+            protocol: var_protocol,
+            src: var_src,
+            dst: var_dst,
+            exit_node: var_exitNode,
+            idle_ms: var_idleMs,
+        };
+    }
+}
+
+impl SseDecode for crate::api::simple::KVExitFlowInfo {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_exitNodes =
+            <Vec<crate::api::simple::KVExitNodeStatus>>::sse_decode(deserializer);
+        let mut var_flows = <Vec<crate::api::simple::KVExitFlow>>::sse_decode(deserializer);
+        let mut var_totalFlows = <u64>::sse_decode(deserializer);
+        return crate::api::simple::KVExitFlowInfo {
+            exit_nodes: var_exitNodes,
+            flows: var_flows,
+            total_flows: var_totalFlows,
+        };
+    }
+}
+
+impl SseDecode for crate::api::simple::KVExitNodeStatus {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_exitNode = <String>::sse_decode(deserializer);
+        let mut var_reachable = <bool>::sse_decode(deserializer);
+        let mut var_healthy = <bool>::sse_decode(deserializer);
+        let mut var_rttMs = <Option<f64>>::sse_decode(deserializer);
+        let mut var_consecutiveFailures = <u32>::sse_decode(deserializer);
+        return crate::api::simple::KVExitNodeStatus {
+            exit_node: var_exitNode,
+            reachable: var_reachable,
+            healthy: var_healthy,
+            rtt_ms: var_rttMs,
+            consecutive_failures: var_consecutiveFailures,
+        };
+    }
+}
+
+impl SseDecode for crate::api::simple::KVInstanceInfo {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_instanceId = <String>::sse_decode(deserializer);
+        let mut var_networkName = <String>::sse_decode(deserializer);
+        let mut var_running = <bool>::sse_decode(deserializer);
+        let mut var_errorMsg = <Option<String>>::sse_decode(deserializer);
+        return crate::api::simple::KVInstanceInfo {
+            instance_id: var_instanceId,
+            network_name: var_networkName,
+            running: var_running,
+            error_msg: var_errorMsg,
+        };
+    }
+}
+
 impl SseDecode for crate::api::simple::KVNetworkStatus {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -3128,6 +4410,48 @@ impl SseDecode for crate::api::simple::KVNodeInfo {
     }
 }
 
+impl SseDecode for crate::api::event::KVPeerConn {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_connId = <String>::sse_decode(deserializer);
+        let mut var_myPeerId = <u32>::sse_decode(deserializer);
+        let mut var_peerId = <u32>::sse_decode(deserializer);
+        let mut var_tunnelType = <String>::sse_decode(deserializer);
+        let mut var_localAddr = <String>::sse_decode(deserializer);
+        let mut var_remoteAddr = <String>::sse_decode(deserializer);
+        let mut var_isClient = <bool>::sse_decode(deserializer);
+        let mut var_networkName = <String>::sse_decode(deserializer);
+        return crate::api::event::KVPeerConn {
+            conn_id: var_connId,
+            my_peer_id: var_myPeerId,
+            peer_id: var_peerId,
+            tunnel_type: var_tunnelType,
+            local_addr: var_localAddr,
+            remote_addr: var_remoteAddr,
+            is_client: var_isClient,
+            network_name: var_networkName,
+        };
+    }
+}
+
+impl SseDecode for crate::api::split_tunnel::KVSplitTunnelStatus {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_active = <bool>::sse_decode(deserializer);
+        let mut var_instanceId = <String>::sse_decode(deserializer);
+        let mut var_devName = <String>::sse_decode(deserializer);
+        let mut var_mode = <crate::api::split_tunnel::SplitTunnelMode>::sse_decode(deserializer);
+        let mut var_appCount = <u32>::sse_decode(deserializer);
+        return crate::api::split_tunnel::KVSplitTunnelStatus {
+            active: var_active,
+            instance_id: var_instanceId,
+            dev_name: var_devName,
+            mode: var_mode,
+            app_count: var_appCount,
+        };
+    }
+}
+
 impl SseDecode for Vec<FilterRule> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -3176,6 +4500,58 @@ impl SseDecode for Vec<crate::api::simple::Forward> {
     }
 }
 
+impl SseDecode for Vec<crate::api::event::KVEventKind> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::api::event::KVEventKind>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::api::simple::KVExitFlow> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::api::simple::KVExitFlow>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::api::simple::KVExitNodeStatus> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::api::simple::KVExitNodeStatus>::sse_decode(
+                deserializer,
+            ));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::api::simple::KVInstanceInfo> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::api::simple::KVInstanceInfo>::sse_decode(
+                deserializer,
+            ));
+        }
+        return ans_;
+    }
+}
+
 impl SseDecode for Vec<crate::api::simple::KVNodeConnectionStats> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -3250,6 +4626,48 @@ impl SseDecode for Vec<u8> {
     }
 }
 
+impl SseDecode for Vec<crate::api::profile::ProfileChange> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::api::profile::ProfileChange>::sse_decode(
+                deserializer,
+            ));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::api::profile::ProfileFieldError> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::api::profile::ProfileFieldError>::sse_decode(
+                deserializer,
+            ));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::api::profile::ProfileProxyNetwork> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::api::profile::ProfileProxyNetwork>::sse_decode(
+                deserializer,
+            ));
+        }
+        return ans_;
+    }
+}
+
 impl SseDecode for Vec<(String, u32)> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -3262,6 +4680,80 @@ impl SseDecode for Vec<(String, u32)> {
     }
 }
 
+impl SseDecode for Vec<crate::api::split_tunnel::SplitTunnelRule> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::api::split_tunnel::SplitTunnelRule>::sse_decode(
+                deserializer,
+            ));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for crate::api::profile::NetworkProfile {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_instanceId = <Option<String>>::sse_decode(deserializer);
+        let mut var_instanceName = <String>::sse_decode(deserializer);
+        let mut var_hostname = <Option<String>>::sse_decode(deserializer);
+        let mut var_networkName = <String>::sse_decode(deserializer);
+        let mut var_networkSecret = <String>::sse_decode(deserializer);
+        let mut var_dhcp = <bool>::sse_decode(deserializer);
+        let mut var_ipv4 = <Option<String>>::sse_decode(deserializer);
+        let mut var_ipv6 = <Option<String>>::sse_decode(deserializer);
+        let mut var_peers = <Vec<String>>::sse_decode(deserializer);
+        let mut var_listeners = <Vec<String>>::sse_decode(deserializer);
+        let mut var_mappedListeners = <Vec<String>>::sse_decode(deserializer);
+        let mut var_proxyNetworks =
+            <Vec<crate::api::profile::ProfileProxyNetwork>>::sse_decode(deserializer);
+        let mut var_exitNodes = <Vec<String>>::sse_decode(deserializer);
+        let mut var_routes = <Option<Vec<String>>>::sse_decode(deserializer);
+        let mut var_socks5Portal = <Option<String>>::sse_decode(deserializer);
+        let mut var_portForwards = <Vec<crate::api::simple::Forward>>::sse_decode(deserializer);
+        let mut var_vpnPortal =
+            <Option<crate::api::profile::ProfileVpnPortal>>::sse_decode(deserializer);
+        let mut var_rpcPortal = <Option<String>>::sse_decode(deserializer);
+        let mut var_rpcPortalWhitelist = <Option<Vec<String>>>::sse_decode(deserializer);
+        let mut var_stunServers = <Option<Vec<String>>>::sse_decode(deserializer);
+        let mut var_stunServersV6 = <Option<Vec<String>>>::sse_decode(deserializer);
+        let mut var_tcpWhitelist = <Vec<String>>::sse_decode(deserializer);
+        let mut var_udpWhitelist = <Vec<String>>::sse_decode(deserializer);
+        let mut var_aclJson = <Option<String>>::sse_decode(deserializer);
+        let mut var_flags = <crate::api::simple::FlagsC>::sse_decode(deserializer);
+        return crate::api::profile::NetworkProfile {
+            instance_id: var_instanceId,
+            instance_name: var_instanceName,
+            hostname: var_hostname,
+            network_name: var_networkName,
+            network_secret: var_networkSecret,
+            dhcp: var_dhcp,
+            ipv4: var_ipv4,
+            ipv6: var_ipv6,
+            peers: var_peers,
+            listeners: var_listeners,
+            mapped_listeners: var_mappedListeners,
+            proxy_networks: var_proxyNetworks,
+            exit_nodes: var_exitNodes,
+            routes: var_routes,
+            socks5_portal: var_socks5Portal,
+            port_forwards: var_portForwards,
+            vpn_portal: var_vpnPortal,
+            rpc_portal: var_rpcPortal,
+            rpc_portal_whitelist: var_rpcPortalWhitelist,
+            stun_servers: var_stunServers,
+            stun_servers_v6: var_stunServersV6,
+            tcp_whitelist: var_tcpWhitelist,
+            udp_whitelist: var_udpWhitelist,
+            acl_json: var_aclJson,
+            flags: var_flags,
+        };
+    }
+}
+
 impl SseDecode for crate::api::simple::NodeHopStats {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -3289,6 +4781,30 @@ impl SseDecode for Option<String> {
     }
 }
 
+impl SseDecode for Option<f64> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<f64>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for Option<crate::api::profile::ProfileVpnPortal> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<crate::api::profile::ProfileVpnPortal>::sse_decode(
+                deserializer,
+            ));
+        } else {
+            return None;
+        }
+    }
+}
+
 impl SseDecode for Option<crate::api::astral_wfp::Protocol> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -3314,22 +4830,83 @@ impl SseDecode for Option<(u16, u16)> {
 impl SseDecode for Option<u16> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        if (<bool>::sse_decode(deserializer)) {
-            return Some(<u16>::sse_decode(deserializer));
-        } else {
-            return None;
-        }
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<u16>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for Option<u32> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<u32>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for Option<Vec<String>> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<Vec<String>>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for crate::api::profile::ProfileChange {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_field = <String>::sse_decode(deserializer);
+        let mut var_oldValue = <Option<String>>::sse_decode(deserializer);
+        let mut var_newValue = <Option<String>>::sse_decode(deserializer);
+        return crate::api::profile::ProfileChange {
+            field: var_field,
+            old_value: var_oldValue,
+            new_value: var_newValue,
+        };
+    }
+}
+
+impl SseDecode for crate::api::profile::ProfileFieldError {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_field = <String>::sse_decode(deserializer);
+        let mut var_message = <String>::sse_decode(deserializer);
+        return crate::api::profile::ProfileFieldError {
+            field: var_field,
+            message: var_message,
+        };
+    }
+}
+
+impl SseDecode for crate::api::profile::ProfileProxyNetwork {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_cidr = <String>::sse_decode(deserializer);
+        let mut var_mappedCidr = <Option<String>>::sse_decode(deserializer);
+        return crate::api::profile::ProfileProxyNetwork {
+            cidr: var_cidr,
+            mapped_cidr: var_mappedCidr,
+        };
     }
 }
 
-impl SseDecode for Option<u32> {
+impl SseDecode for crate::api::profile::ProfileVpnPortal {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        if (<bool>::sse_decode(deserializer)) {
-            return Some(<u32>::sse_decode(deserializer));
-        } else {
-            return None;
-        }
+        let mut var_clientCidr = <String>::sse_decode(deserializer);
+        let mut var_wireguardListen = <String>::sse_decode(deserializer);
+        return crate::api::profile::ProfileVpnPortal {
+            client_cidr: var_clientCidr,
+            wireguard_listen: var_wireguardListen,
+        };
     }
 }
 
@@ -3363,6 +4940,30 @@ impl SseDecode for (u16, u16) {
     }
 }
 
+impl SseDecode for crate::api::split_tunnel::SplitTunnelMode {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::api::split_tunnel::SplitTunnelMode::Include,
+            1 => crate::api::split_tunnel::SplitTunnelMode::Exclude,
+            _ => unreachable!("Invalid variant for SplitTunnelMode: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for crate::api::split_tunnel::SplitTunnelRule {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_appPath = <String>::sse_decode(deserializer);
+        let mut var_enabled = <bool>::sse_decode(deserializer);
+        return crate::api::split_tunnel::SplitTunnelRule {
+            app_path: var_appPath,
+            enabled: var_enabled,
+        };
+    }
+}
+
 impl SseDecode for u16 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -3436,65 +5037,169 @@ fn pde_ffi_dispatcher_primary_impl(
             rust_vec_len,
             data_len,
         ),
-        37 => wire__crate__api__astral_wfp__WfpController_cleanup_impl(
+        37 => wire__crate__api__astral_wfp__WfpController_backend_name_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        38 => wire__crate__api__astral_wfp__WfpController_cleanup_impl(
             port,
             ptr,
             rust_vec_len,
             data_len,
         ),
-        38 => wire__crate__api__astral_wfp__WfpController_delete_filters_impl(
+        39 => wire__crate__api__astral_wfp__WfpController_delete_filters_impl(
             port,
             ptr,
             rust_vec_len,
             data_len,
         ),
-        39 => wire__crate__api__astral_wfp__WfpController_get_layer_name_impl(
+        40 => wire__crate__api__astral_wfp__WfpController_delete_rule_impl(
             port,
             ptr,
             rust_vec_len,
             data_len,
         ),
-        40 => wire__crate__api__astral_wfp__WfpController_initialize_impl(
+        41 => wire__crate__api__astral_wfp__WfpController_get_layer_name_impl(
             port,
             ptr,
             rust_vec_len,
             data_len,
         ),
-        41 => {
+        42 => wire__crate__api__astral_wfp__WfpController_initialize_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        43 => wire__crate__api__astral_wfp__WfpController_list_rules_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        44 => wire__crate__api__astral_wfp__WfpController_load_rules_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        45 => {
             wire__crate__api__astral_wfp__WfpController_new_impl(port, ptr, rust_vec_len, data_len)
         }
-        42 => wire__crate__api__utils__check_sudo_impl(port, ptr, rust_vec_len, data_len),
-        43 => wire__crate__api__simple__close_server_impl(port, ptr, rust_vec_len, data_len),
-        44 => wire__crate__api__simple__create_server_impl(port, ptr, rust_vec_len, data_len),
-        45 => wire__crate__api__simple__easytier_version_impl(port, ptr, rust_vec_len, data_len),
-        46 => wire__crate__api__hops__get_all_interfaces_metrics_impl(
+        46 => wire__crate__api__astral_wfp__WfpController_save_rules_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        47 => wire__crate__api__split_tunnel__add_split_tunnel_rule_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        48 => wire__crate__api__simple__apply_profile_impl(port, ptr, rust_vec_len, data_len),
+        49 => wire__crate__api__utils__check_sudo_impl(port, ptr, rust_vec_len, data_len),
+        50 => wire__crate__api__simple__close_all_servers_impl(port, ptr, rust_vec_len, data_len),
+        51 => wire__crate__api__simple__close_server_impl(port, ptr, rust_vec_len, data_len),
+        52 => wire__crate__api__simple__create_server_impl(port, ptr, rust_vec_len, data_len),
+        53 => wire__crate__api__simple__create_server_with_profile_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        54 => wire__crate__api__profile__default_profile_impl(port, ptr, rust_vec_len, data_len),
+        55 => wire__crate__api__profile__diff_profiles_impl(port, ptr, rust_vec_len, data_len),
+        56 => wire__crate__api__split_tunnel__disable_split_tunnel_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        57 => wire__crate__api__simple__easytier_version_impl(port, ptr, rust_vec_len, data_len),
+        58 => wire__crate__api__split_tunnel__enable_split_tunnel_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        59 => {
+            wire__crate__api__profile__export_profile_toml_impl(port, ptr, rust_vec_len, data_len)
+        }
+        60 => wire__crate__api__hops__get_all_interfaces_metrics_impl(
             port,
             ptr,
             rust_vec_len,
             data_len,
         ),
-        47 => {
+        61 => wire__crate__api__simple__get_exit_flows_impl(port, ptr, rust_vec_len, data_len),
+        62 => {
             wire__crate__api__firewall__get_firewall_status_impl(port, ptr, rust_vec_len, data_len)
         }
-        48 => wire__crate__api__simple__get_ips_impl(port, ptr, rust_vec_len, data_len),
-        49 => wire__crate__api__simple__get_network_status_impl(port, ptr, rust_vec_len, data_len),
-        50 => wire__crate__api__nt__get_nt_path_impl(port, ptr, rust_vec_len, data_len),
-        51 => {
+        63 => wire__crate__api__simple__get_ips_impl(port, ptr, rust_vec_len, data_len),
+        64 => wire__crate__api__simple__get_network_status_impl(port, ptr, rust_vec_len, data_len),
+        65 => wire__crate__api__nt__get_nt_path_impl(port, ptr, rust_vec_len, data_len),
+        66 => {
             wire__crate__api__simple__get_peer_route_pairs_impl(port, ptr, rust_vec_len, data_len)
         }
-        52 => wire__crate__api__simple__get_running_info_impl(port, ptr, rust_vec_len, data_len),
-        53 => wire__crate__api__simple__handle_event_impl(port, ptr, rust_vec_len, data_len),
-        54 => wire__crate__api__simple__init_app_impl(port, ptr, rust_vec_len, data_len),
-        55 => wire__crate__api__simple__is_easytier_running_impl(port, ptr, rust_vec_len, data_len),
-        56 => {
+        67 => wire__crate__api__simple__get_running_info_impl(port, ptr, rust_vec_len, data_len),
+        68 => wire__crate__api__split_tunnel__get_split_tunnel_mode_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        69 => wire__crate__api__split_tunnel__get_split_tunnel_rules_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        70 => wire__crate__api__split_tunnel__get_split_tunnel_status_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        71 => {
+            wire__crate__api__profile__import_profile_toml_impl(port, ptr, rust_vec_len, data_len)
+        }
+        72 => wire__crate__api__simple__init_app_impl(port, ptr, rust_vec_len, data_len),
+        73 => wire__crate__api__simple__is_easytier_running_impl(port, ptr, rust_vec_len, data_len),
+        74 => wire__crate__api__simple__is_instance_running_impl(port, ptr, rust_vec_len, data_len),
+        75 => wire__crate__api__simple__list_instances_impl(port, ptr, rust_vec_len, data_len),
+        76 => wire__crate__api__split_tunnel__remove_split_tunnel_rule_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        77 => {
             wire__crate__api__simple__send_udp_to_localhost_impl(port, ptr, rust_vec_len, data_len)
         }
-        57 => {
+        78 => {
             wire__crate__api__firewall__set_firewall_status_impl(port, ptr, rust_vec_len, data_len)
         }
-        58 => wire__crate__api__hops__set_interface_metric_impl(port, ptr, rust_vec_len, data_len),
-        59 => wire__crate__api__simple__set_tun_fd_impl(port, ptr, rust_vec_len, data_len),
-        60 => wire__crate__api__astral_wfp__to_wide_string_impl(port, ptr, rust_vec_len, data_len),
+        79 => wire__crate__api__hops__set_interface_metric_impl(port, ptr, rust_vec_len, data_len),
+        80 => wire__crate__api__split_tunnel__set_split_tunnel_mode_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        81 => wire__crate__api__split_tunnel__set_split_tunnel_rules_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        82 => wire__crate__api__simple__set_tun_fd_impl(port, ptr, rust_vec_len, data_len),
+        83 => wire__crate__api__event__subscribe_events_impl(port, ptr, rust_vec_len, data_len),
+        84 => wire__crate__api__astral_wfp__to_wide_string_impl(port, ptr, rust_vec_len, data_len),
+        85 => wire__crate__api__profile__validate_profile_impl(port, ptr, rust_vec_len, data_len),
         _ => unreachable!(),
     }
 }
@@ -3673,24 +5378,6 @@ fn pde_ffi_dispatcher_sync_impl(
 
 // Section: rust2dart
 
-// Codec=Dco (DartCObject based), see doc to use other codecs
-impl flutter_rust_bridge::IntoDart for FrbWrapper<EventBusSubscriber> {
-    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
-        flutter_rust_bridge::for_generated::rust_auto_opaque_encode::<_, MoiArc<_>>(self.0)
-            .into_dart()
-    }
-}
-impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
-    for FrbWrapper<EventBusSubscriber>
-{
-}
-
-impl flutter_rust_bridge::IntoIntoDart<FrbWrapper<EventBusSubscriber>> for EventBusSubscriber {
-    fn into_into_dart(self) -> FrbWrapper<EventBusSubscriber> {
-        self.into()
-    }
-}
-
 // Codec=Dco (DartCObject based), see doc to use other codecs
 impl flutter_rust_bridge::IntoDart for FrbWrapper<FilterRule> {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
@@ -3737,36 +5424,81 @@ impl flutter_rust_bridge::IntoIntoDart<FrbWrapper<IpNetwork>> for IpNetwork {
 }
 
 // Codec=Dco (DartCObject based), see doc to use other codecs
-impl flutter_rust_bridge::IntoDart for FrbWrapper<JoinHandle<()>> {
+impl flutter_rust_bridge::IntoDart for FrbWrapper<JoinHandle<Result<KVConfigApplyReport, String>>> {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        flutter_rust_bridge::for_generated::rust_auto_opaque_encode::<_, MoiArc<_>>(self.0)
+            .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for FrbWrapper<JoinHandle<Result<KVConfigApplyReport, String>>>
+{
+}
+
+impl flutter_rust_bridge::IntoIntoDart<FrbWrapper<JoinHandle<Result<KVConfigApplyReport, String>>>>
+    for JoinHandle<Result<KVConfigApplyReport, String>>
+{
+    fn into_into_dart(self) -> FrbWrapper<JoinHandle<Result<KVConfigApplyReport, String>>> {
+        self.into()
+    }
+}
+
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for FrbWrapper<JoinHandle<Result<KVShutdownReport, String>>> {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        flutter_rust_bridge::for_generated::rust_auto_opaque_encode::<_, MoiArc<_>>(self.0)
+            .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for FrbWrapper<JoinHandle<Result<KVShutdownReport, String>>>
+{
+}
+
+impl flutter_rust_bridge::IntoIntoDart<FrbWrapper<JoinHandle<Result<KVShutdownReport, String>>>>
+    for JoinHandle<Result<KVShutdownReport, String>>
+{
+    fn into_into_dart(self) -> FrbWrapper<JoinHandle<Result<KVShutdownReport, String>>> {
+        self.into()
+    }
+}
+
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for FrbWrapper<JoinHandle<Result<String, String>>> {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
         flutter_rust_bridge::for_generated::rust_auto_opaque_encode::<_, MoiArc<_>>(self.0)
             .into_dart()
     }
 }
-impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for FrbWrapper<JoinHandle<()>> {}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for FrbWrapper<JoinHandle<Result<String, String>>>
+{
+}
 
-impl flutter_rust_bridge::IntoIntoDart<FrbWrapper<JoinHandle<()>>> for JoinHandle<()> {
-    fn into_into_dart(self) -> FrbWrapper<JoinHandle<()>> {
+impl flutter_rust_bridge::IntoIntoDart<FrbWrapper<JoinHandle<Result<String, String>>>>
+    for JoinHandle<Result<String, String>>
+{
+    fn into_into_dart(self) -> FrbWrapper<JoinHandle<Result<String, String>>> {
         self.into()
     }
 }
 
 // Codec=Dco (DartCObject based), see doc to use other codecs
-impl flutter_rust_bridge::IntoDart for FrbWrapper<JoinHandle<Result<(), String>>> {
+impl flutter_rust_bridge::IntoDart for FrbWrapper<JoinHandle<Vec<KVShutdownReport>>> {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
         flutter_rust_bridge::for_generated::rust_auto_opaque_encode::<_, MoiArc<_>>(self.0)
             .into_dart()
     }
 }
 impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
-    for FrbWrapper<JoinHandle<Result<(), String>>>
+    for FrbWrapper<JoinHandle<Vec<KVShutdownReport>>>
 {
 }
 
-impl flutter_rust_bridge::IntoIntoDart<FrbWrapper<JoinHandle<Result<(), String>>>>
-    for JoinHandle<Result<(), String>>
+impl flutter_rust_bridge::IntoIntoDart<FrbWrapper<JoinHandle<Vec<KVShutdownReport>>>>
+    for JoinHandle<Vec<KVShutdownReport>>
 {
-    fn into_into_dart(self) -> FrbWrapper<JoinHandle<Result<(), String>>> {
+    fn into_into_dart(self) -> FrbWrapper<JoinHandle<Vec<KVShutdownReport>>> {
         self.into()
     }
 }
@@ -3873,6 +5605,13 @@ impl flutter_rust_bridge::IntoDart for crate::api::simple::FlagsC {
             self.enable_quic_proxy.into_into_dart().into_dart(),
             self.disable_quic_input.into_into_dart().into_dart(),
             self.disable_sym_hole_punching.into_into_dart().into_dart(),
+            self.enable_relay_foreign_network_kcp
+                .into_into_dart()
+                .into_dart(),
+            self.foreign_relay_bps_limit.into_into_dart().into_dart(),
+            self.multi_thread_count.into_into_dart().into_dart(),
+            self.encryption_algorithm.into_into_dart().into_dart(),
+            self.enable_multipath_bonding.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
@@ -3894,11 +5633,294 @@ impl flutter_rust_bridge::IntoDart for crate::api::simple::Forward {
         .into_dart()
     }
 }
-impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::api::simple::Forward {}
-impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::Forward>
-    for crate::api::simple::Forward
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::api::simple::Forward {}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::Forward>
+    for crate::api::simple::Forward
+{
+    fn into_into_dart(self) -> crate::api::simple::Forward {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::event::KVEvent {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            crate::api::event::KVEvent::TunDeviceReady { dev_name } => {
+                [0.into_dart(), dev_name.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::event::KVEvent::TunDeviceError { error } => {
+                [1.into_dart(), error.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::event::KVEvent::PeerAdded { peer_id } => {
+                [2.into_dart(), peer_id.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::event::KVEvent::PeerRemoved { peer_id } => {
+                [3.into_dart(), peer_id.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::event::KVEvent::PeerConnAdded { conn } => {
+                [4.into_dart(), conn.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::event::KVEvent::PeerConnRemoved { conn } => {
+                [5.into_dart(), conn.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::event::KVEvent::ListenerAdded { url } => {
+                [6.into_dart(), url.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::event::KVEvent::ListenerAddFailed { url, error } => [
+                7.into_dart(),
+                url.into_into_dart().into_dart(),
+                error.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::event::KVEvent::ListenerAcceptFailed { url, error } => [
+                8.into_dart(),
+                url.into_into_dart().into_dart(),
+                error.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::event::KVEvent::ConnectionAccepted {
+                local_url,
+                remote_url,
+            } => [
+                9.into_dart(),
+                local_url.into_into_dart().into_dart(),
+                remote_url.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::event::KVEvent::ConnectionError {
+                local_url,
+                remote_url,
+                error,
+            } => [
+                10.into_dart(),
+                local_url.into_into_dart().into_dart(),
+                remote_url.into_into_dart().into_dart(),
+                error.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::event::KVEvent::Connecting { url } => {
+                [11.into_dart(), url.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::event::KVEvent::ConnectError {
+                dst,
+                ip_version,
+                error,
+            } => [
+                12.into_dart(),
+                dst.into_into_dart().into_dart(),
+                ip_version.into_into_dart().into_dart(),
+                error.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::event::KVEvent::VpnPortalStarted { portal } => {
+                [13.into_dart(), portal.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::event::KVEvent::VpnPortalClientConnected {
+                portal,
+                client_addr,
+            } => [
+                14.into_dart(),
+                portal.into_into_dart().into_dart(),
+                client_addr.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::event::KVEvent::VpnPortalClientDisconnected {
+                portal,
+                client_addr,
+            } => [
+                15.into_dart(),
+                portal.into_into_dart().into_dart(),
+                client_addr.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::event::KVEvent::DhcpIpv4Changed { old_cidr, new_cidr } => [
+                16.into_dart(),
+                old_cidr.into_into_dart().into_dart(),
+                new_cidr.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::event::KVEvent::DhcpIpv4Conflicted { cidr } => {
+                [17.into_dart(), cidr.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::event::KVEvent::PortForwardAdded {
+                bind_addr,
+                dst_addr,
+                proto,
+            } => [
+                18.into_dart(),
+                bind_addr.into_into_dart().into_dart(),
+                dst_addr.into_into_dart().into_dart(),
+                proto.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::event::KVEvent::Lagged { missed } => {
+                [19.into_dart(), missed.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::event::KVEvent::Closed => [20.into_dart()].into_dart(),
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::api::event::KVEvent {}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::event::KVEvent> for crate::api::event::KVEvent {
+    fn into_into_dart(self) -> crate::api::event::KVEvent {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::event::KVEventKind {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::TunDeviceReady => 0.into_dart(),
+            Self::TunDeviceError => 1.into_dart(),
+            Self::PeerAdded => 2.into_dart(),
+            Self::PeerRemoved => 3.into_dart(),
+            Self::PeerConnAdded => 4.into_dart(),
+            Self::PeerConnRemoved => 5.into_dart(),
+            Self::ListenerAdded => 6.into_dart(),
+            Self::ListenerAddFailed => 7.into_dart(),
+            Self::ListenerAcceptFailed => 8.into_dart(),
+            Self::ConnectionAccepted => 9.into_dart(),
+            Self::ConnectionError => 10.into_dart(),
+            Self::Connecting => 11.into_dart(),
+            Self::ConnectError => 12.into_dart(),
+            Self::VpnPortalStarted => 13.into_dart(),
+            Self::VpnPortalClientConnected => 14.into_dart(),
+            Self::VpnPortalClientDisconnected => 15.into_dart(),
+            Self::DhcpIpv4Changed => 16.into_dart(),
+            Self::DhcpIpv4Conflicted => 17.into_dart(),
+            Self::PortForwardAdded => 18.into_dart(),
+            Self::Lagged => 19.into_dart(),
+            Self::Closed => 20.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::event::KVEventKind
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::event::KVEventKind>
+    for crate::api::event::KVEventKind
+{
+    fn into_into_dart(self) -> crate::api::event::KVEventKind {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::event::KVEventRecord {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.instance_id.into_into_dart().into_dart(),
+            self.timestamp_ms.into_into_dart().into_dart(),
+            self.event.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::event::KVEventRecord
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::event::KVEventRecord>
+    for crate::api::event::KVEventRecord
+{
+    fn into_into_dart(self) -> crate::api::event::KVEventRecord {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::simple::KVExitFlow {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.protocol.into_into_dart().into_dart(),
+            self.src.into_into_dart().into_dart(),
+            self.dst.into_into_dart().into_dart(),
+            self.exit_node.into_into_dart().into_dart(),
+            self.idle_ms.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::simple::KVExitFlow
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::KVExitFlow>
+    for crate::api::simple::KVExitFlow
+{
+    fn into_into_dart(self) -> crate::api::simple::KVExitFlow {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::simple::KVExitFlowInfo {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.exit_nodes.into_into_dart().into_dart(),
+            self.flows.into_into_dart().into_dart(),
+            self.total_flows.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::simple::KVExitFlowInfo
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::KVExitFlowInfo>
+    for crate::api::simple::KVExitFlowInfo
+{
+    fn into_into_dart(self) -> crate::api::simple::KVExitFlowInfo {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::simple::KVExitNodeStatus {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.exit_node.into_into_dart().into_dart(),
+            self.reachable.into_into_dart().into_dart(),
+            self.healthy.into_into_dart().into_dart(),
+            self.rtt_ms.into_into_dart().into_dart(),
+            self.consecutive_failures.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::simple::KVExitNodeStatus
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::KVExitNodeStatus>
+    for crate::api::simple::KVExitNodeStatus
+{
+    fn into_into_dart(self) -> crate::api::simple::KVExitNodeStatus {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::simple::KVInstanceInfo {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.instance_id.into_into_dart().into_dart(),
+            self.network_name.into_into_dart().into_dart(),
+            self.running.into_into_dart().into_dart(),
+            self.error_msg.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::simple::KVInstanceInfo
 {
-    fn into_into_dart(self) -> crate::api::simple::Forward {
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::KVInstanceInfo>
+    for crate::api::simple::KVInstanceInfo
+{
+    fn into_into_dart(self) -> crate::api::simple::KVInstanceInfo {
         self
     }
 }
@@ -3980,6 +6002,98 @@ impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::KVNodeInfo>
     }
 }
 // Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::event::KVPeerConn {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.conn_id.into_into_dart().into_dart(),
+            self.my_peer_id.into_into_dart().into_dart(),
+            self.peer_id.into_into_dart().into_dart(),
+            self.tunnel_type.into_into_dart().into_dart(),
+            self.local_addr.into_into_dart().into_dart(),
+            self.remote_addr.into_into_dart().into_dart(),
+            self.is_client.into_into_dart().into_dart(),
+            self.network_name.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::api::event::KVPeerConn {}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::event::KVPeerConn>
+    for crate::api::event::KVPeerConn
+{
+    fn into_into_dart(self) -> crate::api::event::KVPeerConn {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::split_tunnel::KVSplitTunnelStatus {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.active.into_into_dart().into_dart(),
+            self.instance_id.into_into_dart().into_dart(),
+            self.dev_name.into_into_dart().into_dart(),
+            self.mode.into_into_dart().into_dart(),
+            self.app_count.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::split_tunnel::KVSplitTunnelStatus
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::split_tunnel::KVSplitTunnelStatus>
+    for crate::api::split_tunnel::KVSplitTunnelStatus
+{
+    fn into_into_dart(self) -> crate::api::split_tunnel::KVSplitTunnelStatus {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::profile::NetworkProfile {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.instance_id.into_into_dart().into_dart(),
+            self.instance_name.into_into_dart().into_dart(),
+            self.hostname.into_into_dart().into_dart(),
+            self.network_name.into_into_dart().into_dart(),
+            self.network_secret.into_into_dart().into_dart(),
+            self.dhcp.into_into_dart().into_dart(),
+            self.ipv4.into_into_dart().into_dart(),
+            self.ipv6.into_into_dart().into_dart(),
+            self.peers.into_into_dart().into_dart(),
+            self.listeners.into_into_dart().into_dart(),
+            self.mapped_listeners.into_into_dart().into_dart(),
+            self.proxy_networks.into_into_dart().into_dart(),
+            self.exit_nodes.into_into_dart().into_dart(),
+            self.routes.into_into_dart().into_dart(),
+            self.socks5_portal.into_into_dart().into_dart(),
+            self.port_forwards.into_into_dart().into_dart(),
+            self.vpn_portal.into_into_dart().into_dart(),
+            self.rpc_portal.into_into_dart().into_dart(),
+            self.rpc_portal_whitelist.into_into_dart().into_dart(),
+            self.stun_servers.into_into_dart().into_dart(),
+            self.stun_servers_v6.into_into_dart().into_dart(),
+            self.tcp_whitelist.into_into_dart().into_dart(),
+            self.udp_whitelist.into_into_dart().into_dart(),
+            self.acl_json.into_into_dart().into_dart(),
+            self.flags.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::profile::NetworkProfile
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::profile::NetworkProfile>
+    for crate::api::profile::NetworkProfile
+{
+    fn into_into_dart(self) -> crate::api::profile::NetworkProfile {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
 impl flutter_rust_bridge::IntoDart for crate::api::simple::NodeHopStats {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
         [
@@ -4003,6 +6117,91 @@ impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::NodeHopStats>
     }
 }
 // Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::profile::ProfileChange {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.field.into_into_dart().into_dart(),
+            self.old_value.into_into_dart().into_dart(),
+            self.new_value.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::profile::ProfileChange
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::profile::ProfileChange>
+    for crate::api::profile::ProfileChange
+{
+    fn into_into_dart(self) -> crate::api::profile::ProfileChange {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::profile::ProfileFieldError {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.field.into_into_dart().into_dart(),
+            self.message.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::profile::ProfileFieldError
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::profile::ProfileFieldError>
+    for crate::api::profile::ProfileFieldError
+{
+    fn into_into_dart(self) -> crate::api::profile::ProfileFieldError {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::profile::ProfileProxyNetwork {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.cidr.into_into_dart().into_dart(),
+            self.mapped_cidr.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::profile::ProfileProxyNetwork
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::profile::ProfileProxyNetwork>
+    for crate::api::profile::ProfileProxyNetwork
+{
+    fn into_into_dart(self) -> crate::api::profile::ProfileProxyNetwork {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::profile::ProfileVpnPortal {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.client_cidr.into_into_dart().into_dart(),
+            self.wireguard_listen.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::profile::ProfileVpnPortal
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::profile::ProfileVpnPortal>
+    for crate::api::profile::ProfileVpnPortal
+{
+    fn into_into_dart(self) -> crate::api::profile::ProfileVpnPortal {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
 impl flutter_rust_bridge::IntoDart for crate::api::astral_wfp::Protocol {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
         match self {
@@ -4023,18 +6222,53 @@ impl flutter_rust_bridge::IntoIntoDart<crate::api::astral_wfp::Protocol>
         self
     }
 }
-
-impl SseEncode for flutter_rust_bridge::for_generated::anyhow::Error {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <String>::sse_encode(format!("{:?}", self), serializer);
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::split_tunnel::SplitTunnelMode {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::Include => 0.into_dart(),
+            Self::Exclude => 1.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::split_tunnel::SplitTunnelMode
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::split_tunnel::SplitTunnelMode>
+    for crate::api::split_tunnel::SplitTunnelMode
+{
+    fn into_into_dart(self) -> crate::api::split_tunnel::SplitTunnelMode {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::split_tunnel::SplitTunnelRule {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.app_path.into_into_dart().into_dart(),
+            self.enabled.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::split_tunnel::SplitTunnelRule
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::split_tunnel::SplitTunnelRule>
+    for crate::api::split_tunnel::SplitTunnelRule
+{
+    fn into_into_dart(self) -> crate::api::split_tunnel::SplitTunnelRule {
+        self
     }
 }
 
-impl SseEncode for EventBusSubscriber {
+impl SseEncode for flutter_rust_bridge::for_generated::anyhow::Error {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<EventBusSubscriber>>>::sse_encode(flutter_rust_bridge::for_generated::rust_auto_opaque_encode::<_, MoiArc<_>>(self), serializer);
+        <String>::sse_encode(format!("{:?}", self), serializer);
     }
 }
 
@@ -4059,18 +6293,55 @@ impl SseEncode for IpNetwork {
     }
 }
 
-impl SseEncode for JoinHandle<()> {
+impl SseEncode for JoinHandle<Result<KVConfigApplyReport, String>> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <RustOpaqueMoi<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<KVConfigApplyReport, String>>,
+            >,
+        >>::sse_encode(
+            flutter_rust_bridge::for_generated::rust_auto_opaque_encode::<_, MoiArc<_>>(self),
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for JoinHandle<Result<KVShutdownReport, String>> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <RustOpaqueMoi<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<KVShutdownReport, String>>,
+            >,
+        >>::sse_encode(
+            flutter_rust_bridge::for_generated::rust_auto_opaque_encode::<_, MoiArc<_>>(self),
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for JoinHandle<Result<String, String>> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner< JoinHandle < () >>>>::sse_encode(flutter_rust_bridge::for_generated::rust_auto_opaque_encode::<_, MoiArc<_>>(self), serializer);
+        <RustOpaqueMoi<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<String, String>>,
+            >,
+        >>::sse_encode(
+            flutter_rust_bridge::for_generated::rust_auto_opaque_encode::<_, MoiArc<_>>(self),
+            serializer,
+        );
     }
 }
 
-impl SseEncode for JoinHandle<Result<(), String>> {
+impl SseEncode for JoinHandle<Vec<KVShutdownReport>> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
         <RustOpaqueMoi<
-            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Result<(), String>>>,
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Vec<KVShutdownReport>>,
+            >,
         >>::sse_encode(
             flutter_rust_bridge::for_generated::rust_auto_opaque_encode::<_, MoiArc<_>>(self),
             serializer,
@@ -4093,7 +6364,7 @@ impl SseEncode for WfpController {
 }
 
 impl SseEncode
-    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<EventBusSubscriber>>
+    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<FilterRule>>
 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -4103,9 +6374,7 @@ impl SseEncode
     }
 }
 
-impl SseEncode
-    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<FilterRule>>
-{
+impl SseEncode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<GUID>> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
         let (ptr, size) = self.sse_encode_raw();
@@ -4114,7 +6383,7 @@ impl SseEncode
     }
 }
 
-impl SseEncode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<GUID>> {
+impl SseEncode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<IpAddr>> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
         let (ptr, size) = self.sse_encode_raw();
@@ -4123,7 +6392,9 @@ impl SseEncode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpa
     }
 }
 
-impl SseEncode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<IpAddr>> {
+impl SseEncode
+    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<IpNetwork>>
+{
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
         let (ptr, size) = self.sse_encode_raw();
@@ -4133,7 +6404,26 @@ impl SseEncode for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpa
 }
 
 impl SseEncode
-    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<IpNetwork>>
+    for RustOpaqueMoi<
+        flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+            JoinHandle<Result<KVConfigApplyReport, String>>,
+        >,
+    >
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        let (ptr, size) = self.sse_encode_raw();
+        <usize>::sse_encode(ptr, serializer);
+        <i32>::sse_encode(size, serializer);
+    }
+}
+
+impl SseEncode
+    for RustOpaqueMoi<
+        flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+            JoinHandle<Result<KVShutdownReport, String>>,
+        >,
+    >
 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -4144,7 +6434,9 @@ impl SseEncode
 }
 
 impl SseEncode
-    for RustOpaqueMoi<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<()>>>
+    for RustOpaqueMoi<
+        flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Result<String, String>>>,
+    >
 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -4156,7 +6448,7 @@ impl SseEncode
 
 impl SseEncode
     for RustOpaqueMoi<
-        flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Result<(), String>>>,
+        flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Vec<KVShutdownReport>>>,
     >
 {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -4189,6 +6481,15 @@ impl SseEncode
     }
 }
 
+impl SseEncode
+    for StreamSink<crate::api::event::KVEventRecord, flutter_rust_bridge::for_generated::SseCodec>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        unimplemented!("")
+    }
+}
+
 impl SseEncode for String {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -4278,22 +6579,246 @@ impl SseEncode for crate::api::simple::FlagsC {
         <bool>::sse_encode(self.enable_quic_proxy, serializer);
         <bool>::sse_encode(self.disable_quic_input, serializer);
         <bool>::sse_encode(self.disable_sym_hole_punching, serializer);
+        <bool>::sse_encode(self.enable_relay_foreign_network_kcp, serializer);
+        <u64>::sse_encode(self.foreign_relay_bps_limit, serializer);
+        <u32>::sse_encode(self.multi_thread_count, serializer);
+        <String>::sse_encode(self.encryption_algorithm, serializer);
+        <bool>::sse_encode(self.enable_multipath_bonding, serializer);
+    }
+}
+
+impl SseEncode for crate::api::simple::Forward {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.bind_addr, serializer);
+        <String>::sse_encode(self.dst_addr, serializer);
+        <String>::sse_encode(self.proto, serializer);
+    }
+}
+
+impl SseEncode for i32 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_i32::<NativeEndian>(self).unwrap();
+    }
+}
+
+impl SseEncode for i64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_i64::<NativeEndian>(self).unwrap();
+    }
+}
+
+impl SseEncode for crate::api::event::KVEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        match self {
+            crate::api::event::KVEvent::TunDeviceReady { dev_name } => {
+                <i32>::sse_encode(0, serializer);
+                <String>::sse_encode(dev_name, serializer);
+            }
+            crate::api::event::KVEvent::TunDeviceError { error } => {
+                <i32>::sse_encode(1, serializer);
+                <String>::sse_encode(error, serializer);
+            }
+            crate::api::event::KVEvent::PeerAdded { peer_id } => {
+                <i32>::sse_encode(2, serializer);
+                <u32>::sse_encode(peer_id, serializer);
+            }
+            crate::api::event::KVEvent::PeerRemoved { peer_id } => {
+                <i32>::sse_encode(3, serializer);
+                <u32>::sse_encode(peer_id, serializer);
+            }
+            crate::api::event::KVEvent::PeerConnAdded { conn } => {
+                <i32>::sse_encode(4, serializer);
+                <crate::api::event::KVPeerConn>::sse_encode(conn, serializer);
+            }
+            crate::api::event::KVEvent::PeerConnRemoved { conn } => {
+                <i32>::sse_encode(5, serializer);
+                <crate::api::event::KVPeerConn>::sse_encode(conn, serializer);
+            }
+            crate::api::event::KVEvent::ListenerAdded { url } => {
+                <i32>::sse_encode(6, serializer);
+                <String>::sse_encode(url, serializer);
+            }
+            crate::api::event::KVEvent::ListenerAddFailed { url, error } => {
+                <i32>::sse_encode(7, serializer);
+                <String>::sse_encode(url, serializer);
+                <String>::sse_encode(error, serializer);
+            }
+            crate::api::event::KVEvent::ListenerAcceptFailed { url, error } => {
+                <i32>::sse_encode(8, serializer);
+                <String>::sse_encode(url, serializer);
+                <String>::sse_encode(error, serializer);
+            }
+            crate::api::event::KVEvent::ConnectionAccepted {
+                local_url,
+                remote_url,
+            } => {
+                <i32>::sse_encode(9, serializer);
+                <String>::sse_encode(local_url, serializer);
+                <String>::sse_encode(remote_url, serializer);
+            }
+            crate::api::event::KVEvent::ConnectionError {
+                local_url,
+                remote_url,
+                error,
+            } => {
+                <i32>::sse_encode(10, serializer);
+                <String>::sse_encode(local_url, serializer);
+                <String>::sse_encode(remote_url, serializer);
+                <String>::sse_encode(error, serializer);
+            }
+            crate::api::event::KVEvent::Connecting { url } => {
+                <i32>::sse_encode(11, serializer);
+                <String>::sse_encode(url, serializer);
+            }
+            crate::api::event::KVEvent::ConnectError {
+                dst,
+                ip_version,
+                error,
+            } => {
+                <i32>::sse_encode(12, serializer);
+                <String>::sse_encode(dst, serializer);
+                <String>::sse_encode(ip_version, serializer);
+                <String>::sse_encode(error, serializer);
+            }
+            crate::api::event::KVEvent::VpnPortalStarted { portal } => {
+                <i32>::sse_encode(13, serializer);
+                <String>::sse_encode(portal, serializer);
+            }
+            crate::api::event::KVEvent::VpnPortalClientConnected {
+                portal,
+                client_addr,
+            } => {
+                <i32>::sse_encode(14, serializer);
+                <String>::sse_encode(portal, serializer);
+                <String>::sse_encode(client_addr, serializer);
+            }
+            crate::api::event::KVEvent::VpnPortalClientDisconnected {
+                portal,
+                client_addr,
+            } => {
+                <i32>::sse_encode(15, serializer);
+                <String>::sse_encode(portal, serializer);
+                <String>::sse_encode(client_addr, serializer);
+            }
+            crate::api::event::KVEvent::DhcpIpv4Changed { old_cidr, new_cidr } => {
+                <i32>::sse_encode(16, serializer);
+                <Option<String>>::sse_encode(old_cidr, serializer);
+                <Option<String>>::sse_encode(new_cidr, serializer);
+            }
+            crate::api::event::KVEvent::DhcpIpv4Conflicted { cidr } => {
+                <i32>::sse_encode(17, serializer);
+                <Option<String>>::sse_encode(cidr, serializer);
+            }
+            crate::api::event::KVEvent::PortForwardAdded {
+                bind_addr,
+                dst_addr,
+                proto,
+            } => {
+                <i32>::sse_encode(18, serializer);
+                <String>::sse_encode(bind_addr, serializer);
+                <String>::sse_encode(dst_addr, serializer);
+                <String>::sse_encode(proto, serializer);
+            }
+            crate::api::event::KVEvent::Lagged { missed } => {
+                <i32>::sse_encode(19, serializer);
+                <u64>::sse_encode(missed, serializer);
+            }
+            crate::api::event::KVEvent::Closed => {
+                <i32>::sse_encode(20, serializer);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseEncode for crate::api::event::KVEventKind {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::api::event::KVEventKind::TunDeviceReady => 0,
+                crate::api::event::KVEventKind::TunDeviceError => 1,
+                crate::api::event::KVEventKind::PeerAdded => 2,
+                crate::api::event::KVEventKind::PeerRemoved => 3,
+                crate::api::event::KVEventKind::PeerConnAdded => 4,
+                crate::api::event::KVEventKind::PeerConnRemoved => 5,
+                crate::api::event::KVEventKind::ListenerAdded => 6,
+                crate::api::event::KVEventKind::ListenerAddFailed => 7,
+                crate::api::event::KVEventKind::ListenerAcceptFailed => 8,
+                crate::api::event::KVEventKind::ConnectionAccepted => 9,
+                crate::api::event::KVEventKind::ConnectionError => 10,
+                crate::api::event::KVEventKind::Connecting => 11,
+                crate::api::event::KVEventKind::ConnectError => 12,
+                crate::api::event::KVEventKind::VpnPortalStarted => 13,
+                crate::api::event::KVEventKind::VpnPortalClientConnected => 14,
+                crate::api::event::KVEventKind::VpnPortalClientDisconnected => 15,
+                crate::api::event::KVEventKind::DhcpIpv4Changed => 16,
+                crate::api::event::KVEventKind::DhcpIpv4Conflicted => 17,
+                crate::api::event::KVEventKind::PortForwardAdded => 18,
+                crate::api::event::KVEventKind::Lagged => 19,
+                crate::api::event::KVEventKind::Closed => 20,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
     }
 }
 
-impl SseEncode for crate::api::simple::Forward {
+impl SseEncode for crate::api::event::KVEventRecord {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <String>::sse_encode(self.bind_addr, serializer);
-        <String>::sse_encode(self.dst_addr, serializer);
-        <String>::sse_encode(self.proto, serializer);
+        <String>::sse_encode(self.instance_id, serializer);
+        <i64>::sse_encode(self.timestamp_ms, serializer);
+        <crate::api::event::KVEvent>::sse_encode(self.event, serializer);
     }
 }
 
-impl SseEncode for i32 {
+impl SseEncode for crate::api::simple::KVExitFlow {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        serializer.cursor.write_i32::<NativeEndian>(self).unwrap();
+        <String>::sse_encode(self.protocol, serializer);
+        <String>::sse_encode(self.src, serializer);
+        <String>::sse_encode(self.dst, serializer);
+        <String>::sse_encode(self.exit_node, serializer);
+        <u64>::sse_encode(self.idle_ms, serializer);
+    }
+}
+
+impl SseEncode for crate::api::simple::KVExitFlowInfo {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <Vec<crate::api::simple::KVExitNodeStatus>>::sse_encode(self.exit_nodes, serializer);
+        <Vec<crate::api::simple::KVExitFlow>>::sse_encode(self.flows, serializer);
+        <u64>::sse_encode(self.total_flows, serializer);
+    }
+}
+
+impl SseEncode for crate::api::simple::KVExitNodeStatus {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.exit_node, serializer);
+        <bool>::sse_encode(self.reachable, serializer);
+        <bool>::sse_encode(self.healthy, serializer);
+        <Option<f64>>::sse_encode(self.rtt_ms, serializer);
+        <u32>::sse_encode(self.consecutive_failures, serializer);
+    }
+}
+
+impl SseEncode for crate::api::simple::KVInstanceInfo {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.instance_id, serializer);
+        <String>::sse_encode(self.network_name, serializer);
+        <bool>::sse_encode(self.running, serializer);
+        <Option<String>>::sse_encode(self.error_msg, serializer);
     }
 }
 
@@ -4335,6 +6860,31 @@ impl SseEncode for crate::api::simple::KVNodeInfo {
     }
 }
 
+impl SseEncode for crate::api::event::KVPeerConn {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.conn_id, serializer);
+        <u32>::sse_encode(self.my_peer_id, serializer);
+        <u32>::sse_encode(self.peer_id, serializer);
+        <String>::sse_encode(self.tunnel_type, serializer);
+        <String>::sse_encode(self.local_addr, serializer);
+        <String>::sse_encode(self.remote_addr, serializer);
+        <bool>::sse_encode(self.is_client, serializer);
+        <String>::sse_encode(self.network_name, serializer);
+    }
+}
+
+impl SseEncode for crate::api::split_tunnel::KVSplitTunnelStatus {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.active, serializer);
+        <String>::sse_encode(self.instance_id, serializer);
+        <String>::sse_encode(self.dev_name, serializer);
+        <crate::api::split_tunnel::SplitTunnelMode>::sse_encode(self.mode, serializer);
+        <u32>::sse_encode(self.app_count, serializer);
+    }
+}
+
 impl SseEncode for Vec<FilterRule> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -4375,6 +6925,46 @@ impl SseEncode for Vec<crate::api::simple::Forward> {
     }
 }
 
+impl SseEncode for Vec<crate::api::event::KVEventKind> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::api::event::KVEventKind>::sse_encode(item, serializer);
+        }
+    }
+}
+
+impl SseEncode for Vec<crate::api::simple::KVExitFlow> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::api::simple::KVExitFlow>::sse_encode(item, serializer);
+        }
+    }
+}
+
+impl SseEncode for Vec<crate::api::simple::KVExitNodeStatus> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::api::simple::KVExitNodeStatus>::sse_encode(item, serializer);
+        }
+    }
+}
+
+impl SseEncode for Vec<crate::api::simple::KVInstanceInfo> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::api::simple::KVInstanceInfo>::sse_encode(item, serializer);
+        }
+    }
+}
+
 impl SseEncode for Vec<crate::api::simple::KVNodeConnectionStats> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -4435,6 +7025,36 @@ impl SseEncode for Vec<u8> {
     }
 }
 
+impl SseEncode for Vec<crate::api::profile::ProfileChange> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::api::profile::ProfileChange>::sse_encode(item, serializer);
+        }
+    }
+}
+
+impl SseEncode for Vec<crate::api::profile::ProfileFieldError> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::api::profile::ProfileFieldError>::sse_encode(item, serializer);
+        }
+    }
+}
+
+impl SseEncode for Vec<crate::api::profile::ProfileProxyNetwork> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::api::profile::ProfileProxyNetwork>::sse_encode(item, serializer);
+        }
+    }
+}
+
 impl SseEncode for Vec<(String, u32)> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -4445,6 +7065,50 @@ impl SseEncode for Vec<(String, u32)> {
     }
 }
 
+impl SseEncode for Vec<crate::api::split_tunnel::SplitTunnelRule> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::api::split_tunnel::SplitTunnelRule>::sse_encode(item, serializer);
+        }
+    }
+}
+
+impl SseEncode for crate::api::profile::NetworkProfile {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <Option<String>>::sse_encode(self.instance_id, serializer);
+        <String>::sse_encode(self.instance_name, serializer);
+        <Option<String>>::sse_encode(self.hostname, serializer);
+        <String>::sse_encode(self.network_name, serializer);
+        <String>::sse_encode(self.network_secret, serializer);
+        <bool>::sse_encode(self.dhcp, serializer);
+        <Option<String>>::sse_encode(self.ipv4, serializer);
+        <Option<String>>::sse_encode(self.ipv6, serializer);
+        <Vec<String>>::sse_encode(self.peers, serializer);
+        <Vec<String>>::sse_encode(self.listeners, serializer);
+        <Vec<String>>::sse_encode(self.mapped_listeners, serializer);
+        <Vec<crate::api::profile::ProfileProxyNetwork>>::sse_encode(
+            self.proxy_networks,
+            serializer,
+        );
+        <Vec<String>>::sse_encode(self.exit_nodes, serializer);
+        <Option<Vec<String>>>::sse_encode(self.routes, serializer);
+        <Option<String>>::sse_encode(self.socks5_portal, serializer);
+        <Vec<crate::api::simple::Forward>>::sse_encode(self.port_forwards, serializer);
+        <Option<crate::api::profile::ProfileVpnPortal>>::sse_encode(self.vpn_portal, serializer);
+        <Option<String>>::sse_encode(self.rpc_portal, serializer);
+        <Option<Vec<String>>>::sse_encode(self.rpc_portal_whitelist, serializer);
+        <Option<Vec<String>>>::sse_encode(self.stun_servers, serializer);
+        <Option<Vec<String>>>::sse_encode(self.stun_servers_v6, serializer);
+        <Vec<String>>::sse_encode(self.tcp_whitelist, serializer);
+        <Vec<String>>::sse_encode(self.udp_whitelist, serializer);
+        <Option<String>>::sse_encode(self.acl_json, serializer);
+        <crate::api::simple::FlagsC>::sse_encode(self.flags, serializer);
+    }
+}
+
 impl SseEncode for crate::api::simple::NodeHopStats {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -4465,6 +7129,26 @@ impl SseEncode for Option<String> {
     }
 }
 
+impl SseEncode for Option<f64> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <f64>::sse_encode(value, serializer);
+        }
+    }
+}
+
+impl SseEncode for Option<crate::api::profile::ProfileVpnPortal> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <crate::api::profile::ProfileVpnPortal>::sse_encode(value, serializer);
+        }
+    }
+}
+
 impl SseEncode for Option<crate::api::astral_wfp::Protocol> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -4505,6 +7189,49 @@ impl SseEncode for Option<u32> {
     }
 }
 
+impl SseEncode for Option<Vec<String>> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <Vec<String>>::sse_encode(value, serializer);
+        }
+    }
+}
+
+impl SseEncode for crate::api::profile::ProfileChange {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.field, serializer);
+        <Option<String>>::sse_encode(self.old_value, serializer);
+        <Option<String>>::sse_encode(self.new_value, serializer);
+    }
+}
+
+impl SseEncode for crate::api::profile::ProfileFieldError {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.field, serializer);
+        <String>::sse_encode(self.message, serializer);
+    }
+}
+
+impl SseEncode for crate::api::profile::ProfileProxyNetwork {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.cidr, serializer);
+        <Option<String>>::sse_encode(self.mapped_cidr, serializer);
+    }
+}
+
+impl SseEncode for crate::api::profile::ProfileVpnPortal {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.client_cidr, serializer);
+        <String>::sse_encode(self.wireguard_listen, serializer);
+    }
+}
+
 impl SseEncode for crate::api::astral_wfp::Protocol {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -4537,6 +7264,30 @@ impl SseEncode for (u16, u16) {
     }
 }
 
+impl SseEncode for crate::api::split_tunnel::SplitTunnelMode {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::api::split_tunnel::SplitTunnelMode::Include => 0,
+                crate::api::split_tunnel::SplitTunnelMode::Exclude => 1,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::api::split_tunnel::SplitTunnelRule {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.app_path, serializer);
+        <bool>::sse_encode(self.enabled, serializer);
+    }
+}
+
 impl SseEncode for u16 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -4601,20 +7352,6 @@ mod io {
 
     flutter_rust_bridge::frb_generated_boilerplate_io!();
 
-    #[unsafe(no_mangle)]
-    pub extern "C" fn frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-        ptr: *const std::ffi::c_void,
-    ) {
-        MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<EventBusSubscriber>>::increment_strong_count(ptr as _);
-    }
-
-    #[unsafe(no_mangle)]
-    pub extern "C" fn frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-        ptr: *const std::ffi::c_void,
-    ) {
-        MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<EventBusSubscriber>>::decrement_strong_count(ptr as _);
-    }
-
     #[unsafe(no_mangle)]
     pub extern "C" fn frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
         ptr: *const std::ffi::c_void,
@@ -4672,34 +7409,90 @@ mod io {
     }
 
     #[unsafe(no_mangle)]
-    pub extern "C" fn frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+    pub extern "C" fn frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+        ptr: *const std::ffi::c_void,
+    ) {
+        MoiArc::<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<KVConfigApplyReport, String>>,
+            >,
+        >::increment_strong_count(ptr as _);
+    }
+
+    #[unsafe(no_mangle)]
+    pub extern "C" fn frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+        ptr: *const std::ffi::c_void,
+    ) {
+        MoiArc::<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<KVConfigApplyReport, String>>,
+            >,
+        >::decrement_strong_count(ptr as _);
+    }
+
+    #[unsafe(no_mangle)]
+    pub extern "C" fn frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+        ptr: *const std::ffi::c_void,
+    ) {
+        MoiArc::<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<KVShutdownReport, String>>,
+            >,
+        >::increment_strong_count(ptr as _);
+    }
+
+    #[unsafe(no_mangle)]
+    pub extern "C" fn frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+        ptr: *const std::ffi::c_void,
+    ) {
+        MoiArc::<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<KVShutdownReport, String>>,
+            >,
+        >::decrement_strong_count(ptr as _);
+    }
+
+    #[unsafe(no_mangle)]
+    pub extern "C" fn frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
         ptr: *const std::ffi::c_void,
     ) {
-        MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner< JoinHandle < () >>>::increment_strong_count(ptr as _);
+        MoiArc::<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<String, String>>,
+            >,
+        >::increment_strong_count(ptr as _);
     }
 
     #[unsafe(no_mangle)]
-    pub extern "C" fn frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+    pub extern "C" fn frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
         ptr: *const std::ffi::c_void,
     ) {
-        MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner< JoinHandle < () >>>::decrement_strong_count(ptr as _);
+        MoiArc::<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<String, String>>,
+            >,
+        >::decrement_strong_count(ptr as _);
     }
 
     #[unsafe(no_mangle)]
-    pub extern "C" fn frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+    pub extern "C" fn frbgen_astral_rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
         ptr: *const std::ffi::c_void,
     ) {
         MoiArc::<
-            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Result<(), String>>>,
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Vec<KVShutdownReport>>,
+            >,
         >::increment_strong_count(ptr as _);
     }
 
     #[unsafe(no_mangle)]
-    pub extern "C" fn frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+    pub extern "C" fn frbgen_astral_rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
         ptr: *const std::ffi::c_void,
     ) {
         MoiArc::<
-            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Result<(), String>>>,
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Vec<KVShutdownReport>>,
+            >,
         >::decrement_strong_count(ptr as _);
     }
 
@@ -4758,20 +7551,6 @@ mod web {
 
     flutter_rust_bridge::frb_generated_boilerplate_web!();
 
-    #[wasm_bindgen]
-    pub fn rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-        ptr: *const std::ffi::c_void,
-    ) {
-        MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<EventBusSubscriber>>::increment_strong_count(ptr as _);
-    }
-
-    #[wasm_bindgen]
-    pub fn rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerEventBusSubscriber(
-        ptr: *const std::ffi::c_void,
-    ) {
-        MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner<EventBusSubscriber>>::decrement_strong_count(ptr as _);
-    }
-
     #[wasm_bindgen]
     pub fn rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerFilterRule(
         ptr: *const std::ffi::c_void,
@@ -4829,34 +7608,90 @@ mod web {
     }
 
     #[wasm_bindgen]
-    pub fn rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+    pub fn rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
         ptr: *const std::ffi::c_void,
     ) {
-        MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner< JoinHandle < () >>>::increment_strong_count(ptr as _);
+        MoiArc::<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<KVConfigApplyReport, String>>,
+            >,
+        >::increment_strong_count(ptr as _);
+    }
+
+    #[wasm_bindgen]
+    pub fn rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVConfigApplyReportString(
+        ptr: *const std::ffi::c_void,
+    ) {
+        MoiArc::<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<KVConfigApplyReport, String>>,
+            >,
+        >::decrement_strong_count(ptr as _);
+    }
+
+    #[wasm_bindgen]
+    pub fn rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
+        ptr: *const std::ffi::c_void,
+    ) {
+        MoiArc::<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<KVShutdownReport, String>>,
+            >,
+        >::increment_strong_count(ptr as _);
     }
 
     #[wasm_bindgen]
-    pub fn rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandle(
+    pub fn rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultKVShutdownReportString(
         ptr: *const std::ffi::c_void,
     ) {
-        MoiArc::<flutter_rust_bridge::for_generated::RustAutoOpaqueInner< JoinHandle < () >>>::decrement_strong_count(ptr as _);
+        MoiArc::<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<KVShutdownReport, String>>,
+            >,
+        >::decrement_strong_count(ptr as _);
+    }
+
+    #[wasm_bindgen]
+    pub fn rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+        ptr: *const std::ffi::c_void,
+    ) {
+        MoiArc::<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<String, String>>,
+            >,
+        >::increment_strong_count(ptr as _);
+    }
+
+    #[wasm_bindgen]
+    pub fn rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultStringString(
+        ptr: *const std::ffi::c_void,
+    ) {
+        MoiArc::<
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Result<String, String>>,
+            >,
+        >::decrement_strong_count(ptr as _);
     }
 
     #[wasm_bindgen]
-    pub fn rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+    pub fn rust_arc_increment_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
         ptr: *const std::ffi::c_void,
     ) {
         MoiArc::<
-            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Result<(), String>>>,
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Vec<KVShutdownReport>>,
+            >,
         >::increment_strong_count(ptr as _);
     }
 
     #[wasm_bindgen]
-    pub fn rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleResultString(
+    pub fn rust_arc_decrement_strong_count_RustOpaque_flutter_rust_bridgefor_generatedRustAutoOpaqueInnerJoinHandleVecKVShutdownReport(
         ptr: *const std::ffi::c_void,
     ) {
         MoiArc::<
-            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<JoinHandle<Result<(), String>>>,
+            flutter_rust_bridge::for_generated::RustAutoOpaqueInner<
+                JoinHandle<Vec<KVShutdownReport>>,
+            >,
         >::decrement_strong_count(ptr as _);
     }
 
@@ -0,0 +1,127 @@
+//! cgroup v2 辅助函数
+//!
+//! Linux 上无法按可执行文件路径匹配流量，因此把目标程序的进程移入 Astral 创建的
+//! cgroup，再由 nftables 的 `socket cgroupv2` 表达式匹配该 cgroup。
+//! 只能覆盖移入时已经在运行的进程，程序重启后需要重新下发规则。
+//! 删除 cgroup 时进程会被移回移入前所在的 cgroup。
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+/// cgroup v2 挂载点
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Astral 创建的 cgroup 都位于该目录下
+const ASTRAL_CGROUP: &str = "astral";
+
+lazy_static! {
+    /// 被移入 Astral cgroup 的进程及其原来所在的 cgroup（相对 cgroup v2 挂载点）
+    static ref ORIGINAL_CGROUPS: Mutex<HashMap<u32, String>> = Mutex::new(HashMap::new());
+}
+
+/// 程序对应的 cgroup 路径（相对 cgroup v2 挂载点）
+///
+/// 路径由程序路径的 FNV-1a 哈希生成，同一程序在不同进程中得到相同的结果
+pub(crate) fn app_cgroup(kind: &str, app_path: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in app_path.as_bytes() {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{}/{}-{:016x}", ASTRAL_CGROUP, kind, hash)
+}
+
+/// cgroup 在层级中的深度，即 nftables `socket cgroupv2 level` 的取值
+pub(crate) fn cgroup_level(cgroup: &str) -> usize {
+    cgroup.split('/').filter(|s| !s.is_empty()).count()
+}
+
+fn cgroup_dir(cgroup: &str) -> PathBuf {
+    Path::new(CGROUP_ROOT).join(cgroup)
+}
+
+/// 正在运行且可执行文件为 `app_path` 的进程
+fn find_app_pids(app_path: &str) -> io::Result<Vec<u32>> {
+    let target = std::fs::canonicalize(app_path).unwrap_or_else(|_| PathBuf::from(app_path));
+    let mut pids = Vec::new();
+    for entry in std::fs::read_dir("/proc")? {
+        let Ok(entry) = entry else { continue };
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|s| s.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        // 无权限读取的进程直接跳过
+        if let Ok(exe) = std::fs::read_link(entry.path().join("exe")) {
+            if exe == target {
+                pids.push(pid);
+            }
+        }
+    }
+    Ok(pids)
+}
+
+/// 进程当前所在的 cgroup v2 路径（相对挂载点，不含开头的 `/`）
+fn current_cgroup(pid: u32) -> Option<String> {
+    let content = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    content
+        .lines()
+        .find_map(|l| l.strip_prefix("0::"))
+        .map(|path| path.trim_start_matches('/').to_string())
+}
+
+fn is_astral_cgroup(cgroup: &str) -> bool {
+    cgroup == ASTRAL_CGROUP || cgroup.starts_with(&format!("{}/", ASTRAL_CGROUP))
+}
+
+/// 创建 cgroup 并把 `app_path` 的所有进程移入其中，返回移入的进程数
+pub(crate) fn attach_app(cgroup: &str, app_path: &str) -> io::Result<usize> {
+    let dir = cgroup_dir(cgroup);
+    std::fs::create_dir_all(&dir)?;
+    let procs = dir.join("cgroup.procs");
+    let mut originals = ORIGINAL_CGROUPS.lock().unwrap();
+    // 已退出进程的记录不再需要，也避免 pid 复用后移错位置
+    originals.retain(|pid, _| Path::new(&format!("/proc/{}", pid)).exists());
+    let mut moved = 0;
+    for pid in find_app_pids(app_path)? {
+        let original = current_cgroup(pid);
+        // 进程可能在此期间退出，单个失败不影响其他进程
+        if std::fs::write(&procs, pid.to_string()).is_ok() {
+            moved += 1;
+            // 在 Astral cgroup 之间移动时保留最初的记录
+            if let Some(original) = original.filter(|c| !is_astral_cgroup(c)) {
+                originals.insert(pid, original);
+            }
+        }
+    }
+    Ok(moved)
+}
+
+/// 把 cgroup 中的进程移回原来的 cgroup 并删除该 cgroup
+///
+/// 原来的 cgroup 已不存在或无法移回时，进程移入根 cgroup
+pub(crate) fn remove(cgroup: &str) -> io::Result<()> {
+    let dir = cgroup_dir(cgroup);
+    let procs = match std::fs::read_to_string(dir.join("cgroup.procs")) {
+        Ok(procs) => procs,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    let root_procs = Path::new(CGROUP_ROOT).join("cgroup.procs");
+    let mut originals = ORIGINAL_CGROUPS.lock().unwrap();
+    for pid in procs.lines().filter_map(|l| l.trim().parse::<u32>().ok()) {
+        let restored = originals.remove(&pid).is_some_and(|original| {
+            std::fs::write(cgroup_dir(&original).join("cgroup.procs"), pid.to_string()).is_ok()
+        });
+        if !restored {
+            let _ = std::fs::write(&root_procs, pid.to_string());
+        }
+    }
+    std::fs::remove_dir(&dir)
+}
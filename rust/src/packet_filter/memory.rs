@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use super::FilterBackend;
+use crate::api::astral_wfp::FilterRule;
+
+/// 只在内存中记录规则的后端
+///
+/// 用于没有原生过滤实现的平台，以及在不修改系统状态的情况下验证 `WfpController` 的行为
+#[derive(Debug, Default)]
+pub(crate) struct MemoryBackend {
+    next_id: u64,
+    // 过滤器 ID -> 规则名称
+    filters: BTreeMap<u64, String>,
+}
+
+impl MemoryBackend {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl FilterBackend for MemoryBackend {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn initialize(&mut self) -> anyhow::Result<()> {
+        self.filters.clear();
+        Ok(())
+    }
+
+    fn add_rule(&mut self, rule: &FilterRule) -> anyhow::Result<Vec<u64>> {
+        self.next_id += 1;
+        self.filters.insert(self.next_id, rule.name.clone());
+        Ok(vec![self.next_id])
+    }
+
+    fn delete_filter(&mut self, filter_id: u64) -> anyhow::Result<()> {
+        match self.filters.remove(&filter_id) {
+            Some(_) => Ok(()),
+            None => Err(anyhow::anyhow!("未找到过滤器 {}", filter_id)),
+        }
+    }
+
+    fn cleanup(&mut self) -> anyhow::Result<()> {
+        self.filters.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::astral_wfp::{Direction, FilterAction, Protocol, WfpController};
+
+    fn rule(name: &str, remote: &str) -> FilterRule {
+        FilterRule::new_with_params(
+            name,
+            None,
+            None,
+            Some(remote.to_string()),
+            None,
+            Some(443),
+            None,
+            None,
+            Some(Protocol::Tcp),
+            Direction::Outbound,
+            FilterAction::Block,
+            None,
+            None,
+        )
+    }
+
+    fn controller() -> WfpController {
+        let mut controller = WfpController::with_backend(Box::new(MemoryBackend::new()));
+        controller.initialize().unwrap();
+        controller
+    }
+
+    #[test]
+    fn test_add_and_delete_rules() {
+        let mut controller = controller();
+        assert_eq!("memory", controller.backend_name());
+
+        // 无效的规则被跳过，其余规则照常下发
+        let ids = controller
+            .add_filters(&[
+                rule("a", "1.1.1.1"),
+                rule("bad", "not an ip"),
+                rule("b", "10.0.0.0/8"),
+            ])
+            .unwrap();
+        assert_eq!(vec![1, 2], ids);
+        assert_eq!(ids, controller.filter_ids);
+        let rules = controller.list_rules();
+        assert_eq!(2, rules.len());
+        assert_eq!(
+            ("a", vec![1]),
+            (rules[0].name.as_str(), rules[0].filter_ids.clone())
+        );
+        assert_eq!(
+            ("b", vec![2]),
+            (rules[1].name.as_str(), rules[1].filter_ids.clone())
+        );
+
+        assert!(controller.add_filters(&[rule("bad", "x")]).is_err());
+
+        assert_eq!(1, controller.delete_rule("a").unwrap());
+        assert!(controller.delete_rule("a").is_err());
+        assert_eq!(vec![2], controller.filter_ids);
+
+        // 未知的过滤器不会被删除
+        assert!(controller.delete_filters(&[42]).is_err());
+        assert_eq!(1, controller.delete_filters(&[2, 42]).unwrap());
+        assert!(controller.list_rules().is_empty());
+        assert!(controller.filter_ids.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load_rules() {
+        let path = std::env::temp_dir().join(format!("astral-rules-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+
+        let mut controller = controller();
+        controller
+            .add_filters(&[rule("a", "1.1.1.1"), rule("b", "2.2.2.2")])
+            .unwrap();
+        controller.save_rules(path).unwrap();
+
+        controller.cleanup().unwrap();
+        assert!(controller.list_rules().is_empty());
+        assert!(controller.filter_ids.is_empty());
+
+        let ids = controller.load_rules(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(2, ids.len());
+        let rules = controller.list_rules();
+        assert_eq!(
+            vec!["a", "b"],
+            rules.iter().map(|r| r.name.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(Some("2.2.2.2".to_string()), rules[1].remote);
+        assert_eq!(Some(443), rules[1].remote_port);
+    }
+}
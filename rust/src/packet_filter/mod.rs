@@ -0,0 +1,42 @@
+//! 与平台无关的数据包过滤后端
+//!
+//! `WfpController` 只维护规则模型（`FilterRule`），具体的下发由 `FilterBackend` 完成：
+//! - Windows: Windows Filtering Platform（实现位于 `api::astral_wfp`）
+//! - Linux: nftables
+//! - 其他平台及测试: 仅在内存中记录规则
+
+use crate::api::astral_wfp::FilterRule;
+
+#[cfg(target_os = "linux")]
+pub(crate) mod cgroup;
+// Windows 和 Linux 使用原生后端，内存后端只在其他平台及测试中使用
+#[cfg(any(test, not(any(target_os = "windows", target_os = "linux"))))]
+mod memory;
+#[cfg(target_os = "linux")]
+mod nftables;
+
+#[cfg(any(test, not(any(target_os = "windows", target_os = "linux"))))]
+pub(crate) use memory::MemoryBackend;
+#[cfg(target_os = "linux")]
+pub(crate) use nftables::NftablesBackend;
+
+/// 过滤规则的下发后端
+pub(crate) trait FilterBackend: Send + Sync {
+    /// 后端名称，用于日志和界面展示
+    fn name(&self) -> &'static str;
+
+    /// 准备后端（打开引擎、创建表和链等）
+    fn initialize(&mut self) -> anyhow::Result<()>;
+
+    /// 下发一条已通过验证的规则，返回对应的过滤器 ID
+    ///
+    /// 一条规则可能展开为多个过滤器（例如双向规则、多个 WFP 层），
+    /// 任意一个过滤器下发失败时应撤销已下发的部分并返回错误
+    fn add_rule(&mut self, rule: &FilterRule) -> anyhow::Result<Vec<u64>>;
+
+    /// 删除单个过滤器
+    fn delete_filter(&mut self, filter_id: u64) -> anyhow::Result<()>;
+
+    /// 删除所有过滤器并释放后端占用的资源
+    fn cleanup(&mut self) -> anyhow::Result<()>;
+}
@@ -0,0 +1,346 @@
+//! 基于 nftables 的过滤后端
+//!
+//! 所有规则写入 Astral 独占的 `inet astral_wfp` 表，入站规则位于挂在 input 钩子上的
+//! `input` 链，出站规则位于 `output` 链。链内按规则优先级从高到低排列，与 WFP 的
+//! 权重语义一致。nftables 中不同表的 accept 互不影响，因此放行规则只能覆盖本表中
+//! 优先级更低的阻止规则。
+
+use std::net::IpAddr;
+
+use super::{cgroup, FilterBackend};
+use crate::api::astral_wfp::{Direction, FilterAction, FilterRule, IpNetwork, Protocol};
+use crate::api::firewall::run_nft;
+
+const TABLE_FAMILY: &str = "inet";
+const TABLE_NAME: &str = "astral_wfp";
+
+/// nftables 注释最长 128 字节
+const MAX_COMMENT_LEN: usize = 128;
+
+/// 已下发的单条 nftables 规则，过滤器 ID 即规则句柄（在同一张表内唯一）
+#[derive(Debug)]
+struct NftFilter {
+    handle: u64,
+    chain: &'static str,
+    priority: u32,
+    cgroup: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct NftablesBackend {
+    // 各链中的规则按链内顺序排列
+    filters: Vec<NftFilter>,
+}
+
+impl NftablesBackend {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    fn delete_table() -> Result<(), std::io::Error> {
+        match run_nft(&["delete", "table", TABLE_FAMILY, TABLE_NAME]) {
+            Ok(_) => Ok(()),
+            // 表不存在，其他错误（权限不足、nft 不可用等）照常返回
+            Err(e)
+                if e.kind() == std::io::ErrorKind::Other
+                    && e.to_string().contains("No such file or directory") =>
+            {
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 添加一条规则到指定链，返回规则句柄
+    fn add_to_chain(
+        &mut self,
+        chain: &'static str,
+        priority: u32,
+        expr: &[String],
+        cgroup: Option<String>,
+    ) -> anyhow::Result<u64> {
+        // 插入到第一条优先级更低的规则之前，没有则追加到链尾
+        let position = self
+            .filters
+            .iter()
+            .position(|f| f.chain == chain && f.priority < priority);
+
+        let mut args: Vec<String> = vec!["-e".into(), "-a".into()];
+        match position {
+            Some(idx) => args.extend([
+                "insert".into(),
+                "rule".into(),
+                TABLE_FAMILY.into(),
+                TABLE_NAME.into(),
+                chain.into(),
+                "position".into(),
+                self.filters[idx].handle.to_string(),
+            ]),
+            None => args.extend([
+                "add".into(),
+                "rule".into(),
+                TABLE_FAMILY.into(),
+                TABLE_NAME.into(),
+                chain.into(),
+            ]),
+        }
+        args.extend(expr.iter().cloned());
+
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let output = run_nft(&args)?;
+        let handle = parse_echoed_handle(&output)
+            .ok_or_else(|| anyhow::anyhow!("无法解析 nft 返回的规则句柄: {}", output.trim()))?;
+
+        let filter = NftFilter {
+            handle,
+            chain,
+            priority,
+            cgroup,
+        };
+        match position {
+            Some(idx) => self.filters.insert(idx, filter),
+            None => self.filters.push(filter),
+        }
+        Ok(handle)
+    }
+
+    fn release_cgroup(&self, cgroup_path: &str) {
+        if self
+            .filters
+            .iter()
+            .any(|f| f.cgroup.as_deref() == Some(cgroup_path))
+        {
+            return;
+        }
+        if let Err(e) = cgroup::remove(cgroup_path) {
+            println!("⚠️ 删除 cgroup {} 失败: {}", cgroup_path, e);
+        }
+    }
+}
+
+impl FilterBackend for NftablesBackend {
+    fn name(&self) -> &'static str {
+        "nftables"
+    }
+
+    fn initialize(&mut self) -> anyhow::Result<()> {
+        // 清理上次异常退出残留的表
+        Self::delete_table()?;
+        self.filters.clear();
+
+        run_nft(&["add", "table", TABLE_FAMILY, TABLE_NAME])?;
+        for (chain, hook) in [("input", "input"), ("output", "output")] {
+            let spec = format!("{{ type filter hook {} priority 0; policy accept; }}", hook);
+            run_nft(&["add", "chain", TABLE_FAMILY, TABLE_NAME, chain, &spec])?;
+        }
+        println!("✓ nftables 表 {} {} 已创建", TABLE_FAMILY, TABLE_NAME);
+        Ok(())
+    }
+
+    fn add_rule(&mut self, rule: &FilterRule) -> anyhow::Result<Vec<u64>> {
+        let cgroup_path = match &rule.app_path {
+            Some(app_path) => {
+                let path = cgroup::app_cgroup("app", app_path);
+                let moved = cgroup::attach_app(&path, app_path)
+                    .map_err(|e| anyhow::anyhow!("创建 cgroup {} 失败: {}", path, e))?;
+                println!("🎯 已将 {} 个 {} 进程移入 cgroup {}", moved, app_path, path);
+                Some(path)
+            }
+            None => None,
+        };
+
+        let chains: &[(&'static str, Direction)] = match rule.direction {
+            Direction::Inbound => &[("input", Direction::Inbound)],
+            Direction::Outbound => &[("output", Direction::Outbound)],
+            Direction::Both => &[
+                ("input", Direction::Inbound),
+                ("output", Direction::Outbound),
+            ],
+        };
+
+        let mut handles = Vec::new();
+        for (chain, direction) in chains {
+            let result =
+                build_rule_expr(rule, direction, cgroup_path.as_deref()).and_then(|expr| {
+                    self.add_to_chain(chain, rule.priority, &expr, cgroup_path.clone())
+                });
+            match result {
+                Ok(handle) => handles.push(handle),
+                Err(e) => {
+                    // 撤销本规则已下发的部分
+                    for handle in handles {
+                        let _ = self.delete_filter(handle);
+                    }
+                    if let Some(path) = &cgroup_path {
+                        self.release_cgroup(path);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(handles)
+    }
+
+    fn delete_filter(&mut self, filter_id: u64) -> anyhow::Result<()> {
+        let idx = self
+            .filters
+            .iter()
+            .position(|f| f.handle == filter_id)
+            .ok_or_else(|| anyhow::anyhow!("未找到过滤器 {}", filter_id))?;
+        let handle = filter_id.to_string();
+        run_nft(&[
+            "delete",
+            "rule",
+            TABLE_FAMILY,
+            TABLE_NAME,
+            self.filters[idx].chain,
+            "handle",
+            &handle,
+        ])?;
+        let filter = self.filters.remove(idx);
+        if let Some(path) = &filter.cgroup {
+            self.release_cgroup(path);
+        }
+        Ok(())
+    }
+
+    fn cleanup(&mut self) -> anyhow::Result<()> {
+        Self::delete_table()?;
+        let mut cgroups: Vec<String> = self.filters.drain(..).filter_map(|f| f.cgroup).collect();
+        cgroups.sort();
+        cgroups.dedup();
+        for path in cgroups {
+            self.release_cgroup(&path);
+        }
+        Ok(())
+    }
+}
+
+/// 从 `nft -e -a` 的输出中解析规则句柄，例如 `add rule ... # handle 5`
+fn parse_echoed_handle(output: &str) -> Option<u64> {
+    let idx = output.rfind("# handle ")?;
+    output[idx + "# handle ".len()..]
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// 把地址或网段转换为 nftables 的匹配表达式，返回 (协议族, 地址)
+fn parse_address(addr: &str) -> anyhow::Result<(&'static str, String)> {
+    let addr = addr.trim();
+    if let Ok(ip) = addr.parse::<IpAddr>() {
+        return Ok((ip_family(&ip), ip.to_string()));
+    }
+    let network = IpNetwork::from_cidr(addr).map_err(|e| anyhow::anyhow!("{}: {}", addr, e))?;
+    // nftables 不接受主机位非零的网段
+    let ip = match network.ip {
+        IpAddr::V6(v6) => {
+            let mask = if network.prefix_len == 0 {
+                0u128
+            } else {
+                u128::MAX << (128 - network.prefix_len as u32)
+            };
+            IpAddr::V6((u128::from(v6) & mask).into())
+        }
+        ip => ip,
+    };
+    Ok((ip_family(&ip), format!("{}/{}", ip, network.prefix_len)))
+}
+
+fn ip_family(ip: &IpAddr) -> &'static str {
+    match ip {
+        IpAddr::V4(_) => "ip",
+        IpAddr::V6(_) => "ip6",
+    }
+}
+
+fn port_expr(port: Option<u16>, range: Option<(u16, u16)>) -> Option<String> {
+    match (port, range) {
+        (Some(port), _) => Some(port.to_string()),
+        (None, Some((start, end))) => Some(format!("{}-{}", start, end)),
+        (None, None) => None,
+    }
+}
+
+/// 生成一条规则在指定方向上的 nftables 表达式
+///
+/// 出站时本地地址/端口是源，远程是目标；入站时相反
+fn build_rule_expr(
+    rule: &FilterRule,
+    direction: &Direction,
+    cgroup_path: Option<&str>,
+) -> anyhow::Result<Vec<String>> {
+    let mut expr = Vec::new();
+
+    if let Some(path) = cgroup_path {
+        expr.extend([
+            "socket".to_string(),
+            "cgroupv2".to_string(),
+            "level".to_string(),
+            cgroup::cgroup_level(path).to_string(),
+            format!("\"{}\"", path),
+        ]);
+    }
+
+    let inbound = matches!(direction, Direction::Inbound);
+    let (local_addr_key, remote_addr_key) = if inbound {
+        ("daddr", "saddr")
+    } else {
+        ("saddr", "daddr")
+    };
+    let (local_port_key, remote_port_key) = if inbound {
+        ("dport", "sport")
+    } else {
+        ("sport", "dport")
+    };
+
+    let mut family = None;
+    for (addr, key) in [
+        (&rule.local, local_addr_key),
+        (&rule.remote, remote_addr_key),
+    ] {
+        let Some(addr) = addr else { continue };
+        let (addr_family, addr) = parse_address(addr)?;
+        if family.is_some_and(|f| f != addr_family) {
+            return Err(anyhow::anyhow!(
+                "规则 {} 的本地和远程地址协议族不一致",
+                rule.name
+            ));
+        }
+        family = Some(addr_family);
+        expr.extend([addr_family.to_string(), key.to_string(), addr]);
+    }
+
+    let local_port = port_expr(rule.local_port, rule.local_port_range);
+    let remote_port = port_expr(rule.remote_port, rule.remote_port_range);
+    let has_ports = local_port.is_some() || remote_port.is_some();
+
+    match &rule.protocol {
+        Some(Protocol::Tcp) => expr.extend(["meta".into(), "l4proto".into(), "tcp".into()]),
+        Some(Protocol::Udp) => expr.extend(["meta".into(), "l4proto".into(), "udp".into()]),
+        // 端口条件需要传输层协议，未指定时同时匹配 TCP 和 UDP
+        None if has_ports => expr.extend(["meta".into(), "l4proto".into(), "{ tcp, udp }".into()]),
+        None => {}
+    }
+    for (port, key) in [(local_port, local_port_key), (remote_port, remote_port_key)] {
+        if let Some(port) = port {
+            expr.extend(["th".to_string(), key.to_string(), port]);
+        }
+    }
+
+    expr.push(
+        match rule.action {
+            FilterAction::Allow => "accept",
+            FilterAction::Block => "drop",
+        }
+        .to_string(),
+    );
+
+    let mut comment: String = rule.name.replace('"', "'");
+    while comment.len() > MAX_COMMENT_LEN {
+        comment.pop();
+    }
+    expr.extend(["comment".to_string(), format!("\"{}\"", comment)]);
+    Ok(expr)
+}